@@ -159,7 +159,7 @@ impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
 }
 
 type PendingBlockAndReceipts<N> =
-    (SealedBlock<<N as NodePrimitives>::Block>, Vec<reth_primitives_traits::ReceiptTy<N>>);
+    (RecoveredBlock<<N as NodePrimitives>::Block>, Vec<reth_primitives_traits::ReceiptTy<N>>);
 
 /// This type is responsible for providing the blocks, receipts, and state for
 /// all canonical blocks not on disk yet and keeps track of the block range that
@@ -475,12 +475,12 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.pending_state().map(|block_state| block_state.block_ref().recovered_block().clone())
     }
 
-    /// Returns a tuple with the `SealedBlock` corresponding to the pending
+    /// Returns a tuple with the `RecoveredBlock` corresponding to the pending
     /// state and a vector of its `Receipt`s.
     pub fn pending_block_and_receipts(&self) -> Option<PendingBlockAndReceipts<N>> {
         self.pending_state().map(|block_state| {
             (
-                block_state.block_ref().recovered_block().sealed_block().clone(),
+                block_state.block_ref().recovered_block().clone(),
                 block_state.executed_block_receipts(),
             )
         })
@@ -935,18 +935,21 @@ mod tests {
     use super::*;
     use crate::test_utils::TestBlockBuilder;
     use alloy_eips::eip7685::Requests;
-    use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue};
+    use alloy_primitives::{
+        map::{B256Map, B256Set},
+        Address, BlockNumber, Bytes, StorageKey, StorageValue,
+    };
     use rand::Rng;
     use reth_errors::ProviderResult;
     use reth_ethereum_primitives::{EthPrimitives, Receipt};
     use reth_primitives_traits::{Account, Bytecode};
     use reth_storage_api::{
-        AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
-        StateRootProvider, StorageRootProvider,
+        AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+        StateProofProvider, StateProvider, StateRootProvider, StorageRootProvider,
     };
     use reth_trie::{
-        AccountProof, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-        StorageProof, TrieInput,
+        AccountProof, HashedAccountRange, HashedStorage, HashedStorageRange, MultiProof,
+        MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
     };
 
     fn create_mock_state(
@@ -1101,6 +1104,42 @@ mod tests {
         }
     }
 
+    impl HashedStateRangeProvider for MockStateProvider {
+        fn hashed_account_range(
+            &self,
+            _hashed_state: HashedPostState,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<HashedAccountRange> {
+            Ok(HashedAccountRange::default())
+        }
+
+        fn hashed_storage_range(
+            &self,
+            _hashed_state: HashedPostState,
+            _hashed_address: B256,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<HashedStorageRange> {
+            Ok(HashedStorageRange::default())
+        }
+
+        fn account_preimages(
+            &self,
+            _hashed_addresses: &B256Set,
+        ) -> ProviderResult<B256Map<Address>> {
+            Ok(B256Map::default())
+        }
+
+        fn storage_preimages(
+            &self,
+            _address: Address,
+            _hashed_slots: &B256Set,
+        ) -> ProviderResult<B256Map<B256>> {
+            Ok(B256Map::default())
+        }
+    }
+
     #[test]
     fn test_in_memory_state_impl_state_by_hash() {
         let mut state_by_hash = HashMap::default();
@@ -1290,7 +1329,7 @@ mod tests {
         // Check the pending block and receipts
         assert_eq!(
             state.pending_block_and_receipts().unwrap(),
-            (block2.recovered_block().sealed_block().clone(), vec![])
+            (block2.recovered_block().clone(), vec![])
         );
     }
 
@@ -1,15 +1,19 @@
 use super::ExecutedBlockWithTrieUpdates;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    keccak256,
+    map::{B256Map, B256Set},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
 use reth_errors::ProviderResult;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
 use reth_storage_api::{
-    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
-    StateRootProvider, StorageRootProvider,
+    AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+    StateProofProvider, StateProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedAccountRange, HashedPostState, HashedStorage,
+    HashedStorageRange, MultiProof, MultiProofTargets, StorageMultiProof, TrieInput,
 };
 use revm_database::BundleState;
 use std::sync::OnceLock;
@@ -213,6 +217,81 @@ impl<N: NodePrimitives> HashedPostStateProvider for MemoryOverlayStateProviderRe
     }
 }
 
+impl<N: NodePrimitives> HashedStateRangeProvider for MemoryOverlayStateProviderRef<'_, N> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedAccountRange> {
+        let mut state = self.trie_state().state.clone();
+        state.extend(hashed_state);
+        self.historical.hashed_account_range(state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        let mut state = self.trie_state().state.clone();
+        state.extend(hashed_state);
+        self.historical.hashed_storage_range(state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>> {
+        let mut preimages = B256Map::default();
+        for block in &self.in_memory {
+            for address in block.execution_output.bundle.state().keys() {
+                let hashed_address = keccak256(address);
+                if hashed_addresses.contains(&hashed_address) {
+                    preimages.insert(hashed_address, *address);
+                }
+            }
+        }
+
+        if preimages.len() < hashed_addresses.len() {
+            let missing = hashed_addresses
+                .iter()
+                .filter(|hashed_address| !preimages.contains_key(*hashed_address))
+                .copied()
+                .collect();
+            preimages.extend(self.historical.account_preimages(&missing)?);
+        }
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>> {
+        let mut preimages = B256Map::default();
+        for block in &self.in_memory {
+            let Some(account) = block.execution_output.bundle.account(&address) else { continue };
+            for slot in account.storage.keys() {
+                let slot = B256::from(*slot);
+                let hashed_slot = keccak256(slot);
+                if hashed_slots.contains(&hashed_slot) {
+                    preimages.insert(hashed_slot, slot);
+                }
+            }
+        }
+
+        if preimages.len() < hashed_slots.len() {
+            let missing = hashed_slots
+                .iter()
+                .filter(|hashed_slot| !preimages.contains_key(*hashed_slot))
+                .copied()
+                .collect();
+            preimages.extend(self.historical.storage_preimages(address, &missing)?);
+        }
+        Ok(preimages)
+    }
+}
+
 impl<N: NodePrimitives> StateProvider for MemoryOverlayStateProviderRef<'_, N> {
     fn storage(
         &self,
@@ -4,7 +4,10 @@ use alloy_primitives::B256;
 use clap::Parser;
 use reth_chainspec::EthChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_config::{config::EtlConfig, Config};
+use reth_config::{
+    config::{EraConfig, EtlConfig},
+    Config,
+};
 use reth_consensus::{noop::NoopConsensus, ConsensusError, FullConsensus};
 use reth_db::{init_db, open_db_read_only, DatabaseEnv};
 use reth_db_common::init::init_genesis;
@@ -83,6 +86,11 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
         }
 
+        // Make sure ERA files aren't downloaded to /tmp/, but to whatever datadir is set to
+        if config.stages.era.download_dir.is_none() {
+            config.stages.era.download_dir = Some(EraConfig::from_datadir(data_dir.data_dir()));
+        }
+
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let (db, sfp) = match access {
             AccessRights::RW => (
@@ -126,7 +134,8 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             self.chain.clone(),
             static_file_provider,
         )
-        .with_prune_modes(prune_modes.clone());
+        .with_prune_modes(prune_modes.clone())
+        .with_address_appearances_index(config.stages.index_address_appearances.enabled);
 
         // Check for consistency between database and static files.
         if let Some(unwind_target) = factory
@@ -0,0 +1,159 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{mdbx::DatabaseArguments, open_db};
+use reth_db_api::database::Database;
+use reth_fs_util as fs;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// Name of the MDBX data file inside the database directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the MDBX lock file inside the database directory.
+const LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Name of the file the compacted copy of the database is written to, before it replaces the
+/// data file.
+const COMPACTED_FILE_NAME: &str = "mdbx.dat.compact";
+
+/// Interval between progress reports while the compacted copy is written.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command;
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// Writes a compacted copy of the database next to it, which requires free disk space for the
+    /// live data, and then atomically renames it over the data file. The original data file is left
+    /// untouched if anything fails before the rename.
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        let data_file = db_path.join(DATA_FILE_NAME);
+        let compacted_file = db_path.join(COMPACTED_FILE_NAME);
+
+        // Opening the database for writing takes the storage lock, so the node can't be running.
+        // The lock is held until the data file is replaced, so no other process can open the
+        // database in between.
+        let db = open_db(db_path, args)?;
+        let lock = db.lock_file().cloned();
+
+        if compacted_file.exists() {
+            warn!(
+                target: "reth::cli",
+                ?compacted_file,
+                "Removing leftover of an interrupted compaction"
+            );
+            fs::remove_file(&compacted_file)?;
+        }
+
+        let page_size = db.stat()?.page_size() as u64;
+        let total_pages = db.info()?.last_pgno() as u64 + 1;
+        let free_pages = db.freelist()? as u64;
+        let size_before = fs::metadata(&data_file)?.len();
+        let live_size = (total_pages - free_pages) * page_size;
+        info!(
+            target: "reth::cli",
+            size = %human_bytes(size_before as f64),
+            free = %human_bytes((free_pages * page_size) as f64),
+            "Compacting database"
+        );
+
+        let start = Instant::now();
+        std::thread::scope(|scope| {
+            let copy = scope.spawn(|| db.copy_to(&compacted_file, true));
+
+            let mut last_report = Instant::now();
+            while !copy.is_finished() {
+                std::thread::sleep(Duration::from_millis(100));
+                if last_report.elapsed() < PROGRESS_INTERVAL {
+                    continue
+                }
+                last_report = Instant::now();
+
+                let written = std::fs::metadata(&compacted_file).map(|m| m.len()).unwrap_or(0);
+                let progress = (written as f64 / live_size.max(1) as f64 * 100.0).min(100.0);
+                info!(
+                    target: "reth::cli",
+                    written = %human_bytes(written as f64),
+                    progress = %format!("{progress:.2}%"),
+                    "Writing compacted database"
+                );
+            }
+
+            copy.join().expect("compaction thread panicked")
+        })?;
+
+        // The environment must be closed before its data file is replaced.
+        drop(db);
+        fs::rename(&compacted_file, &data_file)?;
+
+        // The lock file describes the replaced data file, MDBX recreates it on the next open.
+        let lock_file = db_path.join(LOCK_FILE_NAME);
+        if lock_file.exists() {
+            fs::remove_file(&lock_file)?;
+        }
+        drop(lock);
+
+        let size_after = fs::metadata(&data_file)?.len();
+        println!(
+            "Compacted database in {:?}: {} -> {} ({} reclaimed)",
+            start.elapsed(),
+            human_bytes(size_before as f64),
+            human_bytes(size_after as f64),
+            human_bytes(size_before.saturating_sub(size_after) as f64)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, U256};
+    use reth_db::{init_db, mdbx::MEGABYTE, tables};
+    use reth_db_api::transaction::{DbTx, DbTxMut};
+
+    #[test]
+    fn compacts_database_with_free_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = DatabaseArguments::default().with_growth_step(Some(MEGABYTE));
+        let hash = |number: u64| B256::from(U256::from(number));
+
+        // The pages of the cleared table are freed in front of the pages that are still in use,
+        // so they can't be reclaimed by shrinking the data file.
+        let db = init_db(dir.path(), args.clone()).unwrap();
+        let tx = db.tx_mut().unwrap();
+        for number in 0..100_000 {
+            tx.put::<tables::CanonicalHeaders>(number, hash(number)).unwrap();
+        }
+        tx.commit().unwrap();
+        let tx = db.tx_mut().unwrap();
+        for number in 0..1_000 {
+            tx.put::<tables::HeaderNumbers>(hash(number), number).unwrap();
+        }
+        tx.commit().unwrap();
+        let tx = db.tx_mut().unwrap();
+        tx.clear::<tables::CanonicalHeaders>().unwrap();
+        tx.commit().unwrap();
+        assert!(db.freelist().unwrap() > 0);
+        drop(db);
+
+        let data_file = dir.path().join(DATA_FILE_NAME);
+        let size_before = fs::metadata(&data_file).unwrap().len();
+        Command.execute(dir.path(), args.clone()).unwrap();
+        assert!(fs::metadata(&data_file).unwrap().len() < size_before);
+        assert!(!dir.path().join(COMPACTED_FILE_NAME).exists());
+
+        let db = open_db(dir.path(), args).unwrap();
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 0);
+        assert_eq!(tx.entries::<tables::HeaderNumbers>().unwrap(), 1_000);
+        assert_eq!(tx.get::<tables::HeaderNumbers>(hash(7)).unwrap(), Some(7));
+    }
+}
@@ -1,10 +1,12 @@
 use alloy_consensus::Header;
-use alloy_primitives::{hex, BlockHash};
+use alloy_primitives::{hex, BlockHash, BlockNumber};
 use clap::Parser;
 use reth_db::static_file::{
-    ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+    AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask,
+    StorageChangeSetMask, TransactionMask,
 };
 use reth_db_api::{
+    models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
     table::{Decompress, DupSort, Table},
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
@@ -73,6 +75,12 @@ impl Command {
                         (table_key::<tables::Receipts>(&key)?, <ReceiptMask<ReceiptTy<N>>>::MASK)
                     }
                     StaticFileSegment::BlockMeta => todo!(),
+                    StaticFileSegment::AccountChangeSets => {
+                        (serde_json::from_str::<BlockNumber>(&key)?, AccountChangeSetMask::MASK)
+                    }
+                    StaticFileSegment::StorageChangeSets => {
+                        (serde_json::from_str::<BlockNumber>(&key)?, StorageChangeSetMask::MASK)
+                    }
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -117,6 +125,18 @@ impl Command {
                                 StaticFileSegment::BlockMeta => {
                                     todo!()
                                 }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let changeset = StaticFileAccountChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let changeset = StaticFileStorageChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
                             }
                         }
                     }
@@ -10,9 +10,12 @@ use std::{
 };
 mod checksum;
 mod clear;
+mod compact;
 mod diff;
 mod get;
 mod list;
+mod restore;
+mod snapshot;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -48,6 +51,13 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Reclaims free pages by replacing the database with a compacted copy, the node must be
+    /// stopped
+    Compact(compact::Command),
+    /// Writes a consistent snapshot of the database and static files, without stopping the node
+    Snapshot(snapshot::Command),
+    /// Restores a snapshot written by `reth db snapshot` into an empty datadir
+    Restore(restore::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -79,10 +89,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             data_dir.data_dir()
         );
 
+        // restoring a snapshot populates an empty datadir, so it's done before ensuring the
+        // database exists
+        let command = match self.command {
+            Subcommands::Restore(command) => return command.execute(&db_path, &static_files_path),
+            command => command,
+        };
+
         // ensure the provided database exist
         eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
 
-        match self.command {
+        match command {
             // TODO: We'll need to add this on the DB trait.
             Subcommands::Stats(command) => {
                 db_ro_exec!(self.env, tool, N, {
@@ -133,6 +150,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Compact(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
+            Subcommands::Snapshot(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Restore(_) => unreachable!("restored above"),
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
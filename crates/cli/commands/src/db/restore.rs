@@ -0,0 +1,30 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_provider::providers::restore_snapshot;
+use std::path::{Path, PathBuf};
+
+/// The arguments for the `reth db restore` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory of the snapshot to restore, as written by `reth db snapshot`.
+    snapshot: PathBuf,
+}
+
+impl Command {
+    /// Execute `db restore` command
+    ///
+    /// The database and static files directories must either not exist or be empty.
+    pub fn execute(self, db_path: &Path, static_files_path: &Path) -> eyre::Result<()> {
+        let manifest = restore_snapshot(&self.snapshot, db_path, static_files_path)?;
+
+        println!(
+            "Restored snapshot {} to {}: {} files, {}",
+            self.snapshot.display(),
+            db_path.parent().unwrap_or(db_path).display(),
+            manifest.files.len(),
+            human_bytes(manifest.total_size() as f64)
+        );
+
+        Ok(())
+    }
+}
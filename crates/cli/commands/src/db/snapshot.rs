@@ -0,0 +1,46 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_provider::{
+    providers::ProviderNodeTypes, DatabaseSnapshotProvider, ProviderFactory, SnapshotOptions,
+};
+use std::path::PathBuf;
+
+/// The arguments for the `reth db snapshot` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the snapshot to. It must either not exist or be empty.
+    destination: PathBuf,
+
+    /// Compact the database while copying it, omitting free pages.
+    #[arg(long, default_value_t = false)]
+    compact: bool,
+
+    /// Copy static files below the last finalized block, instead of hard-linking them.
+    #[arg(long, default_value_t = false)]
+    copy_static_files: bool,
+}
+
+impl Command {
+    /// Execute `db snapshot` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let options = SnapshotOptions::default()
+            .with_compact(self.compact)
+            .with_copy_static_files(self.copy_static_files);
+        let manifest = provider_factory.snapshot(&self.destination, options)?;
+
+        println!(
+            "Snapshot written to {}: {} files, {}",
+            self.destination.display(),
+            manifest.files.len(),
+            human_bytes(manifest.total_size() as f64)
+        );
+        for (segment, height) in &manifest.static_files {
+            println!("  {segment}: up to block {height}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Command that exports the history of a chain into era1 files.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era_utils::ExportConfig;
+use reth_node_core::version::SHORT_VERSION;
+use reth_provider::{ChainSpecProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Exports the history of a chain into era1 files.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    ///
+    /// Defaults to the first block of the Byzantium hardfork. Earlier blocks can't be exported
+    /// because their receipts are stored without their intermediate state root. On mainnet, this
+    /// excludes all history before block 4370000.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    first_block_number: Option<BlockNumber>,
+
+    /// The last block to export, which has to be a pre-merge block.
+    ///
+    /// Defaults to the highest block in static files, or the last block before the merge if
+    /// static files go beyond it.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    last_block_number: Option<BlockNumber>,
+
+    /// The directory the era1 files are written to.
+    ///
+    /// Every file holds up to 8192 blocks of one era, and is named
+    /// `<network>-<era-number>-<short-accumulator-root>.era1`, or
+    /// `<network>-<first-block>-<block-count>.era1` if it only holds a part of its era.
+    #[arg(long, value_name = "EXPORT_ERA_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
+    /// Execute `export-era` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let chain_spec = provider_factory.chain_spec();
+        let merge_block = match chain_spec.ethereum_fork_activation(EthereumHardfork::Paris) {
+            ForkCondition::Block(block) |
+            ForkCondition::TTD { activation_block_number: block, .. } => Some(block),
+            ForkCondition::Timestamp(_) | ForkCondition::Never => None,
+        };
+        eyre::ensure!(merge_block != Some(0), "the chain has no pre-merge blocks to export");
+        let byzantium_block = match chain_spec.ethereum_fork_activation(EthereumHardfork::Byzantium)
+        {
+            ForkCondition::Block(block) if block > 0 => Some(block),
+            _ => None,
+        };
+
+        let last_block_number = match self.last_block_number {
+            Some(number) => number,
+            None => {
+                let highest = provider_factory
+                    .static_file_provider()
+                    .get_highest_static_file_block(StaticFileSegment::Headers)
+                    .unwrap_or_default();
+                merge_block.map_or(highest, |merge_block| highest.min(merge_block - 1))
+            }
+        };
+
+        let config = ExportConfig {
+            dir: self.path,
+            first_block_number: self.first_block_number.or(byzantium_block).unwrap_or_default(),
+            last_block_number,
+            network: chain_spec.chain().to_string(),
+            merge_block,
+            byzantium_block,
+        };
+        info!(
+            target: "reth::cli",
+            first = config.first_block_number,
+            last = config.last_block_number,
+            "Exporting era1 files"
+        );
+
+        let files = reth_era_utils::export(&provider_factory.provider()?, &config)?;
+
+        info!(target: "reth::cli", files = files.len(), "Exported era1 files");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ExportEraCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
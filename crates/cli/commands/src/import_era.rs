@@ -0,0 +1,89 @@
+//! Command that initializes the node by importing the history of a chain from era1 files.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::{Args, Parser};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era_utils::EraImportSource;
+use reth_etl::Collector;
+use reth_node_core::version::SHORT_VERSION;
+use reth_provider::StaticFileProviderFactory;
+use reth_static_file_types::StaticFileSegment;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+use url::Url;
+
+/// Imports the history of a chain from era1 files.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(flatten)]
+    import: ImportArgs,
+}
+
+/// The source of the era1 files to import.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct ImportArgs {
+    /// The path to a directory containing era1 files.
+    #[arg(long, value_name = "IMPORT_ERA_PATH", verbatim_doc_comment)]
+    path: Option<PathBuf>,
+
+    /// The URL of a remote host to download era1 files from.
+    ///
+    /// The files are downloaded into the `era` directory of the data directory, unless
+    /// configured otherwise, and removed once they are imported.
+    #[arg(long, value_name = "IMPORT_ERA_URL", verbatim_doc_comment)]
+    url: Option<Url>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraCommand<C> {
+    /// Execute `import-era` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let source = match (self.import.path, self.import.url) {
+            (Some(path), _) => EraImportSource::Path(path),
+            (None, Some(url)) => {
+                let download_dir = config
+                    .stages
+                    .era
+                    .download_dir
+                    .ok_or_else(|| eyre::eyre!("no download directory for era1 files"))?;
+                EraImportSource::Url(url, download_dir)
+            }
+            (None, None) => eyre::bail!("either a path or a URL of era1 files is required"),
+        };
+
+        let height = provider_factory
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+        info!(target: "reth::cli", from = height + 1, "Importing era1 files");
+
+        let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir);
+        let height = reth_era_utils::import(
+            source.stream_from_block(height + 1),
+            &provider_factory,
+            &mut hash_collector,
+        )
+        .await?;
+
+        info!(target: "reth::cli", height, "Imported era1 files");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ImportEraCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -0,0 +1,231 @@
+//! Command that builds a signed EIP-1459 ENR tree for publishing via DNS.
+
+use alloy_primitives::FixedBytes;
+use clap::{Parser, ValueEnum};
+use reth_chainspec::{ForkHash, ForkId};
+use reth_cli_util::get_secret_key;
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_discv5::{
+    discv5::{enr::Enr, Event},
+    Config, Discv5,
+};
+use reth_dns_discovery::{tree::LinkEntry, EnrTreeBuilder};
+use reth_network::config::rng_secret_key;
+use reth_network_peers::NodeRecord;
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+/// Build a signed ENR tree from crawled nodes or a list of node records.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The domain the tree will be published at.
+    #[arg(long, value_name = "DOMAIN")]
+    domain: String,
+
+    /// Secret key the tree is signed with. A new key is generated if the file does not exist.
+    ///
+    /// The public key is part of the `enrtree://` link of the tree.
+    #[arg(long, value_name = "PATH")]
+    signing_key: PathBuf,
+
+    /// File with signed node records to include in the tree, one `enr:` record per line.
+    #[arg(long, value_name = "FILE", required_unless_present = "crawl")]
+    nodes_file: Option<PathBuf>,
+
+    /// Crawl the network via discovery for nodes to include in the tree.
+    #[arg(long)]
+    crawl: bool,
+
+    /// How long to crawl the network for.
+    #[arg(
+        long = "crawl.duration",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "30s"
+    )]
+    crawl_duration: Duration,
+
+    /// Also crawl the network via discv5.
+    #[arg(long = "crawl.discv5")]
+    crawl_discv5: bool,
+
+    /// The address discovery listens on while crawling.
+    #[arg(long = "crawl.addr", value_name = "ADDR", default_value = "0.0.0.0:30309")]
+    crawl_addr: SocketAddr,
+
+    /// Only include nodes that announce this fork hash in their record.
+    ///
+    /// Defaults to the hash of the latest fork of the chain.
+    #[arg(long, value_name = "HASH", conflicts_with = "no_fork_filter")]
+    fork_hash: Option<FixedBytes<4>>,
+
+    /// Include nodes regardless of the fork they announce.
+    #[arg(long)]
+    no_fork_filter: bool,
+
+    /// Links to other trees that are included in the tree.
+    #[arg(long = "link", value_name = "ENRTREE")]
+    links: Vec<LinkEntry>,
+
+    /// Sequence number of the tree. Defaults to the current unix timestamp.
+    #[arg(long, value_name = "SEQ")]
+    seq: Option<u64>,
+
+    /// Output format of the TXT records.
+    #[arg(long, value_enum, default_value_t = TreeFormat::Zone)]
+    format: TreeFormat,
+
+    /// TTL of the records in zone file format.
+    #[arg(long, default_value_t = 3600)]
+    ttl: u32,
+
+    /// Write the records to this file instead of stdout.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Output formats of the `dns-tree` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TreeFormat {
+    /// A DNS zone file.
+    Zone,
+    /// A JSON object that maps every fully qualified name to the content of its TXT record.
+    Json,
+}
+
+impl Command {
+    /// Execute the `dns-tree` command.
+    ///
+    /// The `fork_id` is used to filter nodes unless configured otherwise, and the `bootnodes` are
+    /// used to start crawling.
+    pub async fn execute(&self, fork_id: ForkId, bootnodes: Vec<NodeRecord>) -> eyre::Result<()> {
+        let mut nodes = Vec::new();
+        if let Some(nodes_file) = &self.nodes_file {
+            nodes.extend(read_nodes_file(nodes_file)?);
+            info!(target: "reth::cli", file=?nodes_file, count=nodes.len(), "Loaded node records");
+        }
+        if self.crawl {
+            nodes.extend(self.crawl_nodes(bootnodes).await?);
+        }
+
+        let mut builder = EnrTreeBuilder::default()
+            .with_sequence_number(self.seq.unwrap_or_else(|| {
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+            }))
+            .with_nodes(nodes);
+        if !self.no_fork_filter {
+            let hash = self.fork_hash.map(|hash| ForkHash(hash.0)).unwrap_or(fork_id.hash);
+            builder = builder.with_fork_id_filter(ForkId { hash, ..fork_id });
+        }
+        for link in &self.links {
+            builder = builder.with_link(link.clone());
+        }
+
+        let mut tree = builder.build();
+        let key = get_secret_key(&self.signing_key)?;
+        tree.sign(&key)?;
+
+        let output = match self.format {
+            TreeFormat::Zone => tree.to_zone_file(&self.domain, self.ttl),
+            TreeFormat::Json => serde_json::to_string_pretty(&tree.to_txt_records(&self.domain))?,
+        };
+        match &self.output {
+            Some(path) => reth_fs_util::write(path, output)?,
+            None => println!("{output}"),
+        }
+
+        let link = LinkEntry::<SecretKey> {
+            domain: self.domain.clone(),
+            pubkey: key.public_key(SECP256K1),
+        };
+        info!(target: "reth::cli", %link, entries=tree.entries().len(), "Built ENR tree");
+
+        Ok(())
+    }
+
+    /// Crawls the network via discovery and returns the signed records of all nodes that were
+    /// found.
+    async fn crawl_nodes(&self, bootnodes: Vec<NodeRecord>) -> eyre::Result<Vec<Enr<SecretKey>>> {
+        info!(target: "reth::cli", duration=?self.crawl_duration, "Crawling the network");
+
+        let secret_key = rng_secret_key();
+        let local_record = NodeRecord::from_secret_key(self.crawl_addr, &secret_key);
+        let config = Discv4Config::builder()
+            .add_boot_nodes(bootnodes.clone())
+            .lookup_interval(Duration::from_secs(1))
+            .enable_eip868(true)
+            .build();
+        let (_discv4, mut discv4_service) =
+            Discv4::bind(self.crawl_addr, local_record, secret_key, config).await?;
+        let mut discv4_updates = discv4_service.update_stream();
+        let _discv4_task = discv4_service.spawn();
+
+        let mut discv5_events = None;
+        let mut _discv5 = None;
+        if self.crawl_discv5 {
+            let config = Config::builder(self.crawl_addr)
+                .add_unsigned_boot_nodes(bootnodes)
+                .lookup_interval(1)
+                .build();
+            let (discv5, events, _) = Discv5::start(&secret_key, config).await?;
+            discv5_events = Some(events);
+            _discv5 = Some(discv5);
+        }
+
+        let mut nodes = HashMap::new();
+        let timeout = tokio::time::sleep(self.crawl_duration);
+        tokio::pin!(timeout);
+
+        loop {
+            tokio::select! {
+                _ = &mut timeout => break,
+                Some(update) = discv4_updates.next() => {
+                    if let DiscoveryUpdate::Enr(enr) = update {
+                        nodes.insert(enr.node_id(), enr);
+                    }
+                }
+                Some(event) = async {
+                    match &mut discv5_events {
+                        Some(events) => events.recv().await,
+                        None => futures::future::pending().await,
+                    }
+                } => {
+                    let (Event::Discovered(enr) | Event::SessionEstablished(enr, _)) = event else {
+                        continue
+                    };
+                    // discv5 records use a combined key type, the tree only supports secp256k1
+                    match enr.to_base64().parse::<Enr<SecretKey>>() {
+                        Ok(enr) => {
+                            nodes.insert(enr.node_id(), enr);
+                        }
+                        Err(err) => {
+                            debug!(target: "reth::cli", %err, "Skipping discv5 record");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(target: "reth::cli", count=nodes.len(), "Finished crawling the network");
+        Ok(nodes.into_values().collect())
+    }
+}
+
+/// Reads signed node records from the file, one per line.
+///
+/// Empty lines and lines starting with `#` are skipped.
+fn read_nodes_file(path: &Path) -> eyre::Result<Vec<Enr<SecretKey>>> {
+    reth_fs_util::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.parse().map_err(|err| eyre::eyre!("invalid node record {line}: {err}")))
+        .collect()
+}
@@ -17,6 +17,7 @@ use reth_node_core::{
 };
 
 pub mod bootnode;
+pub mod dns_tree;
 mod rlpx;
 
 /// `reth p2p` command
@@ -72,11 +73,22 @@ pub enum Subcommands {
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
+    /// Build a signed EIP-1459 ENR tree for publishing via DNS
+    DnsTree(dns_tree::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute `p2p` command
     pub async fn execute<N: NetworkPrimitives>(self) -> eyre::Result<()> {
+        // building the tree runs its own discovery services and doesn't need the network
+        if let Subcommands::DnsTree(command) = &self.command {
+            let bootnodes = self
+                .network
+                .resolved_bootnodes()
+                .unwrap_or_else(|| self.chain.bootnodes().unwrap_or_default());
+            return command.execute(self.chain.latest_fork_id(), bootnodes).await
+        }
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
@@ -159,6 +171,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Rlpx(command) => {
                 command.execute().await?;
             }
+            Subcommands::DnsTree(_) => unreachable!("handled before the network is started"),
         }
 
         Ok(())
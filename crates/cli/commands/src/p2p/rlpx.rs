@@ -1,8 +1,14 @@
 //! RLPx subcommand of P2P Debugging tool.
 
+use std::time::Duration;
+
 use clap::{Parser, Subcommand};
+use futures::StreamExt;
 use reth_ecies::stream::ECIESStream;
-use reth_eth_wire::{HelloMessage, UnauthedP2PStream};
+use reth_eth_wire::{
+    protocol::Protocol, EthNetworkPrimitives, EthVersion, HelloMessage, MessageTracer, P2PStream,
+    UnauthedP2PStream,
+};
 use reth_network::config::rng_secret_key;
 use reth_network_peers::{pk2id, AnyNode};
 use secp256k1::SECP256K1;
@@ -20,27 +26,95 @@ impl Command {
     pub async fn execute(self) -> eyre::Result<()> {
         match self.subcommand {
             Subcommands::Ping { node } => {
-                let key = rng_secret_key();
-                let node_record = node
-                    .node_record()
-                    .ok_or_else(|| eyre::eyre!("failed to parse node {}", node))?;
-                let outgoing =
-                    TcpStream::connect((node_record.address, node_record.tcp_port)).await?;
-                let ecies_stream = ECIESStream::connect(outgoing, key, node_record.id).await?;
+                let (_, their_hello) = connect(&node, None).await?;
 
-                let peer_id = pk2id(&key.public_key(SECP256K1));
-                let hello = HelloMessage::builder(peer_id).build();
+                println!("{:#?}", their_hello);
+            }
+            Subcommands::Trace { node, count, duration } => {
+                let protocols =
+                    [EthVersion::Eth69, EthVersion::Eth68, EthVersion::Eth67, EthVersion::Eth66]
+                        .into_iter()
+                        .map(Protocol::from)
+                        .chain(std::iter::once(Protocol::snap_1()))
+                        .collect();
+                let (mut stream, their_hello) = connect(&node, Some(protocols)).await?;
 
-                let (_, their_hello) =
-                    UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+                println!("Connected to {} ({})", node, their_hello.client_version);
+                println!("Shared capabilities:");
+                for cap in stream.shared_capabilities().iter_caps() {
+                    println!(
+                        "  {} offset={} messages={}",
+                        cap.capability(),
+                        cap.message_id_offset(),
+                        cap.num_messages()
+                    );
+                }
 
-                println!("{:#?}", their_hello);
+                let tracer = MessageTracer::new(count).with_eth_messages::<EthNetworkPrimitives>();
+                tracer.set_enabled(true);
+                stream.set_tracer(tracer.clone());
+
+                let deadline = tokio::time::Instant::now() + duration;
+                let mut traced = 0;
+                while traced < count {
+                    match tokio::time::timeout_at(deadline, stream.next()).await {
+                        Ok(Some(Ok(_))) => {}
+                        Ok(Some(Err(err))) => {
+                            println!("Session closed: {err}");
+                            break
+                        }
+                        Ok(None) => {
+                            println!("Session closed by the remote");
+                            break
+                        }
+                        Err(_) => break,
+                    }
+
+                    for msg in tracer.take_messages() {
+                        traced += 1;
+                        println!(
+                            "{} {:?} {} id={} name={} size={}",
+                            msg.timestamp,
+                            msg.direction,
+                            msg.capability,
+                            msg.message_id,
+                            msg.name.as_deref().unwrap_or("unknown"),
+                            msg.size
+                        );
+                        if let Some(message) = msg.message {
+                            println!("  {message}");
+                        }
+                    }
+                }
             }
         }
         Ok(())
     }
 }
 
+/// Connects to the node and performs the `RLPx` handshake.
+///
+/// Advertises the given protocols, or all eth versions if none are given.
+async fn connect(
+    node: &AnyNode,
+    protocols: Option<Vec<Protocol>>,
+) -> eyre::Result<(P2PStream<ECIESStream<TcpStream>>, HelloMessage)> {
+    let key = rng_secret_key();
+    let node_record =
+        node.node_record().ok_or_else(|| eyre::eyre!("failed to parse node {}", node))?;
+    let outgoing = TcpStream::connect((node_record.address, node_record.tcp_port)).await?;
+    let ecies_stream = ECIESStream::connect(outgoing, key, node_record.id).await?;
+
+    let peer_id = pk2id(&key.public_key(SECP256K1));
+    let mut hello = HelloMessage::builder(peer_id);
+    if let Some(protocols) = protocols {
+        hello = hello.protocols(protocols);
+    }
+    let hello = hello.build();
+
+    Ok(UnauthedP2PStream::new(ecies_stream).handshake(hello).await?)
+}
+
 #[derive(Subcommand, Debug)]
 enum Subcommands {
     /// ping node
@@ -48,4 +122,19 @@ enum Subcommands {
         /// The node to ping.
         node: AnyNode,
     },
+    /// Connect to a node, print the negotiated capabilities and trace the messages it sends
+    ///
+    /// No eth status is sent, so most nodes only send their own status before they disconnect.
+    Trace {
+        /// The node to trace.
+        node: AnyNode,
+
+        /// The maximum number of messages to trace.
+        #[arg(long, default_value_t = 16)]
+        count: usize,
+
+        /// How long to wait for messages, e.g. `30s`.
+        #[arg(long, value_parser = humantime::parse_duration, default_value = "30s")]
+        duration: Duration,
+    },
 }
@@ -42,25 +42,29 @@ impl<C: ChainSpecParser> Command<C> {
 
         let tool = DbTool::new(provider_factory)?;
 
-        let static_file_segment = match self.stage {
-            StageEnum::Headers => Some(StaticFileSegment::Headers),
-            StageEnum::Bodies => Some(StaticFileSegment::Transactions),
-            StageEnum::Execution => Some(StaticFileSegment::Receipts),
-            _ => None,
+        let static_file_segments: &[StaticFileSegment] = match self.stage {
+            StageEnum::Headers => &[StaticFileSegment::Headers],
+            StageEnum::Bodies => &[StaticFileSegment::Transactions],
+            StageEnum::Execution => &[
+                StaticFileSegment::Receipts,
+                StaticFileSegment::AccountChangeSets,
+                StaticFileSegment::StorageChangeSets,
+            ],
+            _ => &[],
         };
 
         // Delete static file segment data before inserting the genesis header below
-        if let Some(static_file_segment) = static_file_segment {
-            let static_file_provider = tool.provider_factory.static_file_provider();
-            let static_files = iter_static_files(static_file_provider.directory())?;
-            if let Some(segment_static_files) = static_files.get(&static_file_segment) {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+        for static_file_segment in static_file_segments {
+            if let Some(segment_static_files) = static_files.get(static_file_segment) {
                 // Delete static files from the highest to the lowest block range
                 for (block_range, _) in segment_static_files
                     .iter()
                     .sorted_by_key(|(block_range, _)| block_range.start())
                     .rev()
                 {
-                    static_file_provider.delete_jar(static_file_segment, block_range.start())?;
+                    static_file_provider.delete_jar(*static_file_segment, block_range.start())?;
                 }
             }
         }
@@ -75,6 +79,8 @@ impl<C: ChainSpecParser> Command<C> {
                 tx.clear::<tables::HeaderTerminalDifficulties>()?;
                 tx.clear::<tables::HeaderNumbers>()?;
                 reset_stage_checkpoint(tx, StageId::Headers)?;
+                // Headers are imported by the era stage as well
+                reset_stage_checkpoint(tx, StageId::Era)?;
 
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
@@ -106,6 +112,8 @@ impl<C: ChainSpecParser> Command<C> {
 
                 reset_prune_checkpoint(tx, PruneSegment::Receipts)?;
                 reset_prune_checkpoint(tx, PruneSegment::ContractLogs)?;
+                reset_prune_checkpoint(tx, PruneSegment::AccountChangeSets)?;
+                reset_prune_checkpoint(tx, PruneSegment::StorageChangeSets)?;
                 reset_stage_checkpoint(tx, StageId::Execution)?;
 
                 let alloc = &self.env.chain.genesis().alloc;
@@ -149,6 +157,12 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::AddressAppearances => {
+                tx.clear::<tables::AddressAppearances>()?;
+                reset_prune_checkpoint(tx, PruneSegment::AddressAppearances)?;
+
+                reset_stage_checkpoint(tx, StageId::IndexAddressAppearances)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
@@ -10,7 +10,9 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::get_secret_key;
-use reth_config::config::{HashingConfig, SenderRecoveryConfig, TransactionLookupConfig};
+use reth_config::config::{
+    HashingConfig, IndexAddressAppearancesConfig, SenderRecoveryConfig, TransactionLookupConfig,
+};
 use reth_db_api::database_metrics::DatabaseMetrics;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -40,8 +42,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexAddressAppearancesStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -307,7 +309,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     Box::new(IndexAccountHistoryStage::new(
                         config.stages.index_account_history,
                         etl_config,
-                        prune_modes.account_history,
+                        prune_modes
+                            .account_history
+                            .filter(|_| !prune_modes.has_history_address_filter()),
                     )),
                     None,
                 ),
@@ -315,7 +319,20 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     Box::new(IndexStorageHistoryStage::new(
                         config.stages.index_storage_history,
                         etl_config,
-                        prune_modes.storage_history,
+                        prune_modes
+                            .storage_history
+                            .filter(|_| !prune_modes.has_history_address_filter()),
+                    )),
+                    None,
+                ),
+                StageEnum::AddressAppearances => (
+                    Box::new(IndexAddressAppearancesStage::new(
+                        IndexAddressAppearancesConfig {
+                            enabled: true,
+                            ..config.stages.index_address_appearances
+                        },
+                        etl_config,
+                        prune_modes.address_appearances,
                     )),
                     None,
                 ),
@@ -6,6 +6,7 @@ use std::{
     path::{Path, PathBuf},
     time::Duration,
 };
+use url::Url;
 
 #[cfg(feature = "serde")]
 const EXTENSION: &str = "toml";
@@ -100,6 +101,8 @@ impl Config {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct StageConfig {
+    /// ERA stage configuration.
+    pub era: EraConfig,
     /// Header stage configuration.
     pub headers: HeadersConfig,
     /// Body stage configuration.
@@ -122,6 +125,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Appearances stage configuration.
+    pub index_address_appearances: IndexAddressAppearancesConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -139,6 +144,29 @@ impl StageConfig {
     }
 }
 
+/// ERA stage configuration.
+///
+/// The stage is disabled unless either a local `path` or a remote `url` hosting era1 files is
+/// configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EraConfig {
+    /// Local directory containing era1 files to import.
+    pub path: Option<PathBuf>,
+    /// URL of a remote host to download era1 files from.
+    pub url: Option<Url>,
+    /// Directory where era1 files are downloaded to, before they are imported.
+    pub download_dir: Option<PathBuf>,
+}
+
+impl EraConfig {
+    /// Return default ERA download directory from datadir path.
+    pub fn from_datadir(path: &Path) -> PathBuf {
+        path.join("era")
+    }
+}
+
 /// Header stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -388,6 +416,27 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Address appearances index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAddressAppearancesConfig {
+    /// Whether the address appearances index should be built and maintained.
+    ///
+    /// The index is opt-in, because it's only required by address history RPC endpoints like
+    /// `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`. If it's enabled on an
+    /// already synced node, it's rebuilt from genesis on the next start.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressAppearancesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -425,6 +474,8 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    address_appearances,
+                    history_address_filter,
                     receipts_log_filter,
                 },
         } = other;
@@ -440,6 +491,12 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.address_appearances =
+            self.segments.address_appearances.or(address_appearances);
+
+        if self.segments.history_address_filter.is_empty() && !history_address_filter.is_empty() {
+            self.segments.history_address_filter = history_address_filter;
+        }
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -470,9 +527,9 @@ where
 mod tests {
     use super::{Config, EXTENSION};
     use crate::PruneConfig;
-    use alloy_primitives::Address;
+    use alloy_primitives::{address, Address};
     use reth_network_peers::TrustedPeer;
-    use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
+    use reth_prune_types::{HistoryAddressFilter, PruneMode, PruneModes, ReceiptsLogPruneConfig};
     use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -950,6 +1007,46 @@ receipts = 'full'
         assert!(err.contains("invalid value: string \"full\""), "{}", err);
     }
 
+    #[test]
+    fn test_prune_config_age_and_size() {
+        let s = r"#
+[prune]
+block_interval = 5
+
+[prune.segments]
+receipts = { age = 7776000 }
+account_history = { size = 536870912000 }
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let segments = conf.prune.unwrap().segments;
+        assert_eq!(segments.receipts, Some(PruneMode::Age(7776000)));
+        assert_eq!(segments.account_history, Some(PruneMode::Size(536870912000)));
+    }
+
+    #[test]
+    fn test_prune_config_history_address_filter() {
+        let s = r"#
+[prune]
+block_interval = 5
+
+[prune.segments]
+account_history = { distance = 10064 }
+storage_history = { distance = 10064 }
+history_address_filter = ['0xdac17f958d2ee523a2206206994597c13d831ec7']
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let segments = conf.prune.unwrap().segments;
+        assert_eq!(
+            segments.history_address_filter,
+            HistoryAddressFilter::from_iter([address!(
+                "0xdac17f958d2ee523a2206206994597c13d831ec7"
+            )])
+        );
+
+        let reserialized = toml::to_string(&segments).unwrap();
+        assert_eq!(toml::from_str::<PruneModes>(&reserialized).unwrap(), segments);
+    }
+
     #[test]
     fn test_prune_config_merge() {
         let mut config1 = PruneConfig {
@@ -960,6 +1057,8 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                address_appearances: None,
+                history_address_filter: HistoryAddressFilter::default(),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -975,6 +1074,8 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                address_appearances: Some(PruneMode::Distance(4000)),
+                history_address_filter: HistoryAddressFilter::from_iter([Address::random()]),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -983,6 +1084,7 @@ receipts = 'full'
         };
 
         let original_filter = config1.segments.receipts_log_filter.clone();
+        let history_address_filter = config2.segments.history_address_filter.clone();
         config1.merge(Some(config2));
 
         // Check that the configuration has been merged. Any configuration present in config1
@@ -993,6 +1095,8 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.address_appearances, Some(PruneMode::Distance(4000)));
+        assert_eq!(config1.segments.history_address_filter, history_address_filter);
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -18,7 +18,7 @@ use reth_node_core::primitives::SignedTransaction;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_provider::{
     BlockReader, BlockReaderIdExt, CanonStateNotificationStream, CanonStateSubscriptions,
-    StageCheckpointReader,
+    ChangeSetReader, PruneCheckpointReader, StageCheckpointReader, StorageChangeSetReader,
 };
 use reth_rpc_eth_api::helpers::{EthApiSpec, EthTransactions, TraceExt};
 use reth_rpc_layer::AuthClientService;
@@ -88,8 +88,12 @@ where
         tx_generator: impl Fn(u64) -> Pin<Box<dyn Future<Output = Bytes>>>,
     ) -> eyre::Result<Vec<Payload::BuiltPayload>>
     where
-        AddOns::EthApi: EthApiSpec<Provider: BlockReader<Block = BlockTy<Node::Types>>>
-            + EthTransactions
+        AddOns::EthApi: EthApiSpec<
+                Provider: BlockReader<Block = BlockTy<Node::Types>>
+                              + ChangeSetReader
+                              + StorageChangeSetReader
+                              + PruneCheckpointReader,
+            > + EthTransactions
             + TraceExt,
     {
         let mut chain = Vec::with_capacity(length as usize);
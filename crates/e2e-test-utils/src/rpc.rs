@@ -4,7 +4,7 @@ use alloy_primitives::{Bytes, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, FullNodeComponents};
 use reth_node_builder::{rpc::RpcRegistry, NodeTypes};
-use reth_provider::BlockReader;
+use reth_provider::{BlockReader, ChangeSetReader, PruneCheckpointReader, StorageChangeSetReader};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::{
     helpers::{EthApiSpec, EthTransactions, TraceExt},
@@ -19,8 +19,12 @@ pub struct RpcTestContext<Node: FullNodeComponents, EthApi: EthApiTypes> {
 impl<Node, EthApi> RpcTestContext<Node, EthApi>
 where
     Node: FullNodeComponents<Types: NodeTypes<ChainSpec: EthereumHardforks>>,
-    EthApi: EthApiSpec<Provider: BlockReader<Block = BlockTy<Node::Types>>>
-        + EthTransactions
+    EthApi: EthApiSpec<
+            Provider: BlockReader<Block = BlockTy<Node::Types>>
+                          + ChangeSetReader
+                          + StorageChangeSetReader
+                          + PruneCheckpointReader,
+        > + EthTransactions
         + TraceExt,
 {
     /// Injects a raw transaction into the node tx pool via RPC server
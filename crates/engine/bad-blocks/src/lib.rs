@@ -0,0 +1,318 @@
+//! A bounded, persistent registry of blocks that failed validation.
+//!
+//! The engine records every block it rejects together with the validation error, and invalid
+//! block hooks can attach additional artifacts, like the execution witness or the prestate of the
+//! block. The registry is kept on disk, so the evidence of a consensus bug survives restarts and
+//! can be served by the `debug_getBadBlocks` family of RPC methods.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_primitives::{BlockNumber, Bytes, B256};
+use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_trace::geth::PreStateFrame;
+use parking_lot::RwLock;
+use reth_fs_util::{self as fs, FsPathError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// The default number of bad blocks kept by the [`BadBlockRegistry`].
+///
+/// This is the same limit geth uses for its bad block cache.
+pub const DEFAULT_MAX_BAD_BLOCKS: usize = 10;
+
+/// A block that failed validation, as recorded by the [`BadBlockRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The RLP encoded block.
+    pub block: Bytes,
+    /// The validation error the block was rejected with.
+    pub error: String,
+    /// The unix timestamp in seconds of when the block was recorded.
+    pub recorded_at: u64,
+    /// The execution witness of the block, if it was generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness: Option<ExecutionWitness>,
+    /// The prestate of each transaction in the block, if it was generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prestate: Option<Vec<PreStateFrame>>,
+}
+
+impl BadBlock {
+    /// Creates a new [`BadBlock`] recorded at the current time, without any artifacts.
+    pub fn new(hash: B256, number: BlockNumber, block: Bytes, error: impl ToString) -> Self {
+        let recorded_at =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        Self {
+            hash,
+            number,
+            block,
+            error: error.to_string(),
+            recorded_at,
+            witness: None,
+            prestate: None,
+        }
+    }
+
+    /// Returns the name of the file the block is stored in.
+    fn file_name(&self) -> String {
+        format!("{}_{}.json", self.number, self.hash)
+    }
+}
+
+/// A bounded registry of [`BadBlock`]s.
+///
+/// If the registry is opened with [`BadBlockRegistry::open`], every block is stored as a JSON file
+/// in the given directory and the blocks are loaded from there again on the next start.
+///
+/// Once the limit is reached, the oldest block is evicted together with its file.
+///
+/// The registry is cheap to clone, all clones share the same blocks.
+#[derive(Debug, Clone)]
+pub struct BadBlockRegistry {
+    inner: Arc<RwLock<RegistryInner>>,
+}
+
+#[derive(Debug)]
+struct RegistryInner {
+    /// The directory the blocks are stored in, `None` if the registry is in memory only.
+    dir: Option<PathBuf>,
+    /// The maximum number of blocks to keep.
+    max_blocks: usize,
+    /// The recorded blocks, from oldest to newest.
+    blocks: VecDeque<BadBlock>,
+}
+
+impl BadBlockRegistry {
+    /// Creates a new in-memory registry keeping up to `max_blocks` blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self::with_blocks(None, max_blocks, VecDeque::new())
+    }
+
+    /// Opens a registry that stores up to `max_blocks` blocks in the given directory.
+    ///
+    /// Blocks previously stored in the directory are loaded, files that can't be read are
+    /// skipped.
+    pub fn open(dir: impl Into<PathBuf>, max_blocks: usize) -> Result<Self, FsPathError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let mut blocks = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry.map_err(|err| FsPathError::read_dir(err, &dir))?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue
+            }
+
+            match fs::read_json_file::<BadBlock>(&path) {
+                Ok(block) => blocks.push(block),
+                Err(err) => {
+                    warn!(target: "engine::bad_blocks", %err, "Failed to load bad block");
+                }
+            }
+        }
+        blocks.sort_by_key(|block| (block.recorded_at, block.number));
+
+        let registry = Self::with_blocks(Some(dir), max_blocks, blocks.into());
+        registry.inner.write().evict();
+        Ok(registry)
+    }
+
+    fn with_blocks(dir: Option<PathBuf>, max_blocks: usize, blocks: VecDeque<BadBlock>) -> Self {
+        Self { inner: Arc::new(RwLock::new(RegistryInner { dir, max_blocks, blocks })) }
+    }
+
+    /// Returns the directory the blocks are stored in, if any.
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.inner.read().dir.clone()
+    }
+
+    /// Records a block that failed validation.
+    ///
+    /// Returns `false` if the block is already recorded, in which case the registry is not
+    /// modified.
+    pub fn record(&self, block: BadBlock) -> bool {
+        let mut inner = self.inner.write();
+        if inner.position(block.hash).is_some() {
+            return false
+        }
+
+        inner.save(&block);
+        inner.blocks.push_back(block);
+        inner.evict();
+        true
+    }
+
+    /// Attaches the execution witness to a recorded block.
+    ///
+    /// Returns `false` if the block is not recorded.
+    pub fn set_witness(&self, hash: B256, witness: ExecutionWitness) -> bool {
+        self.update(hash, |block| block.witness = Some(witness))
+    }
+
+    /// Attaches the prestate of each transaction to a recorded block.
+    ///
+    /// Returns `false` if the block is not recorded.
+    pub fn set_prestate(&self, hash: B256, prestate: Vec<PreStateFrame>) -> bool {
+        self.update(hash, |block| block.prestate = Some(prestate))
+    }
+
+    fn update(&self, hash: B256, f: impl FnOnce(&mut BadBlock)) -> bool {
+        let mut inner = self.inner.write();
+        let Some(idx) = inner.position(hash) else { return false };
+
+        f(&mut inner.blocks[idx]);
+        inner.save(&inner.blocks[idx]);
+        true
+    }
+
+    /// Returns the recorded block with the given hash.
+    pub fn get(&self, hash: B256) -> Option<BadBlock> {
+        let inner = self.inner.read();
+        inner.position(hash).map(|idx| inner.blocks[idx].clone())
+    }
+
+    /// Returns all recorded blocks, from oldest to newest.
+    pub fn all(&self) -> Vec<BadBlock> {
+        self.inner.read().blocks.iter().cloned().collect()
+    }
+
+    /// Returns the number of recorded blocks.
+    pub fn len(&self) -> usize {
+        self.inner.read().blocks.len()
+    }
+
+    /// Returns `true` if no blocks are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().blocks.is_empty()
+    }
+}
+
+impl Default for BadBlockRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BAD_BLOCKS)
+    }
+}
+
+impl RegistryInner {
+    fn position(&self, hash: B256) -> Option<usize> {
+        self.blocks.iter().position(|block| block.hash == hash)
+    }
+
+    /// Evicts the oldest blocks until the limit is satisfied.
+    fn evict(&mut self) {
+        while self.blocks.len() > self.max_blocks {
+            let Some(block) = self.blocks.pop_front() else { break };
+            if let Some(path) = self.path(&block) {
+                if let Err(err) = fs::remove_file(&path) {
+                    warn!(target: "engine::bad_blocks", %err, "Failed to remove bad block");
+                }
+            }
+        }
+    }
+
+    /// Writes the block into its file, if the registry is persistent.
+    fn save(&self, block: &BadBlock) {
+        let Some(path) = self.path(block) else { return };
+        if let Err(err) = write_block(&path, block) {
+            warn!(target: "engine::bad_blocks", %err, hash = %block.hash, "Failed to save bad block");
+        }
+    }
+
+    fn path(&self, block: &BadBlock) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(block.file_name()))
+    }
+}
+
+/// Atomically writes the block as JSON into the given file.
+fn write_block(path: &Path, block: &BadBlock) -> Result<(), FsPathError> {
+    fs::atomic_write_file(path, |file| serde_json::to_writer(file, block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bad_block(number: BlockNumber) -> BadBlock {
+        BadBlock::new(
+            B256::with_last_byte(number as u8),
+            number,
+            Bytes::from(vec![number as u8]),
+            "invalid block",
+        )
+    }
+
+    #[test]
+    fn record_and_attach() {
+        let registry = BadBlockRegistry::default();
+        let block = bad_block(1);
+
+        assert!(registry.record(block.clone()));
+        assert!(!registry.record(block.clone()));
+        assert_eq!(registry.len(), 1);
+
+        let witness =
+            ExecutionWitness { codes: vec![Bytes::from_static(&[1])], ..Default::default() };
+        assert!(registry.set_witness(block.hash, witness.clone()));
+        assert!(!registry.set_witness(B256::ZERO, witness.clone()));
+        assert_eq!(registry.get(block.hash).unwrap().witness, Some(witness));
+    }
+
+    #[test]
+    fn evicts_oldest() {
+        let registry = BadBlockRegistry::new(2);
+        for number in 1..=3 {
+            registry.record(bad_block(number));
+        }
+
+        let numbers = registry.all().into_iter().map(|block| block.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![2, 3]);
+    }
+
+    #[test]
+    fn persists_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let registry = BadBlockRegistry::open(dir.path(), 2).unwrap();
+        for number in 1..=3 {
+            registry.record(bad_block(number));
+        }
+        let witness =
+            ExecutionWitness { keys: vec![Bytes::from_static(&[2])], ..Default::default() };
+        registry.set_witness(bad_block(3).hash, witness.clone());
+
+        // the evicted block is removed from disk
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+        drop(registry);
+
+        let registry = BadBlockRegistry::open(dir.path(), 2).unwrap();
+        assert_eq!(registry.len(), 2);
+        let block = registry.get(bad_block(2).hash).unwrap();
+        assert_eq!((block.number, block.block, block.witness), (2, Bytes::from(vec![2]), None));
+        assert_eq!(registry.get(bad_block(3).hash).unwrap().witness, Some(witness));
+
+        // reopening with a lower limit evicts the oldest blocks
+        let registry = BadBlockRegistry::open(dir.path(), 1).unwrap();
+        assert_eq!(
+            registry.all().into_iter().map(|block| block.number).collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::trace::{compare_with_healthy_node, save_file, trace_block};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{
+    GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace, TraceResult,
+};
+use reth_chainspec::EthereumHardforks;
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::TracingInspectorConfig;
+use std::path::PathBuf;
+
+/// Re-executes the given block with the opcode struct logger and saves the opcode traces of every
+/// transaction to a file.
+#[derive(Debug)]
+pub struct InvalidBlockOpcodeHook<P, Evm> {
+    /// The provider to read the historical state.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: Evm,
+    /// The directory to write the opcode traces to. Additionally, diff files will be written to
+    /// this directory in case of a mismatch with the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the opcode traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, Evm> InvalidBlockOpcodeHook<P, Evm> {
+    /// Creates a new opcode hook.
+    pub const fn new(
+        provider: P,
+        evm_config: Evm,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, Evm> InvalidBlockOpcodeHook<P, Evm>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    Evm: ConfigureEvm,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<<Evm::Primitives as NodePrimitives>::BlockHeader>,
+        block: &RecoveredBlock<<Evm::Primitives as NodePrimitives>::Block>,
+    ) -> eyre::Result<()> {
+        // Memory is left out, otherwise the traces of larger blocks get too big to be useful.
+        let config = GethDefaultTracingOptions::default();
+        let frames = trace_block(
+            &self.provider,
+            &self.evm_config,
+            parent_header,
+            block,
+            TracingInspectorConfig::from_geth_config(&config),
+            |inspector, res, _| {
+                let gas_used = res.result.gas_used();
+                let return_value = res.result.output().cloned().unwrap_or_default();
+                Ok(inspector.geth_builder().geth_traces(gas_used, return_value, config))
+            },
+        )?;
+
+        let traces = frames
+            .into_iter()
+            .zip(block.transactions_recovered())
+            .map(|(frame, tx)| TraceResult::Success {
+                result: GethTrace::Default(frame),
+                tx_hash: Some(*tx.tx_hash()),
+            })
+            .collect::<Vec<_>>();
+
+        let re_executed_path = save_file(
+            &self.output_directory,
+            format!("{}_{}.opcode.re_executed.json", block.number(), block.hash()),
+            &traces,
+        )?;
+
+        if let Some(healthy_node_client) = &self.healthy_node_client {
+            compare_with_healthy_node(
+                healthy_node_client,
+                &self.output_directory,
+                block,
+                "opcode",
+                GethDebugTracingOptions { config, ..Default::default() },
+                &traces,
+                &re_executed_path,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, Evm, N> InvalidBlockHook<N> for InvalidBlockOpcodeHook<P, Evm>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    Evm: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::opcode", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::invalid_block;
+    use reth_evm_ethereum::EthEvmConfig;
+
+    #[test]
+    fn writes_re_executed_opcode_traces() {
+        let (provider, parent, block) = invalid_block();
+        let output_directory = tempfile::tempdir().unwrap();
+        let hook = InvalidBlockOpcodeHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            output_directory.path().to_path_buf(),
+            None,
+        );
+        hook.on_invalid_block(&parent, &block).unwrap();
+
+        let path = output_directory.path().join(format!(
+            "{}_{}.opcode.re_executed.json",
+            block.number(),
+            block.hash()
+        ));
+        let traces: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        let [trace] = traces.as_array().unwrap().as_slice() else { panic!("expected one trace") };
+        assert_eq!(
+            trace["txHash"],
+            serde_json::to_value(block.body().transactions[0].tx_hash()).unwrap()
+        );
+        // a plain transfer doesn't execute any opcodes
+        assert_eq!(trace["result"]["gas"], 21_000);
+        assert_eq!(trace["result"]["failed"], false);
+        assert!(trace["result"]["structLogs"].as_array().unwrap().is_empty());
+    }
+}
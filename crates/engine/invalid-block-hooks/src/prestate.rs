@@ -0,0 +1,188 @@
+use crate::trace::{compare_with_healthy_node, save_file, trace_block};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{
+    GethDebugTracingOptions, GethTrace, PreStateConfig, TraceResult,
+};
+use reth_bad_blocks::BadBlockRegistry;
+use reth_chainspec::EthereumHardforks;
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::TracingInspectorConfig;
+use std::path::PathBuf;
+
+/// Re-executes the given block with the prestate tracer in diff mode and saves the state diff of
+/// every transaction to a file.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, Evm> {
+    /// The provider to read the historical state.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: Evm,
+    /// The directory to write the prestate to. Additionally, diff files will be written to this
+    /// directory in case of a mismatch with the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the prestate against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    /// The registry to attach the prestate to the recorded bad block in.
+    bad_block_registry: Option<BadBlockRegistry>,
+}
+
+impl<P, Evm> InvalidBlockPreStateHook<P, Evm> {
+    /// Creates a new prestate hook.
+    pub const fn new(
+        provider: P,
+        evm_config: Evm,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self {
+            provider,
+            evm_config,
+            output_directory,
+            healthy_node_client,
+            bad_block_registry: None,
+        }
+    }
+
+    /// Attaches the generated prestate to the blocks recorded in the given registry.
+    pub fn with_bad_block_registry(mut self, bad_block_registry: BadBlockRegistry) -> Self {
+        self.bad_block_registry = Some(bad_block_registry);
+        self
+    }
+
+    /// The prestate tracer configuration used for the re-execution.
+    fn prestate_config() -> PreStateConfig {
+        PreStateConfig { diff_mode: Some(true), ..Default::default() }
+    }
+}
+
+impl<P, Evm> InvalidBlockPreStateHook<P, Evm>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    Evm: ConfigureEvm,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<<Evm::Primitives as NodePrimitives>::BlockHeader>,
+        block: &RecoveredBlock<<Evm::Primitives as NodePrimitives>::Block>,
+    ) -> eyre::Result<()> {
+        let config = Self::prestate_config();
+        let frames = trace_block(
+            &self.provider,
+            &self.evm_config,
+            parent_header,
+            block,
+            TracingInspectorConfig::from_geth_prestate_config(&config),
+            |inspector, res, db| {
+                Ok(inspector.geth_builder().geth_prestate_traces(res, &config, db)?)
+            },
+        )?;
+
+        let traces = frames
+            .iter()
+            .cloned()
+            .zip(block.transactions_recovered())
+            .map(|(frame, tx)| TraceResult::Success {
+                result: GethTrace::PreStateTracer(frame),
+                tx_hash: Some(*tx.tx_hash()),
+            })
+            .collect::<Vec<_>>();
+
+        let re_executed_path = save_file(
+            &self.output_directory,
+            format!("{}_{}.prestate.re_executed.json", block.number(), block.hash()),
+            &traces,
+        )?;
+        if let Some(registry) = &self.bad_block_registry {
+            registry.set_prestate(block.hash(), frames);
+        }
+
+        if let Some(healthy_node_client) = &self.healthy_node_client {
+            compare_with_healthy_node(
+                healthy_node_client,
+                &self.output_directory,
+                block,
+                "prestate",
+                GethDebugTracingOptions::prestate_tracer(config),
+                &traces,
+                &re_executed_path,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, Evm, N> InvalidBlockHook<N> for InvalidBlockPreStateHook<P, Evm>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    Evm: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::prestate", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{invalid_block, SENDER};
+    use alloy_primitives::{Address, U256};
+    use reth_evm_ethereum::EthEvmConfig;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn writes_re_executed_prestate() {
+        let (provider, parent, block) = invalid_block();
+        let output_directory = tempfile::tempdir().unwrap();
+        let hook = InvalidBlockPreStateHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            output_directory.path().to_path_buf(),
+            None,
+        );
+        hook.on_invalid_block(&parent, &block).unwrap();
+
+        let path = output_directory.path().join(format!(
+            "{}_{}.prestate.re_executed.json",
+            block.number(),
+            block.hash()
+        ));
+        let traces: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        let [trace] = traces.as_array().unwrap().as_slice() else { panic!("expected one trace") };
+        assert_eq!(
+            trace["txHash"],
+            serde_json::to_value(block.body().transactions[0].tx_hash()).unwrap()
+        );
+
+        let accounts = |state: &serde_json::Value| {
+            serde_json::from_value::<BTreeMap<Address, serde_json::Value>>(state.clone()).unwrap()
+        };
+        let pre = accounts(&trace["result"]["pre"]);
+        let post = accounts(&trace["result"]["post"]);
+        let balance = |balance: u64| serde_json::to_value(U256::from(balance)).unwrap();
+        assert_eq!(pre[&SENDER]["balance"], balance(1_000_000));
+        // the sender paid for the gas and the transferred wei
+        assert_eq!(post[&SENDER]["balance"], balance(1_000_000 - 21_000 - 1));
+        assert_eq!(post[&SENDER]["nonce"], 1);
+    }
+}
@@ -0,0 +1,193 @@
+//! Helpers shared by the invalid block hooks.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::Bytes;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, TraceResult};
+use pretty_assertions::Comparison;
+use reth_chainspec::EthereumHardforks;
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm, Evm, HaltReasonFor};
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{ChainSpecProvider, StateProviderBox, StateProviderFactory};
+use reth_revm::{
+    context_interface::{result::ResultAndState, Transaction},
+    database::StateProviderDatabase,
+    db::CacheDB,
+    DatabaseCommit,
+};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// The database the transactions of the invalid block are traced on.
+pub(crate) type TraceDb = CacheDB<StateProviderDatabase<StateProviderBox>>;
+
+/// Re-executes the transactions of the block on top of the state of its parent, inspecting each
+/// transaction with a fresh [`TracingInspector`].
+///
+/// The closure is called with the inspector and the result of the transaction, before the state
+/// changes of the transaction are committed to the database.
+pub(crate) fn trace_block<P, Evm, T>(
+    provider: &P,
+    evm_config: &Evm,
+    parent_header: &SealedHeader<<Evm::Primitives as NodePrimitives>::BlockHeader>,
+    block: &RecoveredBlock<<Evm::Primitives as NodePrimitives>::Block>,
+    config: TracingInspectorConfig,
+    mut f: impl FnMut(
+        TracingInspector,
+        &ResultAndState<HaltReasonFor<Evm>>,
+        &TraceDb,
+    ) -> eyre::Result<T>,
+) -> eyre::Result<Vec<T>>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    Evm: ConfigureEvm,
+{
+    let state = provider.state_by_block_hash(parent_header.hash())?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+    let evm_env = evm_config.evm_env(block.header());
+
+    // apply the system calls of the block before any of the transactions
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+    SystemCaller::new(provider.chain_spec())
+        .apply_pre_execution_changes(block.header(), &mut evm)?;
+    drop(evm);
+
+    let mut results = Vec::with_capacity(block.transaction_count());
+    for tx in block.transactions_recovered() {
+        let tx_env = evm_config.tx_env(tx);
+        let gas_limit = tx_env.gas_limit();
+
+        let mut inspector = TracingInspector::new(config);
+        let res = evm_config
+            .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+            .transact(tx_env)?;
+        inspector.set_transaction_gas_limit(gas_limit);
+
+        results.push(f(inspector, &res, &db)?);
+        db.commit(res.state);
+    }
+
+    Ok(results)
+}
+
+/// Traces the block on the healthy node and writes the diff to the output directory if the traces
+/// don't match the re-executed ones.
+///
+/// The healthy node executes the exact same block on top of its own state of the parent, so any
+/// difference points to the execution of the invalid block.
+pub(crate) fn compare_with_healthy_node<B: Block>(
+    healthy_node_client: &jsonrpsee::http_client::HttpClient,
+    output_directory: &Path,
+    block: &RecoveredBlock<B>,
+    kind: &str,
+    opts: GethDebugTracingOptions,
+    re_executed: &[TraceResult],
+    re_executed_path: &Path,
+) -> eyre::Result<()> {
+    let rlp_block = Bytes::from(alloy_rlp::encode(block.sealed_block()));
+    let healthy_node_traces = futures::executor::block_on(async move {
+        DebugApiClient::<()>::debug_trace_block(healthy_node_client, rlp_block, Some(opts)).await
+    })?;
+
+    let healthy_path = save_file(
+        output_directory,
+        format!("{}_{}.{kind}.healthy.json", block.number(), block.hash()),
+        &healthy_node_traces,
+    )?;
+
+    if re_executed != healthy_node_traces.as_slice() {
+        let diff_path = save_diff(
+            output_directory,
+            format!("{}_{}.{kind}.diff", block.number(), block.hash()),
+            &re_executed,
+            &healthy_node_traces.as_slice(),
+        )?;
+        warn!(
+            target: "engine::invalid_block_hooks",
+            kind,
+            diff_path = %diff_path.display(),
+            re_executed_path = %re_executed_path.display(),
+            healthy_path = %healthy_path.display(),
+            "Trace mismatch against healthy node"
+        );
+    }
+
+    Ok(())
+}
+
+/// Saves the diff of two values into a file with the given name in the output directory.
+pub(crate) fn save_diff<T: PartialEq + Debug>(
+    output_directory: &Path,
+    filename: String,
+    original: &T,
+    new: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    let diff = Comparison::new(original, new);
+    File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+    Ok(path)
+}
+
+/// Saves the value as JSON into a file with the given name in the output directory.
+pub(crate) fn save_file<T: Serialize>(
+    output_directory: &Path,
+    filename: String,
+    value: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{Address, Signature, TxKind, B256, U256};
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction, TransactionSigned};
+    use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    /// The sender of the transaction of the [`invalid_block`].
+    pub(crate) const SENDER: Address = Address::with_last_byte(1);
+
+    /// Returns a provider with the funded [`SENDER`], the parent header and a block with a
+    /// transfer of the sender and a bad state root on top of it.
+    pub(crate) fn invalid_block() -> (MockEthProvider, SealedHeader, RecoveredBlock<Block>) {
+        let provider = MockEthProvider::default();
+        provider.add_account(SENDER, ExtendedAccount::new(0, U256::from(1_000_000)));
+
+        let parent =
+            SealedHeader::seal_slow(Header { gas_limit: 30_000_000, ..Default::default() });
+        let transaction = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy {
+                gas_price: 1,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(2)),
+                value: U256::from(1),
+                ..Default::default()
+            }),
+            Signature::test_signature(),
+        );
+        let block = Block {
+            header: Header {
+                parent_hash: parent.hash(),
+                number: 1,
+                gas_limit: 30_000_000,
+                state_root: B256::random(),
+                ..Default::default()
+            },
+            body: BlockBody { transactions: vec![transaction], ..Default::default() },
+        };
+
+        (provider, parent, RecoveredBlock::new_unhashed(block, vec![SENDER]))
+    }
+}
@@ -1,7 +1,8 @@
+use crate::trace::{save_diff, save_file};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{keccak256, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use pretty_assertions::Comparison;
+use reth_bad_blocks::BadBlockRegistry;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_engine_primitives::InvalidBlockHook;
 use reth_evm::execute::{BlockExecutorProvider, Executor};
@@ -12,7 +13,7 @@ use reth_rpc_api::DebugApiClient;
 use reth_tracing::tracing::warn;
 use reth_trie::{updates::TrieUpdates, HashedStorage};
 use serde::Serialize;
-use std::{fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{fmt::Debug, path::PathBuf};
 
 /// Generates a witness for the given block and saves it to a file.
 #[derive(Debug)]
@@ -26,6 +27,8 @@ pub struct InvalidBlockWitnessHook<P, E> {
     output_directory: PathBuf,
     /// The healthy node client to compare the witness against.
     healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    /// The registry to attach the witness to the recorded bad block in.
+    bad_block_registry: Option<BadBlockRegistry>,
 }
 
 impl<P, E> InvalidBlockWitnessHook<P, E> {
@@ -36,7 +39,13 @@ impl<P, E> InvalidBlockWitnessHook<P, E> {
         output_directory: PathBuf,
         healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
     ) -> Self {
-        Self { provider, executor, output_directory, healthy_node_client }
+        Self { provider, executor, output_directory, healthy_node_client, bad_block_registry: None }
+    }
+
+    /// Attaches the generated witnesses to the blocks recorded in the given registry.
+    pub fn with_bad_block_registry(mut self, bad_block_registry: BadBlockRegistry) -> Self {
+        self.bad_block_registry = Some(bad_block_registry);
+        self
     }
 }
 
@@ -131,11 +140,17 @@ where
             format!("{}_{}.witness.re_executed.json", block.number(), block.hash()),
             &response,
         )?;
+        if let Some(registry) = &self.bad_block_registry {
+            registry.set_witness(block.hash(), response.clone());
+        }
         if let Some(healthy_node_client) = &self.healthy_node_client {
             // Compare the witness against the healthy node.
             let healthy_node_witness = futures::executor::block_on(async move {
-                DebugApiClient::debug_execution_witness(healthy_node_client, block.number().into())
-                    .await
+                DebugApiClient::<()>::debug_execution_witness(
+                    healthy_node_client,
+                    block.number().into(),
+                )
+                .await
             })?;
 
             let healthy_path = self.save_file(
@@ -243,18 +258,11 @@ where
         original: &T,
         new: &T,
     ) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        let diff = Comparison::new(original, new);
-        File::create(&path)?.write_all(diff.to_string().as_bytes())?;
-
-        Ok(path)
+        save_diff(&self.output_directory, filename, original, new)
     }
 
     fn save_file<T: Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
-
-        Ok(path)
+        save_file(&self.output_directory, filename, value)
     }
 }
 
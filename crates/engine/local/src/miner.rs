@@ -5,21 +5,31 @@ use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
-use reth_engine_primitives::BeaconEngineMessage;
+use reth_chain_state::ExecutedBlockWithTrieUpdates;
+use reth_engine_primitives::{
+    BeaconEngineMessage, BuildExecutedBlock, LocalMinerError, LocalMinerMessage,
+    RebuildExecutedBlock, MAX_MINE_BLOCKS,
+};
+use reth_engine_tree::engine::{EngineApiRequest, FromEngine};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
 };
+use reth_primitives_traits::{NodePrimitives, SealedHeader};
 use reth_provider::BlockReader;
 use reth_transaction_pool::TransactionPool;
 use std::{
     future::Future,
     pin::Pin,
+    sync::mpsc::Sender,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -72,33 +82,66 @@ impl Future for MiningMode {
     }
 }
 
+/// The maximum number of executed blocks kept by the [`LocalMiner`].
+const MAX_EXECUTED_BLOCKS: usize = 64;
+
+/// The primitive types of the blocks built for the given payload types.
+type PrimitivesOf<T> = <<T as PayloadTypes>::BuiltPayload as BuiltPayload>::Primitives;
+
+/// Sender for requests to the engine tree.
+type TreeSender<T> = Sender<
+    FromEngine<EngineApiRequest<T, PrimitivesOf<T>>, <PrimitivesOf<T> as NodePrimitives>::Block>,
+>;
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
-pub struct LocalMiner<T: PayloadTypes, B> {
+pub struct LocalMiner<T: PayloadTypes, B, P> {
+    /// The provider to look up mined blocks.
+    provider: P,
     /// The payload attribute builder for the engine
     payload_attributes_builder: B,
     /// Sender for events to engine.
     to_engine: UnboundedSender<BeaconEngineMessage<T>>,
+    /// Sender for inserting already executed blocks into the engine tree.
+    to_tree: TreeSender<T>,
+    /// Receiver for requests from [`LocalMinerHandle`](reth_engine_primitives::LocalMinerHandle)s.
+    from_handle: UnboundedReceiver<LocalMinerMessage<PrimitivesOf<T>>>,
     /// The mining mode for the engine
     mode: MiningMode,
+    /// The instant mining mode, while it is replaced by interval mining.
+    instant_mode: Option<MiningMode>,
+    /// Whether mining through the mining mode is paused, blocks are then only mined on request.
+    paused: bool,
     /// The payload builder for the engine
     payload_builder: PayloadBuilderHandle<T>,
     /// Timestamp for the next block.
     last_timestamp: u64,
+    /// The exact timestamp of the next block, if set.
+    next_timestamp: Option<u64>,
+    /// Fixed number of seconds between the timestamps of consecutive blocks, if set.
+    timestamp_interval: Option<u64>,
+    /// Number of seconds the clock of the miner is ahead of (or behind) the system time.
+    time_offset: i64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// The latest blocks built by the miner, including the ones that were replaced, used to
+    /// rebuild the head and to revert to replaced blocks.
+    executed_blocks: Vec<ExecutedBlockWithTrieUpdates<PrimitivesOf<T>>>,
 }
 
-impl<T, B> LocalMiner<T, B>
+impl<T, B, P> LocalMiner<T, B, P>
 where
     T: PayloadTypes,
     B: PayloadAttributesBuilder<<T as PayloadTypes>::PayloadAttributes>,
+    P: BlockReader + 'static,
 {
     /// Spawns a new [`LocalMiner`] with the given parameters.
     pub fn spawn_new(
-        provider: impl BlockReader,
+        provider: P,
         payload_attributes_builder: B,
         to_engine: UnboundedSender<BeaconEngineMessage<T>>,
+        to_tree: TreeSender<T>,
+        from_handle: UnboundedReceiver<LocalMinerMessage<PrimitivesOf<T>>>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<T>,
     ) {
@@ -106,12 +149,21 @@ where
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
 
         let miner = Self {
+            provider,
             payload_attributes_builder,
             to_engine,
+            to_tree,
+            from_handle,
             mode,
+            instant_mode: None,
+            paused: false,
             payload_builder,
             last_timestamp: latest_header.timestamp(),
+            next_timestamp: None,
+            timestamp_interval: None,
+            time_offset: 0,
             last_block_hashes: vec![latest_header.hash()],
+            executed_blocks: Vec::new(),
         };
 
         // Spawn the miner
@@ -124,11 +176,13 @@ where
         loop {
             tokio::select! {
                 // Wait for the interval or the pool to receive a transaction
-                _ = &mut self.mode => {
+                _ = &mut self.mode, if !self.paused => {
                     if let Err(e) = self.advance().await {
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // Handle requests from the miner handles
+                Some(msg) = self.from_handle.recv() => self.on_message(msg).await,
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -172,16 +226,49 @@ where
         Ok(())
     }
 
+    /// Returns the timestamp of the next block.
+    fn next_timestamp(&mut self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp.take() {
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        std::cmp::max(self.last_timestamp + 1, now().saturating_add_signed(self.time_offset))
+    }
+
+    /// Records the given block as the new head of the mined chain.
+    fn on_new_block(&mut self, hash: B256, timestamp: u64) {
+        self.last_timestamp = timestamp;
+        self.last_block_hashes.push(hash);
+        // ensure we keep at most 64 blocks
+        if self.last_block_hashes.len() > 64 {
+            self.last_block_hashes =
+                self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
+        }
+    }
+
+    /// Keeps the given block, so that it can be rebuilt or reverted to.
+    fn on_executed_block(&mut self, block: ExecutedBlockWithTrieUpdates<PrimitivesOf<T>>) {
+        self.executed_blocks.push(block);
+        if self.executed_blocks.len() > MAX_EXECUTED_BLOCKS {
+            self.executed_blocks.remove(0);
+        }
+    }
+
+    /// Returns the executed block with the given hash, if it was built by the miner.
+    fn executed_block(&self, hash: B256) -> Option<ExecutedBlockWithTrieUpdates<PrimitivesOf<T>>> {
+        self.executed_blocks
+            .iter()
+            .rev()
+            .find(|block| block.recovered_block().hash() == hash)
+            .cloned()
+    }
+
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
-    /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    /// into the tree, either directly if it was already executed or through newPayload.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let timestamp = self.next_timestamp();
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
@@ -204,26 +291,259 @@ where
             eyre::bail!("No payload")
         };
 
-        let block = payload.block();
+        let hash = payload.block().hash();
 
-        let (tx, rx) = oneshot::channel();
-        let payload = T::block_to_payload(payload.block().clone());
-        self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx })?;
+        if let Some(executed) = payload.executed_block() {
+            // insert the block as it was executed by the payload builder, this keeps the senders
+            // of the transactions, which can't be recovered for impersonated accounts
+            self.on_executed_block(executed.clone());
+            self.to_tree
+                .send(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(executed)))?;
+        } else {
+            let (tx, rx) = oneshot::channel();
+            let payload = T::block_to_payload(payload.block().clone());
+            self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx })?;
 
-        let res = rx.await??;
+            let res = rx.await??;
 
-        if !res.is_valid() {
-            eyre::bail!("Invalid payload")
+            if !res.is_valid() {
+                eyre::bail!("Invalid payload")
+            }
         }
 
-        self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
-        // ensure we keep at most 64 blocks
-        if self.last_block_hashes.len() > 64 {
-            self.last_block_hashes =
-                self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
+        self.on_new_block(hash, timestamp);
+
+        Ok(hash)
+    }
+
+    /// Handles a request from a [`LocalMinerHandle`](reth_engine_primitives::LocalMinerHandle).
+    async fn on_message(&mut self, msg: LocalMinerMessage<PrimitivesOf<T>>) {
+        match msg {
+            LocalMinerMessage::Mine { blocks, interval, tx } => {
+                let _ = tx.send(self.mine(blocks, interval).await);
+            }
+            LocalMinerMessage::InsertBlock { build, tx } => {
+                let _ = tx.send(self.insert_block(build).await);
+            }
+            LocalMinerMessage::ReplaceHead { rebuild, tx } => {
+                let _ = tx.send(self.replace_head(rebuild).await);
+            }
+            LocalMinerMessage::Revert { block_hash, tx } => {
+                let _ = tx.send(self.revert(block_hash).await);
+            }
+            LocalMinerMessage::Automine { tx } => {
+                let _ = tx.send(matches!(self.mode, MiningMode::Instant(_)) && !self.paused);
+            }
+            LocalMinerMessage::SetAutomine { enabled, tx } => {
+                let _ = tx.send(self.set_automine(enabled));
+            }
+            LocalMinerMessage::SetIntervalMining { interval, tx } => {
+                match interval {
+                    Some(interval) => {
+                        let mode =
+                            std::mem::replace(&mut self.mode, MiningMode::interval(interval));
+                        if matches!(mode, MiningMode::Instant(_)) {
+                            self.instant_mode = Some(mode);
+                        }
+                        self.paused = false;
+                    }
+                    None => {
+                        // fall back to mining on request only
+                        if let Some(mode) = self.instant_mode.take() {
+                            self.mode = mode;
+                        }
+                        self.paused = true;
+                    }
+                }
+                let _ = tx.send(());
+            }
+            LocalMinerMessage::SetNextBlockTimestamp { timestamp, tx } => {
+                let res = if timestamp <= self.last_timestamp {
+                    Err(LocalMinerError::TimestampTooLow { timestamp, latest: self.last_timestamp })
+                } else {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerMessage::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add_unsigned(seconds);
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerMessage::SetTime { timestamp, tx } => {
+                let now = now();
+                self.time_offset = timestamp as i64 - now as i64;
+                let _ = tx.send(timestamp.abs_diff(now));
+            }
+            LocalMinerMessage::SetBlockTimestampInterval { interval, tx } => {
+                let previous = std::mem::replace(&mut self.timestamp_interval, interval);
+                let _ = tx.send(previous.is_some());
+            }
+        }
+    }
+
+    /// Mines the given number of blocks and makes the last one canonical.
+    async fn mine(
+        &mut self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<B256>, LocalMinerError> {
+        if blocks > MAX_MINE_BLOCKS {
+            return Err(LocalMinerError::TooManyBlocks(blocks))
+        }
+
+        let mut hashes = Vec::new();
+        for i in 0..blocks {
+            if let Some(interval) = interval.filter(|_| i > 0) {
+                self.next_timestamp = Some(self.last_timestamp + interval);
+            }
+            hashes.push(self.advance().await.map_err(|e| LocalMinerError::Internal(e.into()))?);
+        }
+        self.update_forkchoice_state().await.map_err(|e| LocalMinerError::Internal(e.into()))?;
+        Ok(hashes)
+    }
+
+    /// Builds a block on top of the current head, inserts it into the tree and makes it canonical.
+    async fn insert_block(
+        &mut self,
+        build: BuildExecutedBlock<PrimitivesOf<T>>,
+    ) -> Result<B256, LocalMinerError> {
+        let parent = *self.last_block_hashes.last().expect("at least 1 block exists");
+        let timestamp = self.next_timestamp();
+
+        let block = tokio::task::spawn_blocking(move || build(parent, timestamp))
+            .await
+            .map_err(LocalMinerError::internal)??;
+        let hash = block.recovered_block().hash();
+
+        self.insert_executed_block(block)?;
+        self.on_new_block(hash, timestamp);
+        self.update_forkchoice_state().await.map_err(|e| LocalMinerError::Internal(e.into()))?;
+
+        Ok(hash)
+    }
+
+    /// Rebuilds the current head, inserts the rebuilt block into the tree and makes it canonical
+    /// in place of the current head.
+    ///
+    /// Returns `None` if the current head was not built by the miner.
+    async fn replace_head(
+        &mut self,
+        rebuild: RebuildExecutedBlock<PrimitivesOf<T>>,
+    ) -> Result<Option<B256>, LocalMinerError> {
+        let head = *self.last_block_hashes.last().expect("at least 1 block exists");
+        let Some(executed) = self.executed_block(head) else { return Ok(None) };
+
+        let block = tokio::task::spawn_blocking(move || rebuild(executed))
+            .await
+            .map_err(LocalMinerError::internal)??;
+        let hash = block.recovered_block().hash();
+
+        self.insert_executed_block(block)?;
+        *self.last_block_hashes.last_mut().expect("at least 1 block exists") = hash;
+        self.update_forkchoice_state().await.map_err(|e| LocalMinerError::Internal(e.into()))?;
+
+        Ok(Some(hash))
+    }
+
+    /// Keeps the given block and inserts it into the tree.
+    fn insert_executed_block(
+        &mut self,
+        block: ExecutedBlockWithTrieUpdates<PrimitivesOf<T>>,
+    ) -> Result<(), LocalMinerError> {
+        self.on_executed_block(block.clone());
+        self.to_tree
+            .send(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(block)))
+            .map_err(|_| LocalMinerError::MinerUnavailable)
+    }
+
+    /// Returns the header of the given block if it's canonical.
+    fn canonical_header(
+        &self,
+        block_hash: B256,
+    ) -> Result<Option<SealedHeader<P::Header>>, LocalMinerError> {
+        let Some(header) =
+            self.provider.sealed_header_by_hash(block_hash).map_err(LocalMinerError::internal)?
+        else {
+            return Ok(None)
+        };
+        let canonical_hash =
+            self.provider.block_hash(header.number()).map_err(LocalMinerError::internal)?;
+        Ok((canonical_hash == Some(block_hash)).then_some(header))
+    }
+
+    /// Makes the given block the new head of the chain.
+    ///
+    /// The block is either canonical, or a head that was replaced on top of a canonical block.
+    async fn revert(&mut self, block_hash: B256) -> Result<(), LocalMinerError> {
+        if self.canonical_header(block_hash)?.is_some() {
+            return self.revert_to_canonical(block_hash).await
+        }
+
+        // only canonical blocks can be reverted to, anything else would trigger a download, so a
+        // replaced block is inserted again on top of its parent after reverting to the parent
+        let block =
+            self.executed_block(block_hash).ok_or(LocalMinerError::UnknownBlock(block_hash))?;
+        let parent = block.recovered_block().parent_hash();
+        if self.canonical_header(parent)?.is_none() {
+            return Err(LocalMinerError::UnknownBlock(block_hash))
+        }
+        self.revert_to_canonical(parent).await?;
+
+        let timestamp = block.recovered_block().timestamp();
+        self.insert_executed_block(block)?;
+        self.on_new_block(block_hash, timestamp);
+        self.update_forkchoice_state().await.map_err(|e| LocalMinerError::Internal(e.into()))
+    }
+
+    /// Makes the given canonical block the new head of the chain.
+    async fn revert_to_canonical(&mut self, block_hash: B256) -> Result<(), LocalMinerError> {
+        let header =
+            self.canonical_header(block_hash)?.ok_or(LocalMinerError::UnknownBlock(block_hash))?;
+
+        let last_block_hashes = self.last_block_hashes.clone();
+        match self.last_block_hashes.iter().position(|hash| *hash == block_hash) {
+            Some(idx) => self.last_block_hashes.truncate(idx + 1),
+            None => self.last_block_hashes = vec![block_hash],
+        }
+
+        if let Err(err) = self.update_forkchoice_state().await {
+            self.last_block_hashes = last_block_hashes;
+            return Err(LocalMinerError::Internal(err.into()))
+        }
+        self.last_timestamp = header.timestamp();
+
+        // the persisted blocks of the old chain are removed from disk by the persistence task, the
+        // revert is only visible to readers of the latest state once they are gone
+        while self.provider.last_block_number().map_err(LocalMinerError::internal)? >
+            header.number()
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
         }
 
         Ok(())
     }
+
+    /// Enables or disables mining a block as soon as a transaction reaches the pool.
+    fn set_automine(&mut self, enabled: bool) -> Result<(), LocalMinerError> {
+        if enabled {
+            if let Some(mode) = self.instant_mode.take() {
+                self.mode = mode;
+            } else if !matches!(self.mode, MiningMode::Instant(_)) {
+                return Err(LocalMinerError::AutomineUnavailable)
+            }
+            self.paused = false;
+        } else if matches!(self.mode, MiningMode::Instant(_)) {
+            self.paused = true;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
 }
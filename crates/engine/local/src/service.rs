@@ -16,9 +16,12 @@ use std::{
 
 use crate::miner::{LocalMiner, MiningMode};
 use futures_util::{Stream, StreamExt};
+use reth_bad_blocks::BadBlockRegistry;
 use reth_chainspec::EthChainSpec;
 use reth_consensus::{ConsensusError, FullConsensus};
-use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconEngineMessage, EngineValidator};
+use reth_engine_primitives::{
+    BeaconConsensusEngineEvent, BeaconEngineMessage, EngineValidator, LocalMinerMessage,
+};
 use reth_engine_service::service::EngineMessageStream;
 use reth_engine_tree::{
     chain::{ChainEvent, HandlerEvent},
@@ -39,7 +42,7 @@ use reth_provider::{
 };
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::MetricEventsSender;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tracing::error;
 
 /// Provides a local dev service engine that can be used to drive the
@@ -75,9 +78,11 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        bad_block_registry: BadBlockRegistry,
         sync_metrics_tx: MetricEventsSender,
         to_engine: UnboundedSender<BeaconEngineMessage<N::Payload>>,
         from_engine: EngineMessageStream<N::Payload>,
+        from_miner_handle: UnboundedReceiver<LocalMinerMessage<N::Primitives>>,
         mode: MiningMode,
         payload_attributes_builder: B,
         evm_config: C,
@@ -104,18 +109,22 @@ where
                 persistence_handle,
                 payload_builder.clone(),
                 canonical_in_memory_state,
-                tree_config,
+                // the local miner can revert the chain to an earlier block
+                tree_config.with_unwind_canonical_header(true),
                 invalid_block_hook,
+                bad_block_registry,
                 engine_kind,
                 evm_config,
             );
 
-        let handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
+        let handler = EngineApiRequestHandler::new(to_tree_tx.clone(), from_tree);
 
         LocalMiner::spawn_new(
             blockchain_db,
             payload_attributes_builder,
             to_engine,
+            to_tree_tx,
+            from_miner_handle,
             mode,
             payload_builder,
         );
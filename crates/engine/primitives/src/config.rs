@@ -73,6 +73,11 @@ pub struct TreeConfig {
     max_proof_task_concurrency: u64,
     /// Number of reserved CPU cores for non-reth processes
     reserved_cpu_cores: usize,
+    /// Whether a forkchoice update to an ancestor of the canonical head unwinds the canonical
+    /// chain to that ancestor.
+    ///
+    /// This is only useful for dev chains that need to revert to an earlier block.
+    unwind_canonical_header: bool,
 }
 
 impl Default for TreeConfig {
@@ -90,6 +95,7 @@ impl Default for TreeConfig {
             has_enough_parallelism: has_enough_parallelism(),
             max_proof_task_concurrency: DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
+            unwind_canonical_header: false,
         }
     }
 }
@@ -110,6 +116,7 @@ impl TreeConfig {
         has_enough_parallelism: bool,
         max_proof_task_concurrency: u64,
         reserved_cpu_cores: usize,
+        unwind_canonical_header: bool,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -124,6 +131,7 @@ impl TreeConfig {
             has_enough_parallelism,
             max_proof_task_concurrency,
             reserved_cpu_cores,
+            unwind_canonical_header,
         }
     }
 
@@ -184,6 +192,12 @@ impl TreeConfig {
         self.cross_block_cache_size
     }
 
+    /// Returns whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain to that ancestor.
+    pub const fn unwind_canonical_header(&self) -> bool {
+        self.unwind_canonical_header
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -272,6 +286,13 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain to that ancestor.
+    pub const fn with_unwind_canonical_header(mut self, unwind_canonical_header: bool) -> Self {
+        self.unwind_canonical_header = unwind_canonical_header;
+        self
+    }
+
     /// Whether or not to use state root task
     pub const fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
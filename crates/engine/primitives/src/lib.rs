@@ -39,6 +39,9 @@ pub use event::*;
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
+mod miner;
+pub use miner::*;
+
 pub mod config;
 pub use config::*;
 
@@ -0,0 +1,298 @@
+//! Types to control the local miner of a dev chain.
+
+use alloc::{boxed::Box, vec::Vec};
+use alloy_primitives::B256;
+use core::{fmt, time::Duration};
+use reth_chain_state::ExecutedBlockWithTrieUpdates;
+use reth_primitives_traits::NodePrimitives;
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// The maximum number of blocks that can be mined with a single request.
+pub const MAX_MINE_BLOCKS: u64 = 10_000;
+
+/// Builds an already executed block on top of the given parent block hash, with the given
+/// timestamp.
+pub type BuildExecutedBlock<N> =
+    Box<dyn FnOnce(B256, u64) -> Result<ExecutedBlockWithTrieUpdates<N>, LocalMinerError> + Send>;
+
+/// Rebuilds the given executed head block into the block that replaces it.
+pub type RebuildExecutedBlock<N> = Box<
+    dyn FnOnce(
+            ExecutedBlockWithTrieUpdates<N>,
+        ) -> Result<ExecutedBlockWithTrieUpdates<N>, LocalMinerError>
+        + Send,
+>;
+
+/// Represents error cases when controlling the local miner.
+#[derive(Debug, thiserror::Error)]
+pub enum LocalMinerError {
+    /// Thrown when the local miner task is unavailable/stopped.
+    #[error("local miner task stopped")]
+    MinerUnavailable,
+    /// Thrown when the requested block is not part of the mined chain.
+    #[error("block {0} is not part of the canonical chain")]
+    UnknownBlock(B256),
+    /// Thrown when the requested timestamp is not greater than the timestamp of the latest block.
+    #[error("timestamp {timestamp} must be greater than the latest block timestamp {latest}")]
+    TimestampTooLow {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the latest block.
+        latest: u64,
+    },
+    /// Thrown when more than [`MAX_MINE_BLOCKS`] blocks are requested to be mined at once.
+    #[error("cannot mine {0} blocks at once, the maximum is {MAX_MINE_BLOCKS}")]
+    TooManyBlocks(u64),
+    /// Thrown when automine is requested but the node does not mine on new transactions.
+    #[error("automine is not available, the node was started with interval mining")]
+    AutomineUnavailable,
+    /// An internal error occurred.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl LocalMinerError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
+
+/// A message to the local miner that advances a dev chain.
+pub enum LocalMinerMessage<N: NodePrimitives> {
+    /// Mines the given number of blocks, regardless of the mining mode.
+    Mine {
+        /// The number of blocks to mine.
+        blocks: u64,
+        /// The number of seconds between the timestamps of the mined blocks.
+        interval: Option<u64>,
+        /// The sender for returning the hashes of the mined blocks.
+        tx: oneshot::Sender<Result<Vec<B256>, LocalMinerError>>,
+    },
+    /// Builds a block on top of the latest block and makes it canonical.
+    InsertBlock {
+        /// Builds the block.
+        build: BuildExecutedBlock<N>,
+        /// The sender for returning the hash of the inserted block.
+        tx: oneshot::Sender<Result<B256, LocalMinerError>>,
+    },
+    /// Rebuilds the latest block and makes the rebuilt block the new head of the chain, in place
+    /// of the latest block.
+    ReplaceHead {
+        /// Rebuilds the block.
+        rebuild: RebuildExecutedBlock<N>,
+        /// The sender for returning the hash of the new head, or `None` if the latest block was
+        /// not built by the miner and can't be rebuilt.
+        tx: oneshot::Sender<Result<Option<B256>, LocalMinerError>>,
+    },
+    /// Makes the given ancestor of the latest block, or a block it replaced, the new head of the
+    /// chain.
+    Revert {
+        /// The hash of the block to revert to.
+        block_hash: B256,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), LocalMinerError>>,
+    },
+    /// Returns whether a block is mined as soon as a transaction reaches the pool.
+    Automine {
+        /// The sender for returning the result.
+        tx: oneshot::Sender<bool>,
+    },
+    /// Enables or disables mining a block as soon as a transaction reaches the pool.
+    SetAutomine {
+        /// Whether to enable automine.
+        enabled: bool,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), LocalMinerError>>,
+    },
+    /// Mines blocks at a fixed interval, or disables interval mining if `None`.
+    SetIntervalMining {
+        /// The interval to mine blocks at.
+        interval: Option<Duration>,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<()>,
+    },
+    /// Sets the exact timestamp of the next block.
+    SetNextBlockTimestamp {
+        /// The timestamp of the next block.
+        timestamp: u64,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), LocalMinerError>>,
+    },
+    /// Moves the clock of the miner forward by the given number of seconds.
+    IncreaseTime {
+        /// The number of seconds to move forward.
+        seconds: u64,
+        /// The sender for returning the total time adjustment in seconds.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Sets the clock of the miner to the given timestamp.
+    SetTime {
+        /// The new timestamp.
+        timestamp: u64,
+        /// The sender for returning the number of seconds between the timestamp and now.
+        tx: oneshot::Sender<u64>,
+    },
+    /// Sets a fixed number of seconds between the timestamps of consecutive blocks, or removes it
+    /// if `None`.
+    SetBlockTimestampInterval {
+        /// The number of seconds between consecutive blocks.
+        interval: Option<u64>,
+        /// The sender for returning whether an interval was previously set.
+        tx: oneshot::Sender<bool>,
+    },
+}
+
+impl<N: NodePrimitives> fmt::Debug for LocalMinerMessage<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mine { blocks, interval, .. } => {
+                f.debug_struct("Mine").field("blocks", blocks).field("interval", interval).finish()
+            }
+            Self::InsertBlock { .. } => f.debug_struct("InsertBlock").finish_non_exhaustive(),
+            Self::ReplaceHead { .. } => f.debug_struct("ReplaceHead").finish_non_exhaustive(),
+            Self::Revert { block_hash, .. } => {
+                f.debug_struct("Revert").field("block_hash", block_hash).finish()
+            }
+            Self::Automine { .. } => f.debug_struct("Automine").finish(),
+            Self::SetAutomine { enabled, .. } => {
+                f.debug_struct("SetAutomine").field("enabled", enabled).finish()
+            }
+            Self::SetIntervalMining { interval, .. } => {
+                f.debug_struct("SetIntervalMining").field("interval", interval).finish()
+            }
+            Self::SetNextBlockTimestamp { timestamp, .. } => {
+                f.debug_struct("SetNextBlockTimestamp").field("timestamp", timestamp).finish()
+            }
+            Self::IncreaseTime { seconds, .. } => {
+                f.debug_struct("IncreaseTime").field("seconds", seconds).finish()
+            }
+            Self::SetTime { timestamp, .. } => {
+                f.debug_struct("SetTime").field("timestamp", timestamp).finish()
+            }
+            Self::SetBlockTimestampInterval { interval, .. } => {
+                f.debug_struct("SetBlockTimestampInterval").field("interval", interval).finish()
+            }
+        }
+    }
+}
+
+/// A clonable sender type that can be used to control the local miner of a dev chain.
+#[derive(Debug)]
+pub struct LocalMinerHandle<N: NodePrimitives> {
+    to_miner: UnboundedSender<LocalMinerMessage<N>>,
+}
+
+impl<N: NodePrimitives> Clone for LocalMinerHandle<N> {
+    fn clone(&self) -> Self {
+        Self { to_miner: self.to_miner.clone() }
+    }
+}
+
+impl<N: NodePrimitives> LocalMinerHandle<N> {
+    /// Creates a new local miner handle.
+    pub const fn new(to_miner: UnboundedSender<LocalMinerMessage<N>>) -> Self {
+        Self { to_miner }
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    ///
+    /// If an interval is given, the timestamps of the blocks are that many seconds apart. At most
+    /// [`MAX_MINE_BLOCKS`] blocks can be mined at once.
+    pub async fn mine(
+        &self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<B256>, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Builds a block on top of the latest block with the given closure and makes it canonical.
+    ///
+    /// Returns the hash of the inserted block.
+    pub async fn insert_block(
+        &self,
+        build: impl FnOnce(B256, u64) -> Result<ExecutedBlockWithTrieUpdates<N>, LocalMinerError>
+            + Send
+            + 'static,
+    ) -> Result<B256, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::InsertBlock { build: Box::new(build), tx }).await?
+    }
+
+    /// Rebuilds the latest block with the given closure and makes the rebuilt block the new head
+    /// of the chain, in place of the latest block.
+    ///
+    /// Returns the hash of the new head, or `None` if the latest block was not built by the miner,
+    /// e.g. the genesis block, and can't be rebuilt.
+    pub async fn replace_head(
+        &self,
+        rebuild: impl FnOnce(
+                ExecutedBlockWithTrieUpdates<N>,
+            ) -> Result<ExecutedBlockWithTrieUpdates<N>, LocalMinerError>
+            + Send
+            + 'static,
+    ) -> Result<Option<B256>, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::ReplaceHead { rebuild: Box::new(rebuild), tx }).await?
+    }
+
+    /// Makes the given ancestor of the latest block, or a block it replaced, the new head of the
+    /// chain.
+    pub async fn revert(&self, block_hash: B256) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::Revert { block_hash, tx }).await?
+    }
+
+    /// Returns whether a block is mined as soon as a transaction reaches the pool.
+    pub async fn automine(&self) -> Result<bool, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::Automine { tx }).await
+    }
+
+    /// Enables or disables mining a block as soon as a transaction reaches the pool.
+    pub async fn set_automine(&self, enabled: bool) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetAutomine { enabled, tx }).await?
+    }
+
+    /// Mines blocks at the given interval, or stops interval mining if `None`.
+    pub async fn set_interval_mining(
+        &self,
+        interval: Option<Duration>,
+    ) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetIntervalMining { interval, tx }).await
+    }
+
+    /// Sets the exact timestamp of the next block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Moves the clock of the miner forward and returns the total time adjustment in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> Result<i64, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Sets the clock of the miner and returns the number of seconds between the timestamp and
+    /// now.
+    pub async fn set_time(&self, timestamp: u64) -> Result<u64, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetTime { timestamp, tx }).await
+    }
+
+    /// Sets a fixed number of seconds between the timestamps of consecutive blocks, or removes it
+    /// if `None`.
+    ///
+    /// Returns whether an interval was previously set.
+    pub async fn set_block_timestamp_interval(
+        &self,
+        interval: Option<u64>,
+    ) -> Result<bool, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetBlockTimestampInterval { interval, tx }).await
+    }
+
+    /// Sends the message created by the given closure to the miner and waits for the response.
+    async fn request<T>(
+        &self,
+        msg: impl FnOnce(oneshot::Sender<T>) -> LocalMinerMessage<N>,
+    ) -> Result<T, LocalMinerError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(msg(tx)).map_err(|_| LocalMinerError::MinerUnavailable)?;
+        rx.await.map_err(|_| LocalMinerError::MinerUnavailable)
+    }
+}
@@ -1,5 +1,6 @@
 use futures::{Stream, StreamExt};
 use pin_project::pin_project;
+use reth_bad_blocks::BadBlockRegistry;
 use reth_chainspec::EthChainSpec;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconEngineMessage, EngineValidator};
@@ -88,6 +89,7 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        bad_block_registry: BadBlockRegistry,
         sync_metrics_tx: MetricEventsSender,
         evm_config: C,
     ) -> Self
@@ -116,6 +118,7 @@ where
                 canonical_in_memory_state,
                 tree_config,
                 invalid_block_hook,
+                bad_block_registry,
                 engine_kind,
                 evm_config,
             );
@@ -224,6 +227,7 @@ mod tests {
             engine_payload_validator,
             TreeConfig::default(),
             Box::new(NoopInvalidBlockHook::default()),
+            BadBlockRegistry::default(),
             sync_metrics_tx,
             evm_config,
         );
@@ -1,18 +1,21 @@
 //! Implements a state provider that has a shared cache in front of it.
-use alloy_primitives::{Address, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Address, StorageKey, StorageValue, B256,
+};
 use metrics::Gauge;
 use mini_moka::sync::CacheBuilder;
 use reth_errors::ProviderResult;
 use reth_metrics::Metrics;
 use reth_primitives_traits::{Account, Bytecode};
 use reth_provider::{
-    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
-    StateRootProvider, StorageRootProvider,
+    AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+    StateProofProvider, StateProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_revm::db::BundleState;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedAccountRange, HashedPostState, HashedStorage,
+    HashedStorageRange, MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 use revm_primitives::map::DefaultHashBuilder;
 use std::time::Duration;
@@ -276,6 +279,39 @@ impl<S: HashedPostStateProvider> HashedPostStateProvider for CachedStateProvider
     }
 }
 
+impl<S: HashedStateRangeProvider> HashedStateRangeProvider for CachedStateProvider<S> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedAccountRange> {
+        self.state_provider.hashed_account_range(hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        self.state_provider.hashed_storage_range(hashed_state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>> {
+        self.state_provider.account_preimages(hashed_addresses)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>> {
+        self.state_provider.storage_preimages(address, hashed_slots)
+    }
+}
+
 /// The set of caches that are used in the [`CachedStateProvider`].
 #[derive(Debug, Clone)]
 pub(crate) struct ProviderCaches {
@@ -19,6 +19,7 @@ use alloy_rpc_types_engine::{
 use error::{InsertBlockError, InsertBlockErrorKind, InsertBlockFatalError};
 use payload_processor::sparse_trie::StateRootComputeOutcome;
 use persistence_state::CurrentPersistenceAction;
+use reth_bad_blocks::{BadBlock, BadBlockRegistry};
 use reth_chain_state::{
     CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates,
     MemoryOverlayStateProvider, NewCanonicalChain,
@@ -38,14 +39,15 @@ use reth_primitives_traits::{
     Block, GotExpected, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
 };
 use reth_provider::{
-    providers::ConsistentDbView, BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory,
-    ExecutionOutcome, HashedPostStateProvider, ProviderError, StateCommitmentProvider,
-    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
+    providers::ConsistentDbView, BlockNumReader, BlockReader, ChangeSetRangeReader,
+    DatabaseProviderFactory, ExecutionOutcome, HashedPostStateProvider, ProviderError,
+    StateCommitmentProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ControlFlow;
 use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
-use reth_trie_db::{DatabaseHashedPostState, StateCommitment};
+use reth_trie_db::{ChangesetHashedPostState, StateCommitment};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use std::{
     collections::{btree_map, hash_map, BTreeMap, VecDeque},
@@ -601,6 +603,8 @@ where
     metrics: EngineApiMetrics,
     /// An invalid block hook.
     invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+    /// Registry of the blocks that failed validation.
+    bad_block_registry: BadBlockRegistry,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
     /// The type responsible for processing new payloads
@@ -628,6 +632,7 @@ where
             .field("config", &self.config)
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
+            .field("bad_block_registry", &self.bad_block_registry)
             .field("engine_kind", &self.engine_kind)
             .finish()
     }
@@ -645,7 +650,7 @@ where
         + Clone
         + 'static,
     <P as DatabaseProviderFactory>::Provider:
-        BlockReader<Block = N::Block, Header = N::BlockHeader>,
+        BlockReader<Block = N::Block, Header = N::BlockHeader> + ChangeSetRangeReader,
     E: BlockExecutorProvider<Primitives = N>,
     C: ConfigureEvm<Primitives = N> + 'static,
     T: PayloadTypes,
@@ -690,6 +695,7 @@ where
             metrics: Default::default(),
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            bad_block_registry: BadBlockRegistry::default(),
             engine_kind,
             payload_processor,
         }
@@ -700,6 +706,11 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the registry the blocks that fail validation are recorded in.
+    fn set_bad_block_registry(&mut self, bad_block_registry: BadBlockRegistry) {
+        self.bad_block_registry = bad_block_registry;
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -716,6 +727,7 @@ where
         canonical_in_memory_state: CanonicalInMemoryState<N>,
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+        bad_block_registry: BadBlockRegistry,
         kind: EngineApiKind,
         evm_config: C,
     ) -> (Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>, UnboundedReceiver<EngineApiEvent<N>>)
@@ -751,6 +763,7 @@ where
             evm_config,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_bad_block_registry(bad_block_registry);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
@@ -1188,6 +1201,26 @@ where
                 }
             }
 
+            // Dev chains may explicitly revert to an earlier block, in which case the ancestor
+            // becomes the new canonical head.
+            if self.config.unwind_canonical_header() {
+                self.unwind_canonical_chain(&canonical_header)?;
+
+                // update the safe and finalized blocks and ensure their values are valid
+                if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+                    // safe or finalized hashes are invalid
+                    return Ok(TreeOutcome::new(outcome))
+                }
+
+                if let Some(attr) = attrs {
+                    let updated =
+                        self.process_payload_attributes(attr, &canonical_header, state, version);
+                    return Ok(TreeOutcome::new(updated))
+                }
+
+                return Ok(valid_outcome(state.head_block_hash))
+            }
+
             // 2. Client software MAY skip an update of the forkchoice state and MUST NOT begin a
             //    payload build process if `forkchoiceState.headBlockHash` references a `VALID`
             //    ancestor of the head of canonical chain, i.e. the ancestor passed payload
@@ -2184,6 +2217,50 @@ where
         ));
     }
 
+    /// Makes the given ancestor of the canonical head the new canonical head.
+    ///
+    /// Canonical state listeners are notified with a reorg without new blocks. The unwound blocks
+    /// are kept in the tree, blocks that were already persisted are removed from disk once the
+    /// persistence task detects the disk reorg, see [`Self::advance_persistence`].
+    fn unwind_canonical_chain(
+        &mut self,
+        new_head: &SealedHeader<N::BlockHeader>,
+    ) -> ProviderResult<()> {
+        let start = Instant::now();
+        let old_head = self.state.tree_state.current_canonical_head;
+        debug!(target: "engine::tree", old_head = ?old_head, new_head = ?new_head.num_hash(), "unwinding canonical chain");
+
+        // collect the unwound blocks, including the ones that were already persisted
+        let mut old = Vec::new();
+        let mut current = old_head.hash;
+        for _ in new_head.number()..old_head.number {
+            let executed = self
+                .canonical_block_by_hash(current)?
+                .ok_or(ProviderError::BlockHashNotFound(current))?;
+            current = executed.recovered_block().parent_hash();
+            old.push(executed);
+        }
+        old.reverse();
+
+        self.update_reorg_metrics(old.len());
+        self.state.tree_state.set_canonical_head(new_head.num_hash());
+
+        let chain_update = NewCanonicalChain::Reorg { new: Vec::new(), old };
+        let notification = chain_update.to_chain_notification();
+        self.canonical_in_memory_state.update_chain(chain_update);
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+        self.metrics.tree.canonical_chain_height.set(new_head.number() as f64);
+
+        // sends an event to all active listeners about the reverted blocks
+        self.canonical_in_memory_state.notify_canon_state(notification);
+
+        self.emit_event(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(new_head.clone()),
+            start.elapsed(),
+        ));
+        Ok(())
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
@@ -2205,21 +2282,37 @@ where
         }
     }
 
-    /// Invoke the invalid block hook if this is a new invalid block.
+    /// Record the block in the bad block registry and invoke the invalid block hook if this is a
+    /// new invalid block.
+    ///
+    /// The block is recorded before the hook is invoked, so that the hook can attach its
+    /// artifacts to the recorded block.
     fn on_invalid_block(
         &mut self,
         parent_header: &SealedHeader<N::BlockHeader>,
         block: &RecoveredBlock<N::Block>,
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
+        error: &InsertBlockErrorKind,
     ) {
         if self.state.invalid_headers.get(&block.hash()).is_some() {
             // we already marked this block as invalid
             return;
         }
+        self.record_bad_block(block.sealed_block(), error);
         self.invalid_block_hook.on_invalid_block(parent_header, block, output, trie_updates);
     }
 
+    /// Records the block in the bad block registry, unless it is already recorded.
+    fn record_bad_block(&self, block: &SealedBlock<N::Block>, error: impl std::fmt::Display) {
+        self.bad_block_registry.record(BadBlock::new(
+            block.hash(),
+            block.number(),
+            alloy_rlp::encode(block).into(),
+            error,
+        ));
+    }
+
     /// This handles downloaded blocks that are shown to be disconnected from the canonical chain.
     ///
     /// This mainly compares the missing parent of the downloaded block with the current canonical
@@ -2454,9 +2547,10 @@ where
         handle.stop_prewarming_execution();
 
         if let Err(err) = self.consensus.validate_block_post_execution(&block, &output) {
+            let err = err.into();
             // call post-block hook
-            self.on_invalid_block(&parent_block, &block, &output, None);
-            return Err(err.into())
+            self.on_invalid_block(&parent_block, &block, &output, None, &err);
+            return Err(err)
         }
 
         let hashed_state = self.provider.hashed_post_state(&output.state);
@@ -2465,9 +2559,10 @@ where
             .payload_validator
             .validate_block_post_execution_with_hashed_state(&hashed_state, &block)
         {
+            let err = err.into();
             // call post-block hook
-            self.on_invalid_block(&parent_block, &block, &output, None);
-            return Err(err.into())
+            self.on_invalid_block(&parent_block, &block, &output, None, &err);
+            return Err(err)
         }
 
         debug!(target: "engine::tree", block=?block_num_hash, "Calculating block state root");
@@ -2540,12 +2635,19 @@ where
 
         // ensure state root matches
         if state_root != block.header().state_root() {
-            // call post-block hook
-            self.on_invalid_block(&parent_block, &block, &output, Some((&trie_output, state_root)));
-            return Err(ConsensusError::BodyStateRootDiff(
+            let err = ConsensusError::BodyStateRootDiff(
                 GotExpected { got: state_root, expected: block.header().state_root() }.into(),
             )
-            .into())
+            .into();
+            // call post-block hook
+            self.on_invalid_block(
+                &parent_block,
+                &block,
+                &output,
+                Some((&trie_output, state_root)),
+                &err,
+            );
+            return Err(err)
         }
 
         // terminate prewarming task with good state output
@@ -2679,15 +2781,17 @@ where
 
         // Retrieve revert state for historical block.
         let revert_state = if block_number == best_block_number {
-            // We do not check against the `last_block_number` here because
-            // `HashedPostState::from_reverts` only uses the database tables, and not static files.
+            // We do not check against the `last_block_number` here because changesets are only
+            // written up to the best block number.
             debug!(target: "engine::tree", block_number, best_block_number, "Empty revert state");
             HashedPostState::default()
         } else {
-            let revert_state = HashedPostState::from_reverts::<
+            let revert_state = HashedPostState::from_changesets::<
                 <P::StateCommitment as StateCommitment>::KeyHasher,
-            >(provider.tx_ref(), block_number + 1)
-            .map_err(ProviderError::from)?;
+            >(
+                provider.account_changesets_range(block_number + 1..)?,
+                provider.storage_changesets_range(block_number + 1..)?,
+            );
             debug!(
                 target: "engine::tree",
                 block_number,
@@ -2735,6 +2839,8 @@ where
         );
         let latest_valid_hash = self.latest_valid_hash_for_invalid_payload(block.parent_hash())?;
 
+        self.record_bad_block(&block, &validation_err);
+
         // keep track of the invalid header
         self.state.invalid_headers.insert(block.block_with_parent());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
@@ -4651,6 +4757,11 @@ mod tests {
             test_harness.check_invalid_block(block.hash()).await;
         }
 
+        // only the block that failed validation is recorded, not its descendants
+        let bad_blocks = test_harness.tree.bad_block_registry.all();
+        assert_eq!(bad_blocks.len(), 1);
+        assert_eq!(bad_blocks[0].hash, chain_a[chain_a.len() - invalid_index - 1].hash());
+
         // send FCU to make the tip of chain A, expect invalid
         let chain_a_tip_hash = chain_a.last().unwrap().hash();
         test_harness.fcu_to(chain_a_tip_hash, ForkchoiceStatus::Invalid).await;
@@ -11,17 +11,20 @@ use tokio::{
 /// Accesses the network over HTTP.
 pub trait HttpClient {
     /// Makes an HTTP GET request to `url`. Returns a stream of response body bytes.
-    fn get<U: IntoUrl>(
+    fn get<U: IntoUrl + Send + Sync>(
         &self,
         url: U,
-    ) -> impl Future<Output = eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Unpin>>;
+    ) -> impl Future<
+        Output = eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>,
+    > + Send
+           + Sync;
 }
 
 impl HttpClient for Client {
-    async fn get<U: IntoUrl>(
+    async fn get<U: IntoUrl + Send + Sync>(
         &self,
         url: U,
-    ) -> eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Unpin> {
+    ) -> eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin> {
         let response = Self::get(self, url).send().await?;
 
         Ok(response.bytes_stream().map_err(|e| eyre::Error::new(e)))
@@ -74,7 +77,7 @@ impl<Http: HttpClient + Clone> EraClient<Http> {
         if let Ok(mut dir) = fs::read_dir(&self.folder).await {
             while let Ok(Some(entry)) = dir.next_entry().await {
                 if let Some(name) = entry.file_name().to_str() {
-                    if let Some(number) = self.file_name_to_number(name) {
+                    if let Some(number) = file_name_to_number(name) {
                         if max.is_none() || matches!(max, Some(max) if number > max) {
                             max.replace(number);
                         }
@@ -148,36 +151,24 @@ impl<Http: HttpClient + Clone> EraClient<Http> {
 
         Ok(lines.next_line().await?)
     }
+}
 
-    fn file_name_to_number(&self, file_name: &str) -> Option<u64> {
-        file_name.split('-').nth(1).and_then(|v| u64::from_str(v).ok())
-    }
+/// Returns the ordering number of an ERA1 file parsed from its `file_name`.
+pub(crate) fn file_name_to_number(file_name: &str) -> Option<u64> {
+    file_name.split('-').nth(1).and_then(|v| u64::from_str(v).ok())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
     use test_case::test_case;
 
-    impl EraClient<Client> {
-        fn empty() -> Self {
-            Self::new(
-                Client::new(),
-                Url::from_str("file:///").unwrap(),
-                PathBuf::new().into_boxed_path(),
-            )
-        }
-    }
-
     #[test_case("mainnet-00600-a81ae85f.era1", Some(600))]
     #[test_case("mainnet-00000-a81ae85f.era1", Some(0))]
     #[test_case("00000-a81ae85f.era1", None)]
     #[test_case("", None)]
     fn test_file_name_to_number(file_name: &str, expected_number: Option<u64>) {
-        let client = EraClient::empty();
-
-        let actual_number = client.file_name_to_number(file_name);
+        let actual_number = file_name_to_number(file_name);
 
         assert_eq!(actual_number, expected_number);
     }
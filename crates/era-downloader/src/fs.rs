@@ -0,0 +1,62 @@
+use crate::client::file_name_to_number;
+use futures_util::{stream, Stream};
+use std::path::Path;
+
+/// Creates a new ordered asynchronous [`Stream`] of ERA1 files read from `dir`.
+///
+/// The files are ordered by the number in their file name and files ordered before `start_from`
+/// are skipped. Files that do not have the `era1` extension are ignored.
+pub fn read_dir(
+    dir: impl AsRef<Path>,
+    start_from: u64,
+) -> eyre::Result<impl Stream<Item = eyre::Result<Box<Path>>> + Send + Sync + 'static + Unpin> {
+    let mut entries = Vec::new();
+
+    for entry in reth_fs_util::read_dir(dir.as_ref())? {
+        let path = entry?.path();
+
+        if path.extension() != Some("era1".as_ref()) {
+            continue
+        }
+
+        if let Some(number) =
+            path.file_name().and_then(|name| name.to_str()).and_then(file_name_to_number)
+        {
+            if number >= start_from {
+                entries.push((number, path.into_boxed_path()));
+            }
+        }
+    }
+
+    entries.sort_by_key(|(number, _)| *number);
+
+    Ok(stream::iter(entries.into_iter().map(|(_, path)| Ok(path))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_read_dir_orders_and_skips_files() {
+        let dir = tempdir().unwrap();
+        for name in [
+            "mainnet-00002-1a2b3c4d.era1",
+            "mainnet-00000-5ec1ffb8.era1",
+            "mainnet-00001-a5364e9a.era1",
+            "index.html",
+        ] {
+            std::fs::write(dir.path().join(name), []).unwrap();
+        }
+
+        let files = read_dir(dir.path(), 1).unwrap().collect::<Vec<_>>().await;
+        let files = files
+            .into_iter()
+            .map(|file| file.unwrap().file_name().unwrap().to_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(files, ["mainnet-00001-a5364e9a.era1", "mainnet-00002-1a2b3c4d.era1"]);
+    }
+}
@@ -22,11 +22,12 @@ use std::{
 pub struct EraStreamConfig {
     max_files: usize,
     max_concurrent_downloads: usize,
+    start_from: u64,
 }
 
 impl Default for EraStreamConfig {
     fn default() -> Self {
-        Self { max_files: 5, max_concurrent_downloads: 3 }
+        Self { max_files: 5, max_concurrent_downloads: 3, start_from: 0 }
     }
 }
 
@@ -42,6 +43,14 @@ impl EraStreamConfig {
         self.max_concurrent_downloads = max_concurrent_downloads;
         self
     }
+
+    /// The index of the first ERA1 file to download, skipping the files ordered before it.
+    ///
+    /// Files already present in the download directory take precedence if they are further.
+    pub const fn start_from(mut self, start_from: u64) -> Self {
+        self.start_from = start_from;
+        self
+    }
 }
 
 /// An asynchronous stream of ERA1 files.
@@ -69,6 +78,7 @@ impl<Http> EraStream<Http> {
                 recover_index: Box::pin(async move { 0 }),
                 state: Default::default(),
                 max_files: config.max_files,
+                start_from: config.start_from,
                 index: 0,
                 downloading: 0,
             },
@@ -98,7 +108,7 @@ impl<Http: HttpClient + Clone + Send + Sync + 'static + Unpin> Stream for EraStr
     }
 }
 
-type DownloadFuture = Pin<Box<dyn Future<Output = eyre::Result<Box<Path>>>>>;
+type DownloadFuture = Pin<Box<dyn Future<Output = eyre::Result<Box<Path>>> + Send + Sync>>;
 
 struct DownloadStream {
     downloads: FuturesOrdered<DownloadFuture>,
@@ -142,6 +152,7 @@ struct StartingStream<Http> {
     recover_index: Pin<Box<dyn Future<Output = u64> + Send + Sync + 'static>>,
     state: State,
     max_files: usize,
+    start_from: u64,
     index: u64,
     downloading: usize,
 }
@@ -167,7 +178,7 @@ enum State {
 }
 
 impl<Http: HttpClient + Clone + Send + Sync + 'static + Unpin> Stream for StartingStream<Http> {
-    type Item = Pin<Box<dyn Future<Output = eyre::Result<Box<Path>>>>>;
+    type Item = DownloadFuture;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if self.state == State::Initial {
@@ -176,7 +187,7 @@ impl<Http: HttpClient + Clone + Send + Sync + 'static + Unpin> Stream for Starti
 
         if self.state == State::RecoverIndex {
             if let Poll::Ready(index) = self.recover_index.poll_unpin(cx) {
-                self.index = index;
+                self.index = index.max(self.start_from);
                 self.count_files();
             }
         }
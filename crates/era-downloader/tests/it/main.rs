@@ -23,10 +23,13 @@ const MAINNET_1: &[u8] = include_bytes!("../res/mainnet-00001-a5364e9a.era1");
 struct StubClient;
 
 impl HttpClient for StubClient {
-    fn get<U: IntoUrl>(
+    fn get<U: IntoUrl + Send + Sync>(
         &self,
         url: U,
-    ) -> impl Future<Output = eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Unpin>> {
+    ) -> impl Future<
+        Output = eyre::Result<impl Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>,
+    > + Send
+           + Sync {
         let url = url.into_url().unwrap();
 
         async move {
@@ -35,37 +38,37 @@ impl HttpClient for StubClient {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(NIMBUS))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 "https://era1.ethportal.net/" => {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(ETH_PORTAL))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 "https://era1.ethportal.net/mainnet-00000-5ec1ffb8.era1" => {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(MAINNET_0))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 "https://mainnet.era1.nimbus.team/mainnet-00000-5ec1ffb8.era1" => {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(MAINNET_0))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 "https://era1.ethportal.net/mainnet-00001-a5364e9a.era1" => {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(MAINNET_1))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 "https://mainnet.era1.nimbus.team/mainnet-00001-a5364e9a.era1" => {
                     Ok(Box::new(futures::stream::once(Box::pin(async move {
                         Ok(bytes::Bytes::from(MAINNET_1))
                     })))
-                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Unpin>)
+                        as Box<dyn Stream<Item = eyre::Result<Bytes>> + Send + Sync + Unpin>)
                 }
                 v => unimplemented!("Unexpected URL \"{v}\""),
             }
@@ -0,0 +1,55 @@
+//! Short accumulator roots of the canonical era1 files of known networks.
+
+use alloy_consensus::constants::MAINNET_GENESIS_HASH;
+use alloy_primitives::{hex, B256};
+
+/// Short accumulator roots of the mainnet era1 files, hex encoded, one per line, in the order of
+/// their era numbers.
+const MAINNET_SHORT_ROOTS: &str = include_str!("../res/mainnet-short-roots.txt");
+
+/// Returns the short accumulator roots of the canonical era1 files of the chain with the given
+/// genesis hash, in the order of their era numbers, if they're known.
+const fn canonical_short_roots(genesis_hash: B256) -> Option<&'static str> {
+    match genesis_hash {
+        MAINNET_GENESIS_HASH => Some(MAINNET_SHORT_ROOTS),
+        _ => None,
+    }
+}
+
+/// Checks that the era1 file with the given era number and short accumulator root is part of the
+/// canonical history of the chain with the given genesis hash.
+///
+/// Files of chains without a known list of canonical era1 files are accepted.
+pub(crate) fn ensure_canonical(
+    genesis_hash: B256,
+    era: u64,
+    short_root: [u8; 4],
+) -> eyre::Result<()> {
+    let Some(roots) = canonical_short_roots(genesis_hash) else { return Ok(()) };
+    let canonical = usize::try_from(era).ok().and_then(|era| roots.lines().nth(era));
+    eyre::ensure!(
+        canonical == Some(hex::encode(short_root).as_str()),
+        "era {era} with accumulator root {} is not part of the canonical history of the chain",
+        hex::encode(short_root)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mainnet_short_roots() {
+        let roots = canonical_short_roots(MAINNET_GENESIS_HASH).unwrap();
+        assert_eq!(roots.lines().count(), 1897);
+        assert!(roots.lines().all(|root| hex::decode_to_array::<_, 4>(root).is_ok()));
+
+        ensure_canonical(MAINNET_GENESIS_HASH, 0, [0x5e, 0xc1, 0xff, 0xb8]).unwrap();
+        ensure_canonical(MAINNET_GENESIS_HASH, 1896, [0xe6, 0xeb, 0xe5, 0x62]).unwrap();
+        assert!(ensure_canonical(MAINNET_GENESIS_HASH, 1, [0x5e, 0xc1, 0xff, 0xb8]).is_err());
+        assert!(ensure_canonical(MAINNET_GENESIS_HASH, 1897, [0x5e, 0xc1, 0xff, 0xb8]).is_err());
+
+        ensure_canonical(B256::ZERO, 1, [0x5e, 0xc1, 0xff, 0xb8]).unwrap();
+    }
+}
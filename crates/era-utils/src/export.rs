@@ -0,0 +1,296 @@
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, Sealable, TxReceipt};
+use alloy_primitives::BlockNumber;
+use reth_era::{
+    era1_file::{Era1File, Era1Writer},
+    era1_types::{BlockIndex, Era1Group, Era1Id},
+    execution_types::{
+        Accumulator, BlockTuple, CompressedBody, CompressedHeader, CompressedReceipts,
+        HeaderRecord, TotalDifficulty, MAX_BLOCKS_PER_ERA1,
+    },
+};
+use reth_primitives_traits::Block;
+use reth_provider::{BlockReader, ProviderError};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Configuration of an era1 export, see [`export`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportConfig {
+    /// The directory the era1 files are written to.
+    pub dir: PathBuf,
+    /// The first block to export.
+    pub first_block_number: BlockNumber,
+    /// The last block to export.
+    pub last_block_number: BlockNumber,
+    /// The network name used in the names of the files, like `mainnet`.
+    pub network: String,
+    /// The first post-merge block of the chain, if the chain is merged.
+    ///
+    /// Era1 files only hold pre-merge blocks, so the export has to end before this block.
+    pub merge_block: Option<BlockNumber>,
+    /// The first block of the Byzantium hardfork, if the chain activated it after the genesis.
+    ///
+    /// Receipts of earlier blocks are stored with a status instead of their intermediate state
+    /// root, so they can't be exported and the export has to start at or after this block.
+    pub byzantium_block: Option<BlockNumber>,
+}
+
+/// Exports the blocks in the range of `config` from the storage of `provider` into era1 files.
+///
+/// Every file holds the blocks of the range that belong to one era of [`MAX_BLOCKS_PER_ERA1`]
+/// blocks. Files with all blocks of their era, or all blocks of the last era before the merge, are
+/// named `<network>-<era-number>-<short-accumulator-root>.era1`. Files with only a part of their
+/// era are named `<network>-<first-block>-<block-count>.era1` instead.
+///
+/// The export fails up front if the range starts before the Byzantium block of `config`, and for
+/// any block with receipts that don't match the receipts root of its header.
+///
+/// Returns the paths of the written files.
+pub fn export<P>(provider: &P, config: &ExportConfig) -> eyre::Result<Vec<PathBuf>>
+where
+    P: BlockReader,
+{
+    eyre::ensure!(
+        config.first_block_number <= config.last_block_number,
+        "invalid block range {}..={}",
+        config.first_block_number,
+        config.last_block_number
+    );
+    if let Some(merge_block) = config.merge_block {
+        eyre::ensure!(
+            config.last_block_number < merge_block,
+            "block {} is not a pre-merge block, the merge happened at block {merge_block}",
+            config.last_block_number
+        );
+    }
+    if let Some(byzantium_block) = config.byzantium_block {
+        eyre::ensure!(
+            config.first_block_number >= byzantium_block,
+            "blocks {}..{byzantium_block} can't be exported, receipts of pre-Byzantium blocks are \
+             stored without their intermediate state root",
+            config.first_block_number
+        );
+    }
+    reth_fs_util::create_dir_all(&config.dir)?;
+
+    let blocks_per_file = MAX_BLOCKS_PER_ERA1 as u64;
+    let first_era = config.first_block_number / blocks_per_file;
+    let last_era = config.last_block_number / blocks_per_file;
+
+    let mut files = Vec::with_capacity((last_era - first_era + 1) as usize);
+    for era in first_era..=last_era {
+        let start = config.first_block_number.max(era * blocks_per_file);
+        let end = config.last_block_number.min((era + 1) * blocks_per_file - 1);
+
+        let complete = start == era * blocks_per_file &&
+            (end == (era + 1) * blocks_per_file - 1 || Some(end + 1) == config.merge_block);
+
+        let file = export_era(provider, &config.network, start, end, complete)?;
+        let path = config.dir.join(file.id.to_file_name());
+        Era1Writer::create(&path, &file)?;
+
+        info!(target: "era::history::export", path = %path.display(), start, end, "Exported era1 file");
+        files.push(path);
+    }
+
+    Ok(files)
+}
+
+/// Builds the era1 file with the blocks `start..=end`, which must belong to the same era.
+///
+/// The file is only named after its accumulator root if it's `complete`.
+fn export_era<P>(
+    provider: &P,
+    network: &str,
+    start: BlockNumber,
+    end: BlockNumber,
+    complete: bool,
+) -> eyre::Result<Era1File>
+where
+    P: BlockReader,
+{
+    let mut blocks = Vec::with_capacity((end - start + 1) as usize);
+    let mut records = Vec::with_capacity(blocks.capacity());
+
+    for number in start..=end {
+        let block = provider
+            .block_by_number(number)?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let total_difficulty = provider
+            .header_td_by_number(number)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+        let receipts = provider
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| eyre::eyre!("receipts of block {number} not found, they may be pruned"))?
+            .into_iter()
+            .map(TxReceipt::into_with_bloom)
+            .collect::<Vec<_>>();
+
+        let (header, body) = block.split();
+        eyre::ensure!(
+            calculate_receipt_root(&receipts) == header.receipts_root(),
+            "receipts of block {number} do not match its header"
+        );
+        records.push(HeaderRecord::new(header.hash_slow(), total_difficulty));
+
+        blocks.push(BlockTuple::new(
+            CompressedHeader::from_rlp(&alloy_rlp::encode(&header))?,
+            CompressedBody::from_rlp(&alloy_rlp::encode(&body))?,
+            CompressedReceipts::from_encodable(&receipts)?,
+            TotalDifficulty::new(total_difficulty),
+        ));
+    }
+
+    let accumulator = Accumulator::from_header_records(&records)?;
+    let mut id = Era1Id::new(network, start, (end - start + 1) as u32);
+    if complete {
+        id = id.with_hash(accumulator.root[..4].try_into().expect("slice of 4 bytes"));
+    }
+
+    let mut group = Era1Group::new(blocks, accumulator, BlockIndex::new(start, Vec::new()));
+    group.block_index = group.compute_block_index();
+
+    Ok(Era1File::new(group, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        import,
+        test_utils::{create_chain_spec, create_era1_file},
+        EraImportSource,
+    };
+    use alloy_consensus::Eip658Value;
+    use alloy_primitives::B256;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Receipt, TxType};
+    use reth_etl::Collector;
+    use reth_provider::{
+        providers::StaticFileWriter,
+        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+        HeaderProvider, ProviderFactory, StaticFileProviderFactory,
+    };
+    use reth_static_file_types::StaticFileSegment;
+    use tempfile::{tempdir, TempDir};
+
+    /// Imports an era1 file with 3 blocks on top of the genesis, and writes the receipt of the
+    /// transaction in the second block as the execution stage would.
+    async fn import_blocks(
+        status: Eip658Value,
+    ) -> (ProviderFactory<MockNodeTypesWithDB>, TempDir, PathBuf) {
+        let provider_factory = create_test_provider_factory_with_chain_spec(create_chain_spec());
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let dir = tempdir().unwrap();
+        let file = create_era1_file(&genesis, 3, status);
+        let path = dir.path().join(file.id.to_file_name());
+        Era1Writer::create(&path, &file).unwrap();
+
+        let files = EraImportSource::Path(dir.path().to_path_buf()).stream_from_block(1);
+        let mut hash_collector = Collector::new(1024 * 1024, None);
+        import(files, &provider_factory, &mut hash_collector).await.unwrap();
+
+        let static_file_provider = provider_factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+        for number in 1..=3 {
+            writer.increment_block(number).unwrap();
+            if number == 2 {
+                let receipt = Receipt {
+                    tx_type: TxType::Legacy,
+                    success: status.coerce_status(),
+                    cumulative_gas_used: 21_000,
+                    logs: Vec::new(),
+                };
+                writer.append_receipt(0, &receipt).unwrap();
+            }
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        (provider_factory, dir, path)
+    }
+
+    fn config(dir: &TempDir, first: BlockNumber, merge_block: Option<BlockNumber>) -> ExportConfig {
+        ExportConfig {
+            dir: dir.path().to_path_buf(),
+            first_block_number: first,
+            last_block_number: 3,
+            network: "mainnet".to_owned(),
+            merge_block,
+            byzantium_block: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_roundtrip() {
+        let (provider_factory, _import_dir, original_path) =
+            import_blocks(Eip658Value::Eip658(true)).await;
+
+        // The blocks are the last ones before the merge, so the era is complete
+        let export_dir = tempdir().unwrap();
+        let exported =
+            export(&provider_factory.provider().unwrap(), &config(&export_dir, 0, Some(4)))
+                .unwrap();
+
+        assert_eq!(exported, vec![export_dir.path().join(original_path.file_name().unwrap())]);
+        assert_eq!(std::fs::read(&exported[0]).unwrap(), std::fs::read(&original_path).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_export_partial_era() {
+        let (provider_factory, _import_dir, original_path) =
+            import_blocks(Eip658Value::Eip658(true)).await;
+        let provider = provider_factory.provider().unwrap();
+
+        let export_dir = tempdir().unwrap();
+        let exported = export(&provider, &config(&export_dir, 0, None)).unwrap();
+        assert_eq!(exported, vec![export_dir.path().join("mainnet-0-4.era1")]);
+        assert_eq!(std::fs::read(&exported[0]).unwrap(), std::fs::read(&original_path).unwrap());
+
+        let exported = export(&provider, &config(&export_dir, 1, Some(4))).unwrap();
+        assert_eq!(exported, vec![export_dir.path().join("mainnet-1-3.era1")]);
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_post_merge_blocks() {
+        let (provider_factory, _import_dir, _) = import_blocks(Eip658Value::Eip658(true)).await;
+
+        let export_dir = tempdir().unwrap();
+        assert!(export(&provider_factory.provider().unwrap(), &config(&export_dir, 0, Some(3)))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_receipts_without_state_root() {
+        let (provider_factory, _import_dir, _) =
+            import_blocks(Eip658Value::PostState(B256::repeat_byte(0x42))).await;
+
+        let export_dir = tempdir().unwrap();
+        assert!(export(&provider_factory.provider().unwrap(), &config(&export_dir, 0, Some(4)))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_pre_byzantium_blocks() {
+        let (provider_factory, _import_dir, _) =
+            import_blocks(Eip658Value::PostState(B256::repeat_byte(0x42))).await;
+        let provider = provider_factory.provider().unwrap();
+
+        let export_dir = tempdir().unwrap();
+        let pre_byzantium =
+            ExportConfig { byzantium_block: Some(2), ..config(&export_dir, 1, Some(4)) };
+        let err = export(&provider, &pre_byzantium).unwrap_err();
+        assert!(err.to_string().starts_with("blocks 1..2 can't be exported"), "{err}");
+        assert_eq!(std::fs::read_dir(export_dir.path()).unwrap().count(), 0);
+
+        // Block 2 holds the only transaction, so its receipt has no intermediate state root
+        let post_byzantium =
+            ExportConfig { byzantium_block: Some(2), ..config(&export_dir, 3, Some(4)) };
+        assert_eq!(
+            export(&provider, &post_byzantium).unwrap(),
+            vec![export_dir.path().join("mainnet-3-1.era1")]
+        );
+    }
+}
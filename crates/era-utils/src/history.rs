@@ -0,0 +1,465 @@
+use crate::{canonical::ensure_canonical, EraFile};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, ReceiptEnvelope};
+use alloy_primitives::{hex, BlockHash, BlockNumber, U256};
+use futures_util::{Stream, StreamExt};
+use reth_db_api::{
+    cursor::DbCursorRW, table::Value, tables, transaction::DbTxMut, RawKey, RawTable, RawValue,
+};
+use reth_era::{
+    era1_file::{Era1File, Era1Reader},
+    execution_types::{Accumulator, DecodeCompressed, HeaderRecord},
+};
+use reth_etl::Collector;
+use reth_primitives_traits::{BlockBody, FullBlockHeader, NodePrimitives, SealedHeader};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider, StaticFileWriter},
+    writer::UnifiedStorageWriter,
+    BlockHashReader, BlockWriter, DBProvider, DatabaseProviderFactory, HeaderProvider,
+    ProviderError, ProviderFactory, ProviderResult, StageCheckpointWriter,
+    StaticFileProviderFactory, StorageLocation,
+};
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_static_file_types::StaticFileSegment;
+use std::path::Path;
+use tracing::info;
+
+/// A block decoded from an era1 file.
+#[derive(Debug)]
+pub struct EraBlock<H, B> {
+    /// The sealed block header.
+    pub header: SealedHeader<H>,
+    /// The block body.
+    pub body: B,
+    /// The receipts of the block transactions, as encoded in the file.
+    ///
+    /// Receipts of pre-Byzantium blocks carry the intermediate state root instead of a status.
+    pub receipts: Vec<ReceiptEnvelope>,
+    /// The total difficulty of the chain up to and including this block.
+    pub total_difficulty: U256,
+}
+
+/// Decodes all blocks of `file`, verifying the file and every block in it.
+///
+/// The following is checked:
+/// - the block index matches the layout of the file
+/// - the headers are numbered sequentially, starting with the first block of the index
+/// - the headers are linked by their parent hashes and total difficulties
+/// - the accumulator matches the hashes and total difficulties of the headers, and the short
+///   accumulator root in the name of the file, if the [`Era1Id`](reth_era::era1_types::Era1Id) of
+///   the file has one
+/// - the bodies match the transactions and ommers roots of their headers
+/// - the receipts match the receipts roots of their headers
+pub fn decode_file<H, B>(file: &Era1File) -> eyre::Result<Vec<EraBlock<H, B>>>
+where
+    H: FullBlockHeader,
+    B: BlockBody,
+{
+    let group = &file.group;
+    eyre::ensure!(!group.blocks.is_empty(), "era1 file {} has no blocks", file.id.to_file_name());
+    eyre::ensure!(
+        group.block_index.offsets == group.compute_block_index().offsets,
+        "block index of era1 file {} does not match its blocks",
+        file.id.to_file_name()
+    );
+
+    let mut blocks: Vec<EraBlock<H, B>> = Vec::with_capacity(group.blocks.len());
+    let mut records = Vec::with_capacity(group.blocks.len());
+
+    for (number, tuple) in (group.block_index.starting_number..).zip(&group.blocks) {
+        let header = SealedHeader::seal_slow(tuple.header.decode::<H>()?);
+        let total_difficulty = tuple.total_difficulty.value;
+
+        eyre::ensure!(
+            header.number() == number,
+            "expected header of block {number}, got {}",
+            header.number()
+        );
+        if let Some(parent) = blocks.last() {
+            eyre::ensure!(
+                header.parent_hash() == parent.header.hash(),
+                "header of block {number} is not linked to its parent"
+            );
+            eyre::ensure!(
+                total_difficulty == parent.total_difficulty + header.difficulty(),
+                "invalid total difficulty of block {number}"
+            );
+        }
+
+        let body = tuple.body.decode::<B>()?;
+        eyre::ensure!(
+            body.calculate_tx_root() == header.transactions_root(),
+            "transactions of block {number} do not match its header"
+        );
+        if let Some(ommers_root) = body.calculate_ommers_root() {
+            eyre::ensure!(
+                ommers_root == header.ommers_hash(),
+                "ommers of block {number} do not match their header"
+            );
+        }
+
+        let receipts = tuple.receipts.decode::<Vec<ReceiptEnvelope>>()?;
+        eyre::ensure!(
+            receipts.len() == body.transactions().len(),
+            "expected {} receipts for block {number}, got {}",
+            body.transactions().len(),
+            receipts.len()
+        );
+        eyre::ensure!(
+            calculate_receipt_root(&receipts) == header.receipts_root(),
+            "receipts of block {number} do not match its header"
+        );
+
+        records.push(HeaderRecord::new(header.hash(), total_difficulty));
+        blocks.push(EraBlock { header, body, receipts, total_difficulty });
+    }
+
+    let accumulator = Accumulator::from_header_records(&records)?;
+    eyre::ensure!(
+        accumulator.root == group.accumulator.root,
+        "accumulator of era1 file {} does not match its blocks",
+        file.id.to_file_name()
+    );
+    if let Some(short_root) = file.id.hash {
+        eyre::ensure!(
+            accumulator.root[..4] == short_root,
+            "accumulator of era1 file {} does not match the root in its name",
+            file.id.to_file_name()
+        );
+    }
+
+    Ok(blocks)
+}
+
+/// Imports the blocks of the era1 file at `path` which extend the chain in storage, up to
+/// `to_block`.
+///
+/// The file must be named `<network>-<era-number>-<short-accumulator-root>.era1`, and is only
+/// imported if its accumulator matches the root in its name. Files of mainnet must also be on the
+/// list of its canonical era1 files.
+///
+/// Headers and bodies are appended to static files. Receipts are checked against their headers,
+/// but not imported: receipts of pre-Byzantium blocks carry the intermediate state root, which
+/// stored receipts have no field for, and the execution stage drops any receipts in static files
+/// ahead of its checkpoint before it recreates them. The hashes of the imported
+/// headers are inserted into `hash_collector`, to be written into the database by
+/// [`build_index`].
+///
+/// Returns the highest block number in storage after the import.
+pub fn import_file<P, N>(
+    provider: &P,
+    path: &Path,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    to_block: BlockNumber,
+) -> eyre::Result<BlockNumber>
+where
+    P: DBProvider + StaticFileProviderFactory<Primitives = N> + BlockWriter<Block = N::Block>,
+    N: NodePrimitives<BlockHeader: Value>,
+{
+    let static_file_provider = provider.static_file_provider();
+    let height = static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .unwrap_or_default();
+
+    let (network, era, short_root) = parse_file_name(path).ok_or_else(|| {
+        eyre::eyre!(
+            "era1 file {} is not named <network>-<era-number>-<short-accumulator-root>.era1",
+            path.display()
+        )
+    })?;
+    let mut file = Era1Reader::open(path, network)?;
+    file.id = file.id.with_hash(short_root);
+    eyre::ensure!(!file.group.blocks.is_empty(), "era1 file {} has no blocks", path.display());
+
+    eyre::ensure!(
+        file.id.era_number() == era,
+        "era1 file {} contains blocks of era {}",
+        path.display(),
+        file.id.era_number()
+    );
+    let genesis_hash = static_file_provider
+        .block_hash(0)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(0.into()))?;
+    ensure_canonical(genesis_hash, era, short_root)?;
+
+    let range = file.block_range();
+    if *range.end() <= height || *range.start() > to_block {
+        return Ok(height)
+    }
+    eyre::ensure!(
+        *range.start() <= height + 1,
+        "era1 file {} starts at block {}, expected a file with block {}",
+        path.display(),
+        range.start(),
+        height + 1
+    );
+    eyre::ensure!(
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Transactions) ==
+            Some(height),
+        "block bodies in storage are behind the headers, unable to import era1 file {}",
+        path.display()
+    );
+
+    let blocks = decode_file::<N::BlockHeader, N::BlockBody>(&file)?
+        .into_iter()
+        .filter(|block| block.header.number() > height && block.header.number() <= to_block)
+        .collect::<Vec<_>>();
+
+    let Some(first) = blocks.first() else { return Ok(height) };
+    ensure_extends_chain(&static_file_provider, height, first)?;
+
+    let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+    let mut bodies = Vec::with_capacity(blocks.len());
+    let mut last = height;
+
+    for EraBlock { header, body, total_difficulty, .. } in blocks {
+        let (header, hash) = header.split();
+        last = header.number();
+
+        headers_writer.append_header(&header, total_difficulty, &hash)?;
+        hash_collector.insert(hash, last)?;
+
+        bodies.push((last, Some(body)));
+    }
+
+    drop(headers_writer);
+    provider.append_block_bodies(bodies, StorageLocation::StaticFiles)?;
+
+    Ok(last)
+}
+
+/// Checks that `first`, the first block to import, is the child of the block at `height` in
+/// storage.
+fn ensure_extends_chain<N, B>(
+    static_file_provider: &StaticFileProvider<N>,
+    height: BlockNumber,
+    first: &EraBlock<N::BlockHeader, B>,
+) -> eyre::Result<()>
+where
+    N: NodePrimitives<BlockHeader: Value>,
+{
+    let local_head = static_file_provider
+        .sealed_header(height)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(height.into()))?;
+    let local_td = static_file_provider
+        .header_td_by_number(height)?
+        .ok_or(ProviderError::TotalDifficultyNotFound(height))?;
+
+    eyre::ensure!(
+        first.header.parent_hash() == local_head.hash(),
+        "block {} from era1 file does not extend the block {} in storage",
+        first.header.number(),
+        height
+    );
+    eyre::ensure!(
+        first.total_difficulty == local_td + first.header.difficulty(),
+        "total difficulty of block {} from era1 file does not match the chain in storage",
+        first.header.number()
+    );
+
+    Ok(())
+}
+
+/// Returns the network name, the era number and the short accumulator root from the file name
+/// of an era1 file, like `mainnet`, `0` and `5ec1ffb8` for `mainnet-00000-5ec1ffb8.era1`.
+fn parse_file_name(path: &Path) -> Option<(&str, u64, [u8; 4])> {
+    let name = path.file_name()?.to_str()?.strip_suffix(".era1")?;
+    let mut parts = name.rsplitn(3, '-');
+    let short_root = hex::decode_to_array(parts.next()?).ok()?;
+    let era = parts.next()?.parse().ok()?;
+
+    Some((parts.next()?, era, short_root))
+}
+
+/// Writes the header hashes collected by [`import_file`] into [`tables::HeaderNumbers`], clearing
+/// `hash_collector`.
+pub fn build_index<P>(
+    provider: &P,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+) -> eyre::Result<()>
+where
+    P: DBProvider<Tx: DbTxMut>,
+{
+    let mut cursor = provider.tx_ref().cursor_write::<RawTable<tables::HeaderNumbers>>()?;
+
+    for entry in hash_collector.iter()? {
+        let (hash, number) = entry?;
+        cursor.upsert(RawKey::from_vec(hash), &RawValue::from_vec(number))?;
+    }
+
+    hash_collector.clear();
+
+    Ok(())
+}
+
+/// Saves the checkpoints of the [`StageId::Headers`] and [`StageId::Bodies`] stages, whose work
+/// is done by an era1 import up to `height`.
+pub fn save_stage_checkpoints<P>(provider: &P, height: BlockNumber) -> ProviderResult<()>
+where
+    P: StageCheckpointWriter,
+{
+    provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(height))?;
+    provider.save_stage_checkpoint(StageId::Bodies, StageCheckpoint::new(height))?;
+
+    Ok(())
+}
+
+/// Imports all era1 files of `files` into the storage of `provider_factory`, committing after
+/// every file.
+///
+/// Returns the highest block number in storage after the import.
+pub async fn import<N, S>(
+    mut files: S,
+    provider_factory: &ProviderFactory<N>,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+) -> eyre::Result<BlockNumber>
+where
+    N: ProviderNodeTypes<Primitives: NodePrimitives<BlockHeader: Value>>,
+    S: Stream<Item = eyre::Result<EraFile>> + Unpin,
+{
+    let mut height = provider_factory
+        .static_file_provider()
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .unwrap_or_default();
+
+    while let Some(file) = files.next().await {
+        let file = file?;
+        let provider = provider_factory.database_provider_rw()?;
+
+        height = import_file(&provider, file.path(), hash_collector, BlockNumber::MAX)?;
+        build_index(&provider, hash_collector)?;
+        save_stage_checkpoints(&provider, height)?;
+
+        UnifiedStorageWriter::commit(provider)?;
+        file.mark_as_processed()?;
+
+        info!(target: "era::history::import", height, "Imported era1 file");
+    }
+
+    Ok(height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{create_chain_spec, create_era1_file},
+        EraImportSource,
+    };
+    use alloy_consensus::{Eip658Value, Header};
+    use alloy_primitives::B256;
+    use reth_db_common::init::init_genesis;
+    use reth_era::era1_file::Era1Writer;
+    use reth_ethereum_primitives::BlockBody;
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, create_test_provider_factory_with_chain_spec},
+        ReceiptProvider,
+    };
+    use reth_storage_api::{BlockBodyIndicesProvider, BlockNumReader, StageCheckpointReader};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_import_from_path() {
+        let provider_factory = create_test_provider_factory_with_chain_spec(create_chain_spec());
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let dir = tempdir().unwrap();
+        let file = create_era1_file(&genesis, 3, Eip658Value::PostState(B256::repeat_byte(0x42)));
+        Era1Writer::create(dir.path().join(file.id.to_file_name()), &file).unwrap();
+
+        let files = EraImportSource::Path(dir.path().to_path_buf()).stream_from_block(1);
+        let mut hash_collector = Collector::new(1024 * 1024, None);
+        let height = import(files, &provider_factory, &mut hash_collector).await.unwrap();
+        assert_eq!(height, 3);
+
+        let provider = provider_factory.provider().unwrap();
+        for block in &file.group.blocks {
+            let header = block.header.decode::<Header>().unwrap();
+            let hash = header.hash_slow();
+            assert_eq!(provider.block_number(hash).unwrap(), Some(header.number));
+            assert_eq!(
+                provider.header_td_by_number(header.number).unwrap(),
+                Some(block.total_difficulty.value)
+            );
+        }
+
+        // The pre-Byzantium receipt is left to the execution stage
+        let body_indices = provider.block_body_indices(2).unwrap().unwrap();
+        assert_eq!(body_indices.tx_count, 1);
+        assert_eq!(provider.receipt(body_indices.first_tx_num).unwrap(), None);
+
+        for stage in [StageId::Headers, StageId::Bodies] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage).unwrap(),
+                Some(StageCheckpoint::new(3))
+            );
+        }
+    }
+
+    #[test]
+    fn test_import_file_rejects_misnamed_file() {
+        let provider_factory = create_test_provider_factory_with_chain_spec(create_chain_spec());
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let dir = tempdir().unwrap();
+        let file = create_era1_file(&genesis, 3, Eip658Value::Eip658(true));
+        let short_root = hex::encode(&file.group.accumulator.root[..4]);
+
+        for name in [
+            "mainnet-00000-00000000.era1".to_owned(),
+            "mainnet-0-4.era1".to_owned(),
+            format!("mainnet-00001-{short_root}.era1"),
+        ] {
+            let path = dir.path().join(name);
+            Era1Writer::create(&path, &file).unwrap();
+
+            let provider = provider_factory.database_provider_rw().unwrap();
+            let mut hash_collector = Collector::new(1024 * 1024, None);
+            assert!(import_file(&provider, &path, &mut hash_collector, BlockNumber::MAX).is_err());
+        }
+    }
+
+    #[test]
+    fn test_import_file_rejects_non_canonical_era() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let dir = tempdir().unwrap();
+        let file = create_era1_file(&genesis, 3, Eip658Value::Eip658(true));
+        let path = dir.path().join(file.id.to_file_name());
+        Era1Writer::create(&path, &file).unwrap();
+
+        // the file is valid and extends the mainnet genesis, but it's not a mainnet era1 file
+        let provider = provider_factory.database_provider_rw().unwrap();
+        let mut hash_collector = Collector::new(1024 * 1024, None);
+        let err = import_file(&provider, &path, &mut hash_collector, BlockNumber::MAX).unwrap_err();
+        assert!(err.to_string().contains("not part of the canonical history"));
+    }
+
+    #[test]
+    fn test_decode_file_rejects_invalid_accumulator() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let mut file =
+            create_era1_file(&genesis, 3, Eip658Value::PostState(B256::repeat_byte(0x42)));
+        assert!(decode_file::<Header, BlockBody>(&file).is_ok());
+
+        file.group.accumulator = Accumulator::new(B256::repeat_byte(0x01));
+        assert!(decode_file::<Header, BlockBody>(&file).is_err());
+    }
+
+    #[test]
+    fn test_decode_file_rejects_wrong_short_root() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let genesis = provider_factory.sealed_header(0).unwrap().unwrap();
+
+        let mut file = create_era1_file(&genesis, 3, Eip658Value::Eip658(true));
+        file.id.hash = Some([0; 4]);
+        assert!(decode_file::<Header, BlockBody>(&file).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Utilities to import blocks from era1 files into reth storage, and to export them back.
+//!
+//! Era1 files contain the pre-merge history of a chain. Every file is checked against its
+//! accumulator and block index, and every block against its header, before the headers and
+//! bodies are written straight into static files. Exports read the blocks back from storage
+//! and write them into era1 files of up to 8192 blocks.
+//!
+//! See also <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md>
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod canonical;
+mod export;
+mod history;
+mod source;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+pub use export::{export, ExportConfig};
+pub use history::{
+    build_index, decode_file, import, import_file, save_stage_checkpoints, EraBlock,
+};
+pub use source::{EraFile, EraFileStream, EraImportSource};
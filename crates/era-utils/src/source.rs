@@ -0,0 +1,94 @@
+use alloy_primitives::BlockNumber;
+use futures_util::{stream, Stream, TryStreamExt};
+use reqwest::{Client, Url};
+use reth_era::execution_types::MAX_BLOCKS_PER_ERA1;
+use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+/// An ordered stream of era1 files to import.
+pub type EraFileStream = Pin<Box<dyn Stream<Item = eyre::Result<EraFile>> + Send + Sync>>;
+
+/// The source era1 files are imported from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EraImportSource {
+    /// A local directory containing era1 files.
+    Path(PathBuf),
+    /// An HTTP endpoint hosting era1 files, along with the directory the files are downloaded to.
+    ///
+    /// Downloaded files are removed once they are imported.
+    Url(Url, PathBuf),
+}
+
+impl EraImportSource {
+    /// Returns a stream of the era1 files of this source, starting with the file that contains
+    /// `block`.
+    ///
+    /// Era1 files that are left in the download directory of a [`EraImportSource::Url`] source
+    /// from a previous run are removed, as they may not have been imported.
+    pub fn stream_from_block(self, block: BlockNumber) -> EraFileStream {
+        let start_from = block / MAX_BLOCKS_PER_ERA1 as u64;
+
+        match self {
+            Self::Path(dir) => Box::pin(
+                stream::once(async move { read_dir(dir, start_from) })
+                    .try_flatten()
+                    .map_ok(|path| EraFile { path, downloaded: false }),
+            ),
+            Self::Url(url, dir) => Box::pin(
+                stream::once(async move {
+                    clear_download_dir(&dir)?;
+
+                    let client = EraClient::new(Client::new(), url, dir.into_boxed_path());
+                    client.fetch_file_list().await?;
+
+                    Ok::<_, eyre::Report>(EraStream::new(
+                        client,
+                        EraStreamConfig::default().start_from(start_from),
+                    ))
+                })
+                .try_flatten()
+                .map_ok(|path| EraFile { path, downloaded: true }),
+            ),
+        }
+    }
+}
+
+/// Creates the download directory if it's missing and removes all era1 files from it.
+fn clear_download_dir(dir: &Path) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(dir)?;
+
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension() == Some("era1".as_ref()) {
+            reth_fs_util::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// An era1 file yielded by an [`EraFileStream`].
+#[derive(Debug)]
+pub struct EraFile {
+    path: Box<Path>,
+    downloaded: bool,
+}
+
+impl EraFile {
+    /// Returns the path of the file.
+    pub const fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Marks the file as processed, removing it if it was downloaded.
+    pub fn mark_as_processed(self) -> eyre::Result<()> {
+        if self.downloaded {
+            reth_fs_util::remove_file(&self.path)?;
+        }
+
+        Ok(())
+    }
+}
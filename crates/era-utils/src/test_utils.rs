@@ -0,0 +1,122 @@
+//! Helpers for testing era1 imports and exports.
+
+use alloy_consensus::{
+    proofs::{calculate_receipt_root, calculate_transaction_root},
+    Eip658Value, Header, Receipt as AlloyReceipt, ReceiptEnvelope, ReceiptWithBloom, TxLegacy,
+    EMPTY_OMMER_ROOT_HASH,
+};
+use alloy_primitives::{Address, Signature, TxKind, B256, U256};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_era::{
+    era1_file::Era1File,
+    era1_types::{BlockIndex, Era1Group, Era1Id},
+    execution_types::{
+        Accumulator, BlockTuple, CompressedBody, CompressedHeader, CompressedReceipts,
+        DecodeCompressed, HeaderRecord, TotalDifficulty,
+    },
+};
+use reth_ethereum_primitives::{Transaction, TransactionSigned};
+use reth_primitives_traits::SealedHeader;
+use std::sync::Arc;
+
+/// Returns a chain spec with the hardforks of mainnet and the mainnet genesis without its
+/// allocations.
+///
+/// The blocks of [`create_era1_file`] are not part of the canonical mainnet history, so they are
+/// imported on top of the genesis of another chain.
+pub fn create_chain_spec() -> Arc<ChainSpec> {
+    let mut genesis = MAINNET.genesis.clone();
+    genesis.alloc.clear();
+    Arc::new(ChainSpecBuilder::mainnet().genesis(genesis).build())
+}
+
+/// Creates an era1 file with the genesis block of `genesis` and `count` blocks on top, with a
+/// transaction in the second block whose receipt has the given `status`.
+///
+/// The transaction is a free transfer of nothing to [`Address::ZERO`], so the blocks can be
+/// executed on top of any genesis. The file is named after its accumulator root.
+pub fn create_era1_file(genesis: &SealedHeader, count: u64, status: Eip658Value) -> Era1File {
+    let mut parent = genesis.clone();
+    let mut total_difficulty = genesis.difficulty;
+    let mut blocks = vec![create_block_tuple(genesis, &[], &[], total_difficulty)];
+
+    for number in 1..=count {
+        let transactions = if number == 2 {
+            vec![TransactionSigned::new_unhashed(
+                Transaction::Legacy(TxLegacy {
+                    gas_price: 0,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::ZERO),
+                    ..Default::default()
+                }),
+                Signature::test_signature(),
+            )]
+        } else {
+            Vec::new()
+        };
+        let receipts = transactions
+            .iter()
+            .map(|_| {
+                ReceiptEnvelope::Legacy(ReceiptWithBloom::from(AlloyReceipt {
+                    status,
+                    cumulative_gas_used: 21_000,
+                    logs: Vec::new(),
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        let header = Header {
+            parent_hash: parent.hash(),
+            number,
+            difficulty: U256::from(1_000),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            transactions_root: calculate_transaction_root(&transactions),
+            receipts_root: calculate_receipt_root(&receipts),
+            gas_limit: 30_000_000,
+            gas_used: if transactions.is_empty() { 0 } else { 21_000 },
+            timestamp: parent.timestamp + 12,
+            ..Default::default()
+        };
+        total_difficulty += header.difficulty;
+
+        let header = SealedHeader::seal_slow(header);
+        blocks.push(create_block_tuple(&header, &transactions, &receipts, total_difficulty));
+        parent = header;
+    }
+
+    let mut group =
+        Era1Group::new(blocks, Accumulator::new(B256::ZERO), BlockIndex::new(0, Vec::new()));
+    group.block_index = group.compute_block_index();
+    let records = group
+        .blocks
+        .iter()
+        .map(|block| {
+            let header = block.header.decode::<Header>().unwrap();
+            HeaderRecord::new(header.hash_slow(), block.total_difficulty.value)
+        })
+        .collect::<Vec<_>>();
+    group.accumulator = Accumulator::from_header_records(&records).unwrap();
+
+    let short_root = group.accumulator.root[..4].try_into().unwrap();
+    Era1File::new(group, Era1Id::new("mainnet", 0, count as u32 + 1).with_hash(short_root))
+}
+
+fn create_block_tuple(
+    header: &SealedHeader,
+    transactions: &[TransactionSigned],
+    receipts: &[ReceiptEnvelope],
+    total_difficulty: U256,
+) -> BlockTuple {
+    let body = alloy_consensus::BlockBody::<TransactionSigned> {
+        transactions: transactions.to_vec(),
+        ommers: Vec::new(),
+        withdrawals: None,
+    };
+
+    BlockTuple::new(
+        CompressedHeader::from_header(header.header()).unwrap(),
+        CompressedBody::from_body(&body).unwrap(),
+        CompressedReceipts::from_encodable(&receipts.to_vec()).unwrap(),
+        TotalDifficulty::new(total_difficulty),
+    )
+}
@@ -451,6 +451,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_era1_compute_block_index() -> Result<(), E2sError> {
+        let era1_file = create_test_era1_file(1000, 5, "testnet");
+
+        let mut buffer = Vec::new();
+        Era1Writer::new(&mut buffer).write_era1_file(&era1_file)?;
+
+        let block_index = era1_file.group.compute_block_index();
+        assert_eq!(block_index.starting_number, 1000);
+        assert_eq!(block_index.offsets.len(), 5);
+
+        // The block index is the last record of the file
+        let index_position = (buffer.len() - 8 - block_index.to_entry().data.len()) as i64;
+        for (block, offset) in era1_file.group.blocks.iter().zip(block_index.offsets) {
+            let position = (index_position + offset) as usize;
+            let entry = crate::e2s_types::Entry::read(&mut &buffer[position..])?.unwrap();
+            assert_eq!(entry.entry_type, execution_types::COMPRESSED_HEADER);
+            assert_eq!(entry.data, block.header.data);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_era1_roundtrip_file() -> Result<(), E2sError> {
         // Create a temporary directory
@@ -4,7 +4,7 @@
 
 use crate::{
     e2s_types::{E2sError, Entry},
-    execution_types::{Accumulator, BlockTuple},
+    execution_types::{Accumulator, BlockTuple, MAX_BLOCKS_PER_ERA1},
 };
 use alloy_primitives::BlockNumber;
 
@@ -42,8 +42,39 @@ impl Era1Group {
     pub fn add_entry(&mut self, entry: Entry) {
         self.other_entries.push(entry);
     }
+
+    /// Compute the [`BlockIndex`] of this group, with the offsets of each block tuple
+    /// as laid out in the file, starting from the current [`BlockIndex::starting_number`].
+    ///
+    /// The result can be compared to [`Era1Group::block_index`] to check the integrity of a file.
+    pub fn compute_block_index(&self) -> BlockIndex {
+        // Every record starts with an 8-byte header, the version record has no data
+        let entry_size = |data_len: usize| ENTRY_HEADER_SIZE + data_len as i64;
+        let mut position = entry_size(0);
+
+        let mut block_positions = Vec::with_capacity(self.blocks.len());
+        for block in &self.blocks {
+            block_positions.push(position);
+            position += entry_size(block.header.data.len()) +
+                entry_size(block.body.data.len()) +
+                entry_size(block.receipts.data.len()) +
+                entry_size(32);
+        }
+        for entry in &self.other_entries {
+            position += entry_size(entry.data.len());
+        }
+        position += entry_size(self.accumulator.root.len());
+
+        // Offsets are relative to the beginning of the block index record
+        let offsets = block_positions.into_iter().map(|block| block - position).collect();
+
+        BlockIndex::new(self.block_index.starting_number, offsets)
+    }
 }
 
+/// Size of an e2store record header
+const ENTRY_HEADER_SIZE: i64 = 8;
+
 /// [`BlockIndex`] records store offsets to data at specific block numbers
 /// from the beginning of the index record to the beginning of the corresponding data.
 ///
@@ -174,8 +205,15 @@ impl Era1Id {
         self
     }
 
+    /// Returns the number of the era this file belongs to, which is the index of the
+    /// [`MAX_BLOCKS_PER_ERA1`] blocks long range containing the first block of the file.
+    pub const fn era_number(&self) -> u64 {
+        self.start_block / MAX_BLOCKS_PER_ERA1 as u64
+    }
+
     /// Convert to file name following the era1 file naming:
-    /// `<network-name>-<start-block>-<block-count>.era1`
+    /// `<network-name>-<era-number>-<short-historical-root>.era1` if a hash is set,
+    /// `<network-name>-<start-block>-<block-count>.era1` otherwise,
     /// inspired from era file naming convention in
     /// <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md#file-name>
     /// See also <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md>
@@ -185,7 +223,12 @@ impl Era1Id {
             // For example network-00000-5ec1ffb8.era1
             format!(
                 "{}-{:05}-{:02x}{:02x}{:02x}{:02x}.era1",
-                self.network_name, self.start_block, hash[0], hash[1], hash[2], hash[3]
+                self.network_name,
+                self.era_number(),
+                hash[0],
+                hash[1],
+                hash[2],
+                hash[3]
             )
         } else {
             // Original format without hash
@@ -335,15 +378,18 @@ mod tests {
         let mainnet_00000 = Era1Id::new("mainnet", 0, 8192).with_hash([0x5e, 0xc1, 0xff, 0xb8]);
         assert_eq!(mainnet_00000.to_file_name(), "mainnet-00000-5ec1ffb8.era1");
 
-        let mainnet_00012 = Era1Id::new("mainnet", 12, 8192).with_hash([0x5e, 0xcb, 0x9b, 0xf9]);
+        let mainnet_00012 =
+            Era1Id::new("mainnet", 12 * 8192, 8192).with_hash([0x5e, 0xcb, 0x9b, 0xf9]);
         assert_eq!(mainnet_00012.to_file_name(), "mainnet-00012-5ecb9bf9.era1");
 
         // Test with real sepolia examples
         // See <https://sepolia.era1.nimbus.team/>
-        let sepolia_00005 = Era1Id::new("sepolia", 5, 8192).with_hash([0x90, 0x91, 0x84, 0x72]);
+        let sepolia_00005 =
+            Era1Id::new("sepolia", 5 * 8192, 8192).with_hash([0x90, 0x91, 0x84, 0x72]);
         assert_eq!(sepolia_00005.to_file_name(), "sepolia-00005-90918472.era1");
 
-        let sepolia_00019 = Era1Id::new("sepolia", 19, 8192).with_hash([0xfa, 0x77, 0x00, 0x19]);
+        let sepolia_00019 =
+            Era1Id::new("sepolia", 19 * 8192, 8192).with_hash([0xfa, 0x77, 0x00, 0x19]);
         assert_eq!(sepolia_00019.to_file_name(), "sepolia-00019-fa770019.era1");
 
         // Test fallback to original format when no hash is provided
@@ -351,7 +397,8 @@ mod tests {
         assert_eq!(id_without_hash.to_file_name(), "mainnet-1000-100.era1");
 
         // Test with larger era numbers to ensure proper zero-padding
-        let large_era = Era1Id::new("sepolia", 12345, 8192).with_hash([0xab, 0xcd, 0xef, 0x12]);
+        let large_era =
+            Era1Id::new("sepolia", 12345 * 8192, 8192).with_hash([0xab, 0xcd, 0xef, 0x12]);
         assert_eq!(large_era.to_file_name(), "sepolia-12345-abcdef12.era1");
     }
 }
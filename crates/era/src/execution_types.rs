@@ -14,6 +14,7 @@ use crate::e2s_types::{E2sError, Entry};
 use alloy_consensus::{Block, BlockBody, Header};
 use alloy_primitives::{B256, U256};
 use alloy_rlp::{Decodable, Encodable};
+use sha2::{Digest, Sha256};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use std::{
     io::{Read, Write},
@@ -399,6 +400,40 @@ impl Accumulator {
         Self { root }
     }
 
+    /// Compute the [`Accumulator`] of the given header records
+    ///
+    /// The root is the SSZ `hash_tree_root` of `List[HeaderRecord, MAX_BLOCKS_PER_ERA1]`.
+    pub fn from_header_records(records: &[HeaderRecord]) -> Result<Self, E2sError> {
+        if records.len() > MAX_BLOCKS_PER_ERA1 {
+            return Err(E2sError::Ssz(format!(
+                "Too many header records for Accumulator: maximum {}, got {}",
+                MAX_BLOCKS_PER_ERA1,
+                records.len()
+            )));
+        }
+
+        let mut layer = records.iter().map(HeaderRecord::hash_tree_root).collect::<Vec<_>>();
+
+        // Merkleize up to the depth of the list limit, padding each layer with the root of an
+        // empty subtree of the same depth
+        let mut zero_hash = B256::ZERO;
+        let mut width = MAX_BLOCKS_PER_ERA1;
+        while width > 1 {
+            if layer.len() % 2 == 1 {
+                layer.push(zero_hash);
+            }
+            layer = layer.chunks_exact(2).map(|pair| sha256_pair(pair[0], pair[1])).collect();
+            zero_hash = sha256_pair(zero_hash, zero_hash);
+            width /= 2;
+        }
+        let root = layer.first().copied().unwrap_or(zero_hash);
+
+        // Mix in the length of the list
+        let length = B256::from(U256::from(records.len()).to_le_bytes::<32>());
+
+        Ok(Self { root: sha256_pair(root, length) })
+    }
+
     /// Convert to an [`Entry`]
     pub fn to_entry(&self) -> Entry {
         Entry::new(ACCUMULATOR, self.root.to_vec())
@@ -427,6 +462,36 @@ impl Accumulator {
     }
 }
 
+/// A header record, the leaf of the [`Accumulator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The block hash
+    pub block_hash: B256,
+
+    /// The total difficulty at the block
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Create a new [`HeaderRecord`]
+    pub const fn new(block_hash: B256, total_difficulty: U256) -> Self {
+        Self { block_hash, total_difficulty }
+    }
+
+    /// Compute the SSZ `hash_tree_root` of this record
+    pub fn hash_tree_root(&self) -> B256 {
+        sha256_pair(self.block_hash, B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// Hashes the concatenation of two 32-byte chunks with SHA-256
+fn sha256_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
 /// A block tuple in an Era1 file, containing all components for a single block
 #[derive(Debug, Clone)]
 pub struct BlockTuple {
@@ -552,6 +617,27 @@ mod tests {
         assert_eq!(recovered.value, value);
     }
 
+    #[test]
+    fn test_accumulator_from_header_records() {
+        let records = (0..3u64)
+            .map(|i| HeaderRecord::new(B256::with_last_byte(i as u8 + 1), U256::from(i * 1000)))
+            .collect::<Vec<_>>();
+
+        // Merkleize the full tree of leaves, padded with zero chunks up to the list limit
+        let mut layer = records.iter().map(HeaderRecord::hash_tree_root).collect::<Vec<_>>();
+        layer.resize(MAX_BLOCKS_PER_ERA1, B256::ZERO);
+        while layer.len() > 1 {
+            layer = layer.chunks_exact(2).map(|pair| sha256_pair(pair[0], pair[1])).collect();
+        }
+        let expected = sha256_pair(layer[0], B256::from(U256::from(3).to_le_bytes::<32>()));
+
+        let accumulator = Accumulator::from_header_records(&records).unwrap();
+        assert_eq!(accumulator.root, expected);
+
+        let records = vec![HeaderRecord::new(B256::ZERO, U256::ZERO); MAX_BLOCKS_PER_ERA1 + 1];
+        assert!(Accumulator::from_header_records(&records).is_err());
+    }
+
     #[test]
     fn test_compression_roundtrip() {
         let rlp_data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
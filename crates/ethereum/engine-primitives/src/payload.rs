@@ -9,6 +9,7 @@ use alloy_rpc_types_engine::{
     ExecutionPayloadFieldV2, ExecutionPayloadV1, ExecutionPayloadV3, PayloadAttributes, PayloadId,
 };
 use core::convert::Infallible;
+use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_ethereum_primitives::{Block, EthPrimitives};
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives_traits::SealedBlock;
@@ -31,6 +32,8 @@ pub struct EthBuiltPayload {
     pub(crate) sidecars: Vec<BlobTransactionSidecar>,
     /// The requests of the payload
     pub(crate) requests: Option<Requests>,
+    /// The built block with its execution output, if available.
+    pub(crate) executed_block: Option<ExecutedBlockWithTrieUpdates<EthPrimitives>>,
 }
 
 // === impl BuiltPayload ===
@@ -45,7 +48,7 @@ impl EthBuiltPayload {
         fees: U256,
         requests: Option<Requests>,
     ) -> Self {
-        Self { id, block, fees, sidecars: Vec::new(), requests }
+        Self { id, block, fees, sidecars: Vec::new(), requests, executed_block: None }
     }
 
     /// Returns the identifier of the payload.
@@ -81,6 +84,16 @@ impl EthBuiltPayload {
         self.extend_sidecars(sidecars);
         self
     }
+
+    /// Sets the executed block of the payload, which allows inserting the payload into the tree
+    /// without executing it again.
+    pub fn with_executed_block(
+        mut self,
+        executed_block: ExecutedBlockWithTrieUpdates<EthPrimitives>,
+    ) -> Self {
+        self.executed_block = Some(executed_block);
+        self
+    }
 }
 
 impl BuiltPayload for EthBuiltPayload {
@@ -97,6 +110,10 @@ impl BuiltPayload for EthBuiltPayload {
     fn requests(&self) -> Option<Requests> {
         self.requests.clone()
     }
+
+    fn executed_block(&self) -> Option<ExecutedBlockWithTrieUpdates<EthPrimitives>> {
+        self.executed_block.clone()
+    }
 }
 
 // V1 engine_getPayloadV1 response
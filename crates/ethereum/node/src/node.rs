@@ -30,8 +30,13 @@ use reth_node_builder::{
     PayloadTypes,
 };
 use reth_provider::{providers::ProviderFactoryBuilder, CanonStateSubscriptions, EthStorage};
-use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
+use reth_rpc::{eth::core::EthApiFor, AnvilApi, ValidationApi};
+use reth_rpc_api::{
+    eth::FullEthApiServer,
+    servers::{
+        AnvilApiServer, BlockSubmissionValidationApiServer, GanacheApiServer, HardhatApiServer,
+    },
+};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
@@ -197,13 +202,32 @@ where
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
 
+        let local_miner_handle = ctx.local_miner_handle.clone();
+
         self.inner
-            .launch_add_ons_with(ctx, move |modules, _, _| {
+            .launch_add_ons_with(ctx, move |modules, _, registry| {
                 modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
 
+                // the testing namespaces are only available on dev nodes
+                if let Some(miner) = local_miner_handle {
+                    let anvil_api = AnvilApi::new(registry.eth_api().clone(), miner);
+                    modules.merge_if_module_configured(
+                        RethRpcModule::Anvil,
+                        AnvilApiServer::into_rpc(anvil_api.clone()),
+                    )?;
+                    modules.merge_if_module_configured(
+                        RethRpcModule::Anvil,
+                        HardhatApiServer::into_rpc(anvil_api.clone()),
+                    )?;
+                    modules.merge_if_module_configured(
+                        RethRpcModule::Anvil,
+                        GanacheApiServer::into_rpc(anvil_api),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
@@ -0,0 +1,232 @@
+use crate::utils::eth_payload_attributes;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{bytes, Address, B256, U256};
+use alloy_provider::{Provider, ProviderBuilder};
+use alloy_rpc_types_eth::TransactionRequest;
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_e2e_test_utils::{setup_engine, transaction::TransactionTestContext};
+use reth_node_ethereum::EthereumNode;
+use reth_provider::{BlockHashReader, BlockNumReader, DatabaseProviderFactory};
+use std::{sync::Arc, time::Duration};
+
+fn chain_spec() -> Arc<ChainSpec> {
+    Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    )
+}
+
+#[tokio::test]
+async fn test_anvil_set_account_state() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec(), true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    // the modifications are applied to the latest block instead of mining new blocks, except on
+    // top of the genesis block
+    let _: String = provider.raw_request("evm_mine".into(), ()).await?;
+    let head = provider.get_block_number().await?;
+
+    let address = Address::random();
+    let () = provider.raw_request("anvil_setBalance".into(), (address, U256::from(1337))).await?;
+    assert_eq!(provider.get_balance(address).await?, U256::from(1337));
+
+    let code = bytes!("0x6080604052600080fd");
+    let () = provider.raw_request("anvil_setCode".into(), (address, &code)).await?;
+    assert_eq!(provider.get_code_at(address).await?, code);
+
+    let value = B256::with_last_byte(42);
+    let written: bool =
+        provider.raw_request("anvil_setStorageAt".into(), (address, U256::from(1), value)).await?;
+    assert!(written);
+    assert_eq!(provider.get_storage_at(address, U256::from(1)).await?, value.into());
+
+    let () = provider.raw_request("hardhat_setNonce".into(), (address, U256::from(7))).await?;
+    assert_eq!(provider.get_transaction_count(address).await?, 7);
+
+    // the other fields of the account are kept
+    assert_eq!(provider.get_balance(address).await?, U256::from(1337));
+    assert_eq!(provider.get_code_at(address).await?, code);
+    assert_eq!(provider.get_block_number().await?, head);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_anvil_impersonate_account() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec(), true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    let sender = Address::random();
+    let recipient = Address::random();
+    let () = provider
+        .raw_request("anvil_setBalance".into(), (sender, U256::from(10).pow(U256::from(18))))
+        .await?;
+    // the nonce is set explicitly, the filler of the provider would skip the nonce of the
+    // rejected transaction
+    let tx = TransactionRequest::default().from(sender).to(recipient).value(U256::from(1)).nonce(0);
+
+    // the node has no key for the account
+    assert!(provider.send_transaction(tx.clone()).await.is_err());
+
+    let () = provider.raw_request("anvil_impersonateAccount".into(), (sender,)).await?;
+    let receipt = provider.send_transaction(tx.clone()).await?.get_receipt().await?;
+    assert!(receipt.status());
+    assert_eq!(provider.get_balance(recipient).await?, U256::from(1));
+
+    // the signature of the transaction doesn't recover to the impersonated account
+    assert_eq!(receipt.from, sender);
+    let mined = provider.get_transaction_by_hash(receipt.transaction_hash).await?.unwrap();
+    assert_eq!(mined.inner.signer(), sender);
+    let block_receipts =
+        provider.get_block_receipts(receipt.block_number.unwrap().into()).await?.unwrap();
+    assert_eq!(block_receipts[0].from, sender);
+    assert_eq!(provider.get_transaction_count(sender).await?, 1);
+
+    let () = provider.raw_request("anvil_stopImpersonatingAccount".into(), (sender,)).await?;
+    assert!(provider.send_transaction(tx).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_anvil_mine_with_timestamps() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec(), true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    let start = provider.get_block_number().await?;
+    let () = provider.raw_request("anvil_mine".into(), (U256::from(3), U256::from(100))).await?;
+    assert_eq!(provider.get_block_number().await?, start + 3);
+    let mut timestamps = Vec::new();
+    for number in start + 1..=start + 3 {
+        let block = provider.get_block_by_number(number.into()).await?.unwrap();
+        timestamps.push(block.header.timestamp);
+    }
+    assert_eq!(timestamps[1], timestamps[0] + 100);
+    assert_eq!(timestamps[2], timestamps[1] + 100);
+
+    let timestamp = timestamps[2] + 1000;
+    let () = provider.raw_request("anvil_setNextBlockTimestamp".into(), (timestamp,)).await?;
+    let _: String = provider.raw_request("evm_mine".into(), ()).await?;
+    let block = provider.get_block_by_number(BlockNumberOrTag::Latest).await?.unwrap();
+    assert_eq!(block.header.number, start + 4);
+    assert_eq!(block.header.timestamp, timestamp);
+
+    // the timestamp has to increase
+    assert!(provider
+        .raw_request::<_, ()>("anvil_setNextBlockTimestamp".into(), (timestamp,))
+        .await
+        .is_err());
+
+    // the number of blocks mined at once is capped
+    assert!(provider.raw_request::<_, ()>("anvil_mine".into(), (U256::MAX,)).await.is_err());
+    assert_eq!(provider.get_block_number().await?, start + 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_anvil_snapshot_and_revert() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec(), true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    let address = Address::random();
+    let () = provider.raw_request("anvil_setBalance".into(), (address, U256::from(1))).await?;
+    let snapshot: U256 = provider.raw_request("evm_snapshot".into(), ()).await?;
+    let snapshot_block = provider.get_block_by_number(BlockNumberOrTag::Latest).await?.unwrap();
+
+    // mine enough blocks on top of the snapshot for them to be persisted
+    let () = provider.raw_request("anvil_setBalance".into(), (address, U256::from(2))).await?;
+    let () = provider.raw_request("anvil_mine".into(), (U256::from(10),)).await?;
+    let persisted = snapshot_block.header.number + 2;
+    wait_for(|| Ok(node.inner.provider.database_provider_ro()?.last_block_number()? >= persisted))
+        .await?;
+
+    let reverted: bool = provider.raw_request("evm_revert".into(), (snapshot,)).await?;
+    assert!(reverted);
+    assert_eq!(provider.get_block_number().await?, snapshot_block.header.number);
+    assert_eq!(provider.get_balance(address).await?, U256::from(1));
+
+    // a snapshot can only be reverted to once
+    let reverted: bool = provider.raw_request("evm_revert".into(), (snapshot,)).await?;
+    assert!(!reverted);
+
+    // the chain continues on top of the snapshot and replaces the persisted blocks
+    let () = provider.raw_request("anvil_mine".into(), (U256::from(10),)).await?;
+    let block = provider.get_block_by_number(persisted.into()).await?.unwrap();
+    assert_eq!(
+        provider.get_block_by_number((persisted - 1).into()).await?.unwrap().header.parent_hash,
+        snapshot_block.header.hash
+    );
+    assert_eq!(provider.get_balance(address).await?, U256::from(1));
+    wait_for(|| {
+        Ok(node.inner.provider.database_provider_ro()?.block_hash(persisted)? ==
+            Some(block.header.hash))
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_anvil_resend_reverted_transaction() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec(), true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    let sender = wallet.inner.address();
+    let snapshot: U256 = provider.raw_request("evm_snapshot".into(), ()).await?;
+    let snapshot_block = provider.get_block_number().await?;
+
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(MAINNET.chain.id(), wallet.inner).await;
+    let receipt = provider.send_raw_transaction(&raw_tx).await?.get_receipt().await?;
+    assert!(receipt.status());
+    assert_eq!(receipt.block_number, Some(snapshot_block + 1));
+    assert_eq!(provider.get_transaction_count(sender).await?, 1);
+
+    let reverted: bool = provider.raw_request("evm_revert".into(), (snapshot,)).await?;
+    assert!(reverted);
+    assert_eq!(provider.get_block_number().await?, snapshot_block);
+    assert_eq!(provider.get_transaction_count(sender).await?, 0);
+
+    // the pool saw the revert, so the transaction is neither known nor below the sender's nonce
+    let resent = provider.send_raw_transaction(&raw_tx).await?.get_receipt().await?;
+    assert!(resent.status());
+    assert_eq!(resent.transaction_hash, receipt.transaction_hash);
+    assert_eq!(resent.block_number, Some(snapshot_block + 1));
+    assert_eq!(provider.get_transaction_count(sender).await?, 1);
+
+    Ok(())
+}
+
+/// Polls the condition until it holds, failing after a few seconds.
+async fn wait_for(condition: impl Fn() -> eyre::Result<bool>) -> eyre::Result<()> {
+    for _ in 0..100 {
+        if condition()? {
+            return Ok(())
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    eyre::bail!("condition not met in time")
+}
@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
 
+mod anvil;
 mod blobs;
 mod dev;
 mod eth;
@@ -1,18 +1,24 @@
 use crate::utils::eth_payload_attributes;
 use alloy_eips::{calc_next_block_base_fee, eip2718::Encodable2718};
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
 use alloy_rpc_types_beacon::relay::{
-    BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
-    SignedBidSubmissionV3, SignedBidSubmissionV4,
+    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
+    BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4, SignedBidSubmissionV1,
+    SignedBidSubmissionV2, SignedBidSubmissionV3, SignedBidSubmissionV4,
+};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
 };
-use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
 use alloy_rpc_types_eth::TransactionRequest;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::setup_engine;
+use reth_ethereum_engine_primitives::EthPayloadBuilderAttributes;
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
+use reth_provider::{DatabaseProviderFactory, PruneCheckpointWriter};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
 use std::sync::Arc;
 
 alloy_sol_types::sol! {
@@ -115,6 +121,371 @@ async fn test_fee_history() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_debug_modified_accounts_and_intermediate_roots() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let signer = wallet.gen().swap_remove(0);
+    let sender = signer.address();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::new(signer)).on_http(node.rpc_url());
+
+    // deploy two contracts writing to storage in the same block
+    let mut pending = Vec::new();
+    for nonce in 0..2 {
+        pending
+            .push(GasWaster::deploy_builder(&provider, U256::from(10)).nonce(nonce).send().await?);
+    }
+    node.advance_block().await?;
+    let mut contracts = Vec::new();
+    for tx in pending {
+        contracts.push(tx.get_receipt().await?.contract_address.unwrap());
+    }
+    let block = provider.get_block_by_number(1.into()).await?.unwrap();
+
+    let modified: Vec<Address> =
+        provider.raw_request("debug_getModifiedAccountsByNumber".into(), (1,)).await?;
+    for address in contracts.iter().chain([&sender]) {
+        assert!(modified.contains(address), "{address} not modified");
+    }
+    let by_hash: Vec<Address> = provider
+        .raw_request(
+            "debug_getModifiedAccountsByHash".into(),
+            (block.header.parent_hash, block.header.hash),
+        )
+        .await?;
+    assert_eq!(by_hash, modified);
+
+    // the start block has to be before the end block
+    assert!(provider
+        .raw_request::<_, Vec<Address>>("debug_getModifiedAccountsByNumber".into(), (1, 1))
+        .await
+        .is_err());
+
+    // the range is capped at the `trace_filter` limit
+    let err = provider
+        .raw_request::<_, Vec<Address>>("debug_getModifiedAccountsByNumber".into(), (0, 101))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Block range too large"), "{err}");
+
+    // the root after the last transaction is the state root of the block, since there are no
+    // withdrawals
+    let roots: Vec<B256> =
+        provider.raw_request("debug_intermediateRoots".into(), (block.header.hash,)).await?;
+    assert_eq!(roots.len(), 2);
+    assert_ne!(roots[0], roots[1]);
+    assert_eq!(roots[1], block.header.state_root);
+
+    // the changesets of the block are gone once its account history is pruned
+    let provider_rw = node.inner.provider.database_provider_rw()?;
+    provider_rw.save_prune_checkpoint(
+        PruneSegment::AccountHistory,
+        PruneCheckpoint {
+            block_number: Some(1),
+            tx_number: None,
+            prune_mode: PruneMode::Before(2),
+        },
+    )?;
+    provider_rw.commit()?;
+    assert!(provider
+        .raw_request::<_, Vec<Address>>("debug_getModifiedAccountsByNumber".into(), (1,))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_debug_account_and_storage_range() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let signer = wallet.gen().swap_remove(0);
+    let sender = signer.address();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::new(signer)).on_http(node.rpc_url());
+
+    let pending = GasWaster::deploy_builder(&provider, U256::from(10)).send().await?;
+    node.advance_block().await?;
+    let contract = pending.get_receipt().await?.contract_address.unwrap();
+
+    // the storage is read before the first transaction of the next block
+    let pending = provider
+        .send_transaction(TransactionRequest::default().to(sender).value(U256::from(1)))
+        .await?;
+    node.advance_block().await?;
+    pending.get_receipt().await?;
+    let block = provider.get_block_by_number(2.into()).await?.unwrap();
+
+    // the contract doesn't exist before its deployment
+    assert!(provider
+        .raw_request::<_, serde_json::Value>(
+            "debug_storageRangeAt".into(),
+            (1, 0, contract, B256::ZERO, 10),
+        )
+        .await
+        .is_err());
+
+    // walk the storage of the contract in pages
+    let mut slots = serde_json::Map::new();
+    let mut start = Some(B256::ZERO);
+    while let Some(key_start) = start {
+        let page: serde_json::Value = provider
+            .raw_request(
+                "debug_storageRangeAt".into(),
+                (block.header.hash, 0, contract, key_start, 4),
+            )
+            .await?;
+        let storage = page["storage"].as_object().unwrap();
+        assert!(storage.len() <= 4);
+        slots.extend(storage.clone());
+        start = serde_json::from_value(page["nextKey"].clone())?;
+    }
+    assert_eq!(slots.len(), 10);
+    for (hashed_slot, entry) in slots {
+        let slot: B256 = serde_json::from_value(entry["key"].clone())?;
+        assert_eq!(hashed_slot.parse::<B256>()?, keccak256(slot));
+        // the contract stores every slot as its own value
+        assert_eq!(serde_json::from_value::<B256>(entry["value"].clone())?, slot);
+    }
+
+    let dump: serde_json::Value = provider
+        .raw_request("debug_accountRange".into(), ("0x2", "0x", 1000, false, false, true))
+        .await?;
+    assert_eq!(dump["root"], format!("{:x}", block.header.state_root));
+    assert!(dump.get("next").is_none());
+    let account = &dump["accounts"][contract.to_checksum(None)];
+    assert_eq!(account["address"], contract.to_string().to_lowercase());
+    assert_eq!(account["key"], keccak256(contract).to_string());
+    assert_eq!(account["nonce"], 1);
+    assert!(account["code"].is_string());
+    let proof = provider.get_proof(contract, vec![]).number(2).await?;
+    assert_eq!(account["root"], proof.storage_hash.to_string());
+    assert_eq!(account["codeHash"], proof.code_hash.to_string());
+    let storage = account["storage"].as_object().unwrap();
+    assert_eq!(storage.len(), 10);
+    for (slot, value) in storage {
+        // the contract stores every slot as its own value
+        assert_eq!(
+            format!("0x{}", value.as_str().unwrap()).parse::<U256>()?,
+            slot.parse::<U256>()?
+        );
+    }
+
+    // the storage is left out if it's not requested
+    let dump: serde_json::Value = provider
+        .raw_request("debug_accountRange".into(), ("0x2", "0x", 1000, false, true, true))
+        .await?;
+    assert!(dump["accounts"][contract.to_checksum(None)].get("storage").is_none());
+
+    // the next key continues the iteration after the page
+    let page: serde_json::Value = provider
+        .raw_request("debug_accountRange".into(), ("0x2", "0x", 1, true, true, true))
+        .await?;
+    assert_eq!(page["accounts"].as_object().unwrap().len(), 1);
+    let next = page["next"].as_str().unwrap();
+    assert_eq!(next.len(), 44);
+
+    // the addresses of all accounts of the small state are known, so nothing is incomplete
+    let complete: serde_json::Value = provider
+        .raw_request("debug_accountRange".into(), ("0x2", "0x", 1000, false, true, false))
+        .await?;
+    assert_eq!(complete["accounts"], dump["accounts"]);
+
+    Ok(())
+}
+
+/// Payload attributes for chains without an active Cancun hardfork.
+fn pre_cancun_payload_attributes(timestamp: u64) -> EthPayloadBuilderAttributes {
+    let mut attributes = eth_payload_attributes(timestamp);
+    attributes.parent_beacon_block_root = None;
+    attributes
+}
+
+#[tokio::test]
+async fn test_flashbots_validate_v1() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .paris_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, pre_cancun_payload_attributes)
+            .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.gen().swap_remove(0)))
+        .on_http(node.rpc_url());
+
+    node.advance(10, |_| {
+        let provider = provider.clone();
+        Box::pin(async move {
+            let SendableTx::Envelope(tx) =
+                provider.fill(TransactionRequest::default().to(Address::ZERO)).await.unwrap()
+            else {
+                unreachable!()
+            };
+
+            tx.encoded_2718().into()
+        })
+    })
+    .await?;
+
+    let _ = provider.send_transaction(TransactionRequest::default().to(Address::ZERO)).await?;
+    let payload = node.new_payload().await?;
+
+    let mut request = BuilderBlockValidationRequest {
+        request: SignedBidSubmissionV1 {
+            message: BidTrace {
+                parent_hash: payload.block().parent_hash,
+                block_hash: payload.block().hash(),
+                gas_used: payload.block().gas_used,
+                gas_limit: payload.block().gas_limit,
+                ..Default::default()
+            },
+            execution_payload: ExecutionPayloadV1::from_block_unchecked(
+                payload.block().hash(),
+                &payload.block().clone().into_block(),
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+
+    provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .expect("request should validate");
+
+    request.registered_gas_limit -= 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.registered_gas_limit += 1;
+
+    request.request.execution_payload.state_root = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flashbots_validate_v2() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .shanghai_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, pre_cancun_payload_attributes)
+            .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.gen().swap_remove(0)))
+        .on_http(node.rpc_url());
+
+    node.advance(10, |_| {
+        let provider = provider.clone();
+        Box::pin(async move {
+            let SendableTx::Envelope(tx) =
+                provider.fill(TransactionRequest::default().to(Address::ZERO)).await.unwrap()
+            else {
+                unreachable!()
+            };
+
+            tx.encoded_2718().into()
+        })
+    })
+    .await?;
+
+    let _ = provider.send_transaction(TransactionRequest::default().to(Address::ZERO)).await?;
+    let payload = node.new_payload().await?;
+    let block = payload.block().clone().into_block();
+
+    let message = BidTrace {
+        parent_hash: payload.block().parent_hash,
+        block_hash: payload.block().hash(),
+        gas_used: payload.block().gas_used,
+        gas_limit: payload.block().gas_limit,
+        ..Default::default()
+    };
+    let mut request = BuilderBlockValidationRequestV2 {
+        request: SignedBidSubmissionV2 {
+            message: message.clone(),
+            execution_payload: ExecutionPayloadV2::from_block_unchecked(
+                payload.block().hash(),
+                &block,
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+
+    provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .expect("request should validate");
+
+    // the block requires withdrawals, which are missing in the v1 payload
+    let v1_request = BuilderBlockValidationRequest {
+        request: SignedBidSubmissionV1 {
+            message,
+            execution_payload: ExecutionPayloadV1::from_block_unchecked(
+                payload.block().hash(),
+                &block,
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&v1_request,))
+        .await
+        .is_err());
+
+    request.request.execution_payload.payload_inner.state_root = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_flashbots_validate_v3() -> eyre::Result<()> {
     reth_tracing::init_test_tracing();
@@ -13,11 +13,13 @@ pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
 use alloy_consensus::{Transaction, Typed2718};
+use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
+use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
@@ -26,6 +28,7 @@ use reth_evm::{
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
+use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
@@ -289,11 +292,12 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
-    let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
+    let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } =
+        builder.finish(&state_provider)?;
 
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
-        .then_some(execution_result.requests);
+        .then(|| execution_result.requests.clone());
 
     // initialize empty blob sidecars at first. If cancun is active then this will
     let mut blob_sidecars = Vec::new();
@@ -310,16 +314,41 @@ where
                     .map(|tx| *tx.tx_hash())
                     .collect(),
             )
+            .map_err(PayloadBuilderError::other)?
+            .into_iter()
+            .map(|sidecar| {
+                // sidecars with cell proofs are converted back into sidecars with blob proofs
+                Arc::unwrap_or_clone(sidecar).try_into_eip4844(EnvKzgSettings::Default.get())
+            })
+            .collect::<Result<Vec<_>, _>>()
             .map_err(PayloadBuilderError::other)?;
     }
 
     let sealed_block = Arc::new(block.sealed_block().clone());
     debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "sealed built block");
 
-    let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests);
+    let execution_outcome = ExecutionOutcome::new(
+        db.take_bundle(),
+        vec![execution_result.receipts],
+        block.number,
+        vec![execution_result.requests],
+    );
+
+    // create the executed block data
+    let executed = ExecutedBlockWithTrieUpdates {
+        block: ExecutedBlock {
+            recovered_block: Arc::new(block),
+            execution_output: Arc::new(execution_outcome),
+            hashed_state: Arc::new(hashed_state),
+        },
+        trie: Arc::new(trie_updates),
+    };
+
+    let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
+        .with_executed_block(executed);
 
     // extend the payload with the blob sidecars from the executed txs
-    payload.extend_sidecars(blob_sidecars.into_iter().map(Arc::unwrap_or_clone));
+    payload.extend_sidecars(blob_sidecars);
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
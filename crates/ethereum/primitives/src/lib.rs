@@ -17,6 +17,12 @@ pub use receipt::*;
 mod transaction;
 pub use transaction::*;
 
+mod pooled;
+pub use pooled::*;
+
+mod sidecar;
+pub use sidecar::*;
+
 #[cfg(feature = "alloy-compat")]
 mod alloy_compat;
 
@@ -0,0 +1,360 @@
+//! The pooled transaction format, which carries the blob sidecar of EIP-4844 transactions.
+
+use crate::{BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant};
+use alloy_consensus::{
+    transaction::{RlpEcdsaDecodableTx, RlpEcdsaEncodableTx},
+    EthereumTxEnvelope, SignableTransaction, Transaction, TxEip4844, TxEip4844WithSidecar, TxType,
+    Typed2718,
+};
+use alloy_eips::{
+    eip2930::AccessList, eip4844::BlobTransactionSidecar, eip7702::SignedAuthorization,
+};
+use alloy_primitives::{Bytes, ChainId, Signature, TxKind, B256, U256};
+use alloy_rlp::{BufMut, Header};
+use reth_primitives_traits::InMemorySize;
+
+/// The pooled transaction format of Ethereum, which is used to gossip transactions and to submit
+/// raw transactions.
+///
+/// EIP-4844 transactions carry a blob sidecar of either format, see
+/// [`TxEip4844WithSidecarVariant`].
+pub type PooledTransaction = EthereumTxEnvelope<TxEip4844WithSidecarVariant>;
+
+/// A [`TxEip4844`] along with its [`BlobTransactionSidecarVariant`].
+///
+/// This is the [`TxEip4844WithSidecar`] counterpart that also accepts sidecars with cell proofs,
+/// which replace the blob proofs once Osaka is active. Both sidecar formats are encoded after the
+/// transaction fields, the cell proofs are preceded by the version byte of the sidecar.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TxEip4844WithSidecarVariant {
+    /// The actual transaction.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub tx: TxEip4844,
+    /// The sidecar.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub sidecar: BlobTransactionSidecarVariant,
+}
+
+impl TxEip4844WithSidecarVariant {
+    /// Constructs a new [`TxEip4844WithSidecarVariant`] from a [`TxEip4844`] and a sidecar.
+    pub fn from_tx_and_sidecar(
+        tx: TxEip4844,
+        sidecar: impl Into<BlobTransactionSidecarVariant>,
+    ) -> Self {
+        Self { tx, sidecar: sidecar.into() }
+    }
+
+    /// Get access to the inner tx [`TxEip4844`].
+    pub const fn tx(&self) -> &TxEip4844 {
+        &self.tx
+    }
+
+    /// Get access to the inner [`BlobTransactionSidecarVariant`].
+    pub const fn sidecar(&self) -> &BlobTransactionSidecarVariant {
+        &self.sidecar
+    }
+
+    /// Consumes the type and returns the inner [`TxEip4844`] and [`BlobTransactionSidecarVariant`].
+    pub fn into_parts(self) -> (TxEip4844, BlobTransactionSidecarVariant) {
+        (self.tx, self.sidecar)
+    }
+
+    /// Converts this into a [`TxEip4844WithSidecar`] if the sidecar carries one proof per blob.
+    ///
+    /// Returns the transaction as is if the sidecar carries cell proofs.
+    pub fn try_into_eip4844_with_sidecar(self) -> Result<TxEip4844WithSidecar, Self> {
+        match self.sidecar {
+            BlobTransactionSidecarVariant::Eip4844(sidecar) => {
+                Ok(TxEip4844WithSidecar { tx: self.tx, sidecar })
+            }
+            sidecar => Err(Self { tx: self.tx, sidecar }),
+        }
+    }
+}
+
+impl From<TxEip4844WithSidecar> for TxEip4844WithSidecarVariant {
+    fn from(tx: TxEip4844WithSidecar) -> Self {
+        let (tx, sidecar) = tx.into_parts();
+        Self::from_tx_and_sidecar(tx, sidecar)
+    }
+}
+
+impl From<(TxEip4844, BlobTransactionSidecar)> for TxEip4844WithSidecarVariant {
+    fn from((tx, sidecar): (TxEip4844, BlobTransactionSidecar)) -> Self {
+        Self::from_tx_and_sidecar(tx, sidecar)
+    }
+}
+
+impl From<(TxEip4844, BlobTransactionSidecarEip7594)> for TxEip4844WithSidecarVariant {
+    fn from((tx, sidecar): (TxEip4844, BlobTransactionSidecarEip7594)) -> Self {
+        Self::from_tx_and_sidecar(tx, sidecar)
+    }
+}
+
+impl From<TxEip4844WithSidecarVariant> for TxEip4844 {
+    fn from(tx: TxEip4844WithSidecarVariant) -> Self {
+        tx.tx
+    }
+}
+
+impl SignableTransaction<Signature> for TxEip4844WithSidecarVariant {
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.tx.chain_id = chain_id;
+    }
+
+    fn encode_for_signing(&self, out: &mut dyn BufMut) {
+        // the sidecar is not part of the signed payload
+        self.tx.encode_for_signing(out);
+    }
+
+    fn payload_len_for_signature(&self) -> usize {
+        self.tx.payload_len_for_signature()
+    }
+}
+
+impl Transaction for TxEip4844WithSidecarVariant {
+    #[inline]
+    fn chain_id(&self) -> Option<ChainId> {
+        self.tx.chain_id()
+    }
+
+    #[inline]
+    fn nonce(&self) -> u64 {
+        self.tx.nonce()
+    }
+
+    #[inline]
+    fn gas_limit(&self) -> u64 {
+        self.tx.gas_limit()
+    }
+
+    #[inline]
+    fn gas_price(&self) -> Option<u128> {
+        self.tx.gas_price()
+    }
+
+    #[inline]
+    fn max_fee_per_gas(&self) -> u128 {
+        self.tx.max_fee_per_gas()
+    }
+
+    #[inline]
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.tx.max_priority_fee_per_gas()
+    }
+
+    #[inline]
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.tx.max_fee_per_blob_gas()
+    }
+
+    #[inline]
+    fn priority_fee_or_price(&self) -> u128 {
+        self.tx.priority_fee_or_price()
+    }
+
+    fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        self.tx.effective_gas_price(base_fee)
+    }
+
+    #[inline]
+    fn is_dynamic_fee(&self) -> bool {
+        self.tx.is_dynamic_fee()
+    }
+
+    #[inline]
+    fn kind(&self) -> TxKind {
+        self.tx.kind()
+    }
+
+    #[inline]
+    fn is_create(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn value(&self) -> U256 {
+        self.tx.value()
+    }
+
+    #[inline]
+    fn input(&self) -> &Bytes {
+        self.tx.input()
+    }
+
+    #[inline]
+    fn access_list(&self) -> Option<&AccessList> {
+        self.tx.access_list()
+    }
+
+    #[inline]
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        self.tx.blob_versioned_hashes()
+    }
+
+    #[inline]
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        None
+    }
+}
+
+impl Typed2718 for TxEip4844WithSidecarVariant {
+    fn ty(&self) -> u8 {
+        TxType::Eip4844 as u8
+    }
+}
+
+impl RlpEcdsaEncodableTx for TxEip4844WithSidecarVariant {
+    fn rlp_encoded_fields_length(&self) -> usize {
+        self.sidecar.rlp_encoded_fields_length() + self.tx.rlp_encoded_length()
+    }
+
+    fn rlp_encode_fields(&self, out: &mut dyn BufMut) {
+        self.tx.rlp_encode(out);
+        self.sidecar.rlp_encode_fields(out);
+    }
+
+    fn rlp_header_signed(&self, signature: &Signature) -> Header {
+        let payload_length = self.tx.rlp_encoded_length_with_signature(signature) +
+            self.sidecar.rlp_encoded_fields_length();
+        Header { list: true, payload_length }
+    }
+
+    fn rlp_encode_signed(&self, signature: &Signature, out: &mut dyn BufMut) {
+        self.rlp_header_signed(signature).encode(out);
+        self.tx.rlp_encode_signed(signature, out);
+        self.sidecar.rlp_encode_fields(out);
+    }
+
+    fn tx_hash_with_type(&self, signature: &Signature, ty: u8) -> alloy_primitives::TxHash {
+        // the hash is always based on the encoding without the sidecar
+        self.tx.tx_hash_with_type(signature, ty)
+    }
+}
+
+impl RlpEcdsaDecodableTx for TxEip4844WithSidecarVariant {
+    const DEFAULT_TX_TYPE: u8 = TxType::Eip4844 as u8;
+
+    fn rlp_decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let tx = TxEip4844::rlp_decode(buf)?;
+        let sidecar = BlobTransactionSidecarVariant::rlp_decode_fields(buf)?;
+        Ok(Self { tx, sidecar })
+    }
+
+    fn rlp_decode_with_signature(buf: &mut &[u8]) -> alloy_rlp::Result<(Self, Signature)> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let remaining = buf.len();
+
+        let (tx, signature) = TxEip4844::rlp_decode_with_signature(buf)?;
+        let sidecar = BlobTransactionSidecarVariant::rlp_decode_fields(buf)?;
+
+        if buf.len() + header.payload_length != remaining {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+
+        Ok((Self { tx, sidecar }, signature))
+    }
+}
+
+impl InMemorySize for TxEip4844WithSidecarVariant {
+    fn size(&self) -> usize {
+        self.tx.size() + self.sidecar.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransactionSigned, CELLS_PER_EXT_BLOB};
+    use alloy_consensus::Signed;
+    use alloy_eips::{
+        eip2718::{Decodable2718, Encodable2718},
+        eip4844::{Blob, Bytes48},
+    };
+    use alloy_primitives::Address;
+    use alloy_rlp::{Decodable, Encodable};
+    use reth_primitives_traits::SignedTransaction;
+
+    fn signed_blob_transaction(
+        sidecar: impl Into<BlobTransactionSidecarVariant>,
+    ) -> PooledTransaction {
+        let tx = TxEip4844 {
+            chain_id: 1,
+            to: Address::repeat_byte(0x11),
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            max_fee_per_blob_gas: 1,
+            blob_versioned_hashes: vec![B256::repeat_byte(0x01)],
+            ..Default::default()
+        };
+        let signature = Signature::test_signature();
+        let hash = tx.tx_hash(&signature);
+        PooledTransaction::Eip4844(Signed::new_unchecked(
+            TxEip4844WithSidecarVariant::from_tx_and_sidecar(tx, sidecar),
+            signature,
+            hash,
+        ))
+    }
+
+    #[test]
+    fn pooled_blob_transaction_roundtrip() {
+        let blobs = vec![Blob::repeat_byte(0x01)];
+        let commitments = vec![Bytes48::repeat_byte(0x02)];
+        let eip4844 = BlobTransactionSidecar {
+            blobs: blobs.clone(),
+            commitments: commitments.clone(),
+            proofs: vec![Bytes48::repeat_byte(0x03)],
+        };
+        let eip7594 = BlobTransactionSidecarEip7594 {
+            blobs,
+            commitments,
+            cell_proofs: vec![Bytes48::repeat_byte(0x04); CELLS_PER_EXT_BLOB],
+        };
+
+        let v0 = signed_blob_transaction(eip4844);
+        let v1 = signed_blob_transaction(eip7594);
+        // the sidecar is not part of the transaction hash
+        assert_eq!(v0.tx_hash(), v1.tx_hash());
+
+        for tx in [v0, v1] {
+            let encoded = tx.encoded_2718();
+            assert_eq!(encoded.len(), tx.encode_2718_len());
+            assert_eq!(PooledTransaction::decode_2718(&mut encoded.as_slice()).unwrap(), tx);
+
+            let mut network = Vec::new();
+            tx.encode(&mut network);
+            assert_eq!(network.len(), tx.length());
+            assert_eq!(PooledTransaction::decode(&mut network.as_slice()).unwrap(), tx);
+
+            // the consensus encoding drops the sidecar
+            let consensus = TransactionSigned::from(tx.clone());
+            assert_eq!(consensus.tx_hash(), tx.tx_hash());
+            assert!(consensus.encode_2718_len() < tx.encode_2718_len());
+        }
+    }
+
+    #[test]
+    fn decode_alloy_pooled_transaction() {
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(0x01)],
+            commitments: vec![Bytes48::repeat_byte(0x02)],
+            proofs: vec![Bytes48::repeat_byte(0x03)],
+        };
+        let tx = signed_blob_transaction(sidecar.clone());
+        let PooledTransaction::Eip4844(signed) = &tx else { unreachable!() };
+        let alloy =
+            alloy_consensus::transaction::PooledTransaction::Eip4844(Signed::new_unchecked(
+                TxEip4844WithSidecar { tx: signed.tx().tx.clone(), sidecar },
+                *signed.signature(),
+                *signed.hash(),
+            ));
+
+        // sidecars with one proof per blob are encoded the same way
+        assert_eq!(alloy.encoded_2718(), tx.encoded_2718());
+    }
+}
@@ -0,0 +1,455 @@
+//! Blob sidecars with cell proofs, introduced with peer data availability sampling in Osaka.
+//!
+//! See also [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594)
+
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "kzg")]
+use alloy_eips::eip4844::{env_settings::KzgSettings, BlobTransactionValidationError};
+use alloy_eips::eip4844::{
+    kzg_to_versioned_hash, Blob, BlobAndProofV1, BlobAndProofV2, BlobTransactionSidecar, Bytes48,
+    BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_PROOF,
+};
+use alloy_primitives::B256;
+use alloy_rlp::{BufMut, Decodable, Encodable, EMPTY_LIST_CODE};
+
+/// The number of cells of an extended blob, and thus the number of cell proofs per blob.
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+
+/// The version byte that precedes the fields of a [`BlobTransactionSidecarEip7594`] when encoded.
+pub const EIP_7594_WRAPPER_VERSION: u8 = 1;
+
+/// A set of blobs along with their commitments and [`CELLS_PER_EXT_BLOB`] cell proofs per blob.
+///
+/// This replaces the single blob proof of a [`BlobTransactionSidecar`] once Osaka is active.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlobTransactionSidecarEip7594 {
+    /// The blob data.
+    pub blobs: Vec<Blob>,
+    /// The blob commitments.
+    pub commitments: Vec<Bytes48>,
+    /// The cell proofs of all blobs, [`CELLS_PER_EXT_BLOB`] consecutive proofs per blob.
+    pub cell_proofs: Vec<Bytes48>,
+}
+
+impl core::fmt::Debug for BlobTransactionSidecarEip7594 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlobTransactionSidecarEip7594")
+            .field("blobs", &self.blobs.len())
+            .field("commitments", &self.commitments)
+            .field("cell_proofs", &self.cell_proofs.len())
+            .finish()
+    }
+}
+
+impl BlobTransactionSidecarEip7594 {
+    /// Converts a [`BlobTransactionSidecar`] by computing the cell proofs of its blobs.
+    ///
+    /// Note: this is expensive and expects that the sidecar was already validated.
+    #[cfg(feature = "kzg")]
+    pub fn try_from_eip4844(
+        sidecar: BlobTransactionSidecar,
+        settings: &KzgSettings,
+    ) -> Result<Self, c_kzg::Error> {
+        let mut cell_proofs = Vec::with_capacity(sidecar.blobs.len() * CELLS_PER_EXT_BLOB);
+        for blob in &sidecar.blobs {
+            let blob = as_kzg_blob(blob);
+            let (_, proofs) = settings.compute_cells_and_kzg_proofs(blob)?;
+            cell_proofs
+                .extend(proofs.iter().map(|proof| Bytes48::from(proof.to_bytes().into_inner())));
+        }
+
+        Ok(Self { blobs: sidecar.blobs, commitments: sidecar.commitments, cell_proofs })
+    }
+
+    /// Converts this sidecar into a [`BlobTransactionSidecar`] by computing the blob proofs.
+    ///
+    /// This is required to propagate the transaction with the pooled transaction format, which
+    /// only supports one proof per blob.
+    #[cfg(feature = "kzg")]
+    pub fn try_into_eip4844(
+        self,
+        settings: &KzgSettings,
+    ) -> Result<BlobTransactionSidecar, c_kzg::Error> {
+        let proofs = self
+            .blobs
+            .iter()
+            .zip(&self.commitments)
+            .map(|(blob, commitment)| {
+                let blob = as_kzg_blob(blob);
+                let commitment = c_kzg::Bytes48::from_bytes(commitment.as_slice())?;
+                settings
+                    .compute_blob_kzg_proof(blob, &commitment)
+                    .map(|proof| Bytes48::from(proof.to_bytes().into_inner()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(BlobTransactionSidecar { blobs: self.blobs, commitments: self.commitments, proofs })
+    }
+
+    /// Verifies that the versioned hashes match the commitments of this sidecar, and that all cell
+    /// proofs are valid for the cells of the blobs.
+    #[cfg(feature = "kzg")]
+    pub fn validate(
+        &self,
+        blob_versioned_hashes: &[B256],
+        settings: &KzgSettings,
+    ) -> Result<(), BlobTransactionValidationError> {
+        if blob_versioned_hashes.len() != self.commitments.len() ||
+            self.blobs.len() != self.commitments.len()
+        {
+            return Err(c_kzg::Error::MismatchLength(alloc::format!(
+                "There are {} versioned commitment hashes, {} blobs and {} commitments",
+                blob_versioned_hashes.len(),
+                self.blobs.len(),
+                self.commitments.len()
+            ))
+            .into())
+        }
+
+        if self.cell_proofs.len() != self.blobs.len() * CELLS_PER_EXT_BLOB {
+            return Err(c_kzg::Error::MismatchLength(alloc::format!(
+                "There are {} cell proofs for {} blobs",
+                self.cell_proofs.len(),
+                self.blobs.len()
+            ))
+            .into())
+        }
+
+        for (versioned_hash, commitment) in blob_versioned_hashes.iter().zip(&self.commitments) {
+            let calculated_versioned_hash = kzg_to_versioned_hash(commitment.as_slice());
+            if *versioned_hash != calculated_versioned_hash {
+                return Err(BlobTransactionValidationError::WrongVersionedHash {
+                    have: *versioned_hash,
+                    expected: calculated_versioned_hash,
+                })
+            }
+        }
+
+        // every cell is verified against the commitment of its blob
+        let num_cells = self.cell_proofs.len();
+        let mut commitments = Vec::with_capacity(num_cells);
+        let mut cell_indices = Vec::with_capacity(num_cells);
+        let mut cells = Vec::with_capacity(num_cells);
+        for (blob, commitment) in self.blobs.iter().zip(&self.commitments) {
+            let blob = as_kzg_blob(blob);
+            let commitment = c_kzg::Bytes48::from_bytes(commitment.as_slice())?;
+            cells.extend(settings.compute_cells(blob)?.iter().copied());
+            commitments.extend(core::iter::repeat_n(commitment, CELLS_PER_EXT_BLOB));
+            cell_indices.extend(0..CELLS_PER_EXT_BLOB as u64);
+        }
+        let proofs = self
+            .cell_proofs
+            .iter()
+            .map(|proof| c_kzg::Bytes48::from_bytes(proof.as_slice()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        settings
+            .verify_cell_kzg_proof_batch(&commitments, &cell_indices, &cells, &proofs)?
+            .then_some(())
+            .ok_or(BlobTransactionValidationError::InvalidProof)
+    }
+
+    /// Returns an iterator over the versioned hashes of the commitments.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        self.commitments.iter().map(|c| kzg_to_versioned_hash(c.as_slice()))
+    }
+
+    /// Matches versioned hashes and returns an iterator of (index, [`BlobAndProofV2`]) pairs
+    /// where index is the position in `versioned_hashes` that matched the versioned hash in the
+    /// sidecar.
+    ///
+    /// This is used for the `engine_getBlobsV2` RPC endpoint of the engine API
+    pub fn match_versioned_hashes<'a>(
+        &'a self,
+        versioned_hashes: &'a [B256],
+    ) -> impl Iterator<Item = (usize, BlobAndProofV2)> + 'a {
+        self.versioned_hashes().enumerate().flat_map(move |(i, blob_versioned_hash)| {
+            versioned_hashes.iter().enumerate().filter_map(move |(j, target_hash)| {
+                if blob_versioned_hash != *target_hash {
+                    return None
+                }
+                let blob = self.blobs.get(i)?;
+                let proofs =
+                    self.cell_proofs.get(i * CELLS_PER_EXT_BLOB..(i + 1) * CELLS_PER_EXT_BLOB)?;
+                Some((j, BlobAndProofV2 { blob: Box::new(*blob), proofs: proofs.to_vec() }))
+            })
+        })
+    }
+
+    /// Calculates a size heuristic for the in-memory size of the [`BlobTransactionSidecarEip7594`].
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.blobs.len() * BYTES_PER_BLOB + // blobs
+            self.commitments.len() * BYTES_PER_COMMITMENT + // commitments
+            self.cell_proofs.len() * BYTES_PER_PROOF // cell proofs
+    }
+
+    /// Outputs the RLP length of the version byte and the fields, without a RLP header.
+    pub fn rlp_encoded_fields_length(&self) -> usize {
+        EIP_7594_WRAPPER_VERSION.length() +
+            self.blobs.length() +
+            self.commitments.length() +
+            self.cell_proofs.length()
+    }
+
+    /// Encodes the version byte and the fields as RLP bytes, __without__ a RLP header.
+    ///
+    /// This encodes the fields in the following order:
+    /// - `wrapper_version`
+    /// - `blobs`
+    /// - `commitments`
+    /// - `cell_proofs`
+    pub fn rlp_encode_fields(&self, out: &mut dyn BufMut) {
+        EIP_7594_WRAPPER_VERSION.encode(out);
+        self.blobs.encode(out);
+        self.commitments.encode(out);
+        self.cell_proofs.encode(out);
+    }
+
+    /// RLP decodes the version byte and the fields of a [`BlobTransactionSidecarEip7594`].
+    pub fn rlp_decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if u8::decode(buf)? != EIP_7594_WRAPPER_VERSION {
+            return Err(alloy_rlp::Error::Custom("unsupported blob sidecar wrapper version"))
+        }
+
+        Ok(Self {
+            blobs: Decodable::decode(buf)?,
+            commitments: Decodable::decode(buf)?,
+            cell_proofs: Decodable::decode(buf)?,
+        })
+    }
+}
+
+/// A blob sidecar of either format.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum BlobTransactionSidecarVariant {
+    /// A sidecar with one proof per blob.
+    Eip4844(BlobTransactionSidecar),
+    /// A sidecar with cell proofs.
+    Eip7594(BlobTransactionSidecarEip7594),
+}
+
+impl BlobTransactionSidecarVariant {
+    /// Returns the [`BlobTransactionSidecar`] if this is a sidecar with one proof per blob.
+    pub const fn as_eip4844(&self) -> Option<&BlobTransactionSidecar> {
+        match self {
+            Self::Eip4844(sidecar) => Some(sidecar),
+            Self::Eip7594(_) => None,
+        }
+    }
+
+    /// Returns the [`BlobTransactionSidecarEip7594`] if this is a sidecar with cell proofs.
+    pub const fn as_eip7594(&self) -> Option<&BlobTransactionSidecarEip7594> {
+        match self {
+            Self::Eip4844(_) => None,
+            Self::Eip7594(sidecar) => Some(sidecar),
+        }
+    }
+
+    /// Returns `true` if this is a sidecar with cell proofs.
+    pub const fn is_eip7594(&self) -> bool {
+        matches!(self, Self::Eip7594(_))
+    }
+
+    /// Returns the blobs of the sidecar.
+    pub fn blobs(&self) -> &[Blob] {
+        match self {
+            Self::Eip4844(sidecar) => &sidecar.blobs,
+            Self::Eip7594(sidecar) => &sidecar.blobs,
+        }
+    }
+
+    /// Returns the commitments of the sidecar.
+    pub fn commitments(&self) -> &[Bytes48] {
+        match self {
+            Self::Eip4844(sidecar) => &sidecar.commitments,
+            Self::Eip7594(sidecar) => &sidecar.commitments,
+        }
+    }
+
+    /// Returns an iterator over the versioned hashes of the commitments.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        self.commitments().iter().map(|c| kzg_to_versioned_hash(c.as_slice()))
+    }
+
+    /// Verifies the sidecar against the versioned hashes of the transaction, see
+    /// [`BlobTransactionSidecar::validate`] and [`BlobTransactionSidecarEip7594::validate`].
+    #[cfg(feature = "kzg")]
+    pub fn validate(
+        &self,
+        blob_versioned_hashes: &[B256],
+        settings: &KzgSettings,
+    ) -> Result<(), BlobTransactionValidationError> {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.validate(blob_versioned_hashes, settings),
+            Self::Eip7594(sidecar) => sidecar.validate(blob_versioned_hashes, settings),
+        }
+    }
+
+    /// Converts the sidecar into a [`BlobTransactionSidecar`], computing the blob proofs if this is
+    /// a sidecar with cell proofs.
+    #[cfg(feature = "kzg")]
+    pub fn try_into_eip4844(
+        self,
+        settings: &KzgSettings,
+    ) -> Result<BlobTransactionSidecar, c_kzg::Error> {
+        match self {
+            Self::Eip4844(sidecar) => Ok(sidecar),
+            Self::Eip7594(sidecar) => sidecar.try_into_eip4844(settings),
+        }
+    }
+
+    /// Matches versioned hashes against a sidecar with one proof per blob, see
+    /// [`BlobTransactionSidecar::match_versioned_hashes`].
+    ///
+    /// Sidecars with cell proofs don't match any versioned hash.
+    pub fn match_versioned_hashes_v1<'a>(
+        &'a self,
+        versioned_hashes: &'a [B256],
+    ) -> impl Iterator<Item = (usize, BlobAndProofV1)> + 'a {
+        self.as_eip4844()
+            .into_iter()
+            .flat_map(move |sidecar| sidecar.match_versioned_hashes(versioned_hashes))
+    }
+
+    /// Matches versioned hashes against a sidecar with cell proofs, see
+    /// [`BlobTransactionSidecarEip7594::match_versioned_hashes`].
+    ///
+    /// Sidecars with one proof per blob don't match any versioned hash.
+    pub fn match_versioned_hashes_v2<'a>(
+        &'a self,
+        versioned_hashes: &'a [B256],
+    ) -> impl Iterator<Item = (usize, BlobAndProofV2)> + 'a {
+        self.as_eip7594()
+            .into_iter()
+            .flat_map(move |sidecar| sidecar.match_versioned_hashes(versioned_hashes))
+    }
+
+    /// Calculates a size heuristic for the in-memory size of the sidecar.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.size(),
+            Self::Eip7594(sidecar) => sidecar.size(),
+        }
+    }
+
+    /// Outputs the RLP length of the sidecar fields, without a RLP header.
+    pub fn rlp_encoded_fields_length(&self) -> usize {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.rlp_encoded_fields_length(),
+            Self::Eip7594(sidecar) => sidecar.rlp_encoded_fields_length(),
+        }
+    }
+
+    /// Encodes the sidecar fields as RLP bytes, __without__ a RLP header.
+    ///
+    /// The fields of a [`BlobTransactionSidecarEip7594`] are preceded by its version byte.
+    pub fn rlp_encode_fields(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.rlp_encode_fields(out),
+            Self::Eip7594(sidecar) => sidecar.rlp_encode_fields(out),
+        }
+    }
+
+    /// RLP decodes the sidecar fields.
+    ///
+    /// The fields of a [`BlobTransactionSidecar`] start with the list of blobs, while the fields of
+    /// a [`BlobTransactionSidecarEip7594`] start with its version byte.
+    pub fn rlp_decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match buf.first() {
+            Some(&first) if first >= EMPTY_LIST_CODE => {
+                BlobTransactionSidecar::rlp_decode_fields(buf).map(Self::Eip4844)
+            }
+            Some(_) => BlobTransactionSidecarEip7594::rlp_decode_fields(buf).map(Self::Eip7594),
+            None => Err(alloy_rlp::Error::InputTooShort),
+        }
+    }
+}
+
+impl From<BlobTransactionSidecar> for BlobTransactionSidecarVariant {
+    fn from(sidecar: BlobTransactionSidecar) -> Self {
+        Self::Eip4844(sidecar)
+    }
+}
+
+impl From<BlobTransactionSidecarEip7594> for BlobTransactionSidecarVariant {
+    fn from(sidecar: BlobTransactionSidecarEip7594) -> Self {
+        Self::Eip7594(sidecar)
+    }
+}
+
+/// Reinterprets a [`Blob`] as a [`c_kzg::Blob`], without copying the blob onto the stack.
+#[cfg(feature = "kzg")]
+const fn as_kzg_blob(blob: &Blob) -> &c_kzg::Blob {
+    // SAFETY: both types are a plain array of `BYTES_PER_BLOB` bytes with the same alignment
+    unsafe { &*(blob as *const Blob).cast::<c_kzg::Blob>() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "kzg")]
+    fn eip4844_sidecar() -> BlobTransactionSidecar {
+        // the last byte of every field element is set, so that the cells of the blob differ
+        let blob: Vec<u8> =
+            (0..BYTES_PER_BLOB).map(|i| if i % 32 == 31 { (i / 32) as u8 } else { 0 }).collect();
+        BlobTransactionSidecar::try_from_blobs_bytes([blob]).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn convert_and_validate_cell_proofs() {
+        let settings = alloy_eips::eip4844::env_settings::EnvKzgSettings::Default;
+        let sidecar = eip4844_sidecar();
+        let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+
+        let eip7594 =
+            BlobTransactionSidecarEip7594::try_from_eip4844(sidecar.clone(), settings.get())
+                .unwrap();
+        assert_eq!(eip7594.cell_proofs.len(), CELLS_PER_EXT_BLOB);
+        eip7594.validate(&versioned_hashes, settings.get()).unwrap();
+
+        let mut invalid = eip7594.clone();
+        invalid.cell_proofs.swap(0, 1);
+        assert!(invalid.validate(&versioned_hashes, settings.get()).is_err());
+
+        let (index, blob_and_proof) =
+            eip7594.match_versioned_hashes(&versioned_hashes).next().unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(blob_and_proof.proofs, eip7594.cell_proofs);
+
+        assert_eq!(eip7594.try_into_eip4844(settings.get()).unwrap(), sidecar);
+    }
+
+    #[test]
+    fn variant_fields_roundtrip() {
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(0x01)],
+            commitments: vec![Bytes48::repeat_byte(0x02)],
+            proofs: vec![Bytes48::repeat_byte(0x03)],
+        };
+        let eip7594 = BlobTransactionSidecarEip7594 {
+            blobs: sidecar.blobs.clone(),
+            commitments: sidecar.commitments.clone(),
+            cell_proofs: vec![Bytes48::repeat_byte(0x42); CELLS_PER_EXT_BLOB],
+        };
+
+        for variant in [
+            BlobTransactionSidecarVariant::Eip4844(sidecar),
+            BlobTransactionSidecarVariant::Eip7594(eip7594),
+        ] {
+            let mut buf = Vec::with_capacity(variant.rlp_encoded_fields_length());
+            variant.rlp_encode_fields(&mut buf);
+            assert_eq!(buf.len(), variant.rlp_encoded_fields_length());
+
+            let decoded = BlobTransactionSidecarVariant::rlp_decode_fields(&mut buf.as_slice());
+            assert_eq!(decoded.unwrap(), variant);
+        }
+    }
+}
@@ -1,9 +1,10 @@
+use crate::{BlobTransactionSidecarVariant, PooledTransaction, TxEip4844WithSidecarVariant};
 use alloc::vec::Vec;
-pub use alloy_consensus::{transaction::PooledTransaction, TxType};
+pub use alloy_consensus::TxType;
 use alloy_consensus::{
     transaction::{RlpEcdsaDecodableTx, RlpEcdsaEncodableTx},
-    BlobTransactionSidecar, EthereumTxEnvelope, SignableTransaction, Signed, TxEip1559, TxEip2930,
-    TxEip4844, TxEip4844Variant, TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, Typed2718,
+    EthereumTxEnvelope, SignableTransaction, Signed, TxEip1559, TxEip2930, TxEip4844,
+    TxEip4844Variant, TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, Typed2718,
     TypedTransaction,
 };
 use alloy_eips::{
@@ -429,7 +430,7 @@ impl TransactionSigned {
     /// EIP-4844.
     pub fn try_into_pooled_eip4844(
         self,
-        sidecar: BlobTransactionSidecar,
+        sidecar: impl Into<BlobTransactionSidecarVariant>,
     ) -> Result<PooledTransaction, Self> {
         let hash = *self.tx_hash();
         Ok(match self {
@@ -437,7 +438,7 @@ impl TransactionSigned {
             Self { transaction: Transaction::Eip4844(tx), signature, .. } => {
                 // Construct a pooled eip488 tx with the provided sidecar.
                 PooledTransaction::Eip4844(Signed::new_unchecked(
-                    TxEip4844WithSidecar { tx, sidecar },
+                    TxEip4844WithSidecarVariant::from_tx_and_sidecar(tx, sidecar),
                     signature,
                     hash,
                 ))
@@ -561,6 +562,13 @@ impl From<Signed<TxEip4844WithSidecar>> for TransactionSigned {
     }
 }
 
+impl From<Signed<TxEip4844WithSidecarVariant>> for TransactionSigned {
+    fn from(value: Signed<TxEip4844WithSidecarVariant>) -> Self {
+        let (tx, sig, hash) = value.into_parts();
+        Self::new(tx.tx.into(), sig, hash)
+    }
+}
+
 impl From<TxEip4844Variant> for Transaction {
     fn from(variant: TxEip4844Variant) -> Self {
         match variant {
@@ -962,10 +970,7 @@ impl From<PooledTransaction> for TransactionSigned {
             PooledTransaction::Eip2930(tx) => tx.into(),
             PooledTransaction::Eip1559(tx) => tx.into(),
             PooledTransaction::Eip7702(tx) => tx.into(),
-            PooledTransaction::Eip4844(tx) => {
-                let (tx, signature, hash) = tx.into_parts();
-                Signed::new_unchecked(tx.tx, signature, hash).into()
-            }
+            PooledTransaction::Eip4844(tx) => tx.into(),
         }
     }
 }
@@ -373,6 +373,14 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Sets the external address of the node, e.g. after ports were mapped on the gateway.
+    ///
+    /// This will update our [`NodeRecord`] and the EIP-868 [`Enr`] with the external ip and ports.
+    pub fn set_external_addr(&self, ip: IpAddr, tcp_port: u16, udp_port: u16) {
+        let cmd = Discv4Command::SetExternalAddr { ip, tcp_port, udp_port };
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -633,6 +641,21 @@ impl Discv4Service {
         }
     }
 
+    /// Sets the external ip address and ports of the node in the node record announced in
+    /// discovery
+    pub fn set_external_addr(&mut self, ip: IpAddr, tcp_port: u16, udp_port: u16) {
+        let record = NodeRecord { address: ip, tcp_port, udp_port, ..self.local_node_record };
+        if self.local_node_record != record {
+            debug!(target: "discv4", %ip, %tcp_port, %udp_port, "Updating external address");
+            self.local_node_record = record;
+            let _ = self.local_eip_868_enr.set_tcp_socket(record.tcp_addr(), &self.secret_key);
+            let _ = self.local_eip_868_enr.set_udp_socket(record.udp_addr(), &self.secret_key);
+            let mut lock = self.shared_node_record.lock();
+            *lock = self.local_node_record;
+            debug!(target: "discv4", enr=?self.local_eip_868_enr, "Updated local ENR");
+        }
+    }
+
     /// Returns the [`PeerId`] that identifies this node
     pub const fn local_peer_id(&self) -> &PeerId {
         &self.local_node_record.id
@@ -1326,6 +1349,7 @@ impl Discv4Service {
             if resp.echo_hash == msg.request_hash {
                 let key = kad_key(id);
                 let fork_id = msg.eth_fork_id();
+                self.notify(DiscoveryUpdate::Enr(msg.enr));
                 let (record, old_fork_id) = match self.kbuckets.entry(&key) {
                     kbucket::Entry::Present(mut entry, _) => {
                         let id = entry.value_mut().update_with_fork_id(fork_id);
@@ -1750,6 +1774,9 @@ impl Discv4Service {
                             let _ = self.local_eip_868_enr.set_tcp6(port, &self.secret_key);
                         }
                     }
+                    Discv4Command::SetExternalAddr { ip, tcp_port, udp_port } => {
+                        self.set_external_addr(ip, tcp_port, udp_port);
+                    }
 
                     Discv4Command::Terminated => {
                         // terminate the service
@@ -2031,6 +2058,7 @@ impl Default for ReceiveCache {
 enum Discv4Command {
     Add(NodeRecord),
     SetTcpPort(u16),
+    SetExternalAddr { ip: IpAddr, tcp_port: u16, udp_port: u16 },
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
@@ -2385,6 +2413,8 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the signed [`Enr`] of a node via EIP-868.
+    Enr(Enr<SecretKey>),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
@@ -2798,6 +2828,24 @@ mod tests {
         let _ = discv4.lookup_self().await;
     }
 
+    #[tokio::test]
+    async fn test_set_external_addr() {
+        reth_tracing::init_test_tracing();
+
+        let config = Discv4Config::builder().enable_eip868(true).build();
+        let (discv4, service) = create_discv4_with_config(config).await;
+        let _handle = service.spawn();
+
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        discv4.set_external_addr(ip, 40303, 40304);
+        // commands are processed in order, so the address is set once the lookup returns
+        let _ = discv4.lookup_self().await;
+        let record = discv4.node_record();
+        assert_eq!(record.address, ip);
+        assert_eq!(record.tcp_port, 40303);
+        assert_eq!(record.udp_port, 40304);
+    }
+
     #[tokio::test]
     async fn test_requests_timeout() {
         reth_tracing::init_test_tracing();
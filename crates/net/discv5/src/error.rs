@@ -27,6 +27,12 @@ pub enum Error {
     /// An error from underlying [`discv5::Discv5`] node.
     #[error("sigp/discv5 error, {0}")]
     Discv5Error(discv5::Error),
+    /// Failed to update the local [`Enr`](discv5::Enr).
+    #[error("failed to update local enr, {0}")]
+    LocalEnrUpdateFailed(discv5::enr::Error),
+    /// A lookup query of [`discv5::Discv5`] failed.
+    #[error("lookup query failed, {0:?}")]
+    LookupFailed(discv5::QueryError),
     /// The [`ListenConfig`](discv5::ListenConfig) has been misconfigured.
     #[error("misconfigured listen config, RLPx TCP address must also be supported by discv5")]
     ListenConfigMisconfigured,
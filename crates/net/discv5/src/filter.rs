@@ -2,6 +2,7 @@
 
 use std::collections::HashSet;
 
+use alloy_primitives::Bytes;
 use derive_more::Constructor;
 use itertools::Itertools;
 
@@ -87,6 +88,49 @@ impl MustNotIncludeKeys {
     }
 }
 
+/// Filter requiring that peers advertise a kv-pair with a certain key and, optionally, a certain
+/// value. Unlike [`MustIncludeKey`], the key can be chosen at runtime, e.g. to find peers that
+/// run a custom `RLPx` subprotocol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MustIncludeKeyValue {
+    /// Kv-pair key which node record must advertise.
+    key: Vec<u8>,
+    /// Rlp encoded value the kv-pair must have, any value matches if `None`.
+    value: Option<Bytes>,
+}
+
+impl MustIncludeKeyValue {
+    /// Returns a new instance that requires a kv-pair with the given key and any value.
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { key: key.into(), value: None }
+    }
+
+    /// Additionally requires the kv-pair to have the given value.
+    pub fn with_value(mut self, value: impl alloy_rlp::Encodable) -> Self {
+        self.value = Some(alloy_rlp::encode(value).into());
+        self
+    }
+
+    /// Returns `true` if [`Enr`](discv5::Enr) contains the configured kv-pair.
+    pub fn matches(&self, enr: &discv5::Enr) -> bool {
+        match (enr.get_raw_rlp(&self.key), &self.value) {
+            (Some(rlp), Some(value)) => rlp == value.as_ref(),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns [`FilterOutcome::Ok`] if [`Enr`](discv5::Enr) contains the configured kv-pair.
+    pub fn filter(&self, enr: &discv5::Enr) -> FilterOutcome {
+        if !self.matches(enr) {
+            return FilterOutcome::Ignore {
+                reason: format!("{} kv-pair required", String::from_utf8_lossy(&self.key)),
+            }
+        }
+        FilterOutcome::Ok
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +163,29 @@ mod tests {
         assert!(matches!(filter.filter(&enr_1), FilterOutcome::Ignore { .. }));
         assert!(matches!(filter.filter(&enr_2), FilterOutcome::Ignore { .. }));
     }
+
+    #[test]
+    fn must_include_key_value_filter() {
+        const KEY: &[u8] = b"myproto";
+
+        let filter = MustIncludeKeyValue::new(KEY).with_value(2u64);
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr_match =
+            Enr::builder().add_value_rlp(KEY, alloy_rlp::encode(2u64).into()).build(&sk).unwrap();
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr_other_value =
+            Enr::builder().add_value_rlp(KEY, alloy_rlp::encode(1u64).into()).build(&sk).unwrap();
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr_missing = Enr::builder().build(&sk).unwrap();
+
+        assert!(filter.filter(&enr_match).is_ok());
+        assert!(!filter.filter(&enr_other_value).is_ok());
+        assert!(!filter.filter(&enr_missing).is_ok());
+
+        // any value matches if none is configured
+        assert!(MustIncludeKeyValue::new(KEY).matches(&enr_other_value));
+    }
 }
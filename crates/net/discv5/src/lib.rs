@@ -35,6 +35,7 @@ pub mod error;
 pub mod filter;
 pub mod metrics;
 pub mod network_stack_id;
+pub mod subscription;
 
 pub use discv5::{self, IpMode};
 
@@ -45,8 +46,9 @@ pub use config::{
 };
 pub use enr::enr_to_discv4_id;
 pub use error::Error;
-pub use filter::{FilterOutcome, MustNotIncludeKeys};
+pub use filter::{FilterOutcome, MustIncludeKeyValue, MustNotIncludeKeys};
 pub use network_stack_id::NetworkStackId;
+pub use subscription::{DiscoveredEnr, EnrSubscriptions, DEFAULT_ENR_SUBSCRIPTION_BUFFER};
 
 use metrics::{DiscoveredPeersMetrics, Discv5Metrics};
 
@@ -75,6 +77,8 @@ pub struct Discv5 {
     discovered_peer_filter: MustNotIncludeKeys,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
+    /// Subscriptions to discovered peers matching a predicate on their node record.
+    enr_subscriptions: EnrSubscriptions,
 }
 
 impl Discv5 {
@@ -122,6 +126,89 @@ impl Discv5 {
         self.set_eip868_in_local_enr(key, buf.into())
     }
 
+    /// Inserts or updates a custom kv-pair in the local [`Enr`] of the node, at runtime.
+    ///
+    /// The value is rlp encoded. The updated record is advertised to peers from now on, so other
+    /// nodes can find this node with a [`MustIncludeKeyValue`] filter on the same key.
+    pub fn set_enr_kv_pair(
+        &self,
+        key: &str,
+        value: &impl alloy_rlp::Encodable,
+    ) -> Result<(), Error> {
+        self.discv5.enr_insert(key, value).map_err(Error::LocalEnrUpdateFailed)?;
+
+        trace!(target: "net::discv5",
+            key,
+            "updated local enr kv-pair"
+        );
+
+        Ok(())
+    }
+
+    /// Returns the decoded value of the kv-pair with the given key in the local [`Enr`] of the
+    /// node, if set.
+    pub fn local_enr_kv_pair<T: alloy_rlp::Decodable>(
+        &self,
+        key: &[u8],
+    ) -> Option<Result<T, alloy_rlp::Error>> {
+        self.discv5.local_enr().get_decodable::<T>(key)
+    }
+
+    /// Returns a receiver of all peers discovered from now on, whose [`Enr`](discv5::Enr)
+    /// matches the predicate.
+    ///
+    /// Matching is independent of the filter applied before passing discovered peers up to
+    /// `RLPx`, so nodes on the same network that run a custom subprotocol can be found by a key
+    /// they advertise, see [`MustIncludeKeyValue`]. Unreachable peers are skipped.
+    pub fn subscribe_discovered_peers(
+        &self,
+        predicate: impl Fn(&discv5::Enr) -> bool + Send + Sync + 'static,
+    ) -> mpsc::Receiver<DiscoveredEnr> {
+        self.enr_subscriptions.subscribe(predicate, DEFAULT_ENR_SUBSCRIPTION_BUFFER)
+    }
+
+    /// Returns the peers whose [`Enr`](discv5::Enr) matches the predicate, from the kbuckets
+    /// and from a lookup query for up to `count` more matching peers.
+    ///
+    /// The peers returned by the lookup query are also passed to active subscriptions.
+    pub async fn find_matching_peers(
+        &self,
+        predicate: impl Fn(&discv5::Enr) -> bool + Send + 'static,
+        count: usize,
+    ) -> Result<Vec<discv5::Enr>, Error> {
+        let mut peers = self
+            .discv5
+            .table_entries_enr()
+            .into_iter()
+            .filter(|enr| predicate(enr))
+            .collect::<Vec<_>>();
+
+        let target = discv5::enr::NodeId::random();
+        let found = self
+            .discv5
+            .find_node_predicate(target, Box::new(predicate), count)
+            .await
+            .map_err(Error::LookupFailed)?;
+
+        for enr in found {
+            if peers.iter().any(|peer| peer.node_id() == enr.node_id()) {
+                continue
+            }
+            let socket = enr
+                .udp4_socket()
+                .map(SocketAddr::V4)
+                .or_else(|| enr.udp6_socket().map(SocketAddr::V6));
+            if let Some(socket) = socket {
+                if let Ok(node_record) = self.try_into_reachable(&enr, socket) {
+                    self.enr_subscriptions.notify(&enr, node_record);
+                }
+            }
+            peers.push(enr);
+        }
+
+        Ok(peers)
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -145,6 +232,22 @@ impl Discv5 {
         self.discv5.ban_ip(ip, None);
     }
 
+    /// Sets the external address of the node, e.g. after ports were mapped on the gateway.
+    ///
+    /// This will update the sockets advertised in the local [`Enr`](discv5::Enr) of the node.
+    pub fn set_external_addr(&self, ip: IpAddr, tcp_port: u16, udp_port: u16) {
+        let tcp_updated = self.discv5.update_local_enr_socket(SocketAddr::new(ip, tcp_port), true);
+        let udp_updated = self.discv5.update_local_enr_socket(SocketAddr::new(ip, udp_port), false);
+        if tcp_updated || udp_updated {
+            debug!(target: "net::discv5",
+                %ip,
+                tcp_port,
+                udp_port,
+                "updated external address in local enr"
+            );
+        }
+    }
+
     /// Returns the [`NodeRecord`] of the local node.
     ///
     /// This includes the currently tracked external IP address of the node.
@@ -217,7 +320,14 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self {
+                discv5,
+                rlpx_ip_mode,
+                fork_key,
+                discovered_peer_filter,
+                metrics,
+                enr_subscriptions: EnrSubscriptions::default(),
+            },
             discv5_updates,
             bc_enr,
         ))
@@ -307,6 +417,9 @@ impl Discv5 {
                 return None
             }
         };
+
+        self.enr_subscriptions.notify(enr, node_record);
+
         if let FilterOutcome::Ignore { reason } = self.filter_discovered_peer(enr) {
             trace!(target: "net::discv5",
                 ?enr,
@@ -688,6 +801,7 @@ mod test {
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
             metrics: Discv5Metrics::default(),
+            enr_subscriptions: EnrSubscriptions::default(),
         }
     }
 
@@ -784,6 +898,59 @@ mod test {
         )
     }
 
+    #[test]
+    fn set_local_enr_kv_pair() {
+        let discv5 = discv5_noop();
+
+        discv5.set_enr_kv_pair("myproto", &2u64).unwrap();
+        assert_eq!(discv5.local_enr_kv_pair::<u64>(b"myproto").unwrap().unwrap(), 2);
+
+        // updating the kv-pair overwrites the value
+        discv5.set_enr_kv_pair("myproto", &3u64).unwrap();
+        assert_eq!(discv5.local_enr_kv_pair::<u64>(b"myproto").unwrap().unwrap(), 3);
+
+        // the local record can be found by the same key
+        let local_enr = discv5.with_discv5(|discv5| discv5.local_enr());
+        assert!(MustIncludeKeyValue::new("myproto").with_value(3u64).matches(&local_enr));
+    }
+
+    #[test]
+    fn set_external_addr() {
+        let discv5 = discv5_noop();
+        let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1));
+
+        discv5.set_external_addr(ip, 30304, 30305);
+        let record = discv5.node_record().unwrap();
+        assert_eq!((record.address, record.tcp_port, record.udp_port), (ip, 30304, 30305));
+    }
+
+    #[test]
+    fn discovered_peer_sent_to_matching_subscription() {
+        const KEY: &[u8] = b"myproto";
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+
+        let discv5 = discv5_noop();
+        let filter = MustIncludeKeyValue::new(KEY);
+        let mut discovered = discv5.subscribe_discovered_peers(move |enr| filter.matches(enr));
+
+        let remote_key = CombinedKey::generate_secp256k1();
+        let other_enr = Enr::builder().tcp4(30303).build(&remote_key).unwrap();
+        discv5.on_discovered_peer(&other_enr, remote_socket);
+        assert!(discovered.try_recv().is_err());
+
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder()
+            .tcp4(30303)
+            .add_value_rlp(KEY, alloy_rlp::encode(1u8).into())
+            .build(&remote_key)
+            .unwrap();
+        let filtered_peer = discv5.on_discovered_peer(&remote_enr, remote_socket).unwrap();
+
+        let peer = discovered.try_recv().unwrap();
+        assert_eq!(peer.enr, remote_enr);
+        assert_eq!(peer.node_record, filtered_peer.node_record);
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[expect(unreachable_pub)]
@@ -0,0 +1,138 @@
+//! Streams of discovered peers that match a predicate on their node record.
+
+use std::{fmt, sync::Arc};
+
+use parking_lot::Mutex;
+use reth_network_peers::NodeRecord;
+use tokio::sync::mpsc;
+use tracing::trace;
+
+/// Default number of discovered peers buffered per subscription, before newly discovered peers
+/// are dropped.
+pub const DEFAULT_ENR_SUBSCRIPTION_BUFFER: usize = 256;
+
+/// A discovered peer whose [`Enr`](discv5::Enr) matched the predicate of a subscription.
+#[derive(Debug, Clone)]
+pub struct DiscoveredEnr {
+    /// The node record of the peer, as advertised over discovery.
+    pub enr: discv5::Enr,
+    /// The `RLPx` reachable node record of the peer.
+    pub node_record: NodeRecord,
+}
+
+/// Predicate on the [`Enr`](discv5::Enr) of a discovered peer.
+type EnrPredicate = Box<dyn Fn(&discv5::Enr) -> bool + Send + Sync>;
+
+/// An active subscription.
+struct EnrSubscription {
+    predicate: EnrPredicate,
+    tx: mpsc::Sender<DiscoveredEnr>,
+}
+
+/// Subscriptions to discovered peers, shared between all clones of
+/// [`Discv5`](crate::Discv5).
+#[derive(Clone, Default)]
+pub struct EnrSubscriptions {
+    subscriptions: Arc<Mutex<Vec<EnrSubscription>>>,
+}
+
+impl EnrSubscriptions {
+    /// Returns a receiver of all peers discovered from now on, whose node record matches the
+    /// predicate.
+    ///
+    /// Peers are dropped if the receiver falls more than `buffer` peers behind.
+    pub fn subscribe(
+        &self,
+        predicate: impl Fn(&discv5::Enr) -> bool + Send + Sync + 'static,
+        buffer: usize,
+    ) -> mpsc::Receiver<DiscoveredEnr> {
+        let (tx, rx) = mpsc::channel(buffer);
+        self.subscriptions.lock().push(EnrSubscription { predicate: Box::new(predicate), tx });
+        rx
+    }
+
+    /// Returns the number of active subscriptions.
+    pub fn len(&self) -> usize {
+        self.subscriptions.lock().len()
+    }
+
+    /// Returns `true` if there are no active subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.lock().is_empty()
+    }
+
+    /// Sends the discovered peer to all subscriptions it matches. Subscriptions whose receiver
+    /// has been dropped are removed.
+    pub fn notify(&self, enr: &discv5::Enr, node_record: NodeRecord) {
+        let mut subscriptions = self.subscriptions.lock();
+        subscriptions.retain(|subscription| {
+            if subscription.tx.is_closed() {
+                return false
+            }
+            if (subscription.predicate)(enr) {
+                let peer = DiscoveredEnr { enr: enr.clone(), node_record };
+                if let Err(mpsc::error::TrySendError::Full(_)) = subscription.tx.try_send(peer) {
+                    trace!(target: "net::discv5",
+                        ?enr,
+                        "enr subscription full, dropping discovered peer"
+                    );
+                }
+            }
+            true
+        });
+    }
+}
+
+impl fmt::Debug for EnrSubscriptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnrSubscriptions").field("len", &self.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{enr_to_discv4_id, filter::MustIncludeKeyValue};
+    use discv5::enr::{CombinedKey, Enr};
+
+    fn node_record(enr: &discv5::Enr) -> NodeRecord {
+        NodeRecord {
+            address: "127.0.0.1".parse().unwrap(),
+            tcp_port: 30303,
+            udp_port: 30303,
+            id: enr_to_discv4_id(enr).unwrap(),
+        }
+    }
+
+    #[test]
+    fn notify_matching_subscriptions() {
+        const KEY: &[u8] = b"myproto";
+
+        let subscriptions = EnrSubscriptions::default();
+        let filter = MustIncludeKeyValue::new(KEY);
+        let mut rx = subscriptions.subscribe(move |enr| filter.matches(enr), 1);
+        let dropped = subscriptions.subscribe(|_| true, 1);
+        drop(dropped);
+        assert_eq!(subscriptions.len(), 2);
+
+        let sk = CombinedKey::generate_secp256k1();
+        let matching =
+            Enr::builder().add_value_rlp(KEY, alloy_rlp::encode(1u8).into()).build(&sk).unwrap();
+        let sk = CombinedKey::generate_secp256k1();
+        let other = Enr::builder().build(&sk).unwrap();
+
+        subscriptions.notify(&other, node_record(&other));
+        assert!(rx.try_recv().is_err());
+        // closed subscription is removed
+        assert_eq!(subscriptions.len(), 1);
+
+        subscriptions.notify(&matching, node_record(&matching));
+        // buffer is full, peer is dropped
+        subscriptions.notify(&matching, node_record(&matching));
+
+        let peer = rx.try_recv().unwrap();
+        assert_eq!(peer.enr, matching);
+        assert_eq!(peer.node_record, node_record(&matching));
+        assert!(rx.try_recv().is_err());
+    }
+}
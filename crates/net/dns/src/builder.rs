@@ -0,0 +1,316 @@
+//! Building ENR trees that can be published via DNS.
+//!
+//! This is the counterpart of the [`DnsDiscoveryService`](crate::DnsDiscoveryService): it arranges
+//! a list of node records and links into the
+//! [EIP-1459 DNS Record Structure](https://eips.ethereum.org/EIPS/eip-1459#dns-record-structure),
+//! so that every entry fits into a single TXT record.
+//!
+//! ```
+//! use reth_dns_discovery::builder::EnrTreeBuilder;
+//! use secp256k1::SecretKey;
+//!
+//! let key = SecretKey::from_slice(&[1; 32]).unwrap();
+//! let mut tree = EnrTreeBuilder::<SecretKey>::default().with_sequence_number(1).build();
+//! tree.sign(&key).unwrap();
+//!
+//! let records = tree.to_txt_records("nodes.example.org");
+//! assert_eq!(records["nodes.example.org"], tree.root().to_string());
+//! ```
+
+use crate::tree::{BranchEntry, LinkEntry, TreeRootEntry};
+use alloy_primitives::{keccak256, Bytes};
+use data_encoding::BASE32_NOPAD;
+use enr::{Enr, EnrKey, EnrKeyUnambiguous, Error as EnrError};
+use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use secp256k1::SecretKey;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Write,
+};
+
+/// Maximum number of children of a branch entry.
+///
+/// A branch with 13 children, each a 26 character hash plus a separator, still fits into the 370
+/// bytes that are considered safe for a single DNS TXT record.
+pub const MAX_BRANCH_CHILDREN: usize = 13;
+
+/// Maximum length of a single character-string in a TXT record of a zone file.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// Builder for an [`EnrTree`].
+#[derive(Debug, Clone)]
+pub struct EnrTreeBuilder<K: EnrKeyUnambiguous = SecretKey> {
+    /// Records of the nodes in the tree.
+    nodes: Vec<Enr<K>>,
+    /// Links to other trees.
+    links: Vec<LinkEntry<K>>,
+    /// If set, only nodes that announce the same fork hash are included.
+    fork_id: Option<ForkId>,
+    /// The sequence number of the tree.
+    sequence_number: u64,
+}
+
+impl<K: EnrKeyUnambiguous> Default for EnrTreeBuilder<K> {
+    fn default() -> Self {
+        Self { nodes: Vec::new(), links: Vec::new(), fork_id: None, sequence_number: 0 }
+    }
+}
+
+// === impl EnrTreeBuilder ===
+
+impl<K: EnrKeyUnambiguous> EnrTreeBuilder<K> {
+    /// Sets the sequence number of the tree.
+    ///
+    /// Resolvers only pick up a new tree if its sequence number is higher than the one of the
+    /// previously published tree.
+    pub const fn with_sequence_number(mut self, sequence_number: u64) -> Self {
+        self.sequence_number = sequence_number;
+        self
+    }
+
+    /// Adds a node record to the tree.
+    pub fn with_node(mut self, node: Enr<K>) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Adds node records to the tree.
+    pub fn with_nodes(mut self, nodes: impl IntoIterator<Item = Enr<K>>) -> Self {
+        self.nodes.extend(nodes);
+        self
+    }
+
+    /// Adds a link to another tree.
+    pub fn with_link(mut self, link: LinkEntry<K>) -> Self {
+        self.links.push(link);
+        self
+    }
+
+    /// Only include nodes that announce the same fork hash as the given [`ForkId`] in the `eth`
+    /// entry of their record.
+    pub const fn with_fork_id_filter(mut self, fork_id: ForkId) -> Self {
+        self.fork_id = Some(fork_id);
+        self
+    }
+
+    /// Builds the _unsigned_ tree.
+    ///
+    /// If the same node is included multiple times, the record with the highest sequence number is
+    /// used.
+    pub fn build(self) -> EnrTree {
+        let Self { nodes, links, fork_id, sequence_number } = self;
+
+        // sorted by node id, so that the same set of nodes always results in the same tree
+        let mut unique_nodes = BTreeMap::new();
+        for node in nodes {
+            if let Some(fork_id) = fork_id {
+                let announced = node
+                    .get_decodable::<EnrForkIdEntry>(b"eth")
+                    .transpose()
+                    .ok()
+                    .flatten()
+                    .map(ForkId::from);
+                if announced.is_none_or(|announced| announced.hash != fork_id.hash) {
+                    continue
+                }
+            }
+
+            match unique_nodes.entry(node.node_id().raw()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(node);
+                }
+                Entry::Occupied(mut entry) => {
+                    if node.seq() > entry.get().seq() {
+                        entry.insert(node);
+                    }
+                }
+            }
+        }
+
+        let mut links = links.iter().map(ToString::to_string).collect::<Vec<_>>();
+        links.sort_unstable();
+        links.dedup();
+
+        let mut entries = BTreeMap::new();
+        let enr_root = build_subtree(
+            unique_nodes.into_values().map(|node| node.to_base64()).collect(),
+            &mut entries,
+        );
+        let enr_root = insert_entry(enr_root, &mut entries);
+        let link_root = build_subtree(links, &mut entries);
+        let link_root = insert_entry(link_root, &mut entries);
+
+        EnrTree {
+            root: TreeRootEntry { enr_root, link_root, sequence_number, signature: Bytes::new() },
+            entries,
+        }
+    }
+}
+
+/// An ENR tree arranged into DNS entries.
+#[derive(Debug, Clone)]
+pub struct EnrTree {
+    /// The root entry of the tree.
+    root: TreeRootEntry,
+    /// All other entries of the tree, keyed by their subdomain.
+    entries: BTreeMap<String, String>,
+}
+
+// === impl EnrTree ===
+
+impl EnrTree {
+    /// Returns the root entry of the tree.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns all non-root entries of the tree, keyed by their subdomain.
+    pub const fn entries(&self) -> &BTreeMap<String, String> {
+        &self.entries
+    }
+
+    /// Signs the root of the tree with the given key.
+    ///
+    /// Resolvers verify the root against the public key of the `enrtree://` link of the tree.
+    pub fn sign<K: EnrKey>(&mut self, key: &K) -> Result<(), EnrError> {
+        self.root.sign(key)
+    }
+
+    /// Returns all TXT records of the tree when published at the given domain, keyed by their
+    /// fully qualified name.
+    ///
+    /// The root entry is published at the domain itself.
+    pub fn to_txt_records(&self, domain: &str) -> BTreeMap<String, String> {
+        std::iter::once((domain.to_string(), self.root.to_string()))
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(subdomain, entry)| (format!("{subdomain}.{domain}"), entry.clone())),
+            )
+            .collect()
+    }
+
+    /// Returns the TXT records of the tree when published at the given domain in zone file
+    /// format.
+    pub fn to_zone_file(&self, domain: &str, ttl: u32) -> String {
+        let domain = domain.trim_end_matches('.');
+        let mut zone =
+            format!("; ENR tree for {domain}, sequence number {}\n", self.root.sequence_number);
+        for (name, entry) in self.to_txt_records(domain) {
+            // entries can exceed the maximum length of a single character-string, in which case
+            // the record is split into multiple strings that are concatenated by resolvers
+            let content = entry
+                .as_bytes()
+                .chunks(MAX_TXT_STRING_LEN)
+                .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = writeln!(zone, "{name}.\t{ttl}\tIN\tTXT\t{content}");
+        }
+        zone
+    }
+}
+
+/// Returns the subdomain of the given entry: the base32 encoded, abbreviated keccak256 hash of its
+/// content.
+fn subdomain(entry: &str) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.as_bytes())[..16])
+}
+
+/// Inserts the entry and returns its subdomain.
+fn insert_entry(entry: String, entries: &mut BTreeMap<String, String>) -> String {
+    let hash = subdomain(&entry);
+    entries.insert(hash.clone(), entry);
+    hash
+}
+
+/// Arranges the given entries into a subtree and returns the content of its root entry.
+///
+/// All entries of the subtree, except for the returned root, are inserted into `entries`.
+fn build_subtree(mut leaves: Vec<String>, entries: &mut BTreeMap<String, String>) -> String {
+    if leaves.len() == 1 {
+        return leaves.pop().expect("exists")
+    }
+
+    if leaves.len() <= MAX_BRANCH_CHILDREN {
+        let children = leaves.into_iter().map(|leaf| insert_entry(leaf, entries)).collect();
+        return BranchEntry { children }.to_string()
+    }
+
+    let subtrees = leaves
+        .chunks(MAX_BRANCH_CHILDREN)
+        .map(|chunk| build_subtree(chunk.to_vec(), entries))
+        .collect();
+    build_subtree(subtrees, entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::DnsEntry;
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_forks::ForkHash;
+    use secp256k1::rand::thread_rng;
+
+    fn node(fork_id: ForkId) -> Enr<SecretKey> {
+        let key = SecretKey::new(&mut thread_rng());
+        Enr::builder().add_value(b"eth", &EnrForkIdEntry::from(fork_id)).build(&key).unwrap()
+    }
+
+    #[test]
+    fn build_and_verify_tree() {
+        let fork_id = MAINNET.latest_fork_id();
+        let other = ForkId { hash: ForkHash([0xde, 0xad, 0xbe, 0xef]), next: 0 };
+        let nodes = (0..40).map(|_| node(fork_id)).collect::<Vec<_>>();
+
+        let key = SecretKey::new(&mut thread_rng());
+        let mut tree = EnrTreeBuilder::default()
+            .with_sequence_number(7)
+            .with_nodes(nodes.clone())
+            .with_node(node(other))
+            .with_fork_id_filter(fork_id)
+            .build();
+        tree.sign(&key).unwrap();
+        assert!(tree.root().verify::<SecretKey>(&key.public()));
+        assert_eq!(tree.root().sequence_number, 7);
+
+        // every entry is stored under its hash and every branch only references known entries
+        let mut found = Vec::new();
+        for (hash, entry) in tree.entries() {
+            assert_eq!(&subdomain(entry), hash);
+            match entry.parse::<DnsEntry<SecretKey>>().unwrap() {
+                DnsEntry::Branch(branch) => {
+                    assert!(branch.children.len() <= MAX_BRANCH_CHILDREN);
+                    for child in branch.children {
+                        assert!(tree.entries().contains_key(&child));
+                    }
+                }
+                DnsEntry::Node(node) => found.push(node.enr),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(found.len(), nodes.len());
+        for node in &nodes {
+            assert!(found.contains(node));
+        }
+    }
+
+    #[test]
+    fn zone_file_splits_long_records() {
+        let fork_id = MAINNET.latest_fork_id();
+        let tree = EnrTreeBuilder::default().with_node(node(fork_id)).build();
+        let zone = tree.to_zone_file("nodes.example.org", 300);
+
+        // the root, the single node and the empty link branch
+        assert_eq!(zone.lines().filter(|line| !line.starts_with(';')).count(), 3);
+        for line in zone.lines().filter(|line| !line.starts_with(';')) {
+            assert!(
+                line.contains(".nodes.example.org.\t300\tIN\tTXT\t\"") ||
+                    line.starts_with("nodes.example.org.\t300\tIN\tTXT\t\"")
+            );
+            for string in line.split('"').skip(1).step_by(2) {
+                assert!(string.len() <= MAX_TXT_STRING_LEN);
+            }
+        }
+    }
+}
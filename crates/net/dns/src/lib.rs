@@ -13,7 +13,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-pub use crate::resolver::{DnsResolver, MapResolver, Resolver};
+pub use crate::{
+    builder::{EnrTree, EnrTreeBuilder},
+    resolver::{DnsResolver, MapResolver, Resolver},
+};
 use crate::{
     query::{QueryOutcome, QueryPool, ResolveEntryResult, ResolveRootResult},
     sync::{ResolveKind, SyncAction},
@@ -49,6 +52,7 @@ use tokio_stream::{
 };
 use tracing::{debug, trace};
 
+pub mod builder;
 mod config;
 mod error;
 mod query;
@@ -551,6 +555,42 @@ mod tests {
         task.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sync_built_tree() {
+        reth_tracing::init_test_tracing();
+
+        let nodes = (0..20)
+            .map(|_| {
+                let secret_key = SecretKey::new(&mut thread_rng());
+                Enr::builder().ip4(Ipv4Addr::LOCALHOST).tcp4(30303).build(&secret_key).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let mut tree = EnrTreeBuilder::default().with_nodes(nodes.clone()).build();
+        tree.sign(&secret_key).unwrap();
+
+        let link =
+            LinkEntry { domain: "nodes.example.org".to_string(), pubkey: secret_key.public() };
+        let resolver = MapResolver::default();
+        for (name, record) in tree.to_txt_records(&link.domain) {
+            resolver.insert(name, record);
+        }
+
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), Default::default());
+        service.sync_tree_with_link(link);
+
+        let mut discovered = HashSet::new();
+        while discovered.len() < nodes.len() {
+            match poll_fn(|cx| service.poll(cx)).await {
+                DnsDiscoveryEvent::Enr(enr) => {
+                    assert!(nodes.contains(&enr));
+                    discovered.insert(enr.node_id());
+                }
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_recheck_tree() {
         reth_tracing::init_test_tracing();
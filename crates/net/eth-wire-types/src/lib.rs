@@ -12,7 +12,7 @@
 extern crate alloc;
 
 mod status;
-pub use status::{Status, StatusBuilder, StatusEth69, StatusMessage};
+pub use status::{BlockRangeUpdate, Status, StatusBuilder, StatusEth69, StatusMessage};
 
 pub mod version;
 pub use version::{EthVersion, ProtocolVersion};
@@ -50,6 +50,9 @@ pub use primitives::*;
 pub mod snap;
 pub use snap::*;
 
+pub mod trace;
+pub use trace::{MessageDirection, TracedMessage};
+
 /// re-export for convenience
 pub use alloy_eips::eip1898::{BlockHashOrNumber, HashOrNumber};
 pub use alloy_eips::eip2718::Encodable2718;
@@ -1,9 +1,10 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
 use alloc::vec::Vec;
-use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt};
+use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_primitives::B256;
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::add_arbitrary_tests;
 use reth_ethereum_primitives::Receipt;
 
@@ -46,10 +47,151 @@ impl<T: RlpDecodableReceipt> alloy_rlp::Decodable for Receipts<T> {
     }
 }
 
+/// The `eth/69` response to [`GetReceipts`], which no longer contains the bloom filters.
+///
+/// Each receipt is encoded as `[tx-type, post-state-or-status, cumulative-gas, logs]`, the blooms
+/// can be recomputed from the logs, see [`Receipts69::into_receipts`].
+///
+/// See also [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct Receipts69<T = Receipt>(
+    /// Each receipt list should correspond to a block hash in the request.
+    pub Vec<Vec<T>>,
+);
+
+impl<T: TxReceipt> Receipts69<T> {
+    /// Converts the `eth/69` receipts into [`Receipts`] by computing the bloom of each receipt.
+    pub fn into_receipts(self) -> Receipts<T> {
+        Receipts(
+            self.0
+                .into_iter()
+                .map(|receipts| {
+                    receipts
+                        .into_iter()
+                        .map(|receipt| ReceiptWithBloom { logs_bloom: receipt.bloom(), receipt })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T> From<Receipts<T>> for Receipts69<T> {
+    fn from(receipts: Receipts<T>) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T: Encodable2718> Encodable for Receipts69<T> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.as_eth69_receipts().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.as_eth69_receipts().length()
+    }
+}
+
+impl<T: Decodable2718> Decodable for Receipts69<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let receipts: Vec<Vec<Eth69Receipt<T>>> = Decodable::decode(buf)?;
+        Ok(Self(
+            receipts
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.0).collect())
+                .collect(),
+        ))
+    }
+}
+
+impl<T> Receipts69<T> {
+    fn as_eth69_receipts(&self) -> Vec<Vec<Eth69Receipt<&T>>> {
+        self.0.iter().map(|receipts| receipts.iter().map(Eth69Receipt).collect()).collect()
+    }
+}
+
+/// Helper type for the `eth/69` receipt encoding: `[tx-type, ...receipt fields]`.
+///
+/// The receipt fields are taken from the EIP-2718 encoding of the receipt, which does not contain
+/// the bloom.
+struct Eth69Receipt<T>(T);
+
+impl<T: Encodable2718> Eth69Receipt<&T> {
+    /// Returns the EIP-2718 encoding of the receipt and the offset of the receipt fields in it.
+    fn encoded_fields(&self) -> (Vec<u8>, usize) {
+        let encoded = self.0.encoded_2718();
+        let mut fields = if self.0.is_legacy() { &encoded[..] } else { &encoded[1..] };
+        Header::decode(&mut fields).expect("EIP-2718 receipt encoding is a list");
+        let offset = encoded.len() - fields.len();
+        (encoded, offset)
+    }
+
+    fn payload_length(&self, fields_length: usize) -> usize {
+        self.0.ty().length() + fields_length
+    }
+}
+
+impl<T: Encodable2718> Encodable for Eth69Receipt<&T> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        let (encoded, offset) = self.encoded_fields();
+        let fields = &encoded[offset..];
+        Header { list: true, payload_length: self.payload_length(fields.len()) }.encode(out);
+        self.0.ty().encode(out);
+        out.put_slice(fields);
+    }
+
+    fn length(&self) -> usize {
+        let (encoded, offset) = self.encoded_fields();
+        let payload_length = self.payload_length(encoded.len() - offset);
+        Header { list: true, payload_length }.length_with_payload()
+    }
+}
+
+impl<T: Decodable2718> Decodable for Eth69Receipt<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+        let ty = u8::decode(&mut payload)?;
+
+        // rebuild the EIP-2718 encoding: `type || rlp([fields])`, without type for legacy
+        let fields_header = Header { list: true, payload_length: payload.len() };
+        let mut encoded = Vec::with_capacity(1 + fields_header.length_with_payload());
+        if ty != 0 {
+            encoded.push(ty);
+        }
+        fields_header.encode(&mut encoded);
+        encoded.extend_from_slice(payload);
+
+        let mut encoded = encoded.as_slice();
+        let receipt = T::decode_2718(&mut encoded)?;
+        if !encoded.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+
+        *buf = rest;
+        Ok(Self(receipt))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_consensus::TxType;
     use alloy_primitives::{hex, Log};
     use alloy_rlp::{Decodable, Encodable};
@@ -70,6 +212,41 @@ mod tests {
         assert_eq!(receipts, decoded);
     }
 
+    #[test]
+    fn roundtrip_eth69_receipts() {
+        let receipts = Receipts69(vec![
+            vec![
+                Receipt {
+                    tx_type: TxType::Legacy,
+                    cumulative_gas_used: 21_000,
+                    ..Default::default()
+                },
+                Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: true,
+                    cumulative_gas_used: 42_000,
+                    logs: vec![Log::new_unchecked(
+                        hex!("0000000000000000000000000000000000000011").into(),
+                        vec![],
+                        hex!("0100ff")[..].into(),
+                    )],
+                },
+            ],
+            vec![],
+        ]);
+
+        let mut out = vec![];
+        receipts.encode(&mut out);
+        assert_eq!(out.len(), receipts.length());
+
+        let decoded = Receipts69::<Receipt>::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(receipts, decoded);
+
+        let with_bloom = decoded.into_receipts();
+        assert_eq!(with_bloom.0[0][1].logs_bloom, receipts.0[0][1].bloom());
+        assert_eq!(Receipts69::from(with_bloom), receipts);
+    }
+
     #[test]
     // Test vector from: https://eips.ethereum.org/EIPS/eip-2481
     fn encode_get_receipts() {
@@ -169,4 +346,35 @@ mod tests {
             }
         );
     }
+
+    // Test vector from: https://eips.ethereum.org/EIPS/eip-2481, without the bloom
+    #[test]
+    fn encode_decode_eth69_receipts() {
+        let data = hex!("f86d820457f868f866f864808001f85ff85d940000000000000000000000000000000000000011f842a0000000000000000000000000000000000000000000000000000000000000deada0000000000000000000000000000000000000000000000000000000000000beef830100ff");
+        let request = RequestPair {
+            request_id: 1111,
+            message: Receipts69(vec![vec![Receipt {
+                tx_type: TxType::Legacy,
+                cumulative_gas_used: 0x1u64,
+                logs: vec![Log::new_unchecked(
+                    hex!("0000000000000000000000000000000000000011").into(),
+                    vec![
+                        hex!("000000000000000000000000000000000000000000000000000000000000dead")
+                            .into(),
+                        hex!("000000000000000000000000000000000000000000000000000000000000beef")
+                            .into(),
+                    ],
+                    hex!("0100ff")[..].into(),
+                )],
+                success: false,
+            }]]),
+        };
+
+        let mut encoded = vec![];
+        request.encode(&mut encoded);
+        assert_eq!(encoded, data);
+
+        let decoded = RequestPair::<Receipts69>::decode(&mut &data[..]).unwrap();
+        assert_eq!(decoded, request);
+    }
 }
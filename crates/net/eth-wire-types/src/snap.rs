@@ -6,8 +6,9 @@
 //! Current version: snap/1
 
 use alloc::vec::Vec;
-use alloy_primitives::{Bytes, B256};
-use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use alloy_consensus::{constants::KECCAK_EMPTY, EMPTY_ROOT_HASH};
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
 use reth_codecs_derive::add_arbitrary_tests;
 
 /// Message IDs for the snap sync protocol
@@ -61,6 +62,103 @@ pub struct AccountData {
     pub body: Bytes,
 }
 
+/// Account body in the slim format used by the snap protocol.
+///
+/// Unlike the state trie encoding of an account, the storage root and the code hash are encoded as
+/// empty strings if the account has no storage or no code.
+// https://github.com/ethereum/devp2p/blob/master/caps/snap.md#accountrange-0x01
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct SlimAccount {
+    /// Nonce of the account
+    pub nonce: u64,
+    /// Balance of the account
+    pub balance: U256,
+    /// Root of the storage trie of the account
+    pub storage_root: B256,
+    /// Hash of the code of the account
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    /// Returns the encoded storage root, which is empty for accounts without storage.
+    fn storage_root_slim(&self) -> &[u8] {
+        if self.storage_root == EMPTY_ROOT_HASH {
+            &[]
+        } else {
+            self.storage_root.as_slice()
+        }
+    }
+
+    /// Returns the encoded code hash, which is empty for accounts without code.
+    fn code_hash_slim(&self) -> &[u8] {
+        if self.code_hash == KECCAK_EMPTY {
+            &[]
+        } else {
+            self.code_hash.as_slice()
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.storage_root_slim().length() +
+            self.code_hash_slim().length()
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        self.storage_root_slim().encode(out);
+        self.code_hash_slim().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let this = Self {
+            nonce: u64::decode(buf)?,
+            balance: U256::decode(buf)?,
+            storage_root: decode_slim_hash(buf, EMPTY_ROOT_HASH)?,
+            code_hash: decode_slim_hash(buf, KECCAK_EMPTY)?,
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// Decodes a hash of a [`SlimAccount`], where an empty string stands for the given empty hash.
+fn decode_slim_hash(buf: &mut &[u8], empty: B256) -> alloy_rlp::Result<B256> {
+    let hash = Bytes::decode(buf)?;
+    match hash.len() {
+        0 => Ok(empty),
+        32 => Ok(B256::from_slice(&hash)),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
 /// Response containing a number of consecutive accounts and the Merkle proofs for the entire range.
 // http://github.com/ethereum/devp2p/blob/master/caps/snap.md#accountrange-0x01
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -342,6 +440,27 @@ mod tests {
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn test_slim_account_empty_fields() {
+        let account = SlimAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+
+        // [nonce, balance, "", ""]
+        let encoded = alloy_rlp::encode(account);
+        assert_eq!(encoded, [0xc4, 0x01, 0x02, 0x80, 0x80]);
+        assert_eq!(encoded.len(), account.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+
+        let account = SlimAccount { storage_root: b256_from_u64(3), ..account };
+        let encoded = alloy_rlp::encode(account);
+        assert_eq!(encoded.len(), account.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+    }
+
     #[test]
     fn test_all_message_roundtrips() {
         test_roundtrip(SnapProtocolMessage::GetAccountRange(GetAccountRangeMessage {
@@ -3,7 +3,10 @@ use alloy_chains::{Chain, NamedChain};
 use alloy_hardforks::{EthereumHardfork, ForkId, Head};
 use alloy_primitives::{hex, B256, U256};
 use alloy_rlp::{BufMut, Encodable, RlpDecodable, RlpEncodable};
-use core::fmt::{Debug, Display};
+use core::{
+    fmt::{Debug, Display},
+    ops::RangeInclusive,
+};
 use reth_chainspec::{EthChainSpec, Hardforks, MAINNET};
 use reth_codecs_derive::add_arbitrary_tests;
 
@@ -69,14 +72,17 @@ impl Status {
             .forkid(spec.fork_id(head))
     }
 
-    /// Converts this [`Status`] into the [Eth69](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-7642.md) variant that excludes the total difficulty field.
-    pub const fn into_eth69(self) -> StatusEth69 {
+    /// Converts this [`Status`] into the [Eth69](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-7642.md) variant that excludes the total difficulty field
+    /// and advertises the given available block range instead.
+    pub const fn into_eth69(self, block_range: BlockRangeUpdate) -> StatusEth69 {
         StatusEth69 {
             version: EthVersion::Eth69,
             chain: self.chain,
-            blockhash: self.blockhash,
             genesis: self.genesis,
             forkid: self.forkid,
+            earliest: block_range.earliest,
+            latest: block_range.latest,
+            blockhash: block_range.latest_hash,
         }
     }
 }
@@ -225,7 +231,9 @@ impl StatusBuilder {
 }
 
 /// Similar to [`Status`], but for `eth/69` version, which does not contain
-/// the `total_difficulty` field.
+/// the `total_difficulty` field and instead advertises the range of blocks the peer can serve.
+///
+/// See also [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
 #[derive(Copy, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
@@ -239,9 +247,6 @@ pub struct StatusEth69 {
     /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
     pub chain: Chain,
 
-    /// The highest difficulty block hash the peer has seen
-    pub blockhash: B256,
-
     /// The genesis hash of the peer's chain.
     pub genesis: B256,
 
@@ -251,6 +256,26 @@ pub struct StatusEth69 {
     /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
     /// This was added in [`eth/64`](https://eips.ethereum.org/EIPS/eip-2364)
     pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block the peer can serve.
+    pub blockhash: B256,
+}
+
+impl StatusEth69 {
+    /// Returns the [`BlockRangeUpdate`] advertised by this status.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest,
+            latest: self.latest,
+            latest_hash: self.blockhash,
+        }
+    }
 }
 
 impl Display for StatusEth69 {
@@ -259,8 +284,14 @@ impl Display for StatusEth69 {
         let hexed_genesis = hex::encode(self.genesis);
         write!(
             f,
-            "Status {{ version: {}, chain: {}, blockhash: {}, genesis: {}, forkid: {:X?} }}",
-            self.version, self.chain, hexed_blockhash, hexed_genesis, self.forkid
+            "Status {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hexed_genesis,
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hexed_blockhash
         )
     }
 }
@@ -272,22 +303,26 @@ impl Debug for StatusEth69 {
         if f.alternate() {
             write!(
                 f,
-                "Status {{\n\tversion: {:?},\n\tchain: {:?},\n\tblockhash: {},\n\tgenesis: {},\n\tforkid: {:X?}\n}}",
+                "Status {{\n\tversion: {:?},\n\tchain: {:?},\n\tgenesis: {},\n\tforkid: {:X?},\n\tearliest: {},\n\tlatest: {},\n\tblockhash: {}\n}}",
                 self.version,
                 self.chain,
-                hexed_blockhash,
                 hexed_genesis,
-                self.forkid
+                self.forkid,
+                self.earliest,
+                self.latest,
+                hexed_blockhash
             )
         } else {
             write!(
                 f,
-                "Status {{ version: {:?}, chain: {:?}, blockhash: {}, genesis: {}, forkid: {:X?} }}",
+                "Status {{ version: {:?}, chain: {:?}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
                 self.version,
                 self.chain,
-                hexed_blockhash,
                 hexed_genesis,
-                self.forkid
+                self.forkid,
+                self.earliest,
+                self.latest,
+                hexed_blockhash
             )
         }
     }
@@ -296,13 +331,47 @@ impl Debug for StatusEth69 {
 // <https://etherscan.io/block/0>
 impl Default for StatusEth69 {
     fn default() -> Self {
-        Status::default().into()
+        let status = Status::default();
+        let block_range =
+            BlockRangeUpdate { earliest: 0, latest: 0, latest_hash: status.blockhash };
+        status.into_eth69(block_range)
     }
 }
 
-impl From<Status> for StatusEth69 {
-    fn from(status: Status) -> Self {
-        status.into_eth69()
+/// The `BlockRangeUpdate` message, introduced in `eth/69`.
+///
+/// Informs the peer about the range of blocks the node can serve, i.e. the node's available
+/// history. It is sent whenever this range changes, for example after new blocks were imported or
+/// old history was pruned.
+///
+/// See also [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block the node can serve.
+    pub earliest: u64,
+    /// The latest block the node can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the range is well-formed, i.e. `earliest <= latest`.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+
+    /// Returns `true` if the given block number is within the advertised range.
+    pub const fn contains(&self, block: u64) -> bool {
+        self.earliest <= block && block <= self.latest
+    }
+
+    /// Returns the advertised range as a [`RangeInclusive`].
+    pub const fn range(&self) -> RangeInclusive<u64> {
+        self.earliest..=self.latest
     }
 }
 
@@ -318,6 +387,18 @@ pub enum StatusMessage {
 }
 
 impl StatusMessage {
+    /// Creates the status message for the version of the given [`Status`].
+    ///
+    /// For `eth/69` and later this converts the status into a [`StatusEth69`] advertising the
+    /// given block range, otherwise the legacy [`Status`] is used as is.
+    pub const fn new(status: Status, block_range: BlockRangeUpdate) -> Self {
+        if status.version.is_eth69() {
+            Self::Eth69(status.into_eth69(block_range))
+        } else {
+            Self::Legacy(status)
+        }
+    }
+
     /// Returns the genesis hash from the status message.
     pub const fn genesis(&self) -> B256 {
         match self {
@@ -350,17 +431,33 @@ impl StatusMessage {
         }
     }
 
-    /// Converts to legacy Status since full support for EIP-7642
-    /// is not fully implemented
-    /// `<https://github.com/ethereum/EIPs/blob/master/EIPS/eip-7642.md>`
-    pub fn to_legacy(self) -> Status {
+    /// Returns the hash of the latest block known to the peer.
+    pub const fn blockhash(&self) -> B256 {
+        match self {
+            Self::Legacy(legacy_status) => legacy_status.blockhash,
+            Self::Eth69(status_69) => status_69.blockhash,
+        }
+    }
+
+    /// Returns the advertised block range, only available for `eth/69`.
+    pub const fn block_range(&self) -> Option<BlockRangeUpdate> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eth69(status_69) => Some(status_69.block_range()),
+        }
+    }
+
+    /// Converts to the legacy [`Status`].
+    ///
+    /// The `eth/69` status does not contain the total difficulty, so it is set to zero.
+    pub const fn to_legacy(self) -> Status {
         match self {
             Self::Legacy(legacy_status) => legacy_status,
             Self::Eth69(status_69) => Status {
                 version: status_69.version,
                 chain: status_69.chain,
                 // total_difficulty is omitted in Eth69.
-                total_difficulty: U256::default(),
+                total_difficulty: U256::ZERO,
                 blockhash: status_69.blockhash,
                 genesis: status_69.genesis,
                 forkid: status_69.forkid,
@@ -369,6 +466,18 @@ impl StatusMessage {
     }
 }
 
+impl From<Status> for StatusMessage {
+    fn from(status: Status) -> Self {
+        Self::Legacy(status)
+    }
+}
+
+impl From<StatusEth69> for StatusMessage {
+    fn from(status: StatusEth69) -> Self {
+        Self::Eth69(status)
+    }
+}
+
 impl Encodable for StatusMessage {
     fn encode(&self, out: &mut dyn BufMut) {
         match self {
@@ -387,7 +496,7 @@ impl Encodable for StatusMessage {
 
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status, StatusEth69};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69, StatusMessage};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_hardforks::{EthereumHardfork, ForkHash, ForkId, Head};
@@ -437,59 +546,73 @@ mod tests {
 
     #[test]
     fn test_status_to_statuseth69_conversion() {
+        let blockhash =
+            B256::from_str("feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d")
+                .unwrap();
         let status = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
-            blockhash: B256::from_str(
-                "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
-            )
-            .unwrap(),
             genesis: MAINNET_GENESIS_HASH,
             forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 100,
+            blockhash,
         };
-        let status_converted: StatusEth69 = Status {
+        let status_converted = Status {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
             total_difficulty: U256::from(36206751599115524359527u128),
-            blockhash: B256::from_str(
-                "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
-            )
-            .unwrap(),
+            blockhash,
             genesis: MAINNET_GENESIS_HASH,
             forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
         }
-        .into();
+        .into_eth69(BlockRangeUpdate { earliest: 0, latest: 100, latest_hash: blockhash });
         assert_eq!(status, status_converted);
     }
 
+    #[test]
+    fn status_message_for_version() {
+        let block_range = BlockRangeUpdate { earliest: 10, latest: 100, latest_hash: B256::ZERO };
+
+        let legacy = Status { version: EthVersion::Eth68, ..Default::default() };
+        let message = StatusMessage::new(legacy, block_range);
+        assert_eq!(message, StatusMessage::Legacy(legacy));
+        assert_eq!(message.block_range(), None);
+
+        let eth69 = Status { version: EthVersion::Eth69, ..Default::default() };
+        let message = StatusMessage::new(eth69, block_range);
+        assert_eq!(message.version(), EthVersion::Eth69);
+        assert_eq!(message.block_range(), Some(block_range));
+        assert_eq!(message.genesis(), eth69.genesis);
+        assert_eq!(message.forkid(), eth69.forkid);
+    }
+
     #[test]
     fn encode_eth69_status_message() {
-        let expected = hex!("f84b4501a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13da0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d80");
+        let expected = hex!("f84d4501a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d808064a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d");
+        let blockhash =
+            B256::from_str("feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d")
+                .unwrap();
         let status = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
-            blockhash: B256::from_str(
-                "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
-            )
-            .unwrap(),
             genesis: MAINNET_GENESIS_HASH,
             forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 100,
+            blockhash,
         };
 
         let mut rlp_status = vec![];
         status.encode(&mut rlp_status);
         assert_eq!(rlp_status, expected);
 
-        let status: StatusEth69 = Status::builder()
+        let status = Status::builder()
             .chain(Chain::from_named(NamedChain::Mainnet))
-            .blockhash(
-                B256::from_str("feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d")
-                    .unwrap(),
-            )
             .genesis(MAINNET_GENESIS_HASH)
             .forkid(ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 })
             .build()
-            .into();
+            .into_eth69(BlockRangeUpdate { earliest: 0, latest: 100, latest_hash: blockhash });
         let mut rlp_status = vec![];
         status.encode(&mut rlp_status);
         assert_eq!(rlp_status, expected);
@@ -497,16 +620,18 @@ mod tests {
 
     #[test]
     fn decode_eth69_status_message() {
-        let data = hex!("0xf84b4501a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13da0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d80");
+        let data = hex!("0xf84d4501a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d808064a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d");
         let expected = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 100,
             blockhash: B256::from_str(
                 "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
             )
             .unwrap(),
-            genesis: MAINNET_GENESIS_HASH,
-            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
         };
         let status = StatusEth69::decode(&mut &data[..]).unwrap();
         assert_eq!(status, expected);
@@ -0,0 +1,38 @@
+//! Records of subprotocol messages exchanged with a peer.
+
+use crate::Capability;
+use alloc::string::String;
+
+/// Whether a traced message was received from or sent to the peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MessageDirection {
+    /// The message was received from the peer.
+    Incoming,
+    /// The message was sent to the peer.
+    Outgoing,
+}
+
+/// A subprotocol message that was exchanged with a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TracedMessage {
+    /// Whether the message was received or sent.
+    pub direction: MessageDirection,
+    /// The shared capability the message belongs to.
+    pub capability: Capability,
+    /// The id of the message, relative to the message id offset of the capability.
+    pub message_id: u8,
+    /// The name of the message, if the capability is known, e.g. `GetBlockHeaders`.
+    pub name: Option<String>,
+    /// The decoded message in its `Debug` form, truncated to a bounded length.
+    ///
+    /// Only set for `eth` messages, if the tracer decodes them.
+    pub message: Option<String>,
+    /// The size of the uncompressed message in bytes, including the message id.
+    pub size: usize,
+    /// Unix timestamp in milliseconds at which the message was received or sent.
+    pub timestamp: u64,
+}
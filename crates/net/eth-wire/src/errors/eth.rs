@@ -38,6 +38,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    #[error("invalid block range update: earliest {earliest} > latest {latest}")]
+    /// Received a `BlockRangeUpdate` message where the earliest block is after the latest.
+    InvalidBlockRangeUpdate {
+        /// The earliest block of the advertised range.
+        earliest: u64,
+        /// The latest block of the advertised range.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -108,4 +116,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("invalid block range in status message: earliest {earliest} > latest {latest}")]
+    /// The `eth/69` status advertised a block range where the earliest block is after the latest.
+    InvalidBlockRange {
+        /// The earliest block of the advertised range.
+        earliest: u64,
+        /// The latest block of the advertised range.
+        latest: u64,
+    },
 }
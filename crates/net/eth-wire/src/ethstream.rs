@@ -10,7 +10,7 @@ use crate::{
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
     CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, ProtocolMessage,
-    Status,
+    StatusMessage,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use alloy_rlp::Encodable;
@@ -66,19 +66,19 @@ where
     /// remote peer.
     pub async fn handshake<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, StatusMessage), EthStreamError> {
         self.handshake_with_timeout(status, fork_filter, HANDSHAKE_TIMEOUT).await
     }
 
     /// Wrapper around handshake which enforces a timeout.
     pub async fn handshake_with_timeout<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, StatusMessage), EthStreamError> {
         timeout(timeout_limit, Self::handshake_without_timeout(self, status, fork_filter))
             .await
             .map_err(|_| EthStreamError::StreamTimeout)?
@@ -87,20 +87,18 @@ where
     /// Handshake with no timeout
     pub async fn handshake_without_timeout<N: NetworkPrimitives>(
         mut self,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
-        trace!(
-            %status,
-            "sending eth status to peer"
-        );
-        EthereumEthHandshake(&mut self.inner).eth_handshake(status, fork_filter).await?;
+    ) -> Result<(EthStream<S, N>, StatusMessage), EthStreamError> {
+        trace!(?status, "sending eth status to peer");
+        let their_status =
+            EthereumEthHandshake(&mut self.inner).eth_handshake(status, fork_filter).await?;
 
         // now we can create the `EthStream` because the peer has successfully completed
         // the handshake
-        let stream = EthStream::new(status.version, self.inner);
+        let stream = EthStream::new(status.version(), self.inner);
 
-        Ok((stream, status))
+        Ok((stream, their_status))
     }
 }
 
@@ -328,7 +326,7 @@ mod tests {
         hello::DEFAULT_TCP_PORT,
         p2pstream::UnauthedP2PStream,
         EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec,
-        ProtocolVersion, Status,
+        ProtocolVersion, Status, StatusEth69, StatusMessage,
     };
     use alloy_chains::NamedChain;
     use alloy_primitives::{bytes::Bytes, B256, U256};
@@ -348,7 +346,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = StatusMessage::Legacy(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -356,7 +354,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -392,12 +390,59 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = StatusMessage::Eth69(StatusEth69 {
+            version: EthVersion::Eth69,
+            chain: NamedChain::Mainnet.into(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+            earliest: 10,
+            latest: 100,
+            blockhash: B256::random(),
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let status_clone = status;
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (_, their_status) = UnauthedEthStream::new(stream)
+                .handshake::<EthNetworkPrimitives>(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+
+            assert_eq!(their_status, status_clone);
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (stream, their_status) = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await
+            .unwrap();
+
+        // the peer's advertised block range is available after the handshake
+        assert_eq!(their_status.block_range(), status.block_range());
+        assert_eq!(stream.version(), EthVersion::Eth69);
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = StatusMessage::Legacy(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(100)) - U256::from(1),
@@ -405,7 +450,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -446,7 +491,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = StatusMessage::Legacy(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(164)),
@@ -454,7 +499,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -590,7 +635,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = StatusMessage::Legacy(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -598,7 +643,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let status_copy = status;
         let fork_filter_clone = fork_filter.clone();
@@ -661,7 +706,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = StatusMessage::Legacy(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -669,7 +714,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
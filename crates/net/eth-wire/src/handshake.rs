@@ -6,7 +6,7 @@ use crate::{
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream};
 use reth_eth_wire_types::{
-    DisconnectReason, EthMessage, EthNetworkPrimitives, ProtocolMessage, Status, StatusMessage,
+    DisconnectReason, EthMessage, EthNetworkPrimitives, ProtocolMessage, StatusMessage,
 };
 use reth_ethereum_forks::ForkFilter;
 use reth_primitives_traits::GotExpected;
@@ -18,13 +18,15 @@ use tracing::{debug, trace};
 /// A trait that knows how to perform the P2P handshake.
 pub trait EthRlpxHandshake: Debug + Send + Sync + 'static {
     /// Perform the P2P handshake for the `eth` protocol.
+    ///
+    /// Returns the status message of the remote peer.
     fn handshake<'a>(
         &'a self,
         unauth: &'a mut dyn UnauthEth,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Pin<Box<dyn Future<Output = Result<Status, EthStreamError>> + 'a + Send>>;
+    ) -> Pin<Box<dyn Future<Output = Result<StatusMessage, EthStreamError>> + 'a + Send>>;
 }
 
 /// An unauthenticated stream that can send and receive messages.
@@ -57,10 +59,10 @@ impl EthRlpxHandshake for EthHandshake {
     fn handshake<'a>(
         &'a self,
         unauth: &'a mut dyn UnauthEth,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Pin<Box<dyn Future<Output = Result<Status, EthStreamError>> + 'a + Send>> {
+    ) -> Pin<Box<dyn Future<Output = Result<StatusMessage, EthStreamError>> + 'a + Send>> {
         Box::pin(async move {
             timeout(timeout_limit, EthereumEthHandshake(unauth).eth_handshake(status, fork_filter))
                 .await
@@ -79,18 +81,20 @@ where
     EthStreamError: From<E> + From<<S as Sink<Bytes>>::Error>,
 {
     /// Performs the `eth` rlpx protocol handshake using the given input stream.
+    ///
+    /// Returns the validated status message of the remote peer.
     pub async fn eth_handshake(
         self,
-        status: Status,
+        status: StatusMessage,
         fork_filter: ForkFilter,
-    ) -> Result<Status, EthStreamError> {
+    ) -> Result<StatusMessage, EthStreamError> {
         let unauth = self.0;
         // Send our status message
         let status_msg =
             alloy_rlp::encode(ProtocolMessage::<EthNetworkPrimitives>::from(EthMessage::<
                 EthNetworkPrimitives,
             >::Status(
-                StatusMessage::Legacy(status),
+                status
             )))
             .into();
         unauth.send(status_msg).await.map_err(EthStreamError::from)?;
@@ -117,7 +121,7 @@ where
             return Err(EthStreamError::MessageTooBig(their_msg.len()));
         }
 
-        let version = status.version;
+        let version = status.version();
         let msg = match ProtocolMessage::<EthNetworkPrimitives>::decode_message(
             version,
             &mut their_msg.as_ref(),
@@ -138,14 +142,14 @@ where
             EthMessage::Status(their_status_message) => {
                 trace!("Validating incoming ETH status from peer");
 
-                if status.genesis != their_status_message.genesis() {
+                if status.genesis() != their_status_message.genesis() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedGenesis(
                         GotExpected {
-                            expected: status.genesis,
+                            expected: status.genesis(),
                             got: their_status_message.genesis(),
                         }
                         .into(),
@@ -153,41 +157,60 @@ where
                     .into());
                 }
 
-                if status.version != their_status_message.version() {
+                if status.version() != their_status_message.version() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
                         got: their_status_message.version(),
-                        expected: status.version,
+                        expected: status.version(),
                     })
                     .into());
                 }
 
-                if status.chain != *their_status_message.chain() {
+                if status.chain() != their_status_message.chain() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedChain(GotExpected {
                         got: *their_status_message.chain(),
-                        expected: status.chain,
+                        expected: *status.chain(),
                     })
                     .into());
                 }
 
-                // Ensure total difficulty is reasonable
-                if status.total_difficulty.bit_len() > 160 {
-                    unauth
-                        .disconnect(DisconnectReason::ProtocolBreach)
-                        .await
-                        .map_err(EthStreamError::from)?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 160,
+                match their_status_message {
+                    StatusMessage::Legacy(their_status) => {
+                        // Ensure total difficulty is reasonable
+                        if their_status.total_difficulty.bit_len() > 160 {
+                            unauth
+                                .disconnect(DisconnectReason::ProtocolBreach)
+                                .await
+                                .map_err(EthStreamError::from)?;
+                            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                                got: their_status.total_difficulty.bit_len(),
+                                maximum: 160,
+                            }
+                            .into());
+                        }
+                    }
+                    StatusMessage::Eth69(their_status) => {
+                        // Ensure the advertised block range is well-formed
+                        let block_range = their_status.block_range();
+                        if !block_range.is_valid() {
+                            unauth
+                                .disconnect(DisconnectReason::ProtocolBreach)
+                                .await
+                                .map_err(EthStreamError::from)?;
+                            return Err(EthHandshakeError::InvalidBlockRange {
+                                earliest: block_range.earliest,
+                                latest: block_range.latest,
+                            }
+                            .into());
+                        }
                     }
-                    .into());
                 }
 
                 // Fork validation
@@ -202,7 +225,7 @@ where
                     return Err(err.into());
                 }
 
-                Ok(their_status_message.to_legacy())
+                Ok(their_status_message)
             }
             _ => {
                 unauth
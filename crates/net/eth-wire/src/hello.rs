@@ -205,7 +205,12 @@ impl HelloMessageBuilder {
             protocol_version: protocol_version.unwrap_or_default(),
             client_version: client_version.unwrap_or_else(|| RETH_CLIENT_VERSION.to_string()),
             protocols: protocols.unwrap_or_else(|| {
-                vec![EthVersion::Eth68.into(), EthVersion::Eth67.into(), EthVersion::Eth66.into()]
+                vec![
+                    EthVersion::Eth69.into(),
+                    EthVersion::Eth68.into(),
+                    EthVersion::Eth67.into(),
+                    EthVersion::Eth66.into(),
+                ]
             }),
             port: port.unwrap_or(DEFAULT_TCP_PORT),
             id,
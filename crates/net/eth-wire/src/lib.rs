@@ -23,6 +23,7 @@ pub mod multiplex;
 mod p2pstream;
 mod pinger;
 pub mod protocol;
+pub mod tracer;
 
 /// Handshake logic
 pub mod handshake;
@@ -43,6 +44,7 @@ pub use crate::{
         DisconnectP2P, P2PMessage, P2PMessageID, P2PStream, UnauthedP2PStream, HANDSHAKE_TIMEOUT,
         MAX_RESERVED_MESSAGE_ID,
     },
+    tracer::MessageTracer,
     Capability, ProtocolVersion,
 };
 
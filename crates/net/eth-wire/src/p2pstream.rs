@@ -3,7 +3,8 @@ use crate::{
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
     pinger::{Pinger, PingerEvent},
-    DisconnectReason, HelloMessage, HelloMessageWithProtocols,
+    tracer::MessageTracer,
+    DisconnectReason, HelloMessage, HelloMessageWithProtocols, MessageDirection,
 };
 use alloy_primitives::{
     bytes::{Buf, BufMut, Bytes, BytesMut},
@@ -255,6 +256,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Records the subprotocol messages of this stream, if installed.
+    tracer: Option<MessageTracer>,
 }
 
 impl<S> P2PStream<S> {
@@ -271,6 +275,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            tracer: None,
         }
     }
 
@@ -288,6 +293,17 @@ impl<S> P2PStream<S> {
         self.outgoing_message_buffer_capacity = capacity;
     }
 
+    /// Installs a [`MessageTracer`] that records all subprotocol messages of this stream while it
+    /// is enabled.
+    pub fn set_tracer(&mut self, tracer: MessageTracer) {
+        self.tracer = Some(tracer);
+    }
+
+    /// Returns the installed [`MessageTracer`], if any.
+    pub const fn tracer(&self) -> Option<&MessageTracer> {
+        self.tracer.as_ref()
+    }
+
     /// Returns the shared capabilities for this stream.
     ///
     /// This includes all the shared capabilities that were negotiated during the handshake and
@@ -520,6 +536,14 @@ where
                     //
                     decompress_buf[0] = bytes[0] - MAX_RESERVED_MESSAGE_ID - 1;
 
+                    if let Some(tracer) = &this.tracer {
+                        tracer.record(
+                            MessageDirection::Incoming,
+                            &this.shared_capabilities,
+                            &decompress_buf,
+                        );
+                    }
+
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
             }
@@ -592,6 +616,10 @@ where
 
         let this = self.project();
 
+        if let Some(tracer) = this.tracer.as_ref() {
+            tracer.record(MessageDirection::Outgoing, this.shared_capabilities, &item);
+        }
+
         let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
         let compressed_size =
             this.encoder.compress(&item[1..], &mut compressed[1..]).map_err(|err| {
@@ -0,0 +1,291 @@
+//! Opt-in tracing of the subprotocol messages exchanged on a [`P2PStream`](crate::P2PStream).
+
+use crate::{
+    capability::{SharedCapabilities, SharedCapability},
+    EthMessageID, EthVersion, MessageDirection, NetworkPrimitives, ProtocolMessage, TracedMessage,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default number of messages a [`MessageTracer`] keeps.
+pub const DEFAULT_MESSAGE_TRACE_CAPACITY: usize = 1024;
+
+/// The maximum length of the decoded form of a traced message, longer messages are truncated.
+pub const MAX_TRACED_MESSAGE_LEN: usize = 1024;
+
+/// Names of the `snap` messages, indexed by their message id.
+const SNAP_MESSAGE_NAMES: [&str; 8] = [
+    "GetAccountRange",
+    "AccountRange",
+    "GetStorageRanges",
+    "StorageRanges",
+    "GetByteCodes",
+    "ByteCodes",
+    "GetTrieNodes",
+    "TrieNodes",
+];
+
+/// Records the subprotocol messages of a session into a ring buffer.
+///
+/// Tracing is disabled by default and can be toggled at any time. All clones share the same
+/// state, so a tracer that is installed on a [`P2PStream`](crate::P2PStream) can be controlled and
+/// read from outside the session.
+#[derive(Debug, Clone)]
+pub struct MessageTracer {
+    inner: Arc<MessageTracerInner>,
+}
+
+#[derive(Debug)]
+struct MessageTracerInner {
+    /// Whether messages are recorded.
+    enabled: AtomicBool,
+    /// The maximum number of messages to keep, older messages are dropped first.
+    capacity: usize,
+    /// The recorded messages, oldest first.
+    messages: Mutex<VecDeque<TracedMessage>>,
+    /// Decodes the recorded `eth` messages, if set.
+    decoder: Option<MessageDecoder>,
+}
+
+/// Decodes an `eth` message, starting with its message id, into its [`Debug`] form.
+type MessageDecoder = fn(EthVersion, &[u8]) -> Option<String>;
+
+impl MessageTracer {
+    /// Creates a new disabled tracer that keeps up to `capacity` messages.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(MessageTracerInner {
+                enabled: AtomicBool::new(false),
+                capacity,
+                messages: Mutex::new(VecDeque::new()),
+                decoder: None,
+            }),
+        }
+    }
+
+    /// Records the contents of `eth` messages, decoded with the given network primitives.
+    ///
+    /// The [`Debug`] form of the decoded message is kept, truncated to
+    /// [`MAX_TRACED_MESSAGE_LEN`] bytes.
+    pub fn with_eth_messages<N: NetworkPrimitives>(self) -> Self {
+        let mut inner =
+            Arc::try_unwrap(self.inner).expect("should be called when initializing only");
+        inner.decoder = Some(decode_eth_message::<N>);
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns whether messages are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables recording of messages.
+    ///
+    /// Already recorded messages are kept.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.inner.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns all recorded messages, oldest first.
+    pub fn messages(&self) -> Vec<TracedMessage> {
+        self.inner.messages.lock().iter().cloned().collect()
+    }
+
+    /// Removes and returns all recorded messages, oldest first.
+    pub fn take_messages(&self) -> Vec<TracedMessage> {
+        self.inner.messages.lock().drain(..).collect()
+    }
+
+    /// Records the message if tracing is enabled.
+    ///
+    /// The message must start with the message id, relative to the reserved `p2p` message ids.
+    pub fn record(
+        &self,
+        direction: MessageDirection,
+        shared_capabilities: &SharedCapabilities,
+        message: &[u8],
+    ) {
+        if !self.is_enabled() || self.inner.capacity == 0 {
+            return
+        }
+        let Some(&id) = message.first() else { return };
+        let Some(cap) = shared_capabilities.find_by_relative_offset(id) else { return };
+
+        let message_id = id - cap.relative_message_id_offset();
+        let decoded = match (cap, self.inner.decoder) {
+            (SharedCapability::Eth { version, .. }, Some(decoder)) => {
+                let mut buf = message.to_vec();
+                buf[0] = message_id;
+                decoder(*version, &buf)
+            }
+            _ => None,
+        };
+        let traced = TracedMessage {
+            direction,
+            capability: cap.capability().into_owned(),
+            message_id,
+            name: message_name(cap, message_id),
+            message: decoded,
+            size: message.len(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis()
+                as u64,
+        };
+
+        let mut messages = self.inner.messages.lock();
+        if messages.len() >= self.inner.capacity {
+            messages.pop_front();
+        }
+        messages.push_back(traced);
+    }
+}
+
+impl Default for MessageTracer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MESSAGE_TRACE_CAPACITY)
+    }
+}
+
+/// Decodes the `eth` message into its [`Debug`] form of at most [`MAX_TRACED_MESSAGE_LEN`] bytes.
+fn decode_eth_message<N: NetworkPrimitives>(version: EthVersion, message: &[u8]) -> Option<String> {
+    let message = ProtocolMessage::<N>::decode_message(version, &mut &message[..]).ok()?;
+
+    let mut writer = BoundedWriter::default();
+    if write!(writer, "{:?}", message.message).is_err() {
+        writer.buf.push_str("...");
+    }
+    Some(writer.buf)
+}
+
+/// A [`Write`] sink that stops once [`MAX_TRACED_MESSAGE_LEN`] bytes were written, so large
+/// messages aren't formatted entirely.
+#[derive(Default)]
+struct BoundedWriter {
+    buf: String,
+}
+
+impl Write for BoundedWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let remaining = MAX_TRACED_MESSAGE_LEN - self.buf.len();
+        if s.len() <= remaining {
+            self.buf.push_str(s);
+            return Ok(())
+        }
+
+        let mut end = remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf.push_str(&s[..end]);
+        Err(fmt::Error)
+    }
+}
+
+/// Returns the name of the message, if the capability is `eth` or `snap`.
+fn message_name(cap: &SharedCapability, message_id: u8) -> Option<String> {
+    match cap {
+        SharedCapability::Eth { .. } => {
+            EthMessageID::try_from(message_id as usize).ok().map(|id| format!("{id:?}"))
+        }
+        SharedCapability::UnknownCapability { cap, .. } if cap.name == "snap" => {
+            SNAP_MESSAGE_NAMES.get(message_id as usize).map(|name| name.to_string())
+        }
+        SharedCapability::UnknownCapability { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::RequestPair, Capability, EthMessage, EthNetworkPrimitives, GetBlockBodies,
+    };
+    use alloy_primitives::B256;
+
+    fn shared_capabilities() -> SharedCapabilities {
+        SharedCapabilities::try_new(
+            vec![
+                EthVersion::Eth68.into(),
+                crate::protocol::Protocol::new(Capability::new_static("snap", 1), 8),
+                crate::protocol::Protocol::new(Capability::new_static("test", 1), 2),
+            ],
+            vec![
+                Capability::eth(EthVersion::Eth68),
+                Capability::new_static("snap", 1),
+                Capability::new_static("test", 1),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn records_messages_when_enabled() {
+        let caps = shared_capabilities();
+        let tracer = MessageTracer::new(2);
+
+        // disabled by default
+        tracer.record(MessageDirection::Incoming, &caps, &[0x03, 0xc0]);
+        assert!(tracer.messages().is_empty());
+
+        tracer.set_enabled(true);
+        tracer.record(MessageDirection::Incoming, &caps, &[0x03, 0xc0]);
+        let messages = tracer.messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].capability, Capability::eth(EthVersion::Eth68));
+        assert_eq!(messages[0].message_id, 0x03);
+        assert_eq!(messages[0].name.as_deref(), Some("GetBlockHeaders"));
+        assert_eq!(messages[0].size, 2);
+
+        // snap follows the 17 eth/68 message ids
+        let snap_offset = EthMessageID::message_count(EthVersion::Eth68);
+        tracer.record(MessageDirection::Outgoing, &caps, &[snap_offset + 1]);
+        // custom capabilities are not decoded, and the oldest message is dropped
+        tracer.record(MessageDirection::Outgoing, &caps, &[snap_offset + 8 + 1]);
+
+        let messages = tracer.take_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].capability, Capability::new_static("snap", 1));
+        assert_eq!(messages[0].name.as_deref(), Some("AccountRange"));
+        assert_eq!(messages[1].capability, Capability::new_static("test", 1));
+        assert_eq!(messages[1].message_id, 1);
+        assert_eq!(messages[1].name, None);
+        assert_eq!(messages[1].message, None);
+        assert!(tracer.messages().is_empty());
+    }
+
+    #[test]
+    fn records_decoded_eth_messages() {
+        let caps = shared_capabilities();
+        let tracer = MessageTracer::new(2).with_eth_messages::<EthNetworkPrimitives>();
+        tracer.set_enabled(true);
+
+        let small = EthMessage::<EthNetworkPrimitives>::GetBlockBodies(RequestPair {
+            request_id: 1,
+            message: GetBlockBodies(vec![B256::ZERO]),
+        });
+        let large = EthMessage::<EthNetworkPrimitives>::GetBlockBodies(RequestPair {
+            request_id: 2,
+            message: GetBlockBodies(vec![B256::ZERO; 100]),
+        });
+        for message in [&small, &large] {
+            let encoded = alloy_rlp::encode(ProtocolMessage::from(message.clone()));
+            tracer.record(MessageDirection::Outgoing, &caps, &encoded);
+        }
+
+        let messages = tracer.take_messages();
+        assert_eq!(messages[0].name.as_deref(), Some("GetBlockBodies"));
+        assert_eq!(messages[0].message, Some(format!("{small:?}")));
+
+        // large messages are truncated
+        let decoded = messages[1].message.as_deref().unwrap();
+        assert_eq!(decoded.len(), MAX_TRACED_MESSAGE_LEN + 3);
+        assert!(format!("{large:?}").starts_with(decoded.trim_end_matches("...")));
+    }
+}
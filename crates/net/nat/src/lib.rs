@@ -1,4 +1,4 @@
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and for mapping ports on the gateway.
 //!
 //! ## Feature Flags
 //!
@@ -12,6 +12,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod mapping;
 pub mod net_if;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
@@ -46,6 +47,8 @@ pub enum NatResolver {
     Any,
     /// Resolve external IP via `UPnP`.
     Upnp,
+    /// Resolve external IP via PCP or NAT-PMP.
+    NatPmp,
     /// Resolve external IP via a network request.
     PublicIp,
     /// Use the given [`IpAddr`]
@@ -69,6 +72,12 @@ impl NatResolver {
             _ => None,
         }
     }
+
+    /// Returns true if the resolver can map ports on the gateway, see
+    /// [`PortMappingService`](mapping::PortMappingService).
+    pub const fn supports_port_mapping(&self) -> bool {
+        matches!(self, Self::Upnp | Self::NatPmp)
+    }
 }
 
 impl fmt::Display for NatResolver {
@@ -76,6 +85,7 @@ impl fmt::Display for NatResolver {
         match self {
             Self::Any => f.write_str("any"),
             Self::Upnp => f.write_str("upnp"),
+            Self::NatPmp => f.write_str("natpmp"),
             Self::PublicIp => f.write_str("publicip"),
             Self::ExternalIp(ip) => write!(f, "extip:{ip}"),
             Self::NetIf => f.write_str("netif"),
@@ -102,6 +112,7 @@ impl FromStr for NatResolver {
         let r = match s {
             "any" => Self::Any,
             "upnp" => Self::Upnp,
+            "natpmp" | "nat-pmp" | "pcp" => Self::NatPmp,
             "none" => Self::None,
             "publicip" | "public-ip" => Self::PublicIp,
             "netif" => Self::NetIf,
@@ -197,7 +208,11 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp | NatResolver::NatPmp => match resolve_gateway_ip(resolver).await {
+            Some(ip) => Some(ip),
+            None => resolve_external_ip().await,
+        },
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -211,6 +226,18 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     }
 }
 
+/// Asks the gateway for its external IP.
+async fn resolve_gateway_ip(resolver: NatResolver) -> Option<IpAddr> {
+    let ip = match mapping::Gateway::discover(resolver, None).await {
+        Ok(gateway) => gateway.external_ip().await,
+        Err(err) => Err(err),
+    };
+    ip.inspect_err(|err| {
+        debug!(target: "net::nat", %resolver, %err, "Failed to resolve external IP from gateway");
+    })
+    .ok()
+}
+
 async fn resolve_external_ip() -> Option<IpAddr> {
     let futures = EXTERNAL_IP_APIS.iter().copied().map(resolve_external_ip_url_res).map(Box::pin);
     futures_util::future::select_ok(futures)
@@ -265,6 +292,8 @@ mod tests {
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp, "natpmp".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp.to_string(), "natpmp");
 
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
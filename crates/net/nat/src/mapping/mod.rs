@@ -0,0 +1,466 @@
+//! Port mapping on the gateway via `UPnP` IGD, PCP or NAT-PMP.
+//!
+//! A node behind a NAT is not reachable by other peers unless the gateway forwards its ports. The
+//! [`PortMappingService`] requests mappings for the configured ports, renews them before their
+//! lease expires and removes them again on shutdown. The external address of the node, as seen
+//! through the gateway, is published via [`PortMappingHandle::subscribe`].
+
+mod natpmp;
+mod upnp;
+
+pub use natpmp::NatPmpGateway;
+pub use upnp::UpnpGateway;
+
+use crate::NatResolver;
+use std::{
+    fmt,
+    future::Future,
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::{
+    sync::{oneshot, watch},
+    task::JoinHandle,
+};
+use tracing::{debug, trace, warn};
+
+/// The lifetime that is requested for port mappings by default.
+pub const DEFAULT_PORT_MAPPING_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait before retrying if no port could be mapped.
+const PORT_MAPPING_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Lower bound for the interval in which mappings are renewed.
+const MIN_RENEW_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp => f.write_str("TCP"),
+            Self::Udp => f.write_str("UDP"),
+        }
+    }
+}
+
+/// A port mapping that is active on the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    /// The transport protocol of the mapping.
+    pub protocol: Protocol,
+    /// The local port.
+    pub internal_port: u16,
+    /// The port on the gateway that is forwarded to the local port.
+    pub external_port: u16,
+    /// The lifetime the gateway granted. Zero if the mapping is permanent.
+    pub lifetime: Duration,
+}
+
+/// The external address of the node as seen through the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalAddr {
+    /// The external IP of the gateway.
+    pub ip: IpAddr,
+    /// All active port mappings.
+    pub mappings: Vec<PortMapping>,
+}
+
+impl ExternalAddr {
+    /// Returns the external port the given local port is mapped to, if it is mapped.
+    pub fn external_port(&self, protocol: Protocol, internal_port: u16) -> Option<u16> {
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.protocol == protocol && mapping.internal_port == internal_port)
+            .map(|mapping| mapping.external_port)
+    }
+}
+
+/// Errors that can occur when mapping ports on the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No gateway was found.
+    #[error("no gateway found")]
+    NoGateway,
+    /// The resolver does not support port mapping.
+    #[error("port mapping is not supported by the {0} resolver")]
+    Unsupported(NatResolver),
+    /// The gateway did not respond in time.
+    #[error("gateway did not respond")]
+    Timeout,
+    /// The gateway rejected the request.
+    #[error("gateway rejected the request with error code {0}")]
+    Rejected(u16),
+    /// The response of the gateway could not be parsed.
+    #[error("invalid response from gateway: {0}")]
+    InvalidResponse(&'static str),
+    /// Failed to talk to the gateway.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An HTTP request to the gateway failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// Configuration of the [`PortMappingService`].
+#[derive(Debug, Clone)]
+pub struct PortMappingConfig {
+    /// The local ports to map. The gateway is asked to use the same port externally.
+    pub ports: Vec<(Protocol, u16)>,
+    /// The lifetime to request for mappings.
+    pub lifetime: Duration,
+    /// The description of the mappings, shown in the UI of `UPnP` gateways.
+    pub description: String,
+    /// Where to reach the gateway.
+    ///
+    /// For `UPnP` this is the address the SSDP search is sent to, for PCP and NAT-PMP the address
+    /// of the gateway's server. If not set, the SSDP multicast address or the default route is
+    /// used respectively.
+    pub gateway: Option<SocketAddr>,
+}
+
+impl PortMappingConfig {
+    /// Adds a local port to map.
+    pub fn with_port(mut self, protocol: Protocol, port: u16) -> Self {
+        self.ports.push((protocol, port));
+        self
+    }
+
+    /// Sets the lifetime to request for mappings.
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets the address of the gateway.
+    pub const fn with_gateway(mut self, gateway: SocketAddr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            ports: Vec::new(),
+            lifetime: DEFAULT_PORT_MAPPING_LIFETIME,
+            description: "reth".to_string(),
+            gateway: None,
+        }
+    }
+}
+
+/// A gateway that supports port mapping.
+#[derive(Debug)]
+pub enum Gateway {
+    /// A `UPnP` Internet Gateway Device.
+    Upnp(UpnpGateway),
+    /// A PCP or NAT-PMP server.
+    NatPmp(NatPmpGateway),
+}
+
+impl Gateway {
+    /// Discovers the gateway for the given resolver.
+    pub async fn discover(
+        resolver: NatResolver,
+        gateway: Option<SocketAddr>,
+    ) -> Result<Self, PortMappingError> {
+        match resolver {
+            NatResolver::Upnp => UpnpGateway::discover(gateway).await.map(Self::Upnp),
+            NatResolver::NatPmp => NatPmpGateway::discover(gateway).await.map(Self::NatPmp),
+            resolver => Err(PortMappingError::Unsupported(resolver)),
+        }
+    }
+
+    /// Returns the external IP of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.external_ip().await,
+            Self::NatPmp(gateway) => gateway.external_ip().await,
+        }
+    }
+
+    /// Maps the local port to the same port on the gateway, or renews an existing mapping.
+    pub async fn add_mapping(
+        &mut self,
+        protocol: Protocol,
+        internal_port: u16,
+        lifetime: Duration,
+        description: &str,
+    ) -> Result<PortMapping, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => {
+                gateway.add_mapping(protocol, internal_port, lifetime, description).await
+            }
+            Self::NatPmp(gateway) => gateway.add_mapping(protocol, internal_port, lifetime).await,
+        }
+    }
+
+    /// Removes the mapping from the gateway.
+    pub async fn remove_mapping(&mut self, mapping: &PortMapping) -> Result<(), PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.remove_mapping(mapping).await,
+            Self::NatPmp(gateway) => gateway.remove_mapping(mapping).await,
+        }
+    }
+}
+
+/// Maintains port mappings on a gateway.
+#[derive(Debug)]
+pub struct PortMappingService {
+    /// The gateway the ports are mapped on.
+    gateway: Gateway,
+    /// The configured ports and lease settings.
+    config: PortMappingConfig,
+    /// The currently active mappings.
+    mappings: Vec<PortMapping>,
+    /// Publishes the external address.
+    external_addr: watch::Sender<Option<ExternalAddr>>,
+}
+
+impl PortMappingService {
+    /// Creates a new service that maps the configured ports on the given gateway.
+    pub fn new(gateway: Gateway, config: PortMappingConfig) -> Self {
+        Self::with_sender(gateway, config, watch::Sender::new(None))
+    }
+
+    const fn with_sender(
+        gateway: Gateway,
+        config: PortMappingConfig,
+        external_addr: watch::Sender<Option<ExternalAddr>>,
+    ) -> Self {
+        Self { gateway, config, mappings: Vec::new(), external_addr }
+    }
+
+    /// Spawns a task that discovers the gateway for the resolver and maintains the mappings until
+    /// the returned handle is dropped or shut down.
+    pub fn spawn(resolver: NatResolver, config: PortMappingConfig) -> PortMappingHandle {
+        let (external_addr_tx, external_addr_rx) = watch::channel(None);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let gateway = match Gateway::discover(resolver, config.gateway).await {
+                Ok(gateway) => gateway,
+                Err(err) => {
+                    warn!(target: "net::nat", %resolver, %err, "Failed to discover gateway for port mapping");
+                    return
+                }
+            };
+            debug!(target: "net::nat", ?gateway, "Discovered gateway");
+
+            let service = Self::with_sender(gateway, config, external_addr_tx);
+            service
+                .run(async move {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+        });
+
+        PortMappingHandle { external_addr: external_addr_rx, shutdown: shutdown_tx, task }
+    }
+
+    /// Returns a receiver for the external address.
+    pub fn subscribe(&self) -> watch::Receiver<Option<ExternalAddr>> {
+        self.external_addr.subscribe()
+    }
+
+    /// Maps all configured ports on the gateway, or renews the existing mappings.
+    ///
+    /// Fails if none of the ports could be mapped.
+    pub async fn map_ports(&mut self) -> Result<ExternalAddr, PortMappingError> {
+        let mut mappings = Vec::with_capacity(self.config.ports.len());
+        let mut last_err = None;
+        for &(protocol, port) in &self.config.ports {
+            match self
+                .gateway
+                .add_mapping(protocol, port, self.config.lifetime, &self.config.description)
+                .await
+            {
+                Ok(mapping) => {
+                    trace!(target: "net::nat", ?mapping, "Mapped port");
+                    mappings.push(mapping)
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", %protocol, %port, %err, "Failed to map port");
+                    last_err = Some(err);
+                }
+            }
+        }
+        if let Some(err) = last_err.filter(|_| mappings.is_empty()) {
+            return Err(err)
+        }
+        self.mappings.clone_from(&mappings);
+
+        let ip = self.gateway.external_ip().await?;
+        Ok(ExternalAddr { ip, mappings })
+    }
+
+    /// Removes all active mappings from the gateway.
+    pub async fn unmap_ports(&mut self) {
+        for mapping in std::mem::take(&mut self.mappings) {
+            if let Err(err) = self.gateway.remove_mapping(&mapping).await {
+                debug!(target: "net::nat", ?mapping, %err, "Failed to remove port mapping");
+            }
+        }
+    }
+
+    /// Maps the ports and keeps renewing them until `shutdown` resolves, then removes them.
+    pub async fn run<F>(mut self, shutdown: F)
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        loop {
+            let renew_after = match self.map_ports().await {
+                Ok(external_addr) => {
+                    let renew_after = self.renew_interval();
+                    self.external_addr.send_if_modified(|current| {
+                        if current.as_ref() == Some(&external_addr) {
+                            return false
+                        }
+                        debug!(target: "net::nat", ?external_addr, "Updated external address");
+                        *current = Some(external_addr);
+                        true
+                    });
+                    renew_after
+                }
+                Err(err) => {
+                    warn!(target: "net::nat", %err, "Failed to map ports on gateway");
+                    PORT_MAPPING_RETRY_INTERVAL
+                }
+            };
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(renew_after) => {}
+            }
+        }
+
+        self.unmap_ports().await;
+    }
+
+    /// Returns when the active mappings should be renewed: halfway through the shortest granted
+    /// lifetime.
+    fn renew_interval(&self) -> Duration {
+        let lifetime = self
+            .mappings
+            .iter()
+            .map(|mapping| mapping.lifetime)
+            .filter(|lifetime| !lifetime.is_zero())
+            .min()
+            .unwrap_or(self.config.lifetime);
+        (lifetime / 2).max(MIN_RENEW_INTERVAL)
+    }
+}
+
+/// Handle to a spawned [`PortMappingService`].
+///
+/// The mappings are removed from the gateway when the handle is dropped.
+#[derive(Debug)]
+pub struct PortMappingHandle {
+    /// The external address published by the service.
+    external_addr: watch::Receiver<Option<ExternalAddr>>,
+    /// Signals the service to shut down, either explicitly or by being dropped.
+    shutdown: oneshot::Sender<()>,
+    /// The task of the service.
+    task: JoinHandle<()>,
+}
+
+impl PortMappingHandle {
+    /// Returns the current external address, if the ports were mapped.
+    pub fn external_addr(&self) -> Option<ExternalAddr> {
+        self.external_addr.borrow().clone()
+    }
+
+    /// Returns a receiver that is notified whenever the external address changes.
+    pub fn subscribe(&self) -> watch::Receiver<Option<ExternalAddr>> {
+        self.external_addr.clone()
+    }
+
+    /// Shuts the service down and waits until the mappings are removed.
+    pub async fn shutdown(self) {
+        let Self { shutdown, task, .. } = self;
+        let _ = shutdown.send(());
+        let _ = task.await;
+    }
+}
+
+/// Returns the local IP that is used to reach the given address.
+fn local_ip_towards(addr: SocketAddr) -> io::Result<IpAddr> {
+    let unspecified = match addr {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = std::net::UdpSocket::bind((unspecified, 0))?;
+    socket.connect(addr)?;
+    Ok(socket.local_addr()?.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UdpSocket;
+
+    #[tokio::test]
+    async fn service_maps_renews_and_removes() {
+        reth_tracing::init_test_tracing();
+
+        // a NAT-PMP gateway that grants short leases and reports all requests
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let gateway_addr = socket.local_addr().unwrap();
+        let (requests_tx, mut requests_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1100];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let response = natpmp::tests::fake_natpmp_response(&buf[..len], 20);
+                if buf[0] == 0 && buf[1] != 0 {
+                    let lifetime = u32::from_be_bytes(buf[8..12].try_into().unwrap());
+                    requests_tx.send((buf[1], lifetime)).unwrap();
+                }
+                socket.send_to(&response, from).await.unwrap();
+            }
+        });
+
+        let gateway = Gateway::NatPmp(NatPmpGateway::discover(Some(gateway_addr)).await.unwrap());
+        let config = PortMappingConfig::default()
+            .with_port(Protocol::Tcp, 30303)
+            .with_port(Protocol::Udp, 30303)
+            .with_lifetime(Duration::from_secs(20));
+        let service = PortMappingService::new(gateway, config);
+        let mut external_addr = service.subscribe();
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(service.run(async move {
+            let _ = shutdown_rx.await;
+        }));
+
+        external_addr.changed().await.unwrap();
+        let addr = external_addr.borrow().clone().unwrap();
+        assert_eq!(addr.ip, IpAddr::V4(natpmp::tests::EXTERNAL_IP));
+        assert_eq!(addr.external_port(Protocol::Tcp, 30303), Some(30303));
+        assert_eq!(addr.external_port(Protocol::Udp, 30303), Some(30303));
+        assert_eq!(requests_rx.recv().await, Some((2, 20)));
+        assert_eq!(requests_rx.recv().await, Some((1, 20)));
+
+        // renews the mappings halfway through the granted lifetime
+        tokio::time::pause();
+        tokio::time::advance(Duration::from_secs(11)).await;
+        tokio::time::resume();
+        assert_eq!(requests_rx.recv().await, Some((2, 20)));
+        assert_eq!(requests_rx.recv().await, Some((1, 20)));
+
+        // removes the mappings on shutdown
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+        assert_eq!(requests_rx.recv().await, Some((2, 0)));
+        assert_eq!(requests_rx.recv().await, Some((1, 0)));
+    }
+}
@@ -0,0 +1,422 @@
+//! Port mapping via [PCP](https://datatracker.ietf.org/doc/html/rfc6887) and its predecessor
+//! [NAT-PMP](https://datatracker.ietf.org/doc/html/rfc6886).
+
+use super::{local_ip_towards, PortMapping, PortMappingError, Protocol};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The port PCP and NAT-PMP servers listen on.
+const SERVER_PORT: u16 = 5351;
+
+/// Version of NAT-PMP messages.
+const NATPMP_VERSION: u8 = 0;
+
+/// Version of PCP messages.
+const PCP_VERSION: u8 = 2;
+
+/// Set in the opcode of responses.
+const RESPONSE_BIT: u8 = 0x80;
+
+/// NAT-PMP opcode to request the external address.
+const NATPMP_OP_EXTERNAL_ADDR: u8 = 0;
+
+/// PCP opcode to probe the server.
+const PCP_OP_ANNOUNCE: u8 = 0;
+
+/// PCP opcode to create, renew or delete a mapping.
+const PCP_OP_MAP: u8 = 1;
+
+/// Timeout of the first attempt of a request, doubled on every retransmission.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// How often a request is sent before giving up.
+const MAX_ATTEMPTS: usize = 4;
+
+/// The protocol version a gateway speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Version {
+    Pcp,
+    NatPmp,
+}
+
+/// A gateway that runs a PCP or NAT-PMP server.
+///
+/// PCP is preferred, gateways that only speak NAT-PMP are detected by the version of their
+/// response.
+#[derive(Debug)]
+pub struct NatPmpGateway {
+    /// Address of the server.
+    addr: SocketAddr,
+    /// The local IP that is used to reach the server.
+    local_ip: IpAddr,
+    /// The protocol version the server speaks.
+    version: Version,
+    /// PCP nonces of the mappings, required to renew or delete them.
+    nonces: HashMap<(Protocol, u16), [u8; 12]>,
+    /// The external IP that was assigned in the last PCP mapping.
+    external_ip: Option<IpAddr>,
+}
+
+impl NatPmpGateway {
+    /// Probes the server at the given address, or the default gateway if not set.
+    pub async fn discover(addr: Option<SocketAddr>) -> Result<Self, PortMappingError> {
+        let addr = match addr {
+            Some(addr) => addr,
+            None => SocketAddr::new(
+                default_gateway().ok_or(PortMappingError::NoGateway)?.into(),
+                SERVER_PORT,
+            ),
+        };
+        let mut gateway = Self {
+            addr,
+            local_ip: local_ip_towards(addr)?,
+            version: Version::Pcp,
+            nonces: HashMap::new(),
+            external_ip: None,
+        };
+
+        // servers that only speak NAT-PMP answer with an unsupported version error in their
+        // own version
+        let response = gateway.request(&gateway.pcp_header(PCP_OP_ANNOUNCE, 0)).await?;
+        match response.first() {
+            Some(&PCP_VERSION) => {}
+            Some(&NATPMP_VERSION) => gateway.version = Version::NatPmp,
+            _ => return Err(PortMappingError::InvalidResponse("unknown protocol version")),
+        }
+
+        Ok(gateway)
+    }
+
+    /// Returns the external IP of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        if let Some(ip) = self.external_ip {
+            return Ok(ip)
+        }
+
+        let response = self.request(&[NATPMP_VERSION, NATPMP_OP_EXTERNAL_ADDR]).await?;
+        let response = natpmp_response(&response, NATPMP_OP_EXTERNAL_ADDR, 12)?;
+        Ok(IpAddr::V4(Ipv4Addr::new(response[8], response[9], response[10], response[11])))
+    }
+
+    /// Maps the local port to the same port on the gateway, or renews an existing mapping.
+    pub async fn add_mapping(
+        &mut self,
+        protocol: Protocol,
+        internal_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, PortMappingError> {
+        let lifetime = lifetime.as_secs().try_into().unwrap_or(u32::MAX);
+        match self.version {
+            Version::Pcp => self.pcp_map(protocol, internal_port, internal_port, lifetime).await,
+            Version::NatPmp => {
+                self.natpmp_map(protocol, internal_port, internal_port, lifetime).await
+            }
+        }
+    }
+
+    /// Removes the mapping from the gateway.
+    pub async fn remove_mapping(&mut self, mapping: &PortMapping) -> Result<(), PortMappingError> {
+        match self.version {
+            Version::Pcp => {
+                self.pcp_map(mapping.protocol, mapping.internal_port, 0, 0).await?;
+                self.nonces.remove(&(mapping.protocol, mapping.internal_port));
+            }
+            Version::NatPmp => {
+                self.natpmp_map(mapping.protocol, mapping.internal_port, 0, 0).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn natpmp_map(
+        &self,
+        protocol: Protocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: u32,
+    ) -> Result<PortMapping, PortMappingError> {
+        let opcode = match protocol {
+            Protocol::Udp => 1,
+            Protocol::Tcp => 2,
+        };
+        let mut request = vec![NATPMP_VERSION, opcode, 0, 0];
+        request.extend_from_slice(&internal_port.to_be_bytes());
+        request.extend_from_slice(&external_port.to_be_bytes());
+        request.extend_from_slice(&lifetime.to_be_bytes());
+
+        let response = self.request(&request).await?;
+        let response = natpmp_response(&response, opcode, 16)?;
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_port: u16::from_be_bytes([response[10], response[11]]),
+            lifetime: Duration::from_secs(
+                u32::from_be_bytes([response[12], response[13], response[14], response[15]]).into(),
+            ),
+        })
+    }
+
+    async fn pcp_map(
+        &mut self,
+        protocol: Protocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: u32,
+    ) -> Result<PortMapping, PortMappingError> {
+        let nonce = *self.nonces.entry((protocol, internal_port)).or_insert_with(random_nonce);
+        let mut request = self.pcp_header(PCP_OP_MAP, lifetime);
+        request.extend_from_slice(&nonce);
+        request.push(match protocol {
+            Protocol::Tcp => 6,
+            Protocol::Udp => 17,
+        });
+        request.extend_from_slice(&[0; 3]);
+        request.extend_from_slice(&internal_port.to_be_bytes());
+        request.extend_from_slice(&external_port.to_be_bytes());
+        // no preference for the external address, in the family of the local address
+        request.extend_from_slice(
+            &match self.local_ip {
+                IpAddr::V4(_) => Ipv4Addr::UNSPECIFIED.to_ipv6_mapped(),
+                IpAddr::V6(_) => Ipv6Addr::UNSPECIFIED,
+            }
+            .octets(),
+        );
+
+        let response = self.request(&request).await?;
+        if response.len() < 60 || response[0] != PCP_VERSION {
+            return Err(PortMappingError::InvalidResponse("truncated PCP response"))
+        }
+        if response[1] != PCP_OP_MAP | RESPONSE_BIT || response[24..36] != nonce {
+            return Err(PortMappingError::InvalidResponse("unexpected PCP response"))
+        }
+        if response[3] != 0 {
+            return Err(PortMappingError::Rejected(response[3].into()))
+        }
+
+        let external_ip =
+            Ipv6Addr::from(<[u8; 16]>::try_from(&response[44..60]).expect("16 bytes"));
+        let external_ip =
+            external_ip.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(external_ip));
+        if lifetime != 0 {
+            self.external_ip = Some(external_ip);
+        }
+
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_port: u16::from_be_bytes([response[42], response[43]]),
+            lifetime: Duration::from_secs(
+                u32::from_be_bytes([response[4], response[5], response[6], response[7]]).into(),
+            ),
+        })
+    }
+
+    /// Returns the common header of PCP requests.
+    fn pcp_header(&self, opcode: u8, lifetime: u32) -> Vec<u8> {
+        let client_ip = match self.local_ip {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+            IpAddr::V6(ip) => ip,
+        };
+        let mut header = Vec::with_capacity(60);
+        header.extend_from_slice(&[PCP_VERSION, opcode, 0, 0]);
+        header.extend_from_slice(&lifetime.to_be_bytes());
+        header.extend_from_slice(&client_ip.octets());
+        header
+    }
+
+    /// Sends the request to the server and returns the response, retransmitting with exponential
+    /// backoff.
+    async fn request(&self, request: &[u8]) -> Result<Vec<u8>, PortMappingError> {
+        let unspecified = match self.addr {
+            SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        let socket = UdpSocket::bind((unspecified, 0)).await?;
+        socket.connect(self.addr).await?;
+
+        let mut buf = [0u8; 1100];
+        let mut timeout = INITIAL_TIMEOUT;
+        for _ in 0..MAX_ATTEMPTS {
+            socket.send(request).await?;
+            if let Ok(len) = tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+                return Ok(buf[..len?].to_vec())
+            }
+            timeout *= 2;
+        }
+        Err(PortMappingError::Timeout)
+    }
+}
+
+/// Validates a NAT-PMP response to the given opcode.
+fn natpmp_response(response: &[u8], opcode: u8, len: usize) -> Result<&[u8], PortMappingError> {
+    if response.len() < len || response[0] != NATPMP_VERSION {
+        return Err(PortMappingError::InvalidResponse("truncated NAT-PMP response"))
+    }
+    if response[1] != opcode | RESPONSE_BIT {
+        return Err(PortMappingError::InvalidResponse("unexpected NAT-PMP response"))
+    }
+    match u16::from_be_bytes([response[2], response[3]]) {
+        0 => Ok(response),
+        code => Err(PortMappingError::Rejected(code)),
+    }
+}
+
+/// Returns a nonce for a new PCP mapping.
+///
+/// The nonce only has to be unpredictable for off-path attackers, so the randomly seeded std
+/// hasher is good enough.
+fn random_nonce() -> [u8; 12] {
+    let mut nonce = [0; 12];
+    let first = RandomState::new().build_hasher().finish();
+    let second = RandomState::new().build_hasher().finish();
+    nonce[..8].copy_from_slice(&first.to_le_bytes());
+    nonce[8..].copy_from_slice(&second.to_le_bytes()[..4]);
+    nonce
+}
+
+/// Returns the gateway of the default route.
+#[cfg(target_os = "linux")]
+fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&routes)
+}
+
+/// Returns the gateway of the default route.
+#[cfg(not(target_os = "linux"))]
+const fn default_gateway() -> Option<Ipv4Addr> {
+    None
+}
+
+/// Parses the gateway of the default route from the contents of `/proc/net/route`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    /// Flag of routes that use a gateway.
+    const RTF_GATEWAY: u16 = 0x2;
+
+    routes.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace().skip(1);
+        let (destination, gateway, flags) = (columns.next()?, columns.next()?, columns.next()?);
+        if destination != "00000000" || u16::from_str_radix(flags, 16).ok()? & RTF_GATEWAY == 0 {
+            return None
+        }
+        // the address is printed in host byte order of its in-memory representation
+        Some(Ipv4Addr::from(u32::from_str_radix(gateway, 16).ok()?.to_ne_bytes()))
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// The external IP the fake gateways report.
+    pub(in crate::mapping) const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 7);
+
+    /// Answers a request like a NAT-PMP gateway that grants at most `max_lifetime` seconds.
+    pub(in crate::mapping) fn fake_natpmp_response(request: &[u8], max_lifetime: u32) -> Vec<u8> {
+        let epoch = 1000u32.to_be_bytes();
+        if request[0] != NATPMP_VERSION {
+            // unsupported version
+            let mut response = vec![NATPMP_VERSION, request[1] | RESPONSE_BIT, 0, 1];
+            response.extend_from_slice(&epoch);
+            return response
+        }
+
+        let mut response = vec![NATPMP_VERSION, request[1] | RESPONSE_BIT, 0, 0];
+        response.extend_from_slice(&epoch);
+        if request[1] == NATPMP_OP_EXTERNAL_ADDR {
+            response.extend_from_slice(&EXTERNAL_IP.octets());
+        } else {
+            let lifetime = u32::from_be_bytes(request[8..12].try_into().unwrap());
+            response.extend_from_slice(&request[4..6]);
+            response.extend_from_slice(if lifetime == 0 { &[0, 0] } else { &request[4..6] });
+            response.extend_from_slice(&lifetime.min(max_lifetime).to_be_bytes());
+        }
+        response
+    }
+
+    /// Answers a request like a PCP gateway that maps every port to `port + 1`.
+    fn fake_pcp_response(request: &[u8]) -> Vec<u8> {
+        let mut response = vec![PCP_VERSION, request[1] | RESPONSE_BIT, 0, 0];
+        response.extend_from_slice(&request[4..8]);
+        response.extend_from_slice(&1000u32.to_be_bytes());
+        response.extend_from_slice(&[0; 12]);
+        if request[1] == PCP_OP_MAP {
+            let port = u16::from_be_bytes([request[40], request[41]]);
+            response.extend_from_slice(&request[24..42]);
+            response.extend_from_slice(&(port + 1).to_be_bytes());
+            response.extend_from_slice(&EXTERNAL_IP.to_ipv6_mapped().octets());
+        }
+        response
+    }
+
+    async fn spawn_gateway(respond: fn(&[u8]) -> Vec<u8>) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1100];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                socket.send_to(&respond(&buf[..len]), from).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn map_via_natpmp() {
+        let addr = spawn_gateway(|request| fake_natpmp_response(request, 7200)).await;
+        let mut gateway = NatPmpGateway::discover(Some(addr)).await.unwrap();
+        assert_eq!(gateway.version, Version::NatPmp);
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::V4(EXTERNAL_IP));
+
+        let mapping =
+            gateway.add_mapping(Protocol::Tcp, 30303, Duration::from_secs(3600)).await.unwrap();
+        assert_eq!(
+            mapping,
+            PortMapping {
+                protocol: Protocol::Tcp,
+                internal_port: 30303,
+                external_port: 30303,
+                lifetime: Duration::from_secs(3600),
+            }
+        );
+        gateway.remove_mapping(&mapping).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn map_via_pcp() {
+        let addr = spawn_gateway(fake_pcp_response).await;
+        let mut gateway = NatPmpGateway::discover(Some(addr)).await.unwrap();
+        assert_eq!(gateway.version, Version::Pcp);
+
+        let mapping =
+            gateway.add_mapping(Protocol::Udp, 30303, Duration::from_secs(3600)).await.unwrap();
+        assert_eq!(mapping.external_port, 30304);
+        assert_eq!(mapping.lifetime, Duration::from_secs(3600));
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::V4(EXTERNAL_IP));
+
+        // renewals reuse the nonce of the mapping
+        let nonce = gateway.nonces[&(Protocol::Udp, 30303)];
+        gateway.add_mapping(Protocol::Udp, 30303, Duration::from_secs(3600)).await.unwrap();
+        assert_eq!(gateway.nonces[&(Protocol::Udp, 30303)], nonce);
+
+        gateway.remove_mapping(&mapping).await.unwrap();
+        assert!(gateway.nonces.is_empty());
+    }
+
+    #[test]
+    fn parse_proc_net_route() {
+        let routes =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+                      eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0\n\
+                      eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\t0\t0\t0\n";
+        let expected = Ipv4Addr::from(0x0101A8C0u32.to_ne_bytes());
+        assert_eq!(parse_default_gateway(routes), Some(expected));
+        assert_eq!(parse_default_gateway("Iface\tDestination\tGateway\n"), None);
+    }
+}
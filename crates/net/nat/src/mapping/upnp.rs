@@ -0,0 +1,396 @@
+//! Port mapping via a `UPnP` Internet Gateway Device (IGD).
+//!
+//! The gateway is discovered via SSDP, its device description names the control URL of the WAN
+//! connection service, which is then called via SOAP.
+
+use super::{local_ip_towards, PortMapping, PortMappingError, Protocol};
+use reqwest::Url;
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The multicast address SSDP searches are sent to.
+const SSDP_MULTICAST_ADDR: SocketAddr =
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250)), 1900);
+
+/// How long to wait for a gateway to answer the SSDP search.
+const SSDP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Timeout of HTTP requests to the gateway.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The SSDP search target of gateways.
+const IGD_SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+/// Services of the gateway that can map ports.
+const WAN_CONNECTION_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// Error code of gateways that only support permanent mappings.
+const ONLY_PERMANENT_LEASES_SUPPORTED: u16 = 725;
+
+/// A `UPnP` Internet Gateway Device.
+#[derive(Debug, Clone)]
+pub struct UpnpGateway {
+    /// The URL SOAP requests are sent to.
+    control_url: Url,
+    /// The type of the WAN connection service.
+    service_type: String,
+    /// The local IP that is used to reach the gateway, which ports are forwarded to.
+    local_ip: IpAddr,
+    /// Client for requests to the gateway.
+    client: reqwest::Client,
+}
+
+impl UpnpGateway {
+    /// Searches for a gateway by sending an SSDP search to the given address, or the SSDP
+    /// multicast address if not set.
+    pub async fn discover(ssdp_addr: Option<SocketAddr>) -> Result<Self, PortMappingError> {
+        let location = search_gateway(ssdp_addr.unwrap_or(SSDP_MULTICAST_ADDR)).await?;
+        let client = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        let description =
+            client.get(location.clone()).send().await?.error_for_status()?.text().await?;
+        let (service_type, control_url) = parse_description(&description, &location)
+            .ok_or(PortMappingError::InvalidResponse("no WAN connection service"))?;
+
+        let gateway_ip = location
+            .host_str()
+            .and_then(|host| host.trim_start_matches('[').trim_end_matches(']').parse().ok())
+            .ok_or(PortMappingError::InvalidResponse("invalid device description location"))?;
+        let local_ip = local_ip_towards(SocketAddr::new(
+            gateway_ip,
+            location.port_or_known_default().unwrap_or(80),
+        ))?;
+
+        Ok(Self { control_url, service_type: service_type.to_string(), local_ip, client })
+    }
+
+    /// Returns the external IP of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.call("GetExternalIPAddress", &[]).await?;
+        element_text(&response, "NewExternalIPAddress")
+            .and_then(|ip| ip.parse().ok())
+            .ok_or(PortMappingError::InvalidResponse("missing external IP"))
+    }
+
+    /// Maps the local port to the same port on the gateway, or renews an existing mapping.
+    ///
+    /// Falls back to a permanent mapping if the gateway does not support leases.
+    pub async fn add_mapping(
+        &self,
+        protocol: Protocol,
+        internal_port: u16,
+        lifetime: Duration,
+        description: &str,
+    ) -> Result<PortMapping, PortMappingError> {
+        let lifetime =
+            match self.add_port_mapping(protocol, internal_port, lifetime, description).await {
+                Err(PortMappingError::Rejected(ONLY_PERMANENT_LEASES_SUPPORTED)) => {
+                    self.add_port_mapping(protocol, internal_port, Duration::ZERO, description)
+                        .await?;
+                    Duration::ZERO
+                }
+                res => {
+                    res?;
+                    lifetime
+                }
+            };
+        Ok(PortMapping { protocol, internal_port, external_port: internal_port, lifetime })
+    }
+
+    /// Removes the mapping from the gateway.
+    pub async fn remove_mapping(&self, mapping: &PortMapping) -> Result<(), PortMappingError> {
+        let external_port = mapping.external_port.to_string();
+        let protocol = mapping.protocol.to_string();
+        self.call(
+            "DeletePortMapping",
+            &[
+                ("NewRemoteHost", ""),
+                ("NewExternalPort", &external_port),
+                ("NewProtocol", &protocol),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn add_port_mapping(
+        &self,
+        protocol: Protocol,
+        port: u16,
+        lifetime: Duration,
+        description: &str,
+    ) -> Result<(), PortMappingError> {
+        let port = port.to_string();
+        let protocol = protocol.to_string();
+        let local_ip = self.local_ip.to_string();
+        let lifetime = lifetime.as_secs().min(u32::MAX.into()).to_string();
+        self.call(
+            "AddPortMapping",
+            &[
+                ("NewRemoteHost", ""),
+                ("NewExternalPort", &port),
+                ("NewProtocol", &protocol),
+                ("NewInternalPort", &port),
+                ("NewInternalClient", &local_ip),
+                ("NewEnabled", "1"),
+                ("NewPortMappingDescription", description),
+                ("NewLeaseDuration", &lifetime),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Calls the action of the WAN connection service and returns the response body.
+    async fn call(&self, action: &str, args: &[(&str, &str)]) -> Result<String, PortMappingError> {
+        let args = args
+            .iter()
+            .map(|(name, value)| format!("<{name}>{}</{name}>", escape_xml(value)))
+            .collect::<String>();
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service}\">{args}</u:{action}></s:Body></s:Envelope>",
+            service = self.service_type
+        );
+
+        let response = self
+            .client
+            .post(self.control_url.clone())
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{action}\"", self.service_type))
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if status.is_success() {
+            return Ok(text)
+        }
+
+        // failures are reported as SOAP faults that carry the UPnP error code
+        Err(element_text(&text, "errorCode")
+            .and_then(|code| code.parse().ok())
+            .map(PortMappingError::Rejected)
+            .unwrap_or(PortMappingError::InvalidResponse("SOAP request failed")))
+    }
+}
+
+/// Sends an SSDP search to the address and returns the location of the device description of
+/// the first gateway that answers.
+async fn search_gateway(addr: SocketAddr) -> Result<Url, PortMappingError> {
+    let unspecified = match addr {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind((unspecified, 0)).await?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {addr}\r\nST: {IGD_SEARCH_TARGET}\r\n\
+         MAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n"
+    );
+    socket.send_to(request.as_bytes(), addr).await?;
+
+    let mut buf = [0u8; 2048];
+    tokio::time::timeout(SSDP_TIMEOUT, async {
+        loop {
+            let (len, _) = socket.recv_from(&mut buf).await?;
+            if let Some(location) = parse_ssdp_response(&buf[..len]) {
+                return Ok::<_, PortMappingError>(location)
+            }
+        }
+    })
+    .await
+    .map_err(|_| PortMappingError::NoGateway)?
+}
+
+/// Returns the `LOCATION` header of an SSDP response.
+fn parse_ssdp_response(response: &[u8]) -> Option<Url> {
+    std::str::from_utf8(response).ok()?.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Returns the type and the control URL of the first WAN connection service in the device
+/// description.
+fn parse_description<'a>(description: &'a str, location: &Url) -> Option<(&'a str, Url)> {
+    let base = element_text(description, "URLBase")
+        .and_then(|base| base.parse().ok())
+        .unwrap_or_else(|| location.clone());
+
+    // the services are nested in the embedded devices of the gateway
+    description.split("<service>").skip(1).find_map(|service| {
+        let service_type = element_text(service, "serviceType")?;
+        if !WAN_CONNECTION_SERVICES.contains(&service_type) {
+            return None
+        }
+        let control_url = base.join(element_text(service, "controlURL")?).ok()?;
+        Some((service_type, control_url))
+    })
+}
+
+/// Returns the text of the first element with the given name.
+fn element_text<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let len = xml[start..].find(&format!("</{name}>"))?;
+    Some(xml[start..start + len].trim())
+}
+
+/// Escapes the characters that are not allowed in XML text.
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device>
+    <deviceType>urn:schemas-upnp-org:device:InternetGatewayDevice:1</deviceType>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+        <controlURL>/ctl/L3F</controlURL>
+      </service>
+    </serviceList>
+    <deviceList>
+      <device>
+        <deviceType>urn:schemas-upnp-org:device:WANConnectionDevice:1</deviceType>
+        <serviceList>
+          <service>
+            <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+            <controlURL>/ctl/IPConn</controlURL>
+          </service>
+        </serviceList>
+      </device>
+    </deviceList>
+  </device>
+</root>"#;
+
+    /// Spawns a gateway that answers SSDP searches and SOAP calls, rejecting leases, and returns
+    /// its SSDP address and the actions it was called with.
+    async fn spawn_gateway() -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let http_addr = listener.local_addr().unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let ssdp = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let ssdp_addr = ssdp.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            loop {
+                let (_, from) = ssdp.recv_from(&mut buf).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nST: {IGD_SEARCH_TARGET}\r\n\
+                     LOCATION: http://{http_addr}/rootDesc.xml\r\n\r\n"
+                );
+                ssdp.send_to(response.as_bytes(), from).await.unwrap();
+            }
+        });
+
+        let recorded = calls.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // read until the complete body arrived
+                let (head, body) = loop {
+                    let len = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..len]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let content_length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= content_length {
+                            break (head.to_string(), body.to_string())
+                        }
+                    }
+                };
+
+                let (status, body) = if head.starts_with("GET /rootDesc.xml") {
+                    ("200 OK", DESCRIPTION.to_string())
+                } else if body.contains("<u:GetExternalIPAddress") {
+                    recorded.lock().unwrap().push("GetExternalIPAddress".to_string());
+                    (
+                        "200 OK",
+                        "<NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>".to_string(),
+                    )
+                } else if body.contains("<u:AddPortMapping") &&
+                    !body.contains("<NewLeaseDuration>0<")
+                {
+                    (
+                        "500 Internal Server Error",
+                        "<UPnPError><errorCode>725</errorCode></UPnPError>".to_string(),
+                    )
+                } else {
+                    let action = body.split("<u:").nth(1).unwrap().split(' ').next().unwrap();
+                    recorded.lock().unwrap().push(action.to_string());
+                    ("200 OK", String::new())
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (ssdp_addr, calls)
+    }
+
+    #[tokio::test]
+    async fn map_via_upnp() {
+        let (ssdp_addr, calls) = spawn_gateway().await;
+        let gateway = UpnpGateway::discover(Some(ssdp_addr)).await.unwrap();
+        assert_eq!(gateway.service_type, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(gateway.control_url.path(), "/ctl/IPConn");
+        assert_eq!(gateway.external_ip().await.unwrap(), "203.0.113.7".parse::<IpAddr>().unwrap());
+
+        // falls back to a permanent mapping
+        let mapping = gateway
+            .add_mapping(Protocol::Tcp, 30303, Duration::from_secs(3600), "reth")
+            .await
+            .unwrap();
+        assert_eq!(mapping.external_port, 30303);
+        assert_eq!(mapping.lifetime, Duration::ZERO);
+
+        gateway.remove_mapping(&mapping).await.unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["GetExternalIPAddress", "AddPortMapping", "DeletePortMapping"]
+        );
+    }
+
+    #[test]
+    fn parse_ssdp_location() {
+        let response = b"HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\n\
+                         Location: http://192.168.1.1:5000/rootDesc.xml\r\n\r\n";
+        assert_eq!(
+            parse_ssdp_response(response).unwrap().as_str(),
+            "http://192.168.1.1:5000/rootDesc.xml"
+        );
+        assert!(parse_ssdp_response(b"HTTP/1.1 200 OK\r\n\r\n").is_none());
+    }
+}
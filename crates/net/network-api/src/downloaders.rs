@@ -3,7 +3,7 @@
 use std::fmt::Debug;
 
 use futures::Future;
-use reth_network_p2p::BlockClient;
+use reth_network_p2p::{snap::client::SnapClient, BlockClient};
 use tokio::sync::oneshot;
 
 /// Provides client for downloading blocks.
@@ -19,3 +19,16 @@ pub trait BlockDownloaderProvider {
         &self,
     ) -> impl Future<Output = Result<Self::Client, oneshot::error::RecvError>> + Send;
 }
+
+/// Provides client for downloading state through the `snap` protocol.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SnapDownloaderProvider {
+    /// The client this type can provide.
+    type SnapClient: SnapClient + Send + Sync + Clone + 'static;
+
+    /// Returns a new [`SnapClient`], used for fetching state from peers, or `None` if the `snap`
+    /// protocol isn't enabled.
+    fn snap_client(
+        &self,
+    ) -> impl Future<Output = Result<Option<Self::SnapClient>, oneshot::error::RecvError>> + Send;
+}
@@ -28,7 +28,7 @@ use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
 pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
-pub use downloaders::BlockDownloaderProvider;
+pub use downloaders::{BlockDownloaderProvider, SnapDownloaderProvider};
 pub use error::NetworkError;
 pub use events::{
     DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider, PeerRequest,
@@ -37,7 +37,9 @@ pub use events::{
 
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
 
-use reth_eth_wire_types::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use reth_eth_wire_types::{
+    capability::Capabilities, DisconnectReason, EthVersion, Status, TracedMessage,
+};
 use reth_network_peers::NodeRecord;
 
 /// The `PeerId` type.
@@ -46,6 +48,7 @@ pub type PeerId = alloy_primitives::B512;
 /// Helper trait that unifies network API needed to launch node.
 pub trait FullNetwork:
     BlockDownloaderProvider
+    + SnapDownloaderProvider
     + NetworkSyncUpdater
     + NetworkInfo
     + NetworkEventListenerProvider
@@ -59,6 +62,7 @@ pub trait FullNetwork:
 
 impl<T> FullNetwork for T where
     T: BlockDownloaderProvider
+        + SnapDownloaderProvider
         + NetworkSyncUpdater
         + NetworkInfo
         + NetworkEventListenerProvider
@@ -211,6 +215,19 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Enables or disables tracing of the `RLPx` messages exchanged with the given peer.
+    ///
+    /// Tracing remains selected for the peer across reconnects until it's disabled again.
+    fn set_message_tracing(&self, peer_id: PeerId, enabled: bool);
+
+    /// Returns the traced messages of the active session with the given peer, oldest first.
+    ///
+    /// Returns `None` if there's no active session with the peer.
+    fn peer_messages(
+        &self,
+        peer_id: PeerId,
+    ) -> impl Future<Output = Result<Option<Vec<TracedMessage>>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -240,6 +257,41 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The `eth` requests the peer made to us during the session, if they're accounted for.
+    pub request_usage: Option<PeerRequestUsage>,
+}
+
+/// Accounting of the `eth` requests a peer made to the local node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerRequestUsage {
+    /// Usage of `GetBlockHeaders` requests.
+    pub headers: RequestUsage,
+    /// Usage of `GetBlockBodies` requests.
+    pub bodies: RequestUsage,
+    /// Usage of `GetReceipts` requests.
+    pub receipts: RequestUsage,
+}
+
+impl PeerRequestUsage {
+    /// Returns the total cost of all served requests.
+    pub const fn total_cost(&self) -> u64 {
+        self.headers.cost.saturating_add(self.bodies.cost).saturating_add(self.receipts.cost)
+    }
+}
+
+/// Accounting of a single kind of `eth` request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RequestUsage {
+    /// Number of requests that were served.
+    pub served: u64,
+    /// Number of requests that were answered empty, because the peer exceeded its quota.
+    pub throttled: u64,
+    /// Cost of the served requests, based on database reads and response bytes.
+    pub cost: u64,
 }
 
 /// The direction of the connection.
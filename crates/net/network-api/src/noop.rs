@@ -7,7 +7,7 @@ use std::net::{IpAddr, SocketAddr};
 
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
-use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
+use reth_eth_wire_types::{DisconnectReason, ProtocolVersion, TracedMessage};
 use reth_network_peers::NodeRecord;
 use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
@@ -117,4 +117,13 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    fn set_message_tracing(&self, _peer_id: PeerId, _enabled: bool) {}
+
+    async fn peer_messages(
+        &self,
+        _peer_id: PeerId,
+    ) -> Result<Option<Vec<TracedMessage>>, NetworkError> {
+        Ok(None)
+    }
 }
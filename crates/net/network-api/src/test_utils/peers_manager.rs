@@ -5,7 +5,7 @@ use std::net::SocketAddr;
 
 use derive_more::Constructor;
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{Peer, ReputationChangeKind};
+use reth_network_types::{Peer, PersistedPeer, ReputationChangeKind};
 use tokio::sync::{mpsc, oneshot};
 
 /// Provides an API for managing the peers of the network.
@@ -61,6 +61,15 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Returns all peers in the peerset that should be persisted, including their reputation and
+    /// history.
+    pub async fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetPersistedPeers(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Commands the `PeersManager` listens for.
@@ -78,4 +87,6 @@ pub enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Get the persistable snapshot of all peers
+    GetPersistedPeers(oneshot::Sender<Vec<PersistedPeer>>),
 }
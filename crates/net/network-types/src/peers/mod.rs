@@ -1,15 +1,21 @@
 pub mod addr;
 pub mod config;
 pub mod kind;
+pub mod persisted;
 pub mod reputation;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use persisted::PersistedPeer;
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
+use std::sync::Arc;
+
 use alloy_eip2124::ForkId;
 use tracing::trace;
 
+use reth_network_peers::NodeRecord;
+
 use crate::{
     is_banned_reputation, PeerAddr, PeerConnectionState, PeerKind, ReputationChangeOutcome,
     DEFAULT_REPUTATION,
@@ -35,6 +41,12 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Unix timestamp in seconds of the last time we had an active session with the peer.
+    pub last_seen: Option<u64>,
+    /// The client version the peer advertised in its last session.
+    pub client_version: Option<Arc<str>>,
+    /// Whether the peer ever responded with non-empty headers or bodies.
+    pub served_blocks: bool,
 }
 
 // === impl Peer ===
@@ -66,9 +78,48 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_seen: None,
+            client_version: None,
+            served_blocks: false,
         }
     }
 
+    /// Returns a new peer restored from a [`PersistedPeer`] entry.
+    pub fn from_persisted(peer: &PersistedPeer) -> Self {
+        let record = &peer.record;
+        let mut restored = Self::new(PeerAddr::new_with_ports(
+            record.address,
+            record.tcp_port,
+            Some(record.udp_port),
+        ));
+        restored.restore_history(peer);
+        restored
+    }
+
+    /// Restores the reputation and history recorded in the given [`PersistedPeer`] entry.
+    pub fn restore_history(&mut self, peer: &PersistedPeer) {
+        self.reputation = peer.reputation;
+        self.last_seen = peer.last_seen;
+        self.client_version = peer.client_version.as_deref().map(Into::into);
+        self.served_blocks = peer.served_blocks;
+    }
+
+    /// Returns the [`PersistedPeer`] entry for this peer, reachable via the given [`NodeRecord`].
+    pub fn to_persisted(&self, record: NodeRecord) -> PersistedPeer {
+        PersistedPeer {
+            record,
+            reputation: self.reputation,
+            last_seen: self.last_seen,
+            client_version: self.client_version.as_deref().map(ToString::to_string),
+            served_blocks: self.served_blocks,
+        }
+    }
+
+    /// Records that we currently have an active session with the peer.
+    pub fn mark_seen(&mut self) {
+        self.last_seen = Some(persisted::unix_timestamp());
+    }
+
     /// Returns a new peer for given [`PeerAddr`] and [`PeerKind`].
     pub fn with_kind(addr: PeerAddr, kind: PeerKind) -> Self {
         Self { kind, ..Self::new(addr) }
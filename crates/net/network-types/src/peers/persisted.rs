@@ -0,0 +1,105 @@
+//! Peer entries that are persisted across restarts.
+
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use reth_network_peers::NodeRecord;
+use serde_json::{Map, Value};
+
+use crate::DEFAULT_REPUTATION;
+
+/// A snapshot of a known peer that is written to the peers file, so that the peer set can be
+/// restored with its history after a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistedPeer {
+    /// Where to reach the peer.
+    pub record: NodeRecord,
+    /// Reputation of the peer at the time of the snapshot.
+    pub reputation: i32,
+    /// Unix timestamp in seconds of the last time we had a session with the peer.
+    pub last_seen: Option<u64>,
+    /// The client version the peer advertised in its `Hello` message.
+    pub client_version: Option<String>,
+    /// Whether the peer ever responded with non-empty headers or bodies.
+    pub served_blocks: bool,
+}
+
+impl PersistedPeer {
+    /// Returns a new entry for the given [`NodeRecord`] without any history.
+    pub const fn new(record: NodeRecord) -> Self {
+        Self {
+            record,
+            reputation: DEFAULT_REPUTATION,
+            last_seen: None,
+            client_version: None,
+            served_blocks: false,
+        }
+    }
+
+    /// Returns the entry of the peers file for this peer.
+    pub fn to_json(&self) -> Value {
+        let mut entry = Map::new();
+        entry.insert("record".into(), self.record.to_string().into());
+        entry.insert("reputation".into(), self.reputation.into());
+        if let Some(last_seen) = self.last_seen {
+            entry.insert("last_seen".into(), last_seen.into());
+        }
+        if let Some(client_version) = &self.client_version {
+            entry.insert("client_version".into(), client_version.clone().into());
+        }
+        entry.insert("served_blocks".into(), self.served_blocks.into());
+        entry.into()
+    }
+
+    /// Parses an entry of the peers file.
+    ///
+    /// Older versions only wrote the plain [`NodeRecord`], so both formats are accepted.
+    pub fn from_json(entry: &Value) -> io::Result<Self> {
+        let invalid = |reason: &str| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("invalid peer {entry}: {reason}"))
+        };
+        let parse_record = |record: &Value| {
+            record
+                .as_str()
+                .ok_or_else(|| invalid("expected an enode"))?
+                .parse::<NodeRecord>()
+                .map_err(|err| invalid(&err.to_string()))
+        };
+
+        let Value::Object(fields) = entry else { return parse_record(entry).map(Self::new) };
+        let mut peer = Self::new(parse_record(fields.get("record").unwrap_or(&Value::Null))?);
+        if let Some(reputation) = fields.get("reputation") {
+            peer.reputation = reputation
+                .as_i64()
+                .and_then(|reputation| reputation.try_into().ok())
+                .ok_or_else(|| invalid("invalid reputation"))?;
+        }
+        if let Some(last_seen) = fields.get("last_seen").filter(|value| !value.is_null()) {
+            peer.last_seen = Some(last_seen.as_u64().ok_or_else(|| invalid("invalid last_seen"))?);
+        }
+        if let Some(client_version) = fields.get("client_version").filter(|value| !value.is_null())
+        {
+            let client_version =
+                client_version.as_str().ok_or_else(|| invalid("invalid client_version"))?;
+            peer.client_version = Some(client_version.to_string());
+        }
+        if let Some(served_blocks) = fields.get("served_blocks") {
+            peer.served_blocks =
+                served_blocks.as_bool().ok_or_else(|| invalid("invalid served_blocks"))?;
+        }
+        Ok(peer)
+    }
+}
+
+impl From<NodeRecord> for PersistedPeer {
+    fn from(record: NodeRecord) -> Self {
+        Self::new(record)
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
@@ -35,6 +35,12 @@ const BAD_PROTOCOL_REPUTATION_CHANGE: i32 = i32::MIN;
 // todo: current value is a hint, needs to be set properly
 const BAD_ANNOUNCEMENT_REPUTATION_CHANGE: i32 = REPUTATION_UNIT;
 
+/// The reputation change to apply to a peer that exceeded its quota for serving `eth` requests.
+///
+/// This is applied at most once per accounting window, so a peer that keeps draining the node
+/// is banned after a couple of windows.
+const EXCEEDED_REQUEST_QUOTA_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
+
 /// The maximum reputation change that can be applied to a trusted peer.
 /// This is used to prevent a single bad message from a trusted peer to cause a significant change.
 /// This gives a trusted peer more leeway when interacting with the node, which is useful for in
@@ -77,6 +83,8 @@ pub enum ReputationChangeKind {
     AlreadySeenTransaction,
     /// Peer failed to respond in time.
     Timeout,
+    /// Peer made more requests to us than its quota allows.
+    ExceededRequestQuota,
     /// Peer does not adhere to network protocol rules.
     BadProtocol,
     /// Failed to establish a connection to the peer.
@@ -124,6 +132,8 @@ pub struct ReputationChangeWeights {
     pub dropped: Reputation,
     /// Weight for [`ReputationChangeKind::BadAnnouncement`]
     pub bad_announcement: Reputation,
+    /// Weight for [`ReputationChangeKind::ExceededRequestQuota`]
+    pub exceeded_request_quota: Reputation,
 }
 
 // === impl ReputationChangeWeights ===
@@ -141,6 +151,7 @@ impl ReputationChangeWeights {
             failed_to_connect: 0,
             dropped: 0,
             bad_announcement: 0,
+            exceeded_request_quota: 0,
         }
     }
 
@@ -159,6 +170,7 @@ impl ReputationChangeWeights {
             ReputationChangeKind::Reset => DEFAULT_REPUTATION.into(),
             ReputationChangeKind::Other(val) => val.into(),
             ReputationChangeKind::BadAnnouncement => self.bad_announcement.into(),
+            ReputationChangeKind::ExceededRequestQuota => self.exceeded_request_quota.into(),
         }
     }
 }
@@ -175,6 +187,7 @@ impl Default for ReputationChangeWeights {
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
             dropped: REMOTE_DISCONNECT_REPUTATION_CHANGE,
             bad_announcement: BAD_ANNOUNCEMENT_REPUTATION_CHANGE,
+            exceeded_request_quota: EXCEEDED_REQUEST_QUOTA_REPUTATION_CHANGE,
         }
     }
 }
@@ -2,6 +2,8 @@
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap::{SnapFetchClient, SnapProtocolHandler},
+    snap_requests::SnapRequestHandler,
     transactions::{TransactionPropagationPolicy, TransactionsManager, TransactionsManagerConfig},
     NetworkHandle, NetworkManager,
 };
@@ -14,6 +16,10 @@ use tokio::sync::mpsc;
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// We set the max channel capacity of the `SnapRequestHandler` to 256 as well, responses are
+/// capped at the same soft limit as `eth` responses.
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
 /// A builder that can configure all components of the network.
 #[expect(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth, N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -62,10 +68,28 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let request_handler = EthRequestHandler::new(client, peers, rx)
+            .with_request_quotas(network.eth_request_quotas().clone());
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Announces the `snap/1` protocol to peers and returns the [`SnapRequestHandler`] that serves
+    /// their requests, together with a [`SnapFetchClient`] for requesting state from them.
+    ///
+    /// The returned handler must be spawned for requests to be answered.
+    pub fn snap_request_handler<Client>(
+        &mut self,
+        client: Client,
+    ) -> (SnapRequestHandler<Client>, SnapFetchClient) {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        let peers = self.network.handle().peers_handle().clone();
+        let protocol = SnapProtocolHandler::new(peers, tx);
+        let fetch_client = protocol.fetch_client();
+        self.network.add_rlpx_sub_protocol(protocol);
+        self.network.set_snap_client(fetch_client.clone());
+        (SnapRequestHandler::new(client, rx), fetch_client)
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -3,6 +3,7 @@
 use crate::{
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
+    request_quota::EthRequestQuotaConfig,
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
@@ -12,7 +13,8 @@ use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{
     handshake::{EthHandshake, EthRlpxHandshake},
-    EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives, Status,
+    BlockRangeUpdate, EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols,
+    NetworkPrimitives, Status,
 };
 use reth_ethereum_forks::{ForkFilter, Head};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
@@ -74,6 +76,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub executor: Box<dyn TaskSpawner>,
     /// The `Status` message to send to peers at the beginning.
     pub status: Status,
+    /// The block range advertised to `eth/69` peers at the beginning.
+    pub block_range: BlockRangeUpdate,
     /// Sets the hello message for the p2p handshake in `RLPx`
     pub hello_message: HelloMessageWithProtocols,
     /// Additional protocols to announce and handle in `RLPx`
@@ -82,6 +86,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// Per-peer quotas for serving `eth` requests.
+    pub eth_request_quota_config: EthRequestQuotaConfig,
     /// The NAT resolver for external IP
     pub nat: Option<NatResolver>,
     /// The Ethereum P2P handshake, see also:
@@ -211,6 +217,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     block_import: Option<Box<dyn BlockImport<N::Block>>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// Per-peer quotas for serving `eth` requests.
+    eth_request_quota_config: EthRequestQuotaConfig,
     /// The NAT resolver for external IP
     nat: Option<NatResolver>,
     /// The Ethereum P2P handshake, see also:
@@ -254,6 +262,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            eth_request_quota_config: Default::default(),
             nat: None,
             handshake: Arc::new(EthHandshake::default()),
         }
@@ -345,6 +354,12 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Configures the per-peer quotas for serving `eth` requests.
+    pub const fn eth_request_quota_config(mut self, config: EthRequestQuotaConfig) -> Self {
+        self.eth_request_quota_config = config;
+        self
+    }
+
     /// Sets the discovery and listener address
     ///
     /// This is a convenience function for both [`NetworkConfigBuilder::listener_addr`] and
@@ -594,6 +609,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            eth_request_quota_config,
             nat,
             handshake,
         } = self;
@@ -624,6 +640,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         // set the status
         let status = Status::spec_builder(&chain_spec, &head).build();
 
+        // the full history is available until pruning advances the earliest block
+        let block_range =
+            BlockRangeUpdate { earliest: 0, latest: head.number, latest_hash: head.hash };
+
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
 
@@ -657,11 +677,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             network_mode,
             executor: executor.unwrap_or_else(|| Box::<TokioTaskExecutor>::default()),
             status,
+            block_range,
             hello_message,
             extra_protocols,
             fork_filter,
             tx_gossip_disabled,
             transactions_manager_config,
+            eth_request_quota_config,
             nat,
             handshake,
         }
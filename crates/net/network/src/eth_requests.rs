@@ -1,8 +1,10 @@
 //! Blocks/Headers management for the p2p network.
 
 use crate::{
-    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    metered_poll_nested_stream_with_budget,
     metrics::EthRequestHandlerMetrics,
+    request_quota::{EthRequestKind, EthRequestQuotas, QuotaOutcome},
 };
 use alloy_consensus::{BlockHeader, ReceiptWithBloom};
 use alloy_eips::BlockHashOrNumber;
@@ -15,6 +17,7 @@ use reth_eth_wire::{
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::error::RequestResult;
 use reth_network_peers::PeerId;
+use reth_network_types::ReputationChangeKind;
 use reth_primitives_traits::Block;
 use reth_storage_api::{BlockReader, HeaderProvider};
 use std::{
@@ -25,6 +28,7 @@ use std::{
 };
 use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -55,12 +59,12 @@ pub const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 pub struct EthRequestHandler<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The client type that can interact with the chain.
     client: C,
-    /// Used for reporting peers.
-    // TODO use to report spammers
-    #[expect(dead_code)]
+    /// Used for reporting peers that exceed their request quota.
     peers: PeersHandle,
     /// Incoming request from the [`NetworkManager`](crate::NetworkManager).
     incoming_requests: ReceiverStream<IncomingEthRequest<N>>,
+    /// Accounts the requests of each peer against its quota.
+    quotas: EthRequestQuotas,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
 }
@@ -68,14 +72,54 @@ pub struct EthRequestHandler<C, N: NetworkPrimitives = EthNetworkPrimitives> {
 // === impl EthRequestHandler ===
 impl<C, N: NetworkPrimitives> EthRequestHandler<C, N> {
     /// Create a new instance
+    ///
+    /// Requests are accounted against the default
+    /// [`EthRequestQuotaConfig`](crate::request_quota::EthRequestQuotaConfig), but only for the
+    /// peers added to the [`EthRequestQuotas`], see [`Self::with_request_quotas`].
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest<N>>) -> Self {
         Self {
             client,
             peers,
             incoming_requests: ReceiverStream::new(incoming),
+            quotas: Default::default(),
             metrics: Default::default(),
         }
     }
+
+    /// Sets the [`EthRequestQuotas`] requests are accounted against.
+    pub fn with_request_quotas(mut self, quotas: EthRequestQuotas) -> Self {
+        self.quotas = quotas;
+        self
+    }
+
+    /// Returns the [`EthRequestQuotas`] requests are accounted against.
+    pub const fn request_quotas(&self) -> &EthRequestQuotas {
+        &self.quotas
+    }
+
+    /// Returns `true` if the peer has budget left for a request of the given kind.
+    ///
+    /// Peers that are over budget are penalized once per accounting window.
+    fn acquire_quota(&self, peer_id: PeerId, kind: EthRequestKind) -> bool {
+        match self.quotas.try_acquire(peer_id, kind) {
+            QuotaOutcome::Allowed => true,
+            QuotaOutcome::Throttled { penalize } => {
+                trace!(target: "net::eth", %peer_id, ?kind, "peer exceeded request quota");
+                self.metrics.eth_requests_throttled_total.increment(1);
+                if penalize {
+                    self.peers
+                        .reputation_change(peer_id, ReputationChangeKind::ExceededRequestQuota);
+                }
+                false
+            }
+        }
+    }
+
+    /// Charges the peer for a served request.
+    fn charge(&self, peer_id: PeerId, kind: EthRequestKind, reads: usize, bytes: usize) {
+        let cost = self.quotas.charge(peer_id, kind, reads, bytes);
+        self.metrics.eth_requests_served_cost_total.increment(cost);
+    }
 }
 
 impl<C, N> EthRequestHandler<C, N>
@@ -83,17 +127,20 @@ where
     N: NetworkPrimitives,
     C: BlockReader,
 {
-    /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<C::Header> {
+    /// Returns the list of requested headers, with the number of database reads and the size of
+    /// the response.
+    fn get_headers_response(&self, request: GetBlockHeaders) -> (Vec<C::Header>, usize, usize) {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
 
         let mut headers = Vec::new();
+        let mut reads = 0;
 
         let mut block: BlockHashOrNumber = match start_block {
             BlockHashOrNumber::Hash(start) => start.into(),
             BlockHashOrNumber::Number(num) => {
+                reads += 1;
                 let Some(hash) = self.client.block_hash(num).unwrap_or_default() else {
-                    return headers
+                    return (headers, reads, 0)
                 };
                 hash.into()
             }
@@ -103,6 +150,7 @@ where
         let mut total_bytes = 0;
 
         for _ in 0..limit {
+            reads += 1;
             if let Some(header) = self.client.header_by_hash_or_number(block).unwrap_or_default() {
                 match direction {
                     HeadersDirection::Rising => {
@@ -140,32 +188,45 @@ where
             }
         }
 
-        headers
+        (headers, reads, total_bytes)
     }
 
     fn on_headers_request(
         &self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetBlockHeaders,
         response: oneshot::Sender<RequestResult<BlockHeaders<C::Header>>>,
     ) {
         self.metrics.eth_headers_requests_received_total.increment(1);
-        let headers = self.get_headers_response(request);
+        if !self.acquire_quota(peer_id, EthRequestKind::Headers) {
+            let _ = response.send(Ok(BlockHeaders(Vec::new())));
+            return
+        }
+
+        let (headers, reads, total_bytes) = self.get_headers_response(request);
+        self.charge(peer_id, EthRequestKind::Headers, reads, total_bytes);
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
     fn on_bodies_request(
         &self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetBlockBodies,
         response: oneshot::Sender<RequestResult<BlockBodies<<C::Block as Block>::Body>>>,
     ) {
         self.metrics.eth_bodies_requests_received_total.increment(1);
+        if !self.acquire_quota(peer_id, EthRequestKind::Bodies) {
+            let _ = response.send(Ok(BlockBodies(Vec::new())));
+            return
+        }
+
         let mut bodies = Vec::new();
 
         let mut total_bytes = 0;
+        let mut reads = 0;
 
         for hash in request.0 {
+            reads += 1;
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
                 let body = block.into_body();
                 total_bytes += body.length();
@@ -179,22 +240,29 @@ where
             }
         }
 
+        self.charge(peer_id, EthRequestKind::Bodies, reads, total_bytes);
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
     fn on_receipts_request(
         &self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetReceipts,
         response: oneshot::Sender<RequestResult<Receipts<C::Receipt>>>,
     ) {
         self.metrics.eth_receipts_requests_received_total.increment(1);
+        if !self.acquire_quota(peer_id, EthRequestKind::Receipts) {
+            let _ = response.send(Ok(Receipts(Vec::new())));
+            return
+        }
 
         let mut receipts = Vec::new();
 
         let mut total_bytes = 0;
+        let mut reads = 0;
 
         for hash in request.0 {
+            reads += 1;
             if let Some(receipts_by_block) =
                 self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
             {
@@ -212,6 +280,7 @@ where
             }
         }
 
+        self.charge(peer_id, EthRequestKind::Receipts, reads, total_bytes);
         let _ = response.send(Ok(Receipts(receipts)));
     }
 }
@@ -5,9 +5,13 @@ mod client;
 pub use client::FetchClient;
 
 use crate::message::BlockRequest;
+use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
 use futures::StreamExt;
-use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
+use reth_eth_wire::{
+    BlockRangeUpdate, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, HeadersDirection,
+    NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
@@ -18,6 +22,7 @@ use reth_network_peers::PeerId;
 use reth_network_types::ReputationChangeKind;
 use std::{
     collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -79,6 +84,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         peer_id: PeerId,
         best_hash: B256,
         best_number: u64,
+        block_range: Option<BlockRangeUpdate>,
         timeout: Arc<AtomicU64>,
     ) {
         self.peers.insert(
@@ -87,6 +93,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 state: PeerState::Idle,
                 best_hash,
                 best_number,
+                block_range,
                 timeout,
                 last_response_likely_bad: false,
             },
@@ -123,6 +130,19 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         false
     }
 
+    /// Updates the block range the peer advertised via `BlockRangeUpdate`.
+    ///
+    /// This also advances the peer's best block if the range's latest block is newer.
+    pub(crate) fn update_peer_block_range(&mut self, peer_id: &PeerId, range: BlockRangeUpdate) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.block_range = Some(range);
+            if range.latest > peer.best_number {
+                peer.best_hash = range.latest_hash;
+                peer.best_number = range.latest;
+            }
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -131,14 +151,26 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
+    /// prioritizing those that serve the hinted block range, those with the lowest timeout/latency
+    /// and those that recently responded with adequate data.
+    fn next_best_peer(&self, range_hint: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
         let mut best_peer = idle.next()?;
 
         for maybe_better in idle {
+            // prefer peers that still serve the requested blocks
+            if let Some(range) = range_hint {
+                match (best_peer.1.has_blocks(range), maybe_better.1.has_blocks(range)) {
+                    (false, true) => {
+                        best_peer = maybe_better;
+                        continue
+                    }
+                    (true, false) => continue,
+                    _ => {}
+                }
+            }
+
             // replace best peer if our current best peer sent us a bad response last time
             if best_peer.1.last_response_likely_bad && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
@@ -163,7 +195,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let range_hint = self.queued_requests.front().and_then(|req| req.range_hint());
+        let Some(peer_id) = self.next_best_peer(range_hint.as_ref()) else {
+            return PollAction::NoPeersAvailable
+        };
 
         let request = self.queued_requests.pop_front().expect("not empty");
         let request = self.prepare_block_request(peer_id, request);
@@ -338,6 +373,8 @@ struct Peer {
     best_hash: B256,
     /// Tracks the best number of the peer.
     best_number: u64,
+    /// The range of blocks the peer serves, if it advertised one (`eth/69`).
+    block_range: Option<BlockRangeUpdate>,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
     /// Tracks whether the peer has recently responded with a likely bad response.
@@ -353,6 +390,13 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer can serve the given blocks.
+    ///
+    /// Peers that didn't advertise a block range are assumed to serve the full history.
+    fn has_blocks(&self, range: &RangeInclusive<u64>) -> bool {
+        self.block_range.is_none_or(|block_range| block_range.earliest <= *range.start())
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -414,6 +458,7 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
     },
 }
 
@@ -441,6 +486,21 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the range of block numbers this request targets, if known.
+    fn range_hint(&self) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                let BlockHashOrNumber::Number(start) = request.start else { return None };
+                let span = request.limit.saturating_sub(1);
+                match request.direction {
+                    HeadersDirection::Rising => Some(start..=start.saturating_add(span)),
+                    HeadersDirection::Falling => Some(start.saturating_sub(span)..=start),
+                }
+            }
+            Self::GetBlockBodies { range_hint, .. } => range_hint.clone(),
+        }
+    }
 }
 
 /// An action the syncer can emit.
@@ -486,6 +546,7 @@ mod tests {
                 request: vec![],
                 response: tx,
                 priority: Priority::default(),
+                range_hint: None,
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -502,20 +563,20 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::new(AtomicU64::new(1)));
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -530,18 +591,73 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(30)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::clone(&peer2_timeout));
+        fetcher.new_active_peer(peer3, B256::random(), 3, None, Arc::new(AtomicU64::new(50)));
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_peer_prioritization_by_block_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let pruned_peer = B512::random();
+        let archive_peer = B512::random();
+
+        let pruned_range =
+            BlockRangeUpdate { earliest: 1000, latest: 2000, latest_hash: B256::random() };
+        fetcher.new_active_peer(
+            pruned_peer,
+            B256::random(),
+            2000,
+            Some(pruned_range),
+            Arc::new(AtomicU64::new(10)),
+        );
+        fetcher.new_active_peer(
+            archive_peer,
+            B256::random(),
+            2000,
+            None,
+            Arc::new(AtomicU64::new(50)),
+        );
+
+        // the pruned peer has the lowest timeout
+        assert_eq!(fetcher.next_best_peer(None), Some(pruned_peer));
+        assert_eq!(fetcher.next_best_peer(Some(&(1500..=1600))), Some(pruned_peer));
+        // but it no longer serves old history
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(archive_peer));
+
+        // the pruned peer is still used if it's the only idle peer
+        fetcher.on_pending_disconnect(&archive_peer);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(pruned_peer));
+    }
+
+    #[test]
+    fn test_headers_request_range_hint() {
+        let (response, _rx) = oneshot::channel();
+        let request = DownloadRequest::<EthNetworkPrimitives>::GetBlockHeaders {
+            request: HeadersRequest::falling(100u64.into(), 10),
+            response,
+            priority: Priority::Normal,
+        };
+        assert_eq!(request.range_hint(), Some(91..=100));
+
+        let (response, _rx) = oneshot::channel();
+        let request = DownloadRequest::<EthNetworkPrimitives>::GetBlockHeaders {
+            request: HeadersRequest::falling(B256::random().into(), 10),
+            response,
+            priority: Priority::Normal,
+        };
+        assert_eq!(request.range_hint(), None);
     }
 
     #[tokio::test]
@@ -600,6 +716,7 @@ mod tests {
             peer_id,
             Default::default(),
             Default::default(),
+            None,
             Default::default(),
         );
 
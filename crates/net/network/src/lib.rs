@@ -130,6 +130,9 @@ pub mod import;
 pub mod message;
 pub mod peers;
 pub mod protocol;
+pub mod request_quota;
+pub mod snap;
+pub mod snap_requests;
 pub mod transactions;
 
 mod budget;
@@ -153,7 +156,7 @@ pub use reth_network_api::{
     NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
 };
 pub use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
-pub use reth_network_types::{PeersConfig, SessionsConfig};
+pub use reth_network_types::{PeersConfig, PersistedPeer, SessionsConfig};
 pub use session::{
     ActiveSessionHandle, ActiveSessionMessage, Direction, EthRlpxConnection, PeerInfo,
     PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
@@ -165,7 +168,7 @@ pub use config::{NetworkConfig, NetworkConfigBuilder};
 pub use discovery::Discovery;
 pub use fetch::FetchClient;
 pub use flattened_response::FlattenedResponse;
-pub use manager::NetworkManager;
+pub use manager::{write_peers_to_file, NetworkManager};
 pub use metrics::TxTypesCounter;
 pub use network::{NetworkHandle, NetworkProtocols};
 pub use swarm::NetworkConnectionState;
@@ -29,7 +29,9 @@ use crate::{
     peers::PeersManager,
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
-    session::SessionManager,
+    request_quota::EthRequestQuotas,
+    session::{ActiveSessionHandle, SessionManager},
+    snap::SnapFetchClient,
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -37,21 +39,28 @@ use crate::{
 };
 use futures::{Future, StreamExt};
 use parking_lot::Mutex;
+use reth_discv4::Discv4;
+use reth_discv5::Discv5;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, NetworkPrimitives};
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
+use reth_net_nat::{
+    mapping::{PortMappingConfig, PortMappingHandle, PortMappingService, Protocol},
+    NatResolver,
+};
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
     test_utils::PeersHandle,
     EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerKind, PersistedPeer, ReputationChangeKind};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
+    io::{self, Write},
     net::SocketAddr,
     path::Path,
     pin::Pin,
@@ -128,6 +137,13 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest<N>>>,
+    /// Accounts the `eth` requests served to each peer against its quota.
+    ///
+    /// Shared with the [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    eth_request_quotas: EthRequestQuotas,
+    /// The client for requesting state from the peers that serve the `snap` protocol, if it's
+    /// enabled.
+    snap_client: Option<SnapFetchClient>,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -137,6 +153,10 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Maintains the port mappings on the gateway, if the NAT resolver supports it.
+    ///
+    /// The mappings are removed once this is dropped.
+    port_mapping: Option<PortMappingHandle>,
 }
 
 impl NetworkManager {
@@ -189,6 +209,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Returns the [`EthRequestQuotas`] the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler) should account requests
+    /// against, so that the usage of each peer is reported in its [`PeerInfo`].
+    pub const fn eth_request_quotas(&self) -> &EthRequestQuotas {
+        &self.eth_request_quotas
+    }
+
+    /// Sets the client for requesting state through the `snap` protocol, that's handed out by
+    /// [`NetworkHandle::snap_client`](reth_network_api::SnapDownloaderProvider::snap_client).
+    pub fn set_snap_client(&mut self, client: SnapFetchClient) {
+        self.snap_client = Some(client);
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -242,11 +275,13 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             executor,
             hello_message,
             status,
+            block_range,
             fork_filter,
             dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
+            eth_request_quota_config,
             nat,
             handshake,
         } = config;
@@ -275,6 +310,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             // merge configured boot nodes
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
+        let discv5_udp_port =
+            discovery_v5_config.as_ref().map(|discv5| discv5.discovery_socket().port());
 
         let discovery = Discovery::new(
             listener_addr,
@@ -290,6 +327,16 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let discv4 = discovery.discv4();
         let discv5 = discovery.discv5();
 
+        // forward the listener and discovery ports on the gateway
+        let port_mapping = nat.filter(NatResolver::supports_port_mapping).map(|resolver| {
+            spawn_port_mapping(
+                resolver,
+                listener_addr.port(),
+                discv4.clone(),
+                discv5.clone().zip(discv5_udp_port),
+            )
+        });
+
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
         let sessions = SessionManager::new(
@@ -297,6 +344,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -340,9 +388,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            eth_request_quotas: EthRequestQuotas::new(eth_request_quota_config),
+            snap_client: None,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            port_mapping,
         })
     }
 
@@ -422,12 +473,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
     }
 
     /// Collect the peers from the [`NetworkManager`] and write them to the given
-    /// `persistent_peers_file`.
+    /// `persistent_peers_file`, together with their reputation and history.
+    ///
+    /// See also [`write_peers_to_file`].
     pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
-        let known_peers = self.all_peers().collect::<Vec<_>>();
-        persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
-        reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
-        Ok(())
+        write_peers_to_file(&self.swarm.state().peers().persisted_peers(), persistent_peers_file)
     }
 
     /// Returns a new [`FetchClient`] that can be cloned and shared.
@@ -604,6 +654,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             PeerMessage::EthRequest(req) => {
                 self.on_eth_request(peer_id, req);
             }
+            PeerMessage::BlockRangeUpdated(block_range) => {
+                self.swarm.state_mut().on_block_range_update(&peer_id, block_range);
+            }
             PeerMessage::ReceivedTransaction(msg) => {
                 self.notify_tx_manager(NetworkTransactionEvent::IncomingTransactions {
                     peer_id,
@@ -680,9 +733,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::SetMessageTracing(peer_id, enabled) => {
+                self.swarm.sessions_mut().set_message_tracing(peer_id, enabled);
+            }
+            NetworkHandleMessage::GetPeerMessages(peer_id, tx) => {
+                let _ = tx.send(self.swarm.sessions().traced_messages(&peer_id));
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
+            NetworkHandleMessage::SnapClient(tx) => {
+                let _ = tx.send(self.snap_client.clone());
+            }
             NetworkHandleMessage::GetStatus(tx) => {
                 let _ = tx.send(self.status());
             }
@@ -691,6 +753,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
+            NetworkHandleMessage::UpdateBlockRange(block_range) => {
+                self.swarm.sessions_mut().update_block_range(block_range);
+            }
             NetworkHandleMessage::GetPeerInfos(tx) => {
                 let _ = tx.send(self.get_peer_infos());
             }
@@ -750,6 +815,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 status,
                 direction,
             } => {
+                self.eth_request_quotas.add_peer(peer_id);
                 let total_active = self.num_active_peers.fetch_add(1, Ordering::Relaxed) + 1;
                 self.metrics.connected_peers.set(total_active as f64);
                 debug!(
@@ -774,6 +840,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     self.swarm.state_mut().peers_mut().on_active_outgoing_established(peer_id);
                 }
 
+                self.swarm
+                    .state_mut()
+                    .peers_mut()
+                    .set_client_version(&peer_id, Arc::clone(&client_version));
+
                 self.update_active_connection_metrics();
 
                 let peer_kind = self
@@ -807,6 +878,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 self.metrics.tracked_peers.set(self.swarm.state().peers().num_known_peers() as f64);
             }
             SwarmEvent::SessionClosed { peer_id, remote_addr, error } => {
+                self.eth_request_quotas.remove_peer(&peer_id);
                 let total_active = self.num_active_peers.fetch_sub(1, Ordering::Relaxed) - 1;
                 self.metrics.connected_peers.set(total_active as f64);
                 trace!(
@@ -974,7 +1046,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     .state()
                     .peers()
                     .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
+                    .map(|(record, kind)| self.peer_info(session, &record, kind))
             })
             .collect()
     }
@@ -988,10 +1060,22 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 .state()
                 .peers()
                 .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
+                .map(|(record, kind)| self.peer_info(session, &record, kind))
         })
     }
 
+    /// Returns the [`PeerInfo`] of the session, including the usage of served `eth` requests.
+    fn peer_info(
+        &self,
+        session: &ActiveSessionHandle<N>,
+        record: &NodeRecord,
+        kind: PeerKind,
+    ) -> PeerInfo {
+        let mut info = session.peer_info(record, kind);
+        info.request_usage = self.eth_request_quotas.usage(&session.remote_id());
+        info
+    }
+
     /// Returns [`PeerInfo`] for a given peers.
     ///
     /// Ignore the non-active peer.
@@ -1057,6 +1141,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.swarm.sessions_mut().disconnect_all(Some(DisconnectReason::ClientQuitting));
         // drop pending connections
         self.swarm.sessions_mut().disconnect_all_pending();
+        // remove the port mappings from the gateway
+        self.port_mapping.take();
     }
 }
 
@@ -1132,6 +1218,70 @@ impl<N: NetworkPrimitives> Future for NetworkManager<N> {
     }
 }
 
+/// Writes the given peers to the `persistent_peers_file`.
+///
+/// The file can be loaded again with [`PeersConfig::with_basic_nodes_from_file`] to restore the
+/// peer set, including reputation and history, after a restart.
+///
+/// [`PeersConfig::with_basic_nodes_from_file`]: reth_network_types::PeersConfig::with_basic_nodes_from_file
+pub fn write_peers_to_file(
+    peers: &[PersistedPeer],
+    persistent_peers_file: &Path,
+) -> Result<(), FsPathError> {
+    persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
+    // the file is rewritten periodically while the node is running, so it must never be left
+    // behind partially written
+    fs::atomic_write_file(persistent_peers_file, |file| {
+        let mut writer = io::BufWriter::new(file);
+        let entries = peers.iter().map(PersistedPeer::to_json).collect::<Vec<_>>();
+        serde_json::to_writer_pretty(&mut writer, &entries).map_err(io::Error::from)?;
+        writer.flush()
+    })
+}
+
+/// Spawns a [`PortMappingService`] for the tcp listener and the discv4 and discv5 sockets.
+///
+/// The mapped external address is announced in the discv4 node record and the discv5 ENR whenever
+/// it changes.
+fn spawn_port_mapping(
+    resolver: NatResolver,
+    tcp_port: u16,
+    discv4: Option<Discv4>,
+    discv5: Option<(Discv5, u16)>,
+) -> PortMappingHandle {
+    let discv4_udp_port = discv4.as_ref().map(|discv4| discv4.local_addr().port());
+    let discv5_udp_port = discv5.as_ref().map(|(_, port)| *port);
+
+    let mut config = PortMappingConfig::default().with_port(Protocol::Tcp, tcp_port);
+    for udp_port in discv4_udp_port.into_iter().chain(discv5_udp_port) {
+        if !config.ports.contains(&(Protocol::Udp, udp_port)) {
+            config = config.with_port(Protocol::Udp, udp_port);
+        }
+    }
+    let handle = PortMappingService::spawn(resolver, config);
+
+    if discv4.is_some() || discv5.is_some() {
+        let mut external_addr = handle.subscribe();
+        tokio::spawn(async move {
+            while external_addr.changed().await.is_ok() {
+                let Some(addr) = external_addr.borrow_and_update().clone() else { continue };
+                let tcp_port = addr.external_port(Protocol::Tcp, tcp_port).unwrap_or(tcp_port);
+                if let Some((discv4, udp_port)) = discv4.as_ref().zip(discv4_udp_port) {
+                    let udp_port = addr.external_port(Protocol::Udp, udp_port).unwrap_or(udp_port);
+                    discv4.set_external_addr(addr.ip, tcp_port, udp_port);
+                }
+                if let Some((discv5, udp_port)) = &discv5 {
+                    let udp_port =
+                        addr.external_port(Protocol::Udp, *udp_port).unwrap_or(*udp_port);
+                    discv5.set_external_addr(addr.ip, tcp_port, udp_port);
+                }
+            }
+        });
+    }
+
+    handle
+}
+
 #[derive(Debug, Default)]
 struct NetworkManagerPollDurations {
     acc_network_handle: Duration,
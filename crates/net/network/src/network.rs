@@ -1,5 +1,5 @@
 use crate::{
-    config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol,
+    config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol, snap::SnapFetchClient,
     swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
 };
 use alloy_primitives::B256;
@@ -9,8 +9,8 @@ use parking_lot::Mutex;
 use reth_discv4::{Discv4, NatResolver};
 use reth_discv5::Discv5;
 use reth_eth_wire::{
-    DisconnectReason, EthNetworkPrimitives, NetworkPrimitives, NewBlock,
-    NewPooledTransactionHashes, SharedTransactions,
+    BlockRangeUpdate, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives, NewBlock,
+    NewPooledTransactionHashes, SharedTransactions, TracedMessage,
 };
 use reth_ethereum_forks::Head;
 use reth_network_api::{
@@ -18,7 +18,7 @@ use reth_network_api::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SnapDownloaderProvider,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -111,6 +111,13 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
 
+    /// Update the range of blocks the node serves.
+    ///
+    /// This is announced to all `eth/69` peers via `BlockRangeUpdate`.
+    pub fn update_block_range(&self, block_range: BlockRangeUpdate) {
+        self.send_message(NetworkHandleMessage::UpdateBlockRange(block_range));
+    }
+
     /// Announce a block over devp2p
     ///
     /// Caution: in `PoS` this is a noop because new blocks are no longer announced over devp2p.
@@ -190,6 +197,14 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
     pub fn secret_key(&self) -> &SecretKey {
         &self.inner.secret_key
     }
+
+    /// Returns the [`Discv5`] handle, if discovery v5 is enabled.
+    ///
+    /// This can be used to advertise custom kv-pairs in the local ENR at runtime and to find
+    /// peers that advertise them.
+    pub fn discv5(&self) -> Option<&Discv5> {
+        self.inner.discv5.as_ref()
+    }
 }
 
 // === API Implementations ===
@@ -356,6 +371,19 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    fn set_message_tracing(&self, peer_id: PeerId, enabled: bool) {
+        self.send_message(NetworkHandleMessage::SetMessageTracing(peer_id, enabled));
+    }
+
+    async fn peer_messages(
+        &self,
+        peer_id: PeerId,
+    ) -> Result<Option<Vec<TracedMessage>>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetPeerMessages(peer_id, tx));
+        Ok(rx.await?)
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -415,6 +443,11 @@ impl<N: NetworkPrimitives> NetworkSyncUpdater for NetworkHandle<N> {
     fn update_status(&self, head: Head) {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
+
+    /// Update the range of blocks the node serves.
+    fn update_block_range(&self, block_range: BlockRangeUpdate) {
+        self.send_message(NetworkHandleMessage::UpdateBlockRange(block_range));
+    }
 }
 
 impl<N: NetworkPrimitives> BlockDownloaderProvider for NetworkHandle<N> {
@@ -427,6 +460,16 @@ impl<N: NetworkPrimitives> BlockDownloaderProvider for NetworkHandle<N> {
     }
 }
 
+impl<N: NetworkPrimitives> SnapDownloaderProvider for NetworkHandle<N> {
+    type SnapClient = SnapFetchClient;
+
+    async fn snap_client(&self) -> Result<Option<Self::SnapClient>, oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::SnapClient(tx));
+        rx.await
+    }
+}
+
 #[derive(Debug)]
 struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Number of active peer sessions the node's currently handling.
@@ -512,11 +555,16 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     ReputationChange(PeerId, ReputationChangeKind),
     /// Returns the client that can be used to interact with the network.
     FetchClient(oneshot::Sender<FetchClient<N>>),
+    /// Returns the client that can be used to request state from peers through the `snap`
+    /// protocol, if it's enabled.
+    SnapClient(oneshot::Sender<Option<SnapFetchClient>>),
     /// Applies a status update.
     StatusUpdate {
         /// The head status to apply.
         head: Head,
     },
+    /// Updates the range of blocks the node serves.
+    UpdateBlockRange(BlockRangeUpdate),
     /// Retrieves the current status via a oneshot sender.
     GetStatus(oneshot::Sender<NetworkStatus>),
     /// Gets `PeerInfo` for the specified peer IDs.
@@ -529,6 +577,10 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Enables or disables message tracing for a specific peer.
+    SetMessageTracing(PeerId, bool),
+    /// Gets the traced messages of a specific peer via a oneshot sender.
+    GetPeerMessages(PeerId, oneshot::Sender<Option<Vec<TracedMessage>>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle<N>>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
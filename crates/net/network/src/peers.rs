@@ -14,11 +14,12 @@ use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
+    is_banned_reputation,
     peers::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig, PersistedPeer,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -26,6 +27,7 @@ use std::{
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -104,6 +106,7 @@ impl PeersManager {
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
             incoming_ip_throttle_duration,
         } = config;
@@ -113,7 +116,8 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len() + persisted_peers.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
         for trusted_peer in &trusted_nodes {
@@ -130,6 +134,19 @@ impl PeersManager {
             }
         }
 
+        // restore the peers of a previous run with their history, peers that were banned are
+        // skipped so that they start over once they are discovered again
+        for persisted in
+            persisted_peers.iter().filter(|peer| !is_banned_reputation(peer.reputation))
+        {
+            match peers.entry(persisted.record.id) {
+                Entry::Occupied(mut entry) => entry.get_mut().restore_history(persisted),
+                Entry::Vacant(entry) => {
+                    entry.insert(Peer::from_persisted(persisted));
+                }
+            }
+        }
+
         for NodeRecord { address, tcp_port, udp_port, id } in basic_nodes {
             peers.entry(id).or_insert_with(|| {
                 Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
@@ -185,6 +202,26 @@ impl PeersManager {
         })
     }
 
+    /// Returns a snapshot of all peers that should be persisted, together with their reputation
+    /// and history.
+    ///
+    /// Banned peers and peers for which we only know the address of an incoming connection are
+    /// skipped.
+    pub(crate) fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| !peer.is_banned() && !peer.remove_after_disconnect)
+            .map(|(peer_id, peer)| {
+                peer.to_persisted(NodeRecord::new_with_ports(
+                    peer.addr.tcp().ip(),
+                    peer.addr.tcp().port(),
+                    peer.addr.udp().map(|addr| addr.port()),
+                    *peer_id,
+                ))
+            })
+            .collect()
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -352,6 +389,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.mark_seen();
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -360,6 +398,7 @@ impl PeersManager {
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(PeerAddr::from_tcp(addr), PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                peer.mark_seen();
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -435,6 +474,8 @@ impl PeersManager {
 
         // update reputation via seconds connected
         for peer in self.peers.iter_mut().filter(|(_, peer)| peer.state.is_connected()) {
+            peer.1.mark_seen();
+
             // update reputation via seconds connected, but keep the target _around_ the default
             // reputation.
             if peer.1.reputation < DEFAULT_REPUTATION {
@@ -466,6 +507,7 @@ impl PeersManager {
                         rep,
                         ReputationChangeKind::Dropped |
                             ReputationChangeKind::BadAnnouncement |
+                            ReputationChangeKind::ExceededRequestQuota |
                             ReputationChangeKind::Timeout |
                             ReputationChangeKind::AlreadySeenTransaction
                     ) {
@@ -535,6 +577,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().mark_seen();
                     return
                 }
             }
@@ -550,6 +593,23 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.mark_seen();
+        }
+    }
+
+    /// Records the client version the peer advertised for its active session.
+    pub(crate) fn set_client_version(&mut self, peer_id: &PeerId, client_version: Arc<str>) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.client_version = Some(client_version);
+        }
+    }
+
+    /// Called when the peer responded with non-empty headers or bodies.
+    ///
+    /// Such peers are preferred when filling outbound slots, also after a restart.
+    pub(crate) fn on_served_blocks(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.served_blocks = true;
         }
     }
 
@@ -872,6 +932,9 @@ impl PeersManager {
     /// Peers that are `trusted` or `static`, see [`PeerKind`], are prioritized as long as they're
     /// not currently marked as banned or backed off.
     ///
+    /// Among peers with the same reputation, peers that served us blocks before are preferred,
+    /// followed by the most recently seen peers.
+    ///
     /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then this will only consider
     /// `trusted` peers.
     ///
@@ -897,8 +960,8 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer using the reputation and history
+            if dial_priority(maybe_better.1) > dial_priority(best_peer.1) {
                 best_peer = maybe_better;
             }
         }
@@ -994,6 +1057,9 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::GetPersistedPeers(tx) => {
+                        let _ = tx.send(self.persisted_peers());
+                    }
                 }
             }
 
@@ -1036,6 +1102,11 @@ impl PeersManager {
     }
 }
 
+/// Returns the key by which unconnected peers are ranked when filling outbound slots.
+const fn dial_priority(peer: &Peer) -> (i32, bool, Option<u64>) {
+    (peer.reputation, peer.served_blocks, peer.last_seen)
+}
+
 impl Default for PeersManager {
     fn default() -> Self {
         Self::new(Default::default())
@@ -1203,9 +1274,10 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
+        BackoffKind, Peer, PersistedPeer, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1225,7 +1297,7 @@ mod tests {
             PeerConnectionState,
         },
         session::PendingSessionHandshakeError,
-        PeersConfig,
+        write_peers_to_file, PeersConfig,
     };
 
     struct PeerActionFuture<'a> {
@@ -2887,4 +2959,82 @@ mod tests {
         let updated_peer = manager.peers.get(&peer_id).unwrap();
         assert_eq!(updated_peer.addr.tcp().ip(), updated_ip);
     }
+
+    #[tokio::test]
+    async fn test_persisted_peers_are_prioritized() {
+        let good_peer = PeerId::random();
+        let good_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut good = PersistedPeer::new(NodeRecord::new(good_sock, good_peer));
+        good.served_blocks = true;
+        good.last_seen = Some(1);
+
+        let other_peer = PeerId::random();
+        let other_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        let mut other = PersistedPeer::new(NodeRecord::new(other_sock, other_peer));
+        other.last_seen = Some(2);
+
+        let banned_peer = PeerId::random();
+        let banned_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8010);
+        let mut banned = PersistedPeer::new(NodeRecord::new(banned_sock, banned_peer));
+        banned.reputation = BANNED_REPUTATION - 1;
+
+        let config = PeersConfig::test().with_persisted_peers(vec![other, good, banned]);
+        let mut peers = PeersManager::new(config);
+        assert!(!peers.peers.contains_key(&banned_peer));
+        assert!(peers.peers.get(&good_peer).unwrap().served_blocks);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, good_peer);
+                assert_eq!(remote_addr, good_sock);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, other_peer);
+                assert_eq!(remote_addr, other_sock);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peers_file_roundtrip() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        peers.set_client_version(&peer, "reth/v1.0.0".into());
+        peers.on_served_blocks(&peer);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::Timeout);
+        let reputation = peers.get_reputation(&peer).unwrap();
+        assert!(reputation < DEFAULT_REPUTATION);
+
+        let persisted = peers.persisted_peers();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known-peers.json");
+        write_peers_to_file(&persisted, &path).unwrap();
+
+        let config = PeersConfig::test().with_basic_nodes_from_file(Some(&path)).unwrap();
+        assert_eq!(config.persisted_peers, persisted);
+
+        let restored = PeersManager::new(config);
+        let restored_peer = restored.peers.get(&peer).unwrap();
+        assert_eq!(restored_peer.reputation, reputation);
+        assert_eq!(restored_peer.client_version.as_deref(), Some("reth/v1.0.0"));
+        assert!(restored_peer.served_blocks);
+    }
+
+    #[test]
+    fn test_load_plain_peers_file() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let record = NodeRecord::new(socket_addr, PeerId::random());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known-peers.json");
+        std::fs::write(&path, serde_json::to_string(&vec![record]).unwrap()).unwrap();
+
+        let config = PeersConfig::test().with_basic_nodes_from_file(Some(&path)).unwrap();
+        assert_eq!(config.persisted_peers, vec![PersistedPeer::new(record)]);
+    }
 }
@@ -0,0 +1,362 @@
+//! Per-peer quotas for serving `eth` requests.
+//!
+//! Every served request is charged a cost, based on the number of database reads and the size of
+//! the response. Each peer has a budget of requests and cost per request kind that is reset every
+//! accounting window. Requests of peers that are over budget are answered with empty responses.
+
+use parking_lot::Mutex;
+use reth_network_api::{PeerRequestUsage, RequestUsage};
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Default length of the window over which requests are accounted: 1 minute
+pub const DEFAULT_REQUEST_QUOTA_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default number of requests of each kind a peer can make per window.
+pub const DEFAULT_MAX_REQUESTS_PER_WINDOW: u64 = 1_000;
+
+/// Default cost of requests of each kind a peer can make per window.
+///
+/// With the default cost model, this is roughly 1000 full `GetBlockHeaders` requests, or 1GB of
+/// bodies or receipts per minute.
+pub const DEFAULT_MAX_COST_PER_WINDOW: u64 = 1_000_000;
+
+/// The kinds of `eth` requests that are accounted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EthRequestKind {
+    /// `GetBlockHeaders` request.
+    Headers,
+    /// `GetBlockBodies` request.
+    Bodies,
+    /// `GetReceipts` request.
+    Receipts,
+}
+
+/// Budget of a single request kind per window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestQuota {
+    /// Maximum number of requests per window.
+    pub max_requests: u64,
+    /// Maximum cost of requests per window.
+    pub max_cost: u64,
+}
+
+impl RequestQuota {
+    /// Returns a quota that is never exceeded.
+    pub const fn unlimited() -> Self {
+        Self { max_requests: u64::MAX, max_cost: u64::MAX }
+    }
+}
+
+impl Default for RequestQuota {
+    fn default() -> Self {
+        Self {
+            max_requests: DEFAULT_MAX_REQUESTS_PER_WINDOW,
+            max_cost: DEFAULT_MAX_COST_PER_WINDOW,
+        }
+    }
+}
+
+/// Configures the cost model and the per-peer quotas for serving `eth` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EthRequestQuotaConfig {
+    /// Length of the window after which the budget of a peer is reset.
+    pub window: Duration,
+    /// Cost of a single database read.
+    pub cost_per_read: u64,
+    /// Cost of every started KiB of the response.
+    pub cost_per_kib: u64,
+    /// Budget for `GetBlockHeaders` requests.
+    pub headers: RequestQuota,
+    /// Budget for `GetBlockBodies` requests.
+    pub bodies: RequestQuota,
+    /// Budget for `GetReceipts` requests.
+    pub receipts: RequestQuota,
+}
+
+impl EthRequestQuotaConfig {
+    /// Returns a config that never throttles peers, but still accounts for their requests.
+    pub fn unlimited() -> Self {
+        Self::default().with_quota(RequestQuota::unlimited())
+    }
+
+    /// Sets the window after which the budget of a peer is reset.
+    pub const fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets the same budget for all request kinds.
+    pub const fn with_quota(mut self, quota: RequestQuota) -> Self {
+        self.headers = quota;
+        self.bodies = quota;
+        self.receipts = quota;
+        self
+    }
+
+    /// Returns the budget for the given request kind.
+    pub const fn quota(&self, kind: EthRequestKind) -> &RequestQuota {
+        match kind {
+            EthRequestKind::Headers => &self.headers,
+            EthRequestKind::Bodies => &self.bodies,
+            EthRequestKind::Receipts => &self.receipts,
+        }
+    }
+
+    /// Returns the cost of a response that took the given number of database reads and has the
+    /// given size in bytes.
+    pub const fn cost(&self, reads: usize, bytes: usize) -> u64 {
+        (reads as u64)
+            .saturating_mul(self.cost_per_read)
+            .saturating_add((bytes.div_ceil(1024) as u64).saturating_mul(self.cost_per_kib))
+    }
+}
+
+impl Default for EthRequestQuotaConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_REQUEST_QUOTA_WINDOW,
+            cost_per_read: 1,
+            cost_per_kib: 1,
+            headers: RequestQuota::default(),
+            bodies: RequestQuota::default(),
+            receipts: RequestQuota::default(),
+        }
+    }
+}
+
+/// Outcome of checking a request against the quota of the peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaOutcome {
+    /// The request can be served.
+    Allowed,
+    /// The peer is over budget, the request should be answered with an empty response.
+    Throttled {
+        /// Whether this is the first throttled request of the peer in the current window, and
+        /// the peer should be penalized.
+        penalize: bool,
+    },
+}
+
+/// Accounting of a peer in the current window.
+#[derive(Debug)]
+struct PeerQuotaState {
+    /// When the current window started.
+    window_start: Instant,
+    /// Usage in the current window.
+    window: PeerRequestUsage,
+    /// Whether the peer was already penalized in the current window.
+    penalized: bool,
+    /// Usage since the session was established.
+    total: PeerRequestUsage,
+}
+
+impl PeerQuotaState {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            window: Default::default(),
+            penalized: false,
+            total: Default::default(),
+        }
+    }
+}
+
+/// Returns the usage of the given request kind.
+const fn usage_mut(usage: &mut PeerRequestUsage, kind: EthRequestKind) -> &mut RequestUsage {
+    match kind {
+        EthRequestKind::Headers => &mut usage.headers,
+        EthRequestKind::Bodies => &mut usage.bodies,
+        EthRequestKind::Receipts => &mut usage.receipts,
+    }
+}
+
+/// Tracks the `eth` requests of all connected peers against their quotas.
+///
+/// This is shared between the [`EthRequestHandler`](crate::eth_requests::EthRequestHandler),
+/// which charges requests, and the [`NetworkManager`](crate::NetworkManager), which adds a peer
+/// once its session is established, reports its usage in its
+/// [`PeerInfo`](reth_network_api::PeerInfo) and drops it once the session is closed.
+///
+/// Only the requests of added peers are accounted for. Requests that are still queued when the
+/// session of the peer is closed are neither tracked nor throttled.
+#[derive(Debug, Clone, Default)]
+pub struct EthRequestQuotas {
+    inner: Arc<EthRequestQuotasInner>,
+}
+
+#[derive(Debug, Default)]
+struct EthRequestQuotasInner {
+    config: EthRequestQuotaConfig,
+    peers: Mutex<HashMap<PeerId, PeerQuotaState>>,
+}
+
+impl EthRequestQuotas {
+    /// Creates a new instance with the given config.
+    pub fn new(config: EthRequestQuotaConfig) -> Self {
+        Self { inner: Arc::new(EthRequestQuotasInner { config, peers: Default::default() }) }
+    }
+
+    /// Returns the config.
+    pub fn config(&self) -> &EthRequestQuotaConfig {
+        &self.inner.config
+    }
+
+    /// Checks whether the peer still has budget for a request of the given kind.
+    ///
+    /// Since the cost of a request is only known once it's served, a peer can go over its cost
+    /// budget by at most one request. Requests of peers that weren't added are always allowed.
+    pub fn try_acquire(&self, peer_id: PeerId, kind: EthRequestKind) -> QuotaOutcome {
+        self.try_acquire_at(peer_id, kind, Instant::now())
+    }
+
+    fn try_acquire_at(&self, peer_id: PeerId, kind: EthRequestKind, now: Instant) -> QuotaOutcome {
+        let config = &self.inner.config;
+        let quota = config.quota(kind);
+        let mut peers = self.inner.peers.lock();
+        let Some(state) = peers.get_mut(&peer_id) else { return QuotaOutcome::Allowed };
+
+        if now.saturating_duration_since(state.window_start) >= config.window {
+            state.window_start = now;
+            state.window = Default::default();
+            state.penalized = false;
+        }
+
+        let window = usage_mut(&mut state.window, kind);
+        if window.served >= quota.max_requests || window.cost >= quota.max_cost {
+            window.throttled += 1;
+            usage_mut(&mut state.total, kind).throttled += 1;
+            let penalize = !state.penalized;
+            state.penalized = true;
+            return QuotaOutcome::Throttled { penalize }
+        }
+
+        window.served += 1;
+        usage_mut(&mut state.total, kind).served += 1;
+        QuotaOutcome::Allowed
+    }
+
+    /// Charges the peer for a served request of the given kind, that took the given number of
+    /// database reads and has the given response size. Returns the charged cost.
+    pub fn charge(&self, peer_id: PeerId, kind: EthRequestKind, reads: usize, bytes: usize) -> u64 {
+        let cost = self.inner.config.cost(reads, bytes);
+        if let Some(state) = self.inner.peers.lock().get_mut(&peer_id) {
+            let window = usage_mut(&mut state.window, kind);
+            window.cost = window.cost.saturating_add(cost);
+            let total = usage_mut(&mut state.total, kind);
+            total.cost = total.cost.saturating_add(cost);
+        }
+        cost
+    }
+
+    /// Returns the usage of the peer since its session was established.
+    pub fn usage(&self, peer_id: &PeerId) -> Option<PeerRequestUsage> {
+        self.inner.peers.lock().get(peer_id).map(|state| state.total)
+    }
+
+    /// Starts the accounting of the peer, once its session is established.
+    pub fn add_peer(&self, peer_id: PeerId) {
+        self.add_peer_at(peer_id, Instant::now())
+    }
+
+    fn add_peer_at(&self, peer_id: PeerId, now: Instant) {
+        self.inner.peers.lock().insert(peer_id, PeerQuotaState::new(now));
+    }
+
+    /// Removes the accounting of the peer, once its session is closed.
+    pub fn remove_peer(&self, peer_id: &PeerId) {
+        self.inner.peers.lock().remove(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_peer_over_quota() {
+        let config = EthRequestQuotaConfig::default()
+            .with_quota(RequestQuota { max_requests: 2, max_cost: u64::MAX });
+        let quotas = EthRequestQuotas::new(config);
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+        quotas.add_peer_at(peer_id, now);
+
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Headers, now),
+            QuotaOutcome::Allowed
+        );
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Headers, now),
+            QuotaOutcome::Allowed
+        );
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Headers, now),
+            QuotaOutcome::Throttled { penalize: true }
+        );
+        // penalized only once per window
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Headers, now),
+            QuotaOutcome::Throttled { penalize: false }
+        );
+        // other request kinds have their own budget
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Bodies, now),
+            QuotaOutcome::Allowed
+        );
+
+        // budget is reset in the next window
+        let next_window = now + config.window;
+        assert_eq!(
+            quotas.try_acquire_at(peer_id, EthRequestKind::Headers, next_window),
+            QuotaOutcome::Allowed
+        );
+
+        let usage = quotas.usage(&peer_id).unwrap();
+        assert_eq!(usage.headers, RequestUsage { served: 3, throttled: 2, cost: 0 });
+        assert_eq!(usage.bodies, RequestUsage { served: 1, throttled: 0, cost: 0 });
+
+        quotas.remove_peer(&peer_id);
+        assert!(quotas.usage(&peer_id).is_none());
+    }
+
+    #[test]
+    fn throttles_peer_over_cost() {
+        let config = EthRequestQuotaConfig::default()
+            .with_quota(RequestQuota { max_requests: u64::MAX, max_cost: 100 });
+        let quotas = EthRequestQuotas::new(config);
+        let peer_id = PeerId::random();
+        quotas.add_peer(peer_id);
+
+        assert_eq!(quotas.try_acquire(peer_id, EthRequestKind::Receipts), QuotaOutcome::Allowed);
+        // 10 reads and 100 KiB
+        assert_eq!(quotas.charge(peer_id, EthRequestKind::Receipts, 10, 100 * 1024), 110);
+        assert_eq!(
+            quotas.try_acquire(peer_id, EthRequestKind::Receipts),
+            QuotaOutcome::Throttled { penalize: true }
+        );
+        assert_eq!(quotas.usage(&peer_id).unwrap().total_cost(), 110);
+    }
+    #[test]
+    fn ignores_requests_of_closed_sessions() {
+        let config = EthRequestQuotaConfig::default()
+            .with_quota(RequestQuota { max_requests: 0, max_cost: 0 });
+        let quotas = EthRequestQuotas::new(config);
+        let peer_id = PeerId::random();
+        quotas.add_peer(peer_id);
+        quotas.remove_peer(&peer_id);
+
+        // a request that was still queued when the session was closed
+        assert_eq!(quotas.try_acquire(peer_id, EthRequestKind::Headers), QuotaOutcome::Allowed);
+        quotas.charge(peer_id, EthRequestKind::Headers, 10, 1024);
+        assert!(quotas.usage(&peer_id).is_none());
+        assert!(quotas.inner.peers.lock().is_empty());
+    }
+}
@@ -7,7 +7,8 @@ use crate::{
 };
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
-    errors::EthStreamError, Capabilities, DisconnectReason, EthVersion, NetworkPrimitives, Status,
+    errors::EthStreamError, BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion,
+    MessageTracer, NetworkPrimitives, Status,
 };
 use reth_network_api::PeerInfo;
 use reth_network_peers::{NodeRecord, PeerId};
@@ -74,6 +75,8 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Records the messages of the session while tracing is enabled for the peer.
+    pub(crate) message_tracer: MessageTracer,
 }
 
 // === impl ActiveSessionHandle ===
@@ -134,6 +137,11 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
         self.remote_addr
     }
 
+    /// Returns the tracer that records the messages of the session.
+    pub const fn message_tracer(&self) -> &MessageTracer {
+        &self.message_tracer
+    }
+
     /// Extracts the [`PeerInfo`] from the session handle.
     pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
         PeerInfo {
@@ -149,6 +157,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            request_usage: None,
         }
     }
 }
@@ -174,6 +183,8 @@ pub enum PendingSessionEvent<N: NetworkPrimitives> {
         capabilities: Arc<Capabilities>,
         /// The Status message the peer sent for the `eth` handshake
         status: Arc<Status>,
+        /// The block range the peer advertised in its `eth/69` status message
+        block_range: Option<BlockRangeUpdate>,
         /// The actual connection stream which can be used to send and receive `eth` protocol
         /// messages
         conn: EthRlpxConnection<N>,
@@ -181,6 +192,9 @@ pub enum PendingSessionEvent<N: NetworkPrimitives> {
         direction: Direction,
         /// The remote node's user agent, usually containing the client name and version
         client_id: String,
+        /// The tracer installed on the connection, disabled until tracing is requested for the
+        /// peer
+        message_tracer: MessageTracer,
     },
     /// Handshake unsuccessful, session was disconnected.
     Disconnected {
@@ -15,7 +15,7 @@ pub use handle::{
 pub use reth_network_api::{Direction, PeerInfo};
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     future::Future,
     net::SocketAddr,
     sync::{atomic::AtomicU64, Arc},
@@ -34,8 +34,9 @@ use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     errors::EthStreamError, handshake::EthRlpxHandshake, multiplex::RlpxProtocolMultiplexer,
-    Capabilities, DisconnectReason, EthStream, EthVersion, HelloMessageWithProtocols,
-    NetworkPrimitives, Status, UnauthedP2PStream, HANDSHAKE_TIMEOUT,
+    BlockRangeUpdate, Capabilities, DisconnectReason, EthStream, EthVersion,
+    HelloMessageWithProtocols, MessageTracer, NetworkPrimitives, Status, StatusMessage,
+    TracedMessage, UnauthedP2PStream, HANDSHAKE_TIMEOUT,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -54,6 +55,9 @@ use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
 use tracing::{debug, instrument, trace};
 
+/// The number of blocks after which an advanced block range is announced to `eth/69` peers.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -78,6 +82,10 @@ pub struct SessionManager<N: NetworkPrimitives> {
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
     status: Status,
+    /// The range of blocks available locally, advertised to `eth/69` peers.
+    block_range: BlockRangeUpdate,
+    /// The block range that was last announced to active sessions.
+    announced_block_range: BlockRangeUpdate,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -115,6 +123,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     metrics: SessionManagerMetrics,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// Peers whose session messages are traced, including sessions that are established later.
+    traced_peers: HashSet<PeerId>,
 }
 
 // === impl SessionManager ===
@@ -127,6 +137,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
         status: Status,
+        block_range: BlockRangeUpdate,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -144,6 +155,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            block_range,
+            announced_block_range: block_range,
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
@@ -158,6 +171,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             disconnections_counter: Default::default(),
             metrics: Default::default(),
             handshake,
+            traced_peers: Default::default(),
         }
     }
 
@@ -179,6 +193,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         self.status
     }
 
+    /// Returns the block range currently advertised to `eth/69` peers.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        self.block_range
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub const fn secret_key(&self) -> SecretKey {
         self.secret_key
@@ -194,6 +213,27 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         self.hello_message.clone()
     }
 
+    /// Enables or disables tracing of the messages exchanged with the given peer.
+    ///
+    /// This applies to the active session of the peer and to all future sessions.
+    pub(crate) fn set_message_tracing(&mut self, peer_id: PeerId, enabled: bool) {
+        if enabled {
+            self.traced_peers.insert(peer_id);
+        } else {
+            self.traced_peers.remove(&peer_id);
+        }
+        if let Some(session) = self.active_sessions.get(&peer_id) {
+            session.message_tracer.set_enabled(enabled);
+        }
+    }
+
+    /// Returns the traced messages of the active session of the given peer.
+    ///
+    /// Returns `None` if there's no active session with the peer.
+    pub(crate) fn traced_messages(&self, peer_id: &PeerId) -> Option<Vec<TracedMessage>> {
+        self.active_sessions.get(peer_id).map(|session| session.message_tracer.messages())
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub(crate) fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.extra_protocols.push(protocol)
@@ -226,6 +266,26 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         transition
     }
 
+    /// Invoked when the range of locally available blocks changed.
+    ///
+    /// The new range is used for future handshakes. It is announced to all active `eth/69`
+    /// sessions if the earliest block changed or the chain advanced by at least
+    /// [`BLOCK_RANGE_UPDATE_INTERVAL`] blocks since the last announcement.
+    pub(crate) fn update_block_range(&mut self, block_range: BlockRangeUpdate) {
+        self.block_range = block_range;
+
+        let announced = self.announced_block_range;
+        if announced.earliest == block_range.earliest &&
+            block_range.latest.abs_diff(announced.latest) < BLOCK_RANGE_UPDATE_INTERVAL
+        {
+            return
+        }
+        self.announced_block_range = block_range;
+        for peer_id in self.active_sessions.keys() {
+            self.send_message(peer_id, PeerMessage::BlockRangeUpdated(block_range));
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -251,6 +311,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
+        let block_range = self.block_range;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
@@ -269,6 +330,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 secret_key,
                 hello_message,
                 status,
+                block_range,
                 fork_filter,
                 extra_handlers,
             ),
@@ -294,6 +356,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -311,6 +374,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     secret_key,
                     hello_message,
                     status,
+                    block_range,
                     fork_filter,
                     extra_handlers,
                 ),
@@ -475,8 +539,10 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 capabilities,
                 conn,
                 status,
+                block_range,
                 direction,
                 client_id,
+                message_tracer,
             } => {
                 // move from pending to established.
                 self.remove_pending_session(&session_id);
@@ -544,6 +610,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
 
                 self.spawn(session);
 
+                message_tracer.set_enabled(self.traced_peers.contains(&peer_id));
+
                 let client_version = client_id.into();
                 let handle = ActiveSessionHandle {
                     status: status.clone(),
@@ -557,6 +625,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    message_tracer,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -573,6 +642,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     version,
                     capabilities,
                     status,
+                    block_range,
                     messages,
                     direction,
                     timeout,
@@ -682,6 +752,8 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         version: EthVersion,
         /// The Status message the peer sent during the `eth` handshake
         status: Arc<Status>,
+        /// The block range the peer advertised during the `eth/69` handshake
+        block_range: Option<BlockRangeUpdate>,
         /// The channel for sending messages to the peer with the session
         messages: PeerRequestSender<PeerRequest<N>>,
         /// The direction of the session, either `Inbound` or `Outgoing`
@@ -829,6 +901,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -843,6 +916,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
         Direction::Incoming,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -862,6 +936,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -895,6 +970,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
         Direction::Outgoing(remote_peer_id),
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -914,6 +990,7 @@ async fn authenticate<N: NetworkPrimitives>(
     direction: Direction,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -944,6 +1021,7 @@ async fn authenticate<N: NetworkPrimitives>(
         direction,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -997,6 +1075,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent<N> {
@@ -1016,6 +1095,10 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         }
     };
 
+    // messages are only recorded once tracing is enabled for the peer
+    let message_tracer = MessageTracer::default().with_eth_messages::<N>();
+    p2p_stream.set_tracer(message_tracer.clone());
+
     // if we have extra handlers, check if it must be supported by the remote
     if !extra_handlers.is_empty() {
         // ensure that no extra handlers that aren't supported are not mandatory
@@ -1055,12 +1138,13 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         }
     };
 
+    // Before trying status handshake, set up the version to negotiated shared version
+    status.set_eth_version(eth_version);
+    let status = StatusMessage::new(status, block_range);
+
     let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
         // if the shared caps are 1, we know both support the eth version
         // if the hello handshake was successful we can try status handshake
-        //
-        // Before trying status handshake, set up the version to negotiated shared version
-        status.set_eth_version(eth_version);
 
         // perform the eth protocol handshake
         match handshake
@@ -1068,7 +1152,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
             .await
         {
             Ok(their_status) => {
-                let eth_stream = EthStream::new(status.version, p2p_stream);
+                let eth_stream = EthStream::new(eth_version, p2p_stream);
                 (eth_stream.into(), their_status)
             }
             Err(err) => {
@@ -1118,9 +1202,11 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         local_addr,
         peer_id: their_hello.id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
-        status: Arc::new(their_status),
+        status: Arc::new(their_status.to_legacy()),
+        block_range: their_status.block_range(),
         conn,
         direction,
         client_id: their_hello.client_version,
+        message_tracer,
     }
 }
@@ -0,0 +1,473 @@
+//! Support for the `snap` protocol as an `RLPx` subprotocol.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    snap_requests::IncomingSnapRequest,
+};
+use alloy_primitives::bytes::BytesMut;
+use futures::{future, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::SharedCapabilities,
+    multiplex::ProtocolConnection,
+    protocol::Protocol,
+    snap::{
+        GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage,
+        SnapMessageId, SnapProtocolMessage,
+    },
+};
+use reth_network_api::{test_utils::PeersHandle, Direction, PeerId, ReputationChangeKind};
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError, RequestResult},
+    priority::Priority,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_network_peers::WithPeerId;
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::trace;
+
+/// Time to wait for a peer to answer a `snap` request.
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The peers with an active `snap` connection, keyed by their ID.
+type SnapPeers = Arc<Mutex<HashMap<PeerId, mpsc::UnboundedSender<SnapPeerRequest>>>>;
+
+/// The protocol handler that announces `snap/1` to peers and forwards their requests to the
+/// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Handle to the peers, used for reporting misbehaving peers.
+    peers_handle: PeersHandle,
+    /// Sender half for requests to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// The peers with an active `snap` connection.
+    peers: SnapPeers,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that forwards the requests of all peers to the given channel.
+    pub fn new(
+        peers_handle: PeersHandle,
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    ) -> Self {
+        Self { peers_handle, to_request_handler, peers: Default::default() }
+    }
+
+    /// Returns a [`SnapFetchClient`] that sends requests to the peers of this handler.
+    pub fn fetch_client(&self) -> SnapFetchClient {
+        SnapFetchClient {
+            peers_handle: self.peers_handle.clone(),
+            peers: self.peers.clone(),
+            next_peer: Default::default(),
+        }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+}
+
+impl ConnectionHandler for SnapProtocolHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.peers.lock().insert(peer_id, tx);
+        SnapConnection::new(
+            peer_id,
+            conn,
+            self.peers_handle,
+            self.to_request_handler,
+            self.peers,
+            UnboundedReceiverStream::new(rx),
+        )
+    }
+}
+
+/// The `snap` connection of a single peer.
+///
+/// Decodes the requests of the peer, forwards them to the
+/// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler) and yields the encoded
+/// responses. Requests sent through the [`SnapFetchClient`] are sent to the peer and answered
+/// with the peer's responses.
+#[derive(Debug)]
+#[must_use = "Connection does nothing unless polled."]
+pub struct SnapConnection {
+    /// The ID of the peer.
+    peer_id: PeerId,
+    /// The protocol connection.
+    conn: ProtocolConnection,
+    /// Handle to the peers, used for reporting misbehaving peers.
+    peers_handle: PeersHandle,
+    /// Sender half for requests to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Responses that are being served by the request handler.
+    pending_responses: FuturesUnordered<PendingResponse>,
+    /// The peers with an active `snap` connection, this connection removes itself on drop.
+    peers: SnapPeers,
+    /// Requests to send to the peer.
+    commands: UnboundedReceiverStream<SnapPeerRequest>,
+    /// Incremental counter for request ids.
+    next_id: u64,
+    /// Requests sent to the peer that await a response.
+    inflight_requests: HashMap<u64, SnapPeerRequest>,
+    /// Flag indicating whether this stream has previously been terminated.
+    terminated: bool,
+}
+
+impl SnapConnection {
+    /// Creates a new connection.
+    fn new(
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+        peers_handle: PeersHandle,
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+        peers: SnapPeers,
+        commands: UnboundedReceiverStream<SnapPeerRequest>,
+    ) -> Self {
+        Self {
+            peer_id,
+            conn,
+            peers_handle,
+            to_request_handler,
+            pending_responses: Default::default(),
+            peers,
+            commands,
+            next_id: 0,
+            inflight_requests: HashMap::default(),
+            terminated: false,
+        }
+    }
+
+    /// Returns the next request id
+    const fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Report bad message from current peer.
+    fn report_bad_message(&self) {
+        self.peers_handle.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    /// Assigns a request id to the request and returns the message to send to the peer.
+    fn on_command(&mut self, mut command: SnapPeerRequest) -> SnapProtocolMessage {
+        let request_id = self.next_id();
+        match &mut command.request {
+            SnapProtocolMessage::GetAccountRange(msg) => msg.request_id = request_id,
+            SnapProtocolMessage::GetStorageRanges(msg) => msg.request_id = request_id,
+            SnapProtocolMessage::GetByteCodes(msg) => msg.request_id = request_id,
+            SnapProtocolMessage::GetTrieNodes(msg) => msg.request_id = request_id,
+            _ => {}
+        }
+        let message = command.request.clone();
+        self.inflight_requests.insert(request_id, command);
+        message
+    }
+
+    /// Forwards a request of the peer to the request handler.
+    fn on_request<Res>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<Res>) -> IncomingSnapRequest,
+        into_message: fn(Res) -> SnapProtocolMessage,
+    ) where
+        Res: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        if self.to_request_handler.try_send(request(tx)).is_err() {
+            trace!(target: "net::snap", peer_id = %self.peer_id, "dropping request, request handler is busy");
+            return
+        }
+        self.pending_responses
+            .push(PendingResponse(Box::pin(rx.map(move |res| res.ok().map(into_message)))));
+    }
+
+    /// Resolves the inflight request the response belongs to.
+    fn on_response(&mut self, request_id: u64, response: SnapResponse) {
+        let expected = self.inflight_requests.get(&request_id).map(|req| req.request.message_id());
+        let matches = matches!(
+            (expected, &response),
+            (Some(SnapMessageId::GetAccountRange), SnapResponse::AccountRange(_)) |
+                (Some(SnapMessageId::GetStorageRanges), SnapResponse::StorageRanges(_)) |
+                (Some(SnapMessageId::GetByteCodes), SnapResponse::ByteCodes(_)) |
+                (Some(SnapMessageId::GetTrieNodes), SnapResponse::TrieNodes(_))
+        );
+        if !matches {
+            self.report_bad_message();
+            return
+        }
+        if let Some(request) = self.inflight_requests.remove(&request_id) {
+            let _ = request.response.send(Ok(response));
+        }
+    }
+
+    fn on_message(&mut self, msg: SnapProtocolMessage) {
+        let peer_id = self.peer_id;
+        match msg {
+            SnapProtocolMessage::GetAccountRange(request) => self.on_request(
+                |response| IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                SnapProtocolMessage::AccountRange,
+            ),
+            SnapProtocolMessage::GetStorageRanges(request) => self.on_request(
+                |response| IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                SnapProtocolMessage::StorageRanges,
+            ),
+            SnapProtocolMessage::GetByteCodes(request) => self.on_request(
+                |response| IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                SnapProtocolMessage::ByteCodes,
+            ),
+            SnapProtocolMessage::GetTrieNodes(request) => self.on_request(
+                |response| IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                SnapProtocolMessage::TrieNodes,
+            ),
+            SnapProtocolMessage::AccountRange(res) => {
+                self.on_response(res.request_id, SnapResponse::AccountRange(res))
+            }
+            SnapProtocolMessage::StorageRanges(res) => {
+                self.on_response(res.request_id, SnapResponse::StorageRanges(res))
+            }
+            SnapProtocolMessage::ByteCodes(res) => {
+                self.on_response(res.request_id, SnapResponse::ByteCodes(res))
+            }
+            SnapProtocolMessage::TrieNodes(res) => {
+                self.on_response(res.request_id, SnapResponse::TrieNodes(res))
+            }
+        }
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        self.commands.close();
+        let mut peers = self.peers.lock();
+        // the peer may have reconnected in the meantime
+        if peers.get(&self.peer_id).is_some_and(|tx| tx.is_closed()) {
+            peers.remove(&self.peer_id);
+        }
+        drop(peers);
+
+        for (_, request) in self.inflight_requests.drain() {
+            let _ = request.response.send(Err(RequestError::ConnectionDropped));
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.terminated {
+            return Poll::Ready(None)
+        }
+
+        loop {
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                let message = this.on_command(cmd);
+                trace!(target: "net::snap", peer_id = %this.peer_id, message_id = ?message.message_id(), "Sending peer request");
+                return Poll::Ready(Some(BytesMut::from(&message.encode()[..])))
+            }
+
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(BytesMut::from(&response.encode()[..])))
+                }
+                continue
+            }
+
+            if let Poll::Ready(maybe_msg) = this.conn.poll_next_unpin(cx) {
+                let Some(next) = maybe_msg else { break };
+                let Some((&id, mut body)) = next.split_first() else {
+                    this.report_bad_message();
+                    continue
+                };
+                match SnapProtocolMessage::decode(id, &mut body) {
+                    Ok(msg) => {
+                        trace!(target: "net::snap", peer_id = %this.peer_id, message_id = ?msg.message_id(), "Processing message");
+                        this.on_message(msg);
+                    }
+                    Err(error) => {
+                        trace!(target: "net::snap", peer_id = %this.peer_id, %error, "Error decoding peer message");
+                        this.report_bad_message();
+                    }
+                }
+                continue
+            }
+
+            return Poll::Pending
+        }
+
+        // Terminating the connection.
+        this.terminated = true;
+        Poll::Ready(None)
+    }
+}
+
+/// A response that is being served by the request handler.
+///
+/// Resolves to `None` if the request handler dropped the request.
+struct PendingResponse(Pin<Box<dyn Future<Output = Option<SnapProtocolMessage>> + Send>>);
+
+impl std::fmt::Debug for PendingResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingResponse").finish_non_exhaustive()
+    }
+}
+
+impl Future for PendingResponse {
+    type Output = Option<SnapProtocolMessage>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// A request sent to a peer through its [`SnapConnection`].
+#[derive(Debug)]
+struct SnapPeerRequest {
+    /// The request message, the request id is assigned by the connection.
+    request: SnapProtocolMessage,
+    /// The sender for the response.
+    response: oneshot::Sender<RequestResult<SnapResponse>>,
+}
+
+/// The future returned by the [`SnapFetchClient`].
+pub type SnapResponseFut =
+    Pin<Box<dyn Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync>>;
+
+/// Front-end API for fetching state from the peers that support `snap/1`.
+///
+/// Requests are distributed among the connected peers in a round-robin fashion.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// The handle to the peers
+    peers_handle: PeersHandle,
+    /// The peers with an active `snap` connection.
+    peers: SnapPeers,
+    /// Counter used to select the next peer.
+    next_peer: Arc<AtomicUsize>,
+}
+
+impl SnapFetchClient {
+    /// Sends the request to the next peer and returns the future that resolves to its response.
+    fn send_request(&self, request: SnapProtocolMessage) -> SnapResponseFut {
+        let next_peer = {
+            let peers = self.peers.lock();
+            let num_peers = peers.len().max(1);
+            peers
+                .iter()
+                .nth(self.next_peer.fetch_add(1, Ordering::Relaxed) % num_peers)
+                .map(|(peer_id, tx)| (*peer_id, tx.clone()))
+        };
+        let Some((peer_id, tx)) = next_peer else {
+            return Box::pin(future::err(RequestError::ConnectionDropped))
+        };
+
+        let (response, rx) = oneshot::channel();
+        if tx.send(SnapPeerRequest { request, response }).is_err() {
+            return Box::pin(future::err(RequestError::ConnectionDropped))
+        }
+
+        Box::pin(async move {
+            match tokio::time::timeout(SNAP_REQUEST_TIMEOUT, rx).await {
+                Ok(Ok(response)) => response.map(|response| WithPeerId::new(peer_id, response)),
+                Ok(Err(_)) => Err(RequestError::ConnectionDropped),
+                Err(_) => Err(RequestError::Timeout),
+            }
+        })
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.lock().len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    type Output = SnapResponseFut;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetAccountRange(request))
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetStorageRanges(request))
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetByteCodes(request))
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetTrieNodes(request))
+    }
+}
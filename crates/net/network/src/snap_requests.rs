@@ -0,0 +1,966 @@
+//! State management for the `snap` protocol on top of the p2p network.
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, eth_requests::SOFT_RESPONSE_LIMIT,
+    metered_poll_nested_stream_with_budget, metrics::SnapRequestHandlerMetrics,
+};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_primitives::{Bytes, B256};
+use futures::StreamExt;
+use reth_eth_wire::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount, StorageData,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_peers::PeerId;
+use reth_storage_api::{
+    BlockNumReader, HashedStateRangeProvider, HeaderProvider, StateProofProvider, StateProvider,
+    StateProviderBox, StateProviderFactory,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie_common::{proof::ProofNodes, HashedPostState, MultiProofTargets, Nibbles, TrieInput};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.15.5/eth/protocols/snap/handler.go#L35-L62>
+
+/// Maximum number of contract codes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Maximum time spent on serving the trie nodes of a single request.
+pub const MAX_TRIE_NODE_TIME_SPENDING: Duration = Duration::from_secs(5);
+
+/// Number of the most recent blocks whose state can be requested.
+///
+/// Older states are answered with empty responses, as the spec allows.
+pub const MAX_SERVED_STATE_DEPTH: u64 = 128;
+
+/// Number of accounts or storage slots read from the state at once.
+const RANGE_PAGE_SIZE: usize = 128;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Requests are forwarded by the [`SnapConnection`](crate::snap::SnapConnection)s of the peers.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can access the state.
+    client: C,
+    /// Incoming requests from the peers.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: StateProviderFactory + BlockNumReader + HeaderProvider,
+{
+    /// Returns the state with the given state root, if it is the state of one of the
+    /// [`MAX_SERVED_STATE_DEPTH`] most recent blocks.
+    fn state_by_root(&self, root_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
+        let best_number = self.client.best_block_number()?;
+        for number in (best_number.saturating_sub(MAX_SERVED_STATE_DEPTH)..=best_number).rev() {
+            let Some(header) = self.client.sealed_header(number)? else { break };
+            if header.state_root() == root_hash {
+                return self.client.state_by_block_hash(header.hash()).map(Some)
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the accounts of the requested range and the proof of the range boundaries.
+    fn get_account_range_response(
+        &self,
+        request: &GetAccountRangeMessage,
+    ) -> ProviderResult<(Vec<AccountData>, Vec<Bytes>)> {
+        let Some(state) = self.state_by_root(request.root_hash)? else {
+            return Ok(Default::default())
+        };
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let mut accounts = Vec::new();
+        let mut total_bytes = 0;
+
+        let mut next = Some(request.starting_hash);
+        'range: while let Some(start) = next {
+            let range =
+                state.hashed_account_range(HashedPostState::default(), start, RANGE_PAGE_SIZE)?;
+            for entry in range.accounts {
+                let body = alloy_rlp::encode(SlimAccount {
+                    nonce: entry.account.nonce,
+                    balance: entry.account.balance,
+                    storage_root: entry.storage_root,
+                    code_hash: entry.account.get_bytecode_hash(),
+                });
+                total_bytes += B256::len_bytes() + body.len();
+                accounts.push(AccountData { hash: entry.hashed_address, body: body.into() });
+
+                // the first account after the limit is included to prove the end of the range
+                let past_limit = entry.hashed_address >= request.limit_hash;
+                if past_limit || total_bytes > response_bytes {
+                    break 'range
+                }
+            }
+            next = range.next;
+        }
+
+        // prove the origin and the last account of the range
+        let mut targets = MultiProofTargets::account(request.starting_hash);
+        if let Some(last) = accounts.last() {
+            targets.insert(last.hash, Default::default());
+        }
+        let proof = state.multiproof(TrieInput::default(), targets)?.account_subtree;
+
+        Ok((accounts, proof_nodes(proof)))
+    }
+
+    /// Returns the storage slots of the requested accounts and the proof of the range boundaries
+    /// if the last range is incomplete.
+    fn get_storage_ranges_response(
+        &self,
+        request: &GetStorageRangesMessage,
+    ) -> ProviderResult<(Vec<Vec<StorageData>>, Vec<Bytes>)> {
+        let Some(state) = self.state_by_root(request.root_hash)? else {
+            return Ok(Default::default())
+        };
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        // allow some slack so that the last range can be completed
+        let hard_limit = response_bytes + response_bytes / 10;
+
+        let mut slots = Vec::new();
+        let mut total_bytes = 0;
+
+        for (idx, hashed_address) in request.account_hashes.iter().copied().enumerate() {
+            if total_bytes >= hard_limit {
+                break
+            }
+
+            // the origin and the limit only apply to the first account
+            let (origin, limit) = if idx == 0 {
+                (request.starting_hash, request.limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut storage = Vec::new();
+            let mut aborted = false;
+
+            let mut next = Some(origin);
+            'range: while let Some(start) = next {
+                let range = state.hashed_storage_range(
+                    HashedPostState::default(),
+                    hashed_address,
+                    start,
+                    RANGE_PAGE_SIZE,
+                )?;
+                for (hashed_slot, value) in range.slots {
+                    if total_bytes >= hard_limit {
+                        aborted = true;
+                        break 'range
+                    }
+
+                    let data = alloy_rlp::encode(value);
+                    total_bytes += B256::len_bytes() + data.len();
+                    storage.push(StorageData { hash: hashed_slot, data: data.into() });
+
+                    if hashed_slot >= limit {
+                        break 'range
+                    }
+                }
+                next = range.next;
+            }
+
+            // a range that doesn't start at the beginning of the storage or that was cut off must
+            // be proven
+            let prove = origin != B256::ZERO || (aborted && !storage.is_empty());
+            let last = storage.last().map(|slot| slot.hash);
+            if !storage.is_empty() {
+                slots.push(storage);
+            }
+
+            if prove {
+                let targets = MultiProofTargets::account_with_slots(
+                    hashed_address,
+                    core::iter::once(origin).chain(last),
+                );
+                let proof = state
+                    .multiproof(TrieInput::default(), targets)?
+                    .storages
+                    .remove(&hashed_address)
+                    .map(|proof| proof_nodes(proof.subtree))
+                    .unwrap_or_default();
+                return Ok((slots, proof))
+            }
+        }
+
+        Ok((slots, Vec::new()))
+    }
+
+    /// Returns the requested contract codes, skipping unknown ones.
+    fn get_byte_codes_response(&self, request: &GetByteCodesMessage) -> ProviderResult<Vec<Bytes>> {
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        let state = self.client.latest()?;
+
+        let mut codes = Vec::new();
+        let mut total_bytes = 0;
+
+        for code_hash in request.hashes.iter().take(MAX_CODE_LOOKUPS) {
+            if *code_hash == KECCAK_EMPTY {
+                codes.push(Bytes::new());
+            } else if let Some(code) = state.bytecode_by_hash(code_hash)? {
+                let code = code.original_bytes();
+                total_bytes += code.len();
+                codes.push(code);
+            }
+
+            if total_bytes > response_bytes {
+                break
+            }
+        }
+
+        Ok(codes)
+    }
+
+    /// Returns the requested trie nodes in order, up to the first unknown node.
+    ///
+    /// The nodes of every path set are proven separately, so that the time spent on the request
+    /// can be checked before each proof.
+    fn get_trie_nodes_response(&self, request: &GetTrieNodesMessage) -> ProviderResult<Vec<Bytes>> {
+        let Some(state) = self.state_by_root(request.root_hash)? else { return Ok(Vec::new()) };
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let start = Instant::now();
+        let mut nodes = Vec::new();
+        let mut total_bytes = 0;
+        let mut lookups = 0;
+        for path_set in &request.paths {
+            if lookups >= MAX_TRIE_NODE_LOOKUPS ||
+                total_bytes > response_bytes ||
+                start.elapsed() > MAX_TRIE_NODE_TIME_SPENDING
+            {
+                break
+            }
+
+            // each path set either refers to a node of the account trie or starts with the hashed
+            // address of the account whose storage trie nodes are requested
+            let mut requested = Vec::new();
+            let mut targets = MultiProofTargets::default();
+            let mut complete = true;
+            if path_set.slot_paths.is_empty() {
+                let Some(path) = decode_compact_path(&path_set.account_path) else { break };
+                targets.entry(padded_key(&path)).or_default();
+                requested.push((None, path));
+            } else {
+                if path_set.account_path.len() != B256::len_bytes() {
+                    break
+                }
+                let hashed_address = B256::from_slice(&path_set.account_path);
+                for slot_path in &path_set.slot_paths {
+                    let Some(path) = decode_compact_path(slot_path) else {
+                        complete = false;
+                        break
+                    };
+                    targets.entry(hashed_address).or_default().insert(padded_key(&path));
+                    requested.push((Some(hashed_address), path));
+                }
+            }
+            requested.truncate(MAX_TRIE_NODE_LOOKUPS - lookups);
+            lookups += requested.len();
+
+            // every node of a path is part of the proof of any key that starts with the path
+            let proof = state.multiproof(TrieInput::default(), targets)?;
+            for (hashed_address, path) in requested {
+                let node = match hashed_address {
+                    None => proof.account_subtree.get(&path).cloned(),
+                    Some(hashed_address) => proof
+                        .storages
+                        .get(&hashed_address)
+                        .and_then(|storage| storage.subtree.get(&path).cloned()),
+                };
+                // the response is truncated at the first unknown node
+                let Some(node) = node else { return Ok(nodes) };
+
+                total_bytes += node.len();
+                nodes.push(node);
+
+                if total_bytes > response_bytes {
+                    return Ok(nodes)
+                }
+            }
+
+            if !complete {
+                break
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    fn on_account_range_request(
+        &self,
+        peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<AccountRangeMessage>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let (accounts, proof) = self.get_account_range_response(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, ?request, %err, "error serving account range");
+            Default::default()
+        });
+        let _ =
+            response.send(AccountRangeMessage { request_id: request.request_id, accounts, proof });
+    }
+
+    fn on_storage_ranges_request(
+        &self,
+        peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<StorageRangesMessage>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let (slots, proof) = self.get_storage_ranges_response(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, ?request, %err, "error serving storage ranges");
+            Default::default()
+        });
+        let _ =
+            response.send(StorageRangesMessage { request_id: request.request_id, slots, proof });
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<ByteCodesMessage>,
+    ) {
+        self.metrics.snap_byte_codes_requests_received_total.increment(1);
+        let codes = self.get_byte_codes_response(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, ?request, %err, "error serving byte codes");
+            Default::default()
+        });
+        let _ = response.send(ByteCodesMessage { request_id: request.request_id, codes });
+    }
+
+    fn on_trie_nodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<TrieNodesMessage>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let nodes = self.get_trie_nodes_response(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, ?request, %err, "error serving trie nodes");
+            Default::default()
+        });
+        let _ = response.send(TrieNodesMessage { request_id: request.request_id, nodes });
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: StateProviderFactory + BlockNumReader + HeaderProvider + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Returns the nodes of the proof, ordered by their path.
+fn proof_nodes(proof: ProofNodes) -> Vec<Bytes> {
+    proof.into_nodes_sorted().into_iter().map(|(_, node)| node).collect()
+}
+
+/// Decodes a trie path in the compact (hex-prefix) encoding.
+///
+/// Returns `None` if the path is malformed.
+fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    let (&flags, rest) = path.split_first()?;
+    let mut nibbles = Nibbles::with_capacity(rest.len() * 2 + 1);
+    // the low bit of the flag nibble marks paths with an odd number of nibbles
+    if flags & 0x10 != 0 {
+        nibbles.push(flags & 0x0f);
+    }
+    nibbles.extend_from_slice(&Nibbles::unpack(rest));
+    (nibbles.len() <= 2 * B256::len_bytes()).then_some(nibbles)
+}
+
+/// Returns the smallest key that starts with the given path.
+fn padded_key(path: &Nibbles) -> B256 {
+    B256::right_padding_from(&path.pack())
+}
+
+/// All `snap` requests delegated by the [`SnapConnection`](crate::snap::SnapConnection)s.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the accounts.
+        peer_id: PeerId,
+        /// The requested account range.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<AccountRangeMessage>,
+    },
+    /// Request the storage ranges of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage ranges.
+        peer_id: PeerId,
+        /// The requested storage ranges.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<StorageRangesMessage>,
+    },
+    /// Request contract codes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the codes.
+        peer_id: PeerId,
+        /// The requested code hashes.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the codes.
+        response: oneshot::Sender<ByteCodesMessage>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The requested trie node paths.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<TrieNodesMessage>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::EMPTY_ROOT_HASH;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{bytes, keccak256, map::HashMap, Address, U256};
+    use alloy_rlp::Decodable;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_common::init::init_genesis;
+    use reth_eth_wire::snap::TriePath;
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+    };
+    use reth_trie_common::{RlpNode, TrieAccount, TrieNode};
+    use std::{collections::BTreeMap, sync::Arc};
+
+    /// Number of externally owned accounts in the test state, enough for the account trie to
+    /// have several levels.
+    const ACCOUNTS: u64 = 300;
+
+    /// Number of storage slots of the large test contract.
+    const SLOTS: u64 = 300;
+
+    const LARGE_CONTRACT: Address = Address::repeat_byte(0xc1);
+    const SMALL_CONTRACT: Address = Address::repeat_byte(0xc2);
+
+    struct TestState {
+        handler: SnapRequestHandler<BlockchainProvider<MockNodeTypesWithDB>>,
+        root: B256,
+        alloc: BTreeMap<Address, GenesisAccount>,
+    }
+
+    impl TestState {
+        /// Returns the hashed addresses of all accounts, in trie order.
+        fn hashed_addresses(&self) -> Vec<B256> {
+            let mut hashes = self.alloc.keys().map(keccak256).collect::<Vec<_>>();
+            hashes.sort_unstable();
+            hashes
+        }
+
+        /// Returns the hashed slots and the values of the storage of the account, in trie order.
+        fn hashed_storage(&self, address: Address) -> Vec<(B256, U256)> {
+            let mut storage = self.alloc[&address]
+                .storage
+                .iter()
+                .flatten()
+                .map(|(slot, value)| (keccak256(slot), U256::from_be_bytes(value.0)))
+                .collect::<Vec<_>>();
+            storage.sort_unstable();
+            storage
+        }
+
+        /// Returns the storage root of the account from a full account range.
+        fn storage_root(&self, address: Address) -> B256 {
+            let hashed_address = keccak256(address);
+            let (accounts, _) = self
+                .handler
+                .get_account_range_response(&self.account_range(B256::ZERO, u64::MAX))
+                .unwrap();
+            let account = accounts.iter().find(|account| account.hash == hashed_address).unwrap();
+            SlimAccount::decode(&mut &account.body[..]).unwrap().storage_root
+        }
+
+        fn account_range(
+            &self,
+            starting_hash: B256,
+            response_bytes: u64,
+        ) -> GetAccountRangeMessage {
+            GetAccountRangeMessage {
+                request_id: 0,
+                root_hash: self.root,
+                starting_hash,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes,
+            }
+        }
+
+        fn storage_ranges(
+            &self,
+            accounts: &[Address],
+            starting_hash: B256,
+            response_bytes: u64,
+        ) -> GetStorageRangesMessage {
+            GetStorageRangesMessage {
+                request_id: 0,
+                root_hash: self.root,
+                account_hashes: accounts.iter().map(keccak256).collect(),
+                starting_hash,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes,
+            }
+        }
+    }
+
+    /// Creates a database with a genesis state of many accounts and two contracts and a handler
+    /// serving it.
+    fn test_state() -> TestState {
+        let mut alloc = (0..ACCOUNTS)
+            .map(|i| {
+                let account =
+                    GenesisAccount::default().with_nonce(Some(i)).with_balance(U256::from(i + 1));
+                (Address::left_padding_from(&(i + 1).to_be_bytes()), account)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let storage = |slots: u64| {
+            (1..=slots)
+                .map(|i| (B256::from(U256::from(i)), B256::from(U256::from(i * 1000))))
+                .collect::<BTreeMap<_, _>>()
+        };
+        alloc.insert(
+            LARGE_CONTRACT,
+            GenesisAccount::default()
+                .with_balance(U256::from(1))
+                .with_code(Some(bytes!("6080604052348015600f57600080fd5b50")))
+                .with_storage(Some(storage(SLOTS))),
+        );
+        alloc.insert(
+            SMALL_CONTRACT,
+            GenesisAccount::default()
+                .with_code(Some(bytes!("60006000f3")))
+                .with_storage(Some(storage(3))),
+        );
+
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(Genesis::default().extend_accounts(alloc.clone()))
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+
+        let (_, incoming) = tokio::sync::mpsc::channel(1);
+        TestState {
+            handler: SnapRequestHandler::new(BlockchainProvider::new(factory).unwrap(), incoming),
+            root: chain_spec.genesis_header().state_root,
+            alloc,
+        }
+    }
+
+    /// Walks the proof from the root to the key and returns the value of the key, or `None` if
+    /// the proof shows that the key doesn't exist.
+    ///
+    /// Panics if a node on the path is missing from the proof.
+    fn proven_value(root: B256, proof: &[Bytes], key: B256) -> Option<Vec<u8>> {
+        let nodes = proof.iter().map(|node| (keccak256(node), node)).collect::<HashMap<_, _>>();
+        let key = Nibbles::unpack(key);
+        let mut path = 0;
+        let mut node = RlpNode::word_rlp(&root);
+        loop {
+            let encoded = match node.as_hash() {
+                Some(hash) => nodes.get(&hash).expect("node is missing from the proof").to_vec(),
+                None => node.to_vec(),
+            };
+            match TrieNode::decode(&mut &encoded[..]).unwrap() {
+                TrieNode::Branch(branch) => {
+                    let nibble = key[path];
+                    if !branch.state_mask.is_bit_set(nibble) {
+                        return None
+                    }
+                    let idx = (0..nibble).filter(|i| branch.state_mask.is_bit_set(*i)).count();
+                    node = branch.stack[idx].clone();
+                    path += 1;
+                }
+                TrieNode::Extension(extension) => {
+                    if !key.slice(path..).starts_with(&extension.key) {
+                        return None
+                    }
+                    path += extension.key.len();
+                    node = extension.child;
+                }
+                TrieNode::Leaf(leaf) => {
+                    return (key.slice(path..) == leaf.key).then_some(leaf.value)
+                }
+                TrieNode::EmptyRoot => return None,
+            }
+        }
+    }
+
+    /// Returns the value of an account leaf of the trie.
+    fn account_leaf(account: &AccountData) -> Vec<u8> {
+        let account = SlimAccount::decode(&mut &account.body[..]).unwrap();
+        alloy_rlp::encode(TrieAccount {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root: account.storage_root,
+            code_hash: account.code_hash,
+        })
+    }
+
+    #[test]
+    fn serve_account_range() {
+        let state = test_state();
+        let hashed_addresses = state.hashed_addresses();
+
+        let request = state.account_range(B256::ZERO, SOFT_RESPONSE_LIMIT as u64);
+        let (accounts, proof) = state.handler.get_account_range_response(&request).unwrap();
+        assert_eq!(
+            accounts.iter().map(|account| account.hash).collect::<Vec<_>>(),
+            hashed_addresses
+        );
+        for account in &accounts {
+            let address =
+                *state.alloc.keys().find(|address| keccak256(address) == account.hash).unwrap();
+            let expected = &state.alloc[&address];
+            let body = SlimAccount::decode(&mut &account.body[..]).unwrap();
+            assert_eq!(body.nonce, expected.nonce.unwrap_or_default());
+            assert_eq!(body.balance, expected.balance);
+            assert_eq!(
+                body.code_hash,
+                expected.code.as_ref().map(keccak256).unwrap_or(KECCAK_EMPTY)
+            );
+            assert_eq!(body.storage_root == EMPTY_ROOT_HASH, expected.storage.is_none());
+        }
+
+        // the origin is proven absent and the last account is proven
+        assert_eq!(proven_value(state.root, &proof, B256::ZERO), None);
+        let last = accounts.last().unwrap();
+        assert_eq!(proven_value(state.root, &proof, last.hash), Some(account_leaf(last)));
+
+        // a range starting within the state proves the first account of the range
+        let request = state.account_range(hashed_addresses[10], SOFT_RESPONSE_LIMIT as u64);
+        let (accounts, proof) = state.handler.get_account_range_response(&request).unwrap();
+        assert_eq!(accounts.len(), hashed_addresses.len() - 10);
+        assert_eq!(
+            proven_value(state.root, &proof, accounts[0].hash),
+            Some(account_leaf(&accounts[0]))
+        );
+
+        // the first account after the limit ends the range
+        let request = GetAccountRangeMessage {
+            limit_hash: B256::from(U256::from_be_bytes(hashed_addresses[4].0) - U256::from(1)),
+            ..state.account_range(B256::ZERO, SOFT_RESPONSE_LIMIT as u64)
+        };
+        let (accounts, _) = state.handler.get_account_range_response(&request).unwrap();
+        assert_eq!(
+            accounts.iter().map(|account| account.hash).collect::<Vec<_>>(),
+            hashed_addresses[..5]
+        );
+
+        // unknown roots are answered with empty responses
+        let request = GetAccountRangeMessage { root_hash: B256::repeat_byte(1), ..request };
+        assert_eq!(state.handler.get_account_range_response(&request).unwrap(), Default::default());
+    }
+
+    #[test]
+    fn account_range_respects_byte_budget() {
+        let state = test_state();
+
+        // the first account is always served
+        let request = state.account_range(B256::ZERO, 1);
+        let (accounts, proof) = state.handler.get_account_range_response(&request).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            proven_value(state.root, &proof, accounts[0].hash),
+            Some(account_leaf(&accounts[0]))
+        );
+
+        // accounts are served until the budget is exceeded
+        let budget = 1000;
+        let request = state.account_range(B256::ZERO, budget);
+        let (accounts, proof) = state.handler.get_account_range_response(&request).unwrap();
+        let size = |accounts: &[AccountData]| {
+            accounts.iter().map(|account| B256::len_bytes() + account.body.len()).sum::<usize>()
+        };
+        assert!(size(&accounts) > budget as usize);
+        assert!(size(&accounts[..accounts.len() - 1]) <= budget as usize);
+        assert!(accounts.len() < state.alloc.len());
+        let last = accounts.last().unwrap();
+        assert_eq!(proven_value(state.root, &proof, last.hash), Some(account_leaf(last)));
+    }
+
+    #[test]
+    fn serve_storage_ranges() {
+        let state = test_state();
+        let large_storage = state.hashed_storage(LARGE_CONTRACT);
+        let small_storage = state.hashed_storage(SMALL_CONTRACT);
+        let slots_of = |storage: &[StorageData]| {
+            storage
+                .iter()
+                .map(|slot| (slot.hash, U256::decode(&mut &slot.data[..]).unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        // complete storages are served without proof
+        let request = state.storage_ranges(
+            &[SMALL_CONTRACT, LARGE_CONTRACT],
+            B256::ZERO,
+            SOFT_RESPONSE_LIMIT as u64,
+        );
+        let (slots, proof) = state.handler.get_storage_ranges_response(&request).unwrap();
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots_of(&slots[0]), small_storage);
+        assert_eq!(slots_of(&slots[1]), large_storage);
+        assert!(proof.is_empty());
+
+        // a range that doesn't start at the beginning of the storage is proven
+        let storage_root = state.storage_root(LARGE_CONTRACT);
+        let origin = large_storage[100].0;
+        let request = state.storage_ranges(&[LARGE_CONTRACT], origin, SOFT_RESPONSE_LIMIT as u64);
+        let (slots, proof) = state.handler.get_storage_ranges_response(&request).unwrap();
+        assert_eq!(slots_of(&slots[0]), large_storage[100..]);
+        let last = slots[0].last().unwrap();
+        assert_eq!(
+            proven_value(storage_root, &proof, origin),
+            Some(alloy_rlp::encode(large_storage[100].1))
+        );
+        assert_eq!(proven_value(storage_root, &proof, last.hash), Some(last.data.to_vec()));
+    }
+
+    #[test]
+    fn storage_ranges_respect_byte_budget() {
+        let state = test_state();
+        let large_storage = state.hashed_storage(LARGE_CONTRACT);
+        let storage_root = state.storage_root(LARGE_CONTRACT);
+
+        // the large storage is cut off and proven, the following accounts are not served
+        let budget = 1000;
+        let request = state.storage_ranges(&[LARGE_CONTRACT, SMALL_CONTRACT], B256::ZERO, budget);
+        let (slots, proof) = state.handler.get_storage_ranges_response(&request).unwrap();
+        assert_eq!(slots.len(), 1);
+        let served = &slots[0];
+        assert!(served.len() < large_storage.len());
+        assert_eq!(
+            served.iter().map(|slot| slot.hash).collect::<Vec<_>>(),
+            large_storage[..served.len()].iter().map(|(slot, _)| *slot).collect::<Vec<_>>()
+        );
+        // slots are served until the budget with some slack for completing the range is reached
+        let size = |slots: &[StorageData]| {
+            slots.iter().map(|slot| B256::len_bytes() + slot.data.len()).sum::<usize>()
+        };
+        let hard_limit = (budget + budget / 10) as usize;
+        assert!(size(served) >= hard_limit);
+        assert!(size(&served[..served.len() - 1]) < hard_limit);
+        let last = served.last().unwrap();
+        assert_eq!(proven_value(storage_root, &proof, last.hash), Some(last.data.to_vec()));
+    }
+
+    #[test]
+    fn serve_byte_codes() {
+        let state = test_state();
+        let large_code = state.alloc[&LARGE_CONTRACT].code.clone().unwrap();
+        let small_code = state.alloc[&SMALL_CONTRACT].code.clone().unwrap();
+
+        // unknown codes are skipped
+        let request = GetByteCodesMessage {
+            request_id: 0,
+            hashes: vec![
+                keccak256(&large_code),
+                B256::repeat_byte(1),
+                KECCAK_EMPTY,
+                keccak256(&small_code),
+            ],
+            response_bytes: SOFT_RESPONSE_LIMIT as u64,
+        };
+        assert_eq!(
+            state.handler.get_byte_codes_response(&request).unwrap(),
+            vec![large_code.clone(), Bytes::new(), small_code]
+        );
+
+        // codes are served until the budget is exceeded
+        let request = GetByteCodesMessage { response_bytes: 1, ..request };
+        assert_eq!(state.handler.get_byte_codes_response(&request).unwrap(), vec![large_code]);
+    }
+
+    #[test]
+    fn serve_trie_nodes() {
+        let state = test_state();
+        let storage_root = state.storage_root(LARGE_CONTRACT);
+        let compact = |nibbles: &[u8]| {
+            let (flag, rest) = if nibbles.len() % 2 == 1 {
+                (0x10 | nibbles[0], &nibbles[1..])
+            } else {
+                (0x00, nibbles)
+            };
+            let mut path = vec![flag];
+            path.extend_from_slice(&Nibbles::from_nibbles(rest).pack());
+            Bytes::from(path)
+        };
+        let account_path =
+            |nibbles: &[u8]| TriePath { account_path: compact(nibbles), slot_paths: Vec::new() };
+
+        let request = GetTrieNodesMessage {
+            request_id: 0,
+            root_hash: state.root,
+            paths: vec![
+                account_path(&[]),
+                account_path(&[0x1]),
+                TriePath {
+                    account_path: keccak256(LARGE_CONTRACT).into(),
+                    slot_paths: vec![compact(&[]), compact(&[0x2])],
+                },
+            ],
+            response_bytes: SOFT_RESPONSE_LIMIT as u64,
+        };
+        let nodes = state.handler.get_trie_nodes_response(&request).unwrap();
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(keccak256(&nodes[0]), state.root);
+        assert_eq!(keccak256(&nodes[2]), storage_root);
+
+        // the child nodes are referenced by their parents
+        let child_hash =
+            |parent: &Bytes, nibble: u8| match TrieNode::decode(&mut &parent[..]).unwrap() {
+                TrieNode::Branch(branch) => {
+                    let idx = (0..nibble).filter(|i| branch.state_mask.is_bit_set(*i)).count();
+                    branch.stack[idx].as_hash().unwrap()
+                }
+                node => panic!("unexpected root node {node:?}"),
+            };
+        assert_eq!(keccak256(&nodes[1]), child_hash(&nodes[0], 0x1));
+        assert_eq!(keccak256(&nodes[3]), child_hash(&nodes[2], 0x2));
+
+        // the response is truncated at the first unknown node
+        let request = GetTrieNodesMessage {
+            paths: vec![account_path(&[]), account_path(&[0x1; 63]), account_path(&[0x1])],
+            ..request
+        };
+        let nodes = state.handler.get_trie_nodes_response(&request).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(keccak256(&nodes[0]), state.root);
+
+        // nodes are served until the budget is exceeded
+        let request = GetTrieNodesMessage {
+            paths: vec![account_path(&[]), account_path(&[0x1])],
+            response_bytes: 1,
+            ..request
+        };
+        assert_eq!(state.handler.get_trie_nodes_response(&request).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn decode_compact_paths() {
+        // even path
+        assert_eq!(
+            decode_compact_path(&[0x00, 0x12, 0x34]),
+            Some(Nibbles::from_nibbles([0x1, 0x2, 0x3, 0x4]))
+        );
+        // odd path
+        assert_eq!(
+            decode_compact_path(&[0x11, 0x23]),
+            Some(Nibbles::from_nibbles([0x1, 0x2, 0x3]))
+        );
+        // root
+        assert_eq!(decode_compact_path(&[0x00]), Some(Nibbles::default()));
+        // empty and too long paths are malformed
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(decode_compact_path(&[0u8; 34]), None);
+    }
+
+    #[test]
+    fn pad_path_to_key() {
+        let path = Nibbles::from_nibbles([0x1, 0x2, 0x3]);
+        let mut expected = B256::ZERO;
+        expected[0] = 0x12;
+        expected[1] = 0x30;
+        assert_eq!(padded_key(&path), expected);
+    }
+}
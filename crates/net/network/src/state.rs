@@ -12,8 +12,8 @@ use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use rand::seq::SliceRandom;
 use reth_eth_wire::{
-    BlockHashNumber, Capabilities, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
-    NewBlockHashes, Status,
+    BlockHashNumber, BlockRangeUpdate, Capabilities, DisconnectReason, EthNetworkPrimitives,
+    NetworkPrimitives, NewBlockHashes, Status,
 };
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
@@ -147,15 +147,24 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         peer: PeerId,
         capabilities: Arc<Capabilities>,
         status: Arc<Status>,
+        block_range: Option<BlockRangeUpdate>,
         request_tx: PeerRequestSender<PeerRequest<N>>,
         timeout: Arc<AtomicU64>,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
-        // find the corresponding block number
-        let block_number =
-            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        // find the corresponding block number, `eth/69` peers advertise it directly
+        let block_number = match block_range {
+            Some(range) => range.latest,
+            None => self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default(),
+        };
+        self.state_fetcher.new_active_peer(
+            peer,
+            status.blockhash,
+            block_number,
+            block_range,
+            timeout,
+        );
 
         self.active_peers.insert(
             peer,
@@ -255,6 +264,14 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Invoked when the peer announced a new range of blocks it serves.
+    pub(crate) fn on_block_range_update(&mut self, peer_id: &PeerId, range: BlockRangeUpdate) {
+        if let Some(peer) = self.active_peers.get_mut(peer_id) {
+            peer.best_hash = range.latest_hash;
+        }
+        self.state_fetcher.update_peer_block_range(peer_id, range);
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
@@ -404,9 +421,15 @@ impl<N: NetworkPrimitives> NetworkState<N> {
     fn on_eth_response(&mut self, peer: PeerId, resp: PeerResponseResult<N>) {
         let outcome = match resp {
             PeerResponseResult::BlockHeaders(res) => {
+                if res.as_ref().is_ok_and(|headers| !headers.is_empty()) {
+                    self.peers_manager.on_served_blocks(&peer);
+                }
                 self.state_fetcher.on_block_headers_response(peer, res)
             }
             PeerResponseResult::BlockBodies(res) => {
+                if res.as_ref().is_ok_and(|bodies| !bodies.is_empty()) {
+                    self.peers_manager.on_served_blocks(&peer);
+                }
                 self.state_fetcher.on_block_bodies_response(peer, res)
             }
             _ => None,
@@ -611,6 +634,7 @@ mod tests {
             peer_id,
             capabilities(),
             Arc::default(),
+            None,
             peer_tx,
             Arc::new(AtomicU64::new(1)),
         );
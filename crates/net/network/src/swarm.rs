@@ -119,6 +119,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                 capabilities,
                 version,
                 status,
+                block_range,
                 messages,
                 direction,
                 timeout,
@@ -127,6 +128,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                     peer_id,
                     capabilities.clone(),
                     status.clone(),
+                    block_range,
                     messages.clone(),
                     timeout,
                 );
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr, time::Duration};
 
 use super::{
     PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
@@ -9,8 +9,11 @@ use crate::transactions::constants::tx_fetcher::{
     DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
     DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
 };
+use alloy_primitives::TxHash;
 use derive_more::{Constructor, Display};
 use reth_eth_wire::NetworkPrimitives;
+use reth_network_peers::PeerId;
+use reth_transaction_pool::TransactionOrigin;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Clone)]
@@ -98,13 +101,46 @@ impl Default for TransactionFetcherConfig {
     }
 }
 
-/// A policy defining which peers pending transactions are gossiped to.
+/// A policy defining which peers pending transactions are gossiped to, and how.
+///
+/// For every peer that passes [`can_propagate`](Self::can_propagate), the policy decides per
+/// transaction whether it is sent in full, announced by hash or not propagated at all, see
+/// [`propagation_action`](Self::propagation_action).
+///
+/// Transactions with [`TransactionOrigin::Private`] are never subject to the policy: they are only
+/// ever sent to the [`private_relay_peers`](Self::private_relay_peers).
 pub trait TransactionPropagationPolicy: Send + Sync + Unpin + 'static {
     /// Filter a given peer based on the policy.
     ///
     /// This determines whether transactions can be propagated to this peer.
     fn can_propagate<N: NetworkPrimitives>(&self, peer: &mut PeerMetadata<N>) -> bool;
 
+    /// Decides how the transaction is propagated to the peer.
+    ///
+    /// `default` is the action that follows from the configured [`TransactionPropagationMode`].
+    ///
+    /// Note: transactions that can't be broadcast in full, e.g. EIP-4844 transactions, are
+    /// announced by hash, even if [`TransactionPropagationAction::Full`] is returned.
+    fn propagation_action<N: NetworkPrimitives>(
+        &self,
+        _peer: &PeerMetadata<N>,
+        _transaction: &TransactionPropagationInfo,
+        default: TransactionPropagationAction,
+    ) -> TransactionPropagationAction {
+        default
+    }
+
+    /// Returns the peers that [`TransactionOrigin::Private`] transactions are relayed to.
+    ///
+    /// Private transactions are sent in full to connected peers of this set, and to no one else.
+    /// If this returns `None`, private transactions are never propagated.
+    ///
+    /// Note: this is queried once when the [`TransactionsManager`](super::TransactionsManager) is
+    /// created to determine whether it should listen for private transactions at all.
+    fn private_relay_peers(&self) -> Option<&HashSet<PeerId>> {
+        None
+    }
+
     /// A callback on the policy when a new peer session is established.
     fn on_session_established<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>);
 
@@ -149,3 +185,147 @@ impl FromStr for TransactionPropagationKind {
         }
     }
 }
+
+/// How a transaction is propagated to a single peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionPropagationAction {
+    /// Send the full transaction in a `Transactions` message.
+    Full,
+    /// Announce the hash of the transaction in a `NewPooledTransactionHashes` message.
+    Hash,
+    /// Don't propagate the transaction to the peer.
+    Skip,
+}
+
+/// Information about a transaction that is about to be propagated, see
+/// [`TransactionPropagationPolicy::propagation_action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionPropagationInfo {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// Where the transaction originates from.
+    pub origin: TransactionOrigin,
+    /// The encoded length of the transaction.
+    pub size: usize,
+    /// The EIP-2718 type of the transaction.
+    pub ty: u8,
+}
+
+/// A [`TransactionPropagationPolicy`] that relays [`TransactionOrigin::Private`] transactions to a
+/// fixed set of peers and otherwise delegates to the wrapped policy.
+#[derive(Debug, Clone, Default)]
+pub struct PrivateRelayPolicy<P = TransactionPropagationKind> {
+    /// The policy for all non-private transactions.
+    inner: P,
+    /// The peers private transactions are relayed to.
+    relay_peers: HashSet<PeerId>,
+}
+
+impl<P> PrivateRelayPolicy<P> {
+    /// Creates a new policy that relays private transactions to the given peers.
+    pub fn new(inner: P, relay_peers: impl IntoIterator<Item = PeerId>) -> Self {
+        Self { inner, relay_peers: relay_peers.into_iter().collect() }
+    }
+
+    /// Returns the wrapped policy.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Returns the peers private transactions are relayed to.
+    pub const fn relay_peers(&self) -> &HashSet<PeerId> {
+        &self.relay_peers
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for PrivateRelayPolicy<P> {
+    fn can_propagate<N: NetworkPrimitives>(&self, peer: &mut PeerMetadata<N>) -> bool {
+        self.inner.can_propagate(peer)
+    }
+
+    fn propagation_action<N: NetworkPrimitives>(
+        &self,
+        peer: &PeerMetadata<N>,
+        transaction: &TransactionPropagationInfo,
+        default: TransactionPropagationAction,
+    ) -> TransactionPropagationAction {
+        self.inner.propagation_action(peer, transaction, default)
+    }
+
+    fn private_relay_peers(&self) -> Option<&HashSet<PeerId>> {
+        Some(&self.relay_peers)
+    }
+
+    fn on_session_established<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_established(peer)
+    }
+
+    fn on_session_closed<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_closed(peer)
+    }
+}
+
+/// A [`TransactionPropagationPolicy`] that scores peers by their [`PeerMetadata`] and otherwise
+/// delegates to the wrapped policy.
+///
+/// Peers that sent us too many invalid transactions are excluded from propagation, and peers that
+/// respond slowly to transaction requests only receive hash announcements.
+#[derive(Debug, Clone)]
+pub struct PeerScoringPolicy<P = TransactionPropagationKind> {
+    /// The wrapped policy.
+    inner: P,
+    /// Peers that sent us more invalid transactions are excluded from propagation.
+    max_invalid_transactions: u64,
+    /// Peers with a higher latency only receive hash announcements.
+    max_full_latency: Option<Duration>,
+}
+
+impl<P> PeerScoringPolicy<P> {
+    /// Creates a new policy that excludes peers that sent us more than `max_invalid_transactions`
+    /// invalid transactions.
+    pub const fn new(inner: P, max_invalid_transactions: u64) -> Self {
+        Self { inner, max_invalid_transactions, max_full_latency: None }
+    }
+
+    /// Only announce transactions by hash to peers with a latency above the given threshold.
+    pub const fn with_max_full_latency(mut self, max_full_latency: Duration) -> Self {
+        self.max_full_latency = Some(max_full_latency);
+        self
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for PeerScoringPolicy<P> {
+    fn can_propagate<N: NetworkPrimitives>(&self, peer: &mut PeerMetadata<N>) -> bool {
+        peer.invalid_transactions() <= self.max_invalid_transactions &&
+            self.inner.can_propagate(peer)
+    }
+
+    fn propagation_action<N: NetworkPrimitives>(
+        &self,
+        peer: &PeerMetadata<N>,
+        transaction: &TransactionPropagationInfo,
+        default: TransactionPropagationAction,
+    ) -> TransactionPropagationAction {
+        let action = self.inner.propagation_action(peer, transaction, default);
+        let is_slow = self
+            .max_full_latency
+            .zip(peer.latency())
+            .is_some_and(|(max_latency, latency)| latency > max_latency);
+        if is_slow && action == TransactionPropagationAction::Full {
+            return TransactionPropagationAction::Hash
+        }
+        action
+    }
+
+    fn private_relay_peers(&self) -> Option<&HashSet<PeerId>> {
+        self.inner.private_relay_peers()
+    }
+
+    fn on_session_established<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_established(peer)
+    }
+
+    fn on_session_closed<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_closed(peer)
+    }
+}
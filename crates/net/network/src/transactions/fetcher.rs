@@ -37,7 +37,6 @@ use crate::{
     metrics::TransactionFetcherMetrics,
     transactions::{validation, PartiallyFilterMessage},
 };
-use alloy_consensus::transaction::PooledTransaction;
 use alloy_primitives::TxHash;
 use derive_more::{Constructor, Deref};
 use futures::{stream::FuturesUnordered, Future, FutureExt, Stream, StreamExt};
@@ -47,6 +46,7 @@ use reth_eth_wire::{
     PartiallyValidData, RequestTxHashes, ValidAnnouncementData,
 };
 use reth_eth_wire_types::{EthNetworkPrimitives, NetworkPrimitives};
+use reth_ethereum_primitives::PooledTransaction;
 use reth_network_api::PeerRequest;
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
@@ -56,7 +56,7 @@ use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::trace;
@@ -867,7 +867,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
     ) -> FetchEvent<N::PooledTransaction> {
         // update peer activity, requests for buffered hashes can only be made to idle
         // fallback peers
-        let GetPooledTxResponse { peer_id, mut requested_hashes, result } = response;
+        let GetPooledTxResponse { peer_id, mut requested_hashes, result, elapsed } = response;
 
         self.decrement_inflight_request_count_for(&peer_id);
 
@@ -884,7 +884,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                         "received empty `PooledTransactions` response from peer, peer failed to serve hashes it announced"
                     );
 
-                    return FetchEvent::EmptyResponse { peer_id }
+                    return FetchEvent::EmptyResponse { peer_id, elapsed }
                 }
 
                 //
@@ -971,7 +971,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
                 let transactions = valid_payload.into_data().into_values().collect();
 
-                FetchEvent::TransactionsFetched { peer_id, transactions }
+                FetchEvent::TransactionsFetched { peer_id, transactions, elapsed }
             }
             Ok(Err(req_err)) => {
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
@@ -1059,6 +1059,8 @@ pub enum FetchEvent<T = PooledTransaction> {
         peer_id: PeerId,
         /// The transactions that were fetched, if available.
         transactions: PooledTransactions<T>,
+        /// The time it took the peer to respond.
+        elapsed: Duration,
     },
     /// Triggered when there is an error in fetching transactions.
     FetchError {
@@ -1071,6 +1073,8 @@ pub enum FetchEvent<T = PooledTransaction> {
     EmptyResponse {
         /// The ID of the sender.
         peer_id: PeerId,
+        /// The time it took the peer to respond.
+        elapsed: Duration,
     },
 }
 
@@ -1081,6 +1085,8 @@ pub struct GetPooledTxRequest<T = PooledTransaction> {
     /// Transaction hashes that were requested, for cleanup purposes
     requested_hashes: RequestTxHashes,
     response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
+    /// When the request was sent.
+    timestamp: Instant,
 }
 
 /// Upon reception of a response, a [`GetPooledTxRequest`] is deconstructed to form a
@@ -1092,6 +1098,8 @@ pub struct GetPooledTxResponse<T = PooledTransaction> {
     /// subset of requested hashes.
     requested_hashes: RequestTxHashes,
     result: Result<RequestResult<PooledTransactions<T>>, RecvError>,
+    /// The time it took the peer to respond.
+    elapsed: Duration,
 }
 
 /// Stores the response receiver made by sending a [`GetPooledTransactions`] request to a peer's
@@ -1106,12 +1114,19 @@ pub struct GetPooledTxRequestFut<T = PooledTransaction> {
 
 impl<T> GetPooledTxRequestFut<T> {
     #[inline]
-    const fn new(
+    fn new(
         peer_id: PeerId,
         requested_hashes: RequestTxHashes,
         response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
     ) -> Self {
-        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response }) }
+        Self {
+            inner: Some(GetPooledTxRequest {
+                peer_id,
+                requested_hashes,
+                response,
+                timestamp: Instant::now(),
+            }),
+        }
     }
 }
 
@@ -1125,6 +1140,7 @@ impl<T> Future for GetPooledTxRequestFut<T> {
                 peer_id: req.peer_id,
                 requested_hashes: req.requested_hashes,
                 result,
+                elapsed: req.timestamp.elapsed(),
             }),
             Poll::Pending => {
                 self.project().inner.set(Some(req));
@@ -14,7 +14,8 @@ pub use self::constants::{
 };
 use config::TransactionPropagationKind;
 pub use config::{
-    TransactionFetcherConfig, TransactionPropagationMode, TransactionPropagationPolicy,
+    PeerScoringPolicy, PrivateRelayPolicy, TransactionFetcherConfig, TransactionPropagationAction,
+    TransactionPropagationInfo, TransactionPropagationMode, TransactionPropagationPolicy,
     TransactionsManagerConfig,
 };
 pub use validation::*;
@@ -59,7 +60,7 @@ use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
     GetPooledTransactionLimit, PoolTransaction, PropagateKind, PropagatedTransactions,
-    TransactionPool, ValidPoolTransaction,
+    TransactionListenerKind, TransactionOrigin, TransactionPool, ValidPoolTransaction,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -294,6 +295,12 @@ pub struct TransactionsManager<
     ///   - all dynamic fee requirements are (currently) met
     ///   - account has enough balance to cover the transaction's gas
     pending_transactions: ReceiverStream<TxHash>,
+    /// A stream that yields all new __pending__ transactions, including those that are not allowed
+    /// to be propagated.
+    ///
+    /// This is only installed if the policy relays [`TransactionOrigin::Private`] transactions,
+    /// see [`TransactionPropagationPolicy::private_relay_peers`].
+    private_transactions: Option<ReceiverStream<TxHash>>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent<N>>,
     /// How the `TransactionsManager` is configured.
@@ -348,6 +355,12 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
         // install a listener for new __pending__ transactions that are allowed to be propagated
         // over the network
         let pending = pool.pending_transactions_listener();
+        // private transactions are not allowed to be propagated, so they need a separate listener
+        let private_transactions = propagation_policy.private_relay_peers().map(|_| {
+            ReceiverStream::new(
+                pool.pending_transactions_listener_for(TransactionListenerKind::All),
+            )
+        });
         let pending_pool_imports_info = PendingPoolImportsInfo::default();
         let metrics = TransactionsManagerMetrics::default();
         metrics
@@ -369,6 +382,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
             command_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
             pending_transactions: ReceiverStream::new(pending),
+            private_transactions,
             transaction_events: UnboundedMeteredReceiver::new(
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
@@ -392,7 +406,10 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
             self.transaction_fetcher.has_capacity_for_fetching_pending_hashes()
     }
 
-    fn report_peer_bad_transactions(&self, peer_id: PeerId) {
+    fn report_peer_bad_transactions(&mut self, peer_id: PeerId) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.invalid_transactions += 1;
+        }
         self.report_peer(peer_id, ReputationChangeKind::BadTransactions);
         self.metrics.reported_bad_transactions.increment(1);
     }
@@ -465,7 +482,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
             .on_fetch_pending_hashes(&self.peers, has_capacity_wrt_pending_pool_imports);
     }
 
-    fn on_request_error(&self, peer_id: PeerId, req_err: RequestError) {
+    fn on_request_error(&mut self, peer_id: PeerId, req_err: RequestError) {
         let kind = match req_err {
             RequestError::UnsupportedCapability => ReputationChangeKind::BadProtocol,
             RequestError::Timeout => ReputationChangeKind::Timeout,
@@ -768,6 +785,43 @@ where
         self.propagate_all(hashes);
     }
 
+    /// Invoked when new transactions in the local mempool are considered __pending__, including
+    /// those that are not allowed to be propagated.
+    ///
+    /// Only the [`TransactionOrigin::Private`] transactions are propagated, to the private relay
+    /// peers of the policy. All other transactions are handled by
+    /// [`Self::on_new_pending_transactions`].
+    fn on_new_private_transactions(&mut self, hashes: Vec<TxHash>) {
+        if self.network.is_initially_syncing() {
+            return
+        }
+
+        let to_propagate = self
+            .pool
+            .get_all(hashes)
+            .into_iter()
+            .filter(|tx| tx.origin.is_private())
+            .map(PropagateTransaction::pool_tx)
+            .collect::<Vec<_>>();
+        if to_propagate.is_empty() {
+            return
+        }
+
+        trace!(target: "net::tx", num_txs=?to_propagate.len(), "Relaying private transactions");
+
+        let propagated = self.propagate_transactions(to_propagate, PropagationMode::Basic);
+
+        // notify pool so events get fired
+        self.pool.on_propagated(propagated);
+    }
+
+    /// Returns `true` if the peer is one of the private relay peers of the policy.
+    fn is_private_relay_peer(&self, peer_id: &PeerId) -> bool {
+        self.propagation_policy
+            .private_relay_peers()
+            .is_some_and(|relay_peers| relay_peers.contains(peer_id))
+    }
+
     /// Propagate the full transactions to a specific peer.
     ///
     /// Returns the propagated transactions.
@@ -779,13 +833,20 @@ where
     ) -> Option<PropagatedTransactions> {
         trace!(target: "net::tx", ?peer_id, "Propagating transactions to peer");
 
+        let is_private_relay = self.is_private_relay_peer(&peer_id);
         let peer = self.peers.get_mut(&peer_id)?;
         let mut propagated = PropagatedTransactions::default();
 
         // filter all transactions unknown to the peer
         let mut full_transactions = FullTransactionsBuilder::new(peer.version);
 
-        let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
+        let to_propagate = self
+            .pool
+            .get_all(txs)
+            .into_iter()
+            // private transactions are only ever sent to private relay peers
+            .filter(|tx| is_private_relay || !tx.origin.is_private())
+            .map(PropagateTransaction::pool_tx);
 
         if propagation_mode.is_forced() {
             // skip cache check if forced
@@ -852,6 +913,7 @@ where
         // This fetches a transactions from the pool, including the blob transactions, which are
         // only ever sent as hashes.
         let propagated = {
+            let is_private_relay = self.is_private_relay_peer(&peer_id);
             let Some(peer) = self.peers.get_mut(&peer_id) else {
                 // no such peer
                 return
@@ -861,6 +923,8 @@ where
                 .pool
                 .get_all(hashes)
                 .into_iter()
+                // private transactions are only ever sent to private relay peers
+                .filter(|tx| is_private_relay || !tx.origin.is_private())
                 .map(PropagateTransaction::pool_tx)
                 .collect::<Vec<_>>();
 
@@ -924,32 +988,55 @@ where
 
         // send full transactions to a set of the connected peers based on the configured mode
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
+        let relay_peers = self.propagation_policy.private_relay_peers();
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
-            if !self.propagation_policy.can_propagate(peer) {
+            let is_private_relay = relay_peers.is_some_and(|relay| relay.contains(peer_id));
+            let can_propagate = self.propagation_policy.can_propagate(peer);
+            if !can_propagate && !is_private_relay {
                 // skip peers we should not propagate to
                 continue
             }
-            // determine whether to send full tx objects or hashes.
-            let mut builder = if peer_idx > max_num_full {
-                PropagateTransactionsBuilder::pooled(peer.version)
+            // determine whether to send full tx objects or hashes by default.
+            let (mut builder, default_action) = if peer_idx > max_num_full {
+                (
+                    PropagateTransactionsBuilder::pooled(peer.version),
+                    TransactionPropagationAction::Hash,
+                )
             } else {
-                PropagateTransactionsBuilder::full(peer.version)
+                (
+                    PropagateTransactionsBuilder::full(peer.version),
+                    TransactionPropagationAction::Full,
+                )
             };
 
-            if propagation_mode.is_forced() {
-                builder.extend(to_propagate.iter());
-            } else {
-                // Iterate through the transactions to propagate and fill the hashes and full
-                // transaction lists, before deciding whether or not to send full transactions to
-                // the peer.
-                for tx in &to_propagate {
-                    // Only proceed if the transaction is not in the peer's list of seen
-                    // transactions
-                    if !peer.seen_transactions.contains(tx.tx_hash()) {
-                        builder.push(tx);
+            // Iterate through the transactions to propagate and fill the hashes and full
+            // transaction lists, before deciding whether or not to send full transactions to the
+            // peer.
+            for tx in &to_propagate {
+                // Only proceed if the transaction is not in the peer's list of seen transactions,
+                // unless forced
+                if !propagation_mode.is_forced() && peer.seen_transactions.contains(tx.tx_hash()) {
+                    continue
+                }
+
+                let action = if tx.origin.is_private() {
+                    // private transactions are only ever sent to private relay peers, in full
+                    if !is_private_relay {
+                        continue
                     }
+                    TransactionPropagationAction::Full
+                } else if can_propagate {
+                    self.propagation_policy.propagation_action(peer, &tx.info(), default_action)
+                } else {
+                    continue
+                };
+
+                match action {
+                    TransactionPropagationAction::Full => builder.push_full(tx),
+                    TransactionPropagationAction::Hash => builder.push_hash(tx),
+                    TransactionPropagationAction::Skip => {}
                 }
             }
 
@@ -1025,13 +1112,19 @@ where
         request: GetPooledTransactions,
         response: oneshot::Sender<RequestResult<PooledTransactions<N::PooledTransaction>>>,
     ) {
+        let is_private_relay = self.is_private_relay_peer(&peer_id);
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             if self.network.tx_gossip_disabled() {
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
+            let mut hashes = request.0;
+            if !is_private_relay {
+                // private transactions are only handed out to the private relay peers
+                hashes.retain(|hash| !self.pool.get(hash).is_some_and(|tx| tx.origin.is_private()));
+            }
             let transactions = self.pool.get_pooled_transaction_elements(
-                request.0,
+                hashes,
                 GetPooledTransactionLimit::ResponseSizeSoftLimit(
                     self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
                 ),
@@ -1132,19 +1225,35 @@ where
         );
         if pooled_txs.is_empty() {
             trace!(target: "net::tx", ?peer_id, "No transactions in the pool to broadcast");
-            return;
-        }
+        } else {
+            // Build and send transaction hashes message
+            let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
+            for pooled_tx in pooled_txs {
+                peer.seen_transactions.insert(*pooled_tx.hash());
+                msg_builder.push_pooled(pooled_tx);
+            }
 
-        // Build and send transaction hashes message
-        let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
-        for pooled_tx in pooled_txs {
-            peer.seen_transactions.insert(*pooled_tx.hash());
-            msg_builder.push_pooled(pooled_tx);
+            debug!(target: "net::tx", ?peer_id, tx_count = msg_builder.is_empty(), "Broadcasting transaction hashes");
+            let msg = msg_builder.build();
+            self.network.send_transactions_hashes(peer_id, msg);
         }
 
-        debug!(target: "net::tx", ?peer_id, tx_count = msg_builder.is_empty(), "Broadcasting transaction hashes");
-        let msg = msg_builder.build();
-        self.network.send_transactions_hashes(peer_id, msg);
+        // private relay peers also receive all pending private transactions
+        if self.is_private_relay_peer(&peer_id) {
+            let private_txs = self
+                .pool
+                .get_private_pending_transactions()
+                .into_iter()
+                .map(|tx| *tx.hash())
+                .collect();
+            if let Some(propagated) = self.propagate_full_transactions_to_peer(
+                private_txs,
+                peer_id,
+                PropagationMode::Basic,
+            ) {
+                self.pool.on_propagated(propagated);
+            }
+        }
     }
 
     /// Handles a received event related to common network events.
@@ -1360,14 +1469,20 @@ where
     /// Processes a [`FetchEvent`].
     fn on_fetch_event(&mut self, fetch_event: FetchEvent<N::PooledTransaction>) {
         match fetch_event {
-            FetchEvent::TransactionsFetched { peer_id, transactions } => {
+            FetchEvent::TransactionsFetched { peer_id, transactions, elapsed } => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.on_response(elapsed);
+                }
                 self.import_transactions(peer_id, transactions, TransactionSource::Response);
             }
             FetchEvent::FetchError { peer_id, error } => {
                 trace!(target: "net::tx", ?peer_id, %error, "requesting transactions from peer failed");
                 self.on_request_error(peer_id, error);
             }
-            FetchEvent::EmptyResponse { peer_id } => {
+            FetchEvent::EmptyResponse { peer_id, elapsed } => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.on_response(elapsed);
+                }
                 trace!(target: "net::tx", ?peer_id, "peer returned empty response");
             }
         }
@@ -1435,6 +1550,24 @@ where
             this.on_new_pending_transactions(new_txs);
         }
 
+        // Advances all new __pending__ transactions, of which only private transactions are
+        // relayed, if the policy has private relay peers.
+        let mut maybe_more_private_txns = false;
+        if let Some(private_transactions) = this.private_transactions.as_mut() {
+            let mut new_txs = Vec::new();
+            maybe_more_private_txns = metered_poll_nested_stream_with_budget!(
+                poll_durations.acc_imported_txns,
+                "net::tx",
+                "Private transactions stream",
+                DEFAULT_BUDGET_TRY_DRAIN_POOL_IMPORTS,
+                private_transactions.poll_next_unpin(cx),
+                |hash| new_txs.push(hash)
+            );
+            if !new_txs.is_empty() {
+                this.on_new_private_transactions(new_txs);
+            }
+        }
+
         // Advance inflight fetch requests (flush transaction fetcher and queue for
         // import to pool).
         //
@@ -1531,7 +1664,8 @@ where
             maybe_more_tx_events ||
             maybe_more_tx_fetch_events ||
             maybe_more_pool_imports ||
-            maybe_more_pending_txns
+            maybe_more_pending_txns ||
+            maybe_more_private_txns
         {
             // make sure we're woken up again
             cx.waker().wake_by_ref();
@@ -1572,13 +1706,16 @@ impl PropagationMode {
 struct PropagateTransaction<T = TransactionSigned> {
     size: usize,
     transaction: Arc<T>,
+    origin: TransactionOrigin,
 }
 
 impl<T: SignedTransaction> PropagateTransaction<T> {
     /// Create a new instance from a transaction.
+    ///
+    /// The transaction is treated as [`TransactionOrigin::Local`].
     pub fn new(transaction: T) -> Self {
         let size = transaction.length();
-        Self { size, transaction: Arc::new(transaction) }
+        Self { size, transaction: Arc::new(transaction), origin: TransactionOrigin::Local }
     }
 
     /// Create a new instance from a pooled transaction
@@ -1589,12 +1726,23 @@ impl<T: SignedTransaction> PropagateTransaction<T> {
         let size = tx.encoded_length();
         let transaction = tx.transaction.clone_into_consensus();
         let transaction = Arc::new(transaction.into_inner());
-        Self { size, transaction }
+        Self { size, transaction, origin: tx.origin }
     }
 
     fn tx_hash(&self) -> &TxHash {
         self.transaction.tx_hash()
     }
+
+    /// Returns the information about the transaction that is passed to the
+    /// [`TransactionPropagationPolicy`].
+    fn info(&self) -> TransactionPropagationInfo {
+        TransactionPropagationInfo {
+            hash: *self.tx_hash(),
+            origin: self.origin,
+            size: self.size,
+            ty: self.transaction.ty(),
+        }
+    }
 }
 
 /// Helper type to construct the appropriate message to send to the peer based on whether the peer
@@ -1636,13 +1784,6 @@ impl<T> PropagateTransactionsBuilder<T> {
 }
 
 impl<T: SignedTransaction> PropagateTransactionsBuilder<T> {
-    /// Appends all transactions
-    fn extend<'a>(&mut self, txs: impl IntoIterator<Item = &'a PropagateTransaction<T>>) {
-        for tx in txs {
-            self.push(tx);
-        }
-    }
-
     /// Appends a transaction to the list.
     fn push(&mut self, transaction: &PropagateTransaction<T>) {
         match self {
@@ -1650,6 +1791,29 @@ impl<T: SignedTransaction> PropagateTransactionsBuilder<T> {
             Self::Full(builder) => builder.push(transaction),
         }
     }
+
+    /// Appends a transaction that should be sent in full, if possible.
+    ///
+    /// A pooled builder is turned into a full builder, keeping the recorded hashes.
+    fn push_full(&mut self, transaction: &PropagateTransaction<T>) {
+        if let Self::Pooled(pooled) = self {
+            let pooled = std::mem::replace(
+                pooled,
+                PooledTransactionsHashesBuilder::Eth66(Default::default()),
+            );
+            *self =
+                Self::Full(FullTransactionsBuilder { total_size: 0, transactions: vec![], pooled });
+        }
+        self.push(transaction)
+    }
+
+    /// Appends a transaction that should only be announced by hash.
+    fn push_hash(&mut self, transaction: &PropagateTransaction<T>) {
+        match self {
+            Self::Pooled(builder) => builder.push(transaction),
+            Self::Full(builder) => builder.pooled.push(transaction),
+        }
+    }
 }
 
 /// Represents how the transactions should be sent to a peer if any.
@@ -1842,6 +2006,10 @@ pub struct PeerMetadata<N: NetworkPrimitives = EthNetworkPrimitives> {
     client_version: Arc<str>,
     /// The kind of peer.
     peer_kind: PeerKind,
+    /// Number of invalid transactions the peer sent us during the session.
+    invalid_transactions: u64,
+    /// Moving average of the time it took the peer to respond to our transaction requests.
+    latency: Option<Duration>,
 }
 
 impl<N: NetworkPrimitives> PeerMetadata<N> {
@@ -1859,6 +2027,8 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
             version,
             client_version,
             peer_kind,
+            invalid_transactions: 0,
+            latency: None,
         }
     }
 
@@ -1886,6 +2056,25 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
     pub const fn peer_kind(&self) -> PeerKind {
         self.peer_kind
     }
+
+    /// Returns the number of invalid transactions the peer sent us during the session.
+    pub const fn invalid_transactions(&self) -> u64 {
+        self.invalid_transactions
+    }
+
+    /// Returns the moving average of the time it took the peer to respond to our
+    /// [`GetPooledTransactions`] requests, if it responded to any yet.
+    pub const fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Records the time it took the peer to respond to a request.
+    fn on_response(&mut self, elapsed: Duration) {
+        self.latency = Some(match self.latency {
+            Some(latency) => (latency * 3 + elapsed) / 4,
+            None => elapsed,
+        });
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`]
@@ -1995,12 +2184,12 @@ mod tests {
         },
         NetworkConfigBuilder, NetworkManager,
     };
-    use alloy_consensus::{transaction::PooledTransaction, TxEip1559, TxLegacy};
+    use alloy_consensus::{TxEip1559, TxLegacy};
     use alloy_primitives::{hex, Signature, TxKind, U256};
     use alloy_rlp::Decodable;
     use futures::FutureExt;
     use reth_chainspec::MIN_TRANSACTION_GAS;
-    use reth_ethereum_primitives::{Transaction, TransactionSigned};
+    use reth_ethereum_primitives::{PooledTransaction, Transaction, TransactionSigned};
     use reth_network_api::{NetworkInfo, PeerKind};
     use reth_network_p2p::{
         error::{RequestError, RequestResult},
@@ -2008,7 +2197,7 @@ mod tests {
     };
     use reth_storage_api::noop::NoopProvider;
     use reth_transaction_pool::test_utils::{
-        testing_pool, MockTransaction, MockTransactionFactory,
+        testing_pool, MockTransaction, MockTransactionFactory, TestPool,
     };
     use secp256k1::SecretKey;
     use std::{
@@ -2753,4 +2942,154 @@ mod tests {
         let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
         assert!(propagated.0.is_empty());
     }
+
+    /// Creates a new [`TransactionsManager`] with the given policy.
+    async fn new_tx_manager_with_policy<P: TransactionPropagationPolicy>(
+        policy: P,
+    ) -> (TransactionsManager<TestPool, EthNetworkPrimitives, P>, NetworkManager) {
+        let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+        let config = NetworkConfigBuilder::new(secret_key)
+            .listener_port(0)
+            .disable_discovery()
+            .build(NoopProvider::default());
+        let transactions_manager_config = config.transactions_manager_config.clone();
+        let (_handle, network, transactions, _) = NetworkManager::new(config)
+            .await
+            .unwrap()
+            .into_builder()
+            .transactions_with_policy(testing_pool(), transactions_manager_config, policy)
+            .split_with_handle();
+        network.handle().update_sync_state(SyncState::Idle);
+
+        (transactions, network)
+    }
+
+    /// Establishes a mock session with the peer.
+    fn add_mock_peer<P: TransactionPropagationPolicy>(
+        tx_manager: &mut TransactionsManager<TestPool, EthNetworkPrimitives, P>,
+        peer_id: PeerId,
+    ) -> mpsc::Receiver<PeerRequest> {
+        let (tx, rx) = mpsc::channel::<PeerRequest>(1);
+        let info = SessionInfo {
+            peer_id,
+            remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            client_version: Arc::from(""),
+            capabilities: Arc::new(vec![].into()),
+            status: Arc::new(Default::default()),
+            version: EthVersion::Eth68,
+            peer_kind: PeerKind::Basic,
+        };
+        let messages = PeerRequestSender::new(peer_id, tx);
+        tx_manager.on_network_event(NetworkEvent::ActivePeerSession { info, messages });
+        rx
+    }
+
+    #[tokio::test]
+    async fn test_propagate_private_to_relay_peers() {
+        reth_tracing::init_test_tracing();
+
+        let relay_peer = PeerId::random();
+        let policy = PrivateRelayPolicy::new(TransactionPropagationKind::All, [relay_peer]);
+        let (mut tx_manager, _network) = new_tx_manager_with_policy(policy).await;
+        assert!(tx_manager.private_transactions.is_some());
+
+        let other_peer = PeerId::random();
+        let _other_rx = add_mock_peer(&mut tx_manager, other_peer);
+        let _relay_rx = add_mock_peer(&mut tx_manager, relay_peer);
+
+        let mut factory = MockTransactionFactory::default();
+        let private_tx = Arc::new(
+            factory.validated_with_origin(TransactionOrigin::Private, MockTransaction::eip1559()),
+        );
+        let public_tx = Arc::new(factory.create_eip1559());
+        let propagate = vec![
+            PropagateTransaction::pool_tx(private_tx.clone()),
+            PropagateTransaction::pool_tx(public_tx.clone()),
+        ];
+
+        let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Forced);
+
+        // the private transaction is only sent to the relay peer, in full
+        let prop_txs = propagated.0.get(private_tx.hash()).unwrap();
+        assert_eq!(prop_txs.len(), 1);
+        assert_eq!(prop_txs[0], PropagateKind::Full(relay_peer));
+
+        // the public transaction is sent to both peers
+        let prop_txs = propagated.0.get(public_tx.hash()).unwrap();
+        assert_eq!(prop_txs.len(), 2);
+
+        assert!(!tx_manager.peers[&other_peer].seen_transactions.contains(private_tx.hash()));
+    }
+
+    #[tokio::test]
+    async fn test_get_pooled_private_transactions() {
+        reth_tracing::init_test_tracing();
+
+        let relay_peer = PeerId::random();
+        let policy = PrivateRelayPolicy::new(TransactionPropagationKind::All, [relay_peer]);
+        let (mut tx_manager, _network) = new_tx_manager_with_policy(policy).await;
+
+        let other_peer = PeerId::random();
+        let _other_rx = add_mock_peer(&mut tx_manager, other_peer);
+        let _relay_rx = add_mock_peer(&mut tx_manager, relay_peer);
+
+        let private_tx = MockTransaction::eip1559();
+        let public_tx = MockTransaction::eip1559();
+        tx_manager
+            .pool
+            .add_transaction(TransactionOrigin::Private, private_tx.clone())
+            .await
+            .unwrap();
+        tx_manager
+            .pool
+            .add_transaction(TransactionOrigin::External, public_tx.clone())
+            .await
+            .unwrap();
+        let request = || GetPooledTransactions(vec![*private_tx.get_hash(), *public_tx.get_hash()]);
+
+        // the private transaction is only served to the relay peer
+        let (send, receive) = oneshot::channel::<RequestResult<PooledTransactions>>();
+        tx_manager.on_get_pooled_transactions(other_peer, request(), send);
+        let PooledTransactions(transactions) = receive.await.unwrap().unwrap();
+        assert_eq!(
+            transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>(),
+            vec![*public_tx.get_hash()]
+        );
+        assert!(!tx_manager.peers[&other_peer].seen_transactions.contains(private_tx.get_hash()));
+
+        let (send, receive) = oneshot::channel::<RequestResult<PooledTransactions>>();
+        tx_manager.on_get_pooled_transactions(relay_peer, request(), send);
+        let PooledTransactions(transactions) = receive.await.unwrap().unwrap();
+        assert_eq!(transactions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_propagate_with_peer_scoring() {
+        reth_tracing::init_test_tracing();
+
+        let policy = PeerScoringPolicy::new(TransactionPropagationKind::All, 2)
+            .with_max_full_latency(Duration::from_millis(500));
+        let (mut tx_manager, _network) = new_tx_manager_with_policy(policy).await;
+        assert!(tx_manager.private_transactions.is_none());
+
+        let (bad_peer, slow_peer) = (PeerId::random(), PeerId::random());
+        let _bad_rx = add_mock_peer(&mut tx_manager, bad_peer);
+        let _slow_rx = add_mock_peer(&mut tx_manager, slow_peer);
+        for _ in 0..3 {
+            tx_manager.report_peer_bad_transactions(bad_peer);
+        }
+        tx_manager.peers.get_mut(&slow_peer).unwrap().on_response(Duration::from_secs(1));
+
+        let mut factory = MockTransactionFactory::default();
+        let tx = Arc::new(factory.create_eip1559());
+        let propagated = tx_manager.propagate_transactions(
+            vec![PropagateTransaction::pool_tx(tx.clone())],
+            PropagationMode::Basic,
+        );
+
+        // the peer that sent us invalid transactions is skipped, the slow peer only receives
+        // the hash
+        let prop_txs = propagated.0.get(tx.hash()).unwrap();
+        assert_eq!(prop_txs, &vec![PropagateKind::Hash(slow_peer)]);
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
     priority::Priority,
 };
 use alloy_primitives::B256;
+use std::ops::RangeInclusive;
 
 pub use futures::future::Either;
 
@@ -47,6 +48,22 @@ where
             Self::Right(b) => Either::Right(b.get_block_bodies_with_priority(hashes, priority)),
         }
     }
+
+    fn get_block_bodies_with_priority_and_range_hint(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output {
+        match self {
+            Self::Left(a) => Either::Left(
+                a.get_block_bodies_with_priority_and_range_hint(hashes, priority, range_hint),
+            ),
+            Self::Right(b) => Either::Right(
+                b.get_block_bodies_with_priority_and_range_hint(hashes, priority, range_hint),
+            ),
+        }
+    }
 }
 
 impl<A, B> HeadersClient for Either<A, B>
@@ -1,6 +1,7 @@
 //! Traits used when interacting with the sync status of the network.
 
 use alloy_eips::eip2124::Head;
+use reth_eth_wire_types::BlockRangeUpdate;
 
 /// A type that provides information about whether the node is currently syncing and the network is
 /// currently serving syncing related requests.
@@ -27,6 +28,9 @@ pub trait NetworkSyncUpdater: std::fmt::Debug + Send + Sync + 'static {
 
     /// Updates the status of the p2p node
     fn update_status(&self, head: Head);
+
+    /// Updates the range of blocks the node serves, which is announced to `eth/69` peers.
+    fn update_block_range(&self, block_range: BlockRangeUpdate);
 }
 
 /// The state the network is currently in when it comes to synchronization.
@@ -66,4 +70,5 @@ impl SyncStateProvider for NoopSyncStateUpdater {
 impl NetworkSyncUpdater for NoopSyncStateUpdater {
     fn update_sync_state(&self, _state: SyncState) {}
     fn update_status(&self, _: Head) {}
+    fn update_block_range(&self, _: BlockRangeUpdate) {}
 }
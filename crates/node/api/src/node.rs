@@ -2,10 +2,13 @@
 
 use crate::PayloadTypes;
 use alloy_rpc_types_engine::JwtSecret;
+use reth_bad_blocks::BadBlockRegistry;
 use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
+use reth_engine_primitives::{
+    BeaconConsensusEngineEvent, BeaconConsensusEngineHandle, LocalMinerHandle,
+};
 use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
@@ -121,6 +124,10 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Registry of the blocks that failed validation.
+    pub bad_block_registry: BadBlockRegistry,
+    /// Handle to the local miner, only available on dev nodes.
+    pub local_miner_handle: Option<LocalMinerHandle<<N::Types as NodeTypes>::Primitives>>,
 }
 
 /// Customizable node add-on types.
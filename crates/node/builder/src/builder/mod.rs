@@ -17,9 +17,10 @@ use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_exex::ExExContext;
 use reth_network::{
     transactions::{TransactionPropagationPolicy, TransactionsManagerConfig},
-    NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
-    NetworkPrimitives,
+    write_peers_to_file, NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle,
+    NetworkManager, NetworkPrimitives,
 };
+use reth_network_api::test_utils::PeersHandleProvider;
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes,
     NodeTypesWithDBAdapter,
@@ -711,16 +712,57 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         Node::Provider: BlockReaderFor<N>,
         Policy: TransactionPropagationPolicy,
     {
-        let (handle, network, txpool, eth) = builder
+        let mut builder = builder
             .transactions_with_policy(pool, tx_config, propagation_policy)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+            .request_handler(self.provider().clone());
+
+        if !self.config().network.disable_snap {
+            let (snap, _) = builder.snap_request_handler(self.provider().clone());
+            self.executor.spawn_critical_blocking("p2p snap request handler", snap);
+        }
+
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+
+        if let Some(peers_file) = known_peers_file.clone() {
+            // checkpoint the peer set regularly, so that a restart, or a crash, doesn't lose the
+            // peers and their reputation
+            let peers_handle = handle.peers_handle().clone();
+            let persist_interval = self.config().network.peers_file_interval;
+            self.executor.spawn_with_graceful_shutdown_signal(|mut shutdown| async move {
+                let mut interval = tokio::time::interval_at(
+                    tokio::time::Instant::now() + persist_interval,
+                    persist_interval,
+                );
+                loop {
+                    // the peers are written by the network task on shutdown
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = interval.tick() => {}
+                    }
+                    let peers = peers_handle.persisted_peers().await;
+                    // the peer set is empty if the network has already shut down, in which case
+                    // the peers are written on shutdown
+                    if peers.is_empty() {
+                        continue
+                    }
+                    match write_peers_to_file(&peers, peers_file.as_path()) {
+                        Ok(_) => {
+                            trace!(target: "reth::cli", peers_file=?peers_file, num_peers=%peers.len(), "Checkpointed network peers");
+                        }
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Failed to checkpoint network peers");
+                        }
+                    }
+                }
+            });
+        }
+
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
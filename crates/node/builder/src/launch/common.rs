@@ -9,8 +9,12 @@ use alloy_eips::eip2124::Head;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
+use reth_bad_blocks::{BadBlockRegistry, DEFAULT_MAX_BAD_BLOCKS};
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_config::{
+    config::{EraConfig, EtlConfig},
+    PruneConfig,
+};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitStorageError};
@@ -19,7 +23,9 @@ use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{
+    InvalidBlockOpcodeHook, InvalidBlockPreStateHook, InvalidBlockWitnessHook,
+};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
@@ -43,12 +49,16 @@ use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
     BlockHashReader, BlockNumReader, ChainSpecProvider, ProviderError, ProviderFactory,
     ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    StaticFileSegment,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
-use reth_stages::{sets::DefaultStages, MetricEvent, PipelineBuilder, PipelineTarget, StageId};
+use reth_stages::{
+    sets::DefaultStages, stages::IndexAddressAppearancesStage, MetricEvent, PipelineBuilder,
+    PipelineTarget, StageId,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
@@ -265,9 +275,10 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     ///
     /// This includes:
     /// - Making sure the ETL dir is set to the datadir
+    /// - Making sure the ERA download dir is set to the datadir
     /// - RPC settings are adjusted to the correct port
     pub fn with_adjusted_configs(self) -> Self {
-        self.ensure_etl_datadir().with_adjusted_instance_ports()
+        self.ensure_etl_datadir().ensure_era_datadir().with_adjusted_instance_ports()
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
@@ -280,6 +291,16 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         self
     }
 
+    /// Make sure ERA files aren't downloaded to /tmp/, but to whatever datadir is set to
+    pub fn ensure_era_datadir(mut self) -> Self {
+        if self.toml_config_mut().stages.era.download_dir.is_none() {
+            self.toml_config_mut().stages.era.download_dir =
+                Some(EraConfig::from_datadir(self.data_dir().data_dir()))
+        }
+
+        self
+    }
+
     /// Change rpc port numbers based on the instance number.
     pub fn with_adjusted_instance_ports(mut self) -> Self {
         self.node_config_mut().adjust_instance_ports();
@@ -391,8 +412,35 @@ where
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_prune_modes(self.prune_modes())
+        .with_address_appearances_index(self.toml_config().stages.index_address_appearances.enabled)
         .with_static_files_metrics();
 
+        // Changesets that were moved to static files can't be pruned, so history pruning can't be
+        // enabled on a node that already has them in static files.
+        let prune_modes = self.prune_modes();
+        for (segment, prune_mode) in [
+            (StaticFileSegment::AccountChangeSets, prune_modes.account_history),
+            (StaticFileSegment::StorageChangeSets, prune_modes.storage_history),
+        ] {
+            if let Some(highest_block) = prune_mode
+                .and_then(|_| factory.static_file_provider().get_highest_static_file_block(segment))
+            {
+                eyre::bail!("history pruning can't be enabled, {segment} up to block {highest_block} were moved to static files and can't be pruned")
+            }
+        }
+
+        // The call participants are traced by re-executing the blocks on top of the historical
+        // state, which isn't available once the history is pruned.
+        if self.toml_config().stages.index_address_appearances.enabled &&
+            (prune_modes.account_history.is_some() ||
+                prune_modes.storage_history.is_some() ||
+                prune_modes.has_history_address_filter())
+        {
+            eyre::bail!(
+                "the address appearances index can't be enabled together with history pruning"
+            )
+        }
+
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
 
@@ -814,31 +862,47 @@ where
     ///  * the pipeline was interrupted during its previous run
     ///  * a new stage was added
     ///  * stage data was dropped manually through `reth stage drop ...`
+    ///  * the address appearances index was enabled after blocks were synced without it
     ///
     /// # Returns
     ///
     /// A target block hash if the pipeline is inconsistent, otherwise `None`.
     pub fn check_pipeline_consistency(&self) -> ProviderResult<Option<B256>> {
         // If no target was provided, check if the stages are congruent - check if the
-        // checkpoint of the last stage matches the checkpoint of the first.
+        // checkpoint of the last stage matches the checkpoint of the headers stage. The era stage
+        // runs before it, but only imports blocks if a source is configured, so the headers stage
+        // is the reference for the blocks written to the database.
         let first_stage_checkpoint = self
             .blockchain_db()
-            .get_stage_checkpoint(*StageId::ALL.first().unwrap())?
+            .get_stage_checkpoint(StageId::Headers)?
             .unwrap_or_default()
             .block_number;
 
-        // Skip the first stage as we've already retrieved it and comparing all other checkpoints
-        // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
-            let stage_checkpoint = self
-                .blockchain_db()
-                .get_stage_checkpoint(*stage_id)?
-                .unwrap_or_default()
-                .block_number;
+        // The snap sync stage is only part of the pipeline if the state is snap synced.
+        let snap_sync = self.node_config().network.snap_sync;
+
+        // Skip the headers stage as we've already retrieved it and comparing all other
+        // checkpoints against it.
+        for stage_id in &StageId::ALL {
+            if matches!(*stage_id, StageId::Era | StageId::Headers) ||
+                (*stage_id == StageId::SnapSync && !snap_sync)
+            {
+                continue
+            }
 
-            // If the checkpoint of any stage is less than the checkpoint of the first stage,
+            let checkpoint =
+                self.blockchain_db().get_stage_checkpoint(*stage_id)?.unwrap_or_default();
+            let stage_checkpoint = checkpoint.block_number;
+
+            // The address appearances index needs to be rebuilt by the pipeline if it was enabled
+            // after the stage checkpoint was advanced without it.
+            let incomplete_index = *stage_id == StageId::IndexAddressAppearances &&
+                self.toml_config().stages.index_address_appearances.enabled &&
+                !IndexAddressAppearancesStage::is_index_complete(&checkpoint);
+
+            // If the checkpoint of any stage is less than the checkpoint of the headers stage,
             // retrieve and return the block hash of the latest header and use it as the target.
-            if stage_checkpoint < first_stage_checkpoint {
+            if stage_checkpoint < first_stage_checkpoint || incomplete_index {
                 debug!(
                     target: "consensus::engine",
                     first_stage_checkpoint,
@@ -877,9 +941,18 @@ where
     >,
     CB: NodeComponentsBuilder<T>,
 {
+    /// Opens the [`BadBlockRegistry`] of the node in the data directory.
+    pub fn bad_block_registry(&self) -> eyre::Result<BadBlockRegistry> {
+        Ok(BadBlockRegistry::open(self.data_dir().bad_blocks(), DEFAULT_MAX_BAD_BLOCKS)?)
+    }
+
     /// Returns the [`InvalidBlockHook`] to use for the node.
+    ///
+    /// The artifacts generated by the hooks are attached to the blocks recorded in the given
+    /// [`BadBlockRegistry`].
     pub fn invalid_block_hook(
         &self,
+        bad_block_registry: &BadBlockRegistry,
     ) -> eyre::Result<Box<dyn InvalidBlockHook<<T::Types as NodeTypes>::Primitives>>> {
         let Some(ref hook) = self.node_config().debug.invalid_block_hook else {
             return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -894,19 +967,35 @@ where
                 let output_directory = output_directory.join(hook.to_string());
                 fs::create_dir_all(&output_directory)?;
 
-                Ok(match hook {
-                    InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
+                let hook: Box<dyn InvalidBlockHook<_>> = match hook {
+                    InvalidBlockHookType::Witness => Box::new(
+                        InvalidBlockWitnessHook::new(
+                            self.blockchain_db().clone(),
+                            self.components().block_executor().clone(),
+                            output_directory,
+                            healthy_node_rpc_client.clone(),
+                        )
+                        .with_bad_block_registry(bad_block_registry.clone()),
+                    ),
+                    InvalidBlockHookType::PreState => Box::new(
+                        InvalidBlockPreStateHook::new(
+                            self.blockchain_db().clone(),
+                            self.components().evm_config().clone(),
+                            output_directory,
+                            healthy_node_rpc_client.clone(),
+                        )
+                        .with_bad_block_registry(bad_block_registry.clone()),
+                    ),
+                    InvalidBlockHookType::Opcode => Box::new(InvalidBlockOpcodeHook::new(
                         self.blockchain_db().clone(),
-                        self.components().block_executor().clone(),
+                        self.components().evm_config().clone(),
                         output_directory,
                         healthy_node_rpc_client.clone(),
                     )),
-                    InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                        eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                    }
-                } as Box<dyn InvalidBlockHook<_>>)
+                };
+                Ok(hook)
             })
-            .collect::<Result<_, _>>()?;
+            .collect::<eyre::Result<_>>()?;
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
@@ -1064,18 +1153,34 @@ mod tests {
                     sender_recovery_full: false,
                     sender_recovery_distance: None,
                     sender_recovery_before: None,
+                    sender_recovery_age: None,
+                    sender_recovery_size: None,
                     transaction_lookup_full: false,
                     transaction_lookup_distance: None,
                     transaction_lookup_before: None,
+                    transaction_lookup_age: None,
+                    transaction_lookup_size: None,
                     receipts_full: false,
                     receipts_distance: None,
                     receipts_before: None,
+                    receipts_age: None,
+                    receipts_size: None,
                     account_history_full: false,
                     account_history_distance: None,
                     account_history_before: None,
+                    account_history_age: None,
+                    account_history_size: None,
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    storage_history_age: None,
+                    storage_history_size: None,
+                    address_appearances_full: false,
+                    address_appearances_distance: None,
+                    address_appearances_before: None,
+                    address_appearances_age: None,
+                    address_appearances_size: None,
+                    history_address_filter: vec![],
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
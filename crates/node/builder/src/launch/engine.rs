@@ -12,11 +12,11 @@ use reth_engine_tree::{
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
-use reth_network::{NetworkSyncUpdater, SyncState};
-use reth_network_api::BlockDownloaderProvider;
+use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
+use reth_network_api::{BlockDownloaderProvider, SnapDownloaderProvider};
 use reth_node_api::{
-    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
-    PayloadAttributesBuilder, PayloadTypes,
+    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, LocalMinerHandle, NodeTypes,
+    NodeTypesWithDBAdapter, PayloadAttributesBuilder, PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -24,7 +24,12 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
-use reth_provider::providers::{BlockchainProvider, NodeTypesForProvider};
+use reth_provider::{
+    providers::{BlockchainProvider, NodeTypesForProvider},
+    ProviderResult, PruneCheckpointReader,
+};
+use reth_prune::{PruneSegment, PrunerEvent};
+use reth_stages::stages::EvmCallParticipantsTracer;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
@@ -126,6 +131,16 @@ where
             })?
             .with_components(components_builder, on_component_initialized).await?;
 
+        // the address appearances index traces the call participants by re-executing the blocks
+        if ctx.toml_config().stages.index_address_appearances.enabled {
+            ctx.provider_factory().set_call_participants_tracer(Arc::new(
+                EvmCallParticipantsTracer::new(
+                    ctx.components().evm_config().clone(),
+                    ctx.chain_spec(),
+                ),
+            ));
+        }
+
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
             ctx.head(),
@@ -150,12 +165,24 @@ where
 
         let consensus = Arc::new(ctx.components().consensus().clone());
 
+        let snap_client = if node_config.network.snap_sync {
+            // the receipts of the blocks before the snap synced block are never created
+            if !ctx.prune_modes().has_receipts_pruning() {
+                eyre::bail!("snap sync requires receipts pruning")
+            }
+            let snap_client = ctx.components().network().snap_client().await?;
+            Some(snap_client.ok_or_else(|| eyre::eyre!("snap sync requires the snap protocol"))?)
+        } else {
+            None
+        };
+
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
+            snap_client,
             consensus.clone(),
             ctx.provider_factory().clone(),
             ctx.task_executor(),
@@ -179,6 +206,7 @@ where
         }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
+        let mut network_pruner_events = pruner.events().fuse();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
         let event_sender = EventSender::default();
@@ -188,12 +216,19 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        let bad_block_registry = ctx.bad_block_registry()?;
+
+        let (to_local_miner, from_miner_handle) = unbounded_channel();
+        let local_miner_handle = ctx.is_dev().then(|| LocalMinerHandle::new(to_local_miner));
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            bad_block_registry: bad_block_registry.clone(),
+            local_miner_handle,
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -222,10 +257,12 @@ where
                 ctx.components().payload_builder_handle().clone(),
                 engine_payload_validator,
                 engine_tree_config,
-                ctx.invalid_block_hook()?,
+                ctx.invalid_block_hook(&bad_block_registry)?,
+                bad_block_registry,
                 ctx.sync_metrics_tx(),
                 consensus_engine_tx.clone(),
                 Box::pin(consensus_engine_stream),
+                from_miner_handle,
                 ctx.dev_mining_mode(ctx.components().pool()),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
                 ctx.components().evm_config().clone(),
@@ -247,7 +284,8 @@ where
                 ctx.components().payload_builder_handle().clone(),
                 engine_payload_validator,
                 engine_tree_config,
-                ctx.invalid_block_hook()?,
+                ctx.invalid_block_hook(&bad_block_registry)?,
+                bad_block_registry,
                 ctx.sync_metrics_tx(),
                 ctx.components().evm_config().clone(),
             );
@@ -296,6 +334,15 @@ where
             .into_built_payload_stream()
             .fuse();
         let chainspec = ctx.chain_spec();
+        let provider = ctx.blockchain_db().clone();
+        // pruned blocks can be ahead of the head after a reorg, so the announced range is clamped
+        let mut earliest_block = earliest_available_block(&provider)?;
+        let mut block_range = BlockRangeUpdate {
+            earliest: earliest_block.min(ctx.head().number),
+            latest: ctx.head().number,
+            latest_hash: ctx.head().hash,
+        };
+        network_handle.update_block_range(block_range);
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
@@ -321,6 +368,22 @@ where
                             }
                         }
                     }
+                    event = network_pruner_events.select_next_some() => {
+                        // pruning may have moved the earliest block the node can serve
+                        if matches!(event, PrunerEvent::Finished { .. }) {
+                            match earliest_available_block(&provider) {
+                                Ok(earliest) if earliest != earliest_block => {
+                                    earliest_block = earliest;
+                                    block_range.earliest = earliest.min(block_range.latest);
+                                    network_handle.update_block_range(block_range);
+                                }
+                                Ok(_) => {}
+                                Err(err) => {
+                                    error!(target: "reth::cli", %err, "Failed to read prune checkpoints");
+                                }
+                            }
+                        }
+                    }
                     event = engine_service.next() => {
                         let Some(event) = event else { break };
                         debug!(target: "reth::cli", "Event: {event}");
@@ -351,6 +414,11 @@ where
                                         total_difficulty: chainspec.final_paris_total_difficulty().filter(|_| chainspec.is_paris_active_at_block(head.number())).unwrap_or_default(),
                                     };
                                     network_handle.update_status(head_block);
+
+                                    block_range.earliest = earliest_block.min(head.number());
+                                    block_range.latest = head.number();
+                                    block_range.latest_hash = head.hash();
+                                    network_handle.update_block_range(block_range);
                                 }
                                 event_sender.notify(ev);
                             }
@@ -393,3 +461,16 @@ where
         Ok(handle)
     }
 }
+
+/// Returns the earliest block for which the node still serves bodies and receipts to peers.
+fn earliest_available_block(provider: impl PruneCheckpointReader) -> ProviderResult<u64> {
+    let mut earliest = 0;
+    for segment in [PruneSegment::Receipts, PruneSegment::Transactions] {
+        if let Some(block) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            earliest = earliest.max(block + 1);
+        }
+    }
+    Ok(earliest)
+}
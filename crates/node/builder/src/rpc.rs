@@ -456,7 +456,15 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            bad_block_registry,
+            ..
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -490,6 +498,7 @@ where
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
             .with_consensus(node.consensus().clone())
+            .with_bad_block_registry(bad_block_registry)
             .build_with_auth_server(module_config, engine_api, eth_api);
 
         // in dev mode we generate 20 random dev-signer accounts
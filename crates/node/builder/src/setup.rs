@@ -13,21 +13,29 @@ use reth_downloaders::{
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
 use reth_network_p2p::{
-    bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
+    bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader,
+    snap::client::SnapClient, BlockClient,
 };
 use reth_node_api::HeaderTy;
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, SnapSyncStage},
+    Pipeline, StageId, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// If a [`SnapClient`] is given, the state is synced from peers through it, see [`build_pipeline`].
 #[expect(clippy::too_many_arguments)]
-pub fn build_networked_pipeline<N, Client, Executor>(
+pub fn build_networked_pipeline<N, Client, Snap, Executor>(
     config: &StageConfig,
     client: Client,
+    snap_client: Option<Snap>,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
     provider_factory: ProviderFactory<N>,
     task_executor: &TaskExecutor,
@@ -41,6 +49,7 @@ pub fn build_networked_pipeline<N, Client, Executor>(
 where
     N: ProviderNodeTypes,
     Client: BlockClient<Block = BlockTy<N>> + 'static,
+    Snap: SnapClient + Clone + 'static,
     Executor: BlockExecutorProvider<Primitives = N::Primitives>,
 {
     // building network downloaders using the fetch client
@@ -57,6 +66,7 @@ where
         config,
         header_downloader,
         body_downloader,
+        snap_client,
         consensus,
         max_block,
         metrics_tx,
@@ -70,12 +80,16 @@ where
 }
 
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
+///
+/// If a [`SnapClient`] is given, the [`SnapSyncStage`] downloads the state of the target block
+/// after the headers and bodies, so that only the blocks after it are executed.
 #[expect(clippy::too_many_arguments)]
-pub fn build_pipeline<N, H, B, Executor>(
+pub fn build_pipeline<N, H, B, Snap, Executor>(
     provider_factory: ProviderFactory<N>,
     stage_config: &StageConfig,
     header_downloader: H,
     body_downloader: B,
+    snap_client: Option<Snap>,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
     max_block: Option<u64>,
     metrics_tx: reth_stages::MetricEventsSender,
@@ -88,6 +102,7 @@ where
     N: ProviderNodeTypes,
     H: HeaderDownloader<Header = HeaderTy<N>> + 'static,
     B: BodyDownloader<Block = BlockTy<N>> + 'static,
+    Snap: SnapClient + Clone + 'static,
     Executor: BlockExecutorProvider<Primitives = N::Primitives>,
 {
     let mut builder = Pipeline::<N>::builder();
@@ -101,28 +116,32 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes,
+    )
+    .set(ExecutionStage::new(
+        executor,
+        consensus,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        exex_manager_handle,
+    ));
+    if let Some(snap_client) = snap_client {
+        debug!(target: "reth::cli", "Configuring builder to snap sync the state");
+        stages = stages.add_before(SnapSyncStage::new(snap_client), StageId::SenderRecovery);
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes,
-            )
-            .set(ExecutionStage::new(
-                executor,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
-        )
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
@@ -4,6 +4,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use clap::Args;
@@ -16,6 +17,10 @@ use reth_discv5::{
 };
 use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
 use reth_network::{
+    request_quota::{
+        EthRequestQuotaConfig, RequestQuota, DEFAULT_MAX_COST_PER_WINDOW,
+        DEFAULT_MAX_REQUESTS_PER_WINDOW, DEFAULT_REQUEST_QUOTA_WINDOW,
+    },
     transactions::{
         config::TransactionPropagationKind,
         constants::{
@@ -39,6 +44,9 @@ use tracing::error;
 
 use crate::version::P2P_CLIENT_VERSION;
 
+/// Default interval at which the known peers are written to the peers file.
+const DEFAULT_PEERS_FILE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -68,8 +76,8 @@ pub struct NetworkArgs {
     #[arg(long, default_value_t = 0)]
     pub dns_retries: usize,
 
-    /// The path to the known peers file. Connected peers are dumped to this file on nodes
-    /// shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
+    /// The path to the known peers file. Known peers are written to this file periodically and on
+    /// node shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
@@ -88,7 +96,19 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// Interval at which known peers, together with their reputation and history, are written to
+    /// the peers file while the node is running.
+    #[arg(
+        long = "peers-file.interval",
+        value_name = "DURATION",
+        value_parser = parse_peers_file_interval,
+        default_value = "5m"
+    )]
+    pub peers_file_interval: Duration,
+
+    /// NAT resolution method (any|none|upnp|natpmp|publicip|extip:\<IP\>)
+    ///
+    /// With `upnp` and `natpmp` the listener and discovery ports are also mapped on the gateway.
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -161,6 +181,49 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Disable serving the `snap/1` protocol to peers.
+    ///
+    /// By default, the node announces `snap/1` and answers the state requests of snap-syncing
+    /// peers from its recent state.
+    #[arg(long = "disable-snap")]
+    pub disable_snap: bool,
+
+    /// Sync the state from peers through the `snap/1` protocol instead of executing all blocks.
+    ///
+    /// Once the headers and bodies are downloaded, the state of the pipeline's target block is
+    /// downloaded and only the blocks after it are executed. Requires receipts pruning, since the
+    /// receipts of the blocks before the target block are never created.
+    #[arg(long = "snap-sync", conflicts_with = "disable_snap")]
+    pub snap_sync: bool,
+
+    /// Length of the window over which the `eth` requests of a peer are accounted, e.g. `1m`.
+    #[arg(
+        long = "request-quota.window",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "1m"
+    )]
+    pub request_quota_window: Duration,
+
+    /// Maximum number of `eth` requests of each kind a peer can make per window, before its
+    /// requests are answered with empty responses.
+    #[arg(
+        long = "request-quota.max-requests",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_MAX_REQUESTS_PER_WINDOW
+    )]
+    pub request_quota_max_requests: u64,
+
+    /// Maximum cost of the `eth` requests of each kind a peer can make per window.
+    ///
+    /// Every database read and every started KiB of a response cost one unit.
+    #[arg(
+        long = "request-quota.max-cost",
+        value_name = "COST",
+        default_value_t = DEFAULT_MAX_COST_PER_WINDOW
+    )]
+    pub request_quota_max_cost: u64,
 }
 
 impl NetworkArgs {
@@ -206,6 +269,16 @@ impl NetworkArgs {
         }
     }
 
+    /// Configures and returns an [`EthRequestQuotaConfig`] based on the current settings.
+    pub fn eth_request_quota_config(&self) -> EthRequestQuotaConfig {
+        EthRequestQuotaConfig::default().with_window(self.request_quota_window).with_quota(
+            RequestQuota {
+                max_requests: self.request_quota_max_requests,
+                max_cost: self.request_quota_max_cost,
+            },
+        )
+    }
+
     /// Build a [`NetworkConfigBuilder`] from a [`Config`] and a [`EthChainSpec`], in addition to
     /// the values in this option struct.
     ///
@@ -249,6 +322,7 @@ impl NetworkArgs {
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .transactions_manager_config(self.transactions_manager_config())
+            .eth_request_quota_config(self.eth_request_quota_config())
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -328,6 +402,7 @@ impl Default for NetworkArgs {
             identity: P2P_CLIENT_VERSION.to_string(),
             p2p_secret_key: None,
             no_persist_peers: false,
+            peers_file_interval: DEFAULT_PEERS_FILE_INTERVAL,
             nat: NatResolver::Any,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
@@ -342,7 +417,12 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            disable_snap: false,
+            snap_sync: false,
+            request_quota_window: DEFAULT_REQUEST_QUOTA_WINDOW,
+            request_quota_max_requests: DEFAULT_MAX_REQUESTS_PER_WINDOW,
+            request_quota_max_cost: DEFAULT_MAX_COST_PER_WINDOW,
         }
     }
 }
@@ -531,6 +611,15 @@ impl Default for DiscoveryArgs {
     }
 }
 
+/// Parses the interval at which the peers file is written, which must not be zero.
+fn parse_peers_file_interval(value: &str) -> Result<Duration, String> {
+    let interval = humantime::parse_duration(value).map_err(|err| err.to_string())?;
+    if interval.is_zero() {
+        return Err("interval must be greater than zero".to_string())
+    }
+    Ok(interval)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -552,6 +641,20 @@ mod tests {
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
     }
 
+    #[test]
+    fn parse_peers_file_interval_args() {
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--peers-file.interval", "30s"]).args;
+        assert_eq!(args.peers_file_interval, Duration::from_secs(30));
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--peers-file.interval",
+            "0s"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_peer_args() {
         let args =
@@ -1,12 +1,14 @@
 //! Pruning and full node arguments
 
-use crate::args::error::ReceiptsLogError;
+use crate::args::{database::parse_byte_size, error::ReceiptsLogError};
 use alloy_primitives::{Address, BlockNumber};
 use clap::{builder::RangedU64ValueParser, Args};
 use reth_chainspec::EthChainSpec;
 use reth_config::config::PruneConfig;
-use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
-use std::collections::BTreeMap;
+use reth_prune_types::{
+    HistoryAddressFilter, PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE,
+};
+use std::{collections::BTreeMap, time::Duration};
 
 /// Parameters for pruning and full node
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -22,65 +24,130 @@ pub struct PruningArgs {
 
     // Sender Recovery
     /// Prunes all sender recovery data.
-    #[arg(long = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before"])]
+    #[arg(long = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before", "sender_recovery_age", "sender_recovery_size"])]
     pub sender_recovery_full: bool,
     /// Prune sender recovery data before the `head-N` block number. In other words, keep last N +
     /// 1 blocks.
-    #[arg(long = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before"])]
+    #[arg(long = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before", "sender_recovery_age", "sender_recovery_size"])]
     pub sender_recovery_distance: Option<u64>,
     /// Prune sender recovery data before the specified block number. The specified block number is
     /// not pruned.
-    #[arg(long = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance"])]
+    #[arg(long = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_age", "sender_recovery_size"])]
     pub sender_recovery_before: Option<BlockNumber>,
+    /// Prune sender recovery data older than the specified duration, counting back from the
+    /// timestamp of the head block.
+    #[arg(long = "prune.senderrecovery.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_before", "sender_recovery_size"])]
+    pub sender_recovery_age: Option<Duration>,
+    /// Prune the oldest sender recovery data, so that the remaining data fits into the specified
+    /// size.
+    #[arg(long = "prune.senderrecovery.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_before", "sender_recovery_age"])]
+    pub sender_recovery_size: Option<usize>,
 
     // Transaction Lookup
     /// Prunes all transaction lookup data.
-    #[arg(long = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before"])]
+    #[arg(long = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before", "transaction_lookup_age", "transaction_lookup_size"])]
     pub transaction_lookup_full: bool,
     /// Prune transaction lookup data before the `head-N` block number. In other words, keep last N
     /// + 1 blocks.
-    #[arg(long = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before"])]
+    #[arg(long = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before", "transaction_lookup_age", "transaction_lookup_size"])]
     pub transaction_lookup_distance: Option<u64>,
     /// Prune transaction lookup data before the specified block number. The specified block number
     /// is not pruned.
-    #[arg(long = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance"])]
+    #[arg(long = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_age", "transaction_lookup_size"])]
     pub transaction_lookup_before: Option<BlockNumber>,
+    /// Prune transaction lookup data older than the specified duration, counting back from the
+    /// timestamp of the head block.
+    #[arg(long = "prune.transactionlookup.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_before", "transaction_lookup_size"])]
+    pub transaction_lookup_age: Option<Duration>,
+    /// Prune the oldest transaction lookup data, so that the remaining data fits into the
+    /// specified size.
+    #[arg(long = "prune.transactionlookup.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_before", "transaction_lookup_age"])]
+    pub transaction_lookup_size: Option<usize>,
 
     // Receipts
     /// Prunes all receipt data.
-    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_distance", "receipts_before", "receipts_age", "receipts_size"])]
     pub receipts_full: bool,
     /// Prune receipts before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_before"])]
+    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_before", "receipts_age", "receipts_size"])]
     pub receipts_distance: Option<u64>,
     /// Prune receipts before the specified block number. The specified block number is not pruned.
-    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_distance"])]
+    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_age", "receipts_size"])]
     pub receipts_before: Option<BlockNumber>,
+    /// Prune receipts older than the specified duration, counting back from the timestamp of the
+    /// head block.
+    #[arg(long = "prune.receipts.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before", "receipts_size"])]
+    pub receipts_age: Option<Duration>,
+    /// Prune the oldest receipts, so that the remaining data fits into the specified size.
+    #[arg(long = "prune.receipts.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before", "receipts_age"])]
+    pub receipts_size: Option<usize>,
 
     // Account History
     /// Prunes all account history.
-    #[arg(long = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before"])]
+    #[arg(long = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before", "account_history_age", "account_history_size"])]
     pub account_history_full: bool,
     /// Prune account before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before"])]
+    #[arg(long = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before", "account_history_age", "account_history_size"])]
     pub account_history_distance: Option<u64>,
     /// Prune account history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance"])]
+    #[arg(long = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_age", "account_history_size"])]
     pub account_history_before: Option<BlockNumber>,
+    /// Prune account history older than the specified duration, counting back from the timestamp
+    /// of the head block.
+    #[arg(long = "prune.accounthistory.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_before", "account_history_size"])]
+    pub account_history_age: Option<Duration>,
+    /// Prune the oldest account history, so that the remaining data fits into the specified size.
+    #[arg(long = "prune.accounthistory.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_before", "account_history_age"])]
+    pub account_history_size: Option<usize>,
 
     // Storage History
     /// Prunes all storage history data.
-    #[arg(long = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before"])]
+    #[arg(long = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before", "storage_history_age", "storage_history_size"])]
     pub storage_history_full: bool,
     /// Prune storage history before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before"])]
+    #[arg(long = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before", "storage_history_age", "storage_history_size"])]
     pub storage_history_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
+    #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_age", "storage_history_size"])]
     pub storage_history_before: Option<BlockNumber>,
+    /// Prune storage history data older than the specified duration, counting back from the
+    /// timestamp of the head block.
+    #[arg(long = "prune.storagehistory.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_before", "storage_history_size"])]
+    pub storage_history_age: Option<Duration>,
+    /// Prune the oldest storage history data, so that the remaining data fits into the specified
+    /// size.
+    #[arg(long = "prune.storagehistory.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_before", "storage_history_age"])]
+    pub storage_history_size: Option<usize>,
+
+    // Address Appearances
+    /// Prunes all address appearances index data.
+    #[arg(long = "prune.addressappearances.full", conflicts_with_all = &["address_appearances_distance", "address_appearances_before", "address_appearances_age", "address_appearances_size"])]
+    pub address_appearances_full: bool,
+    /// Prune address appearances index data before the `head-N` block number. In other words,
+    /// keep last N + 1 blocks.
+    #[arg(long = "prune.addressappearances.distance", value_name = "BLOCKS", conflicts_with_all = &["address_appearances_full", "address_appearances_before", "address_appearances_age", "address_appearances_size"])]
+    pub address_appearances_distance: Option<u64>,
+    /// Prune address appearances index data before the specified block number. The specified
+    /// block number is not pruned.
+    #[arg(long = "prune.addressappearances.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["address_appearances_full", "address_appearances_distance", "address_appearances_age", "address_appearances_size"])]
+    pub address_appearances_before: Option<BlockNumber>,
+    /// Prune address appearances index data older than the specified duration, counting back from
+    /// the timestamp of the head block.
+    #[arg(long = "prune.addressappearances.age", value_name = "DURATION", value_parser = humantime::parse_duration, conflicts_with_all = &["address_appearances_full", "address_appearances_distance", "address_appearances_before", "address_appearances_size"])]
+    pub address_appearances_age: Option<Duration>,
+    /// Prune the oldest address appearances index data, so that the remaining data fits into the
+    /// specified size.
+    #[arg(long = "prune.addressappearances.size", value_name = "SIZE", value_parser = parse_byte_size, conflicts_with_all = &["address_appearances_full", "address_appearances_distance", "address_appearances_before", "address_appearances_age"])]
+    pub address_appearances_size: Option<usize>,
+
+    // History Address Filter
+    /// Retain the full account and storage history of the specified addresses, regardless of the
+    /// account and storage history prune modes. Format: <`address`>[,<`address`>...]
+    #[arg(long = "prune.historyaddressfilter", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub history_address_filter: Vec<Address>,
 
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
@@ -111,6 +178,8 @@ impl PruningArgs {
                         .or(Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    address_appearances: None,
+                    history_address_filter: HistoryAddressFilter::default(),
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract()
@@ -141,6 +210,13 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.address_appearances_prune_mode() {
+            config.segments.address_appearances = Some(mode);
+        }
+        if !self.history_address_filter.is_empty() {
+            config.segments.history_address_filter =
+                self.history_address_filter.iter().copied().collect();
+        }
 
         Some(config)
     }
@@ -151,6 +227,10 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.sender_recovery_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.sender_recovery_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.sender_recovery_size {
+            Some(PruneMode::Size(size as u64))
         } else {
             None
         }
@@ -163,6 +243,10 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.transaction_lookup_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.transaction_lookup_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.transaction_lookup_size {
+            Some(PruneMode::Size(size as u64))
         } else {
             None
         }
@@ -175,6 +259,10 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.receipts_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.receipts_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.receipts_size {
+            Some(PruneMode::Size(size as u64))
         } else {
             None
         }
@@ -187,6 +275,10 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.account_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.account_history_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.account_history_size {
+            Some(PruneMode::Size(size as u64))
         } else {
             None
         }
@@ -199,6 +291,26 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.storage_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.storage_history_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.storage_history_size {
+            Some(PruneMode::Size(size as u64))
+        } else {
+            None
+        }
+    }
+
+    const fn address_appearances_prune_mode(&self) -> Option<PruneMode> {
+        if self.address_appearances_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.address_appearances_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.address_appearances_before {
+            Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.address_appearances_age {
+            Some(PruneMode::Age(age.as_secs()))
+        } else if let Some(size) = self.address_appearances_size {
+            Some(PruneMode::Size(size as u64))
         } else {
             None
         }
@@ -267,6 +379,59 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn pruning_args_age_and_size() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "90days",
+            "--prune.accounthistory.size",
+            "500GB",
+        ])
+        .args;
+        assert_eq!(args.receipts_prune_mode(), Some(PruneMode::Age(90 * 24 * 60 * 60)));
+        assert_eq!(
+            args.account_history_prune_mode(),
+            Some(PruneMode::Size(500 * 1024 * 1024 * 1024))
+        );
+
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "90days",
+            "--prune.receipts.distance",
+            "100000",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn pruning_args_history_address_filter() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.accounthistory.distance",
+            "10064",
+            "--prune.historyaddressfilter",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        let config = args.prune_config(reth_chainspec::MAINNET.as_ref()).unwrap();
+        assert_eq!(
+            config.segments.history_address_filter,
+            HistoryAddressFilter::from_iter([
+                Address::with_last_byte(1),
+                Address::with_last_byte(2)
+            ])
+        );
+
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.historyaddressfilter",
+            "invalid_address",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_receipts_log_filter() {
         let filter1 = "0x0000000000000000000000000000000000000001:full";
@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The address appearances stage within the pipeline.
+    ///
+    /// Indexes the blocks in which each address appeared.
+    AddressAppearances,
 }
@@ -351,6 +351,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the bad blocks directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
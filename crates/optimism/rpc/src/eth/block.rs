@@ -42,9 +42,7 @@ where
             let mut l1_block_info = reth_optimism_evm::extract_l1_info(block.body())?;
 
             return block
-                .body()
-                .transactions()
-                .iter()
+                .transactions_recovered()
                 .zip(receipts.iter())
                 .enumerate()
                 .map(|(idx, (tx, receipt))| -> Result<_, _> {
@@ -1,6 +1,6 @@
 //! Loads and formats OP receipt RPC response.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, TransactionMeta};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
@@ -26,7 +26,7 @@ where
 {
     async fn build_transaction_receipt(
         &self,
-        tx: OpTransactionSigned,
+        tx: Recovered<OpTransactionSigned>,
         meta: TransactionMeta,
         receipt: OpReceipt,
     ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
@@ -46,7 +46,7 @@ where
 
         Ok(OpReceiptBuilder::new(
             &self.inner.eth_api.provider().chain_spec(),
-            &tx,
+            tx.as_recovered_ref(),
             meta,
             &receipt,
             &receipts,
@@ -224,7 +224,7 @@ impl OpReceiptBuilder {
     /// Returns a new builder.
     pub fn new(
         chain_spec: &OpChainSpec,
-        transaction: &OpTransactionSigned,
+        transaction: Recovered<&OpTransactionSigned>,
         meta: TransactionMeta,
         receipt: &OpReceipt,
         all_receipts: &[OpReceipt],
@@ -253,7 +253,7 @@ impl OpReceiptBuilder {
             })?;
 
         let op_receipt_fields = OpReceiptFieldsBuilder::new(timestamp, block_number)
-            .l1_block_info(chain_spec, transaction, l1_block_info)?
+            .l1_block_info(chain_spec, transaction.tx(), l1_block_info)?
             .build();
 
         Ok(Self { core_receipt, op_receipt_fields })
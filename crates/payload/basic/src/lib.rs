@@ -186,10 +186,15 @@ where
     }
 
     fn on_new_state<N: NodePrimitives>(&mut self, new_state: CanonStateNotification<N>) {
-        let mut cached = CachedReads::default();
-
         // extract the state from the notification and put it into the cache
         let committed = new_state.committed();
+        if committed.is_empty() {
+            // a revert doesn't commit any blocks, so there's no tip to pre-cache state for
+            self.pre_cached = None;
+            return
+        }
+
+        let mut cached = CachedReads::default();
         let new_execution_outcome = committed.execution_outcome();
         for (addr, acc) in new_execution_outcome.bundle_accounts_iter() {
             if let Some(info) = acc.info.clone() {
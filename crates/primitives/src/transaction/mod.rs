@@ -1,10 +1,10 @@
 //! Transaction types.
 
 use crate::Recovered;
-pub use alloy_consensus::transaction::PooledTransaction;
 use once_cell as _;
 #[expect(deprecated)]
 pub use pooled::PooledTransactionsElementEcRecovered;
+pub use reth_ethereum_primitives::PooledTransaction;
 pub use reth_primitives_traits::{
     sync::{LazyLock, OnceLock},
     transaction::{
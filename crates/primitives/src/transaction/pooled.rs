@@ -2,7 +2,7 @@
 //! response to `GetPooledTransactions`.
 
 use crate::Recovered;
-use alloy_consensus::transaction::PooledTransaction;
+use reth_ethereum_primitives::PooledTransaction;
 
 /// A signed pooled transaction with recovered signer.
 #[deprecated(note = "use `Recovered` instead")]
@@ -6,8 +6,9 @@ use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_exex_types::FinishedExExHeight;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
-    NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
+    providers::StaticFileProvider, AddressAppearancesReader, BlockReader, DBProvider,
+    DatabaseProviderFactory, NodePrimitivesProvider, PruneCheckpointWriter,
+    StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use std::time::Duration;
@@ -81,6 +82,7 @@ impl PrunerBuilder {
         PF: DatabaseProviderFactory<
                 ProviderRW: PruneCheckpointWriter
                                 + BlockReader<Transaction: Encodable2718>
+                                + AddressAppearancesReader
                                 + StaticFileProviderFactory<
                     Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>,
                 >,
@@ -110,6 +112,7 @@ impl PrunerBuilder {
         Provider: StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>
             + DBProvider<Tx: DbTxMut>
             + BlockReader<Transaction: Encodable2718>
+            + AddressAppearancesReader
             + PruneCheckpointWriter,
     {
         let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
@@ -4,12 +4,16 @@ use crate::{
     segments::{PruneInput, Segment},
     Metrics, PruneLimiter, PrunerError, PrunerEvent,
 };
+use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
 use reth_exex_types::FinishedExExHeight;
 use reth_provider::{
-    DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    DBProvider, DatabaseProviderFactory, HeaderProvider, ProviderError, PruneCheckpointReader,
+    PruneCheckpointWriter,
+};
+use reth_prune_types::{
+    PruneCheckpoint, PruneMode, PruneProgress, PrunedSegmentInfo, PrunerOutput,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
 use reth_tokio_util::{EventSender, EventStream};
 use std::time::{Duration, Instant};
 use tokio::sync::watch;
@@ -100,7 +104,7 @@ where
 
 impl<Provider, S> Pruner<Provider, S>
 where
-    Provider: PruneCheckpointReader + PruneCheckpointWriter,
+    Provider: PruneCheckpointReader + PruneCheckpointWriter + HeaderProvider,
 {
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
@@ -192,8 +196,21 @@ where
                 break
             }
 
+            let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
+
             if let Some((to_block, prune_mode)) = segment
                 .mode()
+                .map(|mode| {
+                    Self::resolve_prune_mode(
+                        provider,
+                        segment.as_ref(),
+                        mode,
+                        previous_checkpoint,
+                        tip_block_number,
+                    )
+                })
+                .transpose()?
+                .flatten()
                 .map(|mode| {
                     mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
                 })
@@ -210,7 +227,6 @@ where
                 );
 
                 let segment_start = Instant::now();
-                let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
                 let segment_output = segment.prune(
                     provider,
                     PruneInput { previous_checkpoint, to_block, limiter: limiter.clone() },
@@ -263,6 +279,78 @@ where
         Ok((stats, pruned, output))
     }
 
+    /// Resolves the effective prune mode of the segment at the provided tip block number.
+    ///
+    /// [`PruneMode::Age`] and [`PruneMode::Size`] are resolved into [`PruneMode::Before`], never
+    /// pruning into the minimum number of blocks the segment needs to keep. Other prune modes are
+    /// returned as is.
+    ///
+    /// Returns [`None`] if there's nothing to prune yet.
+    fn resolve_prune_mode(
+        provider: &Provider,
+        segment: &dyn Segment<Provider>,
+        mode: PruneMode,
+        previous_checkpoint: Option<PruneCheckpoint>,
+        tip_block_number: BlockNumber,
+    ) -> Result<Option<PruneMode>, PrunerError> {
+        let first_kept_block = match mode {
+            PruneMode::Age(age) => {
+                let timestamp = |block_number: BlockNumber| -> Result<u64, PrunerError> {
+                    Ok(provider
+                        .header_by_number(block_number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?
+                        .timestamp())
+                };
+                let cutoff = timestamp(tip_block_number)?.saturating_sub(age);
+
+                // Binary search for the first block with a timestamp not older than the cutoff.
+                // The tip block always satisfies it.
+                let (mut low, mut high) = (0, tip_block_number);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if timestamp(mid)? >= cutoff {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                low
+            }
+            PruneMode::Size(max_size) => {
+                let Some(size) = segment.size(provider)? else {
+                    debug!(target: "pruner", segment = ?segment.segment(), "Segment doesn't support size-based pruning");
+                    return Ok(None)
+                };
+                if size <= max_size {
+                    return Ok(None)
+                }
+
+                // Assume that the data is evenly distributed across the unpruned blocks, and keep
+                // as many of the latest blocks as fit into the maximum size.
+                let first_unpruned_block = previous_checkpoint
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .map_or(0, |block_number| block_number + 1);
+                let unpruned_blocks = (tip_block_number + 1).saturating_sub(first_unpruned_block);
+                let kept_blocks =
+                    (unpruned_blocks as u128 * max_size as u128 / size as u128) as u64;
+                (tip_block_number + 1).saturating_sub(kept_blocks)
+            }
+            mode => return Ok(Some(mode)),
+        };
+
+        let first_kept_block = first_kept_block
+            .min(tip_block_number.saturating_sub(segment.segment().min_blocks(segment.purpose())));
+        debug!(
+            target: "pruner",
+            segment = ?segment.segment(),
+            ?mode,
+            %first_kept_block,
+            "Resolved prune mode"
+        );
+
+        Ok((first_kept_block > 0).then_some(PruneMode::Before(first_kept_block)))
+    }
+
     /// Returns `true` if the pruning is needed at the provided tip block number.
     /// This is determined by the check against minimum pruning interval and last pruned block
     /// number.
@@ -318,7 +406,9 @@ where
 
 impl<PF> Pruner<PF::ProviderRW, PF>
 where
-    PF: DatabaseProviderFactory<ProviderRW: PruneCheckpointWriter + PruneCheckpointReader>,
+    PF: DatabaseProviderFactory<
+        ProviderRW: PruneCheckpointWriter + PruneCheckpointReader + HeaderProvider,
+    >,
 {
     /// Run the pruner. This will only prune data up to the highest finished ExEx height, if there
     /// are no ExExes.
@@ -335,9 +425,47 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
+    use crate::{
+        segments::{PruneInput, Segment},
+        Pruner, PrunerError,
+    };
+    use alloy_consensus::Header;
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_primitives_traits::SealedHeader;
+    use reth_provider::{
+        errors::provider::ProviderResult, test_utils::create_test_provider_factory,
+        DatabaseProviderFactory,
+    };
+    use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[derive(Debug)]
+    struct TestSegment {
+        mode: PruneMode,
+        size: u64,
+    }
+
+    impl<Provider> Segment<Provider> for TestSegment {
+        fn segment(&self) -> PruneSegment {
+            PruneSegment::SenderRecovery
+        }
+
+        fn mode(&self) -> Option<PruneMode> {
+            Some(self.mode)
+        }
+
+        fn purpose(&self) -> PrunePurpose {
+            PrunePurpose::User
+        }
+
+        fn prune(&self, _: &Provider, _: PruneInput) -> Result<SegmentOutput, PrunerError> {
+            Ok(SegmentOutput::done())
+        }
+
+        fn size(&self, _: &Provider) -> ProviderResult<Option<u64>> {
+            Ok(Some(self.size))
+        }
+    }
 
     #[test]
     fn is_pruning_needed() {
@@ -378,4 +506,39 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn resolve_prune_mode() {
+        let db = TestStageDB::default();
+        let headers = (0..=100)
+            .map(|number| {
+                SealedHeader::seal_slow(Header {
+                    number,
+                    timestamp: number * 12,
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<_>>();
+        db.insert_headers(headers.iter()).unwrap();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let tip = 100;
+
+        let resolve = |mode, size| {
+            let segment = TestSegment { mode, size };
+            Pruner::<_, ()>::resolve_prune_mode(&provider, &segment, mode, None, tip).unwrap()
+        };
+
+        // Keep blocks from the last two minutes
+        assert_eq!(resolve(PruneMode::Age(120), 0), Some(PruneMode::Before(90)));
+        // All blocks are younger than the age
+        assert_eq!(resolve(PruneMode::Age(10_000), 0), None);
+
+        // Keep a quarter of the blocks
+        assert_eq!(resolve(PruneMode::Size(250), 1000), Some(PruneMode::Before(76)));
+        // The data already fits into the size
+        assert_eq!(resolve(PruneMode::Size(2000), 1000), None);
+
+        // Other modes are not resolved
+        assert_eq!(resolve(PruneMode::Distance(10), 0), Some(PruneMode::Distance(10)));
+    }
 }
@@ -9,14 +9,15 @@ use reth_provider::{errors::provider::ProviderResult, BlockReader, PruneCheckpoi
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
 pub use set::SegmentSet;
 pub use static_file::{
-    Headers as StaticFileHeaders, Receipts as StaticFileReceipts,
+    AccountChangeSets as StaticFileAccountChangeSets, Headers as StaticFileHeaders,
+    Receipts as StaticFileReceipts, StorageChangeSets as StaticFileStorageChangeSets,
     Transactions as StaticFileTransactions,
 };
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
-    AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    AccountHistory, AddressAppearances, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery,
+    StorageHistory, TransactionLookup,
 };
 
 /// A segment represents a pruning of some portion of the data.
@@ -39,6 +40,13 @@ pub trait Segment<Provider>: Debug + Send + Sync {
     /// Prune data for [`Self::segment`] using the provided input.
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError>;
 
+    /// Size of the data of [`Self::segment`] in bytes, used to resolve [`PruneMode::Size`].
+    ///
+    /// Returns [`None`] if the segment doesn't support size-based pruning.
+    fn size(&self, _provider: &Provider) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
+
     /// Save checkpoint for [`Self::segment`] to the database.
     fn save_checkpoint(
         &self,
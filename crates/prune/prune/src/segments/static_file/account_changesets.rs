@@ -0,0 +1,183 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::{providers::StaticFileProvider, DBProvider, StaticFileProviderFactory};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::trace;
+
+#[derive(Debug)]
+pub struct AccountChangeSets<N> {
+    static_file_provider: StaticFileProvider<N>,
+}
+
+impl<N> AccountChangeSets<N> {
+    pub const fn new(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider }
+    }
+}
+
+impl<Provider> Segment<Provider> for AccountChangeSets<Provider::Primitives>
+where
+    Provider: StaticFileProviderFactory + DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AccountChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .map(PruneMode::before_inclusive)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::StaticFile
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No account changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+
+        let mut last_pruned_block = None;
+        let (pruned, done) =
+            provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
+                range,
+                &mut limiter,
+                |_| false,
+                |(block_number, _)| last_pruned_block = Some(block_number),
+            )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned account changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more account changesets to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its account changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::Address;
+    use assert_matches::assert_matches;
+    use reth_db_api::{
+        models::{AccountBeforeTx, StaticFileAccountChangeSet},
+        tables,
+        transaction::DbTxMut,
+    };
+    use reth_provider::{
+        providers::StaticFileWriter, DatabaseProviderFactory, StaticFileProviderFactory,
+    };
+    use reth_prune_types::{
+        PruneInterruptReason, PruneMode, PruneProgress, SegmentOutput, SegmentOutputCheckpoint,
+    };
+    use reth_stages::test_utils::TestStageDB;
+    use reth_static_file_types::StaticFileSegment;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        let changeset = |block: u64| AccountBeforeTx {
+            address: Address::with_last_byte(block as u8),
+            info: None,
+        };
+
+        // Changesets of all blocks are in the database, and the ones up to block 5 were copied to
+        // static files
+        db.commit(|tx| {
+            for block in 0..=9 {
+                tx.put::<tables::AccountChangeSets>(block, changeset(block))?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for block in 0..=5 {
+            writer
+                .append_account_changeset(
+                    &StaticFileAccountChangeSet { changes: vec![changeset(block)] },
+                    block,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let segment = super::AccountChangeSets::new(db.factory.static_file_provider());
+        let provider = db.factory.database_provider_rw().unwrap();
+        let prune_mode = mode(&segment, &provider).unwrap();
+        assert_eq!(prune_mode, PruneMode::Before(6));
+
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 5,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(4),
+        };
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            SegmentOutput {
+                progress: PruneProgress::HasMoreData(
+                    PruneInterruptReason::DeletedEntriesLimitReached
+                ),
+                pruned: 4,
+                checkpoint: Some(SegmentOutputCheckpoint { block_number: Some(2), .. })
+            }
+        );
+
+        let input = PruneInput {
+            previous_checkpoint: result.checkpoint.map(|c| c.as_prune_checkpoint(prune_mode)),
+            to_block: 5,
+            limiter: PruneLimiter::default(),
+        };
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned: 2,
+                checkpoint: Some(SegmentOutputCheckpoint { block_number: Some(5), .. })
+            }
+        );
+        provider.commit().expect("commit");
+
+        // Only the changesets which aren't in static files are left in the database
+        assert_eq!(
+            db.table::<tables::AccountChangeSets>().unwrap(),
+            (6..=9).map(|block| (block, changeset(block))).collect::<Vec<_>>()
+        );
+    }
+
+    fn mode<Provider>(segment: &impl Segment<Provider>, _: &Provider) -> Option<PruneMode> {
+        segment.mode()
+    }
+}
@@ -1,14 +1,23 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    segments::{
+        user::history::{prune_history_indices, save_history_address_checkpoints},
+        PruneInput, Segment,
+    },
     PrunerError,
 };
 use itertools::Itertools;
-use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
-use reth_provider::DBProvider;
+use reth_db_api::{
+    models::ShardedKey,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{errors::provider::ProviderResult, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    HistoryAddressFilter, PruneMode, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
 };
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -21,17 +30,19 @@ const ACCOUNT_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Addresses whose history is retained regardless of the prune mode.
+    address_filter: HistoryAddressFilter,
 }
 
 impl AccountHistory {
-    pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub const fn new(mode: PruneMode, address_filter: HistoryAddressFilter) -> Self {
+        Self { mode, address_filter }
     }
 }
 
 impl<Provider> Segment<Provider> for AccountHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::AccountHistory
@@ -68,6 +79,9 @@ where
             ))
         }
 
+        // The history of the filtered addresses isn't pruned from here on.
+        save_history_address_checkpoints(provider, &self.address_filter)?;
+
         let mut last_changeset_pruned_block = None;
         // Deleted account changeset keys (account addresses) with the highest block number deleted
         // for that key.
@@ -82,10 +96,13 @@ where
             provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
-                |_| false,
+                |(block_number, account)| {
+                    // Changesets of the filtered addresses are walked over, but not deleted
+                    last_changeset_pruned_block = Some(*block_number);
+                    self.address_filter.contains(&account.address)
+                },
                 |(block_number, account)| {
                     highest_deleted_accounts.insert(account.address, block_number);
-                    last_changeset_pruned_block = Some(block_number);
                 },
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
@@ -122,6 +139,17 @@ where
             }),
         })
     }
+
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        let tx = provider.tx_ref();
+        Ok(Some(
+            tx.table_size::<tables::AccountChangeSets>()? +
+                tx.table_size::<tables::AccountsHistory>()? +
+                provider
+                    .static_file_provider()
+                    .segment_size(StaticFileSegment::AccountChangeSets)?,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -133,9 +161,10 @@ mod tests {
     use alloy_primitives::{BlockNumber, B256};
     use assert_matches::assert_matches;
     use reth_db_api::{tables, BlockNumberList};
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter};
     use reth_prune_types::{
-        PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
+        HistoryAddressFilter, PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress,
+        PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
@@ -199,7 +228,7 @@ mod tests {
                     to_block,
                     limiter: limiter.clone(),
                 };
-                let segment = AccountHistory::new(prune_mode);
+                let segment = AccountHistory::new(prune_mode, HistoryAddressFilter::default());
 
                 let provider = db.factory.database_provider_rw().unwrap();
                 let result = segment.prune(&provider, input).unwrap();
@@ -303,4 +332,103 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 998));
         test_prune(1400, 3, (PruneProgress::Finished, 804));
     }
+
+    #[test]
+    fn prune_with_address_filter() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained_address = *accounts.keys().next().unwrap();
+        let other_address = *accounts.keys().nth(1).unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let original_changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        let original_shards = db.table::<tables::AccountsHistory>().unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let segment =
+            AccountHistory::new(prune_mode, HistoryAddressFilter::from_iter([retained_address]));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+        provider.commit().expect("commit");
+
+        // All changesets of the retained address are kept, while the ones of other addresses are
+        // pruned up to the target block.
+        let expected_changesets = original_changesets
+            .into_iter()
+            .filter(|(block_number, change)| {
+                change.address == retained_address || *block_number > to_block
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::AccountChangeSets>().unwrap(), expected_changesets);
+
+        // Same for the history shards.
+        let expected_shards = original_shards
+            .into_iter()
+            .filter_map(|(key, blocks)| {
+                if key.key == retained_address {
+                    return Some((key, blocks))
+                }
+                let new_blocks =
+                    blocks.iter().skip_while(|block| *block <= to_block).collect::<Vec<_>>();
+                (!new_blocks.is_empty()).then(|| (key, BlockNumberList::new_pre_sorted(new_blocks)))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::AccountsHistory>().unwrap(), expected_shards);
+
+        // Nothing was pruned before, so the history of the retained address is complete.
+        assert_eq!(
+            db.table::<tables::HistoryAddressCheckpoints>().unwrap(),
+            vec![(retained_address, 0)]
+        );
+
+        // The other address is retained from the block after the pruned ones, and the address
+        // that was removed from the filter isn't retained anymore.
+        let previous_checkpoint =
+            PruneCheckpoint { block_number: Some(to_block), tx_number: None, prune_mode };
+        let to_block = 70;
+        let input = PruneInput {
+            previous_checkpoint: Some(previous_checkpoint),
+            to_block,
+            limiter: PruneLimiter::default(),
+        };
+        let segment = AccountHistory::new(
+            PruneMode::Before(to_block),
+            HistoryAddressFilter::from_iter([other_address]),
+        );
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.save_prune_checkpoint(PruneSegment::AccountHistory, previous_checkpoint).unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            db.table::<tables::HistoryAddressCheckpoints>().unwrap(),
+            vec![(other_address, 51)]
+        );
+    }
 }
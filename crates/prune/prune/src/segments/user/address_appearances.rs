@@ -0,0 +1,202 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::Address;
+use reth_db_api::{
+    models::ShardedKey,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{errors::provider::ProviderResult, AddressAppearancesReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use std::collections::BTreeSet;
+use tracing::{instrument, trace};
+
+/// Prunes the [`tables::AddressAppearances`] index.
+///
+/// Unlike account and storage history, the index has no changesets of its own, so the addresses
+/// to prune are collected from the same block data the index is built from, see
+/// [`AddressAppearancesReader::address_appearances_with_range`]. The segment is therefore run
+/// before the segments pruning that data.
+#[derive(Debug)]
+pub struct AddressAppearances {
+    mode: PruneMode,
+}
+
+impl AddressAppearances {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressAppearances
+where
+    Provider: DBProvider<Tx: DbTxMut> + AddressAppearancesReader,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressAppearances
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address appearances to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        // Collect the addresses that appeared in the pruned blocks block by block, so that the
+        // limit is checked between blocks and all appearances up to the last collected block are
+        // pruned.
+        let mut addresses = BTreeSet::new();
+        let mut last_pruned_block = None;
+        let mut done = true;
+        for block_number in range {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            let appearances =
+                provider.address_appearances_with_range(block_number..=block_number)?;
+            limiter.increment_deleted_entries_count_by(appearances.len());
+            addresses.extend(appearances.into_keys());
+            last_pruned_block = Some(block_number);
+        }
+
+        let Some(last_pruned_block) = last_pruned_block else {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        };
+
+        let outcomes = prune_history_indices::<Provider, tables::AddressAppearances, Address>(
+            provider,
+            addresses.into_iter().map(|address| ShardedKey::new(address, last_pruned_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned address appearances");
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        Ok(Some(provider.tx_ref().table_size::<tables::AddressAppearances>()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressAppearances, PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::B256;
+    use reth_db_api::{tables, BlockNumberList};
+    use reth_provider::{
+        AddressAppearancesReader, DatabaseProviderFactory, HistoryWriter, PruneCheckpointReader,
+    };
+    use reth_prune_types::{PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
+    };
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 5);
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets, Some(1)).expect("insert changesets");
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider
+            .insert_address_appearances_index(
+                provider.address_appearances_with_range(1..=100).unwrap(),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+        let original_shards = db.table::<tables::AddressAppearances>().unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = AddressAppearances::new(prune_mode);
+        let mut runs = 0;
+        loop {
+            runs += 1;
+            let provider = db.factory.database_provider_rw().unwrap();
+            let input = PruneInput {
+                previous_checkpoint: provider
+                    .get_prune_checkpoint(PruneSegment::AddressAppearances)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(20),
+            };
+            let result = segment.prune(&provider, input).unwrap();
+            let checkpoint = result.checkpoint.unwrap();
+            segment.save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode)).unwrap();
+            provider.commit().unwrap();
+
+            // all appearances up to the checkpoint are pruned, and the later ones are kept
+            let last_pruned_block = checkpoint.block_number.unwrap();
+            let expected_shards = original_shards
+                .iter()
+                .filter_map(|(key, blocks)| {
+                    let blocks = blocks.iter().filter(|block| *block > last_pruned_block);
+                    let blocks = BlockNumberList::new_pre_sorted(blocks);
+                    (!blocks.is_empty()).then(|| (key.clone(), blocks))
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(db.table::<tables::AddressAppearances>().unwrap(), expected_shards);
+
+            if result.progress == PruneProgress::Finished {
+                assert_eq!(last_pruned_block, to_block);
+                break
+            }
+        }
+        assert!(runs > 1);
+    }
+}
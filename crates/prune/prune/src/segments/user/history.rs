@@ -3,10 +3,12 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     models::ShardedKey,
     table::Table,
-    transaction::DbTxMut,
+    tables,
+    transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_provider::DBProvider;
+use reth_prune_types::{HistoryAddressFilter, PruneSegment};
 
 enum PruneShardOutcome {
     Deleted,
@@ -21,6 +23,48 @@ pub(crate) struct PrunedIndices {
     pub(crate) unchanged: usize,
 }
 
+/// Saves the block from which the history of each address in the filter is retained into
+/// [`tables::HistoryAddressCheckpoints`], and removes the addresses that aren't in the filter
+/// anymore.
+///
+/// Addresses that are already saved keep their block. Newly added addresses are retained from the
+/// block after the highest block pruned by the account and storage history segments, their history
+/// before it may have been pruned already.
+pub(crate) fn save_history_address_checkpoints<Provider>(
+    provider: &Provider,
+    address_filter: &HistoryAddressFilter,
+) -> Result<(), DatabaseError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let tx = provider.tx_ref();
+
+    let mut retained_from = 0;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(block_number) = tx
+            .get::<tables::PruneCheckpoints>(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            retained_from = retained_from.max(block_number + 1);
+        }
+    }
+
+    let mut cursor = tx.cursor_write::<tables::HistoryAddressCheckpoints>()?;
+    let mut walker = cursor.walk(None)?;
+    while let Some((address, _)) = walker.next().transpose()? {
+        if !address_filter.contains(&address) {
+            walker.delete_current()?;
+        }
+    }
+    for address in address_filter.iter() {
+        if cursor.seek_exact(*address)?.is_none() {
+            cursor.upsert(*address, &retained_from)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Prune history indices according to the provided list of highest sharded keys.
 ///
 /// Returns total number of deleted, updated and unchanged entities.
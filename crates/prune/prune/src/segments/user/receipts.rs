@@ -2,13 +2,18 @@ use crate::{
     segments::{PruneInput, Segment},
     PrunerError,
 };
-use reth_db_api::{table::Value, transaction::DbTxMut};
+use reth_db_api::{
+    table::Value,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    errors::provider::ProviderResult, BlockReader, DBProvider, NodePrimitivesProvider,
-    PruneCheckpointWriter, TransactionsProvider,
+    errors::provider::ProviderResult, BlockReader, DBProvider, PruneCheckpointWriter,
+    StaticFileProviderFactory, TransactionsProvider,
 };
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use reth_static_file_types::StaticFileSegment;
 use tracing::instrument;
 
 #[derive(Debug)]
@@ -28,7 +33,7 @@ where
         + PruneCheckpointWriter
         + TransactionsProvider
         + BlockReader
-        + NodePrimitivesProvider<Primitives: NodePrimitives<Receipt: Value>>,
+        + StaticFileProviderFactory<Primitives: NodePrimitives<Receipt: Value>>,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::Receipts
@@ -47,6 +52,15 @@ where
         crate::segments::receipts::prune(provider, input)
     }
 
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        let size = provider
+            .tx_ref()
+            .table_size::<tables::Receipts<<Provider::Primitives as NodePrimitives>::Receipt>>()?;
+        let static_file_size =
+            provider.static_file_provider().segment_size(StaticFileSegment::Receipts)?;
+        Ok(Some(size + static_file_size))
+    }
+
     fn save_checkpoint(
         &self,
         provider: &Provider,
@@ -3,8 +3,13 @@ use crate::{
     segments::{PruneInput, Segment},
     PrunerError,
 };
-use reth_db_api::{tables, transaction::DbTxMut};
-use reth_provider::{BlockReader, DBProvider, TransactionsProvider};
+use reth_db_api::{
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{
+    errors::provider::ProviderResult, BlockReader, DBProvider, TransactionsProvider,
+};
 use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
@@ -78,6 +83,10 @@ where
             }),
         })
     }
+
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        Ok(Some(provider.tx_ref().table_size::<tables::TransactionSenders>()?))
+    }
 }
 
 #[cfg(test)]
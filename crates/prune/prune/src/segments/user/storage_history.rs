@@ -1,16 +1,22 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment, SegmentOutput},
+    segments::{
+        user::history::{prune_history_indices, save_history_address_checkpoints},
+        PruneInput, Segment, SegmentOutput,
+    },
     PrunerError,
 };
 use itertools::Itertools;
 use reth_db_api::{
     models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress},
     tables,
-    transaction::DbTxMut,
+    transaction::{DbTx, DbTxMut},
 };
-use reth_provider::DBProvider;
-use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_provider::{errors::provider::ProviderResult, DBProvider, StaticFileProviderFactory};
+use reth_prune_types::{
+    HistoryAddressFilter, PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -23,17 +29,19 @@ const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    /// Addresses whose history is retained regardless of the prune mode.
+    address_filter: HistoryAddressFilter,
 }
 
 impl StorageHistory {
-    pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub const fn new(mode: PruneMode, address_filter: HistoryAddressFilter) -> Self {
+        Self { mode, address_filter }
     }
 }
 
 impl<Provider> Segment<Provider> for StorageHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::StorageHistory
@@ -70,6 +78,9 @@ where
             ))
         }
 
+        // The history of the filtered addresses isn't pruned from here on.
+        save_history_address_checkpoints(provider, &self.address_filter)?;
+
         let mut last_changeset_pruned_block = None;
         // Deleted storage changeset keys (account addresses and storage slots) with the highest
         // block number deleted for that key.
@@ -84,10 +95,13 @@ where
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
-                |_| false,
+                |(BlockNumberAddress((block_number, address)), _)| {
+                    // Changesets of the filtered addresses are walked over, but not deleted
+                    last_changeset_pruned_block = Some(*block_number);
+                    self.address_filter.contains(address)
+                },
                 |(BlockNumberAddress((block_number, address)), entry)| {
                     highest_deleted_storages.insert((address, entry.key), block_number);
-                    last_changeset_pruned_block = Some(block_number);
                 },
             )?;
         trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
@@ -129,6 +143,17 @@ where
             }),
         })
     }
+
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        let tx = provider.tx_ref();
+        Ok(Some(
+            tx.table_size::<tables::StorageChangeSets>()? +
+                tx.table_size::<tables::StoragesHistory>()? +
+                provider
+                    .static_file_provider()
+                    .segment_size(StaticFileSegment::StorageChangeSets)?,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -141,7 +166,9 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_db_api::{tables, BlockNumberList};
     use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
-    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress, PruneSegment};
+    use reth_prune_types::{
+        HistoryAddressFilter, PruneCheckpoint, PruneMode, PruneProgress, PruneSegment,
+    };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
@@ -205,7 +232,7 @@ mod tests {
                 to_block,
                 limiter: limiter.clone(),
             };
-            let segment = StorageHistory::new(prune_mode);
+            let segment = StorageHistory::new(prune_mode, HistoryAddressFilter::default());
 
             let provider = db.factory.database_provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
@@ -316,4 +343,68 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 499));
         test_prune(1200, 3, (PruneProgress::Finished, 202));
     }
+
+    #[test]
+    fn prune_with_address_filter() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained_address = *accounts.keys().next().unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            1..2,
+            1..2,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let original_changesets = db.table::<tables::StorageChangeSets>().unwrap();
+        let original_shards = db.table::<tables::StoragesHistory>().unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let segment =
+            StorageHistory::new(prune_mode, HistoryAddressFilter::from_iter([retained_address]));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+        provider.commit().expect("commit");
+
+        // All changesets of the retained address are kept, while the ones of other addresses are
+        // pruned up to the target block.
+        let expected_changesets = original_changesets
+            .into_iter()
+            .filter(|(key, _)| key.address() == retained_address || key.block_number() > to_block)
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::StorageChangeSets>().unwrap(), expected_changesets);
+
+        // Same for the history shards.
+        let expected_shards = original_shards
+            .into_iter()
+            .filter_map(|(key, blocks)| {
+                if key.address == retained_address {
+                    return Some((key, blocks))
+                }
+                let new_blocks =
+                    blocks.iter().skip_while(|block| *block <= to_block).collect::<Vec<_>>();
+                (!new_blocks.is_empty()).then(|| (key, BlockNumberList::new_pre_sorted(new_blocks)))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::StoragesHistory>().unwrap(), expected_shards);
+    }
 }
@@ -5,8 +5,11 @@ use crate::{
 };
 use alloy_eips::eip2718::Encodable2718;
 use rayon::prelude::*;
-use reth_db_api::{tables, transaction::DbTxMut};
-use reth_provider::{BlockReader, DBProvider};
+use reth_db_api::{
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{errors::provider::ProviderResult, BlockReader, DBProvider};
 use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
 use tracing::{instrument, trace};
 
@@ -105,6 +108,10 @@ where
             }),
         })
     }
+
+    fn size(&self, provider: &Provider) -> ProviderResult<Option<u64>> {
+        Ok(Some(provider.tx_ref().table_size::<tables::TransactionHashNumbers>()?))
+    }
 }
 
 #[cfg(test)]
@@ -18,7 +18,10 @@ mod pruner;
 mod segment;
 mod target;
 
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use alloy_primitives::{Address, BlockNumber};
 use core::ops::Deref;
 
@@ -117,6 +120,38 @@ impl Deref for ReceiptsLogPruneConfig {
     }
 }
 
+/// Set of addresses whose account and storage history is retained in full, regardless of the
+/// account and storage history prune modes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryAddressFilter(pub BTreeSet<Address>);
+
+impl HistoryAddressFilter {
+    /// Checks if the filter is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the history of the given address is retained.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.contains(address)
+    }
+}
+
+impl FromIterator<Address> for HistoryAddressFilter {
+    fn from_iter<I: IntoIterator<Item = Address>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Deref for HistoryAddressFilter {
+    type Target = BTreeSet<Address>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
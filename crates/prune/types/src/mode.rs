@@ -15,6 +15,16 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks with a timestamp older than the specified number of seconds, counting back
+    /// from the timestamp of the tip block.
+    ///
+    /// Resolved into [`PruneMode::Before`] by the pruner on every run.
+    Age(u64),
+    /// Prune the oldest blocks, so that the data of the segment fits into the specified number of
+    /// bytes.
+    ///
+    /// Resolved into [`PruneMode::Before`] by the pruner on every run.
+    Size(u64),
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -51,6 +61,8 @@ impl PruneMode {
             Self::Before(n) => {
                 (tip - n >= segment.min_blocks(purpose)).then(|| ((*n).saturating_sub(1), *self))
             }
+            // Resolved into `Self::Before` by the pruner, nothing to prune until then
+            Self::Age(_) | Self::Size(_) => None,
             _ => return Err(PruneSegmentError::Configuration(segment)),
         };
         Ok(result)
@@ -67,6 +79,8 @@ impl PruneMode {
                 block < tip - *distance
             }
             Self::Before(n) => *n > block,
+            // Resolved only by the pruner, so the data is always kept until then
+            Self::Age(_) | Self::Size(_) => false,
         }
     }
 
@@ -115,6 +129,9 @@ mod tests {
             ),
             // Nothing to prune
             (PruneMode::Before(tip - 1), Ok(None)),
+            // Resolved by the pruner
+            (PruneMode::Age(3600), Ok(None)),
+            (PruneMode::Size(1024), Ok(None)),
         ];
 
         for (index, (mode, expected_result)) in tests.into_iter().enumerate() {
@@ -152,6 +169,8 @@ mod tests {
             ),
             (PruneMode::Before(tip + 1), 1, should_prune),
             (PruneMode::Before(tip + 1), tip + 1, !should_prune),
+            (PruneMode::Age(0), 1, !should_prune),
+            (PruneMode::Size(0), 1, !should_prune),
         ];
 
         for (index, (mode, block, expected_result)) in tests.into_iter().enumerate() {
@@ -167,12 +186,16 @@ mod tests {
             b: Option<PruneMode>,
             c: Option<PruneMode>,
             d: Option<PruneMode>,
+            e: Option<PruneMode>,
+            f: Option<PruneMode>,
         }
 
         let toml_str = r#"
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        e = { age = 7776000 }
+        f = { size = 536870912000 }
     "#;
 
         assert_matches!(
@@ -181,7 +204,9 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
+                d: None,
+                e: Some(PruneMode::Age(7776000)),
+                f: Some(PruneMode::Size(536870912000)),
             })
         );
     }
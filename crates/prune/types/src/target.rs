@@ -1,4 +1,4 @@
-use crate::{PruneMode, ReceiptsLogPruneConfig};
+use crate::{HistoryAddressFilter, PruneMode, ReceiptsLogPruneConfig};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -46,6 +46,20 @@ pub struct PruneModes {
         )
     )]
     pub storage_history: Option<PruneMode>,
+    /// Address Appearances pruning configuration.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
+    pub address_appearances: Option<PruneMode>,
+    /// Account and storage history pruning configuration by retaining the full history of the
+    /// specified addresses, discarding others according to `account_history` and
+    /// `storage_history`.
+    ///
+    /// History of an address is only retained from the moment it's added to the filter, anything
+    /// pruned before that can't be recovered.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(skip_serializing_if = "HistoryAddressFilter::is_empty")
+    )]
+    pub history_address_filter: HistoryAddressFilter,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -68,6 +82,8 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            address_appearances: Some(PruneMode::Full),
+            history_address_filter: Default::default(),
             receipts_log_filter: Default::default(),
         }
     }
@@ -77,6 +93,12 @@ impl PruneModes {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
     }
 
+    /// Returns whether the history of some addresses is retained regardless of the account and
+    /// storage history prune modes.
+    pub fn has_history_address_filter(&self) -> bool {
+        !self.history_address_filter.is_empty()
+    }
+
     /// Returns true if all prune modes are set to [`None`].
     pub fn is_empty(&self) -> bool {
         self == &Self::none()
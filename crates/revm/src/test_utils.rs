@@ -1,16 +1,19 @@
 use alloc::vec::Vec;
 use alloy_primitives::{
-    keccak256, map::HashMap, Address, BlockNumber, Bytes, StorageKey, B256, U256,
+    keccak256,
+    map::{B256Map, B256Set, HashMap},
+    Address, BlockNumber, Bytes, StorageKey, B256, U256,
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
-    StateRootProvider, StorageRootProvider,
+    AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+    StateProofProvider, StateProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher,
-    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedAccountRange, HashedPostState, HashedStorage,
+    HashedStorageRange, KeccakKeyHasher, MultiProof, MultiProofTargets, StorageMultiProof,
+    StorageProof, TrieInput,
 };
 
 /// Mock state for testing
@@ -150,6 +153,51 @@ impl HashedPostStateProvider for StateProviderTest {
     }
 }
 
+impl HashedStateRangeProvider for StateProviderTest {
+    fn hashed_account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedAccountRange> {
+        unimplemented!("hashed state range is not supported")
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        unimplemented!("hashed state range is not supported")
+    }
+
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>> {
+        Ok(self
+            .accounts
+            .keys()
+            .map(|address| (keccak256(address), *address))
+            .filter(|(hashed_address, _)| hashed_addresses.contains(hashed_address))
+            .collect())
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>> {
+        Ok(self
+            .accounts
+            .get(&address)
+            .into_iter()
+            .flat_map(|(storage, _)| storage.keys())
+            .map(|slot| (keccak256(slot), *slot))
+            .filter(|(hashed_slot, _)| hashed_slots.contains(hashed_slot))
+            .collect())
+    }
+}
+
 impl StateProvider for StateProviderTest {
     fn storage(
         &self,
@@ -1,6 +1,9 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_eth_wire_types::TracedMessage;
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+use reth_storage_api::SnapshotManifest;
+use std::path::PathBuf;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +37,31 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the `RLPx` messages traced for the active session with the given peer, oldest
+    /// first.
+    ///
+    /// If `trace` is set, tracing for the peer is enabled or disabled before the messages are
+    /// returned. Tracing is disabled by default and stays selected for the peer across
+    /// reconnects. Returns `null` if there's no active session with the peer.
+    #[method(name = "peerMessages")]
+    async fn peer_messages(
+        &self,
+        peer_id: PeerId,
+        trace: Option<bool>,
+    ) -> RpcResult<Option<Vec<TracedMessage>>>;
+
+    /// Writes a consistent snapshot of the database and the static files of the node to the given
+    /// directory, which must either not exist or be empty, and returns its manifest.
+    ///
+    /// The node keeps running while the snapshot is taken. If `compact` is set, free pages are
+    /// omitted from the database copy. The snapshot can be restored with `reth db restore`.
+    #[method(name = "snapshot")]
+    async fn snapshot(
+        &self,
+        destination: PathBuf,
+        compact: Option<bool>,
+    ) -> RpcResult<SnapshotManifest>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
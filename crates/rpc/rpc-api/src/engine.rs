@@ -219,12 +219,16 @@ pub trait EngineApi<Engine: EngineTypes> {
         versioned_hashes: Vec<B256>,
     ) -> RpcResult<Vec<Option<BlobAndProofV1>>>;
 
-    /// Fetch blobs for the consensus layer from the blob store.
+    /// Fetch blobs with their cell proofs for the consensus layer from the blob store.
+    ///
+    /// Returns `null` if any of the requested blobs is missing.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/osaka.md#engine_getblobsv2>
     #[method(name = "getBlobsV2")]
     async fn get_blobs_v2(
         &self,
         versioned_hashes: Vec<B256>,
-    ) -> RpcResult<Vec<Option<BlobAndProofV2>>>;
+    ) -> RpcResult<Option<Vec<BlobAndProofV2>>>;
 }
 
 /// A subset of the ETH rpc interface: <https://ethereum.github.io/execution-apis/api-documentation/>
@@ -31,6 +31,8 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{AccountRangeResult, BadBlock, DumpAccount, StorageRangeEntry, StorageRangeResult};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,8 +40,11 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        ganache::GanacheApiServer,
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
@@ -32,14 +32,17 @@ use jsonrpsee::{
     },
     Methods, RpcModule,
 };
+use reth_bad_blocks::BadBlockRegistry;
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, FullRpcProvider, ProviderBlock, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseSnapshotProvider,
+    FullRpcProvider, ProviderBlock, PruneCheckpointReader, StateProviderFactory,
+    StorageChangeSetReader,
 };
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
@@ -118,7 +121,11 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + AddressAppearancesReader
+        + DatabaseSnapshotProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -164,6 +171,8 @@ pub struct RpcModuleBuilder<N, Provider, Pool, Network, Tasks, EvmConfig, BlockE
     block_executor: BlockExecutor,
     /// The consensus implementation.
     consensus: Consensus,
+    /// The registry of blocks that failed validation, served by the `debug_` namespace.
+    bad_block_registry: Option<BadBlockRegistry>,
     /// Node data primitives.
     _primitives: PhantomData<N>,
 }
@@ -193,10 +202,17 @@ where
             evm_config,
             block_executor,
             consensus,
+            bad_block_registry: None,
             _primitives: PhantomData,
         }
     }
 
+    /// Configure the registry of blocks that failed validation.
+    pub fn with_bad_block_registry(mut self, bad_block_registry: BadBlockRegistry) -> Self {
+        self.bad_block_registry = Some(bad_block_registry);
+        self
+    }
+
     /// Configure the provider instance.
     pub fn with_provider<P>(
         self,
@@ -208,6 +224,7 @@ where
             + 'static,
     {
         let Self {
+            bad_block_registry,
             pool,
             network,
             executor,
@@ -218,6 +235,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -238,6 +256,7 @@ where
         P: TransactionPool<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
     {
         let Self {
+            bad_block_registry,
             provider,
             network,
             executor,
@@ -248,6 +267,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -277,6 +297,7 @@ where
         Consensus,
     > {
         let Self {
+            bad_block_registry,
             provider,
             executor,
             network,
@@ -287,6 +308,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             executor,
             network,
@@ -307,6 +329,7 @@ where
         Net: NetworkInfo + Peers + 'static,
     {
         let Self {
+            bad_block_registry,
             provider,
             pool,
             executor,
@@ -317,6 +340,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -338,6 +362,7 @@ where
     ) -> RpcModuleBuilder<N, Provider, Pool, NoopNetwork, Tasks, EvmConfig, BlockExecutor, Consensus>
     {
         let Self {
+            bad_block_registry,
             provider,
             pool,
             executor,
@@ -348,6 +373,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             pool,
             executor,
@@ -368,6 +394,7 @@ where
         T: TaskSpawner + 'static,
     {
         let Self {
+            bad_block_registry,
             pool,
             network,
             provider,
@@ -378,6 +405,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -406,6 +434,7 @@ where
         Consensus,
     > {
         let Self {
+            bad_block_registry,
             pool,
             network,
             provider,
@@ -416,6 +445,7 @@ where
             ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -436,9 +466,18 @@ where
         EvmConfig: 'static,
     {
         let Self {
-            provider, pool, executor, network, block_executor, consensus, _primitives, ..
+            bad_block_registry,
+            provider,
+            pool,
+            executor,
+            network,
+            block_executor,
+            consensus,
+            _primitives,
+            ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -458,9 +497,19 @@ where
     where
         BE: BlockExecutorProvider<Primitives = N> + 'static,
     {
-        let Self { provider, network, pool, executor, evm_config, consensus, _primitives, .. } =
-            self;
+        let Self {
+            bad_block_registry,
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            _primitives,
+            ..
+        } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -478,9 +527,18 @@ where
         consensus: C,
     ) -> RpcModuleBuilder<N, Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, C> {
         let Self {
-            provider, network, pool, executor, evm_config, block_executor, _primitives, ..
+            bad_block_registry,
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            block_executor,
+            _primitives,
+            ..
         } = self;
         RpcModuleBuilder {
+            bad_block_registry,
             provider,
             network,
             pool,
@@ -537,7 +595,11 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + AddressAppearancesReader
+        + DatabaseSnapshotProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -566,7 +628,15 @@ where
         EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     {
         let Self {
-            provider, pool, network, executor, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_block_registry,
+            ..
         } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
@@ -581,7 +651,8 @@ where
             evm_config,
             eth,
             block_executor,
-        );
+        )
+        .with_bad_block_registry(bad_block_registry.unwrap_or_default());
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -603,7 +674,15 @@ where
         EthApi: EthApiTypes + 'static,
     {
         let Self {
-            provider, pool, network, executor, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_block_registry,
+            ..
         } = self;
         RpcRegistryInner::new(
             provider,
@@ -616,6 +695,7 @@ where
             eth,
             block_executor,
         )
+        .with_bad_block_registry(bad_block_registry.unwrap_or_default())
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -631,7 +711,15 @@ where
         let mut modules = TransportRpcModules::default();
 
         let Self {
-            provider, pool, network, executor, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            bad_block_registry,
+            ..
         } = self;
 
         if !module_config.is_empty() {
@@ -647,7 +735,8 @@ where
                 evm_config,
                 eth,
                 block_executor,
-            );
+            )
+            .with_bad_block_registry(bad_block_registry.unwrap_or_default());
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -768,6 +857,8 @@ pub struct RpcRegistryInner<
     modules: HashMap<RethRpcModule, Methods>,
     /// eth config settings
     eth_config: EthConfig,
+    /// The registry of blocks that failed validation
+    bad_block_registry: BadBlockRegistry,
 }
 
 // === impl RpcRegistryInner ===
@@ -819,8 +910,16 @@ where
             blocking_pool_guard,
             block_executor,
             eth_config: config.eth,
+            bad_block_registry: BadBlockRegistry::default(),
         }
     }
+
+    /// Configures the registry of blocks that failed validation, which is served by the `debug_`
+    /// namespace.
+    pub fn with_bad_block_registry(mut self, bad_block_registry: BadBlockRegistry) -> Self {
+        self.bad_block_registry = bad_block_registry;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, EthApi, BlockExecutor, Consensus>
@@ -881,8 +980,10 @@ where
     pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec>
     where
         Network: Peers,
+        Provider: DatabaseSnapshotProvider + Clone + 'static,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec())
+            .with_snapshot_provider(self.provider.clone())
     }
 
     /// Instantiates `Web3Api`
@@ -894,6 +995,7 @@ where
     pub fn register_admin(&mut self) -> &mut Self
     where
         Network: Peers,
+        Provider: DatabaseSnapshotProvider + Clone + 'static,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -918,7 +1020,11 @@ where
             Receipt = N::Receipt,
             Transaction = N::SignedTx,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + AddressAppearancesReader
+        + DatabaseSnapshotProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiServer<
@@ -948,6 +1054,7 @@ where
     pub fn register_ots(&mut self) -> &mut Self
     where
         EthApi: TraceExt + EthTransactions,
+        EthApi::Provider: AddressAppearancesReader,
     {
         let otterscan_api = self.otterscan_api();
         self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
@@ -962,6 +1069,7 @@ where
     pub fn register_debug(&mut self) -> &mut Self
     where
         EthApi: EthApiSpec + EthTransactions + TraceExt,
+        EthApi::Provider: ChangeSetReader + StorageChangeSetReader + PruneCheckpointReader,
         BlockExecutor::Primitives: NodePrimitives<Block = ProviderBlock<EthApi::Provider>>,
     {
         let debug_api = self.debug_api();
@@ -1033,7 +1141,11 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + AddressAppearancesReader
+        + DatabaseSnapshotProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiTypes,
@@ -1079,6 +1191,8 @@ where
             self.blocking_pool_guard.clone(),
             self.block_executor.clone(),
         )
+        .with_bad_block_registry(self.bad_block_registry.clone())
+        .with_max_range_blocks(self.eth_config.max_trace_filter_blocks)
     }
 
     /// Instantiates `NetApi`
@@ -1107,7 +1221,11 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + AddressAppearancesReader
+        + DatabaseSnapshotProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -1193,6 +1311,7 @@ where
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
                             AdminApi::new(self.network.clone(), self.provider.chain_spec())
+                                .with_snapshot_provider(self.provider.clone())
                                 .into_rpc()
                                 .into()
                         }
@@ -1201,6 +1320,8 @@ where
                             self.blocking_pool_guard.clone(),
                             self.block_executor.clone(),
                         )
+                        .with_bad_block_registry(self.bad_block_registry.clone())
+                        .with_max_range_blocks(self.eth_config.max_trace_filter_blocks)
                         .into_rpc()
                         .into(),
                         RethRpcModule::Eth => {
@@ -1255,6 +1376,8 @@ where
                         // implementation
                         // TODO: can we get rid of this here?
                         RethRpcModule::Flashbots => Default::default(),
+                        // only available on dev nodes and configured in `EthereumAddOns`
+                        RethRpcModule::Anvil => Default::default(),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
                             EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
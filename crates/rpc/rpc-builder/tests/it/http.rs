@@ -28,7 +28,7 @@ use reth_rpc_api::{
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::{collections::HashSet, path::PathBuf};
 
 fn is_unimplemented(err: jsonrpsee::core::client::Error) -> bool {
     match err {
@@ -146,6 +146,9 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::peer_messages(client, node.id, Some(true)).await.unwrap();
+    // the noop provider doesn't support snapshots
+    AdminApiClient::snapshot(client, PathBuf::from("snapshot"), None).await.unwrap_err();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -362,11 +365,11 @@ where
 {
     let block_id = BlockId::Number(BlockNumberOrTag::default());
 
-    DebugApiClient::raw_header(client, block_id).await.unwrap();
-    DebugApiClient::raw_block(client, block_id).await.unwrap_err();
-    DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
-    DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    DebugApiClient::<Block>::raw_header(client, block_id).await.unwrap();
+    DebugApiClient::<Block>::raw_block(client, block_id).await.unwrap_err();
+    DebugApiClient::<Block>::raw_transaction(client, B256::default()).await.unwrap();
+    DebugApiClient::<Block>::raw_receipts(client, block_id).await.unwrap();
+    assert!(DebugApiClient::<Block>::bad_blocks(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -467,28 +470,22 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -26,7 +26,6 @@ use reth_payload_primitives::{
 };
 use reth_primitives_traits::{Block, BlockBody};
 use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
-use reth_rpc_server_types::result::internal_rpc_err;
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
@@ -781,7 +780,7 @@ where
 
         self.inner
             .tx_pool
-            .get_blobs_for_versioned_hashes(&versioned_hashes)
+            .get_blobs_for_versioned_hashes_v1(&versioned_hashes)
             .map_err(|err| EngineApiError::Internal(Box::new(err)))
     }
 
@@ -804,6 +803,44 @@ where
 
         res
     }
+
+    fn get_blobs_v2(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Option<Vec<BlobAndProofV2>>> {
+        if versioned_hashes.len() > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::BlobRequestTooLarge { len: versioned_hashes.len() })
+        }
+
+        self.inner
+            .tx_pool
+            .get_blobs_for_versioned_hashes_v2(&versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))
+    }
+
+    fn get_blobs_v2_metered(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Option<Vec<BlobAndProofV2>>> {
+        let hashes_len = versioned_hashes.len();
+        let start = Instant::now();
+        let res = Self::get_blobs_v2(self, versioned_hashes);
+        self.inner.metrics.latency.get_blobs_v2.record(start.elapsed());
+
+        match &res {
+            Ok(Some(_)) => {
+                self.inner.metrics.blob_metrics.blob_count.increment(hashes_len as u64);
+                self.inner.metrics.blob_metrics.get_blobs_v2_hits.increment(1);
+            }
+            Ok(None) => {
+                self.inner.metrics.blob_metrics.blob_misses.increment(hashes_len as u64);
+                self.inner.metrics.blob_metrics.get_blobs_v2_misses.increment(1);
+            }
+            Err(_) => {}
+        }
+
+        res
+    }
 }
 
 impl<Provider, PayloadT, Pool, Validator, ChainSpec>
@@ -1072,12 +1109,17 @@ where
         Ok(self.get_blobs_v1_metered(versioned_hashes)?)
     }
 
+    /// Handler for `engine_getBlobsV2`
+    ///
+    /// Returns the blobs with their cell proofs if all of them are available, `null` otherwise.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/osaka.md#engine_getblobsv2>
     async fn get_blobs_v2(
         &self,
-        _versioned_hashes: Vec<B256>,
-    ) -> RpcResult<Vec<Option<BlobAndProofV2>>> {
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Option<Vec<BlobAndProofV2>>> {
         trace!(target: "rpc::engine", "Serving engine_getBlobsV2");
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.get_blobs_v2_metered(versioned_hashes)?)
     }
 }
 
@@ -52,6 +52,8 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
     /// Latency for `engine_getBlobsV1`
     pub(crate) get_blobs_v1: Histogram,
+    /// Latency for `engine_getBlobsV2`
+    pub(crate) get_blobs_v2: Histogram,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
@@ -113,6 +115,10 @@ pub(crate) struct BlobMetrics {
     pub(crate) blob_count: Counter,
     /// Count of blob misses
     pub(crate) blob_misses: Counter,
+    /// Count of `engine_getBlobsV2` requests that returned all requested blobs
+    pub(crate) get_blobs_v2_hits: Counter,
+    /// Count of `engine_getBlobsV2` requests that missed at least one blob
+    pub(crate) get_blobs_v2_misses: Counter,
 }
 
 impl NewPayloadStatusResponseMetrics {
@@ -6,7 +6,10 @@ use crate::{
     helpers::estimate::EstimateCall, FromEthApiError, FullEthApiTypes, IntoEthApiError,
     RpcNodeCore, RpcNodeCoreExt, RpcReceipt, RpcTransaction,
 };
-use alloy_consensus::{transaction::TransactionMeta, BlockHeader, Transaction};
+use alloy_consensus::{
+    transaction::{Recovered, TransactionMeta},
+    BlockHeader, Transaction,
+};
 use alloy_dyn_abi::TypedData;
 use alloy_eips::{eip2718::Encodable2718, BlockId};
 use alloy_network::TransactionBuilder;
@@ -168,7 +171,11 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         hash: TxHash,
     ) -> impl Future<
         Output = Result<
-            Option<(ProviderTx<Self::Provider>, TransactionMeta, ProviderReceipt<Self::Provider>)>,
+            Option<(
+                Recovered<ProviderTx<Self::Provider>>,
+                TransactionMeta,
+                ProviderReceipt<Self::Provider>,
+            )>,
             Self::Error,
         >,
     > + Send
@@ -184,6 +191,7 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 Some((tx, meta)) => (tx, meta),
                 None => return Ok(None),
             };
+            let tx = with_stored_sender(&provider, hash, tx).map_err(Self::Error::from_eth_err)?;
 
             let receipt = match provider.receipt_by_hash(hash).map_err(Self::Error::from_eth_err)? {
                 Some(recpt) => recpt,
@@ -479,12 +487,8 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                     {
                         None => Ok(None),
                         Some((tx, meta)) => {
-                            // Note: we assume this transaction is valid, because it's mined (or
-                            // part of pending block) and already. We don't need to
-                            // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                            let transaction = tx
-                                .into_recovered_unchecked()
-                                .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                            let transaction = with_stored_sender(this.provider(), hash, tx)
+                                .map_err(Self::Error::from_eth_err)?;
 
                             let tx = TransactionSource::Block {
                                 transaction,
@@ -569,3 +573,25 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
         }
     }
 }
+
+/// Returns the mined transaction with its sender.
+///
+/// The sender is taken from the stored senders, because the signatures of transactions sent from
+/// impersonated accounts don't recover to them. It's only recovered from the signature if the
+/// sender isn't stored, e.g. because senders are pruned.
+fn with_stored_sender<P: TransactionsProvider>(
+    provider: &P,
+    hash: TxHash,
+    tx: ProviderTx<P>,
+) -> Result<Recovered<ProviderTx<P>>, EthApiError> {
+    let sender = match provider.transaction_id(hash)? {
+        Some(id) => provider.transaction_sender(id)?,
+        None => None,
+    };
+    match sender {
+        Some(sender) => Ok(Recovered::new_unchecked(tx, sender)),
+        // Note: we assume this transaction is valid, because it's mined (or part of pending
+        // block). We don't need to check for pre EIP-2 because this transaction could be pre-EIP-2.
+        None => tx.into_recovered_unchecked().map_err(|_| EthApiError::InvalidTransactionSignature),
+    }
+}
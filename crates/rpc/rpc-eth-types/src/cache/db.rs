@@ -145,6 +145,42 @@ impl HashedPostStateProvider for StateProviderTraitObjWrapper<'_> {
     }
 }
 
+impl reth_storage_api::HashedStateRangeProvider for StateProviderTraitObjWrapper<'_> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> reth_errors::ProviderResult<reth_trie::HashedAccountRange> {
+        self.0.hashed_account_range(hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> reth_errors::ProviderResult<reth_trie::HashedStorageRange> {
+        self.0.hashed_storage_range(hashed_state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        hashed_addresses: &alloy_primitives::map::B256Set,
+    ) -> reth_errors::ProviderResult<alloy_primitives::map::B256Map<Address>> {
+        self.0.account_preimages(hashed_addresses)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &alloy_primitives::map::B256Set,
+    ) -> reth_errors::ProviderResult<alloy_primitives::map::B256Map<B256>> {
+        self.0.storage_preimages(address, hashed_slots)
+    }
+}
+
 impl StateProvider for StateProviderTraitObjWrapper<'_> {
     fn storage(
         &self,
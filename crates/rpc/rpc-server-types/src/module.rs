@@ -273,6 +273,8 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `anvil_`, `hardhat_` and `evm_` testing modules, only available on dev nodes
+    Anvil,
 }
 
 // === impl RethRpcModule ===
@@ -324,6 +326,7 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "anvil" => Self::Anvil,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
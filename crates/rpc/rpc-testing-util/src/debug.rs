@@ -77,7 +77,7 @@ pub trait DebugApiExt {
 
 impl<T> DebugApiExt for T
 where
-    T: EthApiClient<Transaction, Block, Receipt, Header> + DebugApiClient + Sync,
+    T: EthApiClient<Transaction, Block, Receipt, Header> + DebugApiClient<Block> + Sync,
 {
     type Provider = T;
 
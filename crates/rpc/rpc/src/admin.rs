@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -8,11 +8,14 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
+use reth_eth_wire_types::TracedMessage;
 use reth_network_api::{NetworkInfo, Peers};
-use reth_network_peers::{id2pk, AnyNode, NodeRecord};
+use reth_network_peers::{id2pk, AnyNode, NodeRecord, PeerId};
 use reth_network_types::PeerKind;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_storage_api::{DatabaseSnapshotProvider, SnapshotManifest, SnapshotOptions};
+use tokio::sync::oneshot;
 
 /// `admin` API implementation.
 ///
@@ -22,12 +25,23 @@ pub struct AdminApi<N, ChainSpec> {
     network: N,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// Takes snapshots of the database, if supported by the node.
+    snapshots: Option<Arc<dyn DatabaseSnapshotProvider>>,
 }
 
 impl<N, ChainSpec> AdminApi<N, ChainSpec> {
     /// Creates a new instance of `AdminApi`.
     pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+        Self { network, chain_spec, snapshots: None }
+    }
+
+    /// Sets the provider used to take database snapshots for `admin_snapshot`.
+    pub fn with_snapshot_provider(
+        mut self,
+        provider: impl DatabaseSnapshotProvider + 'static,
+    ) -> Self {
+        self.snapshots = Some(Arc::new(provider));
+        self
     }
 }
 
@@ -94,7 +108,13 @@ where
                             version: peer.status.version as u64,
                         })),
                         snap: None,
-                        other: Default::default(),
+                        // requests the peer made to us, and how much they cost us to serve
+                        other: peer
+                            .request_usage
+                            .and_then(|usage| serde_json::to_value(usage).ok())
+                            .map(|usage| ("ethRequests".to_string(), usage))
+                            .into_iter()
+                            .collect(),
                     },
                 })
             }
@@ -103,6 +123,41 @@ where
         Ok(infos)
     }
 
+    /// Handler for `admin_peerMessages`
+    async fn peer_messages(
+        &self,
+        peer_id: PeerId,
+        trace: Option<bool>,
+    ) -> RpcResult<Option<Vec<TracedMessage>>> {
+        if let Some(enabled) = trace {
+            self.network.set_message_tracing(peer_id, enabled);
+        }
+        self.network.peer_messages(peer_id).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_snapshot`
+    async fn snapshot(
+        &self,
+        destination: PathBuf,
+        compact: Option<bool>,
+    ) -> RpcResult<SnapshotManifest> {
+        let Some(snapshots) = self.snapshots.clone() else {
+            return Err(internal_rpc_err("database snapshots are not supported by this node"))
+        };
+        let options = SnapshotOptions::default().with_compact(compact.unwrap_or_default());
+
+        // snapshots can take a long time, so they're taken on a dedicated thread
+        let (tx, rx) = oneshot::channel();
+        std::thread::Builder::new()
+            .name("admin-snapshot".to_string())
+            .spawn(move || {
+                let _ = tx.send(snapshots.snapshot(&destination, options));
+            })
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        rx.await.map_err(|err| internal_rpc_err(err.to_string()))?.to_rpc_result()
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
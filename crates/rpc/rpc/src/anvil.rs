@@ -0,0 +1,905 @@
+use alloy_consensus::{BlockHeader, SignableTransaction, TxEnvelope};
+use alloy_dyn_abi::TypedData;
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip4895::Withdrawals,
+    BlockId,
+};
+use alloy_network::Ethereum;
+use alloy_primitives::{map::HashMap, Address, Bytes, Signature, B256, U256};
+use alloy_rpc_types_anvil::{
+    Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
+};
+use alloy_rpc_types_eth::{Block, TransactionRequest};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use parking_lot::{Mutex, RwLock};
+use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
+use reth_engine_primitives::{LocalMinerError, LocalMinerHandle};
+use reth_evm::{
+    execute::{BlockBuilder, BlockBuilderOutcome},
+    ConfigureEvm, NextBlockEnvAttributes,
+};
+use reth_execution_types::ExecutionOutcome;
+use reth_network_api::NetworkInfo;
+use reth_primitives_traits::{Block as _, NodePrimitives, RecoveredBlock};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{
+        states::{bundle_state::BundleRetention, reverts::AccountInfoRevert},
+        BundleState, State,
+    },
+};
+use reth_rpc_api::{AnvilApiServer, GanacheApiServer, HardhatApiServer};
+use reth_rpc_eth_api::{
+    helpers::{signer, EthBlocks, EthFees, EthSigner, EthTransactions, FullEthApi},
+    EthApiTypes, RpcNodeCore,
+};
+use reth_rpc_eth_types::{EthApiError, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{BlockNumReader, BlockReaderIdExt, HeaderProvider, StateProviderFactory};
+use reth_transaction_pool::TransactionPool;
+use revm::{
+    bytecode::Bytecode,
+    state::{Account, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The signature of transactions sent from impersonated accounts, same as anvil.
+///
+/// The signature recovers to an unrelated address, the actual sender is only known to the pool
+/// and the block that includes the transaction.
+const IMPERSONATED_SIGNATURE: Signature =
+    Signature::new(U256::from_limbs([1, 0, 0, 0]), U256::from_limbs([1, 0, 0, 0]), false);
+
+/// `anvil`, `hardhat` and `evm` API implementation for dev nodes.
+///
+/// This type provides the functionality for handling the testing related requests of development
+/// tools like foundry and hardhat. Blocks are mined through the [`LocalMinerHandle`] of the dev
+/// node. State modifications replace the latest block with a block that has the same transactions
+/// and includes the modification in its state, so that no block is mined for them.
+pub struct AnvilApi<Eth, N: NodePrimitives> {
+    inner: Arc<AnvilApiInner<Eth, N>>,
+}
+
+impl<Eth, N> AnvilApi<Eth, N>
+where
+    Eth: EthTransactions,
+    N: NodePrimitives,
+{
+    /// Create a new instance of the [`AnvilApi`].
+    ///
+    /// This registers a signer for impersonated accounts with the given `Eth` API.
+    pub fn new(eth_api: Eth, miner: LocalMinerHandle<N>) -> Self {
+        let impersonated = Arc::new(RwLock::new(ImpersonatedAccounts::default()));
+        EthTransactions::signers(&eth_api)
+            .write()
+            .push(Box::new(ImpersonationSigner(impersonated.clone())));
+
+        let inner = Arc::new(AnvilApiInner {
+            eth_api,
+            miner,
+            impersonated,
+            snapshots: Mutex::new(Snapshots::default()),
+            instance_id: B256::random(),
+        });
+        Self { inner }
+    }
+}
+
+impl<Eth, N: NodePrimitives> AnvilApi<Eth, N> {
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Access the handle to the local miner.
+    pub fn miner(&self) -> &LocalMinerHandle<N> {
+        &self.inner.miner
+    }
+
+    /// Starts or stops impersonating the given account.
+    fn set_impersonated(&self, address: Address, impersonated: bool) {
+        let mut accounts = self.inner.impersonated.write();
+        if impersonated {
+            accounts.accounts.insert(address);
+        } else {
+            accounts.accounts.remove(&address);
+        }
+    }
+}
+
+impl<Eth, N> AnvilApi<Eth, N>
+where
+    Eth: FullEthApi
+        + RpcNodeCore<
+            Provider: HeaderProvider<Header = alloy_consensus::Header>
+                          + StateProviderFactory
+                          + ChainSpecProvider<ChainSpec: EthereumHardforks + Hardforks>
+                          + 'static,
+            Evm: ConfigureEvm<Primitives = N, NextBlockEnvCtx = NextBlockEnvAttributes> + 'static,
+        > + EthApiTypes<NetworkTypes = Ethereum>
+        + 'static,
+    N: NodePrimitives<BlockHeader = alloy_consensus::Header>,
+{
+    /// Applies the given change to the account on top of the state of the latest block.
+    ///
+    /// The latest block is replaced by a block with the change applied. If the latest block
+    /// wasn't built by the miner, e.g. the genesis block, the change is committed as a new block
+    /// without transactions on top of it instead.
+    async fn modify_account(&self, address: Address, change: AccountChange) -> RpcResult<()> {
+        let provider = self.eth_api().provider().clone();
+        let replaced = self
+            .miner()
+            .replace_head({
+                let provider = provider.clone();
+                let change = change.clone();
+                move |head| rebuild_modified_block(&provider, head, address, change)
+            })
+            .await
+            .map_err(miner_error)?;
+        if replaced.is_some() {
+            return Ok(())
+        }
+
+        let evm_config = self.eth_api().evm_config().clone();
+        self.miner()
+            .insert_block(move |parent, timestamp| {
+                build_modified_block(&provider, &evm_config, parent, timestamp, address, change)
+            })
+            .await
+            .map_err(miner_error)?;
+        Ok(())
+    }
+
+    /// Mines the blocks requested by the given options and returns their hashes.
+    async fn mine_with_options(&self, opts: Option<MineOptions>) -> RpcResult<Vec<B256>> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks.unwrap_or(1)),
+            MineOptions::Timestamp(timestamp) => (timestamp, 1),
+        };
+        if let Some(timestamp) = timestamp {
+            self.miner().set_next_block_timestamp(timestamp).await.map_err(miner_error)?;
+        }
+        self.miner().mine(blocks, None).await.map_err(miner_error)
+    }
+
+    /// Mines the given number of blocks, with timestamps `interval` seconds apart.
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks.map_or(1, |blocks| blocks.saturating_to());
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.miner().mine(blocks, interval).await.map_err(miner_error)?;
+        Ok(())
+    }
+
+    /// Removes the transaction from the pool and returns whether it was removed.
+    fn drop_transaction(&self, tx_hash: B256) -> bool {
+        !self.eth_api().pool().remove_transactions(vec![tx_hash]).is_empty()
+    }
+
+    /// Records the current head of the chain and returns the id of the snapshot.
+    fn snapshot(&self) -> RpcResult<U256> {
+        let chain_info = self.eth_api().provider().chain_info().map_err(EthApiError::from)?;
+
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += U256::from(1);
+        snapshots.by_id.insert(id, (chain_info.best_number, chain_info.best_hash));
+        Ok(id)
+    }
+
+    /// Reverts the chain to the given snapshot, removing it and all snapshots taken after it.
+    ///
+    /// Returns `false` if the snapshot does not exist.
+    async fn revert(&self, id: U256) -> RpcResult<bool> {
+        let snapshot = {
+            let mut snapshots = self.inner.snapshots.lock();
+            let Some(snapshot) = snapshots.by_id.get(&id).copied() else { return Ok(false) };
+            snapshots.by_id.split_off(&id);
+            snapshot
+        };
+        self.miner().revert(snapshot.1).await.map_err(miner_error)?;
+        Ok(true)
+    }
+
+    /// Returns metadata about the node.
+    async fn metadata(&self) -> RpcResult<Metadata> {
+        let chain_info = self.eth_api().provider().chain_info().map_err(EthApiError::from)?;
+        let client_version = self
+            .eth_api()
+            .network()
+            .network_status()
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+            .client_version;
+
+        Ok(Metadata {
+            client_version,
+            chain_id: self.eth_api().provider().chain_spec().chain().id(),
+            instance_id: self.inner.instance_id,
+            latest_block_number: chain_info.best_number,
+            latest_block_hash: chain_info.best_hash,
+            forked_network: None,
+            snapshots: self.inner.snapshots.lock().by_id.clone(),
+        })
+    }
+}
+
+impl<Eth, N: NodePrimitives> Clone for AnvilApi<Eth, N> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Eth, N: NodePrimitives> std::fmt::Debug for AnvilApi<Eth, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<Eth, N> AnvilApiServer for AnvilApi<Eth, N>
+where
+    Eth: FullEthApi
+        + RpcNodeCore<
+            Provider: HeaderProvider<Header = alloy_consensus::Header>
+                          + StateProviderFactory
+                          + ChainSpecProvider<ChainSpec: EthereumHardforks + Hardforks>
+                          + 'static,
+            Evm: ConfigureEvm<Primitives = N, NextBlockEnvCtx = NextBlockEnvAttributes> + 'static,
+        > + EthApiTypes<NetworkTypes = Ethereum>
+        + 'static,
+    N: NodePrimitives<BlockHeader = alloy_consensus::Header>,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.set_impersonated(address, true);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.set_impersonated(address, false);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.impersonated.write().auto = enabled;
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.miner().automine().await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.miner().set_automine(enabled).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        let interval = (interval > 0).then(|| Duration::from_secs(interval));
+        self.miner().set_interval_mining(interval).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        Ok(self.drop_transaction(tx_hash).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_reset is not supported").into())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Balance(balance)).await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Code(code)).await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Nonce(nonce.saturating_to())).await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        self.modify_account(address, AccountChange::Storage(slot, value.into())).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setCoinbase is not supported").into())
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId is not supported").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled is not supported").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setMinGasPrice is not supported").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        self.miner().set_time(timestamp).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        Err(EthApiError::Unsupported("anvil_dumpState is not supported").into())
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, _state: Bytes) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_loadState is not supported").into())
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        let provider = self.eth_api().provider();
+        let header = provider
+            .latest_header()
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+        let chain_spec = provider.chain_spec();
+        let hard_fork = chain_spec
+            .forks_iter()
+            .filter(|(_, condition)| {
+                condition.active_at_timestamp_or_number(header.timestamp(), header.number())
+            })
+            .last()
+            .map(|(fork, _)| fork.name().to_string())
+            .unwrap_or_default();
+        let gas_price = EthFees::gas_price(self.eth_api()).await.map_err(Into::into)?;
+
+        Ok(NodeInfo {
+            current_block_number: header.number(),
+            current_block_timestamp: header.timestamp(),
+            current_block_hash: header.hash(),
+            hard_fork,
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee: header.base_fee_per_gas().unwrap_or_default() as u128,
+                chain_id: chain_spec.chain().id(),
+                gas_limit: header.gas_limit(),
+                gas_price: gas_price.saturating_to(),
+            },
+            fork_config: NodeForkConfig::default(),
+        })
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        self.metadata().await
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        self.snapshot()
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        self.revert(id).await
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.miner().increase_time(seconds.saturating_to()).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.miner().set_next_block_timestamp(seconds).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_setBlockGasLimit is not supported").into())
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.miner().set_block_timestamp_interval(Some(seconds)).await.map_err(miner_error)?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        self.miner().set_block_timestamp_interval(None).await.map_err(miner_error)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        let hashes = self.mine_with_options(opts).await?;
+
+        let mut blocks = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let block = EthBlocks::rpc_block(self.eth_api(), hash.into(), true)
+                .await
+                .map_err(Into::into)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_enableTraces is not supported").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.eth_api().pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth, N> HardhatApiServer for AnvilApi<Eth, N>
+where
+    Eth: FullEthApi
+        + RpcNodeCore<
+            Provider: HeaderProvider<Header = alloy_consensus::Header>
+                          + StateProviderFactory
+                          + ChainSpecProvider<ChainSpec: EthereumHardforks + Hardforks>
+                          + 'static,
+            Evm: ConfigureEvm<Primitives = N, NextBlockEnvCtx = NextBlockEnvAttributes> + 'static,
+        > + EthApiTypes<NetworkTypes = Ethereum>
+        + 'static,
+    N: NodePrimitives<BlockHeader = alloy_consensus::Header>,
+{
+    /// Handler for `hardhat_dropTransaction`
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        Ok(self.drop_transaction(tx_hash))
+    }
+
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.set_impersonated(address, true);
+        Ok(())
+    }
+
+    /// Handler for `hardhat_getAutomine`
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        self.miner().automine().await.map_err(miner_error)
+    }
+
+    /// Handler for `hardhat_metadata`
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        self.metadata().await
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    /// Handler for `hardhat_reset`
+    async fn hardhat_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_reset is not supported").into())
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Balance(balance)).await
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Code(code)).await
+    }
+
+    /// Handler for `hardhat_setCoinbase`
+    async fn hardhat_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setCoinbase is not supported").into())
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`
+    async fn hardhat_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setLoggingEnabled is not supported").into())
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`
+    async fn hardhat_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setMinGasPrice is not supported").into())
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        _base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    /// Handler for `hardhat_setPrevRandao`
+    async fn hardhat_set_prev_randao(&self, _prev_randao: B256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setPrevRandao is not supported").into())
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Nonce(nonce.saturating_to())).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        self.modify_account(address, AccountChange::Storage(slot, value.into())).await
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.set_impersonated(address, false);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth, N> GanacheApiServer for AnvilApi<Eth, N>
+where
+    Eth: FullEthApi
+        + RpcNodeCore<
+            Provider: HeaderProvider<Header = alloy_consensus::Header>
+                          + StateProviderFactory
+                          + ChainSpecProvider<ChainSpec: EthereumHardforks + Hardforks>
+                          + 'static,
+            Evm: ConfigureEvm<Primitives = N, NextBlockEnvCtx = NextBlockEnvAttributes> + 'static,
+        > + EthApiTypes<NetworkTypes = Ethereum>
+        + 'static,
+    N: NodePrimitives<BlockHeader = alloy_consensus::Header>,
+{
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.miner().increase_time(seconds.saturating_to()).await.map_err(miner_error)
+    }
+
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        self.mine_with_options(opts).await?;
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        self.revert(snapshot_id).await
+    }
+
+    /// Handler for `evm_setTime`
+    async fn evm_set_time(&self, timestamp: u64) -> RpcResult<bool> {
+        self.miner().set_time(timestamp).await.map_err(miner_error)?;
+        Ok(true)
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        self.snapshot()
+    }
+}
+
+struct AnvilApiInner<Eth, N: NodePrimitives> {
+    /// The implementation of `eth` API
+    eth_api: Eth,
+    /// Handle to the local miner of the dev node.
+    miner: LocalMinerHandle<N>,
+    /// The accounts transactions can be sent from without a key.
+    impersonated: Arc<RwLock<ImpersonatedAccounts>>,
+    /// The snapshots taken with `evm_snapshot`.
+    snapshots: Mutex<Snapshots>,
+    /// Random identifier of this instance, reported by `anvil_metadata`.
+    instance_id: B256,
+}
+
+/// The snapshots of the chain, by id.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// The id of the next snapshot.
+    next_id: U256,
+    /// The number and hash of the head block when the snapshot was taken.
+    by_id: BTreeMap<U256, (u64, B256)>,
+}
+
+/// A modification of an account.
+#[derive(Debug, Clone)]
+enum AccountChange {
+    /// Sets the balance of the account.
+    Balance(U256),
+    /// Sets the nonce of the account.
+    Nonce(u64),
+    /// Sets the code of the account.
+    Code(Bytes),
+    /// Sets the value of a storage slot of the account.
+    Storage(U256, U256),
+}
+
+/// Builds a block without transactions on top of the given parent, that applies the change to the
+/// account before any system calls.
+fn build_modified_block<P, E>(
+    provider: &P,
+    evm_config: &E,
+    parent: B256,
+    timestamp: u64,
+    address: Address,
+    change: AccountChange,
+) -> Result<ExecutedBlockWithTrieUpdates<E::Primitives>, LocalMinerError>
+where
+    P: BlockReaderIdExt<Header = <E::Primitives as NodePrimitives>::BlockHeader>
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    E: ConfigureEvm<NextBlockEnvCtx = NextBlockEnvAttributes>,
+{
+    let parent = provider
+        .sealed_header_by_hash(parent)
+        .map_err(LocalMinerError::internal)?
+        .ok_or(LocalMinerError::UnknownBlock(parent))?;
+    let state_provider =
+        provider.state_by_block_hash(parent.hash()).map_err(LocalMinerError::internal)?;
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(&state_provider))
+        .with_bundle_update()
+        .build();
+
+    // apply the change as a state transition of the new block
+    apply_account_change(&mut db, address, change).map_err(LocalMinerError::internal)?;
+
+    let chain_spec = provider.chain_spec();
+    let mut builder = evm_config
+        .builder_for_next_block(
+            &mut db,
+            &parent,
+            NextBlockEnvAttributes {
+                timestamp,
+                suggested_fee_recipient: Address::ZERO,
+                prev_randao: B256::ZERO,
+                gas_limit: parent.gas_limit(),
+                parent_beacon_block_root: chain_spec
+                    .is_cancun_active_at_timestamp(timestamp)
+                    .then_some(B256::ZERO),
+                withdrawals: chain_spec
+                    .is_shanghai_active_at_timestamp(timestamp)
+                    .then(Withdrawals::default),
+            },
+        )
+        .map_err(LocalMinerError::internal)?;
+    builder.apply_pre_execution_changes().map_err(LocalMinerError::internal)?;
+
+    let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } =
+        builder.finish(&state_provider).map_err(LocalMinerError::internal)?;
+
+    let execution_outcome = ExecutionOutcome::new(
+        db.take_bundle(),
+        vec![execution_result.receipts],
+        block.header().number(),
+        vec![execution_result.requests],
+    );
+
+    Ok(ExecutedBlockWithTrieUpdates {
+        block: ExecutedBlock {
+            recovered_block: Arc::new(block),
+            execution_output: Arc::new(execution_outcome),
+            hashed_state: Arc::new(hashed_state),
+        },
+        trie: Arc::new(trie_updates),
+    })
+}
+
+/// Rebuilds the given head block with the change to the account applied on top of its state.
+///
+/// The rebuilt block has the same transactions and receipts as the head, only its state root
+/// differs.
+fn rebuild_modified_block<P, N>(
+    provider: &P,
+    head: ExecutedBlockWithTrieUpdates<N>,
+    address: Address,
+    change: AccountChange,
+) -> Result<ExecutedBlockWithTrieUpdates<N>, LocalMinerError>
+where
+    P: StateProviderFactory,
+    N: NodePrimitives<BlockHeader = alloy_consensus::Header>,
+{
+    let head_block = head.recovered_block();
+    let state_provider = provider
+        .state_by_block_hash(head_block.parent_hash())
+        .map_err(LocalMinerError::internal)?;
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(&state_provider))
+        .with_bundle_prestate(head.execution_output.bundle.clone())
+        .with_bundle_update()
+        .build();
+
+    apply_account_change(&mut db, address, change).map_err(LocalMinerError::internal)?;
+    db.merge_transitions(BundleRetention::Reverts);
+    let mut bundle = db.take_bundle();
+    merge_last_reverts(&mut bundle);
+
+    let hashed_state = state_provider.hashed_post_state(&bundle);
+    let (state_root, trie_updates) = state_provider
+        .state_root_with_updates(hashed_state.clone())
+        .map_err(LocalMinerError::internal)?;
+
+    let mut header = head_block.clone_header();
+    header.state_root = state_root;
+    let block = RecoveredBlock::new_unhashed(
+        N::Block::new(header, head_block.body().clone()),
+        head_block.senders().to_vec(),
+    );
+
+    Ok(ExecutedBlockWithTrieUpdates {
+        block: ExecutedBlock {
+            recovered_block: Arc::new(block),
+            execution_output: Arc::new(ExecutionOutcome {
+                bundle,
+                ..head.execution_output.as_ref().clone()
+            }),
+            hashed_state: Arc::new(hashed_state),
+        },
+        trie: Arc::new(trie_updates),
+    })
+}
+
+/// Merges the reverts of the last transition of the bundle into the reverts of the block before
+/// it, so that the bundle still describes a single block.
+///
+/// The reverts of the block restore the state before the block, they are only extended by the
+/// accounts and slots that the block didn't modify.
+fn merge_last_reverts(bundle: &mut BundleState) {
+    if bundle.reverts.len() < 2 {
+        return
+    }
+    let last = bundle.reverts.pop().expect("not empty");
+    let block_reverts = bundle.reverts.last_mut().expect("not empty");
+    for (address, revert) in last {
+        match block_reverts.iter_mut().find(|(block_address, _)| *block_address == address) {
+            Some((_, block_revert)) => {
+                if matches!(block_revert.account, AccountInfoRevert::DoNothing) {
+                    block_revert.account = revert.account;
+                }
+                for (slot, value) in revert.storage {
+                    block_revert.storage.entry(slot).or_insert(value);
+                }
+            }
+            None => block_reverts.push((address, revert)),
+        }
+    }
+}
+
+/// Applies the change to the account and commits it to the database.
+fn apply_account_change<DB: Database + DatabaseCommit>(
+    db: &mut DB,
+    address: Address,
+    change: AccountChange,
+) -> Result<(), DB::Error> {
+    let info = db.basic(address)?.unwrap_or_default();
+    let mut account = Account::from(info);
+    match change {
+        AccountChange::Balance(balance) => account.info.balance = balance,
+        AccountChange::Nonce(nonce) => account.info.nonce = nonce,
+        AccountChange::Code(code) => {
+            let code = Bytecode::new_raw(code);
+            account.info.code_hash = code.hash_slow();
+            account.info.code = Some(code);
+        }
+        AccountChange::Storage(slot, value) => {
+            let original = db.storage(address, slot)?;
+            account.storage.insert(slot, EvmStorageSlot::new_changed(original, value));
+        }
+    }
+    account.mark_touch();
+    db.commit(HashMap::from_iter([(address, account)]));
+    Ok(())
+}
+
+/// Converts a [`LocalMinerError`] into an RPC error.
+fn miner_error(err: LocalMinerError) -> ErrorObject<'static> {
+    match err {
+        LocalMinerError::UnknownBlock(_) |
+        LocalMinerError::TimestampTooLow { .. } |
+        LocalMinerError::TooManyBlocks(_) |
+        LocalMinerError::AutomineUnavailable => invalid_params_rpc_err(err.to_string()),
+        LocalMinerError::MinerUnavailable | LocalMinerError::Internal(_) => {
+            internal_rpc_err(err.to_string())
+        }
+    }
+}
+
+/// The accounts that are impersonated.
+#[derive(Debug, Default)]
+struct ImpersonatedAccounts {
+    /// The explicitly impersonated accounts.
+    accounts: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    auto: bool,
+}
+
+/// Signs transactions of impersonated accounts with a placeholder signature.
+///
+/// The pool and the payload builder use the sender the transaction was submitted with, so these
+/// transactions are executed as if they were signed by the impersonated account.
+#[derive(Debug, Clone)]
+struct ImpersonationSigner(Arc<RwLock<ImpersonatedAccounts>>);
+
+#[async_trait]
+impl<T: Decodable2718> EthSigner<T> for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.0.read().accounts.iter().copied().collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        let accounts = self.0.read();
+        accounts.auto || accounts.accounts.contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> signer::Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TransactionRequest,
+        _address: &Address,
+    ) -> signer::Result<T> {
+        let tx = request.build_typed_tx().map_err(|_| SignError::InvalidTransactionRequest)?;
+        let envelope = TxEnvelope::from(tx.into_signed(IMPERSONATED_SIGNATURE));
+
+        // decode transaction into signed transaction type
+        let encoded = envelope.encoded_2718();
+        T::decode_2718(&mut encoded.as_ref()).map_err(|_| SignError::InvalidTransactionRequest)
+    }
+
+    fn sign_typed_data(
+        &self,
+        _address: Address,
+        _payload: &TypedData,
+    ) -> signer::Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
@@ -1,21 +1,26 @@
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{
+    constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY},
+    BlockHeader,
+};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{hex, keccak256, map::B256Map, Address, BlockNumber, Bytes, B256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, BlockTransactionsKind,
+    Bundle, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
-    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-    NoopFrame, TraceResult,
+    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions,
+    GethDefaultTracingOptions, GethTrace, NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_bad_blocks::BadBlockRegistry;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_errors::{ProviderError, RethError};
 use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     ConfigureEvm, EvmEnvFor, TxEnvFor,
@@ -23,35 +28,60 @@ use reth_evm::{
 use reth_primitives_traits::{
     Block as _, BlockBody, NodePrimitives, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
 };
+use reth_prune_types::PruneSegment;
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, BadBlock, DebugApiServer, DumpAccount, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    EthApiTypes, FromEthApiError, FullEthApiTypes, RpcBlock, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS, result::internal_rpc_err, ToRpcResult,
+};
+use reth_rpc_types_compat::block::from_block;
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProvider, StateProviderFactory, TransactionVariant,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReaderIdExt, ChangeSetReader,
+    HashedPostStateProvider, HashedStateRangeProvider, HeaderProvider, ProviderBlock,
+    PruneCheckpointReader, ReceiptProviderIdExt, StateProofProvider, StateProvider,
+    StateProviderFactory, StateRootProvider, StorageChangeSetReader, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
+use reth_trie_common::HashedPostState;
+use revm::{
+    context_interface::Transaction, inspector::inspectors::TracerEip3155, state::EvmState,
+    Database, DatabaseCommit,
+};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    sync::Arc,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::debug;
+
+/// The maximum number of accounts returned by `debug_accountRange`, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth, BlockExecutor> {
     inner: Arc<DebugApiInner<Eth, BlockExecutor>>,
+    /// The registry of blocks that failed validation.
+    bad_block_registry: BadBlockRegistry,
+    /// The maximum number of blocks a `debug_getModifiedAccounts*` request may span.
+    max_range_blocks: u64,
 }
 
 // === impl DebugApi ===
@@ -64,7 +94,25 @@ impl<Eth, BlockExecutor> DebugApi<Eth, BlockExecutor> {
         block_executor: BlockExecutor,
     ) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, block_executor });
-        Self { inner }
+        Self {
+            inner,
+            bad_block_registry: BadBlockRegistry::default(),
+            max_range_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+        }
+    }
+
+    /// Configures the registry of blocks that failed validation, which is served by
+    /// `debug_getBadBlocks` and the related methods.
+    pub fn with_bad_block_registry(mut self, bad_block_registry: BadBlockRegistry) -> Self {
+        self.bad_block_registry = bad_block_registry;
+        self
+    }
+
+    /// Configures the maximum number of blocks a `debug_getModifiedAccounts*` request may span,
+    /// which defaults to the limit of `trace_filter`.
+    pub const fn with_max_range_blocks(mut self, max_range_blocks: u64) -> Self {
+        self.max_range_blocks = max_range_blocks;
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -154,12 +202,21 @@ where
         rlp_block: Bytes,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self.recover_raw_block(&rlp_block)?;
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        self.trace_block(Arc::new(block), evm_env, opts).await
+    }
+
+    /// Decodes the rlp encoded block and recovers the senders of its transactions.
+    fn recover_raw_block(
+        &self,
+        rlp_block: &Bytes,
+    ) -> Result<RecoveredBlock<ProviderBlock<Eth::Provider>>, Eth::Error> {
         let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp_block.as_ref())
             .map_err(BlockError::RlpDecodeRawBlock)
             .map_err(Eth::Error::from_eth_err)?;
 
-        let evm_env = self.eth_api().evm_config().evm_env(block.header());
-
         // Depending on EIP-2 we need to recover the transactions differently
         let senders =
             if self.provider().chain_spec().is_homestead_active_at_block(block.header().number()) {
@@ -182,7 +239,53 @@ where
                     .collect()
             };
 
-        self.trace_block(Arc::new(block.into_recovered_with_signers(senders)), evm_env, opts).await
+        Ok(block.into_recovered_with_signers(senders))
+    }
+
+    /// Replays the block and writes the [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace
+    /// of every transaction into a separate file in the temp directory.
+    ///
+    /// Of the given options, only `enableMemory` is supported.
+    ///
+    /// Returns the paths of the written files.
+    async fn standard_trace_block_to_files(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        evm_env: EvmEnvFor<Eth::Evm>,
+        opts: GethDefaultTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut files = Vec::with_capacity(block.body().transactions().len());
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                let dir = std::env::temp_dir();
+                for (index, tx) in block.transactions_recovered().enumerate() {
+                    let path =
+                        dir.join(format!("block_{}-{index}-{}.jsonl", block.hash(), tx.tx_hash()));
+                    let file = File::create(&path).map_err(|err| {
+                        Eth::Error::from_eth_err(EthApiError::Internal(RethError::other(err)))
+                    })?;
+
+                    let mut inspector = TracerEip3155::buffered(file);
+                    if opts.enable_memory.unwrap_or_default() {
+                        inspector = inspector.with_memory();
+                    }
+
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let (res, _) =
+                        this.eth_api().inspect(&mut db, evm_env.clone(), tx_env, &mut inspector)?;
+                    db.commit(res.state);
+
+                    files.push(path.display().to_string());
+                }
+
+                Ok(files)
+            })
+            .await
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -702,6 +805,331 @@ where
             .map(|b| b.original_bytes()))
     }
 
+    /// Returns all accounts that were modified in the blocks after `start` up to and including
+    /// `end`, sorted by address.
+    ///
+    /// If `end` is not given, returns the accounts modified in the `start` block.
+    ///
+    /// An account counts as modified if its nonce, balance, code hash or storage changed, so this
+    /// is answered from the account and storage changesets of the blocks. Returns an error if the
+    /// range spans more than the configured maximum number of blocks, or if the changesets of any
+    /// block in the range have been pruned.
+    pub async fn debug_get_modified_accounts(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> Result<Vec<Address>, Eth::Error>
+    where
+        Eth::Provider: ChangeSetReader + StorageChangeSetReader + PruneCheckpointReader,
+    {
+        let (start, end) = match end {
+            Some(end) => (start, end),
+            None => (start.saturating_sub(1), start),
+        };
+        if start >= end {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({end})"
+            ))))
+        }
+        if end - start > self.max_range_blocks {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {} blocks",
+                self.max_range_blocks
+            ))))
+        }
+
+        self.eth_api()
+            .spawn_blocking_io(move |eth| {
+                let provider = eth.provider();
+                for number in [start, end] {
+                    if provider.block_hash(number).map_err(Eth::Error::from_eth_err)?.is_none() {
+                        return Err(Eth::Error::from_eth_err(EthApiError::HeaderNotFound(
+                            number.into(),
+                        )))
+                    }
+                }
+
+                // changesets are pruned together with the history indices, up to and including
+                // the block of the prune checkpoint
+                for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+                    let pruned_until = provider
+                        .get_prune_checkpoint(segment)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .and_then(|checkpoint| checkpoint.block_number);
+                    if pruned_until.is_some_and(|pruned_until| pruned_until > start) {
+                        return Err(Eth::Error::from_eth_err(ProviderError::StateAtBlockPruned(
+                            start + 1,
+                        )))
+                    }
+                }
+
+                let mut accounts = BTreeSet::new();
+                for number in start + 1..=end {
+                    let account_changes = provider
+                        .account_block_changeset(number)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(account_changes.into_iter().map(|change| change.address));
+
+                    let storage_changes =
+                        provider.storage_changeset(number).map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(storage_changes.into_iter().map(|(key, _)| key.address()));
+                }
+
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Re-executes the block with the given hash and returns the state root after each of its
+    /// transactions.
+    ///
+    /// The block is looked up in the recorded bad blocks if it's not known to the node.
+    pub async fn debug_intermediate_roots(&self, hash: B256) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().recovered_block(hash.into()).await? {
+            Some(block) => block,
+            None => {
+                let bad_block = self
+                    .bad_block_registry
+                    .get(hash)
+                    .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+                Arc::new(self.recover_raw_block(&bad_block.block)?)
+            }
+        };
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state_provider))
+                    .with_bundle_update()
+                    .build();
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                let mut roots = Vec::with_capacity(block.body().transaction_count());
+                for tx in block.transactions_recovered() {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let (res, _) = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                    db.commit(res.state);
+                    db.merge_transitions(BundleRetention::PlainState);
+
+                    // compute the root of the state changes so far on top of the parent state
+                    let hashed_state = state_provider.hashed_post_state(&db.bundle_state);
+                    let root =
+                        state_provider.state_root(hashed_state).map_err(EthApiError::from)?;
+                    roots.push(root);
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns up to `max_results` accounts of the state at the given block, starting at the
+    /// hashed address `start`, in the format of geth's state dump.
+    ///
+    /// Reth doesn't keep the preimages of hashed addresses and slots, so they are recovered from
+    /// the plain state where possible. Accounts with an unknown address are keyed by their hashed
+    /// address and only returned if `incompletes` is set, same as geth without the preimage
+    /// store. Storage slots with an unknown preimage are left out of the dump.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        let start = hashed_key_start(&start)?;
+        let limit = match usize::try_from(max_results) {
+            Ok(limit) if limit > 0 && limit <= ACCOUNT_RANGE_MAX_RESULTS => limit,
+            _ => ACCOUNT_RANGE_MAX_RESULTS,
+        };
+        let header = self
+            .provider()
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let root = format!("{:x}", header.state_root());
+
+        self.eth_api()
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let range = state
+                    .hashed_account_range(HashedPostState::default(), start, limit)
+                    .map_err(EthApiError::from)?;
+                let hashed_addresses =
+                    range.accounts.iter().map(|entry| entry.hashed_address).collect();
+                let addresses =
+                    state.account_preimages(&hashed_addresses).map_err(EthApiError::from)?;
+
+                let mut accounts = BTreeMap::new();
+                for entry in range.accounts {
+                    let address = addresses.get(&entry.hashed_address).copied();
+                    if address.is_none() && !incompletes {
+                        continue
+                    }
+
+                    let code_hash = entry.account.get_bytecode_hash();
+                    let code = if nocode || code_hash == KECCAK_EMPTY {
+                        None
+                    } else {
+                        state
+                            .bytecode_by_hash(&code_hash)
+                            .map_err(EthApiError::from)?
+                            .map(|code| code.original_bytes())
+                    };
+
+                    // the slots can only be recovered from the plain state of a known address
+                    let storage = match address {
+                        Some(address) if !nostorage && entry.storage_root != EMPTY_ROOT_HASH => {
+                            let slots = state
+                                .hashed_storage_range(
+                                    HashedPostState::default(),
+                                    entry.hashed_address,
+                                    B256::ZERO,
+                                    usize::MAX,
+                                )
+                                .map_err(EthApiError::from)?
+                                .slots;
+                            let hashed_slots =
+                                slots.iter().map(|(hashed_slot, _)| *hashed_slot).collect();
+                            let preimages = state
+                                .storage_preimages(address, &hashed_slots)
+                                .map_err(EthApiError::from)?;
+                            let storage = slots
+                                .into_iter()
+                                .filter_map(|(hashed_slot, value)| {
+                                    let slot = preimages.get(&hashed_slot)?;
+                                    Some((*slot, hex::encode(value.to_be_bytes_trimmed_vec())))
+                                })
+                                .collect::<BTreeMap<_, _>>();
+                            (!storage.is_empty()).then_some(storage)
+                        }
+                        _ => None,
+                    };
+
+                    let key = match address {
+                        Some(address) => address.to_checksum(None),
+                        None => format!("pre({})", entry.hashed_address),
+                    };
+                    accounts.insert(
+                        key,
+                        DumpAccount {
+                            balance: entry.account.balance.to_string(),
+                            nonce: entry.account.nonce,
+                            root: entry.storage_root,
+                            code_hash,
+                            code,
+                            storage,
+                            address,
+                            key: Some(entry.hashed_address),
+                        },
+                    );
+                }
+
+                Ok(AccountRangeResult {
+                    root,
+                    accounts,
+                    next: range.next.map(|next| Bytes::copy_from_slice(next.as_slice())),
+                })
+            })
+            .await
+    }
+
+    /// Returns up to `max_result` storage slots of the account, starting at the hashed slot
+    /// `key_start`, as they were before the transaction at index `tx_idx` of the block.
+    ///
+    /// The preimages of the slots are recovered from the plain state where possible.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_id: BlockId,
+        tx_idx: usize,
+        address: Address,
+        key_start: Bytes,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let start = hashed_key_start(&key_start)?;
+        let limit = usize::try_from(max_result).unwrap_or(usize::MAX);
+        let block = self
+            .eth_api()
+            .recovered_block(block_id)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        if tx_idx > 0 && tx_idx >= block.body().transaction_count() {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {}",
+                block.hash()
+            ))))
+        }
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state))
+                    .with_bundle_update()
+                    .build();
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                for tx in block.transactions_recovered().take(tx_idx) {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let (res, _) = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                    db.commit(res.state);
+                }
+                db.merge_transitions(BundleRetention::PlainState);
+
+                if db.basic(address).map_err(EthApiError::from)?.is_none() {
+                    return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                        "account {address:x} doesn't exist"
+                    ))))
+                }
+
+                let hashed_state = state.hashed_post_state(&db.bundle_state);
+                let range = state
+                    .hashed_storage_range(hashed_state, keccak256(address), start, limit)
+                    .map_err(EthApiError::from)?;
+
+                // the slots written by the replayed transactions are known, the rest are looked
+                // up in the plain state
+                let mut preimages = db
+                    .bundle_state
+                    .account(&address)
+                    .into_iter()
+                    .flat_map(|account| account.storage.keys())
+                    .map(|slot| {
+                        let slot = B256::from(*slot);
+                        (keccak256(slot), slot)
+                    })
+                    .collect::<B256Map<_>>();
+                let missing = range
+                    .slots
+                    .iter()
+                    .map(|(hashed_slot, _)| *hashed_slot)
+                    .filter(|hashed_slot| !preimages.contains_key(hashed_slot))
+                    .collect();
+                preimages
+                    .extend(state.storage_preimages(address, &missing).map_err(EthApiError::from)?);
+
+                let storage = range
+                    .slots
+                    .into_iter()
+                    .map(|(hashed_slot, value)| {
+                        let entry = StorageRangeEntry {
+                            key: preimages.get(&hashed_slot).copied(),
+                            value: value.into(),
+                        };
+                        (hashed_slot, entry)
+                    })
+                    .collect();
+
+                Ok(StorageRangeResult { storage, next_key: range.next })
+            })
+            .await
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// It optionally takes fused inspector ([`TracingInspector::fused`]) to avoid re-creating the
@@ -873,9 +1301,11 @@ where
 }
 
 #[async_trait]
-impl<Eth, BlockExecutor> DebugApiServer for DebugApi<Eth, BlockExecutor>
+impl<Eth, BlockExecutor> DebugApiServer<RpcBlock<Eth::NetworkTypes>>
+    for DebugApi<Eth, BlockExecutor>
 where
-    Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth: FullEthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth::Provider: ChangeSetReader + StorageChangeSetReader + PruneCheckpointReader,
     BlockExecutor:
         BlockExecutorProvider<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>>,
 {
@@ -948,8 +1378,27 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock<RpcBlock<Eth::NetworkTypes>>>> {
+        let mut bad_blocks = Vec::with_capacity(self.bad_block_registry.len());
+        for bad_block in self.bad_block_registry.all() {
+            // a block that can't be recovered is still reported, without the decoded block
+            let block: RpcResult<_> =
+                self.recover_raw_block(&bad_block.block).map_err(Into::into).and_then(|block| {
+                    from_block(block, BlockTransactionsKind::Full, self.eth_api().tx_resp_builder())
+                        .map_err(Into::into)
+                });
+            let block = match block {
+                Ok(block) => Some(block),
+                Err(err) => {
+                    debug!(target: "rpc::debug", hash=%bad_block.hash, %err, "Failed to decode bad block");
+                    None
+                }
+            };
+
+            bad_blocks.push(BadBlock { block, hash: bad_block.hash, rlp: bad_block.block });
+        }
+
+        Ok(bad_blocks)
     }
 
     /// Handler for `debug_traceChain`
@@ -1054,24 +1503,36 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        self.debug_account_range(block_id, start, max_results, nocode, nostorage, incompletes)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_chaindbCompact`
+    ///
+    /// MDBX can only reclaim free pages by rewriting the whole environment, which isn't possible
+    /// while the node has it open, so this is rejected in favor of `reth db compact`.
     async fn debug_chaindb_compact(&self) -> RpcResult<()> {
-        Ok(())
+        Err(EthApiError::Unsupported(
+            "the database can't be compacted while the node is running, stop the node and run \
+             `reth db compact` instead",
+        )
+        .into())
     }
 
     async fn debug_chain_config(&self) -> RpcResult<ChainConfig> {
@@ -1132,18 +1593,26 @@ where
 
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let block_number = |hash: B256| {
+            self.provider()
+                .block_number(hash)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))
+        };
+        let start = block_number(start_hash)?;
+        let end = end_hash.map(block_number).transpose()?;
+        self.debug_get_modified_accounts(start, end).await.map_err(Into::into)
     }
 
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        self.debug_get_modified_accounts(start_number, end_number).await.map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1152,10 +1621,11 @@ where
 
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        self.debug_intermediate_roots(block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1202,12 +1672,24 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDefaultTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        let bad_block = self
+            .bad_block_registry
+            .get(block_hash)
+            .ok_or_else(|| bad_block_not_found(block_hash))?;
+        let block = self.recover_raw_block(&bad_block.block).map_err(Into::into)?;
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        Ok(self
+            .standard_trace_block_to_files(Arc::new(block), evm_env, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)?)
     }
 
     async fn debug_standard_trace_block_to_file(
@@ -1234,23 +1716,37 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_id: BlockId,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: Bytes,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        self.debug_storage_range_at(block_id, tx_idx, contract_address, key_start, max_result)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        let bad_block = self
+            .bad_block_registry
+            .get(block_hash)
+            .ok_or_else(|| bad_block_not_found(block_hash))?;
+
+        Ok(self
+            .debug_trace_raw_block(bad_block.block, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)?)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1282,10 +1778,33 @@ impl<Eth, BlockExecutor> std::fmt::Debug for DebugApi<Eth, BlockExecutor> {
 
 impl<Eth, BlockExecutor> Clone for DebugApi<Eth, BlockExecutor> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            bad_block_registry: self.bad_block_registry.clone(),
+            max_range_blocks: self.max_range_blocks,
+        }
     }
 }
 
+/// Pads the given key prefix to the hashed key the range iteration starts at.
+fn hashed_key_start(start: &[u8]) -> Result<B256, EthApiError> {
+    if start.len() > B256::len_bytes() {
+        return Err(EthApiError::InvalidParams(format!(
+            "start key is longer than {} bytes",
+            B256::len_bytes()
+        )))
+    }
+
+    let mut key = B256::ZERO;
+    key[..start.len()].copy_from_slice(start);
+    Ok(key)
+}
+
+/// Returns the error for a bad block that is not in the registry.
+fn bad_block_not_found(block_hash: B256) -> jsonrpsee::types::ErrorObject<'static> {
+    internal_rpc_err(format!("bad block {block_hash} not found"))
+}
+
 struct DebugApiInner<Eth, BlockExecutor> {
     /// The implementation of `eth` API
     eth_api: Eth,
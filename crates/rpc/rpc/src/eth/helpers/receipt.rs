@@ -1,6 +1,6 @@
 //! Builds an RPC receipt response w.r.t. data layout of network.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, TransactionMeta};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_rpc_eth_api::{helpers::LoadReceipt, FromEthApiError, RpcNodeCoreExt, RpcReceipt};
@@ -19,7 +19,7 @@ where
 {
     async fn build_transaction_receipt(
         &self,
-        tx: TransactionSigned,
+        tx: Recovered<TransactionSigned>,
         meta: TransactionMeta,
         receipt: Receipt,
     ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
@@ -33,6 +33,13 @@ where
             .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
         let blob_params = self.provider().chain_spec().blob_params_at_timestamp(meta.timestamp);
 
-        Ok(EthReceiptBuilder::new(&tx, meta, &receipt, &all_receipts, blob_params)?.build())
+        Ok(EthReceiptBuilder::new(
+            tx.as_recovered_ref(),
+            meta,
+            &receipt,
+            &all_receipts,
+            blob_params,
+        )?
+        .build())
     }
 }
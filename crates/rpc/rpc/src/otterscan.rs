@@ -15,10 +15,12 @@ use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction, TransactionCompat,
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    TransactionCompat,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::AddressAppearancesReader;
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
@@ -62,6 +64,82 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + TraceExt
+        + 'static,
+{
+    /// Returns the transactions of the given block that touched the address, either as the
+    /// sender, the recipient or in any internal call, together with their receipts.
+    async fn address_block_transactions(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let block_id = block_number.into();
+        // retrieve full block and its receipts
+        let block = self.eth.block_by_number(block_id, true);
+        let block_id = block_id.into();
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        // check if the number of transactions matches the number of receipts
+        if block.transactions.len() != receipts.len() {
+            return Err(internal_rpc_err(
+                "the number of transactions does not match the number of receipts",
+            ))
+        }
+
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        // determine which transactions touched the address by inspecting their call traces
+        let touched = self
+            .eth
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |_, inspector, _, _, _| {
+                    Ok(inspector.traces().nodes().iter().any(|node| {
+                        node.trace.caller == address ||
+                            node.trace.address == address ||
+                            node.trace.selfdestruct_refund_target == Some(address)
+                    }))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        let timestamp = Some(block.header.timestamp());
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(touched)
+            .filter(|((tx, receipt), touched)| {
+                *touched ||
+                    tx.from() == address ||
+                    tx.to() == Some(address) ||
+                    receipt.contract_address() == Some(address)
+            })
+            .map(|((tx, receipt), _)| {
+                let receipt = ots_transaction_receipt(receipt, tx.ty(), timestamp);
+                (tx, receipt)
+            })
+            .unzip())
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -74,6 +152,7 @@ where
         > + EthTransactions
         + TraceExt
         + 'static,
+    Eth::Provider: AddressAppearancesReader,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
     async fn get_header_by_number(
@@ -255,32 +334,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_transaction_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -292,21 +346,113 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // block number 0 means the search starts at the tip of the chain
+        let first_page = block_number == 0;
+        let mut cursor = if first_page { u64::MAX } else { block_number };
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        'search: loop {
+            let blocks = self
+                .eth
+                .spawn_blocking_io(move |this| {
+                    this.provider()
+                        .address_appearances_before(address, cursor, page_size.max(1))
+                        .map_err(Eth::Error::from_eth_err)
+                })
+                .await
+                .map_err(Into::into)?;
+            if blocks.is_empty() {
+                break
+            }
+
+            for block in blocks {
+                cursor = block;
+                let (block_txs, block_receipts) =
+                    self.address_block_transactions(address, block).await?;
+                // results are returned in descending order
+                txs.extend(block_txs.into_iter().rev());
+                receipts.extend(block_receipts.into_iter().rev());
+                if txs.len() >= page_size {
+                    break 'search
+                }
+            }
+        }
+
+        let last_page = self
+            .eth
+            .spawn_blocking_io(move |this| {
+                this.provider()
+                    .address_appearances_before(address, cursor, 1)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+            .map_err(Into::into)?
+            .is_empty();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // block number 0 means the search starts at genesis, which is the last page since
+        // results are returned in descending order
+        let last_page = block_number == 0;
+        let mut cursor = block_number;
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        'search: loop {
+            let blocks = self
+                .eth
+                .spawn_blocking_io(move |this| {
+                    this.provider()
+                        .address_appearances_after(address, cursor, page_size.max(1))
+                        .map_err(Eth::Error::from_eth_err)
+                })
+                .await
+                .map_err(Into::into)?;
+            if blocks.is_empty() {
+                break
+            }
+
+            for block in blocks {
+                cursor = block;
+                let (block_txs, block_receipts) =
+                    self.address_block_transactions(address, block).await?;
+                txs.extend(block_txs);
+                receipts.extend(block_receipts);
+                if txs.len() >= page_size {
+                    break 'search
+                }
+            }
+        }
+
+        let first_page = self
+            .eth
+            .spawn_blocking_io(move |this| {
+                this.provider()
+                    .address_appearances_after(address, cursor, 1)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+            .map_err(Into::into)?
+            .is_empty();
+
+        // results are returned in descending order
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -387,3 +533,35 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into an [`OtsTransactionReceipt`], dropping the logs and the logs bloom.
+fn ots_transaction_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
@@ -38,9 +38,10 @@
 //! ```
 use crate::{
     stages::{
-        AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        AccountHashingStage, BodyStage, EraStage, ExecutionStage, FinishStage, HeaderStage,
+        IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexStorageHistoryStage,
+        MerkleStage, PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -65,6 +66,7 @@ use tokio::sync::watch;
 /// - [`FinishStage`]
 ///
 /// This expands to the following series of stages:
+/// - [`EraStage`]
 /// - [`HeaderStage`]
 /// - [`BodyStage`]
 /// - [`SenderRecoveryStage`]
@@ -77,6 +79,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexAddressAppearancesStage`]
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -267,11 +270,16 @@ where
     P: HeaderSyncGapProvider + 'static,
     H: HeaderDownloader<Header = <B::Block as Block>::Header> + 'static,
     B: BodyDownloader + 'static,
+    EraStage: Stage<Provider>,
     HeaderStage<P, H>: Stage<Provider>,
     BodyStage<B>: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
+            .add_stage(EraStage::new(
+                self.stages_config.era.clone(),
+                self.stages_config.etl.clone(),
+            ))
             .add_stage(HeaderStage::new(
                 self.provider,
                 self.header_downloader,
@@ -432,8 +440,17 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexAddressAppearancesStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
+        // History of the filtered addresses has to be indexed from the start, so the indexing
+        // stages can't skip the prunable blocks. The pruner takes care of the rest.
+        let (account_history, storage_history) = if self.prune_modes.has_history_address_filter() {
+            (None, None)
+        } else {
+            (self.prune_modes.account_history, self.prune_modes.storage_history)
+        };
+
         StageSetBuilder::default()
             .add_stage(TransactionLookupStage::new(
                 self.stages_config.transaction_lookup,
@@ -443,12 +460,17 @@ where
             .add_stage(IndexStorageHistoryStage::new(
                 self.stages_config.index_storage_history,
                 self.stages_config.etl.clone(),
-                self.prune_modes.account_history,
+                account_history,
             ))
             .add_stage(IndexAccountHistoryStage::new(
                 self.stages_config.index_account_history,
                 self.stages_config.etl.clone(),
-                self.prune_modes.storage_history,
+                storage_history,
+            ))
+            .add_stage(IndexAddressAppearancesStage::new(
+                self.stages_config.index_address_appearances,
+                self.stages_config.etl.clone(),
+                self.prune_modes.address_appearances,
             ))
     }
 }
@@ -0,0 +1,287 @@
+use alloy_primitives::{BlockHash, BlockNumber};
+use futures_util::StreamExt;
+use reth_config::config::{EraConfig, EtlConfig};
+use reth_db_api::{table::Value, transaction::DbTxMut};
+use reth_era_utils::{
+    build_index, import_file, save_stage_checkpoints, EraFile, EraFileStream, EraImportSource,
+};
+use reth_etl::Collector;
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{
+    BlockWriter, DBProvider, StageCheckpointReader, StageCheckpointWriter,
+    StaticFileProviderFactory,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{
+    fmt,
+    task::{ready, Context, Poll},
+};
+use tracing::*;
+
+/// The era stage.
+///
+/// This stage imports the history of the chain from era1 files, which are either read from a
+/// local directory or downloaded from a remote host, see [`EraImportSource`]. One file is imported
+/// per execution.
+///
+/// Headers and bodies are written into static files, and the checkpoints of the
+/// [`StageId::Headers`] and [`StageId::Bodies`] stages are moved forward with the imported blocks,
+/// so that these stages continue from the last imported block. Receipts are not imported, they
+/// are recreated by the execution stage.
+///
+/// If no source is configured, or all files of the source are imported, the stage does nothing
+/// and follows the [`StageId::Headers`] checkpoint, so that it never reports blocks that are not
+/// written yet.
+pub struct EraStage {
+    /// The source of era1 files, `None` if the stage is disabled.
+    source: Option<EraImportSource>,
+    /// The stream of era1 files, created from the stage checkpoint on first use.
+    stream: Option<EraFileStream>,
+    /// The next era1 file to import.
+    file: Option<EraFile>,
+    /// ETL collector with `HeaderHash` -> `BlockNumber`
+    hash_collector: Collector<BlockHash, BlockNumber>,
+}
+
+impl fmt::Debug for EraStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EraStage")
+            .field("source", &self.source)
+            .field("file", &self.file)
+            .field("hash_collector", &self.hash_collector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for EraStage {
+    fn default() -> Self {
+        Self::new(EraConfig::default(), EtlConfig::default())
+    }
+}
+
+impl EraStage {
+    /// Create a new [`EraStage`].
+    ///
+    /// A local `path` takes precedence over a remote `url` if both are configured.
+    pub fn new(config: EraConfig, etl_config: EtlConfig) -> Self {
+        let EraConfig { path, url, download_dir } = config;
+        let source = match (path, url) {
+            (Some(path), _) => Some(EraImportSource::Path(path)),
+            (None, Some(url)) => Some(EraImportSource::Url(
+                url,
+                download_dir.unwrap_or_else(|| std::env::temp_dir().join("reth-era")),
+            )),
+            (None, None) => None,
+        };
+
+        Self::with_source(source, etl_config)
+    }
+
+    /// Create a new [`EraStage`] importing era1 files from `source`.
+    pub fn with_source(source: Option<EraImportSource>, etl_config: EtlConfig) -> Self {
+        Self {
+            source,
+            stream: None,
+            file: None,
+            hash_collector: Collector::new(etl_config.file_size, etl_config.dir),
+        }
+    }
+}
+
+impl<Provider, N> Stage<Provider> for EraStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + StaticFileProviderFactory<Primitives = N>
+        + BlockWriter<Block = N::Block>
+        + StageCheckpointReader
+        + StageCheckpointWriter,
+    N: NodePrimitives<BlockHeader: Value>,
+{
+    fn id(&self) -> StageId {
+        StageId::Era
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() || self.file.is_some() {
+            return Poll::Ready(Ok(()))
+        }
+
+        let Some(source) = &self.source else { return Poll::Ready(Ok(())) };
+        let stream = self.stream.get_or_insert_with(|| {
+            source.clone().stream_from_block(input.checkpoint().block_number + 1)
+        });
+
+        match ready!(stream.poll_next_unpin(cx)) {
+            Some(Ok(file)) => self.file = Some(file),
+            Some(Err(err)) => {
+                // Recreate the stream on the next attempt
+                self.stream = None;
+                return Poll::Ready(Err(StageError::Recoverable(err.into())))
+            }
+            None => {
+                debug!(target: "sync::stages::era", "All era1 files are imported");
+                self.source = None;
+                self.stream = None;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        let Some(file) = self.file.take() else {
+            // Nothing to import, only report the blocks written by the headers stage
+            let headers = provider.get_stage_checkpoint(StageId::Headers)?.unwrap_or_default();
+            let height =
+                input.checkpoint().block_number.max(headers.block_number).min(input.target());
+            return Ok(ExecOutput::done(StageCheckpoint::new(height)))
+        };
+
+        info!(target: "sync::stages::era", path = %file.path().display(), "Importing era1 file");
+
+        let height = import_file(provider, file.path(), &mut self.hash_collector, input.target())
+            .map_err(|err| StageError::Fatal(err.into()))?;
+        build_index(provider, &mut self.hash_collector)
+            .map_err(|err| StageError::Fatal(err.into()))?;
+        save_stage_checkpoints(provider, height)?;
+
+        file.mark_as_processed().map_err(|err| StageError::Fatal(err.into()))?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(height), done: height >= input.target() })
+    }
+
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // The imported blocks are unwound by the headers and bodies stages, so the files have to
+        // be streamed again from the new checkpoint.
+        self.stream = None;
+        self.file = None;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        stages::{ExecutionStage, SenderRecoveryStage},
+        test_utils::TestStageDB,
+    };
+    use alloy_consensus::Eip658Value;
+    use alloy_primitives::B256;
+    use reth_db_common::init::init_genesis;
+    use reth_era::era1_file::Era1Writer;
+    use reth_era_utils::test_utils::{create_chain_spec, create_era1_file};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_evm::execute::BasicBlockExecutorProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::{
+        test_utils::create_test_provider_factory_with_chain_spec, writer::UnifiedStorageWriter,
+        BlockBodyIndicesProvider, DatabaseProviderFactory, HeaderProvider, ReceiptProvider,
+    };
+    use std::{sync::Arc, task::Waker};
+
+    #[test]
+    fn disabled_stage_follows_headers() {
+        let db = TestStageDB::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+
+        let mut stage = EraStage::default();
+        let input = ExecInput { target: Some(100), checkpoint: None };
+
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(
+            poll_execute_ready(&mut stage, &provider, &mut cx, input),
+            Poll::Ready(Ok(()))
+        ));
+        // No blocks are written yet
+        assert_eq!(
+            stage.execute(&provider, input).unwrap(),
+            ExecOutput::done(StageCheckpoint::new(0))
+        );
+
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(40)).unwrap();
+        assert_eq!(
+            stage.execute(&provider, input).unwrap(),
+            ExecOutput::done(StageCheckpoint::new(40))
+        );
+
+        // Headers beyond the target are not reported
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(150)).unwrap();
+        assert_eq!(
+            stage.execute(&provider, input).unwrap(),
+            ExecOutput::done(StageCheckpoint::new(100))
+        );
+    }
+
+    #[test]
+    fn imported_blocks_are_executed() {
+        let chain_spec = create_chain_spec();
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        let genesis = factory.sealed_header(0).unwrap().unwrap();
+
+        // A pre-Byzantium receipt, with the intermediate state root instead of a status
+        let dir = tempfile::tempdir().unwrap();
+        let file = create_era1_file(&genesis, 3, Eip658Value::PostState(B256::repeat_byte(0x42)));
+        Era1Writer::create(dir.path().join(file.id.to_file_name()), &file).unwrap();
+
+        let mut stage = EraStage::with_source(
+            Some(EraImportSource::Path(dir.path().to_path_buf())),
+            EtlConfig::default(),
+        );
+        let input = ExecInput { target: Some(3), checkpoint: None };
+
+        let provider = factory.database_provider_rw().unwrap();
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(
+            poll_execute_ready(&mut stage, &provider, &mut cx, input),
+            Poll::Ready(Ok(()))
+        ));
+        assert_eq!(
+            stage.execute(&provider, input).unwrap(),
+            ExecOutput::done(StageCheckpoint::new(3))
+        );
+        UnifiedStorageWriter::commit(provider).unwrap();
+
+        // The following stages execute the imported blocks and write their receipts
+        let provider = factory.database_provider_rw().unwrap();
+        SenderRecoveryStage::default().execute(&provider, input).unwrap();
+        let mut execution = ExecutionStage::new_with_executor(
+            BasicBlockExecutorProvider::new(EthEvmConfig::new(chain_spec.clone())),
+            Arc::new(EthBeaconConsensus::new(chain_spec)),
+        );
+        let output = execution.execute(&provider, input).unwrap();
+        assert!(output.done);
+        assert_eq!(output.checkpoint.block_number, 3);
+        UnifiedStorageWriter::commit(provider).unwrap();
+
+        let provider = factory.provider().unwrap();
+        let body_indices = provider.block_body_indices(2).unwrap().unwrap();
+        let receipt = provider.receipt(body_indices.first_tx_num).unwrap().unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.cumulative_gas_used, 21_000);
+    }
+
+    fn poll_execute_ready<Provider>(
+        stage: &mut EraStage,
+        _provider: &Provider,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>>
+    where
+        EraStage: Stage<Provider>,
+    {
+        Stage::<Provider>::poll_execute_ready(stage, cx, input)
+    }
+}
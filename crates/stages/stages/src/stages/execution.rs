@@ -5,6 +5,7 @@ use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::{static_file::HeaderMask, tables};
+use reth_db_api::models::BlockNumberAddress;
 use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     metrics::ExecutorMetrics,
@@ -14,7 +15,7 @@ use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
 use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
+    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HashingWriter, HeaderProvider,
     LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCommitmentProvider,
     StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
 };
@@ -25,6 +26,7 @@ use reth_stages_api::{
     UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
+use reth_trie::KeccakKeyHasher;
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
@@ -34,7 +36,7 @@ use std::{
 };
 use tracing::*;
 
-use super::missing_static_data_error;
+use super::{missing_static_data_error, snap::snap_sync_pivot};
 
 /// The execution stage executes all transactions and
 /// update history indexes.
@@ -264,6 +266,7 @@ where
         + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
+        + HashingWriter
         + StateCommitmentProvider,
 {
     /// Return the id of the stage
@@ -442,7 +445,7 @@ where
                 };
 
                 // If both account history and storage history pruning is configured, clear reverts
-                // for this block.
+                // for this block, keeping only the ones of the addresses with retained history.
                 if prune_modes
                     .account_history
                     .is_some_and(|m| m.should_prune(block_number, max_block)) &&
@@ -450,7 +453,9 @@ where
                         .storage_history
                         .is_some_and(|m| m.should_prune(block_number, max_block))
                 {
-                    reverts.clear();
+                    reverts.retain(|(address, _)| {
+                        prune_modes.history_address_filter.contains(address)
+                    });
                 }
             }
         }
@@ -458,6 +463,13 @@ where
         // write output
         provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
 
+        // the plain state of a snap synced database misses the state synced at the pivot, so the
+        // hashed state is written here instead of by the hashing stages
+        if snap_sync_pivot(provider.tx_ref())?.is_some() {
+            provider
+                .write_hashed_state(&state.hash_state_slow::<KeccakKeyHasher>().into_sorted())?;
+        }
+
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -505,6 +517,13 @@ where
 
         self.ensure_consistency(provider, input.checkpoint.block_number, Some(unwind_to))?;
 
+        // the hashed state of a snap synced database is unwound from the changesets, which are
+        // removed below
+        if snap_sync_pivot(provider.tx_ref())?.is_some() {
+            provider.unwind_account_hashing_range(range.clone())?;
+            provider.unwind_storage_hashing_range(BlockNumberAddress::range(range.clone()))?;
+        }
+
         // Unwind account and storage changesets, as well as receipts.
         //
         // This also updates `PlainStorageState` and `PlainAccountState`.
@@ -1,3 +1,4 @@
+use super::snap::snap_sync_pivot;
 use alloy_primitives::{keccak256, B256};
 use itertools::Itertools;
 use reth_config::config::{EtlConfig, HashingConfig};
@@ -147,6 +148,11 @@ where
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
+        // the hashed state of a snap synced database is written by the execution stage
+        if snap_sync_pivot(provider.tx_ref())?.is_some() {
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
         let (from_block, to_block) = input.next_block_range().into_inner();
 
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
@@ -2,11 +2,15 @@ use super::{collect_history_indices, load_history_indices};
 use alloy_primitives::Address;
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
 use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
     ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
+use reth_static_file_types::StaticFileSegment;
 use std::fmt::Debug;
 use tracing::info;
 
@@ -43,8 +47,11 @@ impl Default for IndexAccountHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexAccountHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + HistoryWriter + PruneCheckpointReader + PruneCheckpointWriter,
+    Provider: DBProvider<Tx: DbTxMut>
+        + StaticFileProviderFactory
+        + HistoryWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -103,9 +110,12 @@ where
 
         info!(target: "sync::stages::index_account_history::exec", ?first_sync, "Collecting indices");
         let collector =
-            collect_history_indices::<_, tables::AccountChangeSets, tables::AccountsHistory, _>(
+            collect_history_indices::<_, tables::AccountChangeSets, tables::AccountsHistory, _, _>(
                 provider,
                 range.clone(),
+                StaticFileSegment::AccountChangeSets,
+                |static_file_provider, range| static_file_provider.account_changesets_range(range),
+                |range| range,
                 ShardedKey::new,
                 |(index, value)| (index, value.address),
                 &self.etl_config,
@@ -153,7 +163,7 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, sharded_key::NUM_OF_INDICES_IN_SHARD, AccountBeforeTx,
-            StoredBlockBodyIndices,
+            StaticFileAccountChangeSet, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
@@ -495,6 +505,48 @@ mod tests {
         assert!(table.is_empty());
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_files() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup changesets of the first blocks in static files and the rest in the database
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for block in 0..=2 {
+            writer
+                .append_account_changeset(
+                    &StaticFileAccountChangeSet { changes: vec![acc()] },
+                    block,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.commit(|tx| {
+            for block in 3..=5 {
+                tx.put::<tables::AccountChangeSets>(block, acc())?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+
+        // unwind
+        unwind(&db, 5, 1);
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1])]));
+    }
+
     stage_test_suite_ext!(IndexAccountHistoryTestRunner, index_account_history);
 
     struct IndexAccountHistoryTestRunner {
@@ -0,0 +1,605 @@
+use super::load_history_indices;
+use alloy_primitives::{Address, BlockNumber};
+use reth_chainspec::EthereumHardforks;
+use reth_config::config::{EtlConfig, IndexAddressAppearancesConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm, Evm};
+use reth_primitives_traits::{BlockBody, NodePrimitives, RecoveredBlock};
+use reth_provider::{
+    AddressAppearancesReader, CallParticipantsTracer, DBProvider, HistoryWriter, ProviderError,
+    ProviderResult, PruneCheckpointReader, PruneCheckpointWriter, StateProvider,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB, DatabaseCommit};
+use reth_stages_api::{
+    ExecInput, ExecOutput, IndexHistoryCheckpoint, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use std::{collections::HashMap, fmt::Debug, ops::RangeInclusive, sync::Arc};
+use tracing::{info, warn};
+
+/// Number of blocks read from the database at once when collecting appearances.
+const BLOCKS_PER_READ: u64 = 1_000;
+
+/// Number of blocks before pushing indices from cache to [`Collector`]
+const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
+
+/// Stage is indexing the blocks every address appeared in, either as a transaction sender or
+/// recipient, log emitter, caller or callee of an internal call or as an account with changed
+/// state. For more information on index sharding take a look at [`tables::AddressAppearances`].
+///
+/// The stage is a no-op unless enabled in the [`IndexAddressAppearancesConfig`] and the provider
+/// traces the call participants, see [`AddressAppearancesReader::traces_call_participants`].
+/// Checkpoints written while the index is maintained carry a stage checkpoint, so that an index
+/// that was disabled for some blocks is rebuilt from scratch once enabled again, see
+/// [`IndexAddressAppearancesStage::is_index_complete`].
+#[derive(Debug)]
+pub struct IndexAddressAppearancesStage {
+    /// Whether the index is enabled.
+    pub enabled: bool,
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressAppearancesStage {
+    /// Create new instance of [`IndexAddressAppearancesStage`].
+    pub const fn new(
+        config: IndexAddressAppearancesConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self {
+            enabled: config.enabled,
+            commit_threshold: config.commit_threshold,
+            etl_config,
+            prune_mode,
+        }
+    }
+
+    /// Returns `true` if the index contains all appearances up to the block of the checkpoint.
+    ///
+    /// This is not the case if the checkpoint was advanced while the index was disabled, either by
+    /// the stage itself or by the blocks persisted outside of the pipeline.
+    pub fn is_index_complete(checkpoint: &StageCheckpoint) -> bool {
+        checkpoint.block_number == 0 || checkpoint.index_history_stage_checkpoint().is_some()
+    }
+
+    /// Returns `true` if the index is enabled and the provider traces the call participants.
+    fn is_maintained(&self, provider: &impl AddressAppearancesReader) -> bool {
+        if !self.enabled {
+            return false
+        }
+        if !provider.traces_call_participants() {
+            warn!(
+                target: "sync::stages::index_address_appearances",
+                "Call participants are not traced, skipping the address appearances index"
+            );
+            return false
+        }
+        true
+    }
+
+    /// Returns the checkpoint of the index maintained up to the given block.
+    fn checkpoint(range: &RangeInclusive<BlockNumber>) -> StageCheckpoint {
+        StageCheckpoint::new(*range.end()).with_index_history_stage_checkpoint(
+            IndexHistoryCheckpoint { block_range: range.into(), progress: Default::default() },
+        )
+    }
+}
+
+impl Default for IndexAddressAppearancesStage {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            commit_threshold: 100_000,
+            prune_mode: None,
+            etl_config: EtlConfig::default(),
+        }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressAppearancesStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + AddressAppearancesReader
+        + HistoryWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressAppearances
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if !self.is_maintained(provider) {
+            // Advance the checkpoint without a stage checkpoint, so the index is rebuilt once
+            // enabled.
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        // The index is missing the appearances of the blocks processed while it was disabled.
+        let rebuild = !Self::is_index_complete(&input.checkpoint());
+        if rebuild {
+            info!(target: "sync::stages::index_address_appearances::exec", checkpoint = input.checkpoint().block_number, "Index is incomplete, rebuilding");
+            input.checkpoint = None;
+        }
+
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressAppearances,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressAppearances)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressAppearances,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            let checkpoint = input.checkpoint().block_number;
+            return Ok(ExecOutput::done(Self::checkpoint(&(checkpoint..=checkpoint))))
+        }
+
+        let range = input.next_block_range();
+        let first_sync = rebuild || input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressAppearances>()?;
+        }
+
+        info!(target: "sync::stages::index_address_appearances::exec", ?first_sync, "Collecting indices");
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut cache: HashMap<Address, Vec<BlockNumber>> = HashMap::default();
+
+        let mut collect = |cache: &mut HashMap<Address, Vec<BlockNumber>>| {
+            for (address, indices) in cache.drain() {
+                let last = *indices.last().expect("qed");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+            Ok::<(), StageError>(())
+        };
+
+        let mut cached_blocks = 0;
+        let mut start = *range.start();
+        while start <= *range.end() {
+            let end = start.saturating_add(BLOCKS_PER_READ - 1).min(*range.end());
+            for (address, blocks) in provider.address_appearances_with_range(start..=end)? {
+                cache.entry(address).or_default().extend(blocks);
+            }
+
+            cached_blocks += end - start + 1;
+            if cached_blocks >= DEFAULT_CACHE_THRESHOLD {
+                collect(&mut cache)?;
+                cached_blocks = 0;
+            }
+
+            start = end + 1;
+        }
+        collect(&mut cache)?;
+
+        info!(target: "sync::stages::index_address_appearances::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressAppearances, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: Self::checkpoint(&range), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        if !self.is_maintained(provider) {
+            return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+        }
+
+        provider.unwind_address_appearances_index_range(range)?;
+
+        // Keep the stage checkpoint, an incomplete index stays incomplete after the unwind.
+        Ok(UnwindOutput { checkpoint: input.checkpoint.with_block_number(unwind_progress) })
+    }
+}
+
+/// [`CallParticipantsTracer`] that re-executes the blocks with a [`TracingInspector`].
+#[derive(Debug)]
+pub struct EvmCallParticipantsTracer<E, ChainSpec> {
+    /// The EVM configuration the blocks are re-executed with.
+    evm_config: E,
+    /// The chain spec the system calls of the blocks are applied with.
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl<E, ChainSpec> EvmCallParticipantsTracer<E, ChainSpec> {
+    /// Create new instance of [`EvmCallParticipantsTracer`].
+    pub const fn new(evm_config: E, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { evm_config, chain_spec }
+    }
+}
+
+impl<E, ChainSpec> CallParticipantsTracer<<E::Primitives as NodePrimitives>::Block>
+    for EvmCallParticipantsTracer<E, ChainSpec>
+where
+    E: ConfigureEvm,
+    ChainSpec: EthereumHardforks + Debug + Send + Sync,
+{
+    fn call_participants(
+        &self,
+        parent_state: &dyn StateProvider,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+    ) -> ProviderResult<Vec<Address>> {
+        if block.body().transaction_count() == 0 {
+            return Ok(Vec::new())
+        }
+
+        let mut db = CacheDB::new(StateProviderDatabase::new(parent_state));
+        let evm_env = self.evm_config.evm_env(block.header());
+
+        // apply the system calls of the block before any of the transactions
+        let mut evm = self.evm_config.evm_with_env(&mut db, evm_env.clone());
+        SystemCaller::new(self.chain_spec.clone())
+            .apply_pre_execution_changes(block.header(), &mut evm)
+            .map_err(ProviderError::other)?;
+        drop(evm);
+
+        let mut participants = Vec::new();
+        for tx in block.transactions_recovered() {
+            let mut inspector = TracingInspector::new(TracingInspectorConfig::none());
+            let res = self
+                .evm_config
+                .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+                .transact(self.evm_config.tx_env(tx))
+                .map_err(ProviderError::other)?;
+            participants.extend(
+                inspector
+                    .traces()
+                    .nodes()
+                    .iter()
+                    .flat_map(|node| [node.trace.caller, node.trace.address]),
+            );
+            db.commit(res.state);
+        }
+        participants.sort_unstable();
+        participants.dedup();
+
+        Ok(participants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, StorageKind,
+        TestRunnerError, TestStageDB, UnwindStageTestRunner,
+    };
+    use alloy_consensus::Transaction;
+    use alloy_primitives::{Log, B256};
+    use reth_db_api::{
+        cursor::DbCursorRO,
+        models::AccountBeforeTx,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_ethereum_primitives::{Block, Receipt};
+    use reth_primitives_traits::{SealedBlock, SignedTransaction};
+    use reth_provider::{
+        providers::StaticFileWriter, test_utils::MockCallParticipantsTracer,
+        DatabaseProviderFactory,
+    };
+    use reth_testing_utils::generators::{
+        self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
+        BlockParams, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    stage_test_suite_ext!(IndexAddressAppearancesTestRunner, index_address_appearances);
+
+    #[tokio::test]
+    async fn disabled_stage_is_noop() {
+        let db = TestStageDB::default();
+        let input = ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(5)) };
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = IndexAddressAppearancesStage::default().execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput::done(StageCheckpoint::new(10)));
+        provider.commit().unwrap();
+
+        assert!(db.table::<tables::AddressAppearances>().unwrap().is_empty());
+        assert!(!IndexAddressAppearancesStage::is_index_complete(&out.checkpoint));
+    }
+
+    #[tokio::test]
+    async fn stage_without_tracer_is_noop() {
+        let mut runner =
+            IndexAddressAppearancesTestRunner { db: TestStageDB::default(), ..Default::default() };
+        let input = ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(5)) };
+        runner.seed_execution(input).unwrap();
+
+        let provider = runner.db.factory.database_provider_rw().unwrap();
+        let out = runner.stage().execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput::done(StageCheckpoint::new(10)));
+        provider.commit().unwrap();
+
+        assert!(runner.db.table::<tables::AddressAppearances>().unwrap().is_empty());
+        assert!(!IndexAddressAppearancesStage::is_index_complete(&out.checkpoint));
+    }
+
+    #[tokio::test]
+    async fn appearance_sources() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        // a callee of an internal call that doesn't change any state
+        let callee = Address::random();
+        db.factory.set_call_participants_tracer(Arc::new(MockCallParticipantsTracer(vec![callee])));
+
+        let block = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(B256::ZERO), tx_count: Some(1), ..Default::default() },
+        );
+        db.insert_blocks([&block].into_iter(), StorageKind::Database(None)).unwrap();
+        let transaction = &block.body().transactions[0];
+        let sender = transaction.recover_signer().unwrap();
+
+        // a log emitter, an account and a storage changed by internal calls
+        let emitter = Address::random();
+        let changed_account = Address::random();
+        let changed_storage = Address::random();
+        db.insert_receipts([(
+            0,
+            Receipt {
+                logs: vec![Log::new_unchecked(emitter, Vec::new(), Default::default())],
+                ..Default::default()
+            },
+        )])
+        .unwrap();
+        db.commit(|tx| {
+            tx.put::<tables::AccountChangeSets>(
+                1,
+                AccountBeforeTx { address: changed_account, info: None },
+            )?;
+            Ok(tx.put::<tables::StorageChangeSets>(
+                (1, changed_storage).into(),
+                Default::default(),
+            )?)
+        })
+        .unwrap();
+
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let provider = db.factory.database_provider_rw().unwrap();
+        IndexAddressAppearancesTestRunner::default().stage().execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        let mut expected = vec![sender, emitter, changed_account, changed_storage, callee];
+        expected.extend(transaction.to());
+        expected.sort();
+        expected.dedup();
+        let indexed = db
+            .table::<tables::AddressAppearances>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, blocks)| {
+                assert_eq!(blocks.iter().collect::<Vec<_>>(), vec![1]);
+                key.key
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(indexed, expected);
+    }
+
+    #[tokio::test]
+    async fn rebuild_incomplete_index() {
+        let mut runner = IndexAddressAppearancesTestRunner::default();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+        runner.seed_execution(input).unwrap();
+
+        // an appearance that doesn't exist in the blocks, indexed before the index was disabled
+        let stale = ShardedKey::new(Address::random(), u64::MAX);
+        runner
+            .db
+            .commit(|tx| {
+                Ok(tx.put::<tables::AddressAppearances>(
+                    stale.clone(),
+                    BlockNumberList::new_pre_sorted([1]),
+                )?)
+            })
+            .unwrap();
+
+        // the checkpoint was advanced while the index was disabled
+        let input = ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(5)) };
+        let provider = runner.db.factory.database_provider_rw().unwrap();
+        let output = runner.stage().execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(output.checkpoint.block_number, 10);
+        assert!(IndexAddressAppearancesStage::is_index_complete(&output.checkpoint));
+        assert!(runner
+            .db
+            .table::<tables::AddressAppearances>()
+            .unwrap()
+            .iter()
+            .all(|(key, _)| key != &stale));
+        runner
+            .validate_execution(ExecInput { target: Some(10), checkpoint: None }, Some(output))
+            .unwrap();
+    }
+
+    struct IndexAddressAppearancesTestRunner {
+        pub(crate) db: TestStageDB,
+        commit_threshold: u64,
+        prune_mode: Option<PruneMode>,
+    }
+
+    impl Default for IndexAddressAppearancesTestRunner {
+        fn default() -> Self {
+            let db = TestStageDB::default();
+            db.factory
+                .set_call_participants_tracer(Arc::new(MockCallParticipantsTracer::default()));
+            Self { db, commit_threshold: 1000, prune_mode: None }
+        }
+    }
+
+    impl StageTestRunner for IndexAddressAppearancesTestRunner {
+        type S = IndexAddressAppearancesStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            Self::S {
+                enabled: true,
+                commit_threshold: self.commit_threshold,
+                prune_mode: self.prune_mode,
+                etl_config: EtlConfig::default(),
+            }
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexAddressAppearancesTestRunner {
+        type Seed = Vec<SealedBlock<Block>>;
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let stage_process = input.checkpoint().block_number;
+            let start = stage_process + 1;
+            let end = input.target();
+            let mut rng = generators::rng();
+
+            let num_of_accounts = 31;
+            let accounts = random_eoa_accounts(&mut rng, num_of_accounts)
+                .into_iter()
+                .collect::<BTreeMap<_, _>>();
+
+            let blocks = random_block_range(
+                &mut rng,
+                start..=end,
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+            );
+
+            let (changesets, _) = random_changeset_range(
+                &mut rng,
+                blocks.iter(),
+                accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+                0..3,
+                0..256,
+            );
+
+            // add block changeset from block 1.
+            self.db.insert_changesets(changesets, Some(start))?;
+            self.db.insert_blocks(blocks.iter(), crate::test_utils::StorageKind::Static)?;
+
+            Ok(blocks)
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                // Without a completion marker the stage rebuilds the index from scratch.
+                let start_block =
+                    if IndexAddressAppearancesStage::is_index_complete(&input.checkpoint()) {
+                        input.next_block()
+                    } else {
+                        ExecInput { checkpoint: None, ..input }.next_block()
+                    };
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                assert!(output.done);
+                assert_eq!(output.checkpoint.block_number, input.target());
+                assert!(IndexAddressAppearancesStage::is_index_complete(&output.checkpoint));
+
+                let provider = self.db.factory.provider()?;
+                let mut result = BTreeMap::new();
+                for (address, blocks) in
+                    provider.address_appearances_with_range(start_block..=end_block)?
+                {
+                    result.insert(ShardedKey::new(address, u64::MAX), blocks);
+                }
+
+                let table = self.db.query(|tx| {
+                    Ok(tx
+                        .cursor_read::<tables::AddressAppearances>()?
+                        .walk(None)?
+                        .map(|entry| entry.map(|(k, v)| (k, v.iter().collect::<Vec<_>>())))
+                        .collect::<Result<BTreeMap<_, _>, _>>()?)
+                })?;
+                assert_eq!(table, result);
+            } else {
+                self.check_no_appearances_above(input.checkpoint().block_number)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexAddressAppearancesTestRunner {
+        fn validate_unwind(&self, input: UnwindInput) -> Result<(), TestRunnerError> {
+            self.check_no_appearances_above(input.unwind_to)
+        }
+    }
+
+    impl IndexAddressAppearancesTestRunner {
+        fn check_no_appearances_above(&self, block: BlockNumber) -> Result<(), TestRunnerError> {
+            self.db.query(|tx| {
+                let mut cursor = tx.cursor_read::<tables::AddressAppearances>()?;
+                for entry in cursor.walk(None)? {
+                    let (_, list) = entry?;
+                    assert!(list.iter().all(|number| number <= block));
+                }
+                Ok(())
+            })?;
+            Ok(())
+        }
+    }
+}
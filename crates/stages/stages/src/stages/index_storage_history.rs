@@ -7,9 +7,13 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_static_file_types::StaticFileSegment;
 use std::fmt::Debug;
 use tracing::info;
 
@@ -46,8 +50,11 @@ impl Default for IndexStorageHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexStorageHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + PruneCheckpointWriter + HistoryWriter + PruneCheckpointReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + StaticFileProviderFactory
+        + PruneCheckpointWriter
+        + HistoryWriter
+        + PruneCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -106,9 +113,12 @@ where
 
         info!(target: "sync::stages::index_storage_history::exec", ?first_sync, "Collecting indices");
         let collector =
-            collect_history_indices::<_, tables::StorageChangeSets, tables::StoragesHistory, _>(
+            collect_history_indices::<_, tables::StorageChangeSets, tables::StoragesHistory, _, _>(
                 provider,
-                BlockNumberAddress::range(range.clone()),
+                range.clone(),
+                StaticFileSegment::StorageChangeSets,
+                |static_file_provider, range| static_file_provider.storage_changesets_range(range),
+                BlockNumberAddress::range,
                 |AddressStorageKey((address, storage_key)), highest_block_number| {
                     StorageShardedKey::new(address, storage_key, highest_block_number)
                 },
@@ -159,7 +169,7 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, storage_sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey,
-            StoredBlockBodyIndices,
+            StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
@@ -518,6 +528,56 @@ mod tests {
         assert!(table.is_empty());
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_files() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup changesets of the first blocks in static files and the rest in the database
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=2 {
+            writer
+                .append_storage_changeset(
+                    &StaticFileStorageChangeSet {
+                        changes: vec![StorageBeforeTx {
+                            address: ADDRESS,
+                            entry: storage(STORAGE_KEY),
+                        }],
+                    },
+                    block,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.commit(|tx| {
+            for block in 3..=5 {
+                tx.put::<tables::StorageChangeSets>(
+                    block_number_address(block),
+                    storage(STORAGE_KEY),
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+
+        // unwind
+        unwind(&db, 5, 1);
+
+        // verify
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1])]));
+    }
+
     stage_test_suite_ext!(IndexStorageHistoryTestRunner, index_storage_history);
 
     struct IndexStorageHistoryTestRunner {
@@ -8,16 +8,20 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{GotExpected, SealedHeader};
 use reth_provider::{
-    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
-    StatsReader, TrieWriter,
+    ChangeSetRangeReader, DBProvider, HeaderProvider, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StatsReader, TrieWriter,
 };
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
-use reth_trie_db::DatabaseStateRoot;
-use std::fmt::Debug;
+use reth_trie::{
+    IntermediateStateRootState, KeccakKeyHasher, StateRoot, StateRootProgress, StoredSubNode,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseStateRoot, DatabaseTrieCursorFactory, PrefixSetLoader,
+};
+use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::*;
 
 // TODO: automate the process outlined below so the user can just send in a debugging package
@@ -139,6 +143,7 @@ where
         + TrieWriter
         + StatsReader
         + HeaderProvider
+        + ChangeSetRangeReader
         + StageCheckpointReader
         + StageCheckpointWriter,
 {
@@ -252,12 +257,12 @@ where
             }
         } else {
             debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
-            let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
-                    .map_err(|e| {
-                        error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
-                        StageError::Fatal(Box::new(e))
-                    })?;
+            let (root, updates) = incremental_root_calculator(provider, range)?
+                .root_with_updates()
+                .map_err(|e| {
+                    error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
+                    StageError::Fatal(Box::new(e))
+                })?;
 
             provider.write_trie_updates(&updates)?;
 
@@ -324,7 +329,8 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
+            let (block_root, updates) = incremental_root_calculator(provider, range)?
+                .root_with_updates()
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -344,6 +350,31 @@ where
     }
 }
 
+/// Creates a state root calculator with the account and storage prefixes changed in the block
+/// range loaded. The changesets are read from static files or the database, wherever they're
+/// available.
+fn incremental_root_calculator<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<
+    StateRoot<
+        DatabaseTrieCursorFactory<'_, Provider::Tx>,
+        DatabaseHashedCursorFactory<'_, Provider::Tx>,
+    >,
+    StageError,
+>
+where
+    Provider: DBProvider + ChangeSetRangeReader,
+{
+    debug!(target: "sync::stages::merkle", ?range, "Loading prefix sets");
+    let prefix_sets = PrefixSetLoader::<_, KeccakKeyHasher>::new(provider.tx_ref())
+        .load_from_changesets(
+            provider.account_changesets_range(range.clone())?,
+            provider.storage_changesets_range(range)?,
+        )?;
+    Ok(StateRoot::from_tx(provider.tx_ref()).with_prefix_sets(prefix_sets))
+}
+
 /// Check that the computed state root matches the root in the expected header.
 #[inline]
 fn validate_state_root<H: BlockHeader + Sealable + Debug>(
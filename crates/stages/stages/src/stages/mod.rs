@@ -1,5 +1,7 @@
 /// The bodies stage.
 mod bodies;
+/// The era import stage.
+mod era;
 /// The execution stage that generates state diff.
 mod execution;
 /// The finish stage
@@ -12,6 +14,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index blocks in which addresses appeared
+mod index_address_appearances;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -21,21 +25,26 @@ mod prune;
 mod s3;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage.
+mod snap;
 /// The transaction lookup stage
 mod tx_lookup;
 
 pub use bodies::*;
+pub use era::*;
 pub use execution::*;
 pub use finish::*;
 pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_appearances::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
 pub use sender_recovery::*;
+pub use snap::*;
 pub use tx_lookup::*;
 
 mod utils;
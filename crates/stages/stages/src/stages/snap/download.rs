@@ -0,0 +1,688 @@
+//! Requests state from snap peers and validates the responses.
+
+use super::trie::{encode_compact_path, verify_range};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use futures_util::{stream::FuturesUnordered, FutureExt, StreamExt};
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount, StorageRangesMessage,
+    TrieNodesMessage, TriePath,
+};
+use reth_network_p2p::{
+    error::PeerRequestResult,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_primitives_traits::Account;
+use reth_trie::{root::storage_root, Nibbles, TrieAccount, EMPTY_ROOT_HASH};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tracing::*;
+
+/// Soft limit for the size of the responses requested from peers.
+const RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// Number of partitions of the account key space that are downloaded concurrently.
+const ACCOUNT_RANGE_PARTITIONS: u64 = 16;
+
+/// Maximum number of accounts whose storage is requested at once.
+const STORAGE_ACCOUNTS_PER_REQUEST: usize = 64;
+
+/// Maximum number of contract codes requested at once.
+const BYTE_CODES_PER_REQUEST: usize = 128;
+
+/// Maximum number of trie nodes requested at once.
+const TRIE_NODES_PER_REQUEST: usize = 128;
+
+/// Maximum number of inflight requests.
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Number of downloaded entries after which the downloaded data is handed out for writing.
+const MAX_BUFFERED_ENTRIES: usize = 100_000;
+
+/// Time to wait before requests are sent again after a request failed.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Number of consecutive responses without the requested state after which the state root is
+/// considered stale.
+const MAX_UNAVAILABLE_RESPONSES: usize = 32;
+
+/// The largest possible hash.
+const MAX_HASH: B256 = B256::repeat_byte(0xff);
+
+/// A unit of work of the snap sync.
+#[derive(Debug, Clone)]
+pub(crate) enum SnapTask {
+    /// Download the accounts in the range `origin..=limit`.
+    AccountRange {
+        /// The first hashed address of the range.
+        origin: B256,
+        /// The last hashed address of the range.
+        limit: B256,
+    },
+    /// Download the storage of the accounts, starting with the slot `origin` for the first one.
+    StorageRanges {
+        /// The hashed addresses and storage roots of the accounts.
+        accounts: Vec<(B256, B256)>,
+        /// The first hashed slot of the first account.
+        origin: B256,
+    },
+    /// Download the contract codes with the given hashes.
+    ByteCodes {
+        /// The code hashes.
+        hashes: Vec<B256>,
+    },
+    /// Download the account trie nodes at the given paths with the given hashes.
+    TrieNodes {
+        /// The paths and the expected hashes of the nodes.
+        nodes: Vec<(Nibbles, B256)>,
+    },
+}
+
+/// Validated state downloaded from peers.
+#[derive(Debug)]
+pub(crate) enum SnapData {
+    /// Accounts with their hashed address and storage root.
+    Accounts(Vec<(B256, Account, B256)>),
+    /// Storage slots of an account.
+    Storage {
+        /// The hashed address of the account.
+        hashed_address: B256,
+        /// The hashed slots and their values.
+        slots: Vec<(B256, U256)>,
+    },
+    /// Contract codes with their hashes.
+    ByteCodes(Vec<(B256, Bytes)>),
+    /// Account trie nodes with their paths.
+    TrieNodes(Vec<(Nibbles, Bytes)>),
+}
+
+impl SnapData {
+    /// Returns the number of entries.
+    fn len(&self) -> usize {
+        match self {
+            Self::Accounts(accounts) => accounts.len(),
+            Self::Storage { slots, .. } => slots.len(),
+            Self::ByteCodes(codes) => codes.len(),
+            Self::TrieNodes(nodes) => nodes.len(),
+        }
+    }
+}
+
+/// The outcome of validating a response.
+enum Validated {
+    /// The response is valid.
+    Ok,
+    /// The peer doesn't serve the requested state, the task should be retried later.
+    Unavailable,
+    /// The response is invalid, the peer should be penalized.
+    Bad,
+}
+
+/// Downloads the state of a state root from snap peers.
+///
+/// Tasks are requested concurrently, the responses are validated against the state root as far as
+/// possible, and the validated data is buffered until it's taken with
+/// [`SnapDownloader::take_buffered`].
+///
+/// Peers only serve the state of recent blocks. Once they stop serving the state root, the
+/// downloader is [stale](SnapDownloader::is_stale) and can be moved to a newer state root with
+/// [`SnapDownloader::set_root`].
+pub(crate) struct SnapDownloader<C: SnapClient> {
+    /// The client used to request state from peers.
+    client: C,
+    /// The state root to download.
+    root: B256,
+    /// Tasks that aren't requested yet.
+    queue: VecDeque<SnapTask>,
+    /// Requests that await a response.
+    inflight: FuturesUnordered<SnapRequest<C::Output>>,
+    /// Set if requests should be delayed after a failed request.
+    backoff: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// Validated data.
+    buffered: Vec<SnapData>,
+    /// Number of entries in [`Self::buffered`].
+    buffered_entries: usize,
+    /// Number of consecutive responses that didn't contain the state of the root.
+    unavailable_responses: usize,
+}
+
+impl<C: SnapClient> std::fmt::Debug for SnapDownloader<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapDownloader")
+            .field("root", &self.root)
+            .field("queue", &self.queue.len())
+            .field("inflight", &self.inflight.len())
+            .field("buffered_entries", &self.buffered_entries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: SnapClient> SnapDownloader<C> {
+    /// Creates a downloader for the state with the given root, without any queued tasks.
+    pub(crate) fn new(client: C, root: B256) -> Self {
+        Self {
+            client,
+            root,
+            queue: VecDeque::new(),
+            inflight: FuturesUnordered::new(),
+            backoff: None,
+            buffered: Vec::new(),
+            buffered_entries: 0,
+            unavailable_responses: 0,
+        }
+    }
+
+    /// Returns `true` if peers stopped serving the state root.
+    pub(crate) const fn is_stale(&self) -> bool {
+        self.unavailable_responses >= MAX_UNAVAILABLE_RESPONSES
+    }
+
+    /// Requests the state root again, even though it's [stale](Self::is_stale).
+    pub(crate) const fn reset_stale(&mut self) {
+        self.unavailable_responses = 0;
+    }
+
+    /// Continues the download with the state of the given root.
+    ///
+    /// The downloaded data is kept, and so are the remaining account ranges and contract codes.
+    /// Storages and trie nodes are dropped since they depend on the previous root, the storages
+    /// that changed are downloaded again when the trie is healed.
+    pub(crate) fn set_root(&mut self, root: B256) {
+        self.root = root;
+        self.unavailable_responses = 0;
+        self.backoff = None;
+        self.queue.retain(|task| {
+            matches!(task, SnapTask::AccountRange { .. } | SnapTask::ByteCodes { .. })
+        });
+    }
+
+    /// Queues the download of all accounts, split into [`ACCOUNT_RANGE_PARTITIONS`] ranges.
+    pub(crate) fn push_account_ranges(&mut self) {
+        if self.root == EMPTY_ROOT_HASH {
+            return
+        }
+
+        let step = U256::MAX / U256::from(ACCOUNT_RANGE_PARTITIONS);
+        for partition in 0..ACCOUNT_RANGE_PARTITIONS {
+            let origin = step * U256::from(partition) + U256::from(partition.min(1));
+            let limit = if partition + 1 == ACCOUNT_RANGE_PARTITIONS {
+                U256::MAX
+            } else {
+                step * U256::from(partition + 1)
+            };
+            self.push_task(SnapTask::AccountRange {
+                origin: B256::from(origin),
+                limit: B256::from(limit),
+            });
+        }
+    }
+
+    /// Queues a task.
+    pub(crate) fn push_task(&mut self, task: SnapTask) {
+        self.queue.push_back(task);
+    }
+
+    /// Queues the download of the storage of the given accounts.
+    pub(crate) fn push_storages(&mut self, accounts: Vec<(B256, B256)>) {
+        for chunk in accounts.chunks(STORAGE_ACCOUNTS_PER_REQUEST) {
+            self.push_task(SnapTask::StorageRanges {
+                accounts: chunk.to_vec(),
+                origin: B256::ZERO,
+            });
+        }
+    }
+
+    /// Queues the download of the given contract codes.
+    pub(crate) fn push_byte_codes(&mut self, hashes: Vec<B256>) {
+        for chunk in hashes.chunks(BYTE_CODES_PER_REQUEST) {
+            self.push_task(SnapTask::ByteCodes { hashes: chunk.to_vec() });
+        }
+    }
+
+    /// Queues the download of the given account trie nodes.
+    pub(crate) fn push_trie_nodes(&mut self, nodes: Vec<(Nibbles, B256)>) {
+        for chunk in nodes.chunks(TRIE_NODES_PER_REQUEST) {
+            self.push_task(SnapTask::TrieNodes { nodes: chunk.to_vec() });
+        }
+    }
+
+    /// Returns `true` if all tasks are done and all data has been taken.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.inflight.is_empty() && self.buffered.is_empty()
+    }
+
+    /// Takes the validated data.
+    pub(crate) fn take_buffered(&mut self) -> Vec<SnapData> {
+        self.buffered_entries = 0;
+        std::mem::take(&mut self.buffered)
+    }
+
+    /// Drives the requests.
+    ///
+    /// Resolves once enough data is buffered, once all tasks are done, or once the state root is
+    /// stale.
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if self.is_stale() ||
+                self.buffered_entries >= MAX_BUFFERED_ENTRIES ||
+                (self.queue.is_empty() && self.inflight.is_empty())
+            {
+                return Poll::Ready(())
+            }
+
+            if let Some(backoff) = &mut self.backoff {
+                if backoff.poll_unpin(cx).is_ready() {
+                    self.backoff = None;
+                }
+            }
+
+            if self.backoff.is_none() {
+                while self.inflight.len() < MAX_CONCURRENT_REQUESTS {
+                    let Some(task) = self.queue.pop_front() else { break };
+                    let request = self.request(&task);
+                    self.inflight.push(SnapRequest { task: Some(task), root: self.root, request });
+                }
+            }
+
+            let Some((task, root, response)) = ready!(self.inflight.poll_next_unpin(cx)) else {
+                // inflight requests are empty, only the backoff is pending
+                if !self.buffered.is_empty() {
+                    return Poll::Ready(())
+                }
+                return Poll::Pending
+            };
+            if root == self.root || matches!(task, SnapTask::ByteCodes { .. }) {
+                self.on_response(task, response);
+            } else if matches!(task, SnapTask::AccountRange { .. }) {
+                // the response belongs to a previous root, the range is requested again
+                self.queue.push_back(task);
+            }
+        }
+    }
+
+    /// Sends the request for the task.
+    fn request(&self, task: &SnapTask) -> C::Output {
+        match task {
+            SnapTask::AccountRange { origin, limit } => {
+                self.client.get_account_range(GetAccountRangeMessage {
+                    request_id: 0,
+                    root_hash: self.root,
+                    starting_hash: *origin,
+                    limit_hash: *limit,
+                    response_bytes: RESPONSE_BYTES,
+                })
+            }
+            SnapTask::StorageRanges { accounts, origin } => {
+                self.client.get_storage_ranges(GetStorageRangesMessage {
+                    request_id: 0,
+                    root_hash: self.root,
+                    account_hashes: accounts
+                        .iter()
+                        .map(|(hashed_address, _)| *hashed_address)
+                        .collect(),
+                    starting_hash: *origin,
+                    limit_hash: MAX_HASH,
+                    response_bytes: RESPONSE_BYTES,
+                })
+            }
+            SnapTask::ByteCodes { hashes } => self.client.get_byte_codes(GetByteCodesMessage {
+                request_id: 0,
+                hashes: hashes.clone(),
+                response_bytes: RESPONSE_BYTES,
+            }),
+            SnapTask::TrieNodes { nodes } => self.client.get_trie_nodes(GetTrieNodesMessage {
+                request_id: 0,
+                root_hash: self.root,
+                paths: nodes
+                    .iter()
+                    .map(|(path, _)| TriePath {
+                        account_path: encode_compact_path(path),
+                        slot_paths: Vec::new(),
+                    })
+                    .collect(),
+                response_bytes: RESPONSE_BYTES,
+            }),
+        }
+    }
+
+    /// Validates the response and buffers its data, failed tasks are queued again.
+    fn on_response(&mut self, task: SnapTask, response: PeerRequestResult<SnapResponse>) {
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                trace!(target: "sync::stages::snap", ?task, %error, "Snap request failed");
+                self.retry(task);
+                return
+            }
+        };
+        let (peer_id, response) = response.split();
+
+        let validated = match (&task, response) {
+            (SnapTask::AccountRange { origin, limit }, SnapResponse::AccountRange(response)) => {
+                self.on_account_range(*origin, *limit, response)
+            }
+            (
+                SnapTask::StorageRanges { accounts, origin },
+                SnapResponse::StorageRanges(response),
+            ) => self.on_storage_ranges(accounts, *origin, response),
+            (SnapTask::ByteCodes { hashes }, SnapResponse::ByteCodes(response)) => {
+                self.on_byte_codes(hashes, response)
+            }
+            (SnapTask::TrieNodes { nodes }, SnapResponse::TrieNodes(response)) => {
+                self.on_trie_nodes(nodes, response)
+            }
+            _ => Validated::Bad,
+        };
+
+        // only the requests of state that belongs to the root tell whether the root is still served
+        if !matches!(task, SnapTask::ByteCodes { .. }) {
+            match validated {
+                Validated::Ok => self.unavailable_responses = 0,
+                Validated::Unavailable => self.unavailable_responses += 1,
+                Validated::Bad => {}
+            }
+        }
+
+        match validated {
+            Validated::Ok => {}
+            Validated::Unavailable => {
+                trace!(target: "sync::stages::snap", %peer_id, ?task, "Peer can't serve the requested state");
+                self.retry(task);
+            }
+            Validated::Bad => {
+                debug!(target: "sync::stages::snap", %peer_id, ?task, "Bad snap response");
+                self.client.report_bad_message(peer_id);
+                self.retry(task);
+            }
+        }
+    }
+
+    /// Queues the task again and delays further requests.
+    fn retry(&mut self, task: SnapTask) {
+        self.queue.push_back(task);
+        if self.backoff.is_none() {
+            self.backoff = Some(Box::pin(tokio::time::sleep(RETRY_BACKOFF)));
+        }
+    }
+
+    fn buffer(&mut self, data: SnapData) {
+        self.buffered_entries += data.len();
+        self.buffered.push(data);
+    }
+
+    fn on_account_range(
+        &mut self,
+        origin: B256,
+        limit: B256,
+        response: AccountRangeMessage,
+    ) -> Validated {
+        let AccountRangeMessage { accounts, proof, .. } = response;
+        if accounts.is_empty() {
+            if proof.is_empty() {
+                return Validated::Unavailable
+            }
+            // the proof must show that there are no accounts left after the origin
+            return if verify_range(self.root, origin, &[], &proof) {
+                Validated::Ok
+            } else {
+                Validated::Bad
+            }
+        }
+
+        let mut decoded = Vec::with_capacity(accounts.len());
+        let mut prev = None;
+        for account in accounts {
+            if account.hash < origin || prev.is_some_and(|prev| prev >= account.hash) {
+                return Validated::Bad
+            }
+            prev = Some(account.hash);
+
+            let Ok(slim) = SlimAccount::decode(&mut &account.body[..]) else {
+                return Validated::Bad
+            };
+            decoded.push((account.hash, slim));
+        }
+
+        let last = decoded.last().map(|(hash, _)| *hash).expect("not empty");
+        let trie_account = |slim: SlimAccount| TrieAccount {
+            nonce: slim.nonce,
+            balance: slim.balance,
+            storage_root: slim.storage_root,
+            code_hash: slim.code_hash,
+        };
+        if proof.is_empty() {
+            // the whole trie was served
+            let root = reth_trie::root::state_root(
+                decoded.iter().map(|(hash, slim)| (*hash, trie_account(*slim))),
+            );
+            if origin != B256::ZERO || root != self.root {
+                return Validated::Bad
+            }
+        } else {
+            // the range must contain all accounts of the trie between the origin and its end
+            let leaves = decoded
+                .iter()
+                .map(|(hash, slim)| (*hash, alloy_rlp::encode(trie_account(*slim))))
+                .collect::<Vec<_>>();
+            if !verify_range(self.root, origin, &leaves, &proof) {
+                return Validated::Bad
+            }
+        }
+
+        if !proof.is_empty() && last < limit {
+            if let Some(next) = increment(last) {
+                self.push_task(SnapTask::AccountRange { origin: next, limit });
+            }
+        }
+
+        self.buffer(SnapData::Accounts(
+            decoded
+                .into_iter()
+                .filter(|(hash, _)| *hash <= limit)
+                .map(|(hash, slim)| {
+                    let account = Account {
+                        nonce: slim.nonce,
+                        balance: slim.balance,
+                        bytecode_hash: (slim.code_hash != KECCAK_EMPTY).then_some(slim.code_hash),
+                    };
+                    (hash, account, slim.storage_root)
+                })
+                .collect(),
+        ));
+
+        Validated::Ok
+    }
+
+    fn on_storage_ranges(
+        &mut self,
+        accounts: &[(B256, B256)],
+        origin: B256,
+        response: StorageRangesMessage,
+    ) -> Validated {
+        let StorageRangesMessage { slots, proof, .. } = response;
+        if slots.len() > accounts.len() {
+            return Validated::Bad
+        }
+        if slots.is_empty() && proof.is_empty() {
+            return Validated::Unavailable
+        }
+
+        let mut decoded = Vec::with_capacity(slots.len());
+        for (idx, account_slots) in slots.into_iter().enumerate() {
+            let first_slot = if idx == 0 { origin } else { B256::ZERO };
+            let mut prev = None;
+            let mut storage = Vec::with_capacity(account_slots.len());
+            for slot in account_slots {
+                if slot.hash < first_slot || prev.is_some_and(|prev| prev >= slot.hash) {
+                    return Validated::Bad
+                }
+                prev = Some(slot.hash);
+
+                let Ok(value) = U256::decode(&mut &slot.data[..]) else { return Validated::Bad };
+                storage.push((slot.hash, value));
+            }
+            decoded.push(storage);
+        }
+
+        // with a proof, the last storage is incomplete
+        let num_complete =
+            if proof.is_empty() { decoded.len() } else { decoded.len().saturating_sub(1) };
+        for (idx, storage) in decoded.iter().enumerate().take(num_complete) {
+            if storage_root(storage.iter().copied()) != accounts[idx].1 {
+                return Validated::Bad
+            }
+        }
+
+        let mut next_idx = decoded.len();
+        if !proof.is_empty() {
+            match decoded.last().and_then(|storage| storage.last()) {
+                Some((last, _)) => {
+                    let idx = decoded.len() - 1;
+                    let (hashed_address, storage_root) = accounts[idx];
+                    let first_slot = if idx == 0 { origin } else { B256::ZERO };
+                    let leaves = decoded[idx]
+                        .iter()
+                        .map(|(slot, value)| (*slot, alloy_rlp::encode_fixed_size(value).to_vec()))
+                        .collect::<Vec<_>>();
+                    if !verify_range(storage_root, first_slot, &leaves, &proof) {
+                        return Validated::Bad
+                    }
+                    if let Some(next) = increment(*last) {
+                        self.push_task(SnapTask::StorageRanges {
+                            accounts: vec![(hashed_address, storage_root)],
+                            origin: next,
+                        });
+                    }
+                }
+                None => {
+                    // only the first account can have no slots left after the origin
+                    if decoded.len() > 1 ||
+                        origin == B256::ZERO ||
+                        !verify_range(accounts[0].1, origin, &[], &proof)
+                    {
+                        return Validated::Bad
+                    }
+                    next_idx = 1;
+                }
+            }
+        }
+
+        if next_idx < accounts.len() {
+            self.push_task(SnapTask::StorageRanges {
+                accounts: accounts[next_idx..].to_vec(),
+                origin: B256::ZERO,
+            });
+        }
+
+        for (idx, slots) in decoded.into_iter().enumerate() {
+            if !slots.is_empty() {
+                self.buffer(SnapData::Storage { hashed_address: accounts[idx].0, slots });
+            }
+        }
+
+        Validated::Ok
+    }
+
+    fn on_byte_codes(&mut self, hashes: &[B256], response: ByteCodesMessage) -> Validated {
+        if response.codes.is_empty() {
+            return Validated::Unavailable
+        }
+
+        // peers skip unknown codes, but keep the order of the request
+        let mut remaining = hashes.iter();
+        let mut missing = Vec::new();
+        let mut codes = Vec::with_capacity(response.codes.len());
+        for code in response.codes {
+            let hash = keccak256(&code);
+            loop {
+                let Some(requested) = remaining.next() else { return Validated::Bad };
+                if *requested == hash {
+                    break
+                }
+                missing.push(*requested);
+            }
+            codes.push((hash, code));
+        }
+        missing.extend(remaining);
+
+        if !missing.is_empty() {
+            self.push_byte_codes(missing);
+        }
+        self.buffer(SnapData::ByteCodes(codes));
+
+        Validated::Ok
+    }
+
+    fn on_trie_nodes(
+        &mut self,
+        requested: &[(Nibbles, B256)],
+        response: TrieNodesMessage,
+    ) -> Validated {
+        if response.nodes.is_empty() {
+            return Validated::Unavailable
+        }
+        if response.nodes.len() > requested.len() {
+            return Validated::Bad
+        }
+
+        let mut nodes = Vec::with_capacity(response.nodes.len());
+        for ((path, hash), node) in requested.iter().zip(response.nodes) {
+            if keccak256(&node) != *hash {
+                return Validated::Bad
+            }
+            nodes.push((path.clone(), node));
+        }
+
+        if nodes.len() < requested.len() {
+            self.push_trie_nodes(requested[nodes.len()..].to_vec());
+        }
+        self.buffer(SnapData::TrieNodes(nodes));
+
+        Validated::Ok
+    }
+}
+
+/// Returns the hash following the given one, or `None` if it's the largest hash.
+fn increment(hash: B256) -> Option<B256> {
+    U256::from_be_bytes(hash.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// An inflight request for a task.
+struct SnapRequest<F> {
+    /// The requested task, taken once the request resolves.
+    task: Option<SnapTask>,
+    /// The state root the task was requested for.
+    root: B256,
+    /// The request future.
+    request: F,
+}
+
+impl<F> Future for SnapRequest<F>
+where
+    F: Future<Output = PeerRequestResult<SnapResponse>> + Unpin,
+{
+    type Output = (SnapTask, B256, PeerRequestResult<SnapResponse>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let response = ready!(self.request.poll_unpin(cx));
+        Poll::Ready((self.task.take().expect("polled after completion"), self.root, response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_hash() {
+        assert_eq!(increment(B256::ZERO), Some(B256::with_last_byte(1)));
+        assert_eq!(increment(MAX_HASH), None);
+    }
+}
@@ -0,0 +1,1011 @@
+mod download;
+mod trie;
+
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_eips::eip1898::BlockWithParent;
+use alloy_primitives::{keccak256, map::B256Set, BlockNumber, Bytes, B256};
+use alloy_rlp::Decodable;
+use download::{SnapData, SnapDownloader};
+use reth_consensus::ConsensusError;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_network_p2p::snap::client::SnapClient;
+use reth_primitives_traits::{Account, Bytecode, GotExpected, SealedHeader, StorageEntry};
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, PruneCheckpointWriter, StageCheckpointReader,
+    StageCheckpointWriter, TrieWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    BlockErrorKind, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use reth_trie::{
+    proof::Proof, MultiProofTargets, Nibbles, StateRoot, StorageRoot, TrieAccount, TrieNode,
+    EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot};
+use std::task::{Context, Poll};
+use tracing::*;
+use trie::{padded_key, ChildRef};
+
+/// Maximum number of times the trie is healed before the sync is aborted.
+const MAX_HEAL_ROUNDS: usize = 8;
+
+/// The stages whose work is done by the snap sync.
+const SNAP_SYNCED_STAGES: [StageId; 4] =
+    [StageId::Execution, StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute];
+
+/// The history segments that are not available below the pivot of the snap sync.
+const SNAP_SYNCED_SEGMENTS: [PruneSegment; 2] =
+    [PruneSegment::AccountHistory, PruneSegment::StorageHistory];
+
+/// The snap sync stage.
+///
+/// This stage downloads the state of the target block from peers that serve the `snap/1` protocol
+/// through a [`SnapClient`]:
+///
+/// 1. Accounts are downloaded in ranges into [`tables::HashedAccounts`], storage slots into
+///    [`tables::HashedStorages`] and contract codes into [`tables::Bytecodes`]. Account ranges, and
+///    storage ranges that don't fit into a single response, are checked with the proofs of their
+///    boundaries: the root computed from the range and the parts of the trie outside of it must
+///    match the state root of the target block or the storage root of the account, so that a range
+///    can't omit or alter entries. Storages that fit into a single response are checked against the
+///    storage root of their account.
+/// 2. The state root is computed from the downloaded state. If it doesn't match the state root of
+///    the target block, the account trie is healed: starting at the root, the trie nodes that
+///    differ from the local trie are requested with `GetTrieNodes`, the accounts below them are
+///    replaced and storages that don't match their storage root are downloaded again.
+/// 3. Once the state root matches, the checkpoints of the [`StageId::Execution`],
+///    [`StageId::AccountHashing`], [`StageId::StorageHashing`] and [`StageId::MerkleExecute`]
+///    stages are moved to the target block, so that the regular pipeline continues from there. The
+///    account and storage history below the target block is marked as pruned, and the target block
+///    is stored as the progress of the [`StageId::SnapSync`] checkpoint.
+///
+/// Peers only serve the state of recent blocks. If they stop serving the state of the target
+/// block, the stage finishes without reaching the target, so that the pipeline runs again with a
+/// newer target. The sync then moves to the newer target block and continues with the ranges that
+/// are left, the state downloaded so far is kept and repaired when the trie is healed.
+///
+/// The state tables and the trie tables are cleared when a sync starts, the state of a previous,
+/// interrupted sync is not reused. A sync only starts on a database without executed blocks, or
+/// after the state was unwound below the pivot of the previous sync.
+///
+/// The snap protocol only transfers the hashed state, the plain state tables stay empty. The state
+/// providers fall back to the hashed state for accounts and storage slots missing from the plain
+/// state, and the [`ExecutionStage`](super::ExecutionStage) of a snap synced database writes the
+/// hashed state itself. Receipts of the blocks before the target block
+/// are not available, so receipts need to be pruned.
+///
+/// Unwinding below the pivot clears the state and trie tables, the state is synced again on the
+/// next run.
+///
+/// The stage isn't part of the [`DefaultStages`](crate::sets::DefaultStages), snap syncing nodes
+/// add it in front of the [`SenderRecoveryStage`](super::SenderRecoveryStage).
+pub struct SnapSyncStage<C: SnapClient> {
+    /// The client used to request state from peers.
+    client: C,
+    /// The sync in progress, created on the first execution.
+    sync: Option<SnapSync<C>>,
+}
+
+impl<C: SnapClient> std::fmt::Debug for SnapSyncStage<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapSyncStage").field("sync", &self.sync).finish_non_exhaustive()
+    }
+}
+
+impl<C: SnapClient> SnapSyncStage<C> {
+    /// Create a new [`SnapSyncStage`] that downloads state through the given client.
+    pub const fn new(client: C) -> Self {
+        Self { client, sync: None }
+    }
+}
+
+/// The state of a snap sync towards a single pivot block.
+struct SnapSync<C: SnapClient> {
+    /// The block whose state is downloaded.
+    pivot: BlockWithParent,
+    /// The state root of the pivot block.
+    pivot_root: B256,
+    /// The downloader for the state of the pivot block.
+    downloader: SnapDownloader<C>,
+    /// Code hashes that have been queued for download.
+    queued_codes: B256Set,
+    /// Number of times the trie was healed.
+    heal_rounds: usize,
+}
+
+impl<C: SnapClient> std::fmt::Debug for SnapSync<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapSync")
+            .field("pivot", &self.pivot.block)
+            .field("downloader", &self.downloader)
+            .field("heal_rounds", &self.heal_rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, C> Stage<Provider> for SnapSyncStage<C>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + PruneCheckpointWriter
+        + TrieWriter,
+    C: SnapClient + Clone + 'static,
+{
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() {
+            return Poll::Ready(Ok(()))
+        }
+
+        // the sync is created by the first execution
+        let Some(sync) = &mut self.sync else { return Poll::Ready(Ok(())) };
+        sync.downloader.poll(cx).map(Ok)
+    }
+
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let Some(sync) = &mut self.sync else {
+            let synced = match snap_sync_pivot(provider.tx_ref())? {
+                // the state was synced before, and not unwound below the pivot since
+                Some(pivot) => input.checkpoint().block_number >= pivot,
+                // the state of blocks that were executed is not replaced
+                None => provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .is_some_and(|checkpoint| checkpoint.block_number > 0),
+            };
+            if synced {
+                return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+            }
+
+            let target = input.target();
+            let pivot = pivot_header(provider, target)?;
+            let pivot_root = pivot.state_root();
+            info!(target: "sync::stages::snap", number = target, state_root = %pivot_root, "Starting snap sync");
+
+            clear_state(provider.tx_ref())?;
+
+            let mut downloader = SnapDownloader::new(self.client.clone(), pivot_root);
+            downloader.push_account_ranges();
+            self.sync = Some(SnapSync {
+                downloader,
+                pivot: pivot.block_with_parent(),
+                pivot_root,
+                queued_codes: B256Set::default(),
+                heal_rounds: 0,
+            });
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        };
+
+        for data in sync.downloader.take_buffered() {
+            sync.write(provider.tx_ref(), data)?;
+        }
+
+        if sync.downloader.is_stale() {
+            let target = input.target();
+            if target <= sync.pivot.block.number {
+                // give the pipeline the chance to run again with a newer target
+                warn!(target: "sync::stages::snap", number = sync.pivot.block.number, "Peers stopped serving the state of the pivot");
+                sync.downloader.reset_stale();
+                return Ok(ExecOutput::done(input.checkpoint()))
+            }
+
+            let pivot = pivot_header(provider, target)?;
+            info!(target: "sync::stages::snap", number = target, state_root = %pivot.state_root(), "Moving snap sync pivot");
+            sync.pivot = pivot.block_with_parent();
+            sync.pivot_root = pivot.state_root();
+            sync.heal_rounds = 0;
+            sync.downloader.set_root(sync.pivot_root);
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        }
+
+        if !sync.downloader.is_idle() {
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        }
+
+        // all queued state is downloaded, check whether it matches the pivot
+        let tx = provider.tx_ref();
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        let (root, updates) = StateRoot::from_tx(tx)
+            .root_with_updates()
+            .map_err(|e| StageError::Fatal(Box::new(e)))?;
+        provider.write_trie_updates(&updates)?;
+
+        let pivot_root = sync.pivot_root;
+        if root != pivot_root {
+            if sync.heal_rounds >= MAX_HEAL_ROUNDS {
+                error!(target: "sync::stages::snap", %root, %pivot_root, "Snap synced state doesn't match the pivot");
+                return Err(StageError::Block {
+                    error: BlockErrorKind::Validation(ConsensusError::BodyStateRootDiff(
+                        GotExpected { got: root, expected: pivot_root }.into(),
+                    )),
+                    block: Box::new(sync.pivot),
+                })
+            }
+
+            sync.heal_rounds += 1;
+            debug!(target: "sync::stages::snap", %root, %pivot_root, round = sync.heal_rounds, "Healing state trie");
+            sync.downloader.push_trie_nodes(vec![(Nibbles::default(), pivot_root)]);
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        }
+
+        let pivot = sync.pivot.block.number;
+        let checkpoint = StageCheckpoint::new(pivot);
+        for stage_id in SNAP_SYNCED_STAGES {
+            provider.save_stage_checkpoint(stage_id, checkpoint)?;
+        }
+        for segment in SNAP_SYNCED_SEGMENTS {
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(pivot),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(pivot + 1),
+                },
+            )?;
+        }
+        provider.save_stage_checkpoint_progress(StageId::SnapSync, pivot.to_be_bytes().to_vec())?;
+        info!(target: "sync::stages::snap", number = sync.pivot.block.number, %root, "Finished snap sync");
+        self.sync = None;
+
+        Ok(ExecOutput::done(checkpoint))
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        self.sync = None;
+
+        // above the pivot, the state is unwound by the execution, hashing and merkle stages
+        if snap_sync_pivot(provider.tx_ref())?.is_some_and(|pivot| input.unwind_to < pivot) {
+            warn!(target: "sync::stages::snap", unwind_to = input.unwind_to, "Clearing snap synced state");
+            clear_state(provider.tx_ref())?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+impl<C: SnapClient> SnapSync<C> {
+    /// Writes the downloaded data and queues the tasks that follow from it.
+    fn write<TX: DbTx + DbTxMut>(&mut self, tx: &TX, data: SnapData) -> Result<(), StageError> {
+        match data {
+            SnapData::Accounts(accounts) => {
+                let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+                let mut storages = Vec::new();
+                for (hashed_address, account, storage_root) in accounts {
+                    cursor.upsert(hashed_address, &account)?;
+                    if storage_root != EMPTY_ROOT_HASH {
+                        storages.push((hashed_address, storage_root));
+                    }
+                    self.queue_code(account.bytecode_hash);
+                }
+                self.downloader.push_storages(storages);
+            }
+            SnapData::Storage { hashed_address, slots } => {
+                let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                for (key, value) in slots {
+                    if !value.is_zero() {
+                        cursor.upsert(hashed_address, &StorageEntry { key, value })?;
+                    }
+                }
+            }
+            SnapData::ByteCodes(codes) => {
+                for (hash, code) in codes {
+                    tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+                }
+            }
+            SnapData::TrieNodes(nodes) => self.heal(tx, nodes)?,
+        }
+        Ok(())
+    }
+
+    /// Queues the download of the code with the given hash, unless it's queued already.
+    fn queue_code(&mut self, code_hash: Option<B256>) {
+        let Some(code_hash) = code_hash.filter(|hash| *hash != KECCAK_EMPTY) else { return };
+        if self.queued_codes.insert(code_hash) {
+            self.downloader.push_byte_codes(vec![code_hash]);
+        }
+    }
+
+    /// Replaces the local state below the given nodes of the pivot's account trie.
+    ///
+    /// Children of branch and extension nodes that don't match the local trie are requested next,
+    /// leaves replace the local accounts below their path.
+    fn heal<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        nodes: Vec<(Nibbles, Bytes)>,
+    ) -> Result<(), StageError> {
+        let mut pending =
+            nodes.into_iter().map(|(path, node)| (path, node.to_vec())).collect::<Vec<_>>();
+        // children referenced by hash, compared against the local trie below
+        let mut children = Vec::new();
+
+        while let Some((path, node)) = pending.pop() {
+            let Ok(node) = TrieNode::decode(&mut &node[..]) else {
+                // nodes are checked against their hash, so this is a bug of the pivot's trie
+                warn!(target: "sync::stages::snap", ?path, "Failed to decode healed trie node");
+                continue
+            };
+
+            match node {
+                TrieNode::EmptyRoot => delete_accounts(tx, &path, None)?,
+                TrieNode::Branch(branch) => {
+                    for (nibble, child) in branch.as_ref().children() {
+                        let mut child_path = path.clone();
+                        child_path.push(nibble);
+                        match child.map(ChildRef::new) {
+                            None => delete_accounts(tx, &child_path, None)?,
+                            Some(ChildRef::Hash(hash)) => children.push((child_path, hash)),
+                            Some(ChildRef::Inline(node)) => pending.push((child_path, node)),
+                        }
+                    }
+                }
+                TrieNode::Extension(extension) => {
+                    let mut child_path = path.clone();
+                    child_path.extend_from_slice(&extension.key);
+                    delete_accounts_outside(tx, &path, &child_path)?;
+                    match ChildRef::new(&extension.child) {
+                        ChildRef::Hash(hash) => children.push((child_path, hash)),
+                        ChildRef::Inline(node) => pending.push((child_path, node)),
+                    }
+                }
+                TrieNode::Leaf(leaf) => {
+                    let mut key = path.clone();
+                    key.extend_from_slice(&leaf.key);
+                    let hashed_address = B256::from_slice(&key.pack());
+                    let Ok(trie_account) = TrieAccount::decode(&mut &leaf.value[..]) else {
+                        warn!(target: "sync::stages::snap", %hashed_address, "Failed to decode healed account");
+                        continue
+                    };
+                    delete_accounts(tx, &path, Some(hashed_address))?;
+                    self.heal_account(tx, hashed_address, trie_account)?;
+                }
+            }
+        }
+
+        if children.is_empty() {
+            return Ok(())
+        }
+
+        // the local trie nodes at the paths of the children
+        let targets =
+            MultiProofTargets::accounts(children.iter().map(|(path, _)| padded_key(path)));
+        let local = Proof::from_tx(tx)
+            .multiproof(targets)
+            .map_err(|e| StageError::Fatal(Box::new(e)))?
+            .account_subtree;
+
+        let mismatched = children
+            .into_iter()
+            .filter(|(path, hash)| local.get(path).is_none_or(|node| keccak256(node) != *hash))
+            .collect::<Vec<_>>();
+        self.downloader.push_trie_nodes(mismatched);
+
+        Ok(())
+    }
+
+    /// Replaces the local account with the healed one, and downloads its storage again if it
+    /// doesn't match the storage root.
+    fn heal_account<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        hashed_address: B256,
+        trie_account: TrieAccount,
+    ) -> Result<(), StageError> {
+        let account = Account {
+            nonce: trie_account.nonce,
+            balance: trie_account.balance,
+            bytecode_hash: (trie_account.code_hash != KECCAK_EMPTY)
+                .then_some(trie_account.code_hash),
+        };
+        tx.put::<tables::HashedAccounts>(hashed_address, account)?;
+
+        if let Some(code_hash) = account.bytecode_hash {
+            if tx.get::<tables::Bytecodes>(code_hash)?.is_none() {
+                self.queued_codes.remove(&code_hash);
+                self.queue_code(Some(code_hash));
+            }
+        }
+
+        let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+            .root()
+            .map_err(|e| StageError::Fatal(Box::new(e)))?;
+        if storage_root != trie_account.storage_root {
+            delete_storage(tx, hashed_address)?;
+            if trie_account.storage_root != EMPTY_ROOT_HASH {
+                self.downloader.push_storages(vec![(hashed_address, trie_account.storage_root)]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the pivot block of the snap sync, if the state of the database was snap synced.
+///
+/// The plain state of a snap synced database only contains the accounts and storage slots that
+/// were changed after the pivot, so the hashed state is written and unwound by the
+/// [`ExecutionStage`](super::ExecutionStage) instead of the hashing stages.
+pub(crate) fn snap_sync_pivot<TX: DbTx>(tx: &TX) -> Result<Option<BlockNumber>, StageError> {
+    let progress = tx.get::<tables::StageCheckpointProgresses>(StageId::SnapSync.to_string())?;
+    Ok(progress.and_then(|progress| progress.try_into().ok()).map(BlockNumber::from_be_bytes))
+}
+
+/// Clears the state and trie tables, except for the contract codes.
+fn clear_state<TX: DbTxMut>(tx: &TX) -> Result<(), StageError> {
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+    Ok(())
+}
+
+/// Returns the header of the block whose state is synced.
+fn pivot_header<Provider: HeaderProvider>(
+    provider: &Provider,
+    number: u64,
+) -> Result<SealedHeader<Provider::Header>, StageError> {
+    Ok(provider
+        .sealed_header(number)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?)
+}
+
+/// Deletes the local accounts whose hashed address starts with `prefix`, except for `keep`.
+fn delete_accounts<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    prefix: &Nibbles,
+    keep: Option<B256>,
+) -> Result<(), StageError> {
+    let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+    let mut entry = cursor.seek(padded_key(prefix))?;
+    while let Some((hashed_address, _)) = entry {
+        if !Nibbles::unpack(hashed_address).starts_with(prefix) {
+            break
+        }
+        if Some(hashed_address) != keep {
+            cursor.delete_current()?;
+            delete_storage(tx, hashed_address)?;
+        }
+        entry = cursor.next()?;
+    }
+    Ok(())
+}
+
+/// Deletes the local accounts below `prefix` that are not below `inner`.
+fn delete_accounts_outside<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    prefix: &Nibbles,
+    inner: &Nibbles,
+) -> Result<(), StageError> {
+    let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+    let mut entry = cursor.seek(padded_key(prefix))?;
+    while let Some((hashed_address, _)) = entry {
+        let key = Nibbles::unpack(hashed_address);
+        if !key.starts_with(prefix) {
+            break
+        }
+        if !key.starts_with(inner) {
+            cursor.delete_current()?;
+            delete_storage(tx, hashed_address)?;
+        }
+        entry = cursor.next()?;
+    }
+    Ok(())
+}
+
+/// Deletes the local storage of the account.
+fn delete_storage<TX: DbTx + DbTxMut>(tx: &TX, hashed_address: B256) -> Result<(), StageError> {
+    let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    if cursor.seek_exact(hashed_address)?.is_some() {
+        cursor.delete_current_duplicates()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        sets::OfflineStages,
+        stages::FinishStage,
+        test_utils::{StorageKind, TestStageDB},
+    };
+    use alloy_primitives::{Address, U256};
+    use reth_config::config::StageConfig;
+    use reth_consensus::test_utils::TestConsensus;
+    use reth_eth_wire_types::snap::{
+        GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage,
+    };
+    use reth_ethereum_primitives::BlockBody;
+    use reth_evm::execute::BasicBlockExecutorProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network::snap_requests::{IncomingSnapRequest, SnapRequestHandler};
+    use reth_network_p2p::{
+        download::DownloadClient,
+        error::{PeerRequestResult, RequestError},
+        priority::Priority,
+        snap::client::SnapResponse,
+    };
+    use reth_network_peers::PeerId;
+    use reth_primitives_traits::SealedBlock;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::MockNodeTypesWithDB, DatabaseProviderFactory,
+        StageCheckpointReader,
+    };
+    use reth_prune_types::{PruneModes, MINIMUM_PRUNING_DISTANCE};
+    use reth_stages_api::{Pipeline, StageSetBuilder};
+    use reth_static_file::StaticFileProducer;
+    use reth_testing_utils::generators::{self, random_header, random_storage_entry};
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+    use tokio::sync::{mpsc, oneshot};
+
+    /// A [`SnapClient`] that sends all requests to a [`SnapRequestHandler`] of the same process.
+    #[derive(Debug, Clone)]
+    struct InProcessSnapClient {
+        peer_id: PeerId,
+        to_handler: mpsc::Sender<IncomingSnapRequest>,
+        /// The number of requests left until requests are sent to the other handler instead.
+        switch: Option<(Arc<AtomicUsize>, mpsc::Sender<IncomingSnapRequest>)>,
+    }
+
+    impl InProcessSnapClient {
+        /// Sends the requests to the handler of `next` once `requests` requests have been sent.
+        fn switch_after(mut self, requests: usize, next: &Self) -> Self {
+            self.switch = Some((Arc::new(AtomicUsize::new(requests)), next.to_handler.clone()));
+            self
+        }
+
+        fn send<T: Send + 'static>(
+            &self,
+            request: impl FnOnce(oneshot::Sender<T>) -> IncomingSnapRequest,
+            into_response: fn(T) -> SnapResponse,
+        ) -> Pin<Box<dyn Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync>> {
+            let to_handler = match &self.switch {
+                Some((remaining, next))
+                    if remaining
+                        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                        .is_err() =>
+                {
+                    next
+                }
+                _ => &self.to_handler,
+            };
+            let (tx, rx) = oneshot::channel();
+            let sent = to_handler.try_send(request(tx)).map_err(|_| RequestError::ChannelClosed);
+            let peer_id = self.peer_id;
+            Box::pin(async move {
+                sent?;
+                let response = rx.await.map_err(|_| RequestError::ChannelClosed)?;
+                Ok((peer_id, into_response(response)).into())
+            })
+        }
+    }
+
+    impl DownloadClient for InProcessSnapClient {
+        fn report_bad_message(&self, peer_id: PeerId) {
+            panic!("bad message from {peer_id}")
+        }
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for InProcessSnapClient {
+        type Output = Pin<Box<dyn Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync>>;
+
+        fn get_account_range_with_priority(
+            &self,
+            request: GetAccountRangeMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let peer_id = self.peer_id;
+            self.send(
+                |response| IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                SnapResponse::AccountRange,
+            )
+        }
+
+        fn get_storage_ranges_with_priority(
+            &self,
+            request: GetStorageRangesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let peer_id = self.peer_id;
+            self.send(
+                |response| IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                SnapResponse::StorageRanges,
+            )
+        }
+
+        fn get_byte_codes_with_priority(
+            &self,
+            request: GetByteCodesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let peer_id = self.peer_id;
+            self.send(
+                |response| IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                SnapResponse::ByteCodes,
+            )
+        }
+
+        fn get_trie_nodes_with_priority(
+            &self,
+            request: GetTrieNodesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let peer_id = self.peer_id;
+            self.send(
+                |response| IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                SnapResponse::TrieNodes,
+            )
+        }
+    }
+
+    /// Waits until the stage is ready to execute with the given provider.
+    async fn execute_ready<Provider>(
+        stage: &mut impl Stage<Provider>,
+        _provider: &Provider,
+        input: ExecInput,
+    ) -> Result<(), StageError> {
+        std::future::poll_fn(|cx| stage.poll_execute_ready(cx, input)).await
+    }
+
+    /// Executes the stage until it's done.
+    async fn run_stage<C: SnapClient + Clone + 'static>(
+        stage: &mut SnapSyncStage<C>,
+        db: &TestStageDB,
+        input: ExecInput,
+    ) -> ExecOutput {
+        loop {
+            let provider = db.factory.database_provider_rw().unwrap();
+            execute_ready(stage, &provider, input).await.unwrap();
+            let output = stage.execute(&provider, input).unwrap();
+            provider.commit().unwrap();
+            if output.done {
+                return output
+            }
+        }
+    }
+
+    /// Serves the given state as the state of block 1 with a [`SnapRequestHandler`].
+    ///
+    /// Returns the database of the peer, the headers of block 0 and 1 and a client for the peer.
+    fn spawn_peer(
+        accounts: Vec<(Address, (Account, Vec<StorageEntry>))>,
+        codes: Vec<Bytes>,
+    ) -> (TestStageDB, [SealedHeader; 2], InProcessSnapClient) {
+        let genesis = random_header(&mut generators::rng(), 0, None);
+        let (source, pivot, client) = spawn_peer_on(vec![genesis.clone()], accounts, codes);
+        (source, [genesis, pivot], client)
+    }
+
+    /// Serves the given state as the state of the block on top of the given ancestors with a
+    /// [`SnapRequestHandler`].
+    ///
+    /// Returns the database of the peer, the header of the block and a client for the peer.
+    fn spawn_peer_on(
+        ancestors: Vec<SealedHeader>,
+        accounts: Vec<(Address, (Account, Vec<StorageEntry>))>,
+        codes: Vec<Bytes>,
+    ) -> (TestStageDB, SealedHeader, InProcessSnapClient) {
+        let mut rng = generators::rng();
+        let source = TestStageDB::default();
+        source.insert_accounts_and_storages(accounts).unwrap();
+        source
+            .commit(|tx| {
+                for code in codes {
+                    tx.put::<tables::Bytecodes>(keccak256(&code), Bytecode::new_raw(code))?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        let provider = source.factory.provider_rw().unwrap();
+        let (state_root, updates) =
+            StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        provider.commit().unwrap();
+
+        let parent = ancestors.last().expect("at least one ancestor");
+        let mut pivot = random_header(&mut rng, parent.number + 1, Some(parent.hash())).unseal();
+        pivot.state_root = state_root;
+        let pivot = SealedHeader::seal_slow(pivot);
+        source.insert_headers(ancestors.iter().chain([&pivot])).unwrap();
+        source
+            .commit(|tx| {
+                Ok(tx.put::<tables::StageCheckpoints>(
+                    StageId::Finish.to_string(),
+                    StageCheckpoint::new(pivot.number),
+                )?)
+            })
+            .unwrap();
+
+        let (to_handler, incoming) = mpsc::channel(64);
+        let handler = SnapRequestHandler::new(
+            BlockchainProvider::new(source.factory.clone()).unwrap(),
+            incoming,
+        );
+        tokio::spawn(handler);
+
+        (source, pivot, InProcessSnapClient { peer_id: PeerId::random(), to_handler, switch: None })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn snap_sync_from_in_process_peer() {
+        reth_tracing::init_test_tracing();
+        let mut rng = generators::rng();
+
+        // enough accounts and slots to span multiple responses
+        let mut accounts = Vec::new();
+        let mut codes = Vec::new();
+        for idx in 0..2_000u64 {
+            let code = (idx % 10 == 0).then(|| Bytes::from(idx.to_be_bytes().repeat(4)));
+            let account = Account {
+                nonce: idx,
+                balance: U256::from(idx + 1),
+                bytecode_hash: code.as_ref().map(keccak256),
+            };
+            let slots = if idx % 50 == 0 {
+                2_000
+            } else if idx % 5 == 0 {
+                5
+            } else {
+                0
+            };
+            let storage =
+                (0..slots).map(|_| random_storage_entry(&mut rng, 0..u64::MAX)).collect::<Vec<_>>();
+            accounts.push((Address::random(), (account, storage)));
+            codes.extend(code);
+        }
+        let (source, headers, client) = spawn_peer(accounts, codes);
+
+        let db = TestStageDB::default();
+        db.insert_headers(headers.iter()).unwrap();
+
+        let mut stage = SnapSyncStage::new(client);
+        let output =
+            run_stage(&mut stage, &db, ExecInput { target: Some(1), checkpoint: None }).await;
+        assert_eq!(output.checkpoint, StageCheckpoint::new(1));
+
+        // the synced state matches the served one and the pipeline continues from the pivot
+        assert_eq!(
+            db.table::<tables::HashedAccounts>().unwrap(),
+            source.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::HashedStorages>().unwrap(),
+            source.table::<tables::HashedStorages>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::Bytecodes>().unwrap(),
+            source.table::<tables::Bytecodes>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::AccountsTrie>().unwrap(),
+            source.table::<tables::AccountsTrie>().unwrap()
+        );
+        let provider = db.factory.provider().unwrap();
+        for stage_id in SNAP_SYNCED_STAGES {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(1))
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn heal_diverged_state() {
+        let mut rng = generators::rng();
+        let accounts = (0..100u64)
+            .map(|idx| {
+                let account = Account { nonce: idx, balance: U256::from(idx), bytecode_hash: None };
+                let storage = vec![random_storage_entry(&mut rng, 0..u64::MAX)];
+                (Address::random(), (account, storage))
+            })
+            .collect::<Vec<_>>();
+        let (source, headers, client) = spawn_peer(accounts.clone(), Vec::new());
+        let state_root = headers[1].state_root;
+
+        // one account changed, one is missing and one doesn't exist in the pivot
+        let db = TestStageDB::default();
+        let mut local = accounts[1..].to_vec();
+        local[0].1 .0.balance += U256::from(1);
+        local.push((Address::random(), (Account::default(), Vec::new())));
+        db.insert_accounts_and_storages(local).unwrap();
+
+        let mut sync = SnapSync {
+            pivot: headers[1].block_with_parent(),
+            pivot_root: state_root,
+            downloader: SnapDownloader::new(client, state_root),
+            queued_codes: B256Set::default(),
+            heal_rounds: 0,
+        };
+        sync.downloader.push_trie_nodes(vec![(Nibbles::default(), state_root)]);
+        loop {
+            std::future::poll_fn(|cx| sync.downloader.poll(cx)).await;
+            let provider = db.factory.database_provider_rw().unwrap();
+            for data in sync.downloader.take_buffered() {
+                sync.write(provider.tx_ref(), data).unwrap();
+            }
+            provider.commit().unwrap();
+            if sync.downloader.is_idle() {
+                break
+            }
+        }
+
+        assert_eq!(
+            db.table::<tables::HashedAccounts>().unwrap(),
+            source.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::HashedStorages>().unwrap(),
+            source.table::<tables::HashedStorages>().unwrap()
+        );
+        let root = db.query(|tx| Ok(StateRoot::from_tx(tx).root().unwrap())).unwrap();
+        assert_eq!(root, state_root);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn repivot_on_stale_root() {
+        let mut rng = generators::rng();
+        let accounts = (0..1_000u64)
+            .map(|idx| {
+                let account = Account { nonce: idx, balance: U256::from(idx), bytecode_hash: None };
+                let slots = if idx % 4 == 0 { 3 } else { 0 };
+                let storage =
+                    (0..slots).map(|_| random_storage_entry(&mut rng, 0..u64::MAX)).collect();
+                (Address::random(), (account, storage))
+            })
+            .collect::<Vec<_>>();
+        let (_, headers, first) = spawn_peer(accounts.clone(), Vec::new());
+
+        // in the next block, an account and a storage changed, an account was removed and one
+        // was added
+        let mut next = accounts;
+        next[1].1 .0.balance += U256::from(1);
+        next[4].1 .1 = vec![random_storage_entry(&mut rng, 0..u64::MAX)];
+        next.remove(10);
+        next.push((Address::random(), (Account { nonce: 1, ..Default::default() }, Vec::new())));
+
+        // the peer that serves the next block doesn't serve the state of the first pivot
+        let mut unknown = headers[1].clone().unseal();
+        unknown.state_root = B256::random();
+        let (source, pivot, second) = spawn_peer_on(
+            vec![headers[0].clone(), SealedHeader::seal_slow(unknown)],
+            next,
+            Vec::new(),
+        );
+
+        let db = TestStageDB::default();
+        db.insert_headers(headers.iter().chain([&pivot])).unwrap();
+
+        // some account ranges are served before the state of the first pivot goes stale
+        let mut stage = SnapSyncStage::new(first.switch_after(8, &second));
+        let output =
+            run_stage(&mut stage, &db, ExecInput { target: Some(1), checkpoint: None }).await;
+        assert_eq!(output, ExecOutput::done(StageCheckpoint::new(0)));
+        let downloaded = db.table::<tables::HashedAccounts>().unwrap();
+        assert!(!downloaded.is_empty());
+
+        // the next run moves to the newer block and keeps the downloaded ranges
+        let output =
+            run_stage(&mut stage, &db, ExecInput { target: Some(2), checkpoint: None }).await;
+        assert_eq!(output.checkpoint, StageCheckpoint::new(2));
+        assert_eq!(
+            db.table::<tables::HashedAccounts>().unwrap(),
+            source.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::HashedStorages>().unwrap(),
+            source.table::<tables::HashedStorages>().unwrap()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn snap_sync_pipeline() {
+        let mut rng = generators::rng();
+        let accounts = (0..500u64)
+            .map(|idx| {
+                let account = Account { nonce: idx, balance: U256::from(idx), bytecode_hash: None };
+                let slots = if idx % 10 == 0 { 3 } else { 0 };
+                let storage =
+                    (0..slots).map(|_| random_storage_entry(&mut rng, 0..u64::MAX)).collect();
+                (Address::random(), (account, storage))
+            })
+            .collect::<Vec<_>>();
+        let (source, headers, client) = spawn_peer(accounts, Vec::new());
+
+        // the headers and bodies are downloaded by the online stages
+        let db = TestStageDB::default();
+        let blocks = headers
+            .iter()
+            .map(|header| SealedBlock::from_sealed_parts(header.clone(), BlockBody::default()))
+            .collect::<Vec<_>>();
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // the stages are set up like the ones of a node that snap syncs
+        let prune_modes = PruneModes {
+            receipts: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+            ..Default::default()
+        };
+        let executor = BasicBlockExecutorProvider::new(EthEvmConfig::mainnet());
+        let stages = StageSetBuilder::default()
+            .add_set(OfflineStages::new(
+                executor,
+                Arc::new(TestConsensus::default()),
+                StageConfig::default(),
+                prune_modes,
+            ))
+            .add_stage(FinishStage)
+            .add_before(SnapSyncStage::new(client), StageId::SenderRecovery);
+        let mut pipeline =
+            Pipeline::<MockNodeTypesWithDB>::builder().with_max_block(1).add_stages(stages).build(
+                db.factory.clone(),
+                StaticFileProducer::new(db.factory.clone(), PruneModes::default()),
+            );
+        pipeline.run().await.unwrap();
+
+        // the state was synced instead of executed, and all stages reached the pivot
+        assert_eq!(
+            db.table::<tables::HashedAccounts>().unwrap(),
+            source.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            db.table::<tables::HashedStorages>().unwrap(),
+            source.table::<tables::HashedStorages>().unwrap()
+        );
+        let provider = db.factory.provider().unwrap();
+        for stage_id in [
+            StageId::SnapSync,
+            StageId::SenderRecovery,
+            StageId::Execution,
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::TransactionLookup,
+            StageId::IndexStorageHistory,
+            StageId::IndexAccountHistory,
+            StageId::Finish,
+        ] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap().map(|c| c.block_number),
+                Some(1),
+                "{stage_id}"
+            );
+        }
+        assert_eq!(snap_sync_pivot(provider.tx_ref()).unwrap(), Some(1));
+    }
+}
@@ -0,0 +1,278 @@
+//! Helpers for working with the trie nodes served by snap peers.
+
+use alloy_primitives::{keccak256, map::B256Map, Bytes, B256};
+use alloy_rlp::Decodable;
+use reth_trie::{HashBuilder, Nibbles, TrieNode, EMPTY_ROOT_HASH};
+use std::cmp::Ordering;
+
+/// Checks that `leaves` are all entries of the trie with the given root from `origin` up to the
+/// last leaf, using the proof of the range boundaries.
+///
+/// The leaves must be sorted by key and start at or after `origin`. Without leaves, the proof must
+/// show that the trie has no entries at or after `origin`.
+///
+/// The parts of the trie outside of the range are taken from the proof, so that the root can be
+/// computed from them and the leaves: any leaf that is missing from the range, added to it or
+/// modified changes the root.
+pub(crate) fn verify_range(
+    root: B256,
+    origin: B256,
+    leaves: &[(B256, Vec<u8>)],
+    proof: &[Bytes],
+) -> bool {
+    if root == EMPTY_ROOT_HASH {
+        return leaves.is_empty()
+    }
+
+    let nodes: B256Map<&Bytes> = proof.iter().map(|node| (keccak256(node), node)).collect();
+    let range = ProvenRange {
+        nodes,
+        origin: Nibbles::unpack(origin),
+        last: leaves.last().map(|(key, _)| Nibbles::unpack(key)),
+    };
+
+    let mut items = Vec::new();
+    if range.collect(Nibbles::default(), ChildRef::Hash(root), &mut items).is_err() {
+        return false
+    }
+    items.extend(
+        leaves.iter().map(|(key, value)| (Nibbles::unpack(key), RangeItem::Leaf(value.clone()))),
+    );
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut hash_builder = HashBuilder::default();
+    for (path, item) in items {
+        match item {
+            RangeItem::Leaf(value) => hash_builder.add_leaf(path, &value),
+            RangeItem::Branch(hash) => hash_builder.add_branch(path, hash, false),
+        }
+    }
+    hash_builder.root() == root
+}
+
+/// The position of a trie path relative to a range of keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangePosition {
+    /// All keys below the path are before the range.
+    Left,
+    /// The path leads to the first or the last key of the range.
+    Edge,
+    /// All keys below the path are within the range.
+    Inside,
+    /// All keys below the path are after the range.
+    Right,
+}
+
+/// A part of the trie that is needed to compute the root of a range.
+enum RangeItem {
+    /// A leaf with its value.
+    Leaf(Vec<u8>),
+    /// The hash of a subtree.
+    Branch(B256),
+}
+
+/// A range of keys together with the proof nodes of its boundaries.
+struct ProvenRange<'a> {
+    /// The proof nodes by their hash.
+    nodes: B256Map<&'a Bytes>,
+    /// The first key of the range.
+    origin: Nibbles,
+    /// The last key of the range, if the range is unbounded the trie has no keys after `origin`.
+    last: Option<Nibbles>,
+}
+
+impl ProvenRange<'_> {
+    /// Returns the position of the path relative to the range.
+    fn position(&self, path: &Nibbles) -> RangePosition {
+        let left = path[..].cmp(&self.origin[..path.len()]);
+        let right = self.last.as_ref().map(|last| path[..].cmp(&last[..path.len()]));
+        match (left, right) {
+            (Ordering::Less, _) => RangePosition::Left,
+            (_, Some(Ordering::Greater)) => RangePosition::Right,
+            (Ordering::Equal, _) | (_, Some(Ordering::Equal)) => RangePosition::Edge,
+            _ => RangePosition::Inside,
+        }
+    }
+
+    /// Collects the leaves and subtree hashes outside of the range below the node at `path`.
+    fn collect(
+        &self,
+        path: Nibbles,
+        node: ChildRef,
+        items: &mut Vec<(Nibbles, RangeItem)>,
+    ) -> alloy_rlp::Result<()> {
+        let outside = match self.position(&path) {
+            RangePosition::Inside => return Ok(()),
+            RangePosition::Edge => false,
+            RangePosition::Left | RangePosition::Right => true,
+        };
+
+        let node = match node {
+            ChildRef::Hash(hash) if outside => {
+                items.push((path, RangeItem::Branch(hash)));
+                return Ok(())
+            }
+            ChildRef::Hash(hash) => self
+                .nodes
+                .get(&hash)
+                .ok_or(alloy_rlp::Error::Custom("missing proof node"))?
+                .to_vec(),
+            // nodes shorter than a hash are embedded into their parent
+            ChildRef::Inline(node) => node,
+        };
+
+        match TrieNode::decode(&mut &node[..])? {
+            TrieNode::EmptyRoot => {}
+            TrieNode::Branch(branch) => {
+                for (nibble, child) in branch.as_ref().children() {
+                    if let Some(child) = child {
+                        let mut child_path = path.clone();
+                        child_path.push(nibble);
+                        self.collect(child_path, ChildRef::new(child), items)?;
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let mut child_path = path;
+                child_path.extend_from_slice(&extension.key);
+                self.collect(child_path, ChildRef::new(&extension.child), items)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let mut key = path;
+                key.extend_from_slice(&leaf.key);
+                // leaves within the range must be part of the range itself
+                if matches!(self.position(&key), RangePosition::Left | RangePosition::Right) {
+                    items.push((key, RangeItem::Leaf(leaf.value)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a trie path in the compact (hex-prefix) encoding used by `GetTrieNodes`.
+pub(crate) fn encode_compact_path(path: &Nibbles) -> Bytes {
+    let mut encoded = Vec::with_capacity(path.len() / 2 + 1);
+    let nibbles = if path.len() % 2 == 1 {
+        encoded.push(0x10 | path[0]);
+        path.slice(1..)
+    } else {
+        encoded.push(0x00);
+        path.clone()
+    };
+    encoded.extend(nibbles.pack());
+    encoded.into()
+}
+
+/// Returns the smallest key that starts with the given path.
+pub(crate) fn padded_key(path: &Nibbles) -> B256 {
+    B256::right_padding_from(&path.pack())
+}
+
+/// Reference to the child of a branch or extension node.
+pub(crate) enum ChildRef {
+    /// The child is stored separately and referenced by its hash.
+    Hash(B256),
+    /// The child is shorter than a hash and embedded into its parent.
+    Inline(Vec<u8>),
+}
+
+impl ChildRef {
+    /// Creates the reference from the RLP encoded child of a branch or extension node.
+    pub(crate) fn new(child: &reth_trie::RlpNode) -> Self {
+        child.as_hash().map_or_else(|| Self::Inline(child.to_vec()), Self::Hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_trie_common::proof::ProofRetainer;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn compact_path_encoding() {
+        assert_eq!(encode_compact_path(&Nibbles::default()), Bytes::from_static(&[0x00]));
+        assert_eq!(
+            encode_compact_path(&Nibbles::from_nibbles([0x1, 0x2, 0x3])),
+            Bytes::from_static(&[0x11, 0x23])
+        );
+        assert_eq!(
+            encode_compact_path(&Nibbles::from_nibbles([0x1, 0x2, 0x3, 0x4])),
+            Bytes::from_static(&[0x00, 0x12, 0x34])
+        );
+    }
+
+    /// Returns the root of the trie with the given leaves and the proof of the given keys.
+    fn root_with_proof(
+        leaves: &BTreeMap<B256, Vec<u8>>,
+        targets: impl IntoIterator<Item = B256>,
+    ) -> (B256, Vec<Bytes>) {
+        let retainer = targets.into_iter().map(Nibbles::unpack).collect::<ProofRetainer>();
+        let mut hb = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in leaves {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hb.root();
+        let proof = hb
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect::<Vec<_>>();
+        (root, proof)
+    }
+
+    #[test]
+    fn verify_range_with_proof() {
+        let leaves = (0..64u64)
+            .map(|i| {
+                let value = alloy_rlp::encode_fixed_size(&U256::from(i + 1)).to_vec();
+                (keccak256(B256::from(U256::from(i))), value)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let keys = leaves.keys().copied().collect::<Vec<_>>();
+        let range = |from: usize, to: usize| {
+            keys[from..=to].iter().map(|key| (*key, leaves[key].clone())).collect::<Vec<_>>()
+        };
+
+        // the whole trie
+        let (root, proof) = root_with_proof(&leaves, [B256::ZERO, keys[63]]);
+        assert!(verify_range(root, B256::ZERO, &range(0, 63), &proof));
+
+        // a range that starts at a key
+        let (_, proof) = root_with_proof(&leaves, [keys[10], keys[40]]);
+        assert!(verify_range(root, keys[10], &range(10, 40), &proof));
+
+        // a range that starts between two keys
+        let origin = B256::from(U256::from_be_bytes(keys[9].0) + U256::from(1));
+        let (_, proof) = root_with_proof(&leaves, [origin, keys[40]]);
+        assert!(verify_range(root, origin, &range(10, 40), &proof));
+
+        // missing, modified and additional leaves
+        let mut missing = range(10, 40);
+        missing.remove(15);
+        assert!(!verify_range(root, origin, &missing, &proof));
+        let mut modified = range(10, 40);
+        modified[15].1 = alloy_rlp::encode_fixed_size(&U256::from(1000)).to_vec();
+        assert!(!verify_range(root, origin, &modified, &proof));
+        let mut additional = range(10, 40);
+        additional
+            .insert(15, (B256::from(U256::from_be_bytes(keys[24].0) + U256::from(1)), vec![0x01]));
+        assert!(!verify_range(root, origin, &additional, &proof));
+
+        // the first leaf of the range is missing
+        assert!(!verify_range(root, origin, &range(11, 40), &proof));
+
+        // the proof of the boundaries is required
+        assert!(!verify_range(root, origin, &range(10, 40), &[]));
+
+        // no keys after the origin
+        let after_last = B256::from(U256::from_be_bytes(keys[63].0) + U256::from(1));
+        let (_, proof) = root_with_proof(&leaves, [after_last]);
+        assert!(verify_range(root, after_last, &[], &proof));
+        let (_, proof) = root_with_proof(&leaves, [origin]);
+        assert!(!verify_range(root, origin, &[], &proof));
+    }
+}
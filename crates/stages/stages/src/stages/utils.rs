@@ -10,20 +10,31 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
+    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError, ProviderResult,
     StaticFileProviderFactory,
 };
 use reth_stages_api::StageError;
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Range, RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
 const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
+/// Number of blocks of changesets read from static files at once when collecting history indices.
+const STATIC_FILE_CHANGESETS_CHUNK_SIZE: u64 = 1_000;
+
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
 ///
+/// Changesets of the blocks that were moved to the static file `segment` are read with
+/// `static_file_changesets`, the rest are walked in the database with the range of keys returned
+/// by `database_range`.
+///
 /// ## Process
 /// The function utilizes a `HashMap` cache with a structure of `PartialKey` (`P`) (Address or
 /// Address.StorageKey) to `BlockNumberList`. When the cache exceeds its capacity, its contents are
@@ -38,25 +49,31 @@ const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 ///
 /// As a result, the `Collector` will contain entries such as `(Address1.3, [1,2,3])` and
 /// `(Address1.300, [100,300])`. The entries may be stored across one or more files.
-pub(crate) fn collect_history_indices<Provider, CS, H, P>(
+#[expect(clippy::too_many_arguments)]
+pub(crate) fn collect_history_indices<Provider, CS, H, P, R>(
     provider: &Provider,
-    range: impl RangeBounds<CS::Key>,
+    range: RangeInclusive<BlockNumber>,
+    segment: StaticFileSegment,
+    static_file_changesets: impl Fn(
+        &StaticFileProvider<Provider::Primitives>,
+        Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(CS::Key, CS::Value)>>,
+    database_range: impl FnOnce(RangeInclusive<BlockNumber>) -> R,
     sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
     partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
     etl_config: &EtlConfig,
 ) -> Result<Collector<H::Key, H::Value>, StageError>
 where
-    Provider: DBProvider,
+    Provider: DBProvider + StaticFileProviderFactory,
     CS: Table,
     H: Table<Value = BlockNumberList>,
     P: Copy + Eq + Hash,
+    R: RangeBounds<CS::Key>,
 {
-    let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
-
     let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
     let mut cache: HashMap<P, Vec<u64>> = HashMap::default();
 
-    let mut collect = |cache: &HashMap<P, Vec<u64>>| {
+    let collect = |collector: &mut Collector<H::Key, H::Value>, cache: &HashMap<P, Vec<u64>>| {
         for (key, indices) in cache {
             let last = indices.last().expect("qed");
             collector.insert(
@@ -67,32 +84,57 @@ where
         Ok::<(), StageError>(())
     };
 
-    // observability
-    let total_changesets = provider.tx_ref().entries::<CS>()?;
-    let interval = (total_changesets / 1000).max(1);
-
     let mut flush_counter = 0;
     let mut current_block_number = u64::MAX;
-    for (idx, entry) in changeset_cursor.walk_range(range)?.enumerate() {
-        let (block_number, key) = partial_key_factory(entry?);
+    let mut insert = |collector: &mut Collector<H::Key, H::Value>, entry: (CS::Key, CS::Value)| {
+        let (block_number, key) = partial_key_factory(entry);
         cache.entry(key).or_default().push(block_number);
 
-        if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
-            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
-        }
-
         // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
         if current_block_number != block_number {
             current_block_number = block_number;
             flush_counter += 1;
             if flush_counter > DEFAULT_CACHE_THRESHOLD {
-                collect(&cache)?;
+                collect(collector, &cache)?;
                 cache.clear();
                 flush_counter = 0;
             }
         }
+        Ok::<(), StageError>(())
+    };
+
+    // Changesets of the oldest blocks may have been moved to static files.
+    let static_file_provider = provider.static_file_provider();
+    let static_file_range = *range.start()..
+        static_file_provider
+            .get_highest_static_file_block(segment)
+            .map_or(0, |highest| highest + 1)
+            .clamp(*range.start(), *range.end() + 1);
+    for start in static_file_range.clone().step_by(STATIC_FILE_CHANGESETS_CHUNK_SIZE as usize) {
+        let end = (start + STATIC_FILE_CHANGESETS_CHUNK_SIZE).min(static_file_range.end);
+        if (start - range.start()) % (STATIC_FILE_CHANGESETS_CHUNK_SIZE * 100) == 0 {
+            info!(target: "sync::stages::index_history", block = start, end = static_file_range.end, "Collecting indices from static files");
+        }
+        for entry in static_file_changesets(&static_file_provider, start..end)? {
+            insert(&mut collector, entry)?;
+        }
+    }
+
+    let database_range = database_range(static_file_range.end..=*range.end());
+    let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
+
+    // observability
+    let total_changesets = provider.tx_ref().entries::<CS>()?;
+    let interval = (total_changesets / 1000).max(1);
+
+    for (idx, entry) in changeset_cursor.walk_range(database_range)?.enumerate() {
+        insert(&mut collector, entry?)?;
+
+        if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
+        }
     }
-    collect(&cache)?;
+    collect(&mut collector, &cache)?;
 
     Ok(collector)
 }
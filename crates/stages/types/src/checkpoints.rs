@@ -240,7 +240,9 @@ impl StageCheckpoint {
             StageId::StorageHashing => {
                 StageUnitCheckpoint::Storage(StorageHashingCheckpoint::default())
             }
-            StageId::IndexStorageHistory | StageId::IndexAccountHistory => {
+            StageId::IndexStorageHistory |
+            StageId::IndexAccountHistory |
+            StageId::IndexAddressAppearances => {
                 StageUnitCheckpoint::IndexHistory(IndexHistoryCheckpoint::default())
             }
             _ => return self,
@@ -8,8 +8,10 @@ pub enum StageId {
         note = "Static Files are generated outside of the pipeline and do not require a separate stage"
     )]
     StaticFile,
+    Era,
     Headers,
     Bodies,
+    SnapSync,
     SenderRecovery,
     Execution,
     PruneSenderRecovery,
@@ -20,6 +22,7 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    IndexAddressAppearances,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -28,9 +31,11 @@ pub enum StageId {
 
 impl StageId {
     /// All supported Stages
-    pub const ALL: [Self; 14] = [
+    pub const ALL: [Self; 17] = [
+        Self::Era,
         Self::Headers,
         Self::Bodies,
+        Self::SnapSync,
         Self::SenderRecovery,
         Self::Execution,
         Self::PruneSenderRecovery,
@@ -41,12 +46,13 @@ impl StageId {
         Self::TransactionLookup,
         Self::IndexStorageHistory,
         Self::IndexAccountHistory,
+        Self::IndexAddressAppearances,
         Self::Prune,
         Self::Finish,
     ];
 
     /// Stages that require state.
-    pub const STATE_REQUIRED: [Self; 9] = [
+    pub const STATE_REQUIRED: [Self; 10] = [
         Self::Execution,
         Self::PruneSenderRecovery,
         Self::MerkleUnwind,
@@ -55,6 +61,7 @@ impl StageId {
         Self::MerkleExecute,
         Self::IndexStorageHistory,
         Self::IndexAccountHistory,
+        Self::IndexAddressAppearances,
         Self::Prune,
     ];
 
@@ -63,8 +70,10 @@ impl StageId {
         match self {
             #[expect(deprecated)]
             Self::StaticFile => "StaticFile",
+            Self::Era => "Era",
             Self::Headers => "Headers",
             Self::Bodies => "Bodies",
+            Self::SnapSync => "SnapSync",
             Self::SenderRecovery => "SenderRecovery",
             Self::Execution => "Execution",
             Self::PruneSenderRecovery => "PruneSenderRecovery",
@@ -75,15 +84,17 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressAppearances => "IndexAddressAppearances",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
         }
     }
 
-    /// Returns true if it's a downloading stage [`StageId::Headers`] or [`StageId::Bodies`]
+    /// Returns true if it's a downloading stage [`StageId::Era`], [`StageId::Headers`] or
+    /// [`StageId::Bodies`]
     pub const fn is_downloading_stage(&self) -> bool {
-        matches!(self, Self::Headers | Self::Bodies)
+        matches!(self, Self::Era | Self::Headers | Self::Bodies)
     }
 
     /// Returns `true` if it's [`TransactionLookup`](StageId::TransactionLookup) stage.
@@ -109,8 +120,10 @@ mod tests {
 
     #[test]
     fn stage_id_as_string() {
+        assert_eq!(StageId::Era.to_string(), "Era");
         assert_eq!(StageId::Headers.to_string(), "Headers");
         assert_eq!(StageId::Bodies.to_string(), "Bodies");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
         assert_eq!(StageId::SenderRecovery.to_string(), "SenderRecovery");
         assert_eq!(StageId::Execution.to_string(), "Execution");
         assert_eq!(StageId::MerkleUnwind.to_string(), "MerkleUnwind");
@@ -119,6 +132,7 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexAddressAppearances.to_string(), "IndexAddressAppearances");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
@@ -127,16 +141,17 @@ mod tests {
 
     #[test]
     fn is_downloading_stage() {
+        assert!(StageId::Era.is_downloading_stage());
         assert!(StageId::Headers.is_downloading_stage());
         assert!(StageId::Bodies.is_downloading_stage());
 
         assert!(!StageId::Execution.is_downloading_stage());
     }
 
-    // Multiple places around the codebase assume headers is the first stage.
+    // Multiple places around the codebase assume era is the first stage.
     // Feel free to remove this test if the assumption changes.
     #[test]
-    fn stage_all_headers_first() {
-        assert_eq!(*StageId::ALL.first().unwrap(), StageId::Headers);
+    fn stage_all_era_first() {
+        assert_eq!(*StageId::ALL.first().unwrap(), StageId::Era);
     }
 }
@@ -0,0 +1,46 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::DbCursorRO, models::StaticFileAccountChangeSet, tables, transaction::DbTx,
+};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::AccountChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct AccountChangeSets;
+
+impl<Provider> Segment<Provider> for AccountChangeSets
+where
+    Provider: StaticFileProviderFactory + DBProvider,
+{
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::AccountChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::AccountChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+
+        for block in block_range {
+            let changes = changesets_cursor
+                .walk_range(block..=block)?
+                .map(|entry| entry.map(|(_, change)| change))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            static_file_writer
+                .append_account_changeset(&StaticFileAccountChangeSet { changes }, block)?;
+        }
+
+        Ok(())
+    }
+}
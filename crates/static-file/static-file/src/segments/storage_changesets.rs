@@ -0,0 +1,51 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{BlockNumberAddress, StaticFileStorageChangeSet, StorageBeforeTx},
+    tables,
+    transaction::DbTx,
+};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::StorageChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct StorageChangeSets;
+
+impl<Provider> Segment<Provider> for StorageChangeSets
+where
+    Provider: StaticFileProviderFactory + DBProvider,
+{
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::StorageChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::StorageChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+
+        for block in block_range {
+            let changes = changesets_cursor
+                .walk_range(BlockNumberAddress::range(block..=block))?
+                .map(|entry| {
+                    entry.map(|(key, entry)| StorageBeforeTx { address: key.address(), entry })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            static_file_writer
+                .append_storage_changeset(&StaticFileStorageChangeSet { changes }, block)?;
+        }
+
+        Ok(())
+    }
+}
@@ -140,6 +140,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -175,19 +181,29 @@ where
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
+    /// Changesets are only copied once the history indices have been built from them.
+    ///
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints = [
+            StageId::Headers,
+            StageId::Execution,
+            StageId::Bodies,
+            StageId::IndexAccountHistory,
+            StageId::IndexStorageHistory,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
             block_meta: stages_checkpoints[2],
+            account_changesets: stages_checkpoints[3],
+            storage_changesets: stages_checkpoints[4],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -230,6 +246,29 @@ where
             block_meta: finalized_block_numbers.block_meta.and_then(|finalized_block_number| {
                 self.get_static_file_target(highest_static_files.block_meta, finalized_block_number)
             }),
+            // StaticFile changesets only if they're not pruned according to the user configuration.
+            // Changesets in static files can't be pruned, so the node refuses to start with history
+            // pruning enabled once they're moved.
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -259,14 +298,20 @@ mod tests {
     use crate::static_file_producer::{
         StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
     };
-    use alloy_primitives::{B256, U256};
+    use alloy_primitives::{Address, B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db_api::{
+        database::Database,
+        models::{AccountBeforeTx, BlockNumberAddress},
+        tables,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives_traits::{Account, StorageEntry};
     use reth_provider::{
         providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
         ProviderFactory, StaticFileProviderFactory,
     };
-    use reth_prune_types::PruneModes;
+    use reth_prune_types::{PruneMode, PruneModes};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::generators::{
@@ -327,6 +372,8 @@ mod tests {
                 receipts: Some(1),
                 transactions: Some(1),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -335,7 +382,9 @@ mod tests {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
                 transactions: Some(0..=1),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -345,7 +394,9 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
 
@@ -355,6 +406,8 @@ mod tests {
                 receipts: Some(3),
                 transactions: Some(3),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -363,7 +416,9 @@ mod tests {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
                 transactions: Some(2..=3),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -373,7 +428,9 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
 
@@ -383,6 +440,8 @@ mod tests {
                 receipts: Some(4),
                 transactions: Some(4),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -391,7 +450,9 @@ mod tests {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
                 transactions: Some(4..=4),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(
@@ -404,9 +465,98 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
+            }
+        );
+    }
+
+    #[test]
+    fn run_changesets() {
+        let db = TestStageDB::default();
+
+        let address = Address::with_last_byte(1);
+        let account_changesets = (0..=3)
+            .map(|block| {
+                let info = Some(Account { nonce: block, ..Default::default() });
+                (block, AccountBeforeTx { address, info })
+            })
+            .collect::<Vec<_>>();
+        let storage_changesets = (0..=3)
+            .map(|block| {
+                let entry = StorageEntry { key: B256::with_last_byte(1), value: U256::from(block) };
+                (BlockNumberAddress((block, address)), entry)
+            })
+            .collect::<Vec<_>>();
+        db.commit(|tx| {
+            for (block, changeset) in &account_changesets {
+                tx.put::<tables::AccountChangeSets>(*block, changeset.clone())?;
+            }
+            for (key, entry) in &storage_changesets {
+                tx.put::<tables::StorageChangeSets>(*key, *entry)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let static_file_producer =
+            StaticFileProducerInner::new(db.factory.clone(), PruneModes::default());
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_changesets: Some(2),
+                storage_changesets: Some(2),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+                account_changesets: Some(0..=2),
+                storage_changesets: Some(0..=2),
             }
         );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = db.factory.static_file_provider();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(2)
+        );
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(2)
+        );
+        assert_eq!(
+            static_file_provider.account_changesets_range(0..3).unwrap(),
+            account_changesets[..3]
+        );
+        assert_eq!(
+            static_file_provider.storage_changesets_range(0..3).unwrap(),
+            storage_changesets[..3]
+        );
+
+        // Changesets are not copied if they're pruned according to the user configuration
+        let static_file_producer = StaticFileProducerInner::new(
+            db.factory,
+            PruneModes { account_history: Some(PruneMode::Full), ..Default::default() },
+        );
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(targets.account_changesets, None);
+        assert_eq!(targets.storage_changesets, Some(3..=3));
     }
 
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
@@ -434,6 +584,8 @@ mod tests {
                         receipts: Some(1),
                         transactions: Some(1),
                         block_meta: None,
+                        account_changesets: None,
+                        storage_changesets: None,
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
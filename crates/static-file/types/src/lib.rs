@@ -39,6 +39,12 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub block_meta: Option<BlockNumber>,
+    /// Highest static file block of account changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest static file block of storage changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub storage_changesets: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -49,6 +55,8 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
             StaticFileSegment::BlockMeta => self.block_meta,
+            StaticFileSegment::AccountChangeSets => self.account_changesets,
+            StaticFileSegment::StorageChangeSets => self.storage_changesets,
         }
     }
 
@@ -59,12 +67,22 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
             StaticFileSegment::BlockMeta => &mut self.block_meta,
+            StaticFileSegment::AccountChangeSets => &mut self.account_changesets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_changesets,
         }
     }
 
     /// Returns an iterator over all static file segments
     fn iter(&self) -> impl Iterator<Item = Option<BlockNumber>> {
-        [self.headers, self.transactions, self.receipts, self.block_meta].into_iter()
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.block_meta,
+            self.account_changesets,
+            self.storage_changesets,
+        ]
+        .into_iter()
     }
 
     /// Returns the minimum block of all segments.
@@ -89,6 +107,10 @@ pub struct StaticFileTargets {
     pub transactions: Option<RangeInclusive<BlockNumber>>,
     /// Targeted range of block meta.
     pub block_meta: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of account changesets.
+    pub account_changesets: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of storage changesets.
+    pub storage_changesets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
@@ -97,7 +119,9 @@ impl StaticFileTargets {
         self.headers.is_some() ||
             self.receipts.is_some() ||
             self.transactions.is_some() ||
-            self.block_meta.is_some()
+            self.block_meta.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some()
     }
 
     /// Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -108,6 +132,8 @@ impl StaticFileTargets {
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
             (self.block_meta.as_ref(), static_files.block_meta),
+            (self.account_changesets.as_ref(), static_files.account_changesets),
+            (self.storage_changesets.as_ref(), static_files.storage_changesets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -142,6 +168,8 @@ mod tests {
             receipts: Some(200),
             transactions: None,
             block_meta: None,
+            account_changesets: None,
+            storage_changesets: None,
         };
 
         // Test for headers segment
@@ -173,6 +201,14 @@ mod tests {
         // Modify block meta value
         *files.as_mut(StaticFileSegment::BlockMeta) = Some(350);
         assert_eq!(files.block_meta, Some(350));
+
+        // Modify account changesets value
+        *files.as_mut(StaticFileSegment::AccountChangeSets) = Some(450);
+        assert_eq!(files.account_changesets, Some(450));
+
+        // Modify storage changesets value
+        *files.as_mut(StaticFileSegment::StorageChangeSets) = Some(550);
+        assert_eq!(files.storage_changesets, Some(550));
     }
 
     #[test]
@@ -182,6 +218,8 @@ mod tests {
             receipts: Some(100),
             transactions: None,
             block_meta: None,
+            account_changesets: None,
+            storage_changesets: None,
         };
 
         // Minimum value among the available segments
@@ -199,6 +237,8 @@ mod tests {
             receipts: Some(100),
             transactions: Some(500),
             block_meta: Some(500),
+            account_changesets: Some(400),
+            storage_changesets: None,
         };
 
         // Maximum value among the available segments
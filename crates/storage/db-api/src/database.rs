@@ -3,7 +3,7 @@ use crate::{
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, path::Path, sync::Arc};
 
 /// Main Database trait that can open read-only and read-write transactions.
 ///
@@ -49,6 +49,17 @@ pub trait Database: Send + Sync + Debug {
 
         Ok(res)
     }
+
+    /// Copies the database to a new file at the given path, without blocking readers or writers.
+    ///
+    /// The copy is a consistent view of the database as of the last committed write transaction.
+    /// If `compact` is set, free pages are omitted from the copy.
+    ///
+    /// Returns an error if the database doesn't support copies.
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        let _ = (path, compact);
+        Err(DatabaseError::Other("database doesn't support copies".to_string()))
+    }
 }
 
 impl<DB: Database> Database for Arc<DB> {
@@ -62,6 +73,10 @@ impl<DB: Database> Database for Arc<DB> {
     fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_to(self, path, compact)
+    }
 }
 
 impl<DB: Database> Database for &DB {
@@ -75,4 +90,8 @@ impl<DB: Database> Database for &DB {
     fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_to(self, path, compact)
+    }
 }
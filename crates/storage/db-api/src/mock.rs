@@ -74,6 +74,10 @@ impl DbTx for TxMock {
         Ok(self._table.len())
     }
 
+    fn table_size<T: Table>(&self) -> Result<u64, DatabaseError> {
+        Ok(0)
+    }
+
     fn disable_long_read_transaction_safety(&mut self) {}
 }
 
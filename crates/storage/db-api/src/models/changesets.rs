@@ -0,0 +1,308 @@
+//! Changeset related models and types, used by the changeset static file segments.
+//!
+//! The changes of a block are encoded as the number of changes, followed by the end offset of
+//! every change and the changes themselves, all sorted by their key:
+//!
+//! `count (u32) | end offset of every change (u32) | changes`
+//!
+//! This allows to look up a single change with a binary search over the encoded changeset,
+//! without decoding the other changes of the block.
+
+use alloy_primitives::{Address, B256};
+use reth_codecs::Compact;
+use reth_db_models::AccountBeforeTx;
+use reth_primitives_traits::StorageEntry;
+use serde::{Deserialize, Serialize};
+
+/// Size of the entry count and of every end offset in an encoded changeset.
+const OFFSET_SIZE: usize = 4;
+
+/// Encodes the changes, which have to be sorted by their key.
+fn encode_changes<T, B>(changes: &[T], buf: &mut B) -> usize
+where
+    T: Compact,
+    B: bytes::BufMut + AsMut<[u8]>,
+{
+    let mut data = Vec::new();
+    let mut ends = Vec::with_capacity(changes.len());
+    for change in changes {
+        change.to_compact(&mut data);
+        ends.push(data.len() as u32);
+    }
+
+    buf.put_u32(changes.len() as u32);
+    for end in ends {
+        buf.put_u32(end);
+    }
+    buf.put_slice(&data);
+    OFFSET_SIZE * (changes.len() + 1) + data.len()
+}
+
+/// The encoded changes of a block.
+struct EncodedChanges<'a> {
+    /// The end offsets of the changes.
+    ends: &'a [u8],
+    /// The encoded changes.
+    data: &'a [u8],
+}
+
+impl<'a> EncodedChanges<'a> {
+    /// Splits an encoded changeset into its offsets and changes, and returns the rest of the
+    /// buffer.
+    fn new(buf: &'a [u8]) -> (Self, &'a [u8]) {
+        let count = u32::from_be_bytes(buf[..OFFSET_SIZE].try_into().unwrap()) as usize;
+        let (ends, buf) = buf[OFFSET_SIZE..].split_at(OFFSET_SIZE * count);
+        let mut changes = Self { ends, data: buf };
+        let len = if count == 0 { 0 } else { changes.end(count - 1) };
+        changes.data = &buf[..len];
+        (changes, &buf[len..])
+    }
+
+    /// Returns the number of changes.
+    const fn len(&self) -> usize {
+        self.ends.len() / OFFSET_SIZE
+    }
+
+    /// Returns the end offset of the change at the given index.
+    fn end(&self, index: usize) -> usize {
+        let offset = index * OFFSET_SIZE;
+        u32::from_be_bytes(self.ends[offset..offset + OFFSET_SIZE].try_into().unwrap()) as usize
+    }
+
+    /// Returns the encoded change at the given index.
+    fn get(&self, index: usize) -> &'a [u8] {
+        let start = if index == 0 { 0 } else { self.end(index - 1) };
+        &self.data[start..self.end(index)]
+    }
+
+    /// Decodes all changes.
+    fn decode<T: Compact>(&self) -> Vec<T> {
+        (0..self.len())
+            .map(|index| {
+                let change = self.get(index);
+                T::from_compact(change, change.len()).0
+            })
+            .collect()
+    }
+
+    /// Returns the encoded change that starts with the given key, found with a binary search.
+    fn find(&self, key: &[u8]) -> Option<&'a [u8]> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            let change = self.get(mid);
+            match change[..key.len()].cmp(key) {
+                core::cmp::Ordering::Less => low = mid + 1,
+                core::cmp::Ordering::Greater => high = mid,
+                core::cmp::Ordering::Equal => return Some(change),
+            }
+        }
+        None
+    }
+}
+
+/// Storage slot of an account as it was before the changes of a block.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage slot key and its value before the changes.
+    pub entry: StorageEntry,
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let (entry, buf) = StorageEntry::from_compact(&buf[20..], len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// The static file representation of the `AccountChangeSets` of a single block.
+///
+/// Changes are sorted by address, the same way they're stored in the database, and can be looked
+/// up in the encoded changeset with [`StaticFileAccountChangeSet::find_encoded`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticFileAccountChangeSet {
+    /// Accounts as they were before the changes of the block.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl StaticFileAccountChangeSet {
+    /// Returns the change of the given address, if the account was changed in the block.
+    pub fn get(&self, address: Address) -> Option<&AccountBeforeTx> {
+        self.changes
+            .binary_search_by_key(&address, |change| change.address)
+            .ok()
+            .map(|idx| &self.changes[idx])
+    }
+
+    /// Returns the change of the given address from an encoded changeset, without decoding the
+    /// other changes of the block.
+    pub fn find_encoded(buf: &[u8], address: Address) -> Option<AccountBeforeTx> {
+        let change = EncodedChanges::new(buf).0.find(address.as_slice())?;
+        Some(AccountBeforeTx::from_compact(change, change.len()).0)
+    }
+}
+
+impl Compact for StaticFileAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        encode_changes(&self.changes, buf)
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = EncodedChanges::new(buf);
+        (Self { changes: changes.decode() }, buf)
+    }
+}
+
+/// The static file representation of the `StorageChangeSets` of a single block.
+///
+/// Changes are sorted by address and storage key, the same way they're stored in the database, and
+/// can be looked up in the encoded changeset with [`StaticFileStorageChangeSet::find_encoded`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticFileStorageChangeSet {
+    /// Storage slots as they were before the changes of the block.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl StaticFileStorageChangeSet {
+    /// Returns the change of the given storage slot, if it was changed in the block.
+    pub fn get(&self, address: Address, key: B256) -> Option<&StorageEntry> {
+        self.changes
+            .binary_search_by(|change| (change.address, change.entry.key).cmp(&(address, key)))
+            .ok()
+            .map(|idx| &self.changes[idx].entry)
+    }
+
+    /// Returns the change of the given storage slot from an encoded changeset, without decoding
+    /// the other changes of the block.
+    pub fn find_encoded(buf: &[u8], address: Address, key: B256) -> Option<StorageEntry> {
+        let mut slot = [0u8; 52];
+        slot[..20].copy_from_slice(address.as_slice());
+        slot[20..].copy_from_slice(key.as_slice());
+        let change = EncodedChanges::new(buf).0.find(&slot)?;
+        Some(StorageBeforeTx::from_compact(change, change.len()).0.entry)
+    }
+}
+
+impl Compact for StaticFileStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        encode_changes(&self.changes, buf)
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = EncodedChanges::new(buf);
+        (Self { changes: changes.decode() }, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+    use alloy_primitives::U256;
+    use reth_primitives_traits::Account;
+
+    #[test]
+    fn test_account_changeset() {
+        let changeset = StaticFileAccountChangeSet {
+            changes: vec![
+                AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+                AccountBeforeTx {
+                    address: Address::with_last_byte(2),
+                    info: Some(Account { nonce: 1, ..Default::default() }),
+                },
+            ],
+        };
+        let decoded =
+            StaticFileAccountChangeSet::decompress(&changeset.clone().compress()).unwrap();
+        assert_eq!(decoded, changeset);
+        assert_eq!(decoded.get(Address::with_last_byte(2)), Some(&changeset.changes[1]));
+        assert_eq!(decoded.get(Address::with_last_byte(3)), None);
+
+        let encoded = changeset.clone().compress();
+        for change in &changeset.changes {
+            assert_eq!(
+                StaticFileAccountChangeSet::find_encoded(&encoded, change.address).as_ref(),
+                Some(change)
+            );
+        }
+        assert_eq!(StaticFileAccountChangeSet::find_encoded(&encoded, Address::ZERO), None);
+        assert_eq!(
+            StaticFileAccountChangeSet::find_encoded(&encoded, Address::with_last_byte(3)),
+            None
+        );
+
+        let empty = StaticFileAccountChangeSet::default();
+        let encoded = empty.clone().compress();
+        assert_eq!(StaticFileAccountChangeSet::decompress(&encoded).unwrap(), empty);
+        assert_eq!(StaticFileAccountChangeSet::find_encoded(&encoded, Address::ZERO), None);
+    }
+
+    #[test]
+    fn test_storage_changeset() {
+        let changeset = StaticFileStorageChangeSet {
+            changes: vec![
+                StorageBeforeTx {
+                    address: Address::with_last_byte(1),
+                    entry: StorageEntry { key: B256::with_last_byte(1), value: U256::ZERO },
+                },
+                StorageBeforeTx {
+                    address: Address::with_last_byte(1),
+                    entry: StorageEntry { key: B256::with_last_byte(2), value: U256::from(5) },
+                },
+            ],
+        };
+        let decoded =
+            StaticFileStorageChangeSet::decompress(&changeset.clone().compress()).unwrap();
+        assert_eq!(decoded, changeset);
+        assert_eq!(
+            decoded.get(Address::with_last_byte(1), B256::with_last_byte(2)),
+            Some(&changeset.changes[1].entry)
+        );
+        assert_eq!(decoded.get(Address::with_last_byte(2), B256::with_last_byte(2)), None);
+
+        let encoded = changeset.clone().compress();
+        for change in &changeset.changes {
+            assert_eq!(
+                StaticFileStorageChangeSet::find_encoded(
+                    &encoded,
+                    change.address,
+                    change.entry.key
+                ),
+                Some(change.entry)
+            );
+        }
+        assert_eq!(
+            StaticFileStorageChangeSet::find_encoded(
+                &encoded,
+                Address::with_last_byte(1),
+                B256::with_last_byte(3)
+            ),
+            None
+        );
+        assert_eq!(
+            StaticFileStorageChangeSet::find_encoded(
+                &encoded,
+                Address::with_last_byte(2),
+                B256::with_last_byte(1)
+            ),
+            None
+        );
+    }
+}
@@ -438,6 +438,20 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks in which an address appeared.
+    ///
+    /// An address appears in a block if it's the sender or the recipient of a transaction, if it
+    /// emitted a log, if its account or storage was changed, or if it was the caller or the callee
+    /// of an internal call. The table is only populated if the address appearances index is
+    /// enabled.
+    ///
+    /// Shards are keyed the same way as in [`AccountsHistory`], with the last shard of an address
+    /// containing `u64::MAX` as the highest block number.
+    table AddressAppearances {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -514,6 +528,13 @@ tables! {
         type Value = PruneCheckpoint;
     }
 
+    /// Stores the block from which the account and storage history of each address in the
+    /// history address filter is retained.
+    table HistoryAddressCheckpoints {
+        type Key = Address;
+        type Value = BlockNumber;
+    }
+
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory {
         type Key = u64;
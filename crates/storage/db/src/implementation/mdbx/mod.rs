@@ -197,6 +197,10 @@ impl Database for DatabaseEnv {
         )
         .map_err(|e| DatabaseError::InitTx(e.into()))
     }
+
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        self.inner.copy(path, compact).map_err(|e| DatabaseError::Copy(e.into()))
+    }
 }
 
 impl DatabaseMetrics for DatabaseEnv {
@@ -434,6 +438,13 @@ impl DatabaseEnv {
         Ok(env)
     }
 
+    /// Returns the storage lock held by a read-write environment.
+    ///
+    /// A clone of the lock keeps the storage locked after the environment is dropped.
+    pub const fn lock_file(&self) -> Option<&StorageLock> {
+        self._lock_file.as_ref()
+    }
+
     /// Enables metrics on the database.
     pub fn with_metrics(mut self) -> Self {
         self.metrics = Some(DatabaseEnvMetrics::new().into());
@@ -5,7 +5,10 @@ use crate::{
 };
 use alloy_primitives::BlockHash;
 use reth_db_api::{
-    models::{StaticFileBlockWithdrawals, StoredBlockOmmers},
+    models::{
+        StaticFileAccountChangeSet, StaticFileBlockWithdrawals, StaticFileStorageChangeSet,
+        StoredBlockOmmers,
+    },
     table::Table,
 };
 
@@ -59,3 +62,13 @@ add_static_file_mask! {
     #[doc = "Mask for a `StaticFileBlockWithdrawals` from BlockMeta static file segment"]
     WithdrawalsMask, StaticFileBlockWithdrawals, 0b100
 }
+
+// CHANGESET MASKS
+add_static_file_mask! {
+    #[doc = "Mask for a `StaticFileAccountChangeSet` from AccountChangeSets static file segment"]
+    AccountChangeSetMask, StaticFileAccountChangeSet, 0b1
+}
+add_static_file_mask! {
+    #[doc = "Mask for a `StaticFileStorageChangeSet` from StorageChangeSets static file segment"]
+    StorageChangeSetMask, StaticFileStorageChangeSet, 0b1
+}
@@ -42,6 +42,9 @@ pub enum DatabaseError {
     /// Failed to get database stats.
     #[error("failed to get stats: {_0}")]
     Stats(DatabaseErrorInfo),
+    /// Failed to copy the database.
+    #[error("failed to copy the database: {_0}")]
+    Copy(DatabaseErrorInfo),
     /// Failed to use the specified log level, as it's not available.
     #[error("log level {_0:?} is not available")]
     LogLevelUnavailable(LogLevel),
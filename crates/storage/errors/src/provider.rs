@@ -103,13 +103,43 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// History of the address is not available for the given block number because it is pruned,
+    /// and the address is not in the history address filter, or was added to it after the history
+    /// at the block was pruned.
+    #[error("history of address {address} at block #{block_number} is pruned, the address wasn't in the history address filter before")]
+    AddressHistoryPruned {
+        /// The address whose history was requested.
+        address: Address,
+        /// The block number at which the history was requested.
+        block_number: BlockNumber,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
+    /// The address appearances index was requested, but it's not enabled.
+    #[error("address appearances index is not enabled")]
+    AddressAppearancesIndexDisabled,
     /// Static File is not found at specified path.
     #[cfg(feature = "std")]
     #[error("not able to find {_0} static file at {_1:?}")]
     MissingStaticFilePath(StaticFileSegment, std::path::PathBuf),
+    /// The destination directory of a snapshot is not empty.
+    #[cfg(feature = "std")]
+    #[error("snapshot destination {_0:?} is not empty")]
+    SnapshotDestinationNotEmpty(std::path::PathBuf),
+    /// A file of a snapshot is missing or doesn't match the checksum in its manifest.
+    #[cfg(feature = "std")]
+    #[error("snapshot file {_0:?} is missing or doesn't match its checksum")]
+    SnapshotFileMismatch(std::path::PathBuf),
+    /// The static files of a segment were unwound below the height referred to by the database
+    /// of a snapshot while it was taken.
+    #[error("{segment} static files were unwound below block {height} while taking the snapshot")]
+    SnapshotStaticFilesUnwound {
+        /// Static file segment
+        segment: StaticFileSegment,
+        /// Block the database of the snapshot refers to
+        height: BlockNumber,
+    },
     /// Static File is not found for requested block.
     #[error("not able to find {_0} static file for block number {_1}")]
     MissingStaticFileBlock(StaticFileSegment, BlockNumber),
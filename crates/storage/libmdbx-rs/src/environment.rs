@@ -225,6 +225,39 @@ impl Environment {
 
         Ok(freelist)
     }
+
+    /// Copies the environment to a new database file at the given path, while it stays available
+    /// to readers and writers.
+    ///
+    /// The copy is made from a read transaction, so it's a consistent view of the environment as
+    /// of the last committed write transaction. If `compact` is set, free pages are omitted and
+    /// all pages are renumbered sequentially, which makes the copy as small as possible.
+    ///
+    /// The file must not exist yet, but its parent directory must. No lock file is created for
+    /// the copy, it's recreated once the copy is opened.
+    ///
+    /// Note: A long running copy holds back the reclamation of pages freed by concurrent write
+    /// transactions, which can grow the database file.
+    pub fn copy(&self, path: &Path, compact: bool) -> Result<()> {
+        let path = CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), path.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
 }
 
 /// Container type for Environment internals.
@@ -690,20 +723,6 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
@@ -167,3 +167,37 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    for i in 0..64u64 {
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), i.to_be_bytes(), [1u8; 512], WriteFlags::empty())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    // Free some pages.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let dbi = tx.open_db(None).unwrap().dbi();
+    for i in 0..32u64 {
+        tx.del(dbi, i.to_be_bytes(), None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    for compact in [false, true] {
+        let copy_dir = tempdir().unwrap();
+        env.copy(&copy_dir.path().join("mdbx.dat"), compact).unwrap();
+        // The destination file must not exist.
+        assert!(env.copy(&copy_dir.path().join("mdbx.dat"), compact).is_err());
+
+        let copy = Environment::builder().open(copy_dir.path()).unwrap();
+        let tx = copy.begin_ro_txn().unwrap();
+        let db = tx.open_db(None).unwrap();
+        assert_eq!(tx.db_stat(&db).unwrap().entries(), 32);
+        assert_eq!(tx.get::<[u8; 512]>(db.dbi(), &63u64.to_be_bytes()).unwrap(), Some([1u8; 512]));
+        assert_eq!(tx.get::<[u8; 512]>(db.dbi(), &0u64.to_be_bytes()).unwrap(), None);
+    }
+}
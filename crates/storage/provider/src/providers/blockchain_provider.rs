@@ -1,13 +1,14 @@
 #![allow(unused)]
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
+    DatabaseProvider, DatabaseProviderFactory, FullProvider, HashedPostStateProvider,
+    HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StateReader, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{
@@ -36,15 +37,18 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, OmmersProvider,
-    StateCommitmentProvider, StorageChangeSetReader,
+    BlockBodyIndicesProvider, DBProvider, DatabaseSnapshotProvider, NodePrimitivesProvider,
+    OmmersProvider, SnapshotManifest, SnapshotOptions, StateCommitmentProvider,
+    StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
 use reth_trie_db::StateCommitment;
 use revm_database::BundleState;
 use std::{
+    collections::BTreeMap,
     ops::{Add, RangeBounds, RangeInclusive, Sub},
+    path::Path,
     sync::Arc,
     time::Instant,
 };
@@ -305,7 +309,7 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
 
     fn pending_block_and_receipts(
         &self,
-    ) -> ProviderResult<Option<(SealedBlock<Self::Block>, Vec<Self::Receipt>)>> {
+    ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>> {
         Ok(self.canonical_in_memory_state.pending_block_and_receipts())
     }
 
@@ -733,6 +737,47 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesReader for BlockchainProvider<N> {
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        self.consistent_provider()?.address_appearances_with_range(range)
+    }
+
+    fn traces_call_participants(&self) -> bool {
+        self.consistent_provider().is_ok_and(|provider| provider.traces_call_participants())
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.address_appearances_before(address, block_number, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.address_appearances_after(address, block_number, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> DatabaseSnapshotProvider for BlockchainProvider<N> {
+    fn snapshot(
+        &self,
+        destination: &Path,
+        options: SnapshotOptions,
+    ) -> ProviderResult<SnapshotManifest> {
+        self.database.snapshot(destination, options)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -1217,12 +1262,10 @@ mod tests {
         // Assertions related to the pending block
         assert_eq!(provider.pending_block()?, Some(block.clone()));
 
-        assert_eq!(
-            provider.pending_block_with_senders()?,
-            Some(RecoveredBlock::new_sealed(block.clone(), block.senders().unwrap()))
-        );
+        let recovered_block = RecoveredBlock::new_sealed(block.clone(), block.senders().unwrap());
+        assert_eq!(provider.pending_block_with_senders()?, Some(recovered_block.clone()));
 
-        assert_eq!(provider.pending_block_and_receipts()?, Some((block, vec![])));
+        assert_eq!(provider.pending_block_and_receipts()?, Some((recovered_block, vec![])));
 
         Ok(())
     }
@@ -1,12 +1,12 @@
 use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    providers::StaticFileProvider, AccountReader, AddressAppearancesReader, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider,
+    ChangeSetReader, HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateReader, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
-use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
+use alloy_consensus::{transaction::TransactionMeta, BlockHeader, Transaction as _, TxReceipt};
 use alloy_eips::{
     eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumHash,
     BlockNumberOrTag, HashOrNumber,
@@ -32,6 +32,7 @@ use reth_storage_api::{
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
@@ -835,7 +836,7 @@ impl<N: ProviderNodeTypes> BlockReader for ConsistentProvider<N> {
 
     fn pending_block_and_receipts(
         &self,
-    ) -> ProviderResult<Option<(SealedBlock<Self::Block>, Vec<Self::Receipt>)>> {
+    ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>> {
         Ok(self.canonical_in_memory_state.pending_block_and_receipts())
     }
 
@@ -1440,6 +1441,143 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesReader for ConsistentProvider<N> {
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        let in_memory_chain =
+            self.head_block.as_ref().map(|b| b.chain().collect::<Vec<_>>()).unwrap_or_default();
+
+        // Blocks that are both in memory and in the database are taken from memory.
+        let storage_range = match in_memory_chain.last() {
+            Some(lowest_memory_block) => {
+                *range.start()..=(*range.end()).min(lowest_memory_block.number().saturating_sub(1))
+            }
+            None => range.clone(),
+        };
+        let mut appearances = if storage_range.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.storage_provider.address_appearances_with_range(storage_range)?
+        };
+
+        for state in in_memory_chain.iter().rev().filter(|state| range.contains(&state.number())) {
+            for address in self.block_state_address_appearances(state)? {
+                appearances.entry(address).or_default().push(state.number());
+            }
+        }
+
+        Ok(appearances)
+    }
+
+    fn traces_call_participants(&self) -> bool {
+        self.storage_provider.traces_call_participants()
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        if !self.storage_provider.address_appearances_index_enabled() {
+            return Err(ProviderError::AddressAppearancesIndexDisabled)
+        }
+
+        let in_memory_chain =
+            self.head_block.as_ref().map(|b| b.chain().collect::<Vec<_>>()).unwrap_or_default();
+
+        // The in-memory chain is ordered from the highest block to the lowest one.
+        let mut blocks = Vec::with_capacity(limit);
+        for state in &in_memory_chain {
+            if blocks.len() == limit {
+                return Ok(blocks)
+            }
+            if state.number() < block_number &&
+                self.block_state_address_appearances(state)?.contains(&address)
+            {
+                blocks.push(state.number());
+            }
+        }
+
+        let storage_block_number =
+            in_memory_chain.last().map_or(block_number, |lowest_memory_block| {
+                lowest_memory_block.number().min(block_number)
+            });
+        blocks.extend(self.storage_provider.address_appearances_before(
+            address,
+            storage_block_number,
+            limit - blocks.len(),
+        )?);
+
+        Ok(blocks)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let in_memory_chain =
+            self.head_block.as_ref().map(|b| b.chain().collect::<Vec<_>>()).unwrap_or_default();
+
+        let mut blocks =
+            self.storage_provider.address_appearances_after(address, block_number, limit)?;
+        if let Some(lowest_memory_block) = in_memory_chain.last() {
+            blocks.retain(|block| *block < lowest_memory_block.number());
+        }
+
+        for state in in_memory_chain.iter().rev() {
+            if blocks.len() == limit {
+                break
+            }
+            if state.number() > block_number &&
+                self.block_state_address_appearances(state)?.contains(&address)
+            {
+                blocks.push(state.number());
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<N: ProviderNodeTypes> ConsistentProvider<N> {
+    /// Returns all addresses that appeared in the given in-memory block.
+    ///
+    /// See [`AddressAppearancesReader`] for the definition of an appearance.
+    fn block_state_address_appearances(
+        &self,
+        state: &BlockState<N::Primitives>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let block = state.block_ref();
+        let mut addresses = BTreeSet::new();
+        for (sender, transaction) in block.recovered_block().transactions_with_sender() {
+            addresses.insert(*sender);
+            addresses.extend(transaction.to());
+        }
+        addresses.extend(
+            state
+                .receipts()
+                .iter()
+                .flatten()
+                .flat_map(|receipt| receipt.logs())
+                .map(|log| log.address),
+        );
+        addresses.extend(block.execution_outcome().bundle.state.keys().copied());
+
+        if let Some(tracer) = self.storage_provider.call_participants_tracer() {
+            let parent_state =
+                self.history_by_block_hash_ref(block.recovered_block().header().parent_hash())?;
+            addresses.extend(tracer.call_participants(&*parent_state, block.recovered_block())?);
+        }
+
+        Ok(addresses)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -23,8 +23,8 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, OmmersProvider, StateCommitmentProvider,
-    TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, CallParticipantsTracer, NodePrimitivesProvider, OmmersProvider,
+    StateCommitmentProvider, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -33,7 +33,7 @@ use revm_database::BundleState;
 use std::{
     ops::{RangeBounds, RangeInclusive},
     path::Path,
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
 use tracing::trace;
@@ -51,6 +51,9 @@ mod metrics;
 mod chain;
 pub use chain::*;
 
+mod snapshot;
+pub use snapshot::{restore_snapshot, verify_snapshot};
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -65,6 +68,11 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// Whether the address appearances index is maintained.
+    address_appearances_index: bool,
+    /// Tracer of the call participants for the address appearances index, shared by all clones
+    /// of the factory.
+    call_participants_tracer: Arc<OnceLock<Arc<dyn CallParticipantsTracer<BlockTy<N>>>>>,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -87,6 +95,8 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            address_appearances_index: false,
+            call_participants_tracer: Default::default(),
         }
     }
 
@@ -102,6 +112,25 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets whether the address appearances index is maintained by the providers created by this
+    /// [`ProviderFactory`].
+    pub const fn with_address_appearances_index(mut self, enabled: bool) -> Self {
+        self.address_appearances_index = enabled;
+        self
+    }
+
+    /// Sets the tracer of the call participants for the address appearances index of this
+    /// [`ProviderFactory`] and all of its clones. Has no effect if a tracer is already set.
+    ///
+    /// The tracer usually depends on the EVM configuration, which is only available once the
+    /// factory is shared, so it's not set through a builder method.
+    pub fn set_call_participants_tracer(
+        &self,
+        tracer: Arc<dyn CallParticipantsTracer<BlockTy<N>>>,
+    ) {
+        let _ = self.call_participants_tracer.set(tracer);
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -129,6 +158,8 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            address_appearances_index: false,
+            call_participants_tracer: Default::default(),
         })
     }
 }
@@ -148,7 +179,9 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_address_appearances_index(self.address_appearances_index)
+        .with_call_participants_tracer(self.call_participants_tracer.get().cloned()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -157,13 +190,17 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+            )
+            .with_address_appearances_index(self.address_appearances_index)
+            .with_call_participants_tracer(self.call_participants_tracer.get().cloned()),
+        ))
     }
 
     /// State provider for latest block
@@ -375,7 +412,7 @@ impl<N: ProviderNodeTypes> BlockReader for ProviderFactory<N> {
 
     fn pending_block_and_receipts(
         &self,
-    ) -> ProviderResult<Option<(SealedBlock<Self::Block>, Vec<Self::Receipt>)>> {
+    ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>> {
         self.provider()?.pending_block_and_receipts()
     }
 
@@ -620,13 +657,23 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self {
+            db,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            address_appearances_index,
+            call_participants_tracer,
+        } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("address_appearances_index", &address_appearances_index)
+            .field("call_participants_tracer", &call_participants_tracer)
             .finish()
     }
 }
@@ -639,6 +686,8 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            address_appearances_index: self.address_appearances_index,
+            call_participants_tracer: self.call_participants_tracer.clone(),
         }
     }
 }
@@ -649,10 +698,10 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGap,
-        HeaderSyncGapProvider, StorageLocation, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, ChangeSetRangeReader, DBProvider,
+        HeaderSyncGap, HeaderSyncGapProvider, StorageLocation, TransactionsProvider,
     };
-    use alloy_primitives::{TxNumber, B256, U256};
+    use alloy_primitives::{Address, TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use rand::Rng;
     use reth_chainspec::ChainSpecBuilder;
@@ -660,9 +709,16 @@ mod tests {
         mdbx::DatabaseArguments,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_db_api::tables;
+    use reth_db_api::{
+        models::{
+            AccountBeforeTx, BlockNumberAddress, StaticFileAccountChangeSet,
+            StaticFileStorageChangeSet, StorageBeforeTx,
+        },
+        tables,
+        transaction::DbTxMut,
+    };
     use reth_network_p2p::headers::downloader::SyncTarget;
-    use reth_primitives_traits::SignedTransaction;
+    use reth_primitives_traits::{Account, SignedTransaction, StorageEntry};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
@@ -791,6 +847,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn take_changesets_from_static_files_and_database() {
+        let factory = create_test_provider_factory();
+
+        let address = Address::with_last_byte(1);
+        let account_changeset = |block| {
+            let info = Some(Account { nonce: block, ..Default::default() });
+            (block, AccountBeforeTx { address, info })
+        };
+        let storage_changeset = |block| {
+            let entry = StorageEntry { key: B256::ZERO, value: U256::from(block) };
+            (BlockNumberAddress((block, address)), entry)
+        };
+
+        // Changesets of blocks up to 2 are in static files, the rest are in the database
+        let static_file_provider = factory.static_file_provider();
+        {
+            let mut account_writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            let mut storage_writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=2 {
+                let changes = vec![account_changeset(block).1];
+                account_writer
+                    .append_account_changeset(&StaticFileAccountChangeSet { changes }, block)
+                    .unwrap();
+                let (_, entry) = storage_changeset(block);
+                let changes = vec![StorageBeforeTx { address, entry }];
+                storage_writer
+                    .append_storage_changeset(&StaticFileStorageChangeSet { changes }, block)
+                    .unwrap();
+            }
+            account_writer.commit().unwrap();
+            storage_writer.commit().unwrap();
+        }
+
+        let provider = factory.provider_rw().unwrap();
+        for block in 3..=4 {
+            let (block, account) = account_changeset(block);
+            provider.tx_ref().put::<tables::AccountChangeSets>(block, account).unwrap();
+            let (key, entry) = storage_changeset(block);
+            provider.tx_ref().put::<tables::StorageChangeSets>(key, entry).unwrap();
+        }
+
+        // Take changesets across the static files and database boundary
+        let (storage_changesets, account_changesets) = provider.take_changesets(2..=4).unwrap();
+        assert_eq!(account_changesets, (2..=4).map(account_changeset).collect::<Vec<_>>());
+        assert_eq!(storage_changesets, (2..=4).map(storage_changeset).collect::<Vec<_>>());
+        provider.commit().unwrap();
+        static_file_provider.commit().unwrap();
+
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(1)
+        );
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(1)
+        );
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.account_changesets_range(0..).unwrap(),
+            (0..=1).map(account_changeset).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            provider.storage_changesets_range(0..).unwrap(),
+            (0..=1).map(storage_changeset).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
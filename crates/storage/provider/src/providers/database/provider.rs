@@ -3,23 +3,25 @@ use crate::{
     providers::{
         database::{chain::ChainStorage, metrics},
         static_file::StaticFileWriter,
-        NodeTypesForProvider, StaticFileProvider,
+        LowestAvailableBlocks, NodeTypesForProvider, StaticFileProvider,
     },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
-    DBProvider, HashingWriter, HeaderProvider, HeaderSyncGapProvider, HistoricalStateProvider,
-    HistoricalStateProviderRef, HistoryWriter, LatestStateProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RevertsInit,
-    StageCheckpointReader, StateCommitmentProvider, StateProviderBox, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageLocation, StorageReader, StorageTrieWriter,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, TrieWriter,
-    WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, BlockBodyWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, BundleStateInit,
+    ChainStateBlockReader, ChainStateBlockWriter, DBProvider, HashingWriter, HeaderProvider,
+    HeaderSyncGapProvider, HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter,
+    LatestStateProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, RevertsInit, StageCheckpointReader,
+    StateCommitmentProvider, StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader,
+    StorageLocation, StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+};
+use alloy_consensus::{
+    transaction::TransactionMeta, BlockHeader, Header, Transaction as _, TxReceipt,
 };
-use alloy_consensus::{transaction::TransactionMeta, BlockHeader, Header, TxReceipt};
 use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
@@ -53,8 +55,9 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, OmmersProvider,
-    StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, CallParticipantsTracer, ChangeSetRangeReader,
+    NodePrimitivesProvider, OmmersProvider, StateProvider, StorageChangeSetReader,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -70,7 +73,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
 };
 use tracing::{debug, trace};
@@ -143,6 +146,10 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// Whether the address appearances index is maintained.
+    address_appearances_index: bool,
+    /// Tracer of the call participants for the address appearances index.
+    call_participants_tracer: Option<Arc<dyn CallParticipantsTracer<BlockTy<N>>>>,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -150,6 +157,43 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets whether the address appearances index is maintained.
+    ///
+    /// If enabled, the index is updated and unwound alongside the history indices and can be
+    /// queried through [`AddressAppearancesReader`].
+    pub const fn with_address_appearances_index(mut self, enabled: bool) -> Self {
+        self.address_appearances_index = enabled;
+        self
+    }
+
+    /// Returns `true` if the address appearances index is maintained.
+    pub const fn address_appearances_index_enabled(&self) -> bool {
+        self.address_appearances_index
+    }
+
+    /// Sets the tracer of the call participants for the address appearances index.
+    ///
+    /// Without a tracer, the callers and callees of internal calls can't be indexed, so the index
+    /// is not updated alongside the blocks and has to be rebuilt by the pipeline.
+    pub fn with_call_participants_tracer(
+        mut self,
+        tracer: Option<Arc<dyn CallParticipantsTracer<BlockTy<N>>>>,
+    ) -> Self {
+        self.call_participants_tracer = tracer;
+        self
+    }
+
+    /// Returns the tracer of the call participants for the address appearances index.
+    pub fn call_participants_tracer(&self) -> Option<&Arc<dyn CallParticipantsTracer<BlockTy<N>>>> {
+        self.call_participants_tracer.as_ref()
+    }
+
+    /// Returns `true` if the address appearances index is updated alongside the blocks, which
+    /// requires the call participants to be traced.
+    const fn maintains_address_appearances_index(&self) -> bool {
+        self.address_appearances_index && self.call_participants_tracer.is_some()
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -202,7 +246,28 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Box::new(state_provider))
     }
 
-    #[cfg(feature = "test-utils")]
+    /// Returns the storage changesets within a range of [`BlockNumberAddress`] keys, reading them
+    /// from static files or the database, wherever they're available.
+    fn storage_changesets_by_key_range(
+        &self,
+        range: impl RangeBounds<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        // Static files are keyed by block, so fetch all blocks touched by the range first.
+        let start = match range.start_bound() {
+            Bound::Included(key) | Bound::Excluded(key) => key.block_number(),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) | Bound::Excluded(key) => Bound::Included(key.block_number()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let mut changesets = self.storage_changesets_range((Bound::Included(start), end))?;
+        changesets.retain(|(key, _)| range.contains(key));
+        Ok(changesets)
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
     /// Sets the prune modes for provider.
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
         self.prune_modes = prune_modes;
@@ -239,7 +304,15 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            address_appearances_index: false,
+            call_participants_tracer: None,
+        }
     }
 }
 
@@ -258,11 +331,13 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<()> {
-        let changed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range.clone())?
-            .collect::<Result<Vec<_>, _>>()?;
+        // Unwind address appearances index. The blocks are traced on the historical state, so this
+        // has to happen before the history indices are unwound.
+        if self.maintains_address_appearances_index() {
+            self.unwind_address_appearances_index_range(range.clone())?;
+        }
+
+        let changed_accounts = self.account_changesets_range(range.clone())?;
 
         // Unwind account hashes. Add changed accounts to account prefix set.
         let hashed_addresses = self.unwind_account_hashing(changed_accounts.iter())?;
@@ -277,13 +352,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         // Unwind account history indices.
         self.unwind_account_history_indices(changed_accounts.iter())?;
-        let storage_range = BlockNumberAddress::range(range.clone());
 
-        let changed_storages = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_storages = self.storage_changesets_range(range.clone())?;
 
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
@@ -506,7 +576,15 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            address_appearances_index: false,
+            call_participants_tracer: None,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -786,6 +864,62 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Takes the account and storage changesets of a block range, removing them from the database
+    /// and queueing their removal from static files, to be applied on static file commit.
+    #[expect(clippy::type_complexity)]
+    pub(crate) fn take_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(
+        Vec<(BlockNumberAddress, StorageEntry)>,
+        Vec<(BlockNumber, AccountBeforeTx)>,
+    )> {
+        let start = *range.start();
+
+        // Changesets which were moved to static files may still be present in the database if they
+        // weren't pruned yet, so database entries are only taken into account after the highest
+        // static file block.
+        let mut storage_changeset = Vec::new();
+        let storage_static_file_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest_block| *highest_block >= start);
+        if let Some(highest_block) = storage_static_file_block {
+            storage_changeset =
+                self.static_file_provider.storage_changesets_range(start..highest_block + 1)?;
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::StorageChangeSets)?
+                .prune_storage_changesets(highest_block - start + 1)?;
+        }
+        storage_changeset.extend(
+            self.take::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?
+                .into_iter()
+                .filter(|(key, _)| {
+                    storage_static_file_block.is_none_or(|highest| key.block_number() > highest)
+                }),
+        );
+
+        let mut account_changeset = Vec::new();
+        let account_static_file_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest_block| *highest_block >= start);
+        if let Some(highest_block) = account_static_file_block {
+            account_changeset =
+                self.static_file_provider.account_changesets_range(start..highest_block + 1)?;
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::AccountChangeSets)?
+                .prune_account_changesets(highest_block - start + 1)?;
+        }
+        account_changeset.extend(
+            self.take::<tables::AccountChangeSets>(range)?.into_iter().filter(|(block, _)| {
+                account_static_file_block.is_none_or(|highest| *block > highest)
+            }),
+        );
+
+        Ok((storage_changeset, account_changeset))
+    }
+
     /// Commit database transaction.
     pub fn commit(self) -> ProviderResult<bool> {
         Ok(self.tx.commit()?)
@@ -856,18 +990,16 @@ impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     fn changed_accounts_with_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -885,50 +1017,223 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> AddressAppearancesReader
+    for DatabaseProvider<TX, N>
+{
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        let mut appearances = BTreeMap::<Address, BTreeSet<BlockNumber>>::new();
+        let blocks = self.recovered_block_range(range.clone())?;
+
+        // Transaction senders, recipients and log emitters.
+        for block in &blocks {
+            let block_number = block.number();
+            for (sender, transaction) in block.transactions_with_sender() {
+                appearances.entry(*sender).or_default().insert(block_number);
+                if let Some(to) = transaction.to() {
+                    appearances.entry(to).or_default().insert(block_number);
+                }
+            }
+
+            let receipts = self.receipts_by_block(block_number.into())?.unwrap_or_default();
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                appearances.entry(log.address).or_default().insert(block_number);
+            }
+        }
+
+        // Accounts and storages changed by the execution, including by internal calls.
+        for (block_number, account) in self.account_changesets_range(range.clone())? {
+            appearances.entry(account.address).or_default().insert(block_number);
+        }
+        for (BlockNumberAddress((block_number, address)), _) in
+            self.storage_changesets_range(range)?
+        {
+            appearances.entry(address).or_default().insert(block_number);
+        }
+
+        // Callers and callees of all calls, found by re-executing the blocks on top of the
+        // historical state of their parents.
+        if let Some(tracer) = &self.call_participants_tracer {
+            let lowest_available_blocks = LowestAvailableBlocks {
+                account_history_block_number: self
+                    .get_prune_checkpoint(PruneSegment::AccountHistory)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .map(|block_number| block_number + 1),
+                storage_history_block_number: self
+                    .get_prune_checkpoint(PruneSegment::StorageHistory)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .map(|block_number| block_number + 1),
+            };
+            for block in &blocks {
+                let parent_state = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+                    self,
+                    block.number(),
+                    lowest_available_blocks,
+                );
+                for address in tracer.call_participants(&parent_state, block)? {
+                    appearances.entry(address).or_default().insert(block.number());
+                }
+            }
+        }
+
+        Ok(appearances
+            .into_iter()
+            .map(|(address, blocks)| (address, blocks.into_iter().collect()))
+            .collect())
+    }
+
+    fn traces_call_participants(&self) -> bool {
+        self.call_participants_tracer.is_some()
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        if !self.address_appearances_index {
+            return Err(ProviderError::AddressAppearancesIndexDisabled)
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut blocks = Vec::with_capacity(limit);
+
+        // The first shard with the highest block number greater or equal to the requested one
+        // is the last shard that can contain lower blocks. If there's no such shard, all blocks of
+        // the address are lower than the requested one, and the last shard is right before it.
+        let mut item = match cursor.seek(ShardedKey::new(address, block_number))? {
+            Some((key, list)) if key.key == address => Some((key, list)),
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        while let Some((key, list)) = item {
+            if key.key != address {
+                break
+            }
+
+            for block in list.iter().rev().skip_while(|block| *block >= block_number) {
+                if blocks.len() == limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block);
+            }
+
+            item = cursor.prev()?;
+        }
+
+        Ok(blocks)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        if !self.address_appearances_index {
+            return Err(ProviderError::AddressAppearancesIndexDisabled)
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut blocks = Vec::with_capacity(limit);
+
+        let mut item = cursor.seek(ShardedKey::new(address, block_number.saturating_add(1)))?;
+        while let Some((key, list)) = item {
+            if key.key != address {
+                break
+            }
+
+            for block in list.iter().skip_while(|block| *block <= block_number) {
+                if blocks.len() == limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block);
+            }
+
+            item = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> ChangeSetRangeReader for DatabaseProvider<TX, N> {
+    fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.account_changesets_range(range),
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .walk_range(range)?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )
+    }
+
+    fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.storage_changesets_range(range),
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::StorageChangeSets>()?
+                    .walk_range(
+                        BlockNumberAddress((range.start, Address::ZERO))..
+                            BlockNumberAddress((range.end, Address::ZERO)),
+                    )?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
-        let range = block_number..=block_number;
-        let storage_range = BlockNumberAddress::range(range);
-        self.tx
-            .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+        self.storage_changesets_range(block_number..=block_number)
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     fn account_block_changeset(
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(block_number..=block_number)?
+            .into_iter()
+            .map(|(_, account_before)| account_before)
+            .collect())
     }
 }
 
@@ -1200,7 +1505,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
 
     fn pending_block_and_receipts(
         &self,
-    ) -> ProviderResult<Option<(SealedBlock<Self::Block>, Vec<Self::Receipt>)>> {
+    ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>> {
         Ok(None)
     }
 
@@ -1692,12 +1997,18 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
         let mut cursor = self.tx.cursor_write::<tables::StageCheckpoints>()?;
         for stage_id in StageId::ALL {
             let (_, checkpoint) = cursor.seek_exact(stage_id.to_string())?.unwrap_or_default();
+            let stage_checkpoint = if stage_id == StageId::IndexAddressAppearances {
+                // The stage checkpoint marks the index as complete, which only holds if it was
+                // updated alongside the blocks.
+                checkpoint.stage_checkpoint.filter(|_| self.maintains_address_appearances_index())
+            } else if drop_stage_checkpoint {
+                None
+            } else {
+                checkpoint.stage_checkpoint
+            };
             cursor.upsert(
                 stage_id.to_string(),
-                &StageCheckpoint {
-                    block_number,
-                    ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
-                },
+                &StageCheckpoint { block_number, stage_checkpoint },
             )?;
         }
 
@@ -1733,36 +2044,32 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets_range(range)?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
+            .fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
+                let (BlockNumberAddress((_, address)), storage_entry) = entry;
                 accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+                accounts
+            }))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let storage_changeset_lists = self.storage_changesets_range(range)?.into_iter().fold(
+            BTreeMap::new(),
+            |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
+                storages
+                    .entry((index.address(), storage.key))
+                    .or_default()
+                    .push(index.block_number());
+                storages
+            },
+        );
 
         Ok(storage_changeset_lists)
     }
@@ -2094,10 +2401,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let from_transaction_num =
             block_bodies.first().expect("already checked if there are blocks").first_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (storage_changeset, account_changeset) = self.take_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2192,10 +2496,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (storage_changeset, account_changeset) = self.take_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2413,11 +2714,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_hashing(changesets.iter())
     }
 
@@ -2477,11 +2774,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_by_key_range(range)?;
         self.unwind_storage_hashing(changesets.into_iter())
     }
 
@@ -2605,7 +2898,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
     }
 }
 
-impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvider<TX, N> {
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> HistoryWriter
+    for DatabaseProvider<TX, N>
+{
     fn unwind_account_history_indices<'a>(
         &self,
         changesets: impl Iterator<Item = &'a (BlockNumber, AccountBeforeTx)>,
@@ -2644,11 +2939,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_history_indices(changesets.iter())
     }
 
@@ -2702,11 +2993,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_by_key_range(range)?;
         self.unwind_storage_history_indices(changesets.into_iter())
     }
 
@@ -2722,6 +3009,43 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         )
     }
 
+    fn unwind_address_appearances_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let addresses = self.address_appearances_with_range(range)?;
+
+        // Unwind the address appearances index.
+        let mut cursor = self.tx.cursor_write::<tables::AddressAppearances>()?;
+        for &address in addresses.keys() {
+            let partial_shard = unwind_history_shards::<_, tables::AddressAppearances, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_block,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len())
+    }
+
+    fn insert_address_appearances_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressAppearances>(index_updates, ShardedKey::new)
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2731,10 +3055,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // address appearances stage
+        if self.maintains_address_appearances_index() {
+            let indices = self.address_appearances_with_range(range)?;
+            self.insert_address_appearances_index(indices)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,469 @@
+//! Consistent snapshots of the database and static files of a running node.
+
+use super::ProviderFactory;
+use crate::providers::{ProviderNodeTypes, StaticFileProvider, StaticFileWriter};
+use alloy_primitives::{BlockNumber, B256};
+use reth_db::{
+    mdbx::DatabaseArguments,
+    static_file::iter_static_files,
+    version::{create_db_version_file, db_version_file_path},
+    DatabaseEnv, DatabaseEnvKind,
+};
+use reth_db_api::{database::Database, tables, transaction::DbTx};
+use reth_fs_util::{self as fs, FsPathError};
+use reth_nippy_jar::NippyJar;
+use reth_primitives_traits::NodePrimitives;
+use reth_stages_types::StageId;
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use reth_storage_api::{
+    DatabaseSnapshotProvider, SnapshotFile, SnapshotManifest, SnapshotOptions, SNAPSHOT_DB_DIR,
+    SNAPSHOT_MANIFEST_FILE_NAME, SNAPSHOT_MANIFEST_VERSION, SNAPSHOT_STATIC_FILES_DIR,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    collections::BTreeMap,
+    io::BufReader,
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info};
+
+/// Name of the MDBX data file inside the database directory.
+const MDBX_DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the MDBX lock file inside the database directory.
+const MDBX_LOCK_FILE_NAME: &str = "mdbx.lck";
+
+impl<N: ProviderNodeTypes> DatabaseSnapshotProvider for ProviderFactory<N> {
+    /// Copies the database first, then the static files.
+    ///
+    /// The database is copied by MDBX from its own read transaction. The static file heights are
+    /// read from the stage checkpoints that this transaction observed, by opening the copied
+    /// database, and the static files are copied and truncated up to these heights. If the static
+    /// files were unwound below them in the meantime, the snapshot fails.
+    ///
+    /// Static files that can still be truncated by an unwind, i.e. all files above the last
+    /// finalized block, are always copied, so that the snapshot is not modified through a hard
+    /// link.
+    fn snapshot(
+        &self,
+        destination: &Path,
+        options: SnapshotOptions,
+    ) -> ProviderResult<SnapshotManifest> {
+        ensure_empty_dir(destination)?;
+        let db_dir = destination.join(SNAPSHOT_DB_DIR);
+        let static_files_dir = destination.join(SNAPSHOT_STATIC_FILES_DIR);
+        fs::create_dir_all(&db_dir).map_err(ProviderError::other)?;
+        fs::create_dir_all(&static_files_dir).map_err(ProviderError::other)?;
+
+        info!(target: "providers::db", ?destination, compact = options.compact, "Copying database");
+        self.db_ref().copy_to(&db_dir.join(MDBX_DATA_FILE_NAME), options.compact)?;
+        create_db_version_file(&db_dir)
+            .map_err(|err| FsPathError::create_file(err, db_version_file_path(&db_dir)))
+            .map_err(ProviderError::other)?;
+
+        let static_file_provider = &self.static_file_provider;
+        // A read-only provider, e.g. of a `reth db snapshot` next to a running node, only knows
+        // about the static files that existed when it was opened.
+        if static_file_provider.is_read_only() {
+            static_file_provider.initialize_index()?;
+        }
+
+        let db = DatabaseEnv::open(&db_dir, DatabaseEnvKind::RO, DatabaseArguments::default())?;
+        let tx = db.tx()?;
+        let finalized = tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?;
+
+        let mut heights = BTreeMap::new();
+        for segment in StaticFileSegment::iter() {
+            let Some(highest) = static_file_provider.get_highest_static_file_block(segment) else {
+                continue
+            };
+            let checkpoint = tx
+                .get::<tables::StageCheckpoints>(segment_stage_id(segment).to_string())?
+                .unwrap_or_default()
+                .block_number;
+
+            // Static files are committed before the database, so the static files of these
+            // segments always reach the checkpoint, unless they are unwound. Changesets are only
+            // moved to static files after the history is indexed, receipts are not written to
+            // static files by pruned nodes, and block meta is not integrated yet.
+            let lagging = segment.is_change_sets() ||
+                segment.is_block_meta() ||
+                (segment.is_receipts() && self.prune_modes.has_receipts_pruning());
+            heights.insert(segment, if lagging { highest.min(checkpoint) } else { checkpoint });
+        }
+
+        info!(target: "providers::db", ?heights, ?finalized, "Copying static files");
+        let static_files =
+            iter_static_files(static_file_provider.directory()).map_err(ProviderError::other)?;
+        for (segment, ranges) in static_files {
+            let Some(&height) = heights.get(&segment) else { continue };
+
+            for (block_range, _) in ranges {
+                if block_range.start() > height {
+                    continue
+                }
+
+                let fixed_range = static_file_provider.find_fixed_range(block_range.start());
+                let jar = NippyJar::<SegmentHeader>::load(
+                    &static_file_provider.directory().join(segment.filename(&fixed_range)),
+                )
+                .map_err(ProviderError::other)?;
+
+                // The config is copied before the offsets and the data, so if the jar is written
+                // to in the meantime, the copy has at most more data than its config refers to,
+                // which is healed once the copy is opened. The jar of the height is always
+                // copied, since it's written to by the truncation below.
+                let link = !options.copy_static_files &&
+                    fixed_range != static_file_provider.find_fixed_range(height) &&
+                    finalized.is_some_and(|finalized| fixed_range.end() <= finalized);
+                for path in [
+                    jar.config_path(),
+                    jar.index_path(),
+                    jar.offsets_path(),
+                    jar.data_path().into(),
+                ] {
+                    if !path.exists() {
+                        continue
+                    }
+                    let file_name = path.file_name().expect("jar files have a name");
+                    let target = static_files_dir.join(file_name);
+                    if link {
+                        link_or_copy(&path, &target)?;
+                    } else {
+                        copy(&path, &target)?;
+                    }
+                }
+            }
+        }
+
+        truncate_static_files::<N::Primitives, _>(&static_files_dir, &tx, &heights)?;
+        drop(tx);
+        drop(db);
+        // Opening the copied database created its lock file, which is not part of the snapshot.
+        let lock_file = db_dir.join(MDBX_LOCK_FILE_NAME);
+        if lock_file.exists() {
+            fs::remove_file(&lock_file).map_err(ProviderError::other)?;
+        }
+
+        let manifest = SnapshotManifest {
+            version: SNAPSHOT_MANIFEST_VERSION,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            compacted: options.compact,
+            static_files: heights,
+            files: snapshot_files(destination)?,
+        };
+        fs::write_json_file(&destination.join(SNAPSHOT_MANIFEST_FILE_NAME), &manifest)
+            .map_err(ProviderError::other)?;
+
+        info!(
+            target: "providers::db",
+            ?destination,
+            files = manifest.files.len(),
+            size = manifest.total_size(),
+            "Snapshot taken"
+        );
+
+        Ok(manifest)
+    }
+}
+
+/// Returns the stage whose checkpoint the static files of the segment are written up to.
+const fn segment_stage_id(segment: StaticFileSegment) -> StageId {
+    match segment {
+        StaticFileSegment::Headers => StageId::Headers,
+        StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
+        StaticFileSegment::Receipts |
+        StaticFileSegment::AccountChangeSets |
+        StaticFileSegment::StorageChangeSets => StageId::Execution,
+    }
+}
+
+/// Truncates the copied static files to the given heights, using the block body indices of the
+/// copied database for transaction based segments.
+///
+/// Returns an error if the static files of a segment don't reach their height, because they were
+/// unwound while the snapshot was taken.
+fn truncate_static_files<N, TX>(
+    static_files_dir: &Path,
+    tx: &TX,
+    heights: &BTreeMap<StaticFileSegment, BlockNumber>,
+) -> ProviderResult<()>
+where
+    N: NodePrimitives,
+    TX: DbTx,
+{
+    let static_file_provider = StaticFileProvider::<N>::read_write(static_files_dir)?;
+    for (&segment, &height) in heights {
+        let highest = static_file_provider.get_highest_static_file_block(segment);
+        if highest.is_none_or(|highest| highest < height) {
+            return Err(ProviderError::SnapshotStaticFilesUnwound { segment, height })
+        }
+        let Some(highest) = highest.filter(|&highest| highest > height) else { continue };
+
+        debug!(
+            target: "providers::db",
+            ?segment,
+            from = highest,
+            to = height,
+            "Truncating snapshot static files"
+        );
+        let mut writer = static_file_provider.latest_writer(segment)?;
+        match segment {
+            StaticFileSegment::Headers => writer.prune_headers(highest - height)?,
+            StaticFileSegment::BlockMeta => writer.prune_block_meta(highest - height)?,
+            StaticFileSegment::AccountChangeSets => {
+                writer.prune_account_changesets(highest - height)?
+            }
+            StaticFileSegment::StorageChangeSets => {
+                writer.prune_storage_changesets(highest - height)?
+            }
+            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+                let Some(highest_tx) = static_file_provider.get_highest_static_file_tx(segment)
+                else {
+                    continue
+                };
+                let last_tx = tx
+                    .get::<tables::BlockBodyIndices>(height)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(height))?
+                    .last_tx_num();
+                let to_delete = highest_tx.saturating_sub(last_tx);
+                if segment.is_receipts() {
+                    writer.prune_receipts(to_delete, height)?
+                } else {
+                    writer.prune_transactions(to_delete, height)?
+                }
+            }
+        }
+        writer.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Reads the manifest of the snapshot in the given directory and checks the size and checksum of
+/// all of its files.
+pub fn verify_snapshot(snapshot: &Path) -> ProviderResult<SnapshotManifest> {
+    let manifest: SnapshotManifest =
+        fs::read_json_file(&snapshot.join(SNAPSHOT_MANIFEST_FILE_NAME))
+            .map_err(ProviderError::other)?;
+    if manifest.version != SNAPSHOT_MANIFEST_VERSION {
+        return Err(ProviderError::other(std::io::Error::other(format!(
+            "unsupported snapshot manifest version {}",
+            manifest.version
+        ))))
+    }
+
+    for file in &manifest.files {
+        let path = snapshot_file_path(snapshot, &file.path)?;
+        let size = fs::metadata(&path).map(|metadata| metadata.len()).ok();
+        if size != Some(file.size) || checksum(&path)? != file.checksum {
+            return Err(ProviderError::SnapshotFileMismatch(file.path.clone()))
+        }
+        debug!(target: "providers::db", path = ?file.path, "Verified snapshot file");
+    }
+
+    Ok(manifest)
+}
+
+/// Verifies the snapshot in the given directory and copies its database and static files to the
+/// given paths, which must either not exist or be empty.
+pub fn restore_snapshot(
+    snapshot: &Path,
+    db_path: &Path,
+    static_files_path: &Path,
+) -> ProviderResult<SnapshotManifest> {
+    let manifest = verify_snapshot(snapshot)?;
+    ensure_empty_dir(db_path)?;
+    ensure_empty_dir(static_files_path)?;
+    fs::create_dir_all(db_path).map_err(ProviderError::other)?;
+    fs::create_dir_all(static_files_path).map_err(ProviderError::other)?;
+
+    for file in &manifest.files {
+        let source = snapshot_file_path(snapshot, &file.path)?;
+        let target = if let Ok(path) = file.path.strip_prefix(SNAPSHOT_DB_DIR) {
+            db_path.join(path)
+        } else if let Ok(path) = file.path.strip_prefix(SNAPSHOT_STATIC_FILES_DIR) {
+            static_files_path.join(path)
+        } else {
+            return Err(ProviderError::SnapshotFileMismatch(file.path.clone()))
+        };
+        copy(&source, &target)?;
+    }
+
+    Ok(manifest)
+}
+
+/// Returns an error if the path exists and is not an empty directory.
+fn ensure_empty_dir(path: &Path) -> ProviderResult<()> {
+    if path.exists() && fs::read_dir(path).map_err(ProviderError::other)?.next().is_some() {
+        return Err(ProviderError::SnapshotDestinationNotEmpty(path.to_path_buf()))
+    }
+    Ok(())
+}
+
+/// Returns the absolute path of a file of the snapshot, rejecting paths that escape it.
+fn snapshot_file_path(snapshot: &Path, path: &Path) -> ProviderResult<PathBuf> {
+    if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(ProviderError::SnapshotFileMismatch(path.to_path_buf()))
+    }
+    Ok(snapshot.join(path))
+}
+
+/// Collects the size and checksum of all files in the database and static files directories of
+/// the snapshot.
+fn snapshot_files(snapshot: &Path) -> ProviderResult<Vec<SnapshotFile>> {
+    let mut files = Vec::new();
+    for dir in [SNAPSHOT_DB_DIR, SNAPSHOT_STATIC_FILES_DIR] {
+        let mut entries = fs::read_dir(snapshot.join(dir))
+            .map_err(ProviderError::other)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        for path in entries {
+            let size = fs::metadata(&path).map_err(ProviderError::other)?.len();
+            let relative = path.strip_prefix(snapshot).expect("is in the snapshot").to_path_buf();
+            files.push(SnapshotFile { path: relative, size, checksum: checksum(&path)? });
+        }
+    }
+    Ok(files)
+}
+
+/// Returns the `blake3` hash of the file contents.
+fn checksum(path: &Path) -> ProviderResult<B256> {
+    let mut reader = BufReader::new(fs::open(path).map_err(ProviderError::other)?);
+    let mut hasher = blake3::Hasher::new();
+    std::io::copy(&mut reader, &mut hasher)
+        .map_err(|err| ProviderError::other(FsPathError::read(err, path)))?;
+    Ok(B256::from(*hasher.finalize().as_bytes()))
+}
+
+/// Copies the file.
+fn copy(from: &Path, to: &Path) -> ProviderResult<()> {
+    std::fs::copy(from, to)
+        .map_err(|err| ProviderError::other(FsPathError::write(err, to)))
+        .map(drop)
+}
+
+/// Hard-links the file, or copies it if it can't be linked, e.g. because the target is on another
+/// file system.
+fn link_or_copy(from: &Path, to: &Path) -> ProviderResult<()> {
+    if std::fs::hard_link(from, to).is_err() {
+        copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileProviderFactory,
+    };
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_primitives_traits::Account;
+    use reth_stages_types::StageCheckpoint;
+
+    #[test]
+    fn snapshot_and_restore() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(Address::ZERO, Account::default())
+            .unwrap();
+        provider.commit().unwrap();
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&Default::default(), U256::ZERO, &B256::ZERO).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot");
+        let manifest =
+            factory.snapshot(&snapshot, SnapshotOptions::default().with_compact(true)).unwrap();
+        assert!(manifest.compacted);
+        assert_eq!(manifest.static_files.get(&StaticFileSegment::Headers), Some(&0));
+        assert!(manifest
+            .files
+            .iter()
+            .any(|file| file.path == Path::new(SNAPSHOT_DB_DIR).join(MDBX_DATA_FILE_NAME)));
+        assert!(manifest.files.iter().any(|file| file.path.starts_with(SNAPSHOT_STATIC_FILES_DIR)));
+
+        // The destination must be empty.
+        assert!(matches!(
+            factory.snapshot(&snapshot, SnapshotOptions::default()),
+            Err(ProviderError::SnapshotDestinationNotEmpty(_))
+        ));
+
+        assert_eq!(verify_snapshot(&snapshot).unwrap(), manifest);
+
+        let restored = dir.path().join("restored");
+        restore_snapshot(&snapshot, &restored.join("db"), &restored.join("static_files")).unwrap();
+        assert!(restored.join("db").join(MDBX_DATA_FILE_NAME).is_file());
+
+        // Corrupt a file of the snapshot.
+        let file = &manifest.files[0];
+        fs::write(snapshot.join(&file.path), b"corrupted").unwrap();
+        assert!(matches!(
+            verify_snapshot(&snapshot),
+            Err(ProviderError::SnapshotFileMismatch(path)) if path == file.path
+        ));
+    }
+
+    #[test]
+    fn snapshot_static_files_up_to_checkpoints() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..=2 {
+            let header = Header { number, ..Default::default() };
+            writer.append_header(&header, U256::ZERO, &B256::with_last_byte(number as u8)).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        // The database only refers to the first two headers
+        let provider = factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(1)).unwrap();
+        provider.commit().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot");
+        let manifest = factory.snapshot(&snapshot, SnapshotOptions::default()).unwrap();
+        assert_eq!(manifest.static_files.get(&StaticFileSegment::Headers), Some(&1));
+        assert!(!snapshot.join(SNAPSHOT_DB_DIR).join(MDBX_LOCK_FILE_NAME).exists());
+        assert_eq!(verify_snapshot(&snapshot).unwrap(), manifest);
+
+        let snapshot_static_files = StaticFileProvider::<EthPrimitives>::read_only(
+            snapshot.join(SNAPSHOT_STATIC_FILES_DIR),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            snapshot_static_files.get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(1)
+        );
+
+        // The database refers to headers that were unwound from the static files
+        let provider = factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(5)).unwrap();
+        provider.commit().unwrap();
+
+        assert!(matches!(
+            factory.snapshot(&dir.path().join("unwound"), SnapshotOptions::default()),
+            Err(ProviderError::SnapshotStaticFilesUnwound {
+                segment: StaticFileSegment::Headers,
+                height: 5
+            })
+        ));
+    }
+}
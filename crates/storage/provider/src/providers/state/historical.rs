@@ -1,9 +1,14 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    providers::state::{macros::delegate_provider_impls, snap::SnapSyncedState},
+    AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+    LatestStateProviderRef, ProviderError, StateProvider, StateRootProvider,
+    StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     models::{storage_sharded_key::StorageShardedKey, ShardedKey},
@@ -13,22 +18,27 @@ use reth_db_api::{
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode};
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, ChangeSetRangeReader, DBProvider, StateCommitmentProvider, StateProofProvider,
+    StorageRootProvider, MAX_PREIMAGE_LOOKUP_ENTRIES,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
+    prefix_set::{TriePrefixSets, TriePrefixSetsMut},
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
+    AccountProof, HashedAccountRange, HashedPostState, HashedPostStateSorted, HashedStateRange,
+    HashedStorage, HashedStorageRange, KeyHasher, MultiProof, MultiProofTargets, StateRoot,
     StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
+    ChangesetHashedPostState, ChangesetHashedStorage, DatabaseHashedStateRange, DatabaseProof,
+    DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    StateCommitment,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Bound, sync::OnceLock};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -39,8 +49,9 @@ use std::fmt::Debug;
 /// - [`tables::AccountsHistory`]
 /// - [`tables::Bytecodes`]
 /// - [`tables::StoragesHistory`]
-/// - [`tables::AccountChangeSets`]
-/// - [`tables::StorageChangeSets`]
+/// - [`tables::AccountChangeSets`] and [`StaticFileSegment::AccountChangeSets`]
+/// - [`tables::StorageChangeSets`] and [`StaticFileSegment::StorageChangeSets`]
+/// - [`tables::HashedAccounts`] and [`tables::HashedStorages`], if the database is snap synced
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -49,6 +60,30 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// The state downloaded by the snap sync, if the database is snap synced.
+    snap_synced: SnapSyncedState,
+    /// Revert state shared with the [`HistoricalStateProvider`] this provider was created from, so
+    /// that it's only computed once.
+    revert_state: Option<&'b RevertStateCache>,
+}
+
+/// Revert state of a [`HistoricalStateProvider`], computed once by the first range, proof or root
+/// that needs it.
+#[derive(Debug, Default)]
+struct RevertStateCache {
+    /// The revert state.
+    state: OnceLock<HashedPostState>,
+    /// The sorted revert state and its prefix sets, used by the ranges and proofs that don't add
+    /// an overlay of their own.
+    overlay: OnceLock<RevertOverlay>,
+}
+
+/// Sorted revert state and its prefix sets.
+#[derive(Debug)]
+struct RevertOverlay {
+    state: HashedPostStateSorted,
+    prefix_sets: TriePrefixSetsMut,
+    frozen_prefix_sets: TriePrefixSets,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -59,12 +94,24 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProviderRef<'b, Provider>
+impl<
+        'b,
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > HistoricalStateProviderRef<'b, Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snap_synced: SnapSyncedState::new(),
+            revert_state: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -74,21 +121,31 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks,
+            snap_synced: SnapSyncedState::new(),
+            revert_state: None,
+        }
     }
 
     /// Lookup an account in the `AccountsHistory` table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number =
+            if self.lowest_available_blocks.is_account_history_available(self.block_number) {
+                self.lowest_available_blocks.account_history_block_number
+            } else {
+                // The history of the filtered addresses is not pruned.
+                self.address_history_retained_from(address)?
+            };
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountsHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -98,19 +155,53 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number =
+            if self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+                self.lowest_available_blocks.storage_history_block_number
+            } else {
+                // The history of the filtered addresses is not pruned.
+                self.address_history_retained_from(address)?
+            };
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StoragesHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            lowest_available_block_number,
         )
     }
 
+    /// Returns the lowest block at which the history of the address is available, if the address
+    /// is retained by the [`HistoryAddressFilter`](reth_prune_types::HistoryAddressFilter), for
+    /// when the history at the block is otherwise pruned.
+    ///
+    /// Returns an error if the address isn't retained, or was added to the filter after its history
+    /// at the block was pruned.
+    fn address_history_retained_from(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        let filter = &self.provider.prune_modes_ref().history_address_filter;
+        if filter.is_empty() {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        // The block from which the history is retained is saved by the pruner, before it prunes
+        // anything while the address is in the filter.
+        match self.tx().get::<tables::HistoryAddressCheckpoints>(address)? {
+            Some(retained_from)
+                if filter.contains(&address) && self.block_number >= retained_from =>
+            {
+                Ok((retained_from > 0).then_some(retained_from))
+            }
+            _ => Err(ProviderError::AddressHistoryPruned {
+                address,
+                block_number: self.block_number,
+            }),
+        }
+    }
+
     /// Checks and returns `true` if distance to historical block exceeds the provided limit.
     fn check_distance_against_limit(&self, limit: u64) -> ProviderResult<bool> {
         let tip = self.provider.last_block_number()?;
@@ -120,6 +211,10 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        if let Some(revert_state) = self.revert_state.and_then(|cache| cache.state.get()) {
+            return Ok(revert_state.clone())
+        }
+
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -134,15 +229,44 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
-            <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        // Older changes take precedence, so the changesets are loaded from the tip down, one chunk
+        // of blocks at a time
+        let mut revert_state = HashedPostState::default();
+        for range in revert_ranges(self.block_number, self.provider.last_block_number()?) {
+            revert_state.extend(HashedPostState::from_changesets::<
+                <Provider::StateCommitment as StateCommitment>::KeyHasher,
+            >(
+                self.provider.account_changesets_range(range)?,
+                self.provider.storage_changesets_range(range)?,
+            ));
+        }
+        if let Some(cache) = self.revert_state {
+            let _ = cache.state.set(revert_state.clone());
+        }
+        Ok(revert_state)
+    }
+
+    /// Returns the sorted revert state and its prefix sets, if they can be cached across calls.
+    fn revert_overlay(&self) -> ProviderResult<Option<&'b RevertOverlay>> {
+        let Some(cache) = self.revert_state else { return Ok(None) };
+        if let Some(overlay) = cache.overlay.get() {
+            return Ok(Some(overlay))
+        }
+
+        let revert_state = self.revert_state()?;
+        let prefix_sets = revert_state.construct_prefix_sets();
+        let overlay = RevertOverlay {
+            frozen_prefix_sets: prefix_sets.clone().freeze(),
+            prefix_sets,
+            state: revert_state.into_sorted(),
+        };
+        Ok(Some(cache.overlay.get_or_init(|| overlay)))
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+            self.address_history_retained_from(address)?;
         }
 
         if self.check_distance_against_limit(EPOCH_SLOTS * 10)? {
@@ -153,7 +277,14 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let mut revert_storage = HashedStorage::default();
+        for range in revert_ranges(self.block_number, self.provider.last_block_number()?) {
+            revert_storage.extend(&HashedStorage::from_changesets(
+                address,
+                self.provider.storage_changesets_range(range)?,
+            ));
+        }
+        Ok(revert_storage)
     }
 
     fn history_info<T, K>(
@@ -244,25 +375,43 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, *address)?
-                .filter(|acc| &acc.address == address)
+                .provider
+                .static_file_provider()
+                .get_with_static_file_or_database(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                    |static_file| static_file.account_change(changeset_block_number, *address),
+                    || {
+                        Ok(self
+                            .tx()
+                            .cursor_dup_read::<tables::AccountChangeSets>()?
+                            .seek_by_key_subkey(changeset_block_number, *address)?
+                            .filter(|acc| &acc.address == address))
+                    },
+                )?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address: *address,
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
+                match self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+                    Some(account) => Ok(Some(account)),
+                    None => self.snap_synced.account(self.tx(), address),
+                }
             }
         }
     }
@@ -285,8 +434,13 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > StateRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
         let mut revert_state = self.revert_state()?;
@@ -321,8 +475,13 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > StorageRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
         &self,
@@ -360,8 +519,13 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > StateProofProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
     fn proof(
@@ -379,6 +543,18 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
+        if is_empty_input(&input) {
+            if let Some(overlay) = self.revert_overlay()? {
+                return Proof::overlay_multiproof_sorted(
+                    self.tx(),
+                    &overlay.state,
+                    overlay.prefix_sets.clone(),
+                    targets,
+                )
+                .map_err(ProviderError::from)
+            }
+        }
+
         input.prepend(self.revert_state()?);
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
@@ -401,8 +577,136 @@ impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider>
-    StateProvider for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > HashedStateRangeProvider for HistoricalStateProviderRef<'_, Provider>
+{
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedAccountRange> {
+        if hashed_state.is_empty() {
+            if let Some(overlay) = self.revert_overlay()? {
+                return HashedStateRange::overlay_accounts_sorted(
+                    self.tx(),
+                    &overlay.state,
+                    overlay.frozen_prefix_sets.clone(),
+                    start,
+                    limit,
+                )
+                .map_err(|err| ProviderError::Database(err.into()))
+            }
+        }
+
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        HashedStateRange::overlay_accounts(self.tx(), revert_state, start, limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        if hashed_state.is_empty() {
+            if let Some(overlay) = self.revert_overlay()? {
+                return HashedStateRange::overlay_storage_sorted(
+                    self.tx(),
+                    &overlay.state,
+                    hashed_address,
+                    start,
+                    limit,
+                )
+                .map_err(Into::into)
+            }
+        }
+
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        HashedStateRange::overlay_storage(self.tx(), revert_state, hashed_address, start, limit)
+            .map_err(Into::into)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        // Accounts that were destroyed after the block are no longer in the plain state, but they
+        // still appear in the account history index.
+        let mut preimages =
+            LatestStateProviderRef::new(self.provider).account_preimages(hashed_addresses)?;
+        let mut cursor = self.tx().cursor_read::<tables::AccountsHistory>()?;
+        for entry in cursor.walk(None)?.take(MAX_PREIMAGE_LOOKUP_ENTRIES) {
+            if preimages.len() == hashed_addresses.len() {
+                break
+            }
+
+            let (key, _) = entry?;
+            let hashed_address =
+                <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(key.key);
+            if hashed_addresses.contains(&hashed_address) {
+                preimages.insert(hashed_address, key.key);
+            }
+        }
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>> {
+        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        // Slots that were cleared after the block are no longer in the plain state, but they
+        // still appear in the storage history index.
+        let mut preimages =
+            LatestStateProviderRef::new(self.provider).storage_preimages(address, hashed_slots)?;
+        let mut cursor = self.tx().cursor_read::<tables::StoragesHistory>()?;
+        for entry in cursor
+            .walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))?
+            .take(MAX_PREIMAGE_LOOKUP_ENTRIES)
+        {
+            if preimages.len() == hashed_slots.len() {
+                break
+            }
+
+            let (key, _) = entry?;
+            if key.address != address {
+                break
+            }
+
+            let slot = key.sharded_key.key;
+            let hashed_slot =
+                <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(slot);
+            if hashed_slots.contains(&hashed_slot) {
+                preimages.insert(hashed_slot, slot);
+            }
+        }
+        Ok(preimages)
+    }
+}
+
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + BlockHashReader
+            + StateCommitmentProvider,
+    > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -413,10 +717,25 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.provider
+                    .static_file_provider()
+                    .get_with_static_file_or_database(
+                        StaticFileSegment::StorageChangeSets,
+                        changeset_block_number,
+                        |static_file| {
+                            static_file.storage_change(changeset_block_number, address, storage_key)
+                        },
+                        || {
+                            Ok(self
+                                .tx()
+                                .cursor_dup_read::<tables::StorageChangeSets>()?
+                                .seek_by_key_subkey(
+                                    (changeset_block_number, address).into(),
+                                    storage_key,
+                                )?
+                                .filter(|entry| entry.key == storage_key))
+                        },
+                    )?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -424,13 +743,18 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                let value = match self
+                    .tx()
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                {
+                    Some(entry) => Some(entry.value),
+                    None => self.snap_synced.storage(self.tx(), address, storage_key)?,
+                };
+                Ok(value.or(Some(StorageValue::ZERO)))
+            }
         }
     }
 
@@ -456,14 +780,26 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Revert state, computed once by the first range, proof or root that needs it.
+    revert_state: RevertStateCache,
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProvider<Provider>
+impl<
+        Provider: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + StateCommitmentProvider,
+    > HistoricalStateProvider<Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            revert_state: Default::default(),
+        }
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -487,11 +823,13 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        let mut provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
             &self.provider,
             self.block_number,
             self.lowest_available_blocks,
-        )
+        );
+        provider.revert_state = Some(&self.revert_state);
+        provider
     }
 }
 
@@ -502,7 +840,7 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + StaticFileProviderFactory + ChangeSetRangeReader + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -532,26 +870,68 @@ impl LowestAvailableBlocks {
     }
 }
 
+/// Returns `true` if the trie input doesn't add any nodes, state or prefix sets.
+fn is_empty_input(input: &TrieInput) -> bool {
+    input.nodes.is_empty() &&
+        input.state.is_empty() &&
+        input.prefix_sets.account_prefix_set.is_empty() &&
+        input.prefix_sets.storage_prefix_sets.is_empty() &&
+        input.prefix_sets.destroyed_accounts.is_empty()
+}
+
+/// Number of blocks whose changesets are loaded at once to compute the revert state.
+const REVERT_RANGE_BLOCKS: u64 = 1_000;
+
+/// Splits the blocks from `from` onwards into ranges of [`REVERT_RANGE_BLOCKS`] blocks, from the
+/// tip down.
+///
+/// The first range is open-ended, so it includes changesets above `tip` if there are any.
+fn revert_ranges(
+    from: BlockNumber,
+    tip: BlockNumber,
+) -> impl Iterator<Item = (Bound<BlockNumber>, Bound<BlockNumber>)> {
+    let ranges = tip.saturating_sub(from) / REVERT_RANGE_BLOCKS + 1;
+    (0..ranges).map(move |range| {
+        let start = from + (ranges - range - 1) * REVERT_RANGE_BLOCKS;
+        let end = if range == 0 {
+            Bound::Unbounded
+        } else {
+            Bound::Excluded(start + REVERT_RANGE_BLOCKS)
+        };
+        (Bound::Included(start), end)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{revert_ranges, HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        AccountReader, HashedStateRangeProvider, HistoricalStateProvider,
+        HistoricalStateProviderRef, StateProvider, StaticFileProviderFactory,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey,
+            StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+        },
         tables,
         transaction::{DbTx, DbTxMut},
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_prune_types::{HistoryAddressFilter, PruneModes};
+    use reth_static_file_types::StaticFileSegment;
     use reth_storage_api::{
-        BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
-        StateCommitmentProvider,
+        BlockHashReader, BlockNumReader, ChangeSetRangeReader, DBProvider, DatabaseProviderFactory,
+        StateCommitmentProvider, StateProofProvider,
     };
     use reth_storage_errors::provider::ProviderError;
+    use reth_trie::{HashedPostState, MultiProofTargets, TrieInput};
 
     const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000005");
@@ -561,7 +941,12 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider,
+        T: DBProvider
+            + StaticFileProviderFactory
+            + ChangeSetRangeReader
+            + BlockNumReader
+            + BlockHashReader
+            + StateCommitmentProvider,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
@@ -774,6 +1159,170 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_provider_get_from_static_files() {
+        let factory = create_test_provider_factory();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at5 = Account { nonce: 5, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at5 = StorageEntry { key: STORAGE, value: U256::from(5) };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+
+        // setup changesets of blocks up to 3 in static files only
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=3 {
+            let mut account_changeset = StaticFileAccountChangeSet::default();
+            let mut storage_changeset = StaticFileStorageChangeSet::default();
+            // the account is created in block 1
+            if block == 1 {
+                account_changeset.changes.push(AccountBeforeTx { address: ADDRESS, info: None });
+                storage_changeset.changes.push(StorageBeforeTx {
+                    address: ADDRESS,
+                    entry: StorageEntry { key: STORAGE, value: U256::ZERO },
+                });
+            }
+            if block == 3 {
+                account_changeset
+                    .changes
+                    .push(AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) });
+                storage_changeset
+                    .changes
+                    .push(StorageBeforeTx { address: ADDRESS, entry: entry_at3 });
+            }
+            account_writer.append_account_changeset(&account_changeset, block).unwrap();
+            storage_writer.append_storage_changeset(&storage_changeset, block).unwrap();
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop(account_writer);
+        drop(storage_writer);
+
+        // setup changesets of later blocks, history and plain state in the database
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3, 5]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([1, 3, 5]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            5,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at5) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>((5, ADDRESS).into(), entry_at5).unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // run
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 2).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_at3
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 4).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_at5
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 6).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_plain
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 2).storage(ADDRESS, STORAGE),
+            Ok(Some(value)) if value == entry_at3.value
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 4).storage(ADDRESS, STORAGE),
+            Ok(Some(value)) if value == entry_at5.value
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 6).storage(ADDRESS, STORAGE),
+            Ok(Some(value)) if value == entry_plain.value
+        ));
+    }
+
+    #[test]
+    fn history_provider_reuses_revert_overlay() {
+        let factory = create_test_provider_factory();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at5 = Account { nonce: 5, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at5 = StorageEntry { key: STORAGE, value: U256::from(5) };
+        let hashed_address = keccak256(ADDRESS);
+        let hashed_slot = keccak256(STORAGE);
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::AccountChangeSets>(
+            5,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at5) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>((5, ADDRESS).into(), entry_at5).unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.put::<tables::HashedAccounts>(hashed_address, acc_plain).unwrap();
+        tx.put::<tables::HashedStorages>(
+            hashed_address,
+            StorageEntry { key: hashed_slot, value: entry_plain.value },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        let uncached = HistoricalStateProviderRef::new(&db, 4);
+        let cached = HistoricalStateProvider::new(factory.provider().unwrap(), 4);
+
+        let targets = MultiProofTargets::account_with_slots(hashed_address, [hashed_slot]);
+        let expected_accounts =
+            uncached.hashed_account_range(HashedPostState::default(), B256::ZERO, 10).unwrap();
+        assert_eq!(expected_accounts.accounts[0].account, acc_at5);
+        let expected_storage = uncached
+            .hashed_storage_range(HashedPostState::default(), hashed_address, B256::ZERO, 10)
+            .unwrap();
+        assert_eq!(expected_storage.slots, vec![(hashed_slot, entry_at5.value)]);
+        let expected_proof = uncached.multiproof(TrieInput::default(), targets.clone()).unwrap();
+
+        // the sorted revert state is reused by every range and proof
+        for _ in 0..2 {
+            assert_eq!(
+                cached.hashed_account_range(HashedPostState::default(), B256::ZERO, 10).unwrap(),
+                expected_accounts
+            );
+            assert_eq!(
+                cached
+                    .hashed_storage_range(
+                        HashedPostState::default(),
+                        hashed_address,
+                        B256::ZERO,
+                        10
+                    )
+                    .unwrap(),
+                expected_storage
+            );
+            assert_eq!(
+                cached.multiproof(TrieInput::default(), targets.clone()).unwrap(),
+                expected_proof
+            );
+        }
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -836,4 +1385,108 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+
+    #[test]
+    fn history_provider_unavailable_with_address_filter() {
+        let factory = create_test_provider_factory();
+        let mut db = factory.database_provider_rw().unwrap();
+        db.set_prune_modes(PruneModes {
+            history_address_filter: HistoryAddressFilter::from_iter([ADDRESS]),
+            ..Default::default()
+        });
+
+        let lowest_available_blocks = LowestAvailableBlocks {
+            account_history_block_number: Some(3),
+            storage_history_block_number: Some(3),
+        };
+
+        // provider block_number < lowest available block number, and the pruner hasn't saved from
+        // which block the history of the filtered address is retained yet
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+        );
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == ADDRESS && block_number == provider.block_number
+        ));
+
+        // the history of the filtered address is retained from genesis
+        db.tx_ref().put::<tables::HistoryAddressCheckpoints>(ADDRESS, 0).unwrap();
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+        );
+        assert!(matches!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten)));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::NotYetWritten)
+        ));
+
+        // the history of the filtered address is retained from a block before the provider block,
+        // the history before it is pruned
+        db.tx_ref().put::<tables::HistoryAddressCheckpoints>(ADDRESS, 1).unwrap();
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+        );
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Ok(HistoryInfo::MaybeInPlainState)
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::MaybeInPlainState)
+        ));
+
+        // the filtered address was added after its history at the provider block was pruned
+        db.tx_ref().put::<tables::HistoryAddressCheckpoints>(ADDRESS, 3).unwrap();
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+        );
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == ADDRESS && block_number == provider.block_number
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == ADDRESS && block_number == provider.block_number
+        ));
+
+        // history of the other addresses is pruned
+        assert!(matches!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == HIGHER_ADDRESS && block_number == provider.block_number
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(HIGHER_ADDRESS, STORAGE),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == HIGHER_ADDRESS && block_number == provider.block_number
+        ));
+    }
+
+    #[test]
+    fn revert_ranges_from_tip_down() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        assert_eq!(revert_ranges(5, 5).collect::<Vec<_>>(), vec![(Included(5), Unbounded)]);
+        assert_eq!(revert_ranges(5, 0).collect::<Vec<_>>(), vec![(Included(5), Unbounded)]);
+        assert_eq!(
+            revert_ranges(5, 2504).collect::<Vec<_>>(),
+            vec![
+                (Included(2005), Unbounded),
+                (Included(1005), Excluded(2005)),
+                (Included(5), Excluded(1005)),
+            ]
+        );
+    }
 }
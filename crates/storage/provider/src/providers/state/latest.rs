@@ -1,54 +1,72 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, StateProvider, StateRootProvider,
+    providers::state::{macros::delegate_provider_impls, snap::SnapSyncedState},
+    AccountReader, BlockHashReader, HashedPostStateProvider, HashedStateRangeProvider,
+    StateProvider, StateRootProvider,
+};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
 };
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
     DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    MAX_PREIMAGE_LOOKUP_ENTRIES,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedAccountRange, HashedPostState, HashedStateRange, HashedStorage,
+    HashedStorageRange, KeyHasher, MultiProof, MultiProofTargets, StateRoot, StorageMultiProof,
+    StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedStateRange, DatabaseProof, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
 
 /// State provider over latest state that takes tx reference.
 ///
 /// Wraps a [`DBProvider`] to get access to database.
 #[derive(Debug)]
-pub struct LatestStateProviderRef<'b, Provider>(&'b Provider);
+pub struct LatestStateProviderRef<'b, Provider> {
+    /// Database provider
+    provider: &'b Provider,
+    /// The state downloaded by the snap sync, if the database is snap synced.
+    snap_synced: SnapSyncedState,
+}
 
 impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     /// Create new state provider
     pub const fn new(provider: &'b Provider) -> Self {
-        Self(provider)
+        Self { provider, snap_synced: SnapSyncedState::new() }
     }
 
     fn tx(&self) -> &Provider::Tx {
-        self.0.tx_ref()
+        self.provider.tx_ref()
     }
 }
 
 impl<Provider: DBProvider> AccountReader for LatestStateProviderRef<'_, Provider> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
-        self.tx().get_by_encoded_key::<tables::PlainAccountState>(address).map_err(Into::into)
+        match self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+            Some(account) => Ok(Some(account)),
+            None => self.snap_synced.account(self.tx(), address),
+        }
     }
 }
 
 impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, Provider> {
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
-        self.0.block_hash(number)
+        self.provider.block_hash(number)
     }
 
     fn canonical_hashes_range(
@@ -56,7 +74,7 @@ impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, P
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>> {
-        self.0.canonical_hashes_range(start, end)
+        self.provider.canonical_hashes_range(start, end)
     }
 }
 
@@ -160,6 +178,71 @@ impl<Provider: DBProvider + StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
+impl<Provider: DBProvider + StateCommitmentProvider> HashedStateRangeProvider
+    for LatestStateProviderRef<'_, Provider>
+{
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedAccountRange> {
+        HashedStateRange::overlay_accounts(self.tx(), hashed_state, start, limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        HashedStateRange::overlay_storage(self.tx(), hashed_state, hashed_address, start, limit)
+            .map_err(Into::into)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>> {
+        let mut preimages = B256Map::default();
+        let mut cursor = self.tx().cursor_read::<tables::PlainAccountState>()?;
+        for entry in cursor.walk(None)?.take(MAX_PREIMAGE_LOOKUP_ENTRIES) {
+            if preimages.len() == hashed_addresses.len() {
+                break
+            }
+
+            let (address, _) = entry?;
+            let hashed_address =
+                <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(address);
+            if hashed_addresses.contains(&hashed_address) {
+                preimages.insert(hashed_address, address);
+            }
+        }
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>> {
+        let mut preimages = B256Map::default();
+        let mut cursor = self.tx().cursor_dup_read::<tables::PlainStorageState>()?;
+        for entry in cursor.walk_dup(Some(address), None)?.take(MAX_PREIMAGE_LOOKUP_ENTRIES) {
+            if preimages.len() == hashed_slots.len() {
+                break
+            }
+
+            let (_, entry) = entry?;
+            let hashed_slot =
+                <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(entry.key);
+            if hashed_slots.contains(&hashed_slot) {
+                preimages.insert(hashed_slot, entry.key);
+            }
+        }
+        Ok(preimages)
+    }
+}
+
 impl<Provider: DBProvider + BlockHashReader + StateCommitmentProvider> StateProvider
     for LatestStateProviderRef<'_, Provider>
 {
@@ -175,7 +258,7 @@ impl<Provider: DBProvider + BlockHashReader + StateCommitmentProvider> StateProv
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        self.snap_synced.storage(self.tx(), account, storage_key)
     }
 
     /// Get account code by its hash
@@ -60,6 +60,12 @@ macro_rules! delegate_provider_impls {
             HashedPostStateProvider $(where [$($generics)*])? {
                 fn hashed_post_state(&self, bundle_state: &revm_database::BundleState) -> reth_trie::HashedPostState;
             }
+            HashedStateRangeProvider $(where [$($generics)*])? {
+                fn hashed_account_range(&self, hashed_state: reth_trie::HashedPostState, start: alloy_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::HashedAccountRange>;
+                fn hashed_storage_range(&self, hashed_state: reth_trie::HashedPostState, hashed_address: alloy_primitives::B256, start: alloy_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::HashedStorageRange>;
+                fn account_preimages(&self, hashed_addresses: &alloy_primitives::map::B256Set) -> reth_storage_errors::provider::ProviderResult<alloy_primitives::map::B256Map<alloy_primitives::Address>>;
+                fn storage_preimages(&self, address: alloy_primitives::Address, hashed_slots: &alloy_primitives::map::B256Set) -> reth_storage_errors::provider::ProviderResult<alloy_primitives::map::B256Map<alloy_primitives::B256>>;
+            }
         );
     }
 }
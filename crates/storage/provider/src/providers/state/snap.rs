@@ -0,0 +1,68 @@
+use alloy_primitives::{keccak256, Address, StorageKey, StorageValue};
+use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_primitives_traits::Account;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::ProviderResult;
+use std::sync::OnceLock;
+
+/// Reads the state that was downloaded by the snap sync stage.
+///
+/// The snap sync only downloads the hashed state, so the plain state tables of a snap synced
+/// database only contain the accounts and storage slots that were changed by the blocks executed
+/// after the snap sync. The hashed state is kept up to date by the execution stage of such a
+/// database, so plain state misses are looked up in the hashed state tables instead.
+///
+/// A database is snap synced if the progress of the [`StageId::SnapSync`] checkpoint is set.
+#[derive(Debug, Default)]
+pub(crate) struct SnapSyncedState {
+    /// Whether the database is snap synced, read on the first lookup.
+    enabled: OnceLock<bool>,
+}
+
+impl SnapSyncedState {
+    /// Creates a new [`SnapSyncedState`].
+    pub(crate) const fn new() -> Self {
+        Self { enabled: OnceLock::new() }
+    }
+
+    /// Returns whether the database is snap synced.
+    fn is_enabled<TX: DbTx>(&self, tx: &TX) -> ProviderResult<bool> {
+        if let Some(enabled) = self.enabled.get() {
+            return Ok(*enabled)
+        }
+        let enabled = tx
+            .get::<tables::StageCheckpointProgresses>(StageId::SnapSync.to_string())?
+            .is_some_and(|progress| !progress.is_empty());
+        Ok(*self.enabled.get_or_init(|| enabled))
+    }
+
+    /// Returns the account that is missing from the plain state.
+    pub(crate) fn account<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: &Address,
+    ) -> ProviderResult<Option<Account>> {
+        if !self.is_enabled(tx)? {
+            return Ok(None)
+        }
+        Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+    }
+
+    /// Returns the storage slot that is missing from the plain state.
+    pub(crate) fn storage<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if !self.is_enabled(tx)? {
+            return Ok(None)
+        }
+        let hashed_slot = keccak256(storage_key);
+        Ok(tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|entry| entry.key == hashed_slot)
+            .map(|entry| entry.value))
+    }
+}
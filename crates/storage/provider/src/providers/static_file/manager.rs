@@ -19,13 +19,17 @@ use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        iter_static_files, AccountChangeSetMask, BlockHashMask, BodyIndicesMask, ColumnSelectorOne,
+        HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask,
+        TDWithHashMask, TransactionMask,
     },
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::StoredBlockBodyIndices,
+    models::{
+        AccountBeforeTx, BlockNumberAddress, StaticFileAccountChangeSet,
+        StaticFileStorageChangeSet, StoredBlockBodyIndices,
+    },
     table::{Decompress, Table, Value},
     tables,
     transaction::DbTx,
@@ -33,7 +37,9 @@ use reth_db_api::{
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
-use reth_primitives_traits::{RecoveredBlock, SealedBlock, SealedHeader, SignedTransaction};
+use reth_primitives_traits::{
+    RecoveredBlock, SealedBlock, SealedHeader, SignedTransaction, StorageEntry,
+};
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_static_file_types::{
     find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
@@ -310,29 +316,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             let mut size = 0;
 
             for (block_range, _) in &ranges {
-                let fixed_block_range = self.find_fixed_range(block_range.start());
-                let jar_provider = self
-                    .get_segment_provider(segment, || Some(fixed_block_range), None)?
-                    .ok_or_else(|| {
-                        ProviderError::MissingStaticFileBlock(segment, block_range.start())
-                    })?;
-
+                let jar_provider = self.get_jar_provider(segment, block_range.start())?;
                 entries += jar_provider.rows();
-
-                let data_size = reth_fs_util::metadata(jar_provider.data_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let index_size = reth_fs_util::metadata(jar_provider.index_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let offsets_size = reth_fs_util::metadata(jar_provider.offsets_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let config_size = reth_fs_util::metadata(jar_provider.config_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-
-                size += data_size + index_size + offsets_size + config_size;
+                size += Self::jar_size(&jar_provider);
             }
 
             metrics.record_segment(segment, size, ranges.len(), entries);
@@ -341,6 +327,47 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Returns the total size of the static files of the segment on disk, in bytes.
+    pub fn segment_size(&self, segment: StaticFileSegment) -> ProviderResult<u64> {
+        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let Some(ranges) = static_files.get(&segment) else { return Ok(0) };
+
+        let mut size = 0;
+        for (block_range, _) in ranges {
+            size += Self::jar_size(&self.get_jar_provider(segment, block_range.start())?);
+        }
+        Ok(size)
+    }
+
+    /// Gets the [`StaticFileJarProvider`] of the static file that starts at the block.
+    fn get_jar_provider(
+        &self,
+        segment: StaticFileSegment,
+        block_range_start: BlockNumber,
+    ) -> ProviderResult<StaticFileJarProvider<'_, N>> {
+        let fixed_block_range = self.find_fixed_range(block_range_start);
+        self.get_segment_provider(segment, || Some(fixed_block_range), None)?
+            .ok_or(ProviderError::MissingStaticFileBlock(segment, block_range_start))
+    }
+
+    /// Returns the size of the data, index, offsets and config files of the jar, in bytes.
+    fn jar_size(jar_provider: &StaticFileJarProvider<'_, N>) -> u64 {
+        let data_size = reth_fs_util::metadata(jar_provider.data_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        let index_size = reth_fs_util::metadata(jar_provider.index_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        let offsets_size = reth_fs_util::metadata(jar_provider.offsets_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        let config_size = reth_fs_util::metadata(jar_provider.config_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+
+        data_size + index_size + offsets_size + config_size
+    }
+
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block.
     pub fn get_segment_provider_from_block(
         &self,
@@ -736,6 +763,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 update_unwind_target(highest_block.unwrap_or_default());
             }
 
+            if segment.is_change_sets() {
+                self.ensure_change_sets_invariants(provider, segment, highest_block)?;
+                continue
+            }
+
             // Only applies to transaction-based static files. (Receipts & Transactions)
             //
             // Make sure the last transaction matches the last block from its indices, since a heal
@@ -801,6 +833,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_block,
                         highest_block,
                     )?,
+                // Checked by `ensure_change_sets_invariants` above
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => None,
             } {
                 update_unwind_target(unwind);
             }
@@ -888,6 +922,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
                 StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    unreachable!("changesets are checked by `ensure_change_sets_invariants`")
+                }
             })?
             .unwrap_or_default()
             .block_number;
@@ -935,6 +972,49 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Check invariants of the changeset static file segments.
+    ///
+    /// Changesets are only moved to static files once the history indices have been built from
+    /// them, and blocks may have no changes at all, so a gap between the static files and the
+    /// database table can't be told apart from lost data. However, changeset static files are
+    /// unwound together with the execution stage, so they should never be ahead of its
+    /// checkpoint. If they are, then we failed to do a database commit **but committed** to
+    /// static files, and the extra static file rows are pruned.
+    fn ensure_change_sets_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<()>
+    where
+        Provider: StageCheckpointReader,
+    {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(()) };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let to_delete = highest_static_file_block - checkpoint_block_number;
+            let mut writer = self.latest_writer(segment)?;
+            if segment == StaticFileSegment::AccountChangeSets {
+                writer.prune_account_changesets(to_delete)?;
+            } else {
+                writer.prune_storage_changesets(to_delete)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(())
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -959,6 +1039,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
             block_meta: self.get_highest_static_file_block(StaticFileSegment::BlockMeta),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -1191,6 +1275,142 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(data)
     }
 
+    /// Returns the [`StaticFileAccountChangeSet`] of a block, if it's in static files.
+    pub fn account_changeset(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<StaticFileAccountChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::AccountChangeSets, block, None)
+            .and_then(|provider| provider.cursor()?.get_one::<AccountChangeSetMask>(block.into()))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the [`StaticFileStorageChangeSet`] of a block, if it's in static files.
+    pub fn storage_changeset(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<StaticFileStorageChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::StorageChangeSets, block, None)
+            .and_then(|provider| provider.cursor()?.get_one::<StorageChangeSetMask>(block.into()))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the change of the account in a block, if the block is in static files and changed
+    /// the account.
+    ///
+    /// Only the change of the account is decoded from the changeset of the block.
+    pub fn account_change(
+        &self,
+        block: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(StaticFileSegment::AccountChangeSets, block, None)
+            .and_then(|provider| {
+                Ok(provider
+                    .cursor()?
+                    .get(block.into(), AccountChangeSetMask::MASK)?
+                    .and_then(|row| StaticFileAccountChangeSet::find_encoded(row[0], address)))
+            })
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the change of the storage slot in a block, if the block is in static files and
+    /// changed the slot.
+    ///
+    /// Only the change of the slot is decoded from the changeset of the block.
+    pub fn storage_change(
+        &self,
+        block: BlockNumber,
+        address: Address,
+        key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        self.get_segment_provider_from_block(StaticFileSegment::StorageChangeSets, block, None)
+            .and_then(|provider| {
+                Ok(provider
+                    .cursor()?
+                    .get(block.into(), StorageChangeSetMask::MASK)?
+                    .and_then(|row| StaticFileStorageChangeSet::find_encoded(row[0], address, key)))
+            })
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the account changes of a block range in static files, in the same order and shape
+    /// as they're stored in the `AccountChangeSets` table.
+    pub fn account_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::AccountChangeSets,
+            range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<AccountChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(block, changeset)| {
+                changeset.changes.into_iter().map(move |change| (block, change))
+            })
+            .collect())
+    }
+
+    /// Returns the storage changes of a block range in static files, in the same order and shape
+    /// as they're stored in the `StorageChangeSets` table.
+    pub fn storage_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::StorageChangeSets,
+            range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<StorageChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(block, changeset)| {
+                changeset
+                    .changes
+                    .into_iter()
+                    .map(move |change| (BlockNumberAddress((block, change.address)), change.entry))
+            })
+            .collect())
+    }
+
     /// Returns `static_files` directory
     #[cfg(any(test, feature = "test-utils"))]
     pub fn path(&self) -> &Path {
@@ -1646,7 +1866,7 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
 
     fn pending_block_and_receipts(
         &self,
-    ) -> ProviderResult<Option<(SealedBlock<Self::Block>, Vec<Self::Receipt>)>> {
+    ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
     }
@@ -20,7 +20,7 @@ pub mod blocks;
 mod mock;
 mod noop;
 
-pub use mock::{ExtendedAccount, MockEthProvider};
+pub use mock::{ExtendedAccount, MockCallParticipantsTracer, MockEthProvider};
 pub use noop::NoopProvider;
 pub use reth_chain_state::test_utils::TestCanonStateSubscriptions;
 
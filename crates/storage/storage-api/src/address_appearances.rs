@@ -0,0 +1,69 @@
+use crate::StateProvider;
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_primitives::{Address, BlockNumber};
+use auto_impl::auto_impl;
+use core::{fmt::Debug, ops::RangeInclusive};
+use reth_primitives_traits::{Block, RecoveredBlock};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Address appearances reader.
+///
+/// An address appears in a block if it's the sender or the recipient of a transaction, if it
+/// emitted a log, if its account or storage was changed by the execution of the block, or if it
+/// was the caller or the callee of an internal call.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressAppearancesReader: Send + Sync {
+    /// Collects all addresses that appeared in the given block range alongside the blocks they
+    /// appeared in.
+    ///
+    /// An address appears in a block if it's the sender or the recipient of a transaction, if it
+    /// emitted a log, or if its account or storage was changed. The callers and callees of
+    /// internal calls, e.g. of a `STATICCALL`, leave no trace in the block data, so they only
+    /// appear if the calls are traced, see [`Self::traces_call_participants`].
+    ///
+    /// This is computed from the block data and doesn't require the address appearances index.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>>;
+
+    /// Returns `true` if [`Self::address_appearances_with_range`] includes the participants of
+    /// all calls, which are found by a [`CallParticipantsTracer`].
+    fn traces_call_participants(&self) -> bool;
+
+    /// Returns at most `limit` blocks strictly lower than `block_number` in which the `address`
+    /// appeared, in descending order.
+    ///
+    /// Returns an error if the address appearances index is not enabled.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns at most `limit` blocks strictly higher than `block_number` in which the `address`
+    /// appeared, in ascending order.
+    ///
+    /// Returns an error if the address appearances index is not enabled.
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// Traces the calls of a block to find the addresses that took part in them.
+pub trait CallParticipantsTracer<B: Block>: Debug + Send + Sync {
+    /// Re-executes the transactions of the block on top of the given state of its parent and
+    /// returns the callers and callees of all calls, including internal calls and contract
+    /// creations.
+    fn call_participants(
+        &self,
+        parent_state: &dyn StateProvider,
+        block: &RecoveredBlock<B>,
+    ) -> ProviderResult<Vec<Address>>;
+}
@@ -16,6 +16,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod address_appearances;
+pub use address_appearances::*;
+
 mod block;
 pub use block::*;
 
@@ -36,6 +39,11 @@ pub use header::*;
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+pub use snapshot::*;
+
 mod receipts;
 pub use receipts::*;
 
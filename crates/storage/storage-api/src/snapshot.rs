@@ -0,0 +1,94 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_primitives::{BlockNumber, B256};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file in the root directory of a snapshot.
+pub const SNAPSHOT_MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Directory of the database in a snapshot.
+pub const SNAPSHOT_DB_DIR: &str = "db";
+
+/// Directory of the static files in a snapshot.
+pub const SNAPSHOT_STATIC_FILES_DIR: &str = "static_files";
+
+/// Current version of the [`SnapshotManifest`] format.
+pub const SNAPSHOT_MANIFEST_VERSION: u64 = 1;
+
+/// Options for taking a snapshot with [`DatabaseSnapshotProvider::snapshot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotOptions {
+    /// Whether to compact the database while copying it, omitting free pages.
+    pub compact: bool,
+    /// Whether to copy static files below the last finalized block, instead of hard-linking them.
+    ///
+    /// Static files that can still be written to or truncated by an unwind are always copied.
+    pub copy_static_files: bool,
+}
+
+impl SnapshotOptions {
+    /// Sets whether to compact the database while copying it.
+    pub const fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether to copy static files instead of hard-linking them.
+    pub const fn with_copy_static_files(mut self, copy_static_files: bool) -> Self {
+        self.copy_static_files = copy_static_files;
+        self
+    }
+}
+
+/// A file that is part of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SnapshotFile {
+    /// Path of the file, relative to the root directory of the snapshot.
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// `blake3` hash of the file contents.
+    pub checksum: B256,
+}
+
+/// Describes the contents of a snapshot, written to [`SNAPSHOT_MANIFEST_FILE_NAME`] in its root
+/// directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SnapshotManifest {
+    /// Version of the manifest format.
+    pub version: u64,
+    /// Unix timestamp in seconds of when the snapshot was taken.
+    pub timestamp: u64,
+    /// Whether the database was compacted while copying it.
+    pub compacted: bool,
+    /// Highest block of each static file segment that is included in the snapshot.
+    pub static_files: BTreeMap<StaticFileSegment, BlockNumber>,
+    /// All files of the snapshot, except for the manifest itself.
+    pub files: Vec<SnapshotFile>,
+}
+
+impl SnapshotManifest {
+    /// Returns the total size in bytes of all files of the snapshot.
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// Takes consistent snapshots of the database and the static files of a running node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait DatabaseSnapshotProvider: Send + Sync {
+    /// Writes a snapshot of the database and the static files to the `destination` directory,
+    /// which must either not exist or be empty, and returns its manifest.
+    ///
+    /// Readers and writers are not blocked while the snapshot is taken.
+    fn snapshot(
+        &self,
+        destination: &Path,
+        options: SnapshotOptions,
+    ) -> ProviderResult<SnapshotManifest>;
+}
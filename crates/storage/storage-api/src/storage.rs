@@ -43,6 +43,27 @@ pub trait StorageChangeSetReader: Send + Sync {
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
 }
 
+/// Reader of the account and storage changesets of block ranges, wherever they're stored.
+///
+/// Changesets of older blocks may have been moved to static files, while the changesets of newer
+/// blocks are still in the database.
+#[cfg(feature = "db-api")]
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait ChangeSetRangeReader: Send + Sync {
+    /// Returns the account changesets of a block range, ordered by block number and address.
+    fn account_changesets_range(
+        &self,
+        range: impl core::ops::RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, reth_db_models::AccountBeforeTx)>>;
+
+    /// Returns the storage changesets of a block range, ordered by block number, address and
+    /// storage key.
+    fn storage_changesets_range(
+        &self,
+        range: impl core::ops::RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+}
+
 /// An enum that represents the storage location for a piece of data.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum StorageLocation {
@@ -1,10 +1,13 @@
 use alloc::vec::Vec;
-use alloy_primitives::{map::B256Map, Address, Bytes, B256};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Address, Bytes, B256,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-    StorageProof, TrieInput,
+    AccountProof, HashedAccountRange, HashedPostState, HashedStorage, HashedStorageRange,
+    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -89,6 +92,52 @@ pub trait StateProofProvider: Send + Sync {
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
 }
 
+/// A type that can walk the hashed state on top of a given post state in pages.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait HashedStateRangeProvider: Send + Sync {
+    /// Returns up to `limit` accounts of the `HashedPostState` on top of the current state,
+    /// starting at the given hashed address.
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedAccountRange>;
+
+    /// Returns up to `limit` storage slots of the account in the `HashedPostState` on top of the
+    /// current state, starting at the given hashed slot.
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange>;
+
+    /// Returns the preimages of the given hashed addresses, as far as they can be recovered from
+    /// the plain state.
+    ///
+    /// At most [`MAX_PREIMAGE_LOOKUP_ENTRIES`] entries of the plain state are looked at, so
+    /// preimages can be missing for large states.
+    fn account_preimages(&self, hashed_addresses: &B256Set) -> ProviderResult<B256Map<Address>>;
+
+    /// Returns the preimages of the given hashed storage slots of the account, as far as they can
+    /// be recovered from the plain state.
+    ///
+    /// At most [`MAX_PREIMAGE_LOOKUP_ENTRIES`] entries of the plain state are looked at, so
+    /// preimages can be missing for accounts with a large storage.
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &B256Set,
+    ) -> ProviderResult<B256Map<B256>>;
+}
+
+/// The maximum number of plain state entries that are hashed to recover the preimages of hashed
+/// keys in [`HashedStateRangeProvider::account_preimages`] and
+/// [`HashedStateRangeProvider::storage_preimages`].
+pub const MAX_PREIMAGE_LOOKUP_ENTRIES: usize = 100_000;
+
 /// Trie Writer
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait TrieWriter: Send + Sync {
@@ -1,14 +1,16 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
-use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
+use crate::blobstore::{
+    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecarVariant,
+};
+use alloy_eips::eip4844::{BlobAndProofV1, BlobAndProofV2};
 use alloy_primitives::{TxHash, B256};
 use parking_lot::{Mutex, RwLock};
 use schnellru::{ByLength, LruMap};
 use std::{collections::HashSet, fmt, fs, io, path::PathBuf, sync::Arc};
 use tracing::{debug, trace};
 
-/// How many [`BlobTransactionSidecar`] to cache in memory.
+/// How many [`BlobTransactionSidecarVariant`] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
 
 /// A blob store that stores blob data on disk.
@@ -50,11 +52,14 @@ impl DiskFileBlobStore {
 }
 
 impl BlobStore for DiskFileBlobStore {
-    fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
         self.inner.insert_one(tx, data)
     }
 
-    fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         if txs.is_empty() {
             return Ok(())
         }
@@ -99,7 +104,7 @@ impl BlobStore for DiskFileBlobStore {
         stat
     }
 
-    fn get(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    fn get(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         self.inner.get_one(tx)
     }
 
@@ -110,7 +115,7 @@ impl BlobStore for DiskFileBlobStore {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecar>)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
@@ -120,64 +125,36 @@ impl BlobStore for DiskFileBlobStore {
     fn get_exact(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
         self.inner.get_exact(txs)
     }
 
-    fn get_by_versioned_hashes(
+    fn get_by_versioned_hashes_v1(
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
-        // the response must always be the same len as the request, misses must be None
-        let mut result = vec![None; versioned_hashes.len()];
-
-        // first scan all cached full sidecars
-        for (_tx_hash, blob_sidecar) in self.inner.blob_cache.lock().iter() {
-            for (hash_idx, match_result) in blob_sidecar.match_versioned_hashes(versioned_hashes) {
-                result[hash_idx] = Some(match_result);
+        Ok(self.inner.get_by_versioned_hashes(versioned_hashes, |sidecar, result| {
+            for (hash_idx, match_result) in sidecar.match_versioned_hashes_v1(versioned_hashes) {
+                result[hash_idx].get_or_insert(match_result);
             }
+        }))
+    }
 
-            // return early if all blobs are found.
-            if result.iter().all(|blob| blob.is_some()) {
-                return Ok(result);
-            }
-        }
-
-        // not all versioned hashes were be found, try to look up a matching tx
-
-        let mut missing_tx_hashes = Vec::new();
-
-        {
-            let mut versioned_to_txhashes = self.inner.versioned_hashes_to_txhash.lock();
-            for (idx, _) in
-                result.iter().enumerate().filter(|(_, blob_and_proof)| blob_and_proof.is_none())
-            {
-                // this is safe because the result vec has the same len
-                let versioned_hash = versioned_hashes[idx];
-                if let Some(tx_hash) = versioned_to_txhashes.get(&versioned_hash).copied() {
-                    missing_tx_hashes.push(tx_hash);
-                }
-            }
-        }
-
-        // if we have missing blobs, try to read them from disk and try again
-        if !missing_tx_hashes.is_empty() {
-            let blobs_from_disk = self.inner.read_many_decoded(missing_tx_hashes);
-            for (_, blob_sidecar) in blobs_from_disk {
-                for (hash_idx, match_result) in
-                    blob_sidecar.match_versioned_hashes(versioned_hashes)
-                {
-                    if result[hash_idx].is_none() {
-                        result[hash_idx] = Some(match_result);
-                    }
-                }
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        let result = self.inner.get_by_versioned_hashes(versioned_hashes, |sidecar, result| {
+            for (hash_idx, match_result) in sidecar.match_versioned_hashes_v2(versioned_hashes) {
+                result[hash_idx].get_or_insert(match_result);
             }
-        }
+        });
 
-        Ok(result)
+        // all blobs must be found, otherwise none are returned
+        Ok(result.into_iter().collect())
     }
 
     fn data_size_hint(&self) -> Option<usize> {
@@ -191,7 +168,7 @@ impl BlobStore for DiskFileBlobStore {
 
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
-    blob_cache: Mutex<LruMap<TxHash, Arc<BlobTransactionSidecar>, ByLength>>,
+    blob_cache: Mutex<LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>>,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
@@ -235,7 +212,11 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Ensures blob is in the blob cache and written to the disk.
-    fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert_one(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
         data.rlp_encode_fields(&mut buf);
 
@@ -257,7 +238,10 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Ensures blobs are in the blob cache and written to the disk.
-    fn insert_many(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_many(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         let raw = txs
             .iter()
             .map(|(tx, data)| {
@@ -333,7 +317,10 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Retrieves the blob for the given transaction hash from the blob cache or disk.
-    fn get_one(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    fn get_one(
+        &self,
+        tx: B256,
+    ) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         if let Some(blob) = self.blob_cache.lock().get(&tx) {
             return Ok(Some(blob.clone()))
         }
@@ -348,6 +335,57 @@ impl DiskFileBlobStoreInner {
         Ok(None)
     }
 
+    /// Looks up the blobs of the given versioned hashes, first in the cached sidecars and then in
+    /// the sidecars on disk.
+    ///
+    /// `fill` is called with every candidate sidecar and is expected to set the matching blobs of
+    /// the sidecar in the result, which always has the same length as the request.
+    fn get_by_versioned_hashes<T: Clone>(
+        &self,
+        versioned_hashes: &[B256],
+        fill: impl Fn(&BlobTransactionSidecarVariant, &mut [Option<T>]),
+    ) -> Vec<Option<T>> {
+        // the response must always be the same len as the request, misses must be None
+        let mut result = vec![None; versioned_hashes.len()];
+
+        // first scan all cached full sidecars
+        for (_tx_hash, blob_sidecar) in self.blob_cache.lock().iter() {
+            fill(blob_sidecar, &mut result);
+
+            // return early if all blobs are found.
+            if result.iter().all(|blob| blob.is_some()) {
+                return result
+            }
+        }
+
+        // not all versioned hashes were be found, try to look up a matching tx
+
+        let mut missing_tx_hashes = Vec::new();
+
+        {
+            let mut versioned_to_txhashes = self.versioned_hashes_to_txhash.lock();
+            for (idx, _) in
+                result.iter().enumerate().filter(|(_, blob_and_proof)| blob_and_proof.is_none())
+            {
+                // this is safe because the result vec has the same len
+                let versioned_hash = versioned_hashes[idx];
+                if let Some(tx_hash) = versioned_to_txhashes.get(&versioned_hash).copied() {
+                    missing_tx_hashes.push(tx_hash);
+                }
+            }
+        }
+
+        // if we have missing blobs, try to read them from disk and try again
+        if !missing_tx_hashes.is_empty() {
+            let blobs_from_disk = self.read_many_decoded(missing_tx_hashes);
+            for (_, blob_sidecar) in blobs_from_disk {
+                fill(&blob_sidecar, &mut result);
+            }
+        }
+
+        result
+    }
+
     /// Returns the path to the blob file for the given transaction hash.
     #[inline]
     fn blob_disk_file(&self, tx: B256) -> PathBuf {
@@ -356,7 +394,7 @@ impl DiskFileBlobStoreInner {
 
     /// Retrieves the blob data for the given transaction hash.
     #[inline]
-    fn read_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn read_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         let path = self.blob_disk_file(tx);
         let data = {
             let _lock = self.file_lock.read();
@@ -370,7 +408,7 @@ impl DiskFileBlobStoreInner {
                 }
             }
         };
-        BlobTransactionSidecar::rlp_decode_fields(&mut data.as_slice())
+        BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())
             .map(Some)
             .map_err(BlobStoreError::DecodeError)
     }
@@ -378,11 +416,11 @@ impl DiskFileBlobStoreInner {
     /// Returns decoded blobs read from disk.
     ///
     /// Only returns sidecars that were found and successfully decoded.
-    fn read_many_decoded(&self, txs: Vec<TxHash>) -> Vec<(TxHash, BlobTransactionSidecar)> {
+    fn read_many_decoded(&self, txs: Vec<TxHash>) -> Vec<(TxHash, BlobTransactionSidecarVariant)> {
         self.read_many_raw(txs)
             .into_iter()
             .filter_map(|(tx, data)| {
-                BlobTransactionSidecar::rlp_decode_fields(&mut data.as_slice())
+                BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())
                     .map(|sidecar| (tx, sidecar))
                     .ok()
             })
@@ -435,7 +473,7 @@ impl DiskFileBlobStoreInner {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecar>)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
         let mut res = Vec::with_capacity(txs.len());
         let mut cache_miss = Vec::new();
         {
@@ -472,7 +510,7 @@ impl DiskFileBlobStoreInner {
     fn get_exact(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         txs.into_iter()
             .map(|tx| self.get_one(tx)?.ok_or(BlobStoreError::MissingSidecar(tx)))
             .collect()
@@ -552,6 +590,8 @@ pub enum OpenDiskFileBlobStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blobstore::{BlobTransactionSidecarEip7594, CELLS_PER_EXT_BLOB};
+    use alloy_eips::eip4844::{kzg_to_versioned_hash, Blob, BlobTransactionSidecar, Bytes48};
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -560,13 +600,16 @@ mod tests {
         (store, dir)
     }
 
-    fn rng_blobs(num: usize) -> Vec<(TxHash, BlobTransactionSidecar)> {
+    fn rng_blobs(num: usize) -> Vec<(TxHash, BlobTransactionSidecarVariant)> {
         let mut rng = rand::rng();
         (0..num)
             .map(|_| {
                 let tx = TxHash::random_with(&mut rng);
-                let blob =
-                    BlobTransactionSidecar { blobs: vec![], commitments: vec![], proofs: vec![] };
+                let blob = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
+                    blobs: vec![],
+                    commitments: vec![],
+                    proofs: vec![],
+                });
                 (tx, blob)
             })
             .collect()
@@ -637,11 +680,11 @@ mod tests {
         let result = store.get(tx).unwrap();
         assert_eq!(
             result,
-            Some(Arc::new(BlobTransactionSidecar {
+            Some(Arc::new(BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
                 blobs: vec![],
                 commitments: vec![],
                 proofs: vec![]
-            }))
+            })))
         );
     }
 
@@ -664,11 +707,11 @@ mod tests {
             let result = store.get(tx).unwrap();
             assert_eq!(
                 result,
-                Some(Arc::new(BlobTransactionSidecar {
+                Some(Arc::new(BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
                     blobs: vec![],
                     commitments: vec![],
                     proofs: vec![]
-                }))
+                })))
             );
         }
     }
@@ -718,6 +761,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn disk_get_blobs_by_versioned_hashes_v2() {
+        let (store, _dir) = tmp_store();
+
+        let commitment = Bytes48::random();
+        let sidecar = BlobTransactionSidecarEip7594 {
+            blobs: vec![Blob::default()],
+            commitments: vec![commitment],
+            cell_proofs: vec![Bytes48::random(); CELLS_PER_EXT_BLOB],
+        };
+        let versioned_hash = kzg_to_versioned_hash(commitment.as_slice());
+        store.insert(TxHash::random(), sidecar.clone().into()).unwrap();
+
+        let blobs = store.get_by_versioned_hashes_v2(&[versioned_hash]).unwrap().unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].proofs, sidecar.cell_proofs);
+
+        // all blobs have to be available
+        assert!(store
+            .get_by_versioned_hashes_v2(&[versioned_hash, B256::random()])
+            .unwrap()
+            .is_none());
+
+        // sidecars with cell proofs are not returned as v1 blobs
+        assert_eq!(store.get_by_versioned_hashes_v1(&[versioned_hash]).unwrap(), vec![None]);
+    }
+
     #[test]
     fn disk_data_size_hint() {
         let (store, _dir) = tmp_store();
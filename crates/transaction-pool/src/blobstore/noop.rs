@@ -1,5 +1,7 @@
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError};
-use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
+use crate::blobstore::{
+    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobTransactionSidecarVariant,
+};
+use alloy_eips::eip4844::{BlobAndProofV1, BlobAndProofV2};
 use alloy_primitives::B256;
 use std::sync::Arc;
 
@@ -9,11 +11,18 @@ use std::sync::Arc;
 pub struct NoopBlobStore;
 
 impl BlobStore for NoopBlobStore {
-    fn insert(&self, _tx: B256, _data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert(
+        &self,
+        _tx: B256,
+        _data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
         Ok(())
     }
 
-    fn insert_all(&self, _txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_all(
+        &self,
+        _txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         Ok(())
     }
 
@@ -29,7 +38,7 @@ impl BlobStore for NoopBlobStore {
         BlobStoreCleanupStat::default()
     }
 
-    fn get(&self, _tx: B256) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    fn get(&self, _tx: B256) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         Ok(None)
     }
 
@@ -40,27 +49,37 @@ impl BlobStore for NoopBlobStore {
     fn get_all(
         &self,
         _txs: Vec<B256>,
-    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecar>)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
         Ok(vec![])
     }
 
     fn get_exact(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+    ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(vec![])
         }
         Err(BlobStoreError::MissingSidecar(txs[0]))
     }
 
-    fn get_by_versioned_hashes(
+    fn get_by_versioned_hashes_v1(
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         Ok(vec![None; versioned_hashes.len()])
     }
 
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        if versioned_hashes.is_empty() {
+            return Ok(Some(Vec::new()))
+        }
+        Ok(None)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(0)
     }
@@ -176,6 +176,13 @@ pub enum Eip4844PoolTransactionError {
     /// would introduce gap in the nonce sequence.
     #[error("nonce too high")]
     Eip4844NonceGap,
+    /// Thrown if a blob sidecar with cell proofs arrives before the Osaka hardfork is active.
+    #[error("eip7594 blob sidecar is not allowed before Osaka")]
+    UnexpectedEip7594SidecarBeforeOsaka,
+    /// Thrown if a blob sidecar with one proof per blob arrives from a peer or RPC once the Osaka
+    /// hardfork is active.
+    #[error("eip4844 blob sidecar is not allowed after Osaka")]
+    UnexpectedEip4844SidecarAfterOsaka,
 }
 
 /// Represents all errors that can happen when validating transactions for the pool for EIP-7702
@@ -330,6 +337,15 @@ impl InvalidPoolTransactionError {
                         // this is a malformed transaction and should not be sent over the network
                         true
                     }
+                    Eip4844PoolTransactionError::UnexpectedEip7594SidecarBeforeOsaka => {
+                        // cell proofs are only valid after the fork
+                        true
+                    }
+                    Eip4844PoolTransactionError::UnexpectedEip4844SidecarAfterOsaka => {
+                        // this can happen for transactions that were in flight when the fork
+                        // activated
+                        false
+                    }
                 }
             }
             Self::Eip7702(eip7702_err) => match eip7702_err {
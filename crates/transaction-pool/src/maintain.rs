@@ -20,7 +20,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_execution_types::ChangedAccount;
 use reth_fs_util::FsPathError;
 use reth_primitives_traits::{
-    transaction::signed::SignedTransaction, NodePrimitives, SealedHeader,
+    transaction::signed::SignedTransaction, NodePrimitives, SealedBlock, SealedHeader,
 };
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -299,6 +299,74 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
         // handle the new block or reorg
         let Some(event) = event else { continue };
         match event {
+            CanonStateNotification::Reorg { old, new } if new.is_empty() => {
+                // the chain was reverted without any new blocks, e.g. to a snapshot of a dev chain
+                let (old_blocks, old_state) = old.inner();
+                let new_tip_hash = old_blocks.first().parent_hash();
+
+                // check if the revert is not canonical with the pool's block
+                if old_blocks.tip().hash() != pool_info.last_seen_block_hash {
+                    maintained_state = MaintainedPoolState::Drifted;
+                }
+
+                let Ok(Some(new_tip)) = client.block_by_hash(new_tip_hash) else {
+                    debug!(target: "txpool", %new_tip_hash, "failed to load new tip after revert");
+                    maintained_state = MaintainedPoolState::Drifted;
+                    continue
+                };
+                let new_tip = SealedBlock::new_unchecked(new_tip, new_tip_hash);
+
+                let chain_spec = client.chain_spec();
+
+                // fees for the next block: `new_tip+1`
+                let pending_block_base_fee = new_tip
+                    .header()
+                    .next_block_base_fee(
+                        chain_spec.base_fee_params_at_timestamp(new_tip.timestamp()),
+                    )
+                    .unwrap_or_default();
+                let pending_block_blob_fee = new_tip.header().maybe_next_block_blob_fee(
+                    chain_spec.blob_params_at_timestamp(new_tip.timestamp()),
+                );
+
+                // all accounts changed in the reverted blocks need to be reloaded at the new tip
+                let changed_accounts = match load_accounts(
+                    client.clone(),
+                    new_tip_hash,
+                    old_state.accounts_iter().map(|(a, _)| a),
+                ) {
+                    Ok(LoadedAccounts { accounts, failed_to_load }) => {
+                        // extend accounts we failed to load from database
+                        dirty_addresses.extend(failed_to_load);
+
+                        accounts
+                    }
+                    Err(err) => {
+                        let (addresses, err) = *err;
+                        debug!(
+                            target: "txpool",
+                            %err,
+                            "failed to load changed accounts at new tip: {:?}",
+                            new_tip_hash
+                        );
+                        dirty_addresses.extend(addresses);
+                        vec![]
+                    }
+                };
+
+                let update = CanonicalStateUpdate {
+                    new_tip: &new_tip,
+                    pending_block_base_fee,
+                    pending_block_blob_fee,
+                    changed_accounts,
+                    mined_transactions: Vec::new(),
+                    update_kind: PoolUpdateKind::Reorg,
+                };
+                pool.on_canonical_state_change(update);
+
+                // unlike reorgs, the transactions of the reverted blocks are not re-injected, the
+                // chain was explicitly rolled back so they are discarded with the blocks
+            }
             CanonStateNotification::Reorg { old, new } => {
                 let (old_blocks, old_state) = old.inner();
                 let (new_blocks, new_state) = new.inner();
@@ -716,10 +784,9 @@ mod tests {
         blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
-    use alloy_consensus::transaction::PooledTransaction;
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
-    use reth_ethereum_primitives::TransactionSigned;
+    use reth_ethereum_primitives::{PooledTransaction, TransactionSigned};
     use reth_fs_util as fs;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
@@ -1,6 +1,7 @@
 //! Mock types.
 
 use crate::{
+    blobstore::BlobTransactionSidecarVariant,
     identifier::{SenderIdentifiers, TransactionId},
     pool::txpool::TxPool,
     traits::TransactionOrigin,
@@ -12,7 +13,6 @@ use alloy_consensus::{
         EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
         LEGACY_TX_TYPE_ID,
     },
-    transaction::PooledTransaction,
     EthereumTxEnvelope, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEip7702, TxEnvelope,
     TxLegacy, TxType, Typed2718,
 };
@@ -25,7 +25,7 @@ use alloy_eips::{
 use alloy_primitives::{Address, Bytes, ChainId, Signature, TxHash, TxKind, B256, U256};
 use paste::paste;
 use rand::{distr::Uniform, prelude::Distribution};
-use reth_ethereum_primitives::{Transaction, TransactionSigned};
+use reth_ethereum_primitives::{PooledTransaction, Transaction, TransactionSigned};
 use reth_primitives_traits::{
     transaction::error::{TransactionConversionError, TryFromRecoveredTransactionError},
     InMemorySize, Recovered, SignedTransaction,
@@ -217,7 +217,7 @@ pub enum MockTransaction {
         /// The transaction input data.
         input: Bytes,
         /// The sidecar information for the transaction.
-        sidecar: BlobTransactionSidecar,
+        sidecar: BlobTransactionSidecarVariant,
         /// The blob versioned hashes for the transaction.
         blob_versioned_hashes: Vec<B256>,
         /// The size of the transaction, returned in the implementation of [`PoolTransaction`].
@@ -360,7 +360,7 @@ impl MockTransaction {
             value: Default::default(),
             input: Bytes::new(),
             access_list: Default::default(),
-            sidecar: Default::default(),
+            sidecar: BlobTransactionSidecar::default().into(),
             blob_versioned_hashes: Default::default(),
             size: Default::default(),
             cost: U256::ZERO,
@@ -374,7 +374,7 @@ impl MockTransaction {
             &mut transaction
         {
             *blob_versioned_hashes = sidecar.versioned_hashes().collect();
-            *existing_sidecar = sidecar;
+            *existing_sidecar = sidecar.into();
         }
         transaction
     }
@@ -878,7 +878,7 @@ impl EthPoolTransaction for MockTransaction {
 
     fn try_into_pooled_eip4844(
         self,
-        sidecar: Arc<BlobTransactionSidecar>,
+        sidecar: Arc<BlobTransactionSidecarVariant>,
     ) -> Option<Recovered<Self::Pooled>> {
         let (tx, signer) = self.into_consensus().into_parts();
         tx.try_into_pooled_eip4844(Arc::unwrap_or_clone(sidecar))
@@ -888,7 +888,7 @@ impl EthPoolTransaction for MockTransaction {
 
     fn try_from_eip4844(
         tx: Recovered<Self::Consensus>,
-        sidecar: BlobTransactionSidecar,
+        sidecar: BlobTransactionSidecarVariant,
     ) -> Option<Self> {
         let (tx, signer) = tx.into_parts();
         tx.try_into_pooled_eip4844(sidecar)
@@ -899,7 +899,7 @@ impl EthPoolTransaction for MockTransaction {
 
     fn validate_blob(
         &self,
-        _blob: &BlobTransactionSidecar,
+        _blob: &BlobTransactionSidecarVariant,
         _settings: &KzgSettings,
     ) -> Result<(), alloy_eips::eip4844::BlobTransactionValidationError> {
         match &self {
@@ -1014,7 +1014,7 @@ impl TryFrom<Recovered<TransactionSigned>> for MockTransaction {
                 value,
                 input,
                 access_list,
-                sidecar: BlobTransactionSidecar::default(),
+                sidecar: BlobTransactionSidecar::default().into(),
                 blob_versioned_hashes: Default::default(),
                 size,
                 cost: U256::from(gas_limit) * U256::from(max_fee_per_gas) + value,
@@ -1126,7 +1126,7 @@ impl TryFrom<Recovered<TxEnvelope>> for MockTransaction {
                     value: tx.value,
                     input: tx.input.clone(),
                     access_list: tx.access_list.clone(),
-                    sidecar: BlobTransactionSidecar::default(),
+                    sidecar: BlobTransactionSidecar::default().into(),
                     blob_versioned_hashes: tx.blob_versioned_hashes.clone(),
                     size,
                     cost: U256::from(tx.gas_limit) * U256::from(tx.max_fee_per_gas) + tx.value,
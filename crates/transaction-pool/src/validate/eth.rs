@@ -2,7 +2,7 @@
 
 use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobStore, BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant},
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
@@ -511,6 +511,28 @@ where
                 }
                 EthBlobTransactionSidecar::Present(blob) => {
                     let now = Instant::now();
+                    let is_osaka = self.fork_tracker.is_osaka_activated();
+
+                    if blob.is_eip7594() && !is_osaka {
+                        return TransactionValidationOutcome::Invalid(
+                            transaction,
+                            InvalidPoolTransactionError::Eip4844(
+                                Eip4844PoolTransactionError::UnexpectedEip7594SidecarBeforeOsaka,
+                            ),
+                        )
+                    }
+
+                    // Computing the cell proofs is expensive, so only local transactions may still
+                    // carry a sidecar with one proof per blob once Osaka is active
+                    if !blob.is_eip7594() && is_osaka && !origin.is_local() {
+                        return TransactionValidationOutcome::Invalid(
+                            transaction,
+                            InvalidPoolTransactionError::Eip4844(
+                                Eip4844PoolTransactionError::UnexpectedEip4844SidecarAfterOsaka,
+                            ),
+                        )
+                    }
+
                     // validate the blob
                     if let Err(err) = transaction.validate_blob(&blob, self.kzg_settings.get()) {
                         return TransactionValidationOutcome::Invalid(
@@ -520,6 +542,30 @@ where
                             ),
                         )
                     }
+
+                    // Once Osaka is active, blobs are served with cell proofs, so they are computed
+                    // upfront for local sidecars that only carry one proof per blob
+                    let blob = match blob {
+                        BlobTransactionSidecarVariant::Eip4844(sidecar) if is_osaka => {
+                            match BlobTransactionSidecarEip7594::try_from_eip4844(
+                                sidecar,
+                                self.kzg_settings.get(),
+                            ) {
+                                Ok(sidecar) => sidecar.into(),
+                                Err(err) => {
+                                    return TransactionValidationOutcome::Invalid(
+                                        transaction,
+                                        InvalidPoolTransactionError::Eip4844(
+                                            Eip4844PoolTransactionError::InvalidEip4844Blob(
+                                                err.into(),
+                                            ),
+                                        ),
+                                    )
+                                }
+                            }
+                        }
+                        blob => blob,
+                    };
                     // Record the duration of successful blob validation as histogram
                     self.validation_metrics.blob_validation_duration.record(now.elapsed());
                     // store the extracted blob
@@ -580,6 +626,10 @@ where
             self.fork_tracker.prague.store(true, std::sync::atomic::Ordering::Relaxed);
         }
 
+        if self.chain_spec().is_osaka_active_at_timestamp(new_tip_block.timestamp()) {
+            self.fork_tracker.osaka.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
         if let Some(blob_params) =
             self.chain_spec().blob_params_at_timestamp(new_tip_block.timestamp())
         {
@@ -604,8 +654,10 @@ pub struct EthTransactionValidatorBuilder<Client> {
     shanghai: bool,
     /// Fork indicator whether we are in the Cancun hardfork.
     cancun: bool,
-    /// Fork indicator whether we are in the Cancun hardfork.
+    /// Fork indicator whether we are in the Prague hardfork.
     prague: bool,
+    /// Fork indicator whether we are in the Osaka hardfork.
+    osaka: bool,
     /// Max blob count at the block's timestamp.
     max_blob_count: u64,
     /// Whether using EIP-2718 type transactions is allowed
@@ -669,6 +721,9 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             // prague not yet activated
             prague: false,
 
+            // osaka not yet activated
+            osaka: false,
+
             // max blob count is cancun by default
             max_blob_count: BlobParams::cancun().max_blob_count,
         }
@@ -716,6 +771,17 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self
     }
 
+    /// Disables the Osaka fork.
+    pub const fn no_osaka(self) -> Self {
+        self.set_osaka(false)
+    }
+
+    /// Set the Osaka fork.
+    pub const fn set_osaka(mut self, osaka: bool) -> Self {
+        self.osaka = osaka;
+        self
+    }
+
     /// Disables the support for EIP-2718 transactions.
     pub const fn no_eip2718(self) -> Self {
         self.set_eip2718(false)
@@ -777,6 +843,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self.cancun = self.client.chain_spec().is_cancun_active_at_timestamp(timestamp);
         self.shanghai = self.client.chain_spec().is_shanghai_active_at_timestamp(timestamp);
         self.prague = self.client.chain_spec().is_prague_active_at_timestamp(timestamp);
+        self.osaka = self.client.chain_spec().is_osaka_active_at_timestamp(timestamp);
         self.max_blob_count = self
             .client
             .chain_spec()
@@ -818,6 +885,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             shanghai,
             cancun,
             prague,
+            osaka,
             eip2718,
             eip1559,
             eip4844,
@@ -841,6 +909,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             shanghai: AtomicBool::new(shanghai),
             cancun: AtomicBool::new(cancun),
             prague: AtomicBool::new(prague),
+            osaka: AtomicBool::new(osaka),
             max_blob_count: AtomicU64::new(max_blob_count),
         };
 
@@ -917,6 +986,8 @@ pub struct ForkTracker {
     pub cancun: AtomicBool,
     /// Tracks if prague is activated at the block's timestamp.
     pub prague: AtomicBool,
+    /// Tracks if osaka is activated at the block's timestamp.
+    pub osaka: AtomicBool,
     /// Tracks max blob count at the block's timestamp.
     pub max_blob_count: AtomicU64,
 }
@@ -937,6 +1008,11 @@ impl ForkTracker {
         self.prague.load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Returns `true` if Osaka fork is activated.
+    pub fn is_osaka_activated(&self) -> bool {
+        self.osaka.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
     /// Returns the max blob count.
     pub fn max_blob_count(&self) -> u64 {
         self.max_blob_count.load(std::sync::atomic::Ordering::Relaxed)
@@ -986,10 +1062,13 @@ mod tests {
         blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
-    use alloy_consensus::Transaction;
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
-    use reth_ethereum_primitives::PooledTransaction;
+    use alloy_consensus::{Transaction, TxEip4844};
+    use alloy_eips::{
+        eip2718::Decodable2718,
+        eip4844::{BlobTransactionSidecar, BYTES_PER_BLOB},
+    };
+    use alloy_primitives::{hex, Address, Signature, U256};
+    use reth_ethereum_primitives::{PooledTransaction, TransactionSigned};
     use reth_primitives_traits::SignedTransaction;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 
@@ -1010,6 +1089,7 @@ mod tests {
             shanghai: false.into(),
             cancun: false.into(),
             prague: false.into(),
+            osaka: false.into(),
             max_blob_count: 0.into(),
         };
 
@@ -1148,4 +1228,59 @@ mod tests {
         let outcome = validator.validate_one(TransactionOrigin::Local, transaction);
         assert!(outcome.is_valid());
     }
+
+    fn get_blob_transaction() -> EthPooledTransaction {
+        let sidecar =
+            BlobTransactionSidecar::try_from_blobs_bytes([vec![0u8; BYTES_PER_BLOB]]).unwrap();
+        let tx = TxEip4844 {
+            chain_id: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_blob_gas: 1_000_000_000,
+            to: Address::random(),
+            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+            ..Default::default()
+        };
+        let tx = TransactionSigned::new_unhashed(tx.into(), Signature::test_signature());
+
+        EthPooledTransaction::try_from_eip4844(tx.with_signer(Address::random()), sidecar.into())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn eip4844_sidecar_after_osaka() {
+        let transaction = get_blob_transaction();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator =
+            EthTransactionValidatorBuilder::new(provider).set_osaka(true).build(blob_store);
+
+        // computing the cell proofs is only done for local transactions
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Eip4844(
+                    Eip4844PoolTransactionError::UnexpectedEip4844SidecarAfterOsaka
+                )
+            )
+        ));
+
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction);
+        let TransactionValidationOutcome::Valid {
+            transaction: ValidTransaction::ValidWithSidecar { sidecar, .. },
+            ..
+        } = outcome
+        else {
+            panic!("expected a valid transaction with a sidecar: {outcome:?}")
+        };
+        assert!(sidecar.is_eip7594());
+    }
 }
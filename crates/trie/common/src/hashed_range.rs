@@ -0,0 +1,32 @@
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+use reth_primitives_traits::Account;
+
+/// A page of the hashed accounts, ordered by hashed address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashedAccountRange {
+    /// The accounts of the page.
+    pub accounts: Vec<HashedAccountRangeEntry>,
+    /// The hashed address of the first account after the page, if any.
+    pub next: Option<B256>,
+}
+
+/// An account of a [`HashedAccountRange`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashedAccountRangeEntry {
+    /// The hashed address of the account.
+    pub hashed_address: B256,
+    /// The account info.
+    pub account: Account,
+    /// The storage root of the account.
+    pub storage_root: B256,
+}
+
+/// A page of the hashed storage of an account, ordered by hashed slot.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashedStorageRange {
+    /// The hashed slots and their values.
+    pub slots: Vec<(B256, U256)>,
+    /// The hashed slot of the first entry after the page, if any.
+    pub next: Option<B256>,
+}
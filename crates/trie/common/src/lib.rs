@@ -41,6 +41,10 @@ pub use storage::StorageTrieEntry;
 mod subnode;
 pub use subnode::StoredSubNode;
 
+/// Paginated ranges of the hashed state.
+mod hashed_range;
+pub use hashed_range::{HashedAccountRange, HashedAccountRangeEntry, HashedStorageRange};
+
 /// The implementation of a container for storing intermediate changes to a trie.
 /// The container indicates when the trie has been modified.
 pub mod prefix_set;
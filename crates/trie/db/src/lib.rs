@@ -6,6 +6,7 @@ mod commitment;
 mod hashed_cursor;
 mod prefix_set;
 mod proof;
+mod range;
 mod state;
 mod storage;
 mod trie_cursor;
@@ -17,8 +18,9 @@ pub use hashed_cursor::{
 };
 pub use prefix_set::PrefixSetLoader;
 pub use proof::{DatabaseProof, DatabaseStorageProof};
-pub use state::{DatabaseHashedPostState, DatabaseStateRoot};
-pub use storage::{DatabaseHashedStorage, DatabaseStorageRoot};
+pub use range::DatabaseHashedStateRange;
+pub use state::{ChangesetHashedPostState, DatabaseHashedPostState, DatabaseStateRoot};
+pub use storage::{ChangesetHashedStorage, DatabaseHashedStorage, DatabaseStorageRoot};
 pub use trie_cursor::{
     DatabaseAccountTrieCursor, DatabaseStorageTrieCursor, DatabaseTrieCursorFactory,
 };
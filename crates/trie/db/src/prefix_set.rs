@@ -40,16 +40,49 @@ impl<TX, KH> Deref for PrefixSetLoader<'_, TX, KH> {
 
 impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
     /// Load all account and storage changes for the given block range.
+    ///
+    /// Only the changesets in the database are loaded, see [`Self::load_from_changesets`] for
+    /// the changesets stored elsewhere.
     pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
+        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
+        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let storage_range = BlockNumberAddress::range(range.clone());
+        self.load_changes(
+            account_changeset_cursor.walk_range(range)?,
+            storage_cursor.walk_range(storage_range)?,
+        )
+    }
+
+    /// Load all account and storage changes from the given changesets, e.g. read from both
+    /// static files and the database.
+    pub fn load_from_changesets(
+        self,
+        account_changesets: impl IntoIterator<Item = (BlockNumber, AccountBeforeTx)>,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Result<TriePrefixSets, DatabaseError> {
+        self.load_changes(
+            account_changesets.into_iter().map(Ok),
+            storage_changesets.into_iter().map(Ok),
+        )
+    }
+
+    fn load_changes<A, S>(
+        &self,
+        account_changesets: A,
+        storage_changesets: S,
+    ) -> Result<TriePrefixSets, DatabaseError>
+    where
+        A: Iterator<Item = Result<(BlockNumber, AccountBeforeTx), DatabaseError>>,
+        S: Iterator<Item = Result<(BlockNumberAddress, StorageEntry), DatabaseError>>,
+    {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
         let mut destroyed_accounts = HashSet::default();
 
         // Walk account changeset and insert account prefixes.
-        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
         let mut account_hashed_state_cursor = self.cursor_read::<tables::HashedAccounts>()?;
-        for account_entry in account_changeset_cursor.walk_range(range.clone())? {
+        for account_entry in account_changesets {
             let (_, AccountBeforeTx { address, .. }) = account_entry?;
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
@@ -61,9 +94,7 @@ impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
 
         // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
         // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
-        let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
+        for storage_entry in storage_changesets {
             let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
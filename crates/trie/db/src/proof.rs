@@ -4,6 +4,7 @@ use reth_db_api::transaction::DbTx;
 use reth_execution_errors::StateProofError;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory,
+    prefix_set::TriePrefixSetsMut,
     proof::{Proof, StorageProof},
     trie_cursor::InMemoryTrieCursorFactory,
     AccountProof, HashedPostStateSorted, HashedStorage, MultiProof, MultiProofTargets,
@@ -29,6 +30,15 @@ pub trait DatabaseProof<'a, TX> {
         input: TrieInput,
         targets: MultiProofTargets,
     ) -> Result<MultiProof, StateProofError>;
+
+    /// Generates the state [`MultiProof`] for target hashed account and storage keys on top of an
+    /// already sorted state overlay, so that the overlay can be reused across proofs.
+    fn overlay_multiproof_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        prefix_sets: TriePrefixSetsMut,
+        targets: MultiProofTargets,
+    ) -> Result<MultiProof, StateProofError>;
 }
 
 impl<'a, TX: DbTx> DatabaseProof<'a, TX>
@@ -79,6 +89,21 @@ impl<'a, TX: DbTx> DatabaseProof<'a, TX>
             .with_prefix_sets_mut(input.prefix_sets)
             .multiproof(targets)
     }
+
+    fn overlay_multiproof_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        prefix_sets: TriePrefixSetsMut,
+        targets: MultiProofTargets,
+    ) -> Result<MultiProof, StateProofError> {
+        Self::from_tx(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                state_sorted,
+            ))
+            .with_prefix_sets_mut(prefix_sets)
+            .multiproof(targets)
+    }
 }
 
 /// Extends [`StorageProof`] with operations specific for working with a database transaction.
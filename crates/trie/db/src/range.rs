@@ -0,0 +1,109 @@
+use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use alloy_primitives::B256;
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_execution_errors::StateRootError;
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, prefix_set::TriePrefixSets, HashedAccountRange,
+    HashedPostState, HashedPostStateSorted, HashedStateRange, HashedStorageRange,
+};
+
+/// Extends [`HashedStateRange`] with operations specific for working with a database transaction.
+pub trait DatabaseHashedStateRange<'a, TX> {
+    /// Create a new [`HashedStateRange`] instance.
+    fn from_tx(tx: &'a TX) -> Self;
+
+    /// Returns up to `limit` accounts of the [`HashedPostState`] on top of the database state,
+    /// starting at the given hashed address.
+    fn overlay_accounts(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedAccountRange, StateRootError>;
+
+    /// Returns up to `limit` storage slots of the account in the [`HashedPostState`] on top of the
+    /// database state, starting at the given hashed slot.
+    fn overlay_storage(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError>;
+
+    /// Same as [`Self::overlay_accounts`], but on top of an already sorted state overlay and its
+    /// prefix sets, so that they can be reused across pages.
+    fn overlay_accounts_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        prefix_sets: TriePrefixSets,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedAccountRange, StateRootError>;
+
+    /// Same as [`Self::overlay_storage`], but on top of an already sorted state overlay.
+    fn overlay_storage_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError>;
+}
+
+impl<'a, TX: DbTx> DatabaseHashedStateRange<'a, TX>
+    for HashedStateRange<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
+{
+    fn from_tx(tx: &'a TX) -> Self {
+        Self::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+    }
+
+    fn overlay_accounts(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedAccountRange, StateRootError> {
+        let prefix_sets = post_state.construct_prefix_sets().freeze();
+        Self::overlay_accounts_sorted(tx, &post_state.into_sorted(), prefix_sets, start, limit)
+    }
+
+    fn overlay_storage(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError> {
+        Self::overlay_storage_sorted(tx, &post_state.into_sorted(), hashed_address, start, limit)
+    }
+
+    fn overlay_accounts_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        prefix_sets: TriePrefixSets,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedAccountRange, StateRootError> {
+        HashedStateRange::new(
+            DatabaseTrieCursorFactory::new(tx),
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), state_sorted),
+        )
+        .with_prefix_sets(prefix_sets)
+        .accounts(start, limit)
+    }
+
+    fn overlay_storage_sorted(
+        tx: &'a TX,
+        state_sorted: &HashedPostStateSorted,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError> {
+        HashedStateRange::new(
+            DatabaseTrieCursorFactory::new(tx),
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), state_sorted),
+        )
+        .storage(hashed_address, start, limit)
+    }
+}
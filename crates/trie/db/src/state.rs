@@ -11,12 +11,13 @@ use reth_db_api::{
     DatabaseError,
 };
 use reth_execution_errors::StateRootError;
+use reth_primitives_traits::StorageEntry;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory, trie_cursor::InMemoryTrieCursorFactory,
     updates::TrieUpdates, HashedPostState, HashedStorage, KeccakKeyHasher, KeyHasher, StateRoot,
     StateRootProgress, TrieInput,
 };
-use std::{collections::HashMap, ops::RangeInclusive};
+use std::{collections::HashMap, convert::Infallible, ops::RangeInclusive};
 use tracing::debug;
 
 /// Extends [`StateRoot`] with operations specific for working with a database transaction.
@@ -122,6 +123,17 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     ) -> Result<(B256, TrieUpdates), StateRootError>;
 }
 
+/// Extends [`HashedPostState`] with initialization from changesets, regardless of where they're
+/// stored.
+pub trait ChangesetHashedPostState: Sized {
+    /// Initializes [`HashedPostState`] from the account and storage changesets ordered by block
+    /// number, aggregating them into hashed state in reverse.
+    fn from_changesets<KH: KeyHasher>(
+        account_changesets: impl IntoIterator<Item = (BlockNumber, AccountBeforeTx)>,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Self;
+}
+
 /// Extends [`HashedPostState`] with operations specific for working with a database transaction.
 pub trait DatabaseHashedPostState<TX>: Sized {
     /// Initializes [`HashedPostState`] from reverts. Iterates over state reverts from the specified
@@ -221,48 +233,76 @@ impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
 
 impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
     fn from_reverts<KH: KeyHasher>(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
-        // Iterate over account changesets and record value before first occurring account change.
-        let mut accounts = HashMap::new();
         let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
-        for entry in account_changesets_cursor.walk_range(from..)? {
-            let (_, AccountBeforeTx { address, info }) = entry?;
-            accounts.entry(address).or_insert(info);
-        }
-
-        // Iterate over storage changesets and record value before first occurring storage change.
-        let mut storages = AddressMap::<B256Map<U256>>::default();
         let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in
-            storage_changesets_cursor.walk_range(BlockNumberAddress((from, Address::ZERO))..)?
-        {
-            let (BlockNumberAddress((_, address)), storage) = entry?;
-            let account_storage = storages.entry(address).or_default();
-            account_storage.entry(storage.key).or_insert(storage.value);
-        }
-
-        let hashed_accounts =
-            accounts.into_iter().map(|(address, info)| (KH::hash_key(address), info)).collect();
-
-        let hashed_storages = storages
-            .into_iter()
-            .map(|(address, storage)| {
-                (
-                    KH::hash_key(address),
-                    HashedStorage::from_iter(
-                        // The `wiped` flag indicates only whether previous storage entries
-                        // should be looked up in db or not. For reverts it's a noop since all
-                        // wiped changes had been written as storage reverts.
-                        false,
-                        storage.into_iter().map(|(slot, value)| (KH::hash_key(slot), value)),
-                    ),
-                )
-            })
-            .collect();
-
-        Ok(Self { accounts: hashed_accounts, storages: hashed_storages })
+        hashed_post_state_from_changesets::<KH, _, _, _>(
+            account_changesets_cursor.walk_range(from..)?,
+            storage_changesets_cursor.walk_range(BlockNumberAddress((from, Address::ZERO))..)?,
+        )
     }
 }
 
+impl ChangesetHashedPostState for HashedPostState {
+    fn from_changesets<KH: KeyHasher>(
+        account_changesets: impl IntoIterator<Item = (BlockNumber, AccountBeforeTx)>,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Self {
+        let Ok(state) = hashed_post_state_from_changesets::<KH, Infallible, _, _>(
+            account_changesets.into_iter().map(Ok),
+            storage_changesets.into_iter().map(Ok),
+        );
+        state
+    }
+}
+
+/// Aggregates the changesets ordered by block number into hashed state in reverse, recording the
+/// value before the first change of every account and storage slot.
+fn hashed_post_state_from_changesets<KH, E, A, S>(
+    account_changesets: A,
+    storage_changesets: S,
+) -> Result<HashedPostState, E>
+where
+    KH: KeyHasher,
+    A: Iterator<Item = Result<(BlockNumber, AccountBeforeTx), E>>,
+    S: Iterator<Item = Result<(BlockNumberAddress, StorageEntry), E>>,
+{
+    // Iterate over account changesets and record value before first occurring account change.
+    let mut accounts = HashMap::new();
+    for entry in account_changesets {
+        let (_, AccountBeforeTx { address, info }) = entry?;
+        accounts.entry(address).or_insert(info);
+    }
+
+    // Iterate over storage changesets and record value before first occurring storage change.
+    let mut storages = AddressMap::<B256Map<U256>>::default();
+    for entry in storage_changesets {
+        let (BlockNumberAddress((_, address)), storage) = entry?;
+        let account_storage = storages.entry(address).or_default();
+        account_storage.entry(storage.key).or_insert(storage.value);
+    }
+
+    let hashed_accounts =
+        accounts.into_iter().map(|(address, info)| (KH::hash_key(address), info)).collect();
+
+    let hashed_storages = storages
+        .into_iter()
+        .map(|(address, storage)| {
+            (
+                KH::hash_key(address),
+                HashedStorage::from_iter(
+                    // The `wiped` flag indicates only whether previous storage entries
+                    // should be looked up in db or not. For reverts it's a noop since all
+                    // wiped changes had been written as storage reverts.
+                    false,
+                    storage.into_iter().map(|(slot, value)| (KH::hash_key(slot), value)),
+                ),
+            )
+        })
+        .collect();
+
+    Ok(HashedPostState { accounts: hashed_accounts, storages: hashed_storages })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,9 +4,11 @@ use reth_db_api::{
     cursor::DbCursorRO, models::BlockNumberAddress, tables, transaction::DbTx, DatabaseError,
 };
 use reth_execution_errors::StorageRootError;
+use reth_primitives_traits::StorageEntry;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRoot,
 };
+use std::convert::Infallible;
 
 #[cfg(feature = "metrics")]
 use reth_trie::metrics::TrieRootMetrics;
@@ -34,6 +36,17 @@ pub trait DatabaseHashedStorage<TX>: Sized {
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
 }
 
+/// Extends [`HashedStorage`] with initialization from changesets, regardless of where they're
+/// stored.
+pub trait ChangesetHashedStorage: Sized {
+    /// Initializes [`HashedStorage`] of the address from the storage changesets ordered by block
+    /// number, aggregating them into hashed storage in reverse.
+    fn from_changesets(
+        address: Address,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Self;
+}
+
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
     for StorageRoot<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
 {
@@ -81,17 +94,42 @@ impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
 
 impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError> {
-        let mut storage = Self::new(false);
         let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in storage_changesets_cursor.walk_range(BlockNumberAddress((from, address))..)? {
-            let (BlockNumberAddress((_, storage_address)), storage_change) = entry?;
-            if storage_address == address {
-                let hashed_slot = keccak256(storage_change.key);
-                if let hash_map::Entry::Vacant(entry) = storage.storage.entry(hashed_slot) {
-                    entry.insert(storage_change.value);
-                }
+        hashed_storage_from_changesets(
+            address,
+            storage_changesets_cursor.walk_range(BlockNumberAddress((from, address))..)?,
+        )
+    }
+}
+
+impl ChangesetHashedStorage for HashedStorage {
+    fn from_changesets(
+        address: Address,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Self {
+        let Ok(storage) = hashed_storage_from_changesets::<Infallible>(
+            address,
+            storage_changesets.into_iter().map(Ok),
+        );
+        storage
+    }
+}
+
+/// Aggregates the storage changes of the address ordered by block number into hashed storage in
+/// reverse, recording the value before the first change of every storage slot.
+fn hashed_storage_from_changesets<E>(
+    address: Address,
+    storage_changesets: impl Iterator<Item = Result<(BlockNumberAddress, StorageEntry), E>>,
+) -> Result<HashedStorage, E> {
+    let mut storage = HashedStorage::new(false);
+    for entry in storage_changesets {
+        let (BlockNumberAddress((_, storage_address)), storage_change) = entry?;
+        if storage_address == address {
+            let hashed_slot = keccak256(storage_change.key);
+            if let hash_map::Entry::Vacant(entry) = storage.storage.entry(hashed_slot) {
+                entry.insert(storage_change.value);
             }
         }
-        Ok(storage)
     }
+    Ok(storage)
 }
@@ -0,0 +1,92 @@
+#![allow(missing_docs)]
+
+use alloy_consensus::EMPTY_ROOT_HASH;
+use alloy_primitives::{B256, U256};
+use reth_db::{tables, test_utils::create_test_rw_db};
+use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_primitives_traits::{Account, StorageEntry};
+use reth_trie::{
+    test_utils::storage_root_prehashed, HashedPostState, HashedStateRange, HashedStorage,
+};
+use reth_trie_db::DatabaseHashedStateRange;
+
+#[test]
+fn account_range_pages_over_overlay() {
+    let db = create_test_rw_db();
+    let tx = db.tx_mut().unwrap();
+
+    let storage =
+        (1..4).map(|key| (B256::with_last_byte(key), U256::from(key))).collect::<Vec<_>>();
+    for key in 1..6 {
+        tx.put::<tables::HashedAccounts>(
+            B256::with_last_byte(key),
+            Account { nonce: key as u64, ..Default::default() },
+        )
+        .unwrap();
+    }
+    for (slot, value) in &storage {
+        tx.put::<tables::HashedStorages>(
+            B256::with_last_byte(2),
+            StorageEntry { key: *slot, value: *value },
+        )
+        .unwrap();
+    }
+
+    // the overlay removes the third account and adds the sixth
+    let mut post_state = HashedPostState::default();
+    post_state.accounts.insert(B256::with_last_byte(3), None);
+    post_state.accounts.insert(B256::with_last_byte(6), Some(Account::default()));
+
+    let range = HashedStateRange::overlay_accounts(&tx, post_state.clone(), B256::ZERO, 3).unwrap();
+    assert_eq!(
+        range.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+        vec![B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(4)]
+    );
+    assert_eq!(range.accounts[0].storage_root, EMPTY_ROOT_HASH);
+    assert_eq!(range.accounts[1].storage_root, storage_root_prehashed(storage));
+    assert_eq!(range.next, Some(B256::with_last_byte(5)));
+
+    let range =
+        HashedStateRange::overlay_accounts(&tx, post_state, range.next.unwrap(), 3).unwrap();
+    assert_eq!(
+        range.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+        vec![B256::with_last_byte(5), B256::with_last_byte(6)]
+    );
+    assert_eq!(range.next, None);
+}
+
+#[test]
+fn storage_range_pages_over_overlay() {
+    let db = create_test_rw_db();
+    let tx = db.tx_mut().unwrap();
+
+    let hashed_address = B256::with_last_byte(1);
+    for key in 1..5 {
+        tx.put::<tables::HashedStorages>(
+            hashed_address,
+            StorageEntry { key: B256::with_last_byte(key), value: U256::from(key) },
+        )
+        .unwrap();
+    }
+
+    // the overlay clears the second slot and updates the fourth
+    let mut storage = HashedStorage::default();
+    storage.storage.insert(B256::with_last_byte(2), U256::ZERO);
+    storage.storage.insert(B256::with_last_byte(4), U256::from(40));
+    let post_state = HashedPostState::from_hashed_storage(hashed_address, storage);
+
+    let range =
+        HashedStateRange::overlay_storage(&tx, post_state.clone(), hashed_address, B256::ZERO, 2)
+            .unwrap();
+    assert_eq!(
+        range.slots,
+        vec![(B256::with_last_byte(1), U256::from(1)), (B256::with_last_byte(3), U256::from(3))]
+    );
+    assert_eq!(range.next, Some(B256::with_last_byte(4)));
+
+    let range =
+        HashedStateRange::overlay_storage(&tx, post_state, hashed_address, range.next.unwrap(), 2)
+            .unwrap();
+    assert_eq!(range.slots, vec![(B256::with_last_byte(4), U256::from(40))]);
+    assert_eq!(range.next, None);
+}
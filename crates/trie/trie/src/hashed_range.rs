@@ -0,0 +1,105 @@
+use crate::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory},
+    prefix_set::TriePrefixSets,
+    trie_cursor::TrieCursorFactory,
+    HashedAccountRange, HashedAccountRangeEntry, HashedStorageRange, StorageRoot,
+};
+use alloy_primitives::B256;
+use reth_execution_errors::StateRootError;
+use reth_storage_errors::db::DatabaseError;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::TrieRootMetrics;
+
+/// `HashedStateRange` walks the hashed accounts and storages in pages, in the order of the hashed
+/// keys.
+#[derive(Debug)]
+pub struct HashedStateRange<T, H> {
+    /// The factory for trie cursors.
+    trie_cursor_factory: T,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: H,
+    /// A set of prefix sets that have changed.
+    prefix_sets: TriePrefixSets,
+}
+
+impl<T, H> HashedStateRange<T, H> {
+    /// Creates a new hashed state range walker.
+    pub fn new(trie_cursor_factory: T, hashed_cursor_factory: H) -> Self {
+        Self { trie_cursor_factory, hashed_cursor_factory, prefix_sets: TriePrefixSets::default() }
+    }
+
+    /// Set the prefix sets. They are used to recompute the storage roots of the changed accounts.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
+    }
+}
+
+impl<T, H> HashedStateRange<T, H>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    /// Returns up to `limit` accounts starting at the given hashed address, along with their
+    /// storage roots.
+    pub fn accounts(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedAccountRange, StateRootError> {
+        let mut cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let mut range = HashedAccountRange::default();
+
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if range.accounts.len() == limit {
+                range.next = Some(hashed_address);
+                break
+            }
+
+            let storage_root = StorageRoot::new_hashed(
+                self.trie_cursor_factory.clone(),
+                self.hashed_cursor_factory.clone(),
+                hashed_address,
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .get(&hashed_address)
+                    .cloned()
+                    .unwrap_or_default(),
+                #[cfg(feature = "metrics")]
+                TrieRootMetrics::new(crate::TrieType::Storage),
+            )
+            .root()?;
+            range.accounts.push(HashedAccountRangeEntry { hashed_address, account, storage_root });
+
+            entry = cursor.next()?;
+        }
+
+        Ok(range)
+    }
+
+    /// Returns up to `limit` storage slots of the account starting at the given hashed slot.
+    pub fn storage(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError> {
+        let mut cursor = self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+        let mut range = HashedStorageRange::default();
+
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_slot, value)) = entry {
+            if range.slots.len() == limit {
+                range.next = Some(hashed_slot);
+                break
+            }
+
+            range.slots.push((hashed_slot, value));
+            entry = cursor.next()?;
+        }
+
+        Ok(range)
+    }
+}
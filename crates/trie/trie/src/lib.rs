@@ -39,6 +39,10 @@ pub mod witness;
 mod trie;
 pub use trie::{StateRoot, StorageRoot, TrieType};
 
+/// Paginated walks over the hashed state.
+mod hashed_range;
+pub use hashed_range::HashedStateRange;
+
 /// Utilities for state root checkpoint progress.
 mod progress;
 pub use progress::{IntermediateStateRootState, StateRootProgress};
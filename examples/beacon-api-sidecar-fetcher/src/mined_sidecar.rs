@@ -1,8 +1,5 @@
 use crate::BeaconSidecarConfig;
-use alloy_consensus::{
-    transaction::PooledTransaction, BlockHeader, Signed, Transaction as _, TxEip4844WithSidecar,
-    Typed2718,
-};
+use alloy_consensus::{BlockHeader, Signed, Transaction as _, TxEip4844WithSidecar, Typed2718};
 use alloy_primitives::B256;
 use alloy_rpc_types_beacon::sidecar::{BeaconBlobBundle, SidecarIterator};
 use eyre::Result;
@@ -12,12 +9,12 @@ use reth_ethereum::{
     pool::{BlobStoreError, TransactionPoolExt},
     primitives::{RecoveredBlock, SignedTransaction},
     provider::CanonStateNotification,
+    PooledTransaction, TxEip4844WithSidecarVariant,
 };
 use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     pin::Pin,
-    sync::Arc,
     task::{Context, Poll},
 };
 use thiserror::Error;
@@ -118,11 +115,19 @@ where
             Ok(blobs) => {
                 actions_to_queue.reserve_exact(txs.len());
                 for ((tx, _), sidecar) in txs.iter().zip(blobs.into_iter()) {
+                    // sidecars with cell proofs are fetched from the consensus layer instead
+                    let Some(sidecar) = sidecar.as_eip4844().cloned() else {
+                        all_blobs_available = false;
+                        break
+                    };
                     if let PooledTransaction::Eip4844(transaction) = tx
                         .clone()
-                        .try_into_pooled_eip4844(Arc::unwrap_or_clone(sidecar))
+                        .try_into_pooled_eip4844(sidecar)
                         .expect("should not fail to convert blob tx if it is already eip4844")
                     {
+                        let transaction = transaction
+                            .try_map(TxEip4844WithSidecarVariant::try_into_eip4844_with_sidecar)
+                            .expect("sidecar has blob proofs");
                         let block_metadata = BlockMetadata {
                             block_hash: block.hash(),
                             block_number: block.number,
@@ -278,6 +283,9 @@ async fn fetch_blobs_for_block(
                     .try_into_pooled_eip4844(sidecar)
                     .expect("should not fail to convert blob tx if it is already eip4844")
                 {
+                    let transaction = transaction
+                        .try_map(TxEip4844WithSidecarVariant::try_into_eip4844_with_sidecar)
+                        .expect("sidecar has blob proofs");
                     let block_metadata = BlockMetadata {
                         block_hash: block.hash(),
                         block_number: block.number,
@@ -19,8 +19,8 @@ use reth_ethereum::{
     network::{
         config::rng_secret_key,
         eth_wire::{
-            EthMessage, EthStream, HelloMessage, P2PStream, Status, UnauthedEthStream,
-            UnauthedP2PStream,
+            BlockRangeUpdate, EthMessage, EthStream, HelloMessage, P2PStream, Status,
+            StatusMessage, UnauthedEthStream, UnauthedP2PStream,
         },
         EthNetworkPrimitives,
     },
@@ -75,7 +75,10 @@ async fn main() -> eyre::Result<()> {
 
                 println!(
                     "Successfully connected to a peer at {}:{} ({}) using eth-wire version eth/{}",
-                    peer.address, peer.tcp_port, their_hello.client_version, their_status.version
+                    peer.address,
+                    peer.tcp_port,
+                    their_hello.client_version,
+                    their_status.version()
                 );
 
                 snoop(peer, eth_stream).await;
@@ -101,7 +104,9 @@ async fn handshake_p2p(
 }
 
 // Perform a ETH Wire handshake with a peer
-async fn handshake_eth(p2p_stream: AuthedP2PStream) -> eyre::Result<(AuthedEthStream, Status)> {
+async fn handshake_eth(
+    p2p_stream: AuthedP2PStream,
+) -> eyre::Result<(AuthedEthStream, StatusMessage)> {
     let fork_filter = MAINNET.fork_filter(Head {
         timestamp: MAINNET.fork(EthereumHardfork::Shanghai).as_timestamp().unwrap(),
         ..Default::default()
@@ -115,8 +120,10 @@ async fn handshake_eth(p2p_stream: AuthedP2PStream) -> eyre::Result<(AuthedEthSt
 
     let status =
         Status { version: p2p_stream.shared_capabilities().eth()?.version().try_into()?, ..status };
+    // this node doesn't serve any history
+    let block_range = BlockRangeUpdate { latest_hash: MAINNET_GENESIS_HASH, ..Default::default() };
     let eth_unauthed = UnauthedEthStream::new(p2p_stream);
-    Ok(eth_unauthed.handshake(status, fork_filter).await?)
+    Ok(eth_unauthed.handshake(StatusMessage::new(status, block_range), fork_filter).await?)
 }
 
 // Snoop by greedily capturing all broadcasts that the peer emits